@@ -1,3 +1,11 @@
+use crate::cast::{
+    run_cast_entrypoint_deposit_to, run_cast_mint_mock_erc20, run_cast_transfer_eth,
+};
+use crate::forge::{
+    run_forge_create_entrypoint_v07, run_forge_create_safe_4337_module,
+    run_forge_create_safe_module_setup, run_forge_create_safe_proxy_factory,
+    run_forge_create_safe_singleton,
+};
 use crate::process::null_stdio;
 use crate::util::repo_root;
 use anyhow::{Context, Result};
@@ -333,3 +341,119 @@ pub fn spawn_solver_safe4337_tron_grpc(
     null_stdio(&mut cmd);
     cmd.spawn().context("spawn solver (safe4337 + tron grpc)")
 }
+
+/// Inputs for [`spawn_solver_safe4337`]. Private keys are 0x-prefixed hex.
+pub struct Safe4337SolverOptions<'a> {
+    pub db_url: &'a str,
+    pub postgrest_url: &'a str,
+    pub rpc_url: &'a str,
+    pub pool_contract: &'a str,
+    pub usdt: &'a str,
+    pub mock_reader: &'a str,
+    /// Deploys the AA contracts, owns the Safe and funds it (ETH, EntryPoint deposit, USDT).
+    pub owner_private_key_hex: &'a str,
+    /// Sends `handleOps` for the in-process bundler; must differ from the owner to avoid nonce
+    /// races with the test's own txs.
+    pub bundler_private_key_hex: &'a str,
+    /// USDT minted to the Safe for claim deposits (base units).
+    pub usdt_amount: &'a str,
+    pub instance_id: &'a str,
+    pub extra_env: &'a [(&'a str, &'a str)],
+}
+
+/// A Safe4337 solver and the AA stack it runs on.
+pub struct Safe4337Solver {
+    pub child: Child,
+    pub safe_address: String,
+    pub entrypoint: String,
+    pub safe_4337_module: String,
+    /// Keep alive for as long as the solver runs.
+    pub bundler: aa::MockBundler,
+}
+
+/// Deploys the Safe/EntryPoint stack, deploys and funds the solver's Safe, starts an in-process
+/// mock bundler and spawns a solver in `HUB_TX_MODE=safe4337` (mock Tron) against it.
+pub async fn spawn_solver_safe4337(opts: Safe4337SolverOptions<'_>) -> Result<Safe4337Solver> {
+    let rpc_url = opts.rpc_url;
+    let owner_pk = opts.owner_private_key_hex;
+
+    let safe_singleton = run_forge_create_safe_singleton(rpc_url, owner_pk)?;
+    let safe_proxy_factory = run_forge_create_safe_proxy_factory(rpc_url, owner_pk)?;
+    let safe_module_setup = run_forge_create_safe_module_setup(rpc_url, owner_pk)?;
+    let entrypoint = run_forge_create_entrypoint_v07(rpc_url, owner_pk)?;
+    let safe_4337_module = run_forge_create_safe_4337_module(rpc_url, owner_pk, &entrypoint)?;
+
+    let bundler = aa::MockBundler::start(aa::MockBundlerConfig {
+        rpc_url: rpc_url.to_string(),
+        entrypoint: entrypoint.parse().context("parse entrypoint")?,
+        executor_private_key: parse_private_key(opts.bundler_private_key_hex)?,
+    })
+    .await
+    .context("start mock bundler")?;
+
+    let safe = aa::ensure_safe_deployed(
+        rpc_url,
+        31337,
+        std::sync::Arc::new(aa::LocalHubSigner::new(parse_private_key(owner_pk)?)?),
+        &aa::Safe4337Config {
+            entrypoint: entrypoint.parse().context("parse entrypoint")?,
+            safe_4337_module: safe_4337_module.parse().context("parse safe_4337_module")?,
+        },
+        &aa::SafeDeterministicDeploymentConfig {
+            proxy_factory: safe_proxy_factory.parse().context("parse proxy_factory")?,
+            singleton: safe_singleton.parse().context("parse singleton")?,
+            module_setup: safe_module_setup.parse().context("parse module_setup")?,
+            salt_nonce: alloy::primitives::U256::from(123u64),
+        },
+    )
+    .await
+    .context("ensure safe deployed")?;
+    let safe_address = format!("{safe:#x}");
+
+    // Self-paid userops need ETH on the Safe and an EntryPoint deposit.
+    run_cast_transfer_eth(rpc_url, owner_pk, &safe_address, "1000000000000000000")?;
+    run_cast_entrypoint_deposit_to(
+        rpc_url,
+        owner_pk,
+        &entrypoint,
+        &safe_address,
+        "1000000000000000000",
+    )?;
+    run_cast_mint_mock_erc20(
+        rpc_url,
+        owner_pk,
+        opts.usdt,
+        &safe_address,
+        opts.usdt_amount,
+    )?;
+
+    let child = spawn_solver_safe4337_mock_custom(
+        opts.db_url,
+        opts.postgrest_url,
+        rpc_url,
+        opts.pool_contract,
+        owner_pk,
+        &safe_address,
+        &entrypoint,
+        &safe_4337_module,
+        &bundler.url,
+        opts.mock_reader,
+        opts.instance_id,
+        opts.extra_env,
+    )?;
+
+    Ok(Safe4337Solver {
+        child,
+        safe_address,
+        entrypoint,
+        safe_4337_module,
+        bundler,
+    })
+}
+
+fn parse_private_key(hex_key: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(hex_key.trim_start_matches("0x")).context("decode private key")?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("private key must be 32 bytes"))
+}
@@ -0,0 +1,249 @@
+use anyhow::Result;
+use e2e::{
+    anvil::spawn_anvil,
+    binaries::{cargo_build_indexer_bins, cargo_build_solver_bin, run_migrations},
+    cast::{run_cast_create_trx_transfer_intent, run_cast_mint_mock_erc20},
+    docker::{PostgresOptions, PostgrestOptions, start_postgres, start_postgrest},
+    docker_cleanup::cleanup_untron_e2e_containers,
+    forge::{
+        run_forge_build, run_forge_create_mock_erc20, run_forge_create_mock_tron_tx_reader,
+        run_forge_create_mock_untron_v3, run_forge_create_untron_intents_with_args,
+    },
+    http::wait_for_http_ok,
+    pool_db::{
+        fetch_current_intents, wait_for_intents_solved_and_settled,
+        wait_for_pool_current_intents_count,
+    },
+    postgres::{configure_postgrest_roles, wait_for_postgres},
+    process::KillOnDrop,
+    services::{spawn_indexer, spawn_solver_mock, spawn_solver_mock_custom},
+    solver_db::fetch_job_by_intent_id,
+    util::{find_free_port, require_bins},
+};
+use std::time::{Duration, Instant};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn e2e_solver_archives_old_terminal_jobs_only() -> Result<()> {
+    if !require_bins(&["docker", "anvil", "forge", "cast"]) {
+        return Ok(());
+    }
+
+    cleanup_untron_e2e_containers().ok();
+
+    let network = format!("e2e-net-{}", find_free_port()?);
+    let pg_name = format!("untron-e2e-pg-{}", find_free_port()?);
+
+    let pg = start_postgres(PostgresOptions {
+        network: Some(network.clone()),
+        container_name: Some(pg_name.clone()),
+        ..Default::default()
+    })
+    .await?;
+    let db_url = pg.db_url.clone();
+    wait_for_postgres(&db_url, Duration::from_secs(30)).await?;
+
+    cargo_build_indexer_bins()?;
+    cargo_build_solver_bin()?;
+    run_migrations(&db_url, true)?;
+
+    let anvil_port = find_free_port()?;
+    let rpc_url = format!("http://127.0.0.1:{anvil_port}");
+    let _anvil = KillOnDrop::new(spawn_anvil(anvil_port)?);
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    run_forge_build()?;
+    let pk0 = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let owner0 = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+    let usdt = run_forge_create_mock_erc20(&rpc_url, pk0, "USDT", "USDT", 6)?;
+    let mock_reader = run_forge_create_mock_tron_tx_reader(&rpc_url, pk0)?;
+    let v3 = run_forge_create_mock_untron_v3(
+        &rpc_url,
+        pk0,
+        &mock_reader,
+        "0x0000000000000000000000000000000000000001",
+        &usdt,
+    )?;
+    let intents_addr =
+        run_forge_create_untron_intents_with_args(&rpc_url, pk0, owner0, &v3, &usdt)?;
+
+    run_cast_mint_mock_erc20(&rpc_url, pk0, &usdt, owner0, "5000000")?;
+
+    let _indexer = KillOnDrop::new(spawn_indexer(
+        &db_url,
+        &rpc_url,
+        &intents_addr,
+        "pool",
+        None,
+    )?);
+
+    let to = "0x00000000000000000000000000000000000000aa";
+    let _ = run_cast_create_trx_transfer_intent(&rpc_url, pk0, &intents_addr, to, "1234", 1)?;
+    wait_for_pool_current_intents_count(&db_url, 1, Duration::from_secs(45)).await?;
+    let intent_id = fetch_current_intents(&db_url).await?[0].id.clone();
+
+    let pgrst_pw = "pgrst_pw";
+    configure_postgrest_roles(&db_url, pgrst_pw).await?;
+    let pgrst = start_postgrest(PostgrestOptions {
+        network,
+        container_name: Some(format!("untron-e2e-pgrst-{}", find_free_port()?)),
+        db_uri: format!("postgres://pgrst_authenticator:{pgrst_pw}@{pg_name}:5432/untron"),
+        ..Default::default()
+    })
+    .await?;
+    let postgrest_url = pgrst.base_url.clone();
+    wait_for_http_ok(&format!("{postgrest_url}/health"), Duration::from_secs(30)).await?;
+
+    // Run one intent to completion with archival disabled (default).
+    let solver1 = KillOnDrop::new(spawn_solver_mock(
+        &db_url,
+        &postgrest_url,
+        &rpc_url,
+        &intents_addr,
+        pk0,
+        &mock_reader,
+        "solver1",
+    )?);
+    let _rows = wait_for_intents_solved_and_settled(&db_url, 1, Duration::from_secs(180)).await?;
+    let done = fetch_job_by_intent_id(&db_url, &intent_id).await?;
+    assert_eq!(done.state, "done");
+    drop(solver1);
+
+    let pool = sqlx::PgPool::connect(&db_url).await?;
+
+    // Age the finished job past the archival threshold.
+    sqlx::query("update solver.jobs set updated_at = now() - interval '30 days' where job_id = $1")
+        .bind(done.job_id)
+        .execute(&pool)
+        .await?;
+
+    // An equally old, still-active job (leased by someone else) must stay in the hot table.
+    let active_intent_id = vec![0x42u8; 32];
+    let active_job_id: i64 = sqlx::query_scalar(
+        "insert into solver.jobs(intent_id, intent_type, intent_specs, deadline, state, \
+                                 leased_by, lease_until, updated_at) \
+         values ($1, 0, '\\x00'::bytea, 0, 'claimed', 'other', now() + interval '1 day', \
+                 now() - interval '30 days') \
+         returning job_id",
+    )
+    .bind(&active_intent_id)
+    .fetch_one(&pool)
+    .await?;
+
+    let hub_userops_before: i64 =
+        sqlx::query_scalar("select count(*) from solver.hub_userops where job_id = $1")
+            .bind(done.job_id)
+            .fetch_one(&pool)
+            .await?;
+    let signed_txs_before: i64 =
+        sqlx::query_scalar("select count(*) from solver.tron_signed_txs where job_id = $1")
+            .bind(done.job_id)
+            .fetch_one(&pool)
+            .await?;
+
+    let _solver2 = KillOnDrop::new(spawn_solver_mock_custom(
+        &db_url,
+        &postgrest_url,
+        &rpc_url,
+        &intents_addr,
+        pk0,
+        &mock_reader,
+        "solver2",
+        "trx_transfer",
+        &[
+            ("SOLVER_JOBS_ARCHIVE_AFTER_DAYS", "7"),
+            ("SOLVER_JOBS_ARCHIVE_INTERVAL_SECS", "1"),
+        ],
+    )?);
+
+    let start = Instant::now();
+    loop {
+        let archived: bool = sqlx::query_scalar(
+            "select exists(select 1 from solver.jobs_archive where job_id = $1)",
+        )
+        .bind(done.job_id)
+        .fetch_one(&pool)
+        .await?;
+        if archived {
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(60) {
+            anyhow::bail!("timed out waiting for job {} to be archived", done.job_id);
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    let hot_done: i64 = sqlx::query_scalar("select count(*) from solver.jobs where job_id = $1")
+        .bind(done.job_id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(hot_done, 0, "archived job must leave solver.jobs");
+
+    let archived_state: String =
+        sqlx::query_scalar("select state from solver.jobs_archive where job_id = $1")
+            .bind(done.job_id)
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(archived_state, "done");
+
+    let hub_userops_archived: i64 =
+        sqlx::query_scalar("select count(*) from solver.hub_userops_archive where job_id = $1")
+            .bind(done.job_id)
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(hub_userops_archived, hub_userops_before);
+
+    let hub_userops_hot: i64 =
+        sqlx::query_scalar("select count(*) from solver.hub_userops where job_id = $1")
+            .bind(done.job_id)
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(hub_userops_hot, 0);
+
+    let signed_txs_archived: i64 =
+        sqlx::query_scalar("select count(*) from solver.tron_signed_txs_archive where job_id = $1")
+            .bind(done.job_id)
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(
+        signed_txs_archived, signed_txs_before,
+        "signed Tron txs must be archived with their job"
+    );
+
+    if let Some(txid) = done.tron_txid.as_deref() {
+        let txid = hex::decode(txid.trim_start_matches("0x"))?;
+        let proof_hot: i64 =
+            sqlx::query_scalar("select count(*) from solver.tron_proofs where txid = $1")
+                .bind(&txid)
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(
+            proof_hot, 0,
+            "proof of an archived job must leave solver.tron_proofs"
+        );
+    }
+
+    let active_state: String =
+        sqlx::query_scalar("select state from solver.jobs where job_id = $1")
+            .bind(active_job_id)
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(active_state, "claimed");
+
+    let active_archived: i64 =
+        sqlx::query_scalar("select count(*) from solver.jobs_archive where job_id = $1")
+            .bind(active_job_id)
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(active_archived, 0);
+
+    // Archived intents must not be re-ingested as fresh jobs.
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let reingested: i64 =
+        sqlx::query_scalar("select count(*) from solver.jobs where intent_id = $1")
+            .bind(hex::decode(intent_id.trim_start_matches("0x"))?)
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(reingested, 0);
+
+    Ok(())
+}
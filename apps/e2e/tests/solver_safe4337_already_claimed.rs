@@ -24,6 +24,7 @@ use e2e::{
     solver_db::fetch_job_by_intent_id,
     util::{find_free_port, require_bins},
 };
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 async fn wait_for_job_state(
@@ -124,7 +125,7 @@ async fn e2e_solver_safe4337_marks_already_claimed_as_fatal() -> Result<()> {
     let safe_addr = aa::ensure_safe_deployed(
         &rpc_url,
         31337,
-        owner_key,
+        Arc::new(aa::LocalHubSigner::new(owner_key)?),
         &aa::Safe4337Config {
             entrypoint: entrypoint.parse().context("parse entrypoint")?,
             safe_4337_module: safe_4337_module.parse().context("parse safe_4337_module")?,
@@ -359,7 +359,7 @@ async fn e2e_solver_safe4337_uses_alto_and_recovers_after_crash() -> Result<()>
     let safe_addr = aa::ensure_safe_deployed(
         &rpc_url,
         31337,
-        owner_key,
+        Arc::new(aa::LocalHubSigner::new(owner_key)?),
         &aa::Safe4337Config {
             entrypoint: entrypoint.parse().context("parse entrypoint")?,
             safe_4337_module: safe_4337_module.parse().context("parse safe_4337_module")?,
@@ -429,7 +429,7 @@ async fn e2e_solver_safe4337_uses_alto_and_recovers_after_crash() -> Result<()>
             safe_4337_module: safe_4337_module.parse().context("parse safe_4337_module")?,
             safe_deployment: None,
             bundler_urls: vec![alto_url.clone()],
-            owner_private_key: owner_key,
+            owner: Arc::new(aa::LocalHubSigner::new(owner_key)?),
             paymasters: vec![],
             options: aa::Safe4337UserOpSenderOptions::default(),
         })
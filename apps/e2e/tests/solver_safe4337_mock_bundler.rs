@@ -0,0 +1,166 @@
+use anyhow::Result;
+use e2e::{
+    anvil::spawn_anvil_with_block_time,
+    binaries::{cargo_build_indexer_bins, cargo_build_solver_bin, run_migrations},
+    cast::run_cast_create_trx_transfer_intent,
+    docker::{PostgresOptions, PostgrestOptions, start_postgres, start_postgrest},
+    docker_cleanup::cleanup_untron_e2e_containers,
+    forge::{
+        run_forge_build, run_forge_create_mock_erc20, run_forge_create_mock_tron_tx_reader,
+        run_forge_create_mock_untron_v3, run_forge_create_untron_intents_with_args,
+    },
+    http::wait_for_http_ok,
+    pool_db::{wait_for_intents_solved_and_settled, wait_for_pool_current_intents_count},
+    postgres::{configure_postgrest_roles, wait_for_postgres},
+    process::KillOnDrop,
+    services::{Safe4337SolverOptions, spawn_indexer, spawn_solver_safe4337},
+    util::{find_free_port, require_bins},
+};
+use sqlx::Row;
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn e2e_safe4337_claims_and_proves_via_mock_bundler() -> Result<()> {
+    // Docker is still needed for Postgres/PostgREST; only the bundler runs in-process.
+    if !require_bins(&["docker", "anvil", "forge", "cast"]) {
+        return Ok(());
+    }
+
+    cleanup_untron_e2e_containers().ok();
+
+    let network = format!("e2e-net-{}", find_free_port()?);
+    let pg_name = format!("untron-e2e-pg-{}", find_free_port()?);
+    let pg = start_postgres(PostgresOptions {
+        network: Some(network.clone()),
+        container_name: Some(pg_name.clone()),
+        ..Default::default()
+    })
+    .await?;
+    let db_url = pg.db_url.clone();
+    wait_for_postgres(&db_url, Duration::from_secs(30)).await?;
+
+    cargo_build_indexer_bins()?;
+    cargo_build_solver_bin()?;
+    run_migrations(&db_url, true)?;
+
+    // Hub chain.
+    let anvil_port = find_free_port()?;
+    let rpc_url = format!("http://127.0.0.1:{anvil_port}");
+    let _anvil = KillOnDrop::new(spawn_anvil_with_block_time(anvil_port, 2)?);
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    // Deploy pool contracts; the AA stack comes with the solver below.
+    run_forge_build()?;
+    let pk0 = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let pk1 = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+    let owner0 = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+    let usdt = run_forge_create_mock_erc20(&rpc_url, pk0, "USDT", "USDT", 6)?;
+    let mock_reader = run_forge_create_mock_tron_tx_reader(&rpc_url, pk0)?;
+    let v3 = run_forge_create_mock_untron_v3(
+        &rpc_url,
+        pk0,
+        &mock_reader,
+        "0x0000000000000000000000000000000000000001",
+        "0x0000000000000000000000000000000000000002",
+    )?;
+    let intents_addr =
+        run_forge_create_untron_intents_with_args(&rpc_url, pk0, owner0, &v3, &usdt)?;
+
+    // PostgREST.
+    let pgrst_pw = "pgrst_pw";
+    configure_postgrest_roles(&db_url, pgrst_pw).await?;
+    let pgrst = start_postgrest(PostgrestOptions {
+        network: network.clone(),
+        container_name: Some(format!("untron-e2e-pgrst-{}", find_free_port()?)),
+        db_uri: format!("postgres://pgrst_authenticator:{pgrst_pw}@{pg_name}:5432/untron"),
+        ..Default::default()
+    })
+    .await?;
+    wait_for_http_ok(
+        &format!("{}/health", pgrst.base_url),
+        Duration::from_secs(30),
+    )
+    .await?;
+
+    // Start indexer (pool-only) + solver (safe4337 + mock tron).
+    let _indexer = KillOnDrop::new(spawn_indexer(
+        &db_url,
+        &rpc_url,
+        &intents_addr,
+        "pool",
+        None,
+    )?);
+
+    // Create 1 intent (TRX transfer): claim + prove is the full Safe4337 path we care about here.
+    let to = "0x00000000000000000000000000000000000000aa";
+    let _ = run_cast_create_trx_transfer_intent(&rpc_url, pk0, &intents_addr, to, "1234", 1)?;
+    wait_for_pool_current_intents_count(&db_url, 1, Duration::from_secs(60)).await?;
+
+    // Safe4337 solver behind an in-process bundler submitting `handleOps` to the local EntryPoint.
+    let solver = spawn_solver_safe4337(Safe4337SolverOptions {
+        db_url: &db_url,
+        postgrest_url: &pgrst.base_url,
+        rpc_url: &rpc_url,
+        pool_contract: &intents_addr,
+        usdt: &usdt,
+        mock_reader: &mock_reader,
+        owner_private_key_hex: pk0,
+        bundler_private_key_hex: pk1,
+        usdt_amount: "5000000",
+        instance_id: "solver-aa-mock-bundler",
+        extra_env: &[],
+    })
+    .await?;
+    let _solver = KillOnDrop::new(solver.child);
+    let _bundler = solver.bundler;
+
+    let _rows = wait_for_intents_solved_and_settled(&db_url, 1, Duration::from_secs(180)).await?;
+
+    // Assert receipts came from the mock bundler (not the EntryPoint log fallback).
+    let pool = sqlx::PgPool::connect(&db_url).await?;
+
+    // Indexer-settled does not imply the solver has already polled+persisted AA receipts.
+    // Wait until the solver has populated receipt JSON for all its tracked userops.
+    let start = std::time::Instant::now();
+    loop {
+        let missing: i64 = sqlx::query_scalar(
+            "select count(*) \
+             from solver.hub_userops \
+             where receipt is null",
+        )
+        .fetch_one(&pool)
+        .await?;
+        if missing == 0 {
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(60) {
+            anyhow::bail!("timeout waiting for solver.hub_userops.receipt to be populated");
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    let rows = sqlx::query(
+        "select receipt \
+         from solver.hub_userops \
+         order by userop_id asc",
+    )
+    .fetch_all(&pool)
+    .await?;
+    assert!(!rows.is_empty(), "expected solver.hub_userops rows");
+    for r in rows {
+        let receipt: serde_json::Value = r.try_get("receipt").unwrap_or(serde_json::Value::Null);
+        let src = receipt
+            .get("source")
+            .or_else(|| receipt.get("costSource"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        assert_ne!(src, "entrypoint_log", "unexpected receipt json: {receipt}");
+        assert!(
+            receipt.get("userOpHash").is_some(),
+            "unexpected receipt json: {receipt}"
+        );
+    }
+
+    Ok(())
+}
@@ -25,6 +25,7 @@ use e2e::{
     util::{find_free_port, require_bins},
 };
 use sqlx::Row;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 async fn wait_for_tx_success(rpc_url: &str, tx_hash: &str) -> Result<()> {
@@ -352,7 +353,7 @@ async fn e2e_solver_safe4337_deletes_stale_prepared_userop_and_recovers() -> Res
     let safe_addr = aa::ensure_safe_deployed(
         &rpc_url,
         31337,
-        owner_key,
+        Arc::new(aa::LocalHubSigner::new(owner_key)?),
         &aa::Safe4337Config {
             entrypoint: entrypoint.parse().context("parse entrypoint")?,
             safe_4337_module: safe_4337_module.parse().context("parse safe_4337_module")?,
@@ -443,7 +444,7 @@ async fn e2e_solver_safe4337_deletes_stale_prepared_userop_and_recovers() -> Res
         safe_4337_module: safe_4337_module.parse().context("parse safe_4337_module")?,
         safe_deployment: None,
         bundler_urls: vec![alto_url.clone()],
-        owner_private_key: owner_key,
+        owner: Arc::new(aa::LocalHubSigner::new(owner_key)?),
         paymasters: vec![],
         options: aa::Safe4337UserOpSenderOptions::default(),
     })
@@ -641,7 +642,7 @@ async fn e2e_solver_safe4337_claim_window_prioritizes_and_backpressures() -> Res
     let safe_addr = aa::ensure_safe_deployed(
         &rpc_url,
         31337,
-        owner_key,
+        Arc::new(aa::LocalHubSigner::new(owner_key)?),
         &aa::Safe4337Config {
             entrypoint: entrypoint.parse().context("parse entrypoint")?,
             safe_4337_module: safe_4337_module.parse().context("parse safe_4337_module")?,
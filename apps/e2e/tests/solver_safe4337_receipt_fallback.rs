@@ -25,6 +25,7 @@ use e2e::{
     util::{find_free_port, require_bins},
 };
 use sqlx::Row;
+use std::sync::Arc;
 use std::time::Duration;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -110,7 +111,7 @@ async fn e2e_safe4337_works_when_bundler_receipts_are_missing() -> Result<()> {
     let safe_addr = aa::ensure_safe_deployed(
         &rpc_url,
         31337,
-        owner_key,
+        Arc::new(aa::LocalHubSigner::new(owner_key)?),
         &aa::Safe4337Config {
             entrypoint: entrypoint.parse().context("parse entrypoint")?,
             safe_4337_module: safe_4337_module.parse().context("parse safe_4337_module")?,
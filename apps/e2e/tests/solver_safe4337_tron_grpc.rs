@@ -25,6 +25,7 @@ use e2e::{
     tronbox::{decode_hex32, wait_for_tronbox_accounts, wait_for_tronbox_admin},
     util::{find_free_port, require_bins},
 };
+use std::sync::Arc;
 use std::time::Duration;
 use testcontainers::core::{IntoContainerPort, WaitFor};
 use testcontainers::runners::AsyncRunner;
@@ -156,7 +157,7 @@ async fn e2e_solver_safe4337_tron_grpc_fills_trx_transfer_and_delegate_resource(
     let safe_addr = aa::ensure_safe_deployed(
         &rpc_url,
         31337,
-        owner_key,
+        Arc::new(aa::LocalHubSigner::new(owner_key)?),
         &aa::Safe4337Config {
             entrypoint: entrypoint.parse().context("parse entrypoint")?,
             safe_4337_module: safe_4337_module.parse().context("parse safe_4337_module")?,
@@ -213,7 +214,7 @@ async fn e2e_solver_safe4337_tron_grpc_fills_trx_transfer_and_delegate_resource(
                 .context("parse safe_4337_module (approve)")?,
             safe_deployment: None,
             bundler_urls: vec![alto_url.clone()],
-            owner_private_key: owner_key,
+            owner: Arc::new(aa::LocalHubSigner::new(owner_key)?),
             paymasters: vec![],
             options: aa::Safe4337UserOpSenderOptions::default(),
         })
@@ -0,0 +1,50 @@
+/*
+Per-instance chain position, written by the worker every poll.
+
+- head_block:            latest block reported by RPC ("raw head").
+- confirmed_head_block:  head_block - confirmations; only logs at or below it are ingested and
+                         projected, so newer blocks stay pending on-chain until they confirm.
+- indexed_through_block: highest block whose logs are fully ingested.
+
+Consumers (e.g. the solver's lag guard) should prefer `indexed_through_block` over the highest
+event block, which stalls whenever the contract is quiet.
+*/
+create table if not exists chain.stream_heads (
+stream chain.stream not null,
+chain_id bigint not null,
+contract_address evm_address not null,
+
+head_block bigint not null,
+confirmed_head_block bigint not null,
+confirmations bigint not null,
+indexed_through_block bigint not null,
+
+updated_at timestamptz not null default now(),
+
+primary key (stream, chain_id, contract_address),
+
+constraint stream_heads_instance_fk
+foreign key (stream, chain_id, contract_address)
+references chain.instance (stream, chain_id, contract_address)
+);
+
+create or replace view api.stream_heads as
+select
+    stream,
+    chain_id,
+    contract_address,
+    head_block,
+    confirmed_head_block,
+    confirmations,
+    indexed_through_block,
+    greatest(head_block - confirmed_head_block, 0) as pending_blocks,
+    greatest(confirmed_head_block - indexed_through_block, 0) as backlog_blocks,
+    updated_at
+from chain.stream_heads;
+
+do $$
+begin
+  if exists (select 1 from pg_roles where rolname = 'pgrst_anon') then
+    grant select on api.stream_heads to pgrst_anon;
+  end if;
+end $$ ;
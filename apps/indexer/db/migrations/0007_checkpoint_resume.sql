@@ -0,0 +1,23 @@
+/*
+Resume points restored by `migrate import-checkpoint`.
+
+A checkpoint carries projections but only a short tail of raw EventAppended rows, so the
+"max canonical event block" the worker normally resumes from can be far behind what the exporting
+database had already scanned. `resume_block` acts as a floor for that.
+*/
+create table if not exists chain.checkpoint_resume (
+stream chain.stream not null,
+chain_id bigint not null,
+contract_address evm_address not null,
+
+resume_block bigint not null,
+
+primary key (stream, chain_id, contract_address),
+
+constraint checkpoint_resume_instance_fk
+foreign key (stream, chain_id, contract_address)
+references chain.instance (stream, chain_id, contract_address),
+
+constraint checkpoint_resume_nonnegative
+check (resume_block >= 0)
+);
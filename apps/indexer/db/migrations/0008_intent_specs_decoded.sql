@@ -0,0 +1,146 @@
+/*
+Decoded `intent_specs` for pool intents.
+
+The worker ABI-decodes `IntentCreated.intent_specs` by `intent_type` and attaches the result to the
+event args as `intent_specs_decoded` (see `decode::decode_intent_specs`). This migration projects it
+into a companion ledger so PostgREST clients can filter on spec fields without decoding hex.
+
+Column mapping per `intent_type` (UntronIntents.IntentType):
+- 0 TRIGGER_SMART_CONTRACT: contract = to, amount = callValueSun, selector = data[0..4], call_data = data
+- 1 USDT_TRANSFER:          to_address = to, amount = amount
+- 2 TRX_TRANSFER:           to_address = to, amount = amountSun
+- 3 DELEGATE_RESOURCE:      to_address = receiver, amount = balanceSun, resource, lock_period
+
+`to_address` / `contract` are stored as EVM addresses (the contract's encoding); `to_tron` /
+`contract_tron` are the derived Tron base58 forms.
+
+Intents created before this migration have no decoded row until their events are re-projected.
+Specs that fail to decode are skipped (the raw hex stays in `pool.intent_versions`).
+*/
+create table if not exists pool.intent_specs_decoded (
+event_seq bigint primary key,
+id bytes32_hex not null,
+intent_type smallint not null,
+
+to_address evm_address null,
+to_tron tron_address null,
+amount u256 null,
+
+contract evm_address null,
+contract_tron tron_address null,
+selector text null,
+call_data bytes_hex null,
+
+resource smallint null,
+lock_period u256 null,
+
+constraint pool_intent_specs_decoded_selector_check
+check (selector is null or selector ~ '^0x[0-9a-f]{8}$')
+) ;
+
+create index if not exists pool_intent_specs_decoded_by_id
+on pool.intent_specs_decoded (id, event_seq desc) ;
+
+create index if not exists pool_intent_specs_decoded_by_to
+on pool.intent_specs_decoded (to_address)
+where to_address is not null ;
+
+create or replace function pool.intent_specs_decoded_insert (
+p_seq bigint,
+p_args jsonb
+) returns void language plpgsql as $$
+declare
+  d jsonb := p_args->'intent_specs_decoded';
+begin
+  if d is null or jsonb_typeof(d) <> 'object' then
+    return;
+  end if;
+
+  insert into pool.intent_specs_decoded(
+    event_seq, id, intent_type,
+    to_address, to_tron, amount,
+    contract, contract_tron, selector, call_data,
+    resource, lock_period
+  ) values (
+    p_seq,
+    (p_args->>'id')::bytes32_hex,
+    (p_args->>'intent_type')::smallint,
+    (d->>'to_address')::evm_address,
+    case when d->>'to_address' is null then null
+         else chain.tron_address_from_evm((d->>'to_address')::evm_address) end,
+    (d->>'amount')::u256,
+    (d->>'contract')::evm_address,
+    case when d->>'contract' is null then null
+         else chain.tron_address_from_evm((d->>'contract')::evm_address) end,
+    d->>'selector',
+    (d->>'call_data')::bytes_hex,
+    (d->>'resource')::smallint,
+    (d->>'lock_period')::u256
+  );
+end $$ ;
+
+-- Wrap the existing interpreter / rollback instead of restating them: the base functions keep
+-- their bodies from 0003 and the wrappers only add the companion table.
+alter function pool.apply_one(bigint, bigint, text, jsonb) rename to apply_one_base ;
+
+create or replace function pool.apply_one (
+p_seq bigint,
+p_block_timestamp bigint,
+p_type text,
+p_args jsonb
+)
+returns void language plpgsql as $$
+begin
+  perform pool.apply_one_base(p_seq, p_block_timestamp, p_type, p_args);
+
+  if p_type = 'IntentCreated' then
+    perform pool.intent_specs_decoded_insert(p_seq, p_args);
+  end if;
+end $$ ;
+
+alter function pool.rollback_from(bigint, evm_address, bigint) rename to rollback_from_base ;
+
+create or replace function pool.rollback_from (
+p_chain_id bigint,
+p_contract_address evm_address,
+rollback_seq bigint
+)
+returns void language plpgsql as $$
+begin
+  delete from pool.intent_specs_decoded where event_seq >= rollback_seq;
+  perform pool.rollback_from_base(p_chain_id, p_contract_address, rollback_seq);
+end $$ ;
+
+-- Latest decoded specs per current intent.
+create or replace view api.pool_intent_specs as
+select distinct on (d.id)
+    d.id,
+    d.event_seq,
+    d.intent_type,
+    d.to_address,
+    d.to_tron,
+    d.amount,
+    d.contract,
+    d.contract_tron,
+    d.selector,
+    d.call_data,
+    d.resource,
+    d.lock_period,
+    i.escrow_token,
+    i.escrow_amount,
+    i.deadline,
+    i.solved,
+    i.funded,
+    i.settled,
+    i.closed
+from pool.intent_specs_decoded d
+join pool.intent_versions i
+    on i.id = d.id and i.valid_to_seq is null
+order by d.id, d.event_seq desc;
+
+do $$
+begin
+  if exists (select 1 from pg_roles where rolname = 'pgrst_anon') then
+    grant select on api.pool_intent_specs to pgrst_anon;
+  end if;
+end $$ ;
@@ -0,0 +1,58 @@
+/*
+Solver fill latency percentiles, exposed via PostgREST as `api.solver_fill_latency`.
+
+The solver owns `solver.jobs` and migrates its schema on startup, which may happen after this
+migration runs (or never, on indexer-only databases). So the view is created by
+`chain.ensure_solver_views()`, which is a no-op until `solver.jobs` exists; the `migrate` binary
+calls it on every run.
+
+Latency is `updated_at - created_at` of jobs in state `done` (the `done` transition is a job's last
+write). Archived jobs (`solver.jobs_archive`) are included when that table exists.
+*/
+create or replace function chain.ensure_solver_views()
+returns boolean language plpgsql as $$
+declare
+  v_jobs text;
+begin
+  if to_regclass('solver.jobs') is null then
+    return false;
+  end if;
+
+  v_jobs := 'select intent_type, created_at, updated_at from solver.jobs where state = ''done''';
+  if to_regclass('solver.jobs_archive') is not null then
+    v_jobs := v_jobs
+      || ' union all select intent_type, created_at, updated_at from solver.jobs_archive where state = ''done''';
+  end if;
+
+  execute format($v$
+    create or replace view api.solver_fill_latency as
+    with done_jobs as (%s),
+    windows(window_name, window_interval) as (
+      values ('1h', interval '1 hour'), ('24h', interval '24 hours'), ('7d', interval '7 days')
+    )
+    select
+        w.window_name,
+        j.intent_type,
+        count(*) as fills,
+        percentile_cont(0.5) within group (order by extract(epoch from j.updated_at - j.created_at))
+            as p50_secs,
+        percentile_cont(0.95) within group (order by extract(epoch from j.updated_at - j.created_at))
+            as p95_secs,
+        percentile_cont(0.99) within group (order by extract(epoch from j.updated_at - j.created_at))
+            as p99_secs,
+        max(extract(epoch from j.updated_at - j.created_at)) as max_secs
+    from windows w
+    join done_jobs j
+        on j.updated_at >= now() - w.window_interval
+    group by w.window_name, w.window_interval, j.intent_type
+    order by w.window_interval, j.intent_type
+  $v$, v_jobs);
+
+  if exists (select 1 from pg_roles where rolname = 'pgrst_anon') then
+    grant select on api.solver_fill_latency to pgrst_anon;
+  end if;
+
+  return true;
+end $$ ;
+
+select chain.ensure_solver_views() ;
@@ -0,0 +1,48 @@
+/*
+Creation time of open pool intents, exposed as `api.pool_open_intents.created_at`.
+
+`valid_from_seq` moves with every state change (claims, unclaims), so the creation time is the
+block timestamp of the intent's first version. Solvers use it to enforce an age floor before
+attempting an intent. Null if the creating event is no longer canonical.
+
+The columns are listed explicitly (in `api.pool_intents` order) so a later change to
+`api.pool_intents` can't silently reshape this view.
+*/
+create or replace view api.pool_open_intents as
+select
+    i.id,
+    i.valid_from_seq,
+    i.creator,
+    i.intent_type,
+    i.escrow_token,
+    i.escrow_amount,
+    i.refund_beneficiary,
+    i.deadline,
+    i.deadline_time,
+    i.intent_specs,
+    i.solver,
+    i.solver_claimed_at,
+    i.solver_claimed_time,
+    i.tron_tx_id,
+    i.tron_block_number,
+    i.solved,
+    i.funded,
+    i.settled,
+    i.closed,
+    c.created_at
+from api.pool_intents i
+left join lateral (
+    select e.block_timestamp as created_at
+    from pool.intent_versions v
+    join chain.event_appended e
+        on
+            e.stream = 'pool'
+            and e.canonical
+            and e.event_seq = v.valid_from_seq
+    where v.id = i.id
+    order by v.valid_from_seq asc
+    limit 1
+) c on true
+where
+    i.closed = false
+    and i.deadline > extract(epoch from now())::bigint;
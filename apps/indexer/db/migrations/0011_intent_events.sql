@@ -0,0 +1,166 @@
+/*
+Chain-side lifecycle timeline of pool intents, exposed via PostgREST as `api.pool_intent_events`.
+
+One append-only row per canonical intent lifecycle event, with the tx and block it landed in, so
+operators can cross-check `solver.jobs` against what actually happened on the hub:
+- IntentCreated:   actor = creator
+- IntentClaimed:   actor = solver = claiming solver
+- IntentUnclaimed: actor = caller, solver = prev_solver
+- IntentSolved:    actor = solver = proving solver (`proveIntentFill`)
+- IntentFunded:    actor = funder
+- IntentSettled:   actor = solver = settled solver
+- IntentClosed:    actor = caller
+
+`args` keeps the full decoded event. Rows follow the projection: `pool.apply_one` inserts them and
+`pool.rollback_from` deletes the rolled-back suffix. Events already projected when this migration
+runs are backfilled below.
+*/
+create table if not exists pool.intent_events (
+event_seq bigint primary key,
+id bytes32_hex not null,
+event_type text not null,
+
+actor evm_address not null,
+solver evm_address null,
+
+block_number bigint not null,
+block_timestamp bigint not null,
+block_hash bytes32_hex not null,
+tx_hash txhash_hex not null,
+log_index integer not null,
+
+args jsonb not null,
+
+constraint pool_intent_events_type_check
+check (event_type in (
+  'IntentCreated', 'IntentClaimed', 'IntentUnclaimed', 'IntentSolved',
+  'IntentFunded', 'IntentSettled', 'IntentClosed'
+))
+) ;
+
+create index if not exists pool_intent_events_by_id
+on pool.intent_events (id, event_seq) ;
+
+create index if not exists pool_intent_events_by_solver
+on pool.intent_events (solver, event_seq)
+where solver is not null ;
+
+create or replace function pool.intent_events_insert (
+p_seq bigint,
+p_type text,
+p_args jsonb
+) returns void language plpgsql as $$
+declare
+  ev record;
+  v_actor text;
+  v_solver text;
+begin
+  case p_type
+    when 'IntentCreated' then v_actor := p_args->>'creator';
+    when 'IntentClaimed' then v_actor := p_args->>'solver'; v_solver := v_actor;
+    when 'IntentUnclaimed' then v_actor := p_args->>'caller'; v_solver := p_args->>'prev_solver';
+    when 'IntentSolved' then v_actor := p_args->>'solver'; v_solver := v_actor;
+    when 'IntentFunded' then v_actor := p_args->>'funder';
+    when 'IntentSettled' then v_actor := p_args->>'solver'; v_solver := v_actor;
+    when 'IntentClosed' then v_actor := p_args->>'caller';
+    else return;
+  end case;
+
+  -- Single pool instance (see `chain.instance_pool_singleton`), so stream + seq is unique among
+  -- canonical events.
+  select block_number, block_timestamp, block_hash, tx_hash, log_index
+    into ev
+    from chain.event_appended
+   where stream = 'pool'
+     and canonical
+     and event_seq = p_seq
+   limit 1;
+
+  if not found then
+    raise exception 'no canonical pool event at seq %', p_seq;
+  end if;
+
+  insert into pool.intent_events(
+    event_seq, id, event_type, actor, solver,
+    block_number, block_timestamp, block_hash, tx_hash, log_index,
+    args
+  ) values (
+    p_seq,
+    (p_args->>'id')::bytes32_hex,
+    p_type,
+    v_actor::evm_address,
+    v_solver::evm_address,
+    ev.block_number,
+    ev.block_timestamp,
+    ev.block_hash,
+    ev.tx_hash,
+    ev.log_index,
+    p_args
+  );
+end $$ ;
+
+-- Wrap the current interpreter / rollback (0008's wrappers) the same way 0008 wrapped 0003's.
+alter function pool.apply_one(bigint, bigint, text, jsonb) rename to apply_one_pre_intent_events ;
+
+create or replace function pool.apply_one (
+p_seq bigint,
+p_block_timestamp bigint,
+p_type text,
+p_args jsonb
+)
+returns void language plpgsql as $$
+begin
+  perform pool.apply_one_pre_intent_events(p_seq, p_block_timestamp, p_type, p_args);
+  perform pool.intent_events_insert(p_seq, p_type, p_args);
+end $$ ;
+
+alter function pool.rollback_from(bigint, evm_address, bigint)
+rename to rollback_from_pre_intent_events ;
+
+create or replace function pool.rollback_from (
+p_chain_id bigint,
+p_contract_address evm_address,
+rollback_seq bigint
+)
+returns void language plpgsql as $$
+begin
+  delete from pool.intent_events where event_seq >= rollback_seq;
+  perform pool.rollback_from_pre_intent_events(p_chain_id, p_contract_address, rollback_seq);
+end $$ ;
+
+-- Backfill everything the projection has already applied.
+select pool.intent_events_insert(e.event_seq, e.event_type, e.args)
+from chain.event_appended e
+join chain.stream_cursor c
+    on
+        c.stream = e.stream
+        and c.chain_id = e.chain_id
+        and c.contract_address = e.contract_address
+where
+    e.stream = 'pool'
+    and e.canonical
+    and e.event_seq <= c.applied_through_seq
+order by e.event_seq ;
+
+create or replace view api.pool_intent_events as
+select
+    event_seq,
+    id,
+    event_type,
+    actor,
+    solver,
+    block_number,
+    block_timestamp,
+    to_timestamp(block_timestamp) as block_time,
+    block_hash,
+    tx_hash,
+    log_index,
+    args
+from pool.intent_events;
+
+do $$
+begin
+  if exists (select 1 from pg_roles where rolname = 'pgrst_anon') then
+    grant select on api.pool_intent_events to pgrst_anon;
+  end if;
+end $$ ;
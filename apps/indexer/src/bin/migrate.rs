@@ -1,10 +1,23 @@
+mod checkpoint;
+
 use anyhow::{Context, Result};
 use sqlx::Postgres;
 use sqlx::postgres::PgPoolOptions;
 use std::env;
 
+#[derive(Debug, Clone)]
+enum Command {
+    /// Apply migrations (default).
+    Migrate,
+    /// Write a checkpoint of the current projected state to a file (no migrations are run).
+    ExportCheckpoint(String),
+    /// Apply migrations, then restore a checkpoint into an empty database.
+    ImportCheckpoint(String),
+}
+
 #[derive(Debug, Clone)]
 struct Args {
+    command: Command,
     notify_pgrst: bool,
     pgrst_channel: String,
     pgrst_payload: String,
@@ -13,6 +26,7 @@ struct Args {
 impl Default for Args {
     fn default() -> Self {
         Self {
+            command: Command::Migrate,
             notify_pgrst: true,
             pgrst_channel: "pgrst".to_string(),
             pgrst_payload: "reload schema".to_string(),
@@ -33,6 +47,14 @@ fn parse_args() -> Result<Args> {
             "--pgrst-payload" => {
                 out.pgrst_payload = it.next().context("missing value for --pgrst-payload")?;
             }
+            "export-checkpoint" => {
+                let path = it.next().context("missing path for export-checkpoint")?;
+                out.command = Command::ExportCheckpoint(path);
+            }
+            "import-checkpoint" => {
+                let path = it.next().context("missing path for import-checkpoint")?;
+                out.command = Command::ImportCheckpoint(path);
+            }
             other => anyhow::bail!("unknown arg: {other}"),
         }
     }
@@ -50,12 +72,31 @@ async fn main() -> Result<()> {
         .await
         .context("connect to database")?;
 
+    if let Command::ExportCheckpoint(path) = &args.command {
+        let version = schema_version(&pool).await?;
+        return checkpoint::export(&pool, version, path).await;
+    }
+
     // Apply migrations.
     sqlx::migrate!("./db/migrations")
         .run(&pool)
         .await
         .context("run migrations")?;
 
+    if let Command::ImportCheckpoint(path) = &args.command {
+        let version = schema_version(&pool).await?;
+        checkpoint::import(&pool, version, path).await?;
+    }
+
+    // Views over solver-owned tables; the solver may migrate after us, so retry on every run.
+    let solver_views: bool = sqlx::query_scalar("select chain.ensure_solver_views()")
+        .fetch_one(&pool)
+        .await
+        .context("ensure solver views")?;
+    if !solver_views {
+        println!("solver schema not found; skipped api.solver_fill_latency");
+    }
+
     if args.notify_pgrst {
         sqlx::query("select pg_notify($1, $2)")
             .bind(&args.pgrst_channel)
@@ -71,14 +112,15 @@ async fn main() -> Result<()> {
     }
 
     // Helpful in logs/CI: print the schema migration version.
-    let version: i64 = sqlx::query_scalar::<Postgres, i64>(
-        "select coalesce(max(version), 0) from _sqlx_migrations",
-    )
-    .fetch_one(&pool)
-    .await
-    .context("read migration version")?;
-
+    let version = schema_version(&pool).await?;
     println!("migrations applied, schema version={version}");
 
     Ok(())
 }
+
+async fn schema_version(pool: &sqlx::PgPool) -> Result<i64> {
+    sqlx::query_scalar::<Postgres, i64>("select coalesce(max(version), 0) from _sqlx_migrations")
+        .fetch_one(pool)
+        .await
+        .context("read migration version")
+}
@@ -0,0 +1,260 @@
+//! Checkpoint export/import for bootstrapping a fresh indexer database without replaying the chain.
+//!
+//! Format (newline-delimited JSON):
+//! - line 1: header `{"format":"untron-indexer-checkpoint","version":1,"schema_version":N}`
+//! - then one line per row: `{"table":"<schema.table>","row":{...}}`, where `row` is the row's
+//!   `to_jsonb` form (column name -> value). Rows are grouped by table, in [`TABLES`] order.
+//!
+//! Contents: instance config and stream cursors, every `pool.*`/`forwarder.*` projection table,
+//! a tail of canonical `chain.event_appended` rows (so reorg detection has recent block hashes to
+//! compare against), and one `chain.checkpoint_resume` row per instance with the block to resume
+//! scanning from.
+//!
+//! Schema mapping: rows are restored with `jsonb_populate_record`, so columns are matched by name.
+//! Columns added by later migrations come back as NULL and keys for dropped columns are ignored.
+//! A migration that adds a NOT NULL column (or renames one) on an exported table must bump
+//! [`FORMAT_VERSION`] and teach [`import`] how to map older versions.
+
+use anyhow::{Context, Result};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+const FORMAT: &str = "untron-indexer-checkpoint";
+const FORMAT_VERSION: u32 = 1;
+
+/// Canonical raw events kept per instance, counted back from its latest event block.
+/// Matches the worker's default reorg scan depth.
+const EVENT_TAIL_BLOCKS: i64 = 256;
+
+const IMPORT_BATCH_ROWS: usize = 1_000;
+
+const EVENT_APPENDED: &str = "chain.event_appended";
+const CHECKPOINT_RESUME: &str = "chain.checkpoint_resume";
+
+/// Tables restored verbatim, in dependency order.
+const TABLES: &[&str] = &[
+    "chain.instance",
+    "chain.stream_cursor",
+    "pool.ownership_versions",
+    "pool.recommended_fee_versions",
+    "pool.receiver_intent_params_versions",
+    "pool.receiver_intent_fee_snap_versions",
+    "pool.intent_versions",
+    "pool.intent_claimed_ledger",
+    "pool.intent_unclaimed_ledger",
+    "pool.intent_solved_ledger",
+    "pool.intent_funded_ledger",
+    "pool.intent_settled_ledger",
+    "pool.intent_closed_ledger",
+    "pool.intent_specs_decoded",
+    "pool.intent_events",
+    "forwarder.ownership_versions",
+    "forwarder.bridgers_versions",
+    "forwarder.quoter_versions",
+    "forwarder.receiver_versions",
+    "forwarder.forward_versions",
+    "forwarder.swap_executed_ledger",
+    "forwarder.bridge_initiated_ledger",
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+    schema_version: i64,
+}
+
+/// Only the envelope; `row` is left for Postgres to parse so u256 values never go through f64.
+#[derive(Debug, Deserialize)]
+struct Envelope {
+    table: String,
+}
+
+pub async fn export(pool: &PgPool, schema_version: i64, path: &str) -> Result<()> {
+    let file = std::fs::File::create(path).with_context(|| format!("create {path}"))?;
+    let mut out = BufWriter::new(file);
+
+    // One snapshot for everything, so cursors, projections and resume points agree.
+    let mut tx = pool.begin().await.context("begin export tx")?;
+    sqlx::query("set transaction isolation level repeatable read, read only")
+        .execute(&mut *tx)
+        .await
+        .context("set export tx isolation")?;
+
+    let header = Header {
+        format: FORMAT.to_string(),
+        version: FORMAT_VERSION,
+        schema_version,
+    };
+    writeln!(out, "{}", serde_json::to_string(&header)?).context("write header")?;
+
+    let mut total = 0usize;
+    for table in TABLES {
+        let sql = format!("select to_jsonb(t)::text from {table} t");
+        total += write_rows(&mut tx, &mut out, table, &sql).await?;
+    }
+
+    let tail_sql = format!(
+        "select to_jsonb(e)::text \
+         from {EVENT_APPENDED} e \
+         join ( \
+            select stream, chain_id, contract_address, max(block_number) as max_block \
+            from {EVENT_APPENDED} where canonical \
+            group by stream, chain_id, contract_address \
+         ) m using (stream, chain_id, contract_address) \
+         where e.canonical and e.block_number > m.max_block - {EVENT_TAIL_BLOCKS} \
+         order by e.id"
+    );
+    total += write_rows(&mut tx, &mut out, EVENT_APPENDED, &tail_sql).await?;
+
+    // Prefer the worker's scan position; fall back to the block after the latest event.
+    let resume_sql = format!(
+        "select jsonb_build_object( \
+            'stream', i.stream, \
+            'chain_id', i.chain_id, \
+            'contract_address', i.contract_address, \
+            'resume_block', coalesce( \
+                h.indexed_through_block + 1, \
+                (select max(e.block_number) + 1 from {EVENT_APPENDED} e \
+                  where e.stream = i.stream and e.chain_id = i.chain_id \
+                    and e.contract_address = i.contract_address and e.canonical), \
+                0) \
+         )::text \
+         from chain.instance i \
+         left join chain.stream_heads h using (stream, chain_id, contract_address)"
+    );
+    total += write_rows(&mut tx, &mut out, CHECKPOINT_RESUME, &resume_sql).await?;
+
+    tx.commit().await.context("commit export tx")?;
+    out.flush().context("flush checkpoint")?;
+    println!("checkpoint exported: {total} rows -> {path}");
+    Ok(())
+}
+
+async fn write_rows(
+    tx: &mut Transaction<'_, Postgres>,
+    out: &mut impl Write,
+    table: &str,
+    sql: &str,
+) -> Result<usize> {
+    let mut rows = sqlx::query_scalar::<_, String>(sql).fetch(&mut **tx);
+    let mut n = 0usize;
+    while let Some(row) = rows
+        .try_next()
+        .await
+        .with_context(|| format!("read {table}"))?
+    {
+        writeln!(out, "{{\"table\":\"{table}\",\"row\":{row}}}")
+            .with_context(|| format!("write {table} row"))?;
+        n += 1;
+    }
+    Ok(n)
+}
+
+pub async fn import(pool: &PgPool, schema_version: i64, path: &str) -> Result<()> {
+    let file = std::fs::File::open(path).with_context(|| format!("open {path}"))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header_line = lines
+        .next()
+        .context("checkpoint is empty")?
+        .context("read checkpoint header")?;
+    let header: Header = serde_json::from_str(&header_line).context("parse checkpoint header")?;
+    if header.format != FORMAT {
+        anyhow::bail!("not an indexer checkpoint (format={})", header.format);
+    }
+    if header.version != FORMAT_VERSION {
+        anyhow::bail!(
+            "unsupported checkpoint version {} (this build reads version {FORMAT_VERSION})",
+            header.version
+        );
+    }
+    if header.schema_version > schema_version {
+        anyhow::bail!(
+            "checkpoint was exported at schema version {}, but this database is at {schema_version}; run a newer migrate binary",
+            header.schema_version
+        );
+    }
+
+    let mut tx = pool.begin().await.context("begin import tx")?;
+
+    let existing: i64 = sqlx::query_scalar("select count(*) from chain.instance")
+        .fetch_one(&mut *tx)
+        .await
+        .context("count chain.instance")?;
+    if existing > 0 {
+        anyhow::bail!("refusing to import into a database that already has configured instances");
+    }
+
+    let mut current: Option<String> = None;
+    let mut batch: Vec<String> = Vec::with_capacity(IMPORT_BATCH_ROWS);
+    let mut total = 0usize;
+    for (i, line) in lines.enumerate() {
+        let line = line.with_context(|| format!("read checkpoint line {}", i + 2))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let env: Envelope = serde_json::from_str(&line)
+            .with_context(|| format!("parse checkpoint line {}", i + 2))?;
+        if !is_importable(&env.table) {
+            anyhow::bail!("unexpected table in checkpoint: {}", env.table);
+        }
+
+        if current.as_deref() != Some(env.table.as_str()) || batch.len() >= IMPORT_BATCH_ROWS {
+            if let Some(table) = current.as_deref() {
+                total += insert_batch(&mut tx, table, &mut batch).await?;
+            }
+            current = Some(env.table);
+        }
+        batch.push(line);
+    }
+    if let Some(table) = current.as_deref() {
+        total += insert_batch(&mut tx, table, &mut batch).await?;
+    }
+
+    // Raw events are restored with their original ids.
+    sqlx::query(
+        "select setval(pg_get_serial_sequence('chain.event_appended', 'id'), \
+                       coalesce((select max(id) from chain.event_appended), 0) + 1, false)",
+    )
+    .execute(&mut *tx)
+    .await
+    .context("reset chain.event_appended id sequence")?;
+
+    tx.commit().await.context("commit import tx")?;
+    println!(
+        "checkpoint imported: {total} rows from {path} (exported at schema version {})",
+        header.schema_version
+    );
+    Ok(())
+}
+
+fn is_importable(table: &str) -> bool {
+    TABLES.contains(&table) || table == EVENT_APPENDED || table == CHECKPOINT_RESUME
+}
+
+async fn insert_batch(
+    tx: &mut Transaction<'_, Postgres>,
+    table: &str,
+    batch: &mut Vec<String>,
+) -> Result<usize> {
+    if batch.is_empty() {
+        return Ok(0);
+    }
+    // `table` is checked against the allowlist, so interpolating it is safe.
+    let sql = format!(
+        "insert into {table} \
+         select r.* from unnest($1::text[]) as l(line), \
+              jsonb_populate_record(null::{table}, l.line::jsonb -> 'row') as r"
+    );
+    let n = sqlx::query(&sql)
+        .bind(batch.as_slice())
+        .execute(&mut **tx)
+        .await
+        .with_context(|| format!("insert into {table}"))?
+        .rows_affected();
+    batch.clear();
+    Ok(usize::try_from(n).unwrap_or(usize::MAX))
+}
@@ -38,6 +38,8 @@ pub struct InstanceConfig {
     /// EVM 0x-address (for both pool + forwarder).
     pub contract_address: String,
     pub deployment_block: u64,
+    /// Optional websocket endpoint; new matching logs wake the scanner between polls.
+    pub ws_url: Option<String>,
 
     pub confirmations: u64,
     pub poll_interval: Duration,
@@ -61,6 +63,29 @@ pub struct AppConfig {
 
     /// Optional: only run a subset of streams ("pool" | "forwarder" | "all").
     pub only_stream: Option<StreamSelection>,
+
+    /// Log [`AppConfig::redacted_debug`] once at startup.
+    pub log_effective_config: bool,
+}
+
+impl AppConfig {
+    /// Full effective config as a single debug string, with credentials masked.
+    ///
+    /// The only secrets the indexer holds are its database and RPC URLs; those keep just
+    /// `scheme://host[:port]` since providers embed API keys in userinfo, path or query.
+    pub fn redacted_debug(&self) -> String {
+        let mut cfg = self.clone();
+        cfg.database_url = redact_url(&cfg.database_url);
+        for instance in std::iter::once(&mut cfg.pool).chain(cfg.forwarders.iter_mut()) {
+            for url in &mut instance.rpc.urls {
+                *url = redact_url(url);
+            }
+            if let Some(url) = &mut instance.ws_url {
+                *url = redact_url(url);
+            }
+        }
+        format!("{cfg:?}")
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -101,6 +126,9 @@ struct BaseEnv {
 
     #[serde(rename = "indexer_stream")]
     stream: Option<String>,
+
+    #[serde(rename = "indexer_log_effective_config")]
+    log_effective_config: bool,
 }
 
 impl Default for BaseEnv {
@@ -113,6 +141,7 @@ impl Default for BaseEnv {
             progress_interval_secs: DEFAULT_PROGRESS_INTERVAL_SECS,
             progress_tail_lag_blocks: DEFAULT_PROGRESS_TAIL_LAG_BLOCKS,
             stream: None,
+            log_effective_config: true,
         }
     }
 }
@@ -127,8 +156,11 @@ struct PoolEnv {
 
     confirmations: Option<u64>,
     poll_interval_secs: Option<u64>,
+    /// Takes precedence over `poll_interval_secs` (allows sub-second polling).
+    poll_interval_ms: Option<u64>,
     chunk_blocks: Option<u64>,
     reorg_scan_depth: Option<u64>,
+    ws_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -208,13 +240,20 @@ pub fn load_config() -> Result<AppConfig> {
         },
         contract_address: pool_env.contract_address,
         deployment_block: pool_env.deployment_block,
+        ws_url: pool_env
+            .ws_url
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty()),
         confirmations: pool_env.confirmations.unwrap_or(DEFAULT_POOL_CONFIRMATIONS),
-        poll_interval: Duration::from_secs(
-            pool_env
-                .poll_interval_secs
-                .unwrap_or(DEFAULT_POOL_POLL_INTERVAL_SECS)
-                .max(1),
-        ),
+        poll_interval: match pool_env.poll_interval_ms {
+            Some(ms) => Duration::from_millis(ms.max(MIN_POLL_INTERVAL_MS)),
+            None => Duration::from_secs(
+                pool_env
+                    .poll_interval_secs
+                    .unwrap_or(DEFAULT_POOL_POLL_INTERVAL_SECS)
+                    .max(1),
+            ),
+        },
         chunk_blocks: pool_env
             .chunk_blocks
             .unwrap_or(DEFAULT_POOL_CHUNK_BLOCKS)
@@ -262,6 +301,7 @@ pub fn load_config() -> Result<AppConfig> {
             },
             contract_address,
             deployment_block: entry.forwarder_deployment_block,
+            ws_url: None,
             confirmations: entry
                 .confirmations
                 .or(forwarders_env.forwarder_confirmations)
@@ -301,9 +341,34 @@ pub fn load_config() -> Result<AppConfig> {
         pool,
         forwarders,
         only_stream,
+        log_effective_config: base.log_effective_config,
     })
 }
 
+/// Keeps `scheme://host[:port]` and drops userinfo, path and query.
+fn redact_url(raw: &str) -> String {
+    const REDACTED: &str = "<redacted>";
+    let raw = raw.trim();
+    let Some((scheme, rest)) = raw.split_once("://") else {
+        return if raw.is_empty() {
+            String::new()
+        } else {
+            REDACTED.to_string()
+        };
+    };
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, tail) = rest.split_at(authority_end);
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let mut out = format!("{scheme}://{host}");
+    if host.len() != authority.len() || !matches!(tail, "" | "/") {
+        out.push('/');
+        out.push_str(REDACTED);
+    }
+    out
+}
+
 fn parse_list(raw: &str) -> Vec<String> {
     raw.split(|c: char| c == ',' || c.is_whitespace())
         .map(str::trim)
@@ -332,6 +397,8 @@ const DEFAULT_BLOCK_TIMESTAMP_CACHE_SIZE: usize = 2048;
 const DEFAULT_PROGRESS_INTERVAL_SECS: u64 = 5;
 const DEFAULT_PROGRESS_TAIL_LAG_BLOCKS: u64 = 0;
 
+const MIN_POLL_INTERVAL_MS: u64 = 50;
+
 // Pool stream defaults (tuned for typical EVM RPCs).
 const DEFAULT_POOL_CONFIRMATIONS: u64 = 0;
 const DEFAULT_POOL_POLL_INTERVAL_SECS: u64 = 1;
@@ -343,3 +410,30 @@ const DEFAULT_FORWARDER_CONFIRMATIONS: u64 = 0;
 const DEFAULT_FORWARDER_POLL_INTERVAL_SECS: u64 = 1;
 const DEFAULT_FORWARDER_CHUNK_BLOCKS: u64 = 2_000;
 const DEFAULT_FORWARDER_REORG_SCAN_DEPTH: u64 = 256;
+
+#[cfg(test)]
+mod tests {
+    use super::redact_url;
+
+    #[test]
+    fn redact_url_keeps_only_scheme_host_port() {
+        assert_eq!(
+            redact_url("https://eth-mainnet.g.alchemy.com/v2/SECRETKEY"),
+            "https://eth-mainnet.g.alchemy.com/<redacted>"
+        );
+        assert_eq!(
+            redact_url("postgres://user:hunter2@db:5432/untron"),
+            "postgres://db:5432/<redacted>"
+        );
+        assert_eq!(
+            redact_url("wss://rpc.example.com?apikey=SECRETKEY"),
+            "wss://rpc.example.com/<redacted>"
+        );
+        assert_eq!(
+            redact_url("http://127.0.0.1:8545/"),
+            "http://127.0.0.1:8545"
+        );
+        assert_eq!(redact_url("not a url"), "<redacted>");
+        assert_eq!(redact_url(""), "");
+    }
+}
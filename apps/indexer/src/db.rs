@@ -159,8 +159,12 @@ pub async fn resume_from_block(
     deployment_block: u64,
 ) -> Result<u64> {
     let chain_id_db = i64::try_from(chain_id).context("chain_id out of range for bigint")?;
+    // An imported checkpoint's resume block is a floor: it only ships a tail of raw events.
     let max_block: Option<i64> = query_scalar(
-        "select max(block_number) from chain.event_appended where stream = $1::chain.stream and chain_id = $2 and contract_address = $3::evm_address and canonical",
+        "select greatest( \
+            (select max(block_number) from chain.event_appended where stream = $1::chain.stream and chain_id = $2 and contract_address = $3::evm_address and canonical), \
+            (select resume_block - 1 from chain.checkpoint_resume where stream = $1::chain.stream and chain_id = $2 and contract_address = $3::evm_address) \
+         )",
     )
     .bind(stream.as_str())
     .bind(chain_id_db)
@@ -288,6 +292,41 @@ pub async fn invalidate_from_block(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub async fn record_stream_heads(
+    db: &Db,
+    stream: Stream,
+    chain_id: u64,
+    contract_address: &str,
+    head_block: u64,
+    confirmed_head_block: u64,
+    confirmations: u64,
+    indexed_through_block: u64,
+) -> Result<()> {
+    let to_db = |v: u64| i64::try_from(v).context("block number out of range");
+    sqlx::query(
+        "insert into chain.stream_heads(stream, chain_id, contract_address, head_block, confirmed_head_block, confirmations, indexed_through_block, updated_at) \
+         values ($1::chain.stream, $2, $3::evm_address, $4, $5, $6, $7, now()) \
+         on conflict (stream, chain_id, contract_address) do update set \
+            head_block = excluded.head_block, \
+            confirmed_head_block = excluded.confirmed_head_block, \
+            confirmations = excluded.confirmations, \
+            indexed_through_block = excluded.indexed_through_block, \
+            updated_at = now()",
+    )
+    .bind(stream.as_str())
+    .bind(i64::try_from(chain_id).context("chain_id out of range")?)
+    .bind(contract_address)
+    .bind(to_db(head_block)?)
+    .bind(to_db(confirmed_head_block)?)
+    .bind(to_db(confirmations)?)
+    .bind(to_db(indexed_through_block)?)
+    .execute(&db.pool)
+    .await
+    .context("upsert chain.stream_heads")?;
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 pub struct EventAppendedRow {
     pub stream: Stream,
@@ -2,7 +2,7 @@ use crate::config::Stream;
 use alloy::{
     primitives::{Address, B256, Bytes, U256},
     sol,
-    sol_types::{Error as SolError, SolEventInterface},
+    sol_types::{Error as SolError, SolEventInterface, SolValue},
 };
 use anyhow::Result;
 use serde::Serialize;
@@ -166,7 +166,18 @@ pub enum SemanticEvent {
 impl SemanticEvent {
     pub fn into_db_parts(self) -> (Cow<'static, str>, serde_json::Value) {
         match self {
-            SemanticEvent::Pool(ev) => split_tagged(&ev),
+            SemanticEvent::Pool(ev) => {
+                let (event_type, mut args) = split_tagged(&ev);
+                if let Some(specs) = ev.decoded_intent_specs()
+                    && let Some(obj) = args.as_object_mut()
+                {
+                    obj.insert(
+                        "intent_specs_decoded".to_string(),
+                        serde_json::to_value(specs).expect("serializable"),
+                    );
+                }
+                (event_type, args)
+            }
             SemanticEvent::Forwarder(ev) => split_tagged(&ev),
         }
     }
@@ -287,6 +298,109 @@ stream_events! {
     }
 }
 
+sol! {
+    struct TriggerSmartContractIntent {
+        address to;
+        uint256 callValueSun;
+        bytes data;
+    }
+
+    struct USDTTransferIntent {
+        address to;
+        uint256 amount;
+    }
+
+    struct TRXTransferIntent {
+        address to;
+        uint256 amountSun;
+    }
+
+    struct DelegateResourceIntent {
+        address receiver;
+        uint8 resource;
+        uint256 balanceSun;
+        uint256 lockPeriod;
+    }
+}
+
+/// `UntronIntents.IntentType` discriminants.
+const INTENT_TYPE_TRIGGER_SMART_CONTRACT: u8 = 0;
+const INTENT_TYPE_USDT_TRANSFER: u8 = 1;
+const INTENT_TYPE_TRX_TRANSFER: u8 = 2;
+const INTENT_TYPE_DELEGATE_RESOURCE: u8 = 3;
+
+/// Normalized `intent_specs` fields, projected into `pool.intent_specs_decoded`.
+/// Fields that don't apply to an intent type serialize as `null`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DecodedIntentSpecs {
+    to_address: Option<S<Address>>,
+    amount: Option<S<U256>>,
+    contract: Option<S<Address>>,
+    selector: Option<String>,
+    call_data: Option<HexBytes>,
+    resource: Option<u8>,
+    lock_period: Option<S<U256>>,
+}
+
+/// ABI-decodes `intent_specs` by `intent_type`. Returns `None` for unknown types or malformed specs.
+pub fn decode_intent_specs(intent_type: u8, specs: &[u8]) -> Option<DecodedIntentSpecs> {
+    Some(match intent_type {
+        INTENT_TYPE_TRIGGER_SMART_CONTRACT => {
+            let intent = TriggerSmartContractIntent::abi_decode(specs).ok()?;
+            DecodedIntentSpecs {
+                contract: Some(AsString(intent.to)),
+                amount: Some(AsString(intent.callValueSun)),
+                selector: intent
+                    .data
+                    .get(..4)
+                    .map(|sel| format!("0x{}", hex::encode(sel))),
+                call_data: Some(HexBytes(intent.data)),
+                ..Default::default()
+            }
+        }
+        INTENT_TYPE_USDT_TRANSFER => {
+            let intent = USDTTransferIntent::abi_decode(specs).ok()?;
+            DecodedIntentSpecs {
+                to_address: Some(AsString(intent.to)),
+                amount: Some(AsString(intent.amount)),
+                ..Default::default()
+            }
+        }
+        INTENT_TYPE_TRX_TRANSFER => {
+            let intent = TRXTransferIntent::abi_decode(specs).ok()?;
+            DecodedIntentSpecs {
+                to_address: Some(AsString(intent.to)),
+                amount: Some(AsString(intent.amountSun)),
+                ..Default::default()
+            }
+        }
+        INTENT_TYPE_DELEGATE_RESOURCE => {
+            let intent = DelegateResourceIntent::abi_decode(specs).ok()?;
+            DecodedIntentSpecs {
+                to_address: Some(AsString(intent.receiver)),
+                amount: Some(AsString(intent.balanceSun)),
+                resource: Some(intent.resource),
+                lock_period: Some(AsString(intent.lockPeriod)),
+                ..Default::default()
+            }
+        }
+        _ => return None,
+    })
+}
+
+impl PoolEvent {
+    fn decoded_intent_specs(&self) -> Option<DecodedIntentSpecs> {
+        match self {
+            PoolEvent::IntentCreated {
+                intent_type,
+                intent_specs,
+                ..
+            } => decode_intent_specs(intent_type.0, &intent_specs.0),
+            _ => None,
+        }
+    }
+}
+
 pub fn decode_semantic_event(
     stream: Stream,
     event_signature: B256,
@@ -28,6 +28,9 @@ async fn main() -> Result<()> {
         only_stream = ?cfg.only_stream,
         "config loaded"
     );
+    if cfg.log_effective_config {
+        tracing::info!(config = %cfg.redacted_debug(), "effective config");
+    }
 
     let shutdown = CancellationToken::new();
 
@@ -1,4 +1,8 @@
+use crate::metrics::StreamTelemetry;
+use alloy::providers::{Provider, ProviderBuilder, WsConnect};
+use alloy::rpc::types::Filter;
 use anyhow::{Context, Result};
+use futures::StreamExt;
 use reqwest::StatusCode;
 use serde_json::Value;
 use std::sync::{
@@ -6,6 +10,9 @@ use std::sync::{
     atomic::{AtomicU64, AtomicUsize, Ordering},
 };
 use std::time::Duration;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
 #[derive(Clone)]
 pub struct RpcClient {
@@ -127,6 +134,57 @@ impl RpcClient {
     }
 }
 
+/// Subscribes to `filter` over `ws_url` and notifies on every pushed log.
+///
+/// The caller keeps polling regardless, so a dropped or failing socket only costs latency: the
+/// task logs, backs off and reconnects until `cancel` fires.
+pub fn spawn_log_wakeups(
+    ws_url: String,
+    filter: Filter,
+    telemetry: StreamTelemetry,
+    cancel: CancellationToken,
+) -> Arc<Notify> {
+    let notify = Arc::new(Notify::new());
+    let wakeups = notify.clone();
+    tokio::spawn(async move {
+        let mut backoff = Duration::from_secs(1);
+        loop {
+            let res = tokio::select! {
+                _ = cancel.cancelled() => return,
+                res = forward_logs(&ws_url, &filter, &wakeups) => res,
+            };
+            telemetry.rpc_error("eth_subscribe_logs");
+            match res {
+                Ok(()) => warn!("log subscription ended; polling until reconnected"),
+                Err(e) => warn!(err = %e, "log subscription failed; polling until reconnected"),
+            }
+            tokio::select! {
+                _ = cancel.cancelled() => return,
+                _ = tokio::time::sleep(backoff) => {}
+            }
+            backoff = (backoff * 2).min(Duration::from_secs(30));
+        }
+    });
+    notify
+}
+
+async fn forward_logs(ws_url: &str, filter: &Filter, notify: &Notify) -> Result<()> {
+    let provider = ProviderBuilder::new()
+        .connect_ws(WsConnect::new(ws_url))
+        .await
+        .context("connect websocket")?;
+    let sub = provider
+        .subscribe_logs(filter)
+        .await
+        .context("eth_subscribe(logs)")?;
+    info!("log subscription established");
+    let mut logs = sub.into_stream();
+    while logs.next().await.is_some() {
+        notify.notify_one();
+    }
+    Ok(())
+}
+
 pub fn format_quantity(value: u64) -> String {
     format!("0x{value:x}")
 }
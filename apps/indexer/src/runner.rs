@@ -26,7 +26,7 @@ fn event_appended_topic0(stream: Stream) -> String {
 
 pub async fn run(cfg: AppConfig, shutdown: CancellationToken) -> Result<()> {
     let dbh = db::Db::connect(&cfg.database_url, cfg.db_max_connections).await?;
-    let _schema_version = db::ensure_schema_version(&dbh, 5).await?;
+    let _schema_version = db::ensure_schema_version(&dbh, 8).await?;
 
     let block_timestamp_cache_size = cfg.block_timestamp_cache_size;
     let block_header_concurrency = cfg.block_header_concurrency;
@@ -139,9 +139,23 @@ async fn run_instance(
     let topic0 = event_appended_topic0(cfg.stream);
     let telemetry = StreamTelemetry::new(cfg.stream, cfg.chain_id);
 
-    let mut ticker = time::interval(cfg.poll_interval.max(Duration::from_secs(1)));
+    let mut ticker = time::interval(cfg.poll_interval);
     ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
 
+    // Websocket pushes only shorten the wait; the ticker keeps polling (and covers confirmations
+    // and dropped sockets) either way. The guard stops the subscriber when this instance exits.
+    let ws_cancel = shutdown.child_token();
+    let _ws_guard = ws_cancel.clone().drop_guard();
+    let ws_wakeup = match &cfg.ws_url {
+        Some(url) => Some(rpc::spawn_log_wakeups(
+            url.clone(),
+            log_filter(cfg, &topic0)?,
+            telemetry.clone(),
+            ws_cancel,
+        )),
+        None => None,
+    };
+
     let mut chunk_current = cfg.chunk_blocks.max(1);
     let chunk_target = cfg.chunk_blocks.max(1);
 
@@ -155,7 +169,8 @@ async fn run_instance(
         contract = %cfg.contract_address,
         from_block,
         confirmations = cfg.confirmations,
-        poll_interval_secs = cfg.poll_interval.as_secs(),
+        poll_interval_ms = cfg.poll_interval.as_millis() as u64,
+        ws = cfg.ws_url.is_some(),
         chunk_blocks = cfg.chunk_blocks,
         reorg_scan_depth = cfg.reorg_scan_depth,
         "instance starting"
@@ -165,6 +180,7 @@ async fn run_instance(
         tokio::select! {
             _ = shutdown.cancelled() => return Ok(()),
             _ = ticker.tick() => {}
+            _ = wait_for_wakeup(ws_wakeup.as_deref()) => ticker.reset(),
         }
 
         let head_start = Instant::now();
@@ -175,8 +191,11 @@ async fn run_instance(
         telemetry
             .observe_rpc_latency_ms("eth_blockNumber", head_start.elapsed().as_millis() as u64);
 
+        // Only blocks at or below `safe_head` are ingested; newer logs stay pending on-chain and
+        // are picked up once they have `confirmations` blocks on top.
         let safe_head = head.saturating_sub(cfg.confirmations);
         telemetry.set_chain_position(head, safe_head, from_block, chunk_current);
+        record_heads(dbh, cfg, head, safe_head, from_block).await;
 
         if last_progress_at.elapsed() >= progress_interval.max(Duration::from_secs(1)) {
             let backlog_blocks = if from_block > safe_head {
@@ -233,6 +252,8 @@ async fn run_instance(
             .await?;
             timestamps_cache.clear();
             from_block = from_block.min(reorg_start);
+            // Don't leave a stale scan position behind if we stop before rescanning.
+            record_heads(dbh, cfg, head, safe_head, from_block).await;
         }
 
         while from_block <= safe_head {
@@ -256,6 +277,7 @@ async fn run_instance(
                 Ok((logs_count, total_ms)) => {
                     telemetry.observe_range(from_block, to_block, logs_count, 0, total_ms);
                     from_block = to_block.saturating_add(1);
+                    record_heads(dbh, cfg, head, safe_head, from_block).await;
                     transient_attempts = 0;
                     transient_backoff = Duration::from_millis(250);
                     chunk_current = grow_chunk(chunk_current, chunk_target);
@@ -332,6 +354,54 @@ async fn run_instance(
     }
 }
 
+async fn wait_for_wakeup(notify: Option<&tokio::sync::Notify>) {
+    match notify {
+        Some(n) => n.notified().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn log_filter(cfg: &InstanceConfig, topic0: &str) -> Result<alloy::rpc::types::Filter> {
+    let address: alloy::primitives::Address = cfg
+        .contract_address
+        .parse()
+        .with_context(|| format!("invalid contract address: {}", cfg.contract_address))?;
+    let topic0: alloy::primitives::B256 = topic0.parse().context("invalid topic0")?;
+    Ok(alloy::rpc::types::Filter::new()
+        .address(address)
+        .event_signature(topic0))
+}
+
+/// Best-effort: a failed write only makes `api.stream_heads` stale, which shouldn't stop ingestion.
+async fn record_heads(
+    dbh: &db::Db,
+    cfg: &InstanceConfig,
+    head: u64,
+    safe_head: u64,
+    next_block: u64,
+) {
+    let res = db::record_stream_heads(
+        dbh,
+        cfg.stream,
+        cfg.chain_id,
+        &cfg.contract_address,
+        head,
+        safe_head,
+        cfg.confirmations,
+        next_block.saturating_sub(1),
+    )
+    .await;
+    if let Err(e) = res {
+        warn!(
+            stream = cfg.stream.as_str(),
+            chain_id = cfg.chain_id,
+            contract = %cfg.contract_address,
+            err = %e,
+            "failed to record stream heads"
+        );
+    }
+}
+
 fn looks_like_tip_mismatch(err: &anyhow::Error) -> bool {
     let msg = err.to_string().to_lowercase();
     msg.contains("tip mismatch")
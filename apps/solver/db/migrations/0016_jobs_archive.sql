@@ -0,0 +1,32 @@
+-- Cold storage for terminal jobs (`done` / `failed_fatal`) and the rows associated with them.
+--
+-- Archival moves rows out of the hot tables so leasing queries stay fast. The archive tables mirror
+-- the hot tables' columns (plus `archived_at`), so any column added to a hot table must be added to
+-- its archive table in the same migration.
+
+create table if not exists solver.jobs_archive (
+    like solver.jobs including defaults including constraints including indexes,
+    archived_at timestamptz not null default now()
+);
+
+create table if not exists solver.hub_userops_archive (
+    like solver.hub_userops including defaults including constraints including indexes,
+    archived_at timestamptz not null default now()
+);
+
+create table if not exists solver.tron_tx_costs_archive (
+    like solver.tron_tx_costs including defaults including constraints including indexes,
+    archived_at timestamptz not null default now()
+);
+
+create table if not exists solver.tron_proofs_archive (
+    like solver.tron_proofs including defaults including constraints including indexes,
+    archived_at timestamptz not null default now()
+);
+
+create index if not exists jobs_archive_archived_at_idx
+    on solver.jobs_archive(archived_at desc);
+
+create index if not exists jobs_terminal_updated_at_idx
+    on solver.jobs(updated_at)
+    where state in ('done', 'failed_fatal');
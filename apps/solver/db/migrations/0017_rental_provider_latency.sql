@@ -0,0 +1,7 @@
+create table if not exists solver.rental_provider_stats (
+    provider text primary key,
+    avg_order_ms double precision not null,
+    samples bigint not null default 0,
+    last_order_ms bigint not null,
+    updated_at timestamptz not null default now()
+);
@@ -0,0 +1,16 @@
+-- Dry-run mode (SOLVER_DRY_RUN): intents the solver would have claimed, with the plan and the cost
+-- estimates it would have claimed on. Upserted per intent on every tick it stays attractive.
+create table if not exists solver.dry_run_decisions (
+    intent_id bytea primary key,
+    intent_type smallint not null,
+    plan jsonb not null,
+    hub_cost_usd double precision not null,
+    tron_fee_usd double precision not null,
+    rental_cost_usd double precision not null,
+    decide_count bigint not null default 0,
+    first_seen_at timestamptz not null default now(),
+    last_seen_at timestamptz not null default now()
+);
+
+create index if not exists dry_run_decisions_last_seen_idx
+    on solver.dry_run_decisions(last_seen_at desc);
@@ -0,0 +1,20 @@
+-- Proactive consolidation sweeps (SOLVER_PROACTIVE_CONSOLIDATION): one row per broadcast attempt.
+create table if not exists solver.consolidation_sweeps (
+    sweep_id bigserial primary key,
+    asset text not null,
+    from_evm bytea not null,
+    to_evm bytea not null,
+    amount numeric(78, 0) not null,
+    txid bytea not null,
+    instance_id text not null,
+    error text,
+    created_at timestamptz not null default now(),
+
+    constraint consolidation_sweeps_asset check (asset in ('trx', 'usdt')),
+    constraint consolidation_sweeps_from_len check (octet_length(from_evm) = 20),
+    constraint consolidation_sweeps_to_len check (octet_length(to_evm) = 20),
+    constraint consolidation_sweeps_txid_len check (octet_length(txid) = 32)
+);
+
+create index if not exists consolidation_sweeps_created_idx
+    on solver.consolidation_sweeps(created_at desc);
@@ -0,0 +1,11 @@
+-- Energy rented for the sender of a USDT transfer before broadcasting it
+-- (TRON_SPONSOR_USDT_TRANSFER_ENERGY). Recorded on the transfer's own cost row.
+alter table solver.tron_tx_costs
+    add column if not exists sponsored_energy bigint,
+    add column if not exists sponsored_energy_cost_sun bigint,
+    add column if not exists sponsored_energy_provider text;
+
+alter table solver.tron_tx_costs_archive
+    add column if not exists sponsored_energy bigint,
+    add column if not exists sponsored_energy_cost_sun bigint,
+    add column if not exists sponsored_energy_provider text;
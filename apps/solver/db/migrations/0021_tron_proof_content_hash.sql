@@ -0,0 +1,7 @@
+-- keccak256 over the serialized proof, written with it and checked on load so a partially written
+-- or corrupted proof is rebuilt instead of submitted. Null for proofs saved before this migration.
+alter table solver.tron_proofs
+    add column if not exists content_hash bytea;
+
+alter table solver.tron_proofs_archive
+    add column if not exists content_hash bytea;
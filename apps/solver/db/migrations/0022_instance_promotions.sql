@@ -0,0 +1,7 @@
+-- Promotes a follower solver (SOLVER_ROLE=follower) to active without a restart. Followers poll
+-- this table by SOLVER_INSTANCE_ID each tick:
+--   insert into solver.instance_promotions(instance_id) values ('solver-b');
+create table if not exists solver.instance_promotions (
+    instance_id text primary key,
+    promoted_at timestamptz not null default now()
+);
@@ -0,0 +1,4 @@
+-- Unpadded fee estimate behind each signed tx's fee_limit_sun (which has
+-- TRON_FEE_LIMIT_HEADROOM_PPM and TRON_FEE_LIMIT_CAP_SUN applied), for auditing fee_limit sizing.
+alter table solver.tron_signed_txs
+    add column if not exists fee_limit_estimate_sun bigint;
@@ -0,0 +1,22 @@
+-- Claims we released ourselves via `unclaimIntent` after a job stalled (SOLVER_JOB_ABANDON_AFTER_SECS).
+-- The job moves to the terminal `unclaimed` state; this table keeps the hub tx and the deposit the
+-- pool paid back (full deposit if the intent was unfunded, half of it otherwise). Keyed by
+-- intent_id rather than job_id so rows outlive job archival.
+create table if not exists solver.job_unclaims (
+    intent_id bytea primary key,
+    job_id bigint not null,
+    intent_type smallint not null,
+    from_state text not null,
+    unclaim_tx_hash bytea,
+    funded boolean,
+    recovered_deposit numeric,
+    reason text not null,
+    created_at timestamptz not null default now(),
+
+    constraint job_unclaims_intent_id_len check (octet_length(intent_id) = 32)
+);
+
+drop index if exists solver.jobs_terminal_updated_at_idx;
+create index if not exists jobs_terminal_updated_at_idx
+    on solver.jobs(updated_at)
+    where state in ('done', 'failed_fatal', 'unclaimed');
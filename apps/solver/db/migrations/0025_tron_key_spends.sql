@@ -0,0 +1,17 @@
+-- Amounts each Tron key signed away in fill plans (consolidation pre-txs and final transfers), for
+-- the rolling 24h per-key limits (TRON_KEY_DAILY_LIMIT_SUN / TRON_KEY_DAILY_LIMIT_USDT). Rows are
+-- written with the plan, so a signed tx counts even if it never lands.
+create table if not exists solver.tron_key_spends (
+    txid bytea primary key,
+    job_id bigint not null,
+    owner_address bytea not null,
+    asset text not null,
+    amount numeric not null,
+    created_at timestamptz not null default now(),
+
+    constraint tron_key_spends_txid_len check (octet_length(txid) = 32),
+    constraint tron_key_spends_asset check (asset in ('trx', 'usdt'))
+);
+
+create index if not exists tron_key_spends_owner_asset_created_at_idx
+    on solver.tron_key_spends(owner_address, asset, created_at);
@@ -0,0 +1,17 @@
+-- Jobs record which hub pool (`HUB_POOL_ID` / `HUB_EXTRA_POOLS_JSON`) they came from so one solver
+-- can serve several `UntronIntents` deployments. Intent ids are only unique within a pool (they
+-- don't commit to the pool address), so job uniqueness moves to (pool_id, intent_id). Existing
+-- jobs belong to the primary pool, whose id defaults to 'default'.
+alter table solver.jobs
+    add column if not exists pool_id text not null default 'default';
+alter table solver.jobs_archive
+    add column if not exists pool_id text not null default 'default';
+
+alter table solver.jobs drop constraint if exists jobs_unique_intent;
+alter table solver.jobs
+    add constraint jobs_unique_pool_intent unique (pool_id, intent_id);
+
+-- `jobs_archive` copied the old unique index via `like ... including indexes`.
+alter table solver.jobs_archive drop constraint if exists jobs_archive_intent_id_key;
+create index if not exists jobs_archive_pool_intent_idx
+    on solver.jobs_archive(pool_id, intent_id);
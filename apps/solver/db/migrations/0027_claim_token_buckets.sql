@@ -0,0 +1,9 @@
+-- Token buckets for claim rate limiting (SOLVER_MAX_CLAIMS_PER_MINUTE). Unlike the fixed
+-- per-minute windows in `solver.rate_limits`, a bucket refills continuously, so a flood can't
+-- claim a full window's worth on each side of a minute boundary. Rows are shared by every task and
+-- instance; taking a token locks the row.
+create table if not exists solver.claim_token_buckets (
+    key text primary key,
+    tokens double precision not null,
+    updated_at timestamptz not null default now()
+);
@@ -0,0 +1,7 @@
+-- `lost_race`: terminal state for jobs whose claim failed with `AlreadyClaimed` because another
+-- solver got there first. Tracked apart from `failed_fatal` so claim-race losses can be counted
+-- (and don't trip the fatal-error auto pause or webhook).
+drop index if exists solver.jobs_terminal_updated_at_idx;
+create index if not exists jobs_terminal_updated_at_idx
+    on solver.jobs(updated_at)
+    where state in ('done', 'failed_fatal', 'unclaimed', 'lost_race');
@@ -0,0 +1,9 @@
+-- Proofs handed to an external prove relayer (HUB_PROVE_MODE=delegated_relayer). The job stays in
+-- `proof_built` until the indexer reports the intent solved; this row throttles re-POSTs
+-- (HUB_PROVE_RELAYER_RESUBMIT_SECS).
+create table if not exists solver.prove_relay_submissions (
+    job_id bigint primary key references solver.jobs(job_id) on delete cascade,
+    submit_count integer not null default 1,
+    first_submitted_at timestamptz not null default now(),
+    last_submitted_at timestamptz not null default now()
+);
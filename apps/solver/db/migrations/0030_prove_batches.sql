@@ -0,0 +1,15 @@
+-- Batched proves (HUB_PROVE_BATCH_MAX > 1): one Safe4337 userop runs several jobs'
+-- `proveIntentFill` calls through MultiSendCallOnly. Every job keeps its own `hub_userops` row; the
+-- rows of a batch share the userop and its hash, which is how a row finds the rest of its batch
+-- (e.g. to split the userop's gas cost).
+create index if not exists hub_userops_userop_hash_idx
+    on solver.hub_userops(userop_hash)
+    where userop_hash is not null;
+
+-- Jobs whose batched prove userop reverted. A MultiSend revert doesn't say which call failed, so
+-- these jobs are proved on their own from then on.
+create table if not exists solver.prove_batch_opt_outs (
+    job_id bigint primary key references solver.jobs(job_id) on delete cascade,
+    reason text not null,
+    created_at timestamptz not null default now()
+);
@@ -0,0 +1,5 @@
+-- Tron head when a job's final tx was sent (first seen in `tron_sent`). Anchors the window
+-- (TRON_MAX_INCLUSION_SCAN_BLOCKS) in which the tx must land before the job goes back to
+-- `tron_prepared` for a re-broadcast. Cleared on that transition so the next send gets a fresh one.
+alter table solver.jobs
+    add column if not exists tron_broadcast_block bigint null;
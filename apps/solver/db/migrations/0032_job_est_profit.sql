@@ -0,0 +1,4 @@
+-- Profit (USD) the policy priced an intent at when it was accepted, for the operator summary
+-- (SOLVER_SUMMARY_INTERVAL_SECS). Null when the profitability check didn't price the intent.
+alter table solver.jobs
+    add column if not exists est_profit_usd double precision null;
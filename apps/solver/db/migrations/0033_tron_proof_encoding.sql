@@ -0,0 +1,13 @@
+-- How `blocks` and `encoded_tx` are stored: 'raw', or 'zstd' when TRON_PROOF_COMPRESS was on at
+-- save time. Existing rows default to 'raw', so they load unchanged; the content hash is always
+-- over the decoded proof.
+alter table solver.tron_proofs
+    add column if not exists encoding text not null default 'raw';
+
+alter table solver.tron_proofs
+    drop constraint if exists tron_proofs_encoding_check;
+alter table solver.tron_proofs
+    add constraint tron_proofs_encoding_check check (encoding in ('raw', 'zstd'));
+
+alter table solver.tron_proofs_archive
+    add column if not exists encoding text not null default 'raw';
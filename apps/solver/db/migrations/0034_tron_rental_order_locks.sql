@@ -0,0 +1,10 @@
+-- Held while a job's energy rental order is being placed, so overlapping ticks on the same job
+-- don't both order (and pay for) energy. Expires after TRON_RENTAL_ORDER_LOCK_SECS in case the
+-- holder dies mid-order.
+create table if not exists solver.tron_rental_order_locks (
+    job_id bigint primary key references solver.jobs(job_id) on delete cascade,
+    -- Per acquisition, so a holder only ever releases its own lock.
+    token text not null,
+    locked_by text not null,
+    locked_until timestamptz not null
+);
@@ -0,0 +1,12 @@
+-- Signed Tron txs and energy rentals cascade-delete with their job, so archival used to drop them.
+-- Keep them next to the archived job instead (same rules as 0016: new hot columns go here too).
+
+create table if not exists solver.tron_signed_txs_archive (
+    like solver.tron_signed_txs including defaults including constraints including indexes,
+    archived_at timestamptz not null default now()
+);
+
+create table if not exists solver.tron_rentals_archive (
+    like solver.tron_rentals including defaults including constraints including indexes,
+    archived_at timestamptz not null default now()
+);
@@ -0,0 +1,19 @@
+-- Intent ids are only unique within a hub pool (see 0026), so the per-intent side tables key on
+-- (pool_id, intent_id) like `solver.jobs`. Existing rows belong to the primary pool.
+alter table solver.job_unclaims
+    add column if not exists pool_id text not null default 'default';
+alter table solver.job_unclaims drop constraint if exists job_unclaims_pkey;
+alter table solver.job_unclaims
+    add constraint job_unclaims_pkey primary key (pool_id, intent_id);
+
+alter table solver.dry_run_decisions
+    add column if not exists pool_id text not null default 'default';
+alter table solver.dry_run_decisions drop constraint if exists dry_run_decisions_pkey;
+alter table solver.dry_run_decisions
+    add constraint dry_run_decisions_pkey primary key (pool_id, intent_id);
+
+alter table solver.intent_emulations
+    add column if not exists pool_id text not null default 'default';
+alter table solver.intent_emulations drop constraint if exists intent_emulations_pkey;
+alter table solver.intent_emulations
+    add constraint intent_emulations_pkey primary key (pool_id, intent_id);
@@ -0,0 +1,8 @@
+-- Consecutive hub userop submissions the bundler rejected in account validation (AA1x/AA20/
+-- AA22-AA24), for HUB_USEROP_VALIDATION_FATAL_ATTEMPTS. Counted apart from `attempts` so unrelated
+-- retries earlier in the job's life don't count towards the limit; reset when a userop is
+-- accepted or fails for another reason.
+alter table solver.jobs
+    add column if not exists hub_structural_failures integer not null default 0;
+alter table solver.jobs_archive
+    add column if not exists hub_structural_failures integer not null default 0;
@@ -0,0 +1,6 @@
+-- Times a job's Tron proof was discarded and rebuilt after proveIntentFill rejected its blocks,
+-- for TRON_PROOF_MAX_REBUILDS.
+alter table solver.jobs
+    add column if not exists proof_rebuilds integer not null default 0;
+alter table solver.jobs_archive
+    add column if not exists proof_rebuilds integer not null default 0;
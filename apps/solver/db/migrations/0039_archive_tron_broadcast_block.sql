@@ -0,0 +1,3 @@
+-- Keep `tron_broadcast_block` (0031) when terminal jobs move to the archive.
+alter table solver.jobs_archive
+    add column if not exists tron_broadcast_block bigint null;
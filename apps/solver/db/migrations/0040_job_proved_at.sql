@@ -0,0 +1,8 @@
+-- When a job reached `proved`, so the operator summary counts fills by when they were proved
+-- rather than by their last update. Also keeps `est_profit_usd` (0032) in the archive.
+alter table solver.jobs
+    add column if not exists proved_at timestamptz null;
+alter table solver.jobs_archive
+    add column if not exists proved_at timestamptz null;
+alter table solver.jobs_archive
+    add column if not exists est_profit_usd double precision null;
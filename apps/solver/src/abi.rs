@@ -30,6 +30,22 @@ pub fn encode_trc20_balance_of(owner: Address) -> Vec<u8> {
     out
 }
 
+pub fn encode_trc20_approve(spender: Address, amount: U256) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + 32 + 32);
+    out.extend_from_slice(&selector("approve(address,uint256)"));
+    out.extend_from_slice(&encode_address(spender));
+    out.extend_from_slice(&encode_u256(amount));
+    out
+}
+
+pub fn encode_trc20_allowance(owner: Address, spender: Address) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + 32 + 32);
+    out.extend_from_slice(&selector("allowance(address,address)"));
+    out.extend_from_slice(&encode_address(owner));
+    out.extend_from_slice(&encode_address(spender));
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,4 +82,26 @@ mod tests {
         assert_eq!(&data[4..4 + 12], &[0u8; 12]);
         assert_eq!(&data[4 + 12..4 + 32], owner.as_slice());
     }
+
+    #[test]
+    fn encode_trc20_approve_and_allowance_layout() {
+        let owner: Address = "0x00000000000000000000000000000000000000bb"
+            .parse()
+            .unwrap();
+        let spender: Address = "0x00000000000000000000000000000000000000cc"
+            .parse()
+            .unwrap();
+
+        let data = encode_trc20_approve(spender, U256::from(1234u64));
+        assert_eq!(data.len(), 4 + 32 + 32);
+        assert_eq!(&data[..4], &[0x09, 0x5e, 0xa7, 0xb3]);
+        assert_eq!(&data[4 + 12..4 + 32], spender.as_slice());
+        assert_eq!(&data[4 + 32 + 30..], &[0x04, 0xD2]);
+
+        let data = encode_trc20_allowance(owner, spender);
+        assert_eq!(data.len(), 4 + 32 + 32);
+        assert_eq!(&data[..4], &[0xdd, 0x62, 0xed, 0x3e]);
+        assert_eq!(&data[4 + 12..4 + 32], owner.as_slice());
+        assert_eq!(&data[4 + 32 + 12..], spender.as_slice());
+    }
 }
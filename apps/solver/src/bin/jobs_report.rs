@@ -178,7 +178,7 @@ async fn print_job_detail(pool: &PgPool, job_id: i64) -> Result<()> {
     }
 
     let tx_rows = sqlx::query(
-        "select step, encode(txid, 'hex') as txid_hex, fee_limit_sun, energy_required, tx_size_bytes, updated_at::text as updated_at \
+        "select step, encode(txid, 'hex') as txid_hex, fee_limit_sun, fee_limit_estimate_sun, energy_required, tx_size_bytes, updated_at::text as updated_at \
          from solver.tron_signed_txs \
          where job_id = $1 \
          order by step asc",
@@ -192,12 +192,19 @@ async fn print_job_detail(pool: &PgPool, job_id: i64) -> Result<()> {
         let step: String = r.try_get("step")?;
         let txid_hex: String = r.try_get("txid_hex")?;
         let fee_limit_sun: Option<i64> = r.try_get("fee_limit_sun")?;
+        let fee_limit_estimate_sun: Option<i64> = r.try_get("fee_limit_estimate_sun")?;
         let energy_required: Option<i64> = r.try_get("energy_required")?;
         let tx_size_bytes: Option<i64> = r.try_get("tx_size_bytes")?;
         let updated_at: String = r.try_get("updated_at")?;
         println!(
-            "  step={} txid={} fee_limit_sun={:?} energy_required={:?} tx_size_bytes={:?} updated_at={}",
-            step, txid_hex, fee_limit_sun, energy_required, tx_size_bytes, updated_at
+            "  step={} txid={} fee_limit_sun={:?} fee_limit_estimate_sun={:?} energy_required={:?} tx_size_bytes={:?} updated_at={}",
+            step,
+            txid_hex,
+            fee_limit_sun,
+            fee_limit_estimate_sun,
+            energy_required,
+            tx_size_bytes,
+            updated_at
         );
     }
 
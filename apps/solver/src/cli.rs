@@ -0,0 +1,564 @@
+use crate::{
+    db::SolverDb,
+    indexer::IndexerClient,
+    metrics::SolverTelemetry,
+    tron_backend::{EmulationRevertError, TronBackend},
+    types::{IntentType, JobState, parse_hex_bytes},
+};
+use anyhow::{Context, Result};
+
+/// `solver requeue <intent_id> [--to-state <state>] [--pool <pool_id>] [--db-url <url>]`
+pub async fn requeue(args: &[String]) -> Result<()> {
+    let mut intent_id: Option<[u8; 32]> = None;
+    let mut to_state: Option<JobState> = None;
+    let mut pool_id: Option<String> = None;
+    let mut db_url: Option<String> = None;
+
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--to-state" => {
+                i += 1;
+                let v = args.get(i).context("missing value for --to-state")?;
+                to_state = Some(JobState::parse(v).context("parse --to-state")?);
+            }
+            "--pool" => {
+                i += 1;
+                let v = args.get(i).context("missing value for --pool")?;
+                pool_id = Some(v.clone());
+            }
+            "--db-url" => {
+                i += 1;
+                let v = args.get(i).context("missing value for --db-url")?;
+                db_url = Some(v.clone());
+            }
+            "--help" | "-h" => {
+                print_requeue_help();
+                return Ok(());
+            }
+            other if other.starts_with("--") => anyhow::bail!("unknown arg: {other}"),
+            other => {
+                if intent_id.is_some() {
+                    anyhow::bail!("unexpected extra arg: {other}");
+                }
+                intent_id = Some(parse_intent_id(other)?);
+            }
+        }
+        i += 1;
+    }
+
+    let Some(intent_id) = intent_id else {
+        print_requeue_help();
+        anyhow::bail!("missing <intent_id>");
+    };
+    let db_url = db_url
+        .or_else(|| std::env::var("SOLVER_DB_URL").ok())
+        .context("missing db url: pass --db-url or set SOLVER_DB_URL")?;
+
+    let db = SolverDb::connect(&db_url, 1).await?;
+    let pool_id = pool_id.unwrap_or_else(default_pool_id);
+    let requeued = db.requeue_job(&pool_id, intent_id, to_state).await?;
+    println!(
+        "requeued job_id={} pool_id={pool_id} intent_id=0x{}: {} -> {}",
+        requeued.job_id,
+        hex::encode(intent_id),
+        requeued.from_state.as_db_str(),
+        requeued.to_state.as_db_str()
+    );
+    Ok(())
+}
+
+fn print_requeue_help() {
+    println!("solver requeue <intent_id>");
+    println!("  --to-state <STATE>  Target state (default: current, or progress if failed_fatal)");
+    println!("  --pool <POOL_ID>    Hub pool id (fallback: HUB_POOL_ID env)");
+    println!("  --db-url <URL>      Postgres URL (fallback: SOLVER_DB_URL env)");
+}
+
+/// `solver inspect <intent_id> [--pool <pool_id>] [--db-url <url>]`
+pub async fn inspect(args: &[String]) -> Result<()> {
+    let mut intent_id: Option<[u8; 32]> = None;
+    let mut pool_id: Option<String> = None;
+    let mut db_url: Option<String> = None;
+
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--pool" => {
+                i += 1;
+                let v = args.get(i).context("missing value for --pool")?;
+                pool_id = Some(v.clone());
+            }
+            "--db-url" => {
+                i += 1;
+                let v = args.get(i).context("missing value for --db-url")?;
+                db_url = Some(v.clone());
+            }
+            "--help" | "-h" => {
+                print_inspect_help();
+                return Ok(());
+            }
+            other if other.starts_with("--") => anyhow::bail!("unknown arg: {other}"),
+            other => {
+                if intent_id.is_some() {
+                    anyhow::bail!("unexpected extra arg: {other}");
+                }
+                intent_id = Some(parse_intent_id(other)?);
+            }
+        }
+        i += 1;
+    }
+
+    let Some(intent_id) = intent_id else {
+        print_inspect_help();
+        anyhow::bail!("missing <intent_id>");
+    };
+    let db_url = db_url
+        .or_else(|| std::env::var("SOLVER_DB_URL").ok())
+        .context("missing db url: pass --db-url or set SOLVER_DB_URL")?;
+
+    let db = SolverDb::connect(&db_url, 1).await?;
+    let pool_id = pool_id.unwrap_or_else(default_pool_id);
+    let events = db.job_timeline(&pool_id, intent_id).await?;
+    let out = serde_json::json!({
+        "pool_id": pool_id,
+        "intent_id": format!("0x{}", hex::encode(intent_id)),
+        "events": events,
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&out).context("serialize job timeline")?
+    );
+    Ok(())
+}
+
+fn print_inspect_help() {
+    println!("solver inspect <intent_id>");
+    println!("  Prints the recorded lifecycle of the intent's job as JSON, oldest event first.");
+    println!("  --pool <POOL_ID>    Hub pool id (fallback: HUB_POOL_ID env)");
+    println!("  --db-url <URL>      Postgres URL (fallback: SOLVER_DB_URL env)");
+}
+
+/// `solver breakers [--db-url <url>]`
+pub async fn breakers(args: &[String]) -> Result<()> {
+    let mut db_url: Option<String> = None;
+
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db-url" => {
+                i += 1;
+                let v = args.get(i).context("missing value for --db-url")?;
+                db_url = Some(v.clone());
+            }
+            "--help" | "-h" => {
+                print_breakers_help();
+                return Ok(());
+            }
+            other => anyhow::bail!("unknown arg: {other}"),
+        }
+        i += 1;
+    }
+
+    let db_url = db_url
+        .or_else(|| std::env::var("SOLVER_DB_URL").ok())
+        .context("missing db url: pass --db-url or set SOLVER_DB_URL")?;
+
+    let db = SolverDb::connect(&db_url, 1).await?;
+    let rows = db.list_active_breakers().await?;
+    if rows.is_empty() {
+        println!("no active breakers");
+        return Ok(());
+    }
+    for b in rows {
+        let selector = b
+            .selector
+            .map_or_else(|| "none".to_string(), |s| format!("0x{}", hex::encode(s)));
+        println!(
+            "{:#x} {:<10} failures={:<3} tripped_at={} cooldown_until={} last_error={}",
+            b.contract,
+            selector,
+            b.failure_count,
+            b.tripped_at,
+            b.cooldown_until,
+            b.last_error.as_deref().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+fn print_breakers_help() {
+    println!("solver breakers");
+    println!("  Lists circuit breakers still in cooldown, longest-blocked first.");
+    println!("  --db-url <URL>      Postgres URL (fallback: SOLVER_DB_URL env)");
+}
+
+/// `solver breaker reset <contract> <selector|none> [--db-url <url>]`
+pub async fn breaker(args: &[String]) -> Result<()> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut db_url: Option<String> = None;
+
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db-url" => {
+                i += 1;
+                let v = args.get(i).context("missing value for --db-url")?;
+                db_url = Some(v.clone());
+            }
+            "--help" | "-h" => {
+                print_breaker_help();
+                return Ok(());
+            }
+            other if other.starts_with("--") => anyhow::bail!("unknown arg: {other}"),
+            other => positional.push(other),
+        }
+        i += 1;
+    }
+
+    let ["reset", contract, selector] = positional.as_slice() else {
+        print_breaker_help();
+        anyhow::bail!("expected: reset <contract> <selector|none>");
+    };
+    let contract = crate::types::parse_address(contract).context("parse contract")?;
+    let selector = parse_selector(selector)?;
+    let db_url = db_url
+        .or_else(|| std::env::var("SOLVER_DB_URL").ok())
+        .context("missing db url: pass --db-url or set SOLVER_DB_URL")?;
+
+    let db = SolverDb::connect(&db_url, 1).await?;
+    let selector_str =
+        selector.map_or_else(|| "none".to_string(), |s| format!("0x{}", hex::encode(s)));
+    if db.breaker_reset(contract, selector).await? {
+        println!("reset breaker contract={contract:#x} selector={selector_str}");
+    } else {
+        println!("no breaker for contract={contract:#x} selector={selector_str}");
+    }
+    Ok(())
+}
+
+fn print_breaker_help() {
+    println!("solver breaker reset <contract> <selector|none>");
+    println!(
+        "  Clears a circuit breaker (and its failure count). Use `none` for a contract-wide one."
+    );
+    println!("  --db-url <URL>      Postgres URL (fallback: SOLVER_DB_URL env)");
+}
+
+/// `solver emulate <intent_id>`
+pub async fn emulate(args: &[String]) -> Result<()> {
+    let mut intent_id: Option<[u8; 32]> = None;
+    for arg in args {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                print_emulate_help();
+                return Ok(());
+            }
+            other if other.starts_with("--") => anyhow::bail!("unknown arg: {other}"),
+            other => {
+                if intent_id.is_some() {
+                    anyhow::bail!("unexpected extra arg: {other}");
+                }
+                intent_id = Some(parse_intent_id(other)?);
+            }
+        }
+    }
+    let Some(intent_id) = intent_id else {
+        print_emulate_help();
+        anyhow::bail!("missing <intent_id>");
+    };
+
+    let cfg = crate::config::load_config()?;
+    let telemetry = SolverTelemetry::new();
+    let indexer = IndexerClient::new(
+        cfg.indexer.base_url.clone(),
+        cfg.indexer.timeout,
+        telemetry.clone(),
+    );
+    let id_hex = format!("0x{}", hex::encode(intent_id));
+    let row = indexer
+        .fetch_intent(&id_hex)
+        .await?
+        .with_context(|| format!("intent {id_hex} not found in indexer"))?;
+    let ty = IntentType::from_i16(row.intent_type)?;
+    let specs = parse_hex_bytes(&row.intent_specs).context("parse intent_specs")?;
+    println!("intent_id={id_hex} intent_type={ty:?}");
+
+    let hub = crate::runner::build_hub_client(&cfg.hub, &telemetry).await?;
+    let tron = TronBackend::new(cfg.tron.clone(), cfg.jobs.clone(), telemetry);
+    match tron.emulate_intent(&hub, ty, &specs).await {
+        Ok(Some(energy)) => println!("ok energy_required={energy}"),
+        Ok(None) => println!("intent type {ty:?} is not emulated"),
+        Err(err) => {
+            let Some(revert) = err.downcast_ref::<EmulationRevertError>() else {
+                return Err(err.context("emulation failed"));
+            };
+            println!("reverted code={}", revert.code);
+            println!(
+                "  node_message={}",
+                String::from_utf8_lossy(&revert.message)
+            );
+            println!(
+                "  revert_data=0x{}",
+                revert.data.as_deref().map(hex::encode).unwrap_or_default()
+            );
+            println!(
+                "  selector={}",
+                revert
+                    .revert
+                    .selector
+                    .map_or_else(|| "-".to_string(), |s| format!("0x{}", hex::encode(s)))
+            );
+            println!(
+                "  reason={}",
+                revert.revert.decoded_message.as_deref().unwrap_or("-")
+            );
+            println!("  deterministic={}", revert.revert.is_deterministic());
+        }
+    }
+    Ok(())
+}
+
+fn print_emulate_help() {
+    println!("solver emulate <intent_id>");
+    println!("  Emulates the Tron fill for an intent (primary pool) and prints the result.");
+    println!("  Uses the solver's regular env config (indexer, hub RPC, Tron gRPC and keys).");
+}
+
+/// `solver check`
+pub async fn check(args: &[String]) -> Result<()> {
+    for arg in args {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                print_check_help();
+                return Ok(());
+            }
+            other => anyhow::bail!("unknown arg: {other}"),
+        }
+    }
+
+    let mut checks = Checks::default();
+    let cfg = match crate::config::load_config() {
+        Ok(cfg) => {
+            checks.record("config", Ok("loaded".to_string()));
+            cfg
+        }
+        Err(err) => {
+            checks.record("config", Err(err));
+            return checks.finish();
+        }
+    };
+    let problems = cfg.intent_type_misconfigurations();
+    checks.record(
+        "config intent types",
+        if problems.is_empty() {
+            Ok(format!("{:?}", cfg.policy.enabled_intent_types))
+        } else {
+            Err(anyhow::anyhow!(problems.join("; ")))
+        },
+    );
+    let telemetry = SolverTelemetry::new();
+
+    let db = async {
+        let db = SolverDb::connect(&cfg.db_url, 1).await?;
+        let pending = db.pending_migrations().await?;
+        Ok(if pending.is_empty() {
+            "connected; schema up to date".to_string()
+        } else {
+            format!("connected; would apply migrations {pending:?}")
+        })
+    }
+    .await;
+    checks.record("db", db);
+
+    for pool in cfg.pools() {
+        let id = pool.hub.pool_id.clone();
+        let indexer = IndexerClient::new(
+            pool.indexer.base_url.clone(),
+            pool.indexer.timeout,
+            telemetry.clone(),
+        );
+        let res = async {
+            indexer.health().await?;
+            Ok(match indexer.latest_indexed_pool_block().await? {
+                Some(indexed) => match indexed.heads {
+                    Some(heads) => format!(
+                        "healthy; indexed through block {} (confirmed head {}, head {})",
+                        indexed.block, heads.confirmed_head_block, heads.head_block
+                    ),
+                    None => format!("healthy; indexed through block {}", indexed.block),
+                },
+                None => "healthy; no pool events indexed yet".to_string(),
+            })
+        }
+        .await;
+        checks.record(&format!("indexer [{id}]"), res);
+
+        let res = async {
+            let hub = crate::runner::build_hub_client(&pool.hub, &telemetry).await?;
+            let head = hub.hub_block_number().await?;
+            let deposit = hub.pool_claim_deposit().await?;
+            if deposit != alloy::primitives::U256::from(pool.hub.claim_deposit) {
+                anyhow::bail!(
+                    "HUB_CLAIM_DEPOSIT={} but pool INTENT_CLAIM_DEPOSIT={deposit}",
+                    pool.hub.claim_deposit
+                );
+            }
+            Ok(format!(
+                "head block {head}; solver {}",
+                hub.solver_address()
+            ))
+        }
+        .await;
+        checks.record(&format!("hub rpc [{id}]"), res);
+        if let Some(remote) = &pool.hub.remote_signer {
+            let res = check_reachable(&remote.url, remote.timeout).await;
+            checks.record(&format!("hub remote signer [{id}]"), res);
+        }
+
+        for url in &pool.hub.bundler_urls {
+            let res = check_bundler(url, pool.hub.entrypoint, pool.indexer.timeout).await;
+            checks.record(
+                &format!("bundler [{id}] {}", crate::config::redact_url(url)),
+                res,
+            );
+        }
+        for pm in &pool.hub.paymasters {
+            let res = check_reachable(&pm.url, pool.indexer.timeout).await;
+            checks.record(
+                &format!("paymaster [{id}] {}", crate::config::redact_url(&pm.url)),
+                res,
+            );
+        }
+    }
+
+    match cfg.tron.mode {
+        crate::config::TronMode::Mock => checks.record("tron", Ok("mock mode".to_string())),
+        crate::config::TronMode::Grpc => {
+            let tron = TronBackend::new(cfg.tron.clone(), cfg.jobs.clone(), telemetry);
+            let res = tron.ping().await.map(|()| "serving blocks".to_string());
+            checks.record("tron grpc", res);
+            if let Some(network) = cfg.tron.expected_network {
+                let res = tron.verify_network().await.map(|()| format!("{network:?}"));
+                checks.record("tron network", res);
+            }
+        }
+    }
+    if let Some(remote) = &cfg.tron.remote_signer {
+        let res = check_reachable(&remote.url, remote.timeout).await;
+        checks.record("tron remote signer", res);
+    }
+    for p in &cfg.tron.energy_rental_providers {
+        let timeout = std::time::Duration::from_secs(10);
+        let mut res = check_reachable(&p.url, timeout).await;
+        if res.is_ok()
+            && let Some(q) = &p.quote
+        {
+            res = check_reachable(&q.url, timeout).await;
+        }
+        checks.record(&format!("rental provider {}", p.name), res);
+    }
+
+    checks.finish()
+}
+
+fn print_check_help() {
+    println!("solver check");
+    println!("  Loads the env config and checks the DB (pending migrations are listed, not run),");
+    println!("  each pool's indexer, hub RPC, bundlers and paymasters, Tron gRPC and rental");
+    println!("  providers. Exits non-zero if any check fails.");
+}
+
+#[derive(Default)]
+struct Checks {
+    failed: usize,
+}
+
+impl Checks {
+    fn record(&mut self, name: &str, res: Result<String>) {
+        match res {
+            Ok(detail) => println!("PASS  {name:<40} {detail}"),
+            Err(err) => {
+                self.failed += 1;
+                println!("FAIL  {name:<40} {err:#}");
+            }
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        if self.failed > 0 {
+            anyhow::bail!("{} check(s) failed", self.failed);
+        }
+        Ok(())
+    }
+}
+
+/// Any HTTP response counts: the endpoint is up, even if it wants a different request.
+async fn check_reachable(url: &str, timeout: std::time::Duration) -> Result<String> {
+    let http = reqwest::Client::builder().timeout(timeout).build()?;
+    let resp = http.get(url).send().await.context("request failed")?;
+    Ok(format!("reachable (HTTP {})", resp.status().as_u16()))
+}
+
+/// Asks the bundler for `eth_supportedEntryPoints` and checks ours is among them.
+async fn check_bundler(
+    url: &str,
+    entrypoint: Option<alloy::primitives::Address>,
+    timeout: std::time::Duration,
+) -> Result<String> {
+    let http = reqwest::Client::builder().timeout(timeout).build()?;
+    let body: serde_json::Value = http
+        .post(url)
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_supportedEntryPoints",
+            "params": [],
+        }))
+        .send()
+        .await
+        .context("request failed")?
+        .json()
+        .await
+        .context("decode response")?;
+    let supported: Vec<alloy::primitives::Address> = body
+        .get("result")
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .context("decode eth_supportedEntryPoints")?
+        .with_context(|| format!("no result: {body}"))?;
+    if let Some(ep) = entrypoint
+        && !supported.contains(&ep)
+    {
+        anyhow::bail!("entrypoint {ep} not supported (bundler supports {supported:?})");
+    }
+    Ok(format!("supports {} entrypoint(s)", supported.len()))
+}
+
+fn parse_selector(s: &str) -> Result<Option<[u8; 4]>> {
+    if s.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    let bytes = crate::types::parse_hex_bytes(s).context("parse selector")?;
+    let selector = <[u8; 4]>::try_from(bytes.as_slice())
+        .map_err(|_| anyhow::anyhow!("selector must be 4 bytes"))?;
+    Ok(Some(selector))
+}
+
+/// Intent ids are only unique per hub pool; commands default to the primary pool's id.
+fn default_pool_id() -> String {
+    std::env::var("HUB_POOL_ID")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "default".to_string())
+}
+
+fn parse_intent_id(s: &str) -> Result<[u8; 32]> {
+    let bytes = crate::types::parse_hex_bytes(s).context("parse intent_id")?;
+    bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("intent_id must be 32 bytes"))
+}
@@ -3,10 +3,18 @@ use anyhow::Result;
 mod env;
 mod load;
 mod parse;
+mod redact;
 mod types;
+mod validate;
 
+pub use redact::redact_url;
 pub use types::*;
 
 pub fn load_config() -> Result<AppConfig> {
     load::load_config()
 }
+
+/// Re-reads the config for a live reload (SIGHUP); see `Solver::reload_policy_config`.
+pub fn reload_config() -> Result<AppConfig> {
+    load::reload_config()
+}
@@ -11,6 +11,13 @@ pub(super) struct Env {
 
     pub indexer_max_head_lag_blocks: u64,
 
+    pub indexer_lag_warn_interval_secs: u64,
+
+    /// Extra `/health` attempts before a pool's discovery is given up for the tick.
+    pub indexer_health_retries: u32,
+
+    pub indexer_health_retry_delay_ms: u64,
+
     pub hub_rpc_url: String,
 
     pub hub_chain_id: Option<u64>,
@@ -21,6 +28,16 @@ pub(super) struct Env {
     #[serde(default)]
     pub hub_untron_v3_address: String,
 
+    /// USDT deposit locked per claim; must match the pool's INTENT_CLAIM_DEPOSIT.
+    pub hub_claim_deposit: u64,
+
+    /// Label stored on jobs from the primary pool (`solver.jobs.pool_id`).
+    pub hub_pool_id: String,
+
+    /// Additional pool deployments served by this process, as a JSON array (see
+    /// `parse_extra_pools_json`). Unset fields inherit the primary hub settings.
+    pub hub_extra_pools_json: String,
+
     #[serde(default)]
     pub hub_tx_mode: String,
 
@@ -44,12 +61,67 @@ pub(super) struct Env {
 
     pub hub_signer_private_key_hex: String,
 
+    /// Signing service for a hub key kept out of process (see `aa::RemoteHubSigner`).
+    #[serde(default)]
+    pub hub_remote_signer_url: String,
+
+    /// Hub signer address signed for by `HUB_REMOTE_SIGNER_URL` (replaces
+    /// `HUB_SIGNER_PRIVATE_KEY_HEX`).
+    #[serde(default)]
+    pub hub_remote_signer_address: String,
+
+    pub hub_remote_signer_timeout_ms: u64,
+
+    /// Bearer token sent with every `HUB_REMOTE_SIGNER_URL` request (empty = no auth header).
+    #[serde(default)]
+    pub hub_remote_signer_auth_token: String,
+
+    /// Address that funds claim deposits (empty = the signer/Safe pays them itself).
+    #[serde(default)]
+    pub hub_deposit_funder_address: String,
+    /// Optional key for `HUB_DEPOSIT_FUNDER_ADDRESS`, used only to approve the solver.
+    #[serde(default)]
+    pub hub_deposit_funder_private_key_hex: String,
+    /// Sign the funder's approvals through `HUB_REMOTE_SIGNER_URL` instead of
+    /// `HUB_DEPOSIT_FUNDER_PRIVATE_KEY_HEX`.
+    #[serde(default)]
+    pub hub_deposit_funder_remote_signer: bool,
+
     #[serde(default)]
     pub hub_bundler_urls: String,
 
     #[serde(default)]
     pub hub_paymasters_json: String,
 
+    /// Paymaster freeze: failures within the window before a paymaster is skipped.
+    pub hub_paymaster_fail_threshold: u32,
+    pub hub_paymaster_fail_window_secs: u64,
+    pub hub_paymaster_freeze_secs: u64,
+    /// If true, self-pay userops when no paymaster can sponsor them (Safe must hold ETH/deposit).
+    pub hub_paymaster_self_funded_fallback: bool,
+    /// Warn when a paymaster's EntryPoint deposit drops below this many wei (empty/0 = never).
+    #[serde(default)]
+    pub hub_paymaster_deposit_min_wei: String,
+    /// How often to read paymaster EntryPoint deposits (0 = disabled).
+    pub hub_paymaster_deposit_check_interval_secs: u64,
+    /// How long pool/V3 contract address lookups are cached (0 = no caching).
+    pub hub_address_cache_ttl_secs: u64,
+    /// Dry-run claim + prove via `eth_simulateV1` before claiming (see `HubConfig`).
+    pub hub_simulate_before_claim: bool,
+    /// Confirmations an EOA claim/prove needs before the job advances (1 = first receipt).
+    pub hub_min_confirmations: u64,
+
+    /// `self` (default) or `delegated_relayer`.
+    pub hub_prove_mode: String,
+    /// Relayer endpoint that submits `proveIntentFill` in `delegated_relayer` mode.
+    pub hub_prove_relayer_url: String,
+    pub hub_prove_relayer_timeout_ms: u64,
+    pub hub_prove_relayer_resubmit_secs: u64,
+    /// Most `proveIntentFill` calls packed into one Safe4337 userop (1 = no batching).
+    pub hub_prove_batch_max: usize,
+    /// Safe `MultiSendCallOnly` deployment used for batched proves.
+    pub hub_safe_multisend_address: String,
+
     #[serde(default)]
     pub tron_mode: String,
 
@@ -57,15 +129,43 @@ pub(super) struct Env {
 
     pub tron_api_key: Option<String>,
 
+    /// Per-attempt Tron gRPC deadline in ms (0 = none).
+    pub tron_grpc_timeout_ms: u64,
+    /// Extra attempts after a Tron gRPC timeout or transport-level failure.
+    pub tron_grpc_max_retries: u32,
+    /// Cooldown after a failed Tron health ping before Tron-stage jobs resume (0 = no health gate).
+    pub tron_health_gate_cooldown_secs: u64,
+
     pub tron_private_key_hex: String,
 
     #[serde(default)]
     pub tron_private_keys_hex_csv: String,
 
+    /// Signing service for keys kept out of process (see `tron::RemoteTronSigner`).
+    #[serde(default)]
+    pub tron_remote_signer_url: String,
+
+    /// Tron addresses (base58 or EVM hex) signed for by `TRON_REMOTE_SIGNER_URL`.
+    #[serde(default)]
+    pub tron_remote_signer_addresses_csv: String,
+
+    pub tron_remote_signer_timeout_ms: u64,
+
+    /// `<name>: <value>` header sent with every `TRON_REMOTE_SIGNER_URL` request, e.g.
+    /// `Authorization: Bearer <token>` (empty = no auth header).
+    #[serde(default)]
+    pub tron_remote_signer_auth_header: String,
+
     pub tron_controller_address: String,
 
     #[serde(default)]
     pub tron_mock_reader_address: String,
+    /// Proof format the hub's Tron tx reader expects (`v1`).
+    #[serde(default)]
+    pub tron_proof_format: String,
+    /// `mainnet`, `nile`, `shasta` or a 32-byte genesis block id (empty = unchecked).
+    #[serde(default)]
+    pub tron_expected_network: String,
 
     pub tron_block_lag: u64,
 
@@ -90,23 +190,107 @@ pub(super) struct Env {
     pub tron_rental_provider_fail_window_secs: i64,
     #[serde(default)]
     pub tron_rental_provider_freeze_secs: i64,
+    /// How long a job's rental order lock holds if its holder never releases it.
+    #[serde(default)]
+    pub tron_rental_order_lock_secs: u64,
+    /// Largest signed Tron tx the solver will broadcast, in bytes (0 disables the guard).
+    #[serde(default)]
+    pub tron_max_tx_size_bytes: u64,
 
     #[serde(default)]
     pub tron_resell_energy_headroom_ppm: u64,
 
+    #[serde(default)]
+    pub tron_rental_quote_ttl_secs: u64,
+    #[serde(default)]
+    pub tron_rental_quote_max_cost_trx: f64,
+    /// Global USD cap on a single energy rental order; 0 disables it.
+    #[serde(default)]
+    pub tron_rental_max_cost_usd: f64,
+
+    #[serde(default)]
+    pub tron_out_of_energy_cooldown_secs: u64,
+
+    #[serde(default)]
+    pub tron_sponsor_usdt_transfer_energy: bool,
+
+    #[serde(default)]
+    pub tron_key_daily_limit_sun: u64,
+
+    #[serde(default)]
+    pub tron_key_daily_limit_usdt: u64,
+
+    #[serde(default)]
+    pub tron_auto_approve_trigger_spend: bool,
+
+    /// `selector:arg_index` pairs, e.g. `0x12345678:1`; see `TronConfig::trigger_spend_selectors`.
+    #[serde(default)]
+    pub tron_trigger_spend_selectors: String,
+
+    #[serde(default)]
+    pub tron_auto_stake_enabled: bool,
+    #[serde(default)]
+    pub tron_auto_stake_target_energy_sun: u64,
+    #[serde(default)]
+    pub tron_auto_stake_target_bandwidth_sun: u64,
+    #[serde(default)]
+    pub tron_auto_stake_max_per_tx_sun: u64,
+    #[serde(default)]
+    pub tron_auto_stake_reserve_sun: u64,
+    #[serde(default)]
+    pub tron_auto_stake_interval_secs: u64,
+
     #[serde(default)]
     pub solver_tron_emulation_enabled: bool,
 
+    #[serde(default)]
+    pub tron_sender_check_enabled: bool,
+
+    #[serde(default)]
+    pub tron_fill_verification_enabled: bool,
+
     pub solver_tick_interval_secs: u64,
 
+    /// Job lease duration; should comfortably exceed the lease heartbeat (10s).
+    pub solver_lease_secs: u64,
+
+    /// Random +/- spread applied to job retry backoff, in percent (capped at 30).
+    pub solver_retry_jitter_pct: u64,
+
     pub tron_finality_blocks: u64,
 
+    /// Confirmations required on the tx block before proving (0 = just `tron_finality_blocks`).
+    /// A solidified block is accepted regardless.
+    #[serde(default)]
+    pub tron_proof_min_confirmations: u64,
+
+    /// Max serialized proof size in bytes (0 disables the guard).
+    #[serde(default)]
+    pub tron_proof_max_bytes: u64,
+
+    /// Stale-proof rebuilds after which a job fails instead of rebuilding again (0 disables).
+    pub tron_proof_max_rebuilds: u64,
+
     pub tron_tip_proof_resend_blocks: u64,
 
+    pub tron_inclusion_timeout_secs: u64,
+
+    pub tron_inclusion_poll_interval_ms: u64,
+
+    /// Blocks past the broadcast height a tron_sent tx may take to appear in a block before the
+    /// job goes back to re-broadcast (0 disables the cap).
+    pub tron_max_inclusion_scan_blocks: u64,
+
+    /// zstd-compress block blobs and the encoded tx when saving Tron proofs.
+    pub tron_proof_compress: bool,
+
     pub process_controller_max_events: u64,
 
     pub fill_max_claims: u64,
 
+    /// Open-intent backlog above which discovery switches to nearest-deadline-first; 0 disables.
+    pub solver_backlog_deadline_first_threshold: u64,
+
     #[serde(default)]
     pub solver_max_in_flight_jobs: u64,
     #[serde(default)]
@@ -122,11 +306,16 @@ pub(super) struct Env {
     pub solver_concurrency_trigger_smart_contract: u64,
     #[serde(default)]
     pub solver_concurrency_tron_broadcast: u64,
+    #[serde(default)]
+    pub solver_concurrency_tron_proof: u64,
 
     #[serde(default)]
     pub solver_consolidation_enabled: bool,
     #[serde(default)]
     pub solver_consolidation_max_pre_txs: u64,
+    /// Pre-txs broadcast per tick across all jobs (0 = unlimited).
+    #[serde(default)]
+    pub solver_max_pre_txs_per_tick: u64,
     #[serde(default)]
     pub solver_consolidation_max_total_trx_pull_sun: u64,
     #[serde(default)]
@@ -135,6 +324,8 @@ pub(super) struct Env {
     pub solver_consolidation_max_total_usdt_pull_amount: u64,
     #[serde(default)]
     pub solver_consolidation_max_per_tx_usdt_pull_amount: u64,
+    pub solver_proactive_consolidation: bool,
+    pub solver_proactive_consolidation_interval_secs: u64,
 
     #[serde(default)]
     pub solver_rate_limit_claims_per_minute_global: u64,
@@ -147,6 +338,10 @@ pub(super) struct Env {
     #[serde(default)]
     pub solver_rate_limit_claims_per_minute_trigger_smart_contract: u64,
 
+    /// Consecutive rejections in account validation (AA1x/AA20/AA22-AA24) after which a job's
+    /// userop fails the job; 0 retries forever.
+    pub hub_userop_validation_fatal_attempts: u64,
+
     #[serde(default)]
     pub solver_global_pause_fatal_threshold: u64,
     #[serde(default)]
@@ -160,6 +355,16 @@ pub(super) struct Env {
     #[serde(default)]
     pub solver_delegate_reservation_ttl_secs: u64,
 
+    #[serde(default)]
+    pub solver_job_abandon_after_secs: u64,
+
+    #[serde(default)]
+    pub solver_jobs_archive_after_days: u64,
+    #[serde(default)]
+    pub solver_jobs_archive_interval_secs: u64,
+    #[serde(default)]
+    pub solver_jobs_archive_batch_size: u64,
+
     pub controller_rebalance_threshold_usdt: String,
 
     pub controller_rebalance_keep_usdt: String,
@@ -172,6 +377,12 @@ pub(super) struct Env {
     #[serde(default)]
     pub solver_min_deadline_slack_secs: u64,
 
+    /// `timestamp` (default) or `block`: how the pool expresses intent deadlines.
+    pub solver_deadline_kind: String,
+
+    /// Deadline slack when `SOLVER_DEADLINE_KIND=block`.
+    pub solver_min_deadline_slack_blocks: u64,
+
     #[serde(default)]
     pub solver_min_profit_usd: f64,
 
@@ -187,6 +398,11 @@ pub(super) struct Env {
     #[serde(default)]
     pub solver_tron_fee_usd: f64,
 
+    /// `type=usd` pairs overriding `SOLVER_TRON_FEE_USD` per intent type (e.g.
+    /// `trx_transfer=0.3,trigger_smart_contract=4`).
+    #[serde(default)]
+    pub solver_tron_fee_usd_by_type: String,
+
     #[serde(default)]
     pub solver_tron_fee_history_lookback: u64,
 
@@ -202,6 +418,10 @@ pub(super) struct Env {
     #[serde(default)]
     pub solver_allowed_escrow_tokens_csv: String,
 
+    /// Hub escrow tokens the solver fills at all; empty allows any token.
+    #[serde(default)]
+    pub solver_escrow_token_allowlist_csv: String,
+
     #[serde(default)]
     pub solver_trigger_contract_allowlist_csv: String,
 
@@ -211,9 +431,26 @@ pub(super) struct Env {
     #[serde(default)]
     pub solver_trigger_selector_denylist_csv: String,
 
+    #[serde(default)]
+    pub solver_refund_beneficiary_allowlist_csv: String,
+
+    #[serde(default)]
+    pub solver_refund_beneficiary_denylist_csv: String,
+
     #[serde(default)]
     pub solver_trigger_allow_fallback_calls: bool,
 
+    /// JSON map: selector -> { max_energy, enabled }.
+    pub solver_trigger_selector_budgets_json: String,
+    /// If true, only selectors present in the budgets map are filled.
+    pub solver_trigger_selector_allowlist_mode: bool,
+
+    /// Tron address lists (base58 or hex), checked before claiming.
+    pub solver_tron_address_allowlist_csv: String,
+    pub solver_tron_address_denylist_csv: String,
+    pub solver_tron_contract_allowlist_csv: String,
+    pub solver_tron_contract_denylist_csv: String,
+
     #[serde(default)]
     pub solver_max_trx_transfer_sun: u64,
 
@@ -232,6 +469,23 @@ pub(super) struct Env {
     #[serde(default)]
     pub solver_max_trigger_calldata_len: u64,
 
+    #[serde(default)]
+    pub solver_max_inflight_claim_deposit: u64,
+
+    /// Token-bucket ceiling on claims across all intent types; 0 disables it.
+    #[serde(default)]
+    pub solver_max_claims_per_minute: u64,
+
+    /// Minimum intent age (secs since its creation block) before we attempt it; 0 disables it.
+    #[serde(default)]
+    pub solver_min_intent_age_secs: u64,
+
+    #[serde(default)]
+    pub solver_max_intent_value_usd: f64,
+
+    #[serde(default)]
+    pub solver_max_intent_value_usd_by_type: String,
+
     #[serde(default)]
     pub solver_trx_usd_override: Option<f64>,
 
@@ -252,6 +506,34 @@ pub(super) struct Env {
 
     #[serde(default)]
     pub solver_instance_id: String,
+
+    /// Log the full effective config (secrets redacted) once at startup.
+    pub solver_log_effective_config: bool,
+
+    /// Evaluate intents but never claim, fill or prove; record decisions instead.
+    pub solver_dry_run: bool,
+
+    /// `active` or `follower` (standby that never leases or claims until promoted).
+    pub solver_role: String,
+
+    /// Endpoint POSTed a JSON payload whenever a job fails fatally; empty disables it.
+    pub solver_fatal_webhook_url: String,
+
+    /// Optional HMAC-SHA256 key used to sign fatal webhook bodies.
+    pub solver_fatal_webhook_hmac_secret: String,
+
+    pub solver_fatal_webhook_timeout_ms: u64,
+
+    /// Seconds between operator summaries (fills, profit, skips, breakers, low keys); 0 disables.
+    pub solver_summary_interval_secs: u64,
+
+    /// Slack / Discord incoming webhook each summary is also POSTed to; empty only logs it.
+    pub solver_summary_webhook_url: String,
+
+    pub solver_summary_webhook_timeout_ms: u64,
+
+    /// Tron keys holding less liquid TRX (SUN) than this are listed in the summary.
+    pub solver_summary_key_reserve_sun: u64,
 }
 
 impl Default for Env {
@@ -261,8 +543,14 @@ impl Default for Env {
             indexer_api_base_url: String::new(),
             indexer_timeout_secs: 10,
             indexer_max_head_lag_blocks: 50,
+            indexer_lag_warn_interval_secs: 60,
+            indexer_health_retries: 2,
+            indexer_health_retry_delay_ms: 500,
             hub_rpc_url: String::new(),
             hub_chain_id: None,
+            hub_claim_deposit: 1_000_000,
+            hub_pool_id: "default".to_string(),
+            hub_extra_pools_json: String::new(),
             hub_pool_address: String::new(),
             hub_untron_v3_address: String::new(),
             hub_tx_mode: "eoa".to_string(),
@@ -273,15 +561,46 @@ impl Default for Env {
             hub_safe_singleton_address: String::new(),
             hub_safe_module_setup_address: String::new(),
             hub_signer_private_key_hex: String::new(),
+            hub_remote_signer_url: String::new(),
+            hub_remote_signer_address: String::new(),
+            hub_remote_signer_timeout_ms: 5_000,
+            hub_remote_signer_auth_token: String::new(),
+            hub_deposit_funder_address: String::new(),
+            hub_deposit_funder_private_key_hex: String::new(),
+            hub_deposit_funder_remote_signer: false,
             hub_bundler_urls: String::new(),
             hub_paymasters_json: String::new(),
+            hub_paymaster_fail_threshold: 3,
+            hub_paymaster_fail_window_secs: 60,
+            hub_paymaster_freeze_secs: 300,
+            hub_paymaster_self_funded_fallback: false,
+            hub_paymaster_deposit_min_wei: String::new(),
+            hub_paymaster_deposit_check_interval_secs: 300,
+            hub_address_cache_ttl_secs: 300,
+            hub_simulate_before_claim: false,
+            hub_min_confirmations: 1,
+            hub_prove_mode: String::new(),
+            hub_prove_relayer_url: String::new(),
+            hub_prove_relayer_timeout_ms: 5_000,
+            hub_prove_relayer_resubmit_secs: 300,
+            hub_prove_batch_max: 1,
+            hub_safe_multisend_address: String::new(),
             tron_mode: "grpc".to_string(),
             tron_grpc_url: String::new(),
             tron_api_key: None,
+            tron_grpc_timeout_ms: 10_000,
+            tron_grpc_max_retries: 1,
+            tron_health_gate_cooldown_secs: 30,
             tron_private_key_hex: String::new(),
             tron_private_keys_hex_csv: String::new(),
+            tron_remote_signer_url: String::new(),
+            tron_remote_signer_addresses_csv: String::new(),
+            tron_remote_signer_timeout_ms: 5_000,
+            tron_remote_signer_auth_header: String::new(),
             tron_controller_address: String::new(),
             tron_mock_reader_address: String::new(),
+            tron_proof_format: String::new(),
+            tron_expected_network: String::new(),
             tron_block_lag: 0,
             tron_fee_limit_cap_sun: 200_000_000,
             tron_fee_limit_headroom_ppm: 100_000,
@@ -291,13 +610,42 @@ impl Default for Env {
             tron_rental_provider_fail_threshold: 3,
             tron_rental_provider_fail_window_secs: 60,
             tron_rental_provider_freeze_secs: 300,
+            tron_rental_order_lock_secs: 300,
+            tron_max_tx_size_bytes: 0,
             tron_resell_energy_headroom_ppm: 50_000,
+            tron_rental_quote_ttl_secs: 30,
+            tron_rental_quote_max_cost_trx: 0.0,
+            tron_rental_max_cost_usd: 0.0,
+            tron_out_of_energy_cooldown_secs: 300,
+            tron_sponsor_usdt_transfer_energy: false,
+            tron_key_daily_limit_sun: 0,
+            tron_key_daily_limit_usdt: 0,
+            tron_auto_approve_trigger_spend: false,
+            tron_trigger_spend_selectors: String::new(),
+            tron_auto_stake_enabled: false,
+            tron_auto_stake_target_energy_sun: 0,
+            tron_auto_stake_target_bandwidth_sun: 0,
+            tron_auto_stake_max_per_tx_sun: 0,
+            tron_auto_stake_reserve_sun: 50_000_000,
+            tron_auto_stake_interval_secs: 600,
             solver_tron_emulation_enabled: true,
+            tron_sender_check_enabled: true,
+            tron_fill_verification_enabled: true,
             solver_tick_interval_secs: 5,
+            solver_lease_secs: 30,
+            solver_retry_jitter_pct: 25,
             tron_finality_blocks: 19,
+            tron_proof_min_confirmations: 0,
+            tron_proof_max_bytes: 0,
+            tron_proof_max_rebuilds: 3,
             tron_tip_proof_resend_blocks: 20,
+            tron_inclusion_timeout_secs: 60,
+            tron_inclusion_poll_interval_ms: 1000,
+            tron_max_inclusion_scan_blocks: 100,
+            tron_proof_compress: false,
             process_controller_max_events: 100,
             fill_max_claims: 50,
+            solver_backlog_deadline_first_threshold: 200,
             solver_max_in_flight_jobs: 50,
             solver_safe4337_max_claimed_unproved_jobs: 1,
             solver_concurrency_trx_transfer: 4,
@@ -305,49 +653,85 @@ impl Default for Env {
             solver_concurrency_delegate_resource: 1,
             solver_concurrency_trigger_smart_contract: 1,
             solver_concurrency_tron_broadcast: 1,
+            solver_concurrency_tron_proof: 1,
             solver_consolidation_enabled: false,
             solver_consolidation_max_pre_txs: 0,
+            solver_max_pre_txs_per_tick: 0,
             solver_consolidation_max_total_trx_pull_sun: 0,
             solver_consolidation_max_per_tx_trx_pull_sun: 0,
             solver_consolidation_max_total_usdt_pull_amount: 0,
             solver_consolidation_max_per_tx_usdt_pull_amount: 0,
+            solver_proactive_consolidation: false,
+            solver_proactive_consolidation_interval_secs: 600,
             solver_rate_limit_claims_per_minute_global: 0,
             solver_rate_limit_claims_per_minute_trx_transfer: 0,
             solver_rate_limit_claims_per_minute_usdt_transfer: 0,
             solver_rate_limit_claims_per_minute_delegate_resource: 0,
             solver_rate_limit_claims_per_minute_trigger_smart_contract: 0,
+            hub_userop_validation_fatal_attempts: 5,
             solver_global_pause_fatal_threshold: 0,
             solver_global_pause_window_secs: 300,
             solver_global_pause_duration_secs: 300,
             solver_breaker_mismatch_penalty: 2,
             solver_delegate_reservation_ttl_secs: 600,
+            solver_job_abandon_after_secs: 0,
+            solver_jobs_archive_after_days: 0,
+            solver_jobs_archive_interval_secs: 3600,
+            solver_jobs_archive_batch_size: 500,
             controller_rebalance_threshold_usdt: "0".to_string(),
             controller_rebalance_keep_usdt: "1".to_string(),
             pull_liquidity_ppm: 500_000,
 
             solver_enabled_intent_types: "trx_transfer,delegate_resource".to_string(),
             solver_min_deadline_slack_secs: 30,
+            solver_deadline_kind: "timestamp".to_string(),
+            solver_min_deadline_slack_blocks: 10,
             solver_instance_id: String::new(),
+            solver_log_effective_config: true,
+            solver_dry_run: false,
+            solver_role: "active".to_string(),
+            solver_fatal_webhook_url: String::new(),
+            solver_fatal_webhook_hmac_secret: String::new(),
+            solver_fatal_webhook_timeout_ms: 2_000,
+            solver_summary_interval_secs: 0,
+            solver_summary_webhook_url: String::new(),
+            solver_summary_webhook_timeout_ms: 2_000,
+            solver_summary_key_reserve_sun: 10_000_000,
             solver_min_profit_usd: 0.0,
             solver_hub_cost_usd: 0.0,
             solver_hub_cost_history_lookback: 50,
             solver_hub_cost_headroom_ppm: 200_000,
             solver_tron_fee_usd: 0.0,
+            solver_tron_fee_usd_by_type: String::new(),
             solver_tron_fee_history_lookback: 50,
             solver_tron_fee_headroom_ppm: 200_000,
             solver_capital_lock_ppm_per_day: 0,
             solver_require_priced_escrow: false,
             solver_allowed_escrow_tokens_csv: String::new(),
+            solver_escrow_token_allowlist_csv: String::new(),
             solver_trigger_contract_allowlist_csv: String::new(),
             solver_trigger_contract_denylist_csv: String::new(),
+            solver_refund_beneficiary_allowlist_csv: String::new(),
+            solver_refund_beneficiary_denylist_csv: String::new(),
             solver_trigger_selector_denylist_csv: "0x095ea7b3,0x39509351".to_string(),
             solver_trigger_allow_fallback_calls: false,
+            solver_trigger_selector_budgets_json: String::new(),
+            solver_trigger_selector_allowlist_mode: false,
+            solver_tron_address_allowlist_csv: String::new(),
+            solver_tron_address_denylist_csv: String::new(),
+            solver_tron_contract_allowlist_csv: String::new(),
+            solver_tron_contract_denylist_csv: String::new(),
             solver_max_trx_transfer_sun: 0,
             solver_max_usdt_transfer_amount: 0,
             solver_max_delegate_balance_sun: 0,
             solver_max_delegate_lock_period_secs: 0,
             solver_max_trigger_call_value_sun: 0,
             solver_max_trigger_calldata_len: 0,
+            solver_max_inflight_claim_deposit: 0,
+            solver_max_claims_per_minute: 0,
+            solver_min_intent_age_secs: 0,
+            solver_max_intent_value_usd: 0.0,
+            solver_max_intent_value_usd_by_type: String::new(),
             solver_trx_usd_override: None,
             solver_trx_usd_ttl_secs: 60,
             solver_trx_usd_url:
@@ -1,19 +1,42 @@
 use super::env::Env;
 use super::parse::{
-    opt_u64, parse_address, parse_addresses_csv, parse_csv, parse_hex_32, parse_hex_32_csv,
-    parse_hub_tx_mode, parse_intent_types, parse_optional_address, parse_paymasters_json,
-    parse_selectors_csv, parse_tron_energy_rental_apis_json, parse_tron_mode,
+    opt_u64, parse_address, parse_addresses_csv, parse_csv, parse_deadline_kind,
+    parse_deposit_funder, parse_hex_32, parse_hex_32_csv, parse_hub_tx_mode, parse_intent_types,
+    parse_optional_address, parse_paymasters_json, parse_prove_mode, parse_selectors_csv,
+    parse_solver_role, parse_trigger_selector_budgets_json, parse_trigger_spend_selectors_csv,
+    parse_tron_addresses_csv, parse_tron_energy_rental_apis_json, parse_tron_mode,
+    parse_tron_network, parse_tron_proof_format, parse_usd_by_intent_type_csv, parse_wei,
 };
 use super::{
-    AppConfig, HubConfig, HubTxMode, IndexerConfig, JobConfig, PolicyConfig, TronConfig, TronMode,
+    AppConfig, HubConfig, HubKey, HubRemoteSignerConfig, HubTxMode, IndexerConfig, JobConfig,
+    PolicyConfig, ProveMode, ProveRelayerConfig, SummaryConfig, TronConfig, TronKey, TronMode,
+    TronRemoteSignerConfig, WebhookConfig,
 };
 use aa::SafeDeterministicDeploymentConfig;
+use aa::paymaster::PaymasterFreezePolicy;
 use anyhow::{Context, Result};
 use std::time::Duration;
 
 pub(super) fn load_config() -> Result<AppConfig> {
     let env: Env = envy::from_env().context("load solver env config")?;
+    config_from_env(env)
+}
 
+/// Like [`load_config`], but `.env` values win over the process environment, so edits to `.env`
+/// take effect in a running process (the process environment still holds the startup values).
+pub(super) fn reload_config() -> Result<AppConfig> {
+    let mut vars: std::collections::HashMap<String, String> = std::env::vars().collect();
+    if let Ok(iter) = dotenvy::dotenv_iter() {
+        for item in iter {
+            let (key, value) = item.context("parse .env")?;
+            vars.insert(key, value);
+        }
+    }
+    let env: Env = envy::from_iter(vars).context("load solver env config")?;
+    config_from_env(env)
+}
+
+pub(super) fn config_from_env(env: Env) -> Result<AppConfig> {
     if env.solver_db_url.trim().is_empty() {
         anyhow::bail!("SOLVER_DB_URL must be set");
     }
@@ -50,13 +73,51 @@ pub(super) fn load_config() -> Result<AppConfig> {
         );
     }
 
-    if env.hub_signer_private_key_hex.trim().is_empty() {
-        anyhow::bail!("HUB_SIGNER_PRIVATE_KEY_HEX must be set");
-    }
-    let hub_signer_private_key = parse_hex_32(
-        "HUB_SIGNER_PRIVATE_KEY_HEX",
-        &env.hub_signer_private_key_hex,
+    let hub_remote_signer = if env.hub_remote_signer_url.trim().is_empty() {
+        None
+    } else {
+        Some(HubRemoteSignerConfig {
+            url: env.hub_remote_signer_url.trim().to_string(),
+            timeout: Duration::from_millis(env.hub_remote_signer_timeout_ms.max(1)),
+            auth_token: Some(env.hub_remote_signer_auth_token.trim().to_string())
+                .filter(|t| !t.is_empty()),
+        })
+    };
+    let hub_signer_key = match parse_optional_address(
+        "HUB_REMOTE_SIGNER_ADDRESS",
+        &env.hub_remote_signer_address,
+    )? {
+        Some(addr) => {
+            if hub_remote_signer.is_none() {
+                anyhow::bail!("HUB_REMOTE_SIGNER_ADDRESS requires HUB_REMOTE_SIGNER_URL");
+            }
+            if !env.hub_signer_private_key_hex.trim().is_empty() {
+                anyhow::bail!(
+                    "set only one of HUB_SIGNER_PRIVATE_KEY_HEX and HUB_REMOTE_SIGNER_ADDRESS"
+                );
+            }
+            HubKey::Remote(addr)
+        }
+        None => {
+            if env.hub_signer_private_key_hex.trim().is_empty() {
+                anyhow::bail!(
+                    "HUB_SIGNER_PRIVATE_KEY_HEX or HUB_REMOTE_SIGNER_ADDRESS must be set"
+                );
+            }
+            HubKey::Local(parse_hex_32(
+                "HUB_SIGNER_PRIVATE_KEY_HEX",
+                &env.hub_signer_private_key_hex,
+            )?)
+        }
+    };
+    let deposit_funder = parse_deposit_funder(
+        &env.hub_deposit_funder_address,
+        &env.hub_deposit_funder_private_key_hex,
+        env.hub_deposit_funder_remote_signer,
     )?;
+    if env.hub_deposit_funder_remote_signer && hub_remote_signer.is_none() {
+        anyhow::bail!("HUB_DEPOSIT_FUNDER_REMOTE_SIGNER requires HUB_REMOTE_SIGNER_URL");
+    }
 
     let (hub_entrypoint, hub_safe, hub_module, hub_safe_deployment, bundlers, paymasters) =
         if hub_tx_mode == HubTxMode::Safe4337 {
@@ -119,9 +180,10 @@ pub(super) fn load_config() -> Result<AppConfig> {
         }
         if env.tron_private_key_hex.trim().is_empty()
             && env.tron_private_keys_hex_csv.trim().is_empty()
+            && env.tron_remote_signer_addresses_csv.trim().is_empty()
         {
             anyhow::bail!(
-                "TRON_PRIVATE_KEY_HEX or TRON_PRIVATE_KEYS_HEX_CSV must be set in TRON_MODE=grpc"
+                "TRON_PRIVATE_KEY_HEX, TRON_PRIVATE_KEYS_HEX_CSV or TRON_REMOTE_SIGNER_ADDRESSES_CSV must be set in TRON_MODE=grpc"
             );
         }
         if env.tron_controller_address.trim().is_empty() {
@@ -131,22 +193,53 @@ pub(super) fn load_config() -> Result<AppConfig> {
         anyhow::bail!("TRON_MOCK_READER_ADDRESS must be set in TRON_MODE=mock");
     }
 
+    let tron_remote_signer = if env.tron_remote_signer_url.trim().is_empty() {
+        None
+    } else {
+        let auth_header = match env.tron_remote_signer_auth_header.trim() {
+            "" => None,
+            raw => {
+                let (name, value) = raw.split_once(':').ok_or_else(|| {
+                    anyhow::anyhow!("TRON_REMOTE_SIGNER_AUTH_HEADER must be \"<name>: <value>\"")
+                })?;
+                Some((name.trim().to_string(), value.trim().to_string()))
+            }
+        };
+        Some(TronRemoteSignerConfig {
+            url: env.tron_remote_signer_url.trim().to_string(),
+            timeout: Duration::from_millis(env.tron_remote_signer_timeout_ms.max(1)),
+            auth_header,
+        })
+    };
+    if tron_remote_signer.is_none() && !env.tron_remote_signer_addresses_csv.trim().is_empty() {
+        anyhow::bail!("TRON_REMOTE_SIGNER_ADDRESSES_CSV requires TRON_REMOTE_SIGNER_URL");
+    }
+
     let tron_private_keys = if tron_mode == TronMode::Grpc {
-        let mut keys: Vec<[u8; 32]> = Vec::new();
+        let mut keys: Vec<TronKey> = Vec::new();
         if !env.tron_private_key_hex.trim().is_empty() {
-            keys.push(parse_hex_32(
+            keys.push(TronKey::Local(parse_hex_32(
                 "TRON_PRIVATE_KEY_HEX",
                 &env.tron_private_key_hex,
-            )?);
+            )?));
         }
         if !env.tron_private_keys_hex_csv.trim().is_empty() {
-            keys.extend(parse_hex_32_csv(
-                "TRON_PRIVATE_KEYS_HEX_CSV",
-                &env.tron_private_keys_hex_csv,
-            )?);
+            keys.extend(
+                parse_hex_32_csv("TRON_PRIVATE_KEYS_HEX_CSV", &env.tron_private_keys_hex_csv)?
+                    .into_iter()
+                    .map(TronKey::Local),
+            );
         }
+        keys.extend(
+            parse_tron_addresses_csv(
+                "TRON_REMOTE_SIGNER_ADDRESSES_CSV",
+                &env.tron_remote_signer_addresses_csv,
+            )?
+            .into_iter()
+            .map(|a| TronKey::Remote(tron::TronAddress::from_evm(a))),
+        );
         // Dedup preserving order.
-        let mut out: Vec<[u8; 32]> = Vec::new();
+        let mut out: Vec<TronKey> = Vec::new();
         for k in keys {
             if !out.contains(&k) {
                 out.push(k);
@@ -160,44 +253,115 @@ pub(super) fn load_config() -> Result<AppConfig> {
         Vec::new()
     };
 
-    Ok(AppConfig {
-        indexer: IndexerConfig {
-            base_url: env.indexer_api_base_url,
-            timeout: Duration::from_secs(env.indexer_timeout_secs.max(1)),
-            max_head_lag_blocks: env.indexer_max_head_lag_blocks.max(1),
-        },
-        hub: HubConfig {
-            tx_mode: hub_tx_mode,
-            rpc_url: env.hub_rpc_url,
-            chain_id: env.hub_chain_id,
-            pool: hub_pool,
+    let indexer = IndexerConfig {
+        base_url: env.indexer_api_base_url,
+        timeout: Duration::from_secs(env.indexer_timeout_secs.max(1)),
+        max_head_lag_blocks: env.indexer_max_head_lag_blocks.max(1),
+        lag_warn_interval: Duration::from_secs(env.indexer_lag_warn_interval_secs),
+        health_retries: env.indexer_health_retries,
+        health_retry_delay: Duration::from_millis(env.indexer_health_retry_delay_ms),
+    };
+    let prove_mode = parse_prove_mode(&env.hub_prove_mode)?;
+    let prove_relayer = match prove_mode {
+        ProveMode::SelfSubmit => None,
+        ProveMode::DelegatedRelayer => {
+            let url = env.hub_prove_relayer_url.trim();
+            if url.is_empty() {
+                anyhow::bail!(
+                    "HUB_PROVE_RELAYER_URL must be set when HUB_PROVE_MODE=delegated_relayer"
+                );
+            }
+            Some(ProveRelayerConfig {
+                url: url.to_string(),
+                timeout: Duration::from_millis(env.hub_prove_relayer_timeout_ms.max(100)),
+                resubmit_after: Duration::from_secs(env.hub_prove_relayer_resubmit_secs.max(1)),
+            })
+        }
+    };
+    let prove_batch_max = env.hub_prove_batch_max.max(1);
+    let multisend = parse_optional_address(
+        "HUB_SAFE_MULTISEND_ADDRESS",
+        &env.hub_safe_multisend_address,
+    )?;
+    if prove_batch_max > 1 {
+        if hub_tx_mode != HubTxMode::Safe4337 {
+            anyhow::bail!("HUB_PROVE_BATCH_MAX > 1 requires HUB_TX_MODE=safe4337");
+        }
+        if prove_mode != ProveMode::SelfSubmit {
+            anyhow::bail!("HUB_PROVE_BATCH_MAX > 1 requires HUB_PROVE_MODE=self");
+        }
+        if multisend.is_none() {
+            anyhow::bail!("HUB_SAFE_MULTISEND_ADDRESS must be set when HUB_PROVE_BATCH_MAX > 1");
+        }
+    }
+    let hub = HubConfig {
+        pool_id: env.hub_pool_id.trim().to_string(),
+        tx_mode: hub_tx_mode,
+        rpc_url: env.hub_rpc_url,
+        chain_id: env.hub_chain_id,
+        pool: hub_pool,
+        claim_deposit: env.hub_claim_deposit,
 
-            entrypoint: hub_entrypoint,
-            safe: hub_safe,
-            safe_4337_module: hub_module,
-            safe_deployment: hub_safe_deployment,
-            bundler_urls: bundlers,
-            signer_private_key: hub_signer_private_key,
-            paymasters,
+        entrypoint: hub_entrypoint,
+        safe: hub_safe,
+        safe_4337_module: hub_module,
+        safe_deployment: hub_safe_deployment,
+        bundler_urls: bundlers,
+        signer_key: hub_signer_key,
+        remote_signer: hub_remote_signer,
+        deposit_funder,
+        prove_mode,
+        prove_relayer,
+        prove_batch_max,
+        multisend,
+        paymasters,
+        paymaster_freeze: PaymasterFreezePolicy {
+            fail_threshold: env.hub_paymaster_fail_threshold.max(1),
+            fail_window: Duration::from_secs(env.hub_paymaster_fail_window_secs.max(1)),
+            freeze: Duration::from_secs(env.hub_paymaster_freeze_secs),
         },
+        paymaster_self_funded_fallback: env.hub_paymaster_self_funded_fallback,
+        paymaster_deposit_min_wei: parse_wei(
+            "HUB_PAYMASTER_DEPOSIT_MIN_WEI",
+            &env.hub_paymaster_deposit_min_wei,
+        )?,
+        paymaster_deposit_check_interval: opt_u64(env.hub_paymaster_deposit_check_interval_secs)
+            .map(Duration::from_secs),
+        address_cache_ttl: Duration::from_secs(env.hub_address_cache_ttl_secs),
+        simulate_before_claim: env.hub_simulate_before_claim,
+        min_confirmations: env.hub_min_confirmations.max(1),
+    };
+    let extra_pools = parse_extra_pools_json(&env.hub_extra_pools_json, &hub, &indexer)?;
+
+    Ok(AppConfig {
+        indexer,
+        hub,
         tron: TronConfig {
             mode: tron_mode,
             grpc_url: env.tron_grpc_url,
             api_key: env.tron_api_key.filter(|s| !s.trim().is_empty()),
+            grpc_timeout: (env.tron_grpc_timeout_ms > 0)
+                .then(|| Duration::from_millis(env.tron_grpc_timeout_ms)),
+            grpc_max_retries: env.tron_grpc_max_retries,
+            health_gate_cooldown: opt_u64(env.tron_health_gate_cooldown_secs)
+                .map(Duration::from_secs),
             private_key: if tron_mode == TronMode::Grpc {
                 tron_private_keys
                     .first()
                     .copied()
                     .context("missing Tron private key")?
             } else {
-                [0u8; 32]
+                TronKey::UNSET
             },
             private_keys: tron_private_keys,
+            remote_signer: tron_remote_signer,
             controller_address: env.tron_controller_address,
             mock_reader_address: parse_optional_address(
                 "TRON_MOCK_READER_ADDRESS",
                 &env.tron_mock_reader_address,
             )?,
+            proof_format: parse_tron_proof_format(&env.tron_proof_format)?,
+            expected_network: parse_tron_network(&env.tron_expected_network)?,
             block_lag: env.tron_block_lag,
             fee_limit_cap_sun: env.tron_fee_limit_cap_sun.max(1_000_000),
             fee_limit_headroom_ppm: env.tron_fee_limit_headroom_ppm.min(1_000_000),
@@ -205,20 +369,54 @@ pub(super) fn load_config() -> Result<AppConfig> {
             energy_rental_providers: parse_tron_energy_rental_apis_json(
                 &env.tron_energy_rental_apis_json,
             )?,
-            delegate_resource_resell_enabled: env.tron_delegate_resource_resell_enabled
-                && !env.tron_energy_rental_apis_json.trim().is_empty(),
+            delegate_resource_resell_enabled: env.tron_delegate_resource_resell_enabled,
             rental_provider_fail_threshold: env.tron_rental_provider_fail_threshold.max(1),
             rental_provider_fail_window_secs: env.tron_rental_provider_fail_window_secs.max(1),
             rental_provider_freeze_secs: env.tron_rental_provider_freeze_secs.max(0),
+            rental_order_lock_secs: env.tron_rental_order_lock_secs.max(30),
+            max_tx_size_bytes: opt_u64(env.tron_max_tx_size_bytes),
             resell_energy_headroom_ppm: env.tron_resell_energy_headroom_ppm.min(1_000_000),
+            rental_quote_ttl_secs: env.tron_rental_quote_ttl_secs.max(1),
+            rental_quote_max_cost_trx: (env.tron_rental_quote_max_cost_trx > 0.0)
+                .then_some(env.tron_rental_quote_max_cost_trx),
+            rental_max_cost_usd: (env.tron_rental_max_cost_usd > 0.0)
+                .then_some(env.tron_rental_max_cost_usd),
+            out_of_energy_cooldown_secs: env.tron_out_of_energy_cooldown_secs,
+            sponsor_usdt_transfer_energy: env.tron_sponsor_usdt_transfer_energy,
+            key_daily_limit_sun: opt_u64(env.tron_key_daily_limit_sun),
+            key_daily_limit_usdt: opt_u64(env.tron_key_daily_limit_usdt),
+            auto_approve_trigger_spend: env.tron_auto_approve_trigger_spend,
+            trigger_spend_selectors: parse_trigger_spend_selectors_csv(
+                &env.tron_trigger_spend_selectors,
+            )?,
+            auto_stake_enabled: env.tron_auto_stake_enabled,
+            auto_stake_target_energy_sun: env.tron_auto_stake_target_energy_sun,
+            auto_stake_target_bandwidth_sun: env.tron_auto_stake_target_bandwidth_sun,
+            auto_stake_max_per_tx_sun: opt_u64(env.tron_auto_stake_max_per_tx_sun),
+            auto_stake_reserve_sun: env.tron_auto_stake_reserve_sun,
+            auto_stake_interval: Duration::from_secs(env.tron_auto_stake_interval_secs.max(1)),
             emulation_enabled: env.solver_tron_emulation_enabled,
+            sender_check_enabled: env.tron_sender_check_enabled,
+            fill_verification_enabled: env.tron_fill_verification_enabled,
         },
         jobs: JobConfig {
             tick_interval: Duration::from_secs(env.solver_tick_interval_secs.max(1)),
+            lease_secs: env.solver_lease_secs.max(1),
+            retry_jitter_pct: env.solver_retry_jitter_pct.min(30),
             tron_finality_blocks: env.tron_finality_blocks,
+            tron_proof_min_confirmations: env.tron_proof_min_confirmations,
+            tron_proof_max_bytes: opt_u64(env.tron_proof_max_bytes),
+            tron_proof_max_rebuilds: env.tron_proof_max_rebuilds,
             tip_proof_resend_blocks: env.tron_tip_proof_resend_blocks.max(1),
+            tron_inclusion_timeout_secs: env.tron_inclusion_timeout_secs.max(1),
+            tron_inclusion_poll_interval: Duration::from_millis(
+                env.tron_inclusion_poll_interval_ms.max(100),
+            ),
+            tron_max_inclusion_scan_blocks: opt_u64(env.tron_max_inclusion_scan_blocks),
+            tron_proof_compress: env.tron_proof_compress,
             process_controller_max_events: env.process_controller_max_events,
             fill_max_claims: env.fill_max_claims,
+            backlog_deadline_first_threshold: env.solver_backlog_deadline_first_threshold,
             max_in_flight_jobs: env
                 .solver_max_in_flight_jobs
                 .max(1)
@@ -233,14 +431,20 @@ pub(super) fn load_config() -> Result<AppConfig> {
                 .solver_concurrency_trigger_smart_contract
                 .max(1),
             concurrency_tron_broadcast: env.solver_concurrency_tron_broadcast.max(1),
+            concurrency_tron_proof: env.solver_concurrency_tron_proof.max(1),
             consolidation_enabled: env.solver_consolidation_enabled,
             consolidation_max_pre_txs: env.solver_consolidation_max_pre_txs,
+            max_pre_txs_per_tick: opt_u64(env.solver_max_pre_txs_per_tick),
             consolidation_max_total_trx_pull_sun: env.solver_consolidation_max_total_trx_pull_sun,
             consolidation_max_per_tx_trx_pull_sun: env.solver_consolidation_max_per_tx_trx_pull_sun,
             consolidation_max_total_usdt_pull_amount: env
                 .solver_consolidation_max_total_usdt_pull_amount,
             consolidation_max_per_tx_usdt_pull_amount: env
                 .solver_consolidation_max_per_tx_usdt_pull_amount,
+            proactive_consolidation: env.solver_proactive_consolidation,
+            proactive_consolidation_interval: Duration::from_secs(
+                env.solver_proactive_consolidation_interval_secs.max(1),
+            ),
             rate_limit_claims_per_minute_global: env.solver_rate_limit_claims_per_minute_global,
             rate_limit_claims_per_minute_trx_transfer: env
                 .solver_rate_limit_claims_per_minute_trx_transfer,
@@ -250,11 +454,16 @@ pub(super) fn load_config() -> Result<AppConfig> {
                 .solver_rate_limit_claims_per_minute_delegate_resource,
             rate_limit_claims_per_minute_trigger_smart_contract: env
                 .solver_rate_limit_claims_per_minute_trigger_smart_contract,
+            hub_userop_validation_fatal_attempts: env.hub_userop_validation_fatal_attempts,
             global_pause_fatal_threshold: env.solver_global_pause_fatal_threshold,
             global_pause_window_secs: env.solver_global_pause_window_secs.max(1),
             global_pause_duration_secs: env.solver_global_pause_duration_secs.max(1),
             breaker_mismatch_penalty: env.solver_breaker_mismatch_penalty.clamp(1, 100),
             delegate_reservation_ttl_secs: env.solver_delegate_reservation_ttl_secs.max(30),
+            abandon_after_secs: env.solver_job_abandon_after_secs,
+            archive_after_days: env.solver_jobs_archive_after_days,
+            archive_interval: Duration::from_secs(env.solver_jobs_archive_interval_secs.max(1)),
+            archive_batch_size: env.solver_jobs_archive_batch_size.max(1),
             controller_rebalance_threshold_usdt: env.controller_rebalance_threshold_usdt,
             controller_rebalance_keep_usdt: env.controller_rebalance_keep_usdt,
             pull_liquidity_ppm: env.pull_liquidity_ppm.min(1_000_000),
@@ -262,11 +471,17 @@ pub(super) fn load_config() -> Result<AppConfig> {
         policy: PolicyConfig {
             enabled_intent_types,
             min_deadline_slack_secs: env.solver_min_deadline_slack_secs,
+            deadline_kind: parse_deadline_kind(&env.solver_deadline_kind)?,
+            min_deadline_slack_blocks: env.solver_min_deadline_slack_blocks,
             min_profit_usd: env.solver_min_profit_usd,
             hub_cost_usd: env.solver_hub_cost_usd,
             hub_cost_history_lookback: env.solver_hub_cost_history_lookback.max(1),
             hub_cost_headroom_ppm: env.solver_hub_cost_headroom_ppm.min(1_000_000),
             tron_fee_usd: env.solver_tron_fee_usd,
+            tron_fee_usd_by_type: parse_usd_by_intent_type_csv(
+                "SOLVER_TRON_FEE_USD_BY_TYPE",
+                &env.solver_tron_fee_usd_by_type,
+            )?,
             tron_fee_history_lookback: env.solver_tron_fee_history_lookback.max(1),
             tron_fee_headroom_ppm: env.solver_tron_fee_headroom_ppm.min(1_000_000),
             capital_lock_ppm_per_day: env.solver_capital_lock_ppm_per_day.min(1_000_000),
@@ -286,6 +501,39 @@ pub(super) fn load_config() -> Result<AppConfig> {
                 &env.solver_trigger_selector_denylist_csv,
             )?,
             trigger_allow_fallback_calls: env.solver_trigger_allow_fallback_calls,
+            trigger_selector_budgets: parse_trigger_selector_budgets_json(
+                &env.solver_trigger_selector_budgets_json,
+            )?,
+            trigger_selector_allowlist_mode: env.solver_trigger_selector_allowlist_mode,
+
+            tron_address_allowlist: parse_tron_addresses_csv(
+                "SOLVER_TRON_ADDRESS_ALLOWLIST_CSV",
+                &env.solver_tron_address_allowlist_csv,
+            )?,
+            tron_address_denylist: parse_tron_addresses_csv(
+                "SOLVER_TRON_ADDRESS_DENYLIST_CSV",
+                &env.solver_tron_address_denylist_csv,
+            )?,
+            tron_contract_allowlist: parse_tron_addresses_csv(
+                "SOLVER_TRON_CONTRACT_ALLOWLIST_CSV",
+                &env.solver_tron_contract_allowlist_csv,
+            )?,
+            tron_contract_denylist: parse_tron_addresses_csv(
+                "SOLVER_TRON_CONTRACT_DENYLIST_CSV",
+                &env.solver_tron_contract_denylist_csv,
+            )?,
+            escrow_token_allowlist: parse_addresses_csv(
+                "SOLVER_ESCROW_TOKEN_ALLOWLIST_CSV",
+                &env.solver_escrow_token_allowlist_csv,
+            )?,
+            refund_beneficiary_allowlist: parse_addresses_csv(
+                "SOLVER_REFUND_BENEFICIARY_ALLOWLIST_CSV",
+                &env.solver_refund_beneficiary_allowlist_csv,
+            )?,
+            refund_beneficiary_denylist: parse_addresses_csv(
+                "SOLVER_REFUND_BENEFICIARY_DENYLIST_CSV",
+                &env.solver_refund_beneficiary_denylist_csv,
+            )?,
 
             max_trx_transfer_sun: opt_u64(env.solver_max_trx_transfer_sun),
             max_usdt_transfer_amount: opt_u64(env.solver_max_usdt_transfer_amount),
@@ -293,6 +541,15 @@ pub(super) fn load_config() -> Result<AppConfig> {
             max_delegate_lock_period_secs: opt_u64(env.solver_max_delegate_lock_period_secs),
             max_trigger_call_value_sun: opt_u64(env.solver_max_trigger_call_value_sun),
             max_trigger_calldata_len: opt_u64(env.solver_max_trigger_calldata_len),
+            max_inflight_claim_deposit: opt_u64(env.solver_max_inflight_claim_deposit),
+            max_claims_per_minute: opt_u64(env.solver_max_claims_per_minute),
+            min_intent_age_secs: opt_u64(env.solver_min_intent_age_secs),
+            max_intent_value_usd: (env.solver_max_intent_value_usd > 0.0)
+                .then_some(env.solver_max_intent_value_usd),
+            max_intent_value_usd_by_type: parse_usd_by_intent_type_csv(
+                "SOLVER_MAX_INTENT_VALUE_USD_BY_TYPE",
+                &env.solver_max_intent_value_usd_by_type,
+            )?,
         },
         pricing: crate::pricing::PricingConfig {
             trx_usd_override: env.solver_trx_usd_override,
@@ -308,5 +565,25 @@ pub(super) fn load_config() -> Result<AppConfig> {
         } else {
             env.solver_instance_id
         },
+        log_effective_config: env.solver_log_effective_config,
+        dry_run: env.solver_dry_run,
+        role: parse_solver_role(&env.solver_role)?,
+        fatal_webhook: WebhookConfig {
+            url: Some(env.solver_fatal_webhook_url.trim().to_string()).filter(|s| !s.is_empty()),
+            hmac_secret: Some(env.solver_fatal_webhook_hmac_secret)
+                .filter(|s| !s.trim().is_empty()),
+            timeout: Duration::from_millis(env.solver_fatal_webhook_timeout_ms.clamp(100, 10_000)),
+        },
+        summary: SummaryConfig {
+            interval: opt_u64(env.solver_summary_interval_secs)
+                .map(|secs| Duration::from_secs(secs.max(60))),
+            webhook_url: Some(env.solver_summary_webhook_url.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            webhook_timeout: Duration::from_millis(
+                env.solver_summary_webhook_timeout_ms.clamp(100, 10_000),
+            ),
+            key_reserve_sun: env.solver_summary_key_reserve_sun,
+        },
+        extra_pools,
     })
 }
@@ -1,6 +1,13 @@
-use super::{HubTxMode, PaymasterServiceConfig, TronMode};
-use alloy::primitives::Address;
+use super::{
+    DepositFunderConfig, HubConfig, HubKey, HubPoolConfig, HubTxMode, IndexerConfig,
+    PaymasterServiceConfig, ProveMode, SolverRole, TriggerSelectorBudget, TronMode, TronNetwork,
+    TronProofFormat,
+};
+use crate::types::DeadlineKind;
+use alloy::primitives::{Address, U256};
+use alloy::signers::local::PrivateKeySigner;
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use tron::JsonApiRentalProviderConfig;
 
 pub(super) fn parse_address(label: &str, s: &str) -> Result<Address> {
@@ -32,6 +39,59 @@ pub(super) fn parse_hex_32(label: &str, s: &str) -> Result<[u8; 32]> {
     Ok(out)
 }
 
+pub(super) fn parse_deposit_funder(
+    address: &str,
+    private_key_hex: &str,
+    remote_signer: bool,
+) -> Result<Option<DepositFunderConfig>> {
+    let private_key = if private_key_hex.trim().is_empty() {
+        None
+    } else {
+        Some(parse_hex_32(
+            "HUB_DEPOSIT_FUNDER_PRIVATE_KEY_HEX",
+            private_key_hex.trim(),
+        )?)
+    };
+    let Some(address) = parse_optional_address("HUB_DEPOSIT_FUNDER_ADDRESS", address)? else {
+        if private_key.is_some() {
+            anyhow::bail!(
+                "HUB_DEPOSIT_FUNDER_PRIVATE_KEY_HEX set without HUB_DEPOSIT_FUNDER_ADDRESS"
+            );
+        }
+        if remote_signer {
+            anyhow::bail!(
+                "HUB_DEPOSIT_FUNDER_REMOTE_SIGNER set without HUB_DEPOSIT_FUNDER_ADDRESS"
+            );
+        }
+        return Ok(None);
+    };
+    if remote_signer {
+        if private_key.is_some() {
+            anyhow::bail!(
+                "set only one of HUB_DEPOSIT_FUNDER_PRIVATE_KEY_HEX and HUB_DEPOSIT_FUNDER_REMOTE_SIGNER"
+            );
+        }
+        return Ok(Some(DepositFunderConfig {
+            address,
+            key: Some(HubKey::Remote(address)),
+        }));
+    }
+    if let Some(key) = private_key {
+        let derived = PrivateKeySigner::from_slice(&key)
+            .context("invalid HUB_DEPOSIT_FUNDER_PRIVATE_KEY_HEX")?
+            .address();
+        if derived != address {
+            anyhow::bail!(
+                "HUB_DEPOSIT_FUNDER_PRIVATE_KEY_HEX is for {derived}, not HUB_DEPOSIT_FUNDER_ADDRESS={address}"
+            );
+        }
+    }
+    Ok(Some(DepositFunderConfig {
+        address,
+        key: private_key.map(HubKey::Local),
+    }))
+}
+
 pub(super) fn parse_csv(label: &str, s: &str) -> Result<Vec<String>> {
     let urls = s
         .split(',')
@@ -57,6 +117,30 @@ pub(super) fn parse_addresses_csv(label: &str, s: &str) -> Result<Vec<Address>>
     Ok(out)
 }
 
+/// Parses Tron addresses given as base58check (T...), EVM hex (0x + 20 bytes) or Tron hex
+/// (41 + 20 bytes), normalized to the 20-byte EVM form used in intent specs.
+pub(super) fn parse_tron_addresses_csv(label: &str, s: &str) -> Result<Vec<Address>> {
+    let mut out = Vec::new();
+    for raw in s.split(',') {
+        let v = raw.trim();
+        if v.is_empty() {
+            continue;
+        }
+        let hex41 = v.strip_prefix("0x").unwrap_or(v);
+        let addr = if hex41.len() == 42 && hex41.starts_with("41") {
+            hex41[2..]
+                .parse::<Address>()
+                .with_context(|| format!("invalid Tron address in {label}: {v}"))?
+        } else {
+            tron::TronAddress::parse_text(v)
+                .with_context(|| format!("invalid Tron address in {label}: {v}"))?
+                .evm()
+        };
+        out.push(addr);
+    }
+    Ok(out)
+}
+
 pub(super) fn parse_selectors_csv(label: &str, s: &str) -> Result<Vec<[u8; 4]>> {
     let mut out = Vec::new();
     for raw in s.split(',') {
@@ -64,19 +148,76 @@ pub(super) fn parse_selectors_csv(label: &str, s: &str) -> Result<Vec<[u8; 4]>>
         if v.is_empty() {
             continue;
         }
-        let v = v.strip_prefix("0x").unwrap_or(v);
-        let bytes =
-            hex::decode(v).with_context(|| format!("invalid selector hex in {label}: {v}"))?;
-        if bytes.len() != 4 {
-            anyhow::bail!("{label} entries must be 4 bytes (got {})", bytes.len());
+        out.push(parse_selector(label, v)?);
+    }
+    Ok(out)
+}
+
+fn parse_selector(label: &str, v: &str) -> Result<[u8; 4]> {
+    let v = v.strip_prefix("0x").unwrap_or(v);
+    let bytes = hex::decode(v).with_context(|| format!("invalid selector hex in {label}: {v}"))?;
+    if bytes.len() != 4 {
+        anyhow::bail!("{label} entries must be 4 bytes (got {})", bytes.len());
+    }
+    let mut sel = [0u8; 4];
+    sel.copy_from_slice(&bytes);
+    Ok(sel)
+}
+
+/// Parses `{"0xa9059cbb": {"max_energy": 65000, "enabled": true}, ...}`.
+pub(super) fn parse_trigger_selector_budgets_json(
+    s: &str,
+) -> Result<HashMap<[u8; 4], TriggerSelectorBudget>> {
+    const LABEL: &str = "SOLVER_TRIGGER_SELECTOR_BUDGETS_JSON";
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let raw: HashMap<String, TriggerSelectorBudget> =
+        serde_json::from_str(trimmed).with_context(|| format!("parse {LABEL}"))?;
+    let mut out = HashMap::with_capacity(raw.len());
+    for (k, budget) in raw {
+        let sel = parse_selector(LABEL, k.trim())?;
+        if out.insert(sel, budget).is_some() {
+            anyhow::bail!("{LABEL} has duplicate selector: {k}");
+        }
+    }
+    Ok(out)
+}
+
+/// Parses `0x12345678:1,abcdef01:2` into selector -> ABI argument index of the spent amount.
+pub(super) fn parse_trigger_spend_selectors_csv(s: &str) -> Result<HashMap<[u8; 4], usize>> {
+    const LABEL: &str = "TRON_TRIGGER_SPEND_SELECTORS";
+    let mut out = HashMap::new();
+    for raw in s.split(',') {
+        let v = raw.trim();
+        if v.is_empty() {
+            continue;
+        }
+        let (sel, idx) = v
+            .split_once(':')
+            .with_context(|| format!("{LABEL} entries must be selector:arg_index (got {v})"))?;
+        let sel = parse_selector(LABEL, sel.trim())?;
+        let idx: usize = idx
+            .trim()
+            .parse()
+            .with_context(|| format!("invalid {LABEL} arg index: {v}"))?;
+        if out.insert(sel, idx).is_some() {
+            anyhow::bail!("{LABEL} has duplicate selector: {v}");
         }
-        let mut sel = [0u8; 4];
-        sel.copy_from_slice(&bytes);
-        out.push(sel);
     }
     Ok(out)
 }
 
+/// Parses a decimal wei amount; empty means zero.
+pub(super) fn parse_wei(label: &str, s: &str) -> Result<U256> {
+    let v = s.trim();
+    if v.is_empty() {
+        return Ok(U256::ZERO);
+    }
+    U256::from_str_radix(v, 10).with_context(|| format!("invalid {label}: {v}"))
+}
+
 pub(super) fn opt_u64(v: u64) -> Option<u64> {
     if v == 0 { None } else { Some(v) }
 }
@@ -100,6 +241,91 @@ pub(super) fn parse_paymasters_json(s: &str) -> Result<Vec<PaymasterServiceConfi
     Ok(v)
 }
 
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExtraPoolJson {
+    pool_id: String,
+    pool_address: String,
+    indexer_base_url: String,
+    #[serde(default)]
+    rpc_url: Option<String>,
+    #[serde(default)]
+    chain_id: Option<u64>,
+    #[serde(default)]
+    claim_deposit: Option<u64>,
+    #[serde(default)]
+    bundler_urls: Option<Vec<String>>,
+}
+
+/// Parses `HUB_EXTRA_POOLS_JSON`, e.g.
+/// `[{"pool_id":"base","pool_address":"0x..","indexer_base_url":"https://.."}]`.
+///
+/// Each pool starts from the primary hub/indexer config (signer, tx mode, Safe, paymasters,
+/// timeouts) and overrides the pool address, indexer and optionally RPC, chain id, claim deposit
+/// and bundlers.
+pub(super) fn parse_extra_pools_json(
+    s: &str,
+    primary_hub: &HubConfig,
+    primary_indexer: &IndexerConfig,
+) -> Result<Vec<HubPoolConfig>> {
+    if primary_hub.pool_id.is_empty() {
+        anyhow::bail!("HUB_POOL_ID must not be empty");
+    }
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        return Ok(Vec::new());
+    }
+    let entries: Vec<ExtraPoolJson> =
+        serde_json::from_str(trimmed).context("parse HUB_EXTRA_POOLS_JSON")?;
+
+    let mut seen = vec![primary_hub.pool_id.clone()];
+    let mut out = Vec::with_capacity(entries.len());
+    for e in entries {
+        let pool_id = e.pool_id.trim().to_string();
+        if pool_id.is_empty() {
+            anyhow::bail!("HUB_EXTRA_POOLS_JSON contains an empty pool_id");
+        }
+        if seen.contains(&pool_id) {
+            anyhow::bail!("HUB_EXTRA_POOLS_JSON: duplicate pool_id {pool_id}");
+        }
+        seen.push(pool_id.clone());
+        if e.indexer_base_url.trim().is_empty() {
+            anyhow::bail!("HUB_EXTRA_POOLS_JSON: pool {pool_id} has an empty indexer_base_url");
+        }
+
+        let mut hub = primary_hub.clone();
+        hub.pool = parse_address(
+            &format!("HUB_EXTRA_POOLS_JSON[{pool_id}].pool_address"),
+            e.pool_address.trim(),
+        )?;
+        if let Some(url) = e.rpc_url.filter(|u| !u.trim().is_empty()) {
+            hub.rpc_url = url.trim().to_string();
+            // A different RPC may be a different chain; don't inherit the primary chain id.
+            hub.chain_id = None;
+        }
+        if e.chain_id.is_some() {
+            hub.chain_id = e.chain_id;
+        }
+        if let Some(deposit) = e.claim_deposit {
+            hub.claim_deposit = deposit;
+        }
+        if let Some(urls) = e.bundler_urls {
+            hub.bundler_urls = urls
+                .into_iter()
+                .map(|u| u.trim().to_string())
+                .filter(|u| !u.is_empty())
+                .collect();
+        }
+        hub.pool_id = pool_id;
+
+        let mut indexer = primary_indexer.clone();
+        indexer.base_url = e.indexer_base_url.trim().to_string();
+
+        out.push(HubPoolConfig { hub, indexer });
+    }
+    Ok(out)
+}
+
 pub(super) fn parse_tron_energy_rental_apis_json(
     s: &str,
 ) -> Result<Vec<JsonApiRentalProviderConfig>> {
@@ -121,6 +347,14 @@ pub(super) fn parse_tron_energy_rental_apis_json(
         if p.method.trim().is_empty() {
             p.method = "POST".to_string();
         }
+        if let Some(cap) = p.max_rental_cost_usd
+            && !(cap.is_finite() && cap > 0.0)
+        {
+            anyhow::bail!(
+                "TRON_ENERGY_RENTAL_APIS_JSON: provider {} has an invalid max_rental_cost_usd",
+                p.name
+            );
+        }
     }
     Ok(v)
 }
@@ -133,6 +367,34 @@ pub(super) fn parse_hub_tx_mode(s: &str) -> Result<HubTxMode> {
     }
 }
 
+pub(super) fn parse_prove_mode(s: &str) -> Result<ProveMode> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "" | "self" => Ok(ProveMode::SelfSubmit),
+        "delegated_relayer" | "relayer" => Ok(ProveMode::DelegatedRelayer),
+        other => {
+            anyhow::bail!("unsupported HUB_PROVE_MODE: {other} (expected: self|delegated_relayer)")
+        }
+    }
+}
+
+pub(super) fn parse_deadline_kind(s: &str) -> Result<DeadlineKind> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "" | "timestamp" => Ok(DeadlineKind::Timestamp),
+        "block" => Ok(DeadlineKind::Block),
+        other => {
+            anyhow::bail!("unsupported SOLVER_DEADLINE_KIND: {other} (expected: timestamp|block)")
+        }
+    }
+}
+
+pub(super) fn parse_solver_role(s: &str) -> Result<SolverRole> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "" | "active" => Ok(SolverRole::Active),
+        "follower" | "standby" => Ok(SolverRole::Follower),
+        other => anyhow::bail!("unsupported SOLVER_ROLE: {other} (expected: active|follower)"),
+    }
+}
+
 pub(super) fn parse_tron_mode(s: &str) -> Result<TronMode> {
     match s.trim().to_ascii_lowercase().as_str() {
         "" | "grpc" => Ok(TronMode::Grpc),
@@ -141,6 +403,26 @@ pub(super) fn parse_tron_mode(s: &str) -> Result<TronMode> {
     }
 }
 
+pub(super) fn parse_tron_proof_format(s: &str) -> Result<TronProofFormat> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "" | "v1" => Ok(TronProofFormat::V1),
+        other => anyhow::bail!("unsupported TRON_PROOF_FORMAT: {other} (expected: v1)"),
+    }
+}
+
+pub(super) fn parse_tron_network(s: &str) -> Result<Option<TronNetwork>> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "" => Ok(None),
+        "mainnet" => Ok(Some(TronNetwork::Mainnet)),
+        "nile" => Ok(Some(TronNetwork::Nile)),
+        "shasta" => Ok(Some(TronNetwork::Shasta)),
+        other => Ok(Some(TronNetwork::Genesis(parse_hex_32(
+            "TRON_EXPECTED_NETWORK",
+            other,
+        )?))),
+    }
+}
+
 pub(super) fn parse_intent_types(s: &str) -> Result<Vec<crate::types::IntentType>> {
     if s.trim().is_empty() {
         return Ok(vec![
@@ -154,13 +436,7 @@ pub(super) fn parse_intent_types(s: &str) -> Result<Vec<crate::types::IntentType
         if v.is_empty() {
             continue;
         }
-        let ty = match v {
-            "trigger_smart_contract" => crate::types::IntentType::TriggerSmartContract,
-            "usdt_transfer" => crate::types::IntentType::UsdtTransfer,
-            "trx_transfer" => crate::types::IntentType::TrxTransfer,
-            "delegate_resource" => crate::types::IntentType::DelegateResource,
-            other => anyhow::bail!("unknown intent type: {other}"),
-        };
+        let ty = parse_intent_type(v)?;
         if !out.contains(&ty) {
             out.push(ty);
         }
@@ -171,6 +447,45 @@ pub(super) fn parse_intent_types(s: &str) -> Result<Vec<crate::types::IntentType
     Ok(out)
 }
 
+fn parse_intent_type(v: &str) -> Result<crate::types::IntentType> {
+    Ok(match v {
+        "trigger_smart_contract" => crate::types::IntentType::TriggerSmartContract,
+        "usdt_transfer" => crate::types::IntentType::UsdtTransfer,
+        "trx_transfer" => crate::types::IntentType::TrxTransfer,
+        "delegate_resource" => crate::types::IntentType::DelegateResource,
+        other => anyhow::bail!("unknown intent type: {other}"),
+    })
+}
+
+/// Parses `trx_transfer=500,usdt_transfer=2500` into positive per-intent-type USD amounts.
+pub(super) fn parse_usd_by_intent_type_csv(
+    label: &str,
+    s: &str,
+) -> Result<HashMap<crate::types::IntentType, f64>> {
+    let mut out = HashMap::new();
+    for raw in s.split(',') {
+        let v = raw.trim();
+        if v.is_empty() {
+            continue;
+        }
+        let (ty, cap) = v
+            .split_once('=')
+            .with_context(|| format!("{label} entries must be type=usd (got {v})"))?;
+        let ty = parse_intent_type(ty.trim())?;
+        let cap: f64 = cap
+            .trim()
+            .parse()
+            .with_context(|| format!("invalid {label} amount: {v}"))?;
+        if !cap.is_finite() || cap <= 0.0 {
+            anyhow::bail!("{label} amounts must be positive (got {v})");
+        }
+        if out.insert(ty, cap).is_some() {
+            anyhow::bail!("{label} has duplicate intent type: {v}");
+        }
+    }
+    Ok(out)
+}
+
 pub(super) fn parse_hex_32_csv(label: &str, s: &str) -> Result<Vec<[u8; 32]>> {
     let items = parse_csv(label, s)?;
     let mut out = Vec::with_capacity(items.len());
@@ -195,6 +510,29 @@ mod tests {
         assert!(err.contains("must be 32 bytes"));
     }
 
+    #[test]
+    fn parse_tron_network_names_and_genesis_ids() {
+        assert_eq!(parse_tron_network(" ").unwrap(), None);
+        let mainnet = parse_tron_network("Mainnet").unwrap().unwrap();
+        assert_eq!(mainnet, TronNetwork::Mainnet);
+        // A network's chain id is the tail of its genesis block id.
+        assert_eq!(
+            mainnet.genesis_block_id()[28..],
+            0x2b66_53dcu32.to_be_bytes()
+        );
+        assert_eq!(
+            TronNetwork::Nile.genesis_block_id()[28..],
+            0xcd86_90dcu32.to_be_bytes()
+        );
+
+        let custom = format!("0x{}", "22".repeat(32));
+        assert_eq!(
+            parse_tron_network(&custom).unwrap(),
+            Some(TronNetwork::Genesis([0x22; 32]))
+        );
+        assert!(parse_tron_network("testnet").is_err());
+    }
+
     #[test]
     fn parse_csv_trims_and_requires_non_empty() {
         let urls = parse_csv("U", " a, ,b ,, c ").unwrap();
@@ -231,6 +569,55 @@ mod tests {
         assert!(err.contains("must be a JSON object"));
     }
 
+    #[test]
+    fn parse_paymasters_json_reads_optional_address() {
+        let v = parse_paymasters_json(
+            r#"[{"url":"https://pm.example","address":"0x1111111111111111111111111111111111111111"},{"url":"x"}]"#,
+        )
+        .unwrap();
+        assert_eq!(v[0].address, Some(Address::repeat_byte(0x11)));
+        assert_eq!(v[1].address, None);
+    }
+
+    #[test]
+    fn parse_wei_decimal_and_empty() {
+        assert_eq!(parse_wei("X", "  ").unwrap(), U256::ZERO);
+        assert_eq!(
+            parse_wei("X", " 50000000000000000 ").unwrap(),
+            U256::from(50_000_000_000_000_000u64)
+        );
+        assert!(parse_wei("X", "0x10").is_err());
+    }
+
+    #[test]
+    fn parse_deposit_funder_checks_key_matches_address() {
+        assert!(parse_deposit_funder(" ", "", false).unwrap().is_none());
+
+        let key = [0x11u8; 32];
+        let addr = PrivateKeySigner::from_slice(&key).unwrap().address();
+        let funder = parse_deposit_funder(&addr.to_string(), &hex::encode(key), false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(funder.address, addr);
+        assert_eq!(funder.key, Some(HubKey::Local(key)));
+
+        let other = "0x0000000000000000000000000000000000000009";
+        assert!(
+            parse_deposit_funder(other, "", false)
+                .unwrap()
+                .unwrap()
+                .key
+                .is_none()
+        );
+        assert!(parse_deposit_funder(other, &hex::encode(key), false).is_err());
+        assert!(parse_deposit_funder("", &hex::encode(key), false).is_err());
+
+        let remote = parse_deposit_funder(other, "", true).unwrap().unwrap();
+        assert_eq!(remote.key, Some(HubKey::Remote(other.parse().unwrap())));
+        assert!(parse_deposit_funder(&addr.to_string(), &hex::encode(key), true).is_err());
+        assert!(parse_deposit_funder("", "", true).is_err());
+    }
+
     #[test]
     fn parse_tron_energy_rental_apis_json_empty_ok() {
         assert!(
@@ -274,6 +661,62 @@ mod tests {
         assert!(parse_address("A", "not an address").is_err());
     }
 
+    #[test]
+    fn parse_tron_addresses_accepts_base58_and_hex_forms() {
+        let evm: Address = "0xa614f803b6fd780986a42c78ec9c7f77e6ded13c"
+            .parse()
+            .unwrap();
+        let base58 = tron::TronAddress::from_evm(evm).to_base58check();
+        let csv = format!(
+            "{base58}, 0xa614f803b6fd780986a42c78ec9c7f77e6ded13c,41a614f803b6fd780986a42c78ec9c7f77e6ded13c"
+        );
+        let got = parse_tron_addresses_csv("L", &csv).unwrap();
+        assert_eq!(got, vec![evm, evm, evm]);
+
+        assert!(parse_tron_addresses_csv("L", "Tnotanaddress").is_err());
+    }
+
+    #[test]
+    fn parse_trigger_selector_budgets_json_defaults_enabled() {
+        assert!(parse_trigger_selector_budgets_json("").unwrap().is_empty());
+
+        let got = parse_trigger_selector_budgets_json(
+            r#"{"0xa9059cbb":{"max_energy":65000},"095ea7b3":{"enabled":false}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            got.get(&[0xa9, 0x05, 0x9c, 0xbb]),
+            Some(&TriggerSelectorBudget {
+                max_energy: Some(65_000),
+                enabled: true,
+            })
+        );
+        assert_eq!(
+            got.get(&[0x09, 0x5e, 0xa7, 0xb3]),
+            Some(&TriggerSelectorBudget {
+                max_energy: None,
+                enabled: false,
+            })
+        );
+
+        assert!(parse_trigger_selector_budgets_json(r#"{"0x1234":{}}"#).is_err());
+        assert!(parse_trigger_selector_budgets_json(r#"{"0xa9059cbb":{},"a9059cbb":{}}"#).is_err());
+    }
+
+    #[test]
+    fn parse_trigger_spend_selectors_csv_maps_selector_to_arg_index() {
+        assert!(parse_trigger_spend_selectors_csv("").unwrap().is_empty());
+
+        let got = parse_trigger_spend_selectors_csv("0x23b872dd:2, 12345678:0").unwrap();
+        assert_eq!(got.get(&[0x23, 0xb8, 0x72, 0xdd]), Some(&2));
+        assert_eq!(got.get(&[0x12, 0x34, 0x56, 0x78]), Some(&0));
+
+        assert!(parse_trigger_spend_selectors_csv("0x23b872dd").is_err());
+        assert!(parse_trigger_spend_selectors_csv("0x23b872dd:x").is_err());
+        assert!(parse_trigger_spend_selectors_csv("0x23b8:1").is_err());
+        assert!(parse_trigger_spend_selectors_csv("0x23b872dd:1,23b872dd:2").is_err());
+    }
+
     #[test]
     fn parse_intent_types_dedups_and_preserves_order() {
         let got = parse_intent_types("trx_transfer,delegate_resource,trx_transfer").unwrap();
@@ -288,4 +731,72 @@ mod tests {
         let err = parse_intent_types("nope").unwrap_err().to_string();
         assert!(err.contains("unknown intent type"));
     }
+
+    #[test]
+    fn parse_usd_by_intent_type_csv_parses_and_rejects_bad_entries() {
+        let parse = |s| parse_usd_by_intent_type_csv("K", s);
+        assert!(parse("").unwrap().is_empty());
+
+        let got = parse("trx_transfer=500, usdt_transfer=2500.5").unwrap();
+        assert_eq!(got.get(&IntentType::TrxTransfer), Some(&500.0));
+        assert_eq!(got.get(&IntentType::UsdtTransfer), Some(&2500.5));
+        assert_eq!(got.get(&IntentType::DelegateResource), None);
+
+        assert!(parse("trx_transfer").is_err());
+        assert!(parse("trx_transfer=0").is_err());
+        assert!(parse("nope=1").is_err());
+        assert!(parse("trx_transfer=1,trx_transfer=2").is_err());
+    }
+
+    #[test]
+    fn parse_extra_pools_json_inherits_primary_and_rejects_duplicates() {
+        let env = crate::config::env::Env {
+            solver_db_url: "postgres://db/untron".to_string(),
+            indexer_api_base_url: "https://indexer-a.example".to_string(),
+            hub_rpc_url: "https://rpc-a.example".to_string(),
+            hub_chain_id: Some(42161),
+            hub_pool_address: "0x0000000000000000000000000000000000000001".to_string(),
+            hub_signer_private_key_hex: format!("0x{}", "ab".repeat(32)),
+            tron_grpc_url: "https://grpc.example".to_string(),
+            tron_private_key_hex: format!("0x{}", "cd".repeat(32)),
+            tron_controller_address: "TController".to_string(),
+            ..Default::default()
+        };
+        let cfg = crate::config::load::config_from_env(env).expect("config");
+        assert!(cfg.extra_pools.is_empty());
+
+        let pools = parse_extra_pools_json(
+            r#"[
+                {"pool_id": "same-chain", "pool_address": "0x0000000000000000000000000000000000000002",
+                 "indexer_base_url": "https://indexer-b.example"},
+                {"pool_id": "base", "pool_address": "0x0000000000000000000000000000000000000003",
+                 "indexer_base_url": "https://indexer-c.example",
+                 "rpc_url": "https://rpc-c.example", "claim_deposit": 5}
+            ]"#,
+            &cfg.hub,
+            &cfg.indexer,
+        )
+        .unwrap();
+        assert_eq!(pools.len(), 2);
+        assert_eq!(pools[0].hub.pool_id, "same-chain");
+        assert_eq!(pools[0].hub.rpc_url, "https://rpc-a.example");
+        assert_eq!(pools[0].hub.chain_id, Some(42161));
+        assert_eq!(pools[0].hub.claim_deposit, cfg.hub.claim_deposit);
+        assert_eq!(pools[0].indexer.base_url, "https://indexer-b.example");
+        assert_eq!(pools[1].hub.rpc_url, "https://rpc-c.example");
+        assert_eq!(pools[1].hub.chain_id, None);
+        assert_eq!(pools[1].hub.claim_deposit, 5);
+        assert_eq!(pools[1].hub.signer_key, cfg.hub.signer_key);
+
+        let pool = |id: &str, indexer: &str| {
+            format!(
+                r#"[{{"pool_id": "{id}", "indexer_base_url": "{indexer}",
+                     "pool_address": "0x0000000000000000000000000000000000000002"}}]"#
+            )
+        };
+        let dup = pool("default", "https://indexer-b.example");
+        assert!(parse_extra_pools_json(&dup, &cfg.hub, &cfg.indexer).is_err());
+        let no_indexer = pool("b", " ");
+        assert!(parse_extra_pools_json(&no_indexer, &cfg.hub, &cfg.indexer).is_err());
+    }
 }
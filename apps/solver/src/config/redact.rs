@@ -0,0 +1,258 @@
+use super::{AppConfig, HubConfig, HubKey, TronKey};
+use serde_json::Value;
+
+const REDACTED: &str = "<redacted>";
+
+impl AppConfig {
+    /// Full effective config as a single debug string, with every secret masked.
+    ///
+    /// Private keys, API keys, provider headers/bodies and paymaster contexts are replaced
+    /// entirely; URLs keep only scheme, host and port since RPC/bundler/DB URLs commonly embed
+    /// credentials in userinfo, path or query.
+    pub fn redacted_debug(&self) -> String {
+        let mut cfg = self.clone();
+
+        cfg.db_url = redact_url(&cfg.db_url);
+        cfg.indexer.base_url = redact_url(&cfg.indexer.base_url);
+
+        redact_hub(&mut cfg.hub);
+        for pool in &mut cfg.extra_pools {
+            redact_hub(&mut pool.hub);
+            pool.indexer.base_url = redact_url(&pool.indexer.base_url);
+        }
+
+        cfg.tron.grpc_url = redact_url(&cfg.tron.grpc_url);
+        cfg.tron.api_key = cfg.tron.api_key.as_ref().map(|_| REDACTED.to_string());
+        cfg.tron.private_key = redact_tron_key(cfg.tron.private_key);
+        for key in &mut cfg.tron.private_keys {
+            *key = redact_tron_key(*key);
+        }
+        if let Some(remote) = cfg.tron.remote_signer.as_mut() {
+            remote.url = redact_url(&remote.url);
+            if let Some((_, value)) = remote.auth_header.as_mut() {
+                *value = REDACTED.to_string();
+            }
+        }
+        for p in &mut cfg.tron.energy_rental_providers {
+            p.url = redact_url(&p.url);
+            for v in p.headers.values_mut() {
+                *v = REDACTED.to_string();
+            }
+            redact_json_strings(&mut p.body);
+            if let Some(q) = p.quote.as_mut() {
+                q.url = redact_url(&q.url);
+                for v in q.headers.values_mut() {
+                    *v = REDACTED.to_string();
+                }
+                redact_json_strings(&mut q.body);
+            }
+        }
+
+        cfg.pricing.trx_usd_url = redact_url(&cfg.pricing.trx_usd_url);
+        cfg.pricing.eth_usd_url = redact_url(&cfg.pricing.eth_usd_url);
+
+        cfg.fatal_webhook.url = cfg.fatal_webhook.url.as_deref().map(redact_url);
+        cfg.fatal_webhook.hmac_secret = cfg
+            .fatal_webhook
+            .hmac_secret
+            .as_ref()
+            .map(|_| REDACTED.to_string());
+        cfg.summary.webhook_url = cfg.summary.webhook_url.as_deref().map(redact_url);
+
+        // Keys are fixed-size arrays, so they were zeroed above; swap their debug form for a marker
+        // so the output doesn't suggest an all-zero key is configured.
+        format!("{cfg:?}").replace(&format!("{:?}", [0u8; 32]), REDACTED)
+    }
+}
+
+fn redact_tron_key(key: TronKey) -> TronKey {
+    match key {
+        TronKey::Local(_) => TronKey::UNSET,
+        TronKey::Remote(_) => key,
+    }
+}
+
+fn redact_hub(hub: &mut HubConfig) {
+    hub.rpc_url = redact_url(&hub.rpc_url);
+    for url in &mut hub.bundler_urls {
+        *url = redact_url(url);
+    }
+    for pm in &mut hub.paymasters {
+        pm.url = redact_url(&pm.url);
+        redact_json_strings(&mut pm.context);
+    }
+    if let HubKey::Local(_) = hub.signer_key {
+        hub.signer_key = HubKey::Local([0u8; 32]);
+    }
+    if let Some(remote) = &mut hub.remote_signer {
+        remote.url = redact_url(&remote.url);
+        remote.auth_token = remote.auth_token.as_ref().map(|_| REDACTED.to_string());
+    }
+    if let Some(funder) = &mut hub.deposit_funder
+        && let Some(HubKey::Local(_)) = funder.key
+    {
+        funder.key = Some(HubKey::Local([0u8; 32]));
+    }
+    if let Some(relayer) = &mut hub.prove_relayer {
+        relayer.url = redact_url(&relayer.url);
+    }
+}
+
+/// Keeps `scheme://host[:port]` and drops userinfo, path and query.
+pub fn redact_url(raw: &str) -> String {
+    if raw.trim().is_empty() {
+        return String::new();
+    }
+    let Ok(url) = url::Url::parse(raw.trim()) else {
+        return REDACTED.to_string();
+    };
+    let Some(host) = url.host_str() else {
+        return REDACTED.to_string();
+    };
+    let mut out = format!("{}://{host}", url.scheme());
+    if let Some(port) = url.port() {
+        out.push_str(&format!(":{port}"));
+    }
+    let has_path = !matches!(url.path(), "" | "/");
+    if has_path || url.query().is_some() || !url.username().is_empty() || url.password().is_some() {
+        out.push('/');
+        out.push_str(REDACTED);
+    }
+    out
+}
+
+fn redact_json_strings(v: &mut Value) {
+    match v {
+        Value::String(s) => *s = REDACTED.to_string(),
+        Value::Array(items) => items.iter_mut().for_each(redact_json_strings),
+        Value::Object(map) => map.values_mut().for_each(redact_json_strings),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PaymasterServiceConfig, env::Env, load::config_from_env};
+
+    #[test]
+    fn redact_url_keeps_only_scheme_host_port() {
+        assert_eq!(
+            redact_url("https://eth-mainnet.g.alchemy.com/v2/SECRETKEY"),
+            "https://eth-mainnet.g.alchemy.com/<redacted>"
+        );
+        assert_eq!(
+            redact_url("postgres://user:hunter2@db:5432/untron"),
+            "postgres://db:5432/<redacted>"
+        );
+        assert_eq!(redact_url("http://127.0.0.1:8545"), "http://127.0.0.1:8545");
+        assert_eq!(redact_url("not a url"), REDACTED);
+    }
+
+    #[test]
+    fn redacted_debug_contains_no_secrets() {
+        let hub_key = "ab".repeat(32);
+        let tron_key = "cd".repeat(32);
+        let tron_key_2 = "ef".repeat(32);
+        let funder_key = [0x42u8; 32];
+        let funder = alloy::signers::local::PrivateKeySigner::from_slice(&funder_key)
+            .unwrap()
+            .address();
+        let env = Env {
+            solver_db_url: "postgres://solver:dbpass123@db:5432/untron".to_string(),
+            indexer_api_base_url: "https://indexer.example/api?token=idxtoken".to_string(),
+            hub_rpc_url: "https://rpc.example/v2/rpckey456".to_string(),
+            hub_pool_address: "0x0000000000000000000000000000000000000001".to_string(),
+            hub_signer_private_key_hex: format!("0x{hub_key}"),
+            hub_remote_signer_url: "https://signer.example".to_string(),
+            hub_remote_signer_auth_token: "hubsignertoken".to_string(),
+            hub_deposit_funder_address: funder.to_string(),
+            hub_deposit_funder_private_key_hex: hex::encode(funder_key),
+            tron_grpc_url: "https://grpc.trongrid.example/tronkey789".to_string(),
+            tron_api_key: Some("trongridapikey".to_string()),
+            tron_private_key_hex: format!("0x{tron_key}"),
+            tron_private_keys_hex_csv: format!("0x{tron_key_2}"),
+            tron_remote_signer_url: "https://tron-signer.example".to_string(),
+            tron_remote_signer_auth_header: "X-Signer-Key: tronsignertoken".to_string(),
+            tron_controller_address: "TController".to_string(),
+            tron_energy_rental_apis_json: r#"[{
+                "name": "prov",
+                "url": "https://rent.example/order?apikey=rentkey1",
+                "headers": {"X-API-KEY": "rentheaderkey"},
+                "body": {"secret": "rentbodykey", "amount": "{{amount}}"},
+                "response": {"success_pointer": "/ok"}
+            }]"#
+            .to_string(),
+            solver_trx_usd_url: "https://pro-api.example/price?x_cg_pro_api_key=cgkey".to_string(),
+            solver_fatal_webhook_url: "https://hooks.example/services/hookpath42".to_string(),
+            solver_fatal_webhook_hmac_secret: "hookhmacsecret".to_string(),
+            solver_summary_webhook_url: "https://discord.example/api/webhooks/summaryhook7"
+                .to_string(),
+            hub_extra_pools_json: r#"[{
+                "pool_id": "second",
+                "pool_address": "0x0000000000000000000000000000000000000002",
+                "indexer_base_url": "https://indexer2.example/api?token=idx2token",
+                "rpc_url": "https://rpc2.example/v2/rpc2key"
+            }]"#
+            .to_string(),
+            ..Env::default()
+        };
+        let mut cfg = config_from_env(env).expect("config");
+        cfg.hub.bundler_urls = vec!["https://bundler.example/rpc?apikey=bundlerkey".to_string()];
+        cfg.hub.paymasters = vec![PaymasterServiceConfig {
+            url: "https://pm.example/pmkey".to_string(),
+            context: serde_json::json!({"sponsorshipPolicyId": "pmpolicysecret"}),
+            address: None,
+        }];
+
+        let out = cfg.redacted_debug();
+
+        let secrets = [
+            "dbpass123",
+            "idxtoken",
+            "rpckey456",
+            "tronkey789",
+            "trongridapikey",
+            "rentkey1",
+            "rentheaderkey",
+            "rentbodykey",
+            "cgkey",
+            "bundlerkey",
+            "pmkey",
+            "pmpolicysecret",
+            "hookpath42",
+            "hookhmacsecret",
+            "summaryhook7",
+            "idx2token",
+            "rpc2key",
+            "hubsignertoken",
+            "tronsignertoken",
+        ];
+        for s in secrets {
+            assert!(!out.contains(s), "secret {s:?} leaked: {out}");
+        }
+        let local = |key: TronKey| match key {
+            TronKey::Local(pk) => pk,
+            TronKey::Remote(_) => unreachable!("only local keys configured"),
+        };
+        let HubKey::Local(hub_pk) = cfg.hub.signer_key else {
+            unreachable!("hub key is local");
+        };
+        for key in [
+            hub_pk,
+            funder_key,
+            local(cfg.tron.private_key),
+            local(cfg.tron.private_keys[1]),
+        ] {
+            assert!(
+                !out.contains(&format!("{key:?}")),
+                "private key leaked: {out}"
+            );
+        }
+
+        // Non-secret settings are still present.
+        assert!(out.contains("rpc.example"));
+        assert!(out.contains("TController"));
+        assert!(out.contains("tick_interval"));
+    }
+}
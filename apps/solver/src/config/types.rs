@@ -1,6 +1,9 @@
 use aa::SafeDeterministicDeploymentConfig;
-use alloy::primitives::Address;
+use aa::paymaster::PaymasterFreezePolicy;
+use alloy::primitives::{Address, U256};
+use anyhow::Context;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::time::Duration;
 use tron::JsonApiRentalProviderConfig;
 
@@ -10,16 +13,74 @@ pub enum HubTxMode {
     Safe4337,
 }
 
+/// Who submits `proveIntentFill` for our fills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProveMode {
+    /// The solver's own hub account (EOA or Safe, per `HubTxMode`).
+    SelfSubmit,
+    /// A shared relayer: the solver POSTs the proof to it and waits for the indexer to report the
+    /// intent solved.
+    DelegatedRelayer,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TronMode {
     Grpc,
     Mock,
 }
 
+/// Shape of the Tron inclusion proof the hub's tx reader verifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TronProofFormat {
+    /// 20 signed block headers, the encoded tx, its merkle proof and index.
+    V1,
+}
+
+/// Tron network the gRPC endpoint must serve, identified by its genesis block id (whose last
+/// four bytes are the network's chain id).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TronNetwork {
+    Mainnet,
+    Nile,
+    Shasta,
+    /// Any other network (private nets, local nodes), by genesis block id.
+    Genesis([u8; 32]),
+}
+
+impl TronNetwork {
+    pub fn genesis_block_id(self) -> [u8; 32] {
+        let hex = match self {
+            TronNetwork::Mainnet => {
+                "00000000000000001ebf88508a03865c71d452e25f4d51194196a1d22b6653dc"
+            }
+            TronNetwork::Nile => "0000000000000000d698d4192c56cb6be724a558448e2684802de4d6cd8690dc",
+            TronNetwork::Shasta => {
+                "0000000000000000de1aa88295e1fcf982742f773e0419c5a9c134c994a9059e"
+            }
+            TronNetwork::Genesis(id) => return id,
+        };
+        let mut out = [0u8; 32];
+        hex::decode_to_slice(hex, &mut out).expect("valid genesis block id");
+        out
+    }
+}
+
+/// `Follower` is a hot standby: it checks health and reports metrics but never inserts, leases
+/// or claims jobs until promoted (SIGUSR1 or a `solver.instance_promotions` row).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverRole {
+    Active,
+    Follower,
+}
+
 #[derive(Debug, Clone)]
 pub struct PolicyConfig {
     pub enabled_intent_types: Vec<crate::types::IntentType>,
     pub min_deadline_slack_secs: u64,
+    /// Block-based deadlines are compared against the hub head with `min_deadline_slack_blocks`
+    /// instead of the wall clock with `min_deadline_slack_secs`.
+    pub deadline_kind: crate::types::DeadlineKind,
+    pub min_deadline_slack_blocks: u64,
     pub min_profit_usd: f64,
     /// Fallback hub tx cost used when we don't have enough historical receipt data.
     pub hub_cost_usd: f64,
@@ -27,7 +88,11 @@ pub struct PolicyConfig {
     pub hub_cost_history_lookback: u64,
     /// Extra headroom applied to the hub cost estimate (ppm, i.e. 100_000 = +10%).
     pub hub_cost_headroom_ppm: u64,
+    /// Fallback Tron fee per tx used until `tron_tx_costs` has history for the intent type.
     pub tron_fee_usd: f64,
+    /// Per-intent-type overrides of `tron_fee_usd`, so a fresh solver can start from realistic
+    /// costs (a TriggerSmartContract burns far more than a TRX transfer).
+    pub tron_fee_usd_by_type: HashMap<crate::types::IntentType, f64>,
     /// Number of recent confirmed Tron txs per intent type used to estimate Tron fees.
     pub tron_fee_history_lookback: u64,
     /// Extra headroom applied to the Tron fee estimate (ppm, i.e. 100_000 = +10%).
@@ -35,11 +100,29 @@ pub struct PolicyConfig {
     pub capital_lock_ppm_per_day: u64,
     pub require_priced_escrow: bool,
     pub allowed_escrow_tokens: Vec<Address>,
+    /// Hub escrow tokens we fill at all, priced or not. A non-empty list rejects every other
+    /// token, keeping fee-on-transfer or otherwise non-standard escrows out of settlement.
+    pub escrow_token_allowlist: Vec<Address>,
 
     pub trigger_contract_allowlist: Vec<Address>,
     pub trigger_contract_denylist: Vec<Address>,
     pub trigger_selector_denylist: Vec<[u8; 4]>,
     pub trigger_allow_fallback_calls: bool,
+    /// Per-selector TriggerSmartContract limits. In allowlist mode, selectors missing from the
+    /// map (and fallback calls) are rejected.
+    pub trigger_selector_budgets: HashMap<[u8; 4], TriggerSelectorBudget>,
+    pub trigger_selector_allowlist_mode: bool,
+
+    /// Recipient lists for transfer/delegate intents (`to` / `receiver`), as EVM-form addresses.
+    /// A non-empty allowlist rejects every recipient not on it.
+    pub tron_address_allowlist: Vec<Address>,
+    pub tron_address_denylist: Vec<Address>,
+    /// Target contract lists for TriggerSmartContract intents, as EVM-form addresses.
+    pub tron_contract_allowlist: Vec<Address>,
+    pub tron_contract_denylist: Vec<Address>,
+    /// Hub-side refund beneficiary lists. A non-empty allowlist rejects every beneficiary not on it.
+    pub refund_beneficiary_allowlist: Vec<Address>,
+    pub refund_beneficiary_denylist: Vec<Address>,
 
     pub max_trx_transfer_sun: Option<u64>,
     pub max_usdt_transfer_amount: Option<u64>,
@@ -47,6 +130,39 @@ pub struct PolicyConfig {
     pub max_delegate_lock_period_secs: Option<u64>,
     pub max_trigger_call_value_sun: Option<u64>,
     pub max_trigger_calldata_len: Option<u64>,
+
+    /// Cap on claim deposits (USDT base units) held across all unsettled jobs.
+    pub max_inflight_claim_deposit: Option<u64>,
+    /// Claims per minute across all intent types, enforced with a token bucket shared by all jobs
+    /// and instances (bursts of up to one minute's worth).
+    ///
+    /// Unlike `JobConfig::rate_limit_claims_per_minute_global`, which counts claims per calendar
+    /// minute and so lets up to twice its limit through around a minute boundary, the bucket
+    /// refills continuously and bounds every 60s span. Keep the window limit for coarse per-minute
+    /// accounting and use this one when claim bursts must stay smooth.
+    pub max_claims_per_minute: Option<u64>,
+
+    /// Only attempt intents at least this old (from their creation block timestamp), e.g. to let
+    /// the hub chain settle before racing for a claim.
+    pub min_intent_age_secs: Option<u64>,
+
+    /// Cap on a single intent's escrow value in USD, independent of profitability.
+    pub max_intent_value_usd: Option<f64>,
+    /// Per-intent-type overrides of `max_intent_value_usd`.
+    pub max_intent_value_usd_by_type: HashMap<crate::types::IntentType, f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct TriggerSelectorBudget {
+    /// Max emulated energy for a call with this selector; `None` means unbounded.
+    #[serde(default)]
+    pub max_energy: Option<u64>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -54,6 +170,9 @@ pub struct PaymasterServiceConfig {
     pub url: String,
     #[serde(default)]
     pub context: serde_json::Value,
+    /// Paymaster contract address, for deposit monitoring before the first sponsorship.
+    #[serde(default)]
+    pub address: Option<Address>,
 }
 
 #[derive(Debug, Clone)]
@@ -66,6 +185,57 @@ pub struct AppConfig {
     pub pricing: crate::pricing::PricingConfig,
     pub db_url: String,
     pub instance_id: String,
+    /// Log [`AppConfig::redacted_debug`] once at startup.
+    pub log_effective_config: bool,
+    /// Run the full candidate pipeline but never send claims, Tron txs or proofs; intents that
+    /// would have been claimed are recorded to `solver.dry_run_decisions` instead.
+    pub dry_run: bool,
+    pub role: SolverRole,
+    pub fatal_webhook: WebhookConfig,
+    pub summary: SummaryConfig,
+    /// Pools served in addition to the primary `hub` / `indexer` pair.
+    pub extra_pools: Vec<HubPoolConfig>,
+}
+
+impl AppConfig {
+    /// Every pool this process serves, primary first.
+    pub fn pools(&self) -> Vec<HubPoolConfig> {
+        let primary = HubPoolConfig {
+            hub: self.hub.clone(),
+            indexer: self.indexer.clone(),
+        };
+        std::iter::once(primary)
+            .chain(self.extra_pools.iter().cloned())
+            .collect()
+    }
+}
+
+/// One `UntronIntents` deployment: the hub chain it lives on and the indexer that tracks it.
+#[derive(Debug, Clone)]
+pub struct HubPoolConfig {
+    pub hub: HubConfig,
+    pub indexer: IndexerConfig,
+}
+
+/// Best-effort notification endpoint for jobs that end in `failed_fatal`.
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// `None` disables delivery.
+    pub url: Option<String>,
+    /// When set, each request carries `X-Untron-Signature: sha256=<hex>` over the raw body.
+    pub hmac_secret: Option<String>,
+    pub timeout: Duration,
+}
+
+/// Periodic human-readable health summary for operators without a metrics stack.
+#[derive(Debug, Clone)]
+pub struct SummaryConfig {
+    /// `None` disables the summary.
+    pub interval: Option<Duration>,
+    /// Also POST each summary here; `None` only logs it.
+    pub webhook_url: Option<String>,
+    pub webhook_timeout: Duration,
+    pub key_reserve_sun: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -73,14 +243,24 @@ pub struct IndexerConfig {
     pub base_url: String,
     pub timeout: Duration,
     pub max_head_lag_blocks: u64,
+    /// While a pool stays over `max_head_lag_blocks`, the lag warning is logged at most this often.
+    pub lag_warn_interval: Duration,
+    /// A failed `/health` check is retried this many times (`health_retry_delay` apart) before
+    /// the pool is treated as down for the tick, so a single blip doesn't cost a tick of fills.
+    pub health_retries: u32,
+    pub health_retry_delay: Duration,
 }
 
 #[derive(Debug, Clone)]
 pub struct HubConfig {
+    /// Label persisted on every job from this pool so it is claimed and proved on the same pool.
+    pub pool_id: String,
     pub tx_mode: HubTxMode,
     pub rpc_url: String,
     pub chain_id: Option<u64>,
     pub pool: Address,
+    /// USDT deposit locked per claim (pool's `INTENT_CLAIM_DEPOSIT`; checked at startup).
+    pub claim_deposit: u64,
 
     // AA/Safe4337 options (only used when tx_mode == Safe4337).
     pub entrypoint: Option<Address>,
@@ -89,11 +269,165 @@ pub struct HubConfig {
     pub safe_deployment: Option<SafeDeterministicDeploymentConfig>,
     pub bundler_urls: Vec<String>,
     pub paymasters: Vec<PaymasterServiceConfig>,
+    /// Skip a paymaster for a while after repeated sponsorship failures.
+    pub paymaster_freeze: PaymasterFreezePolicy,
+    /// Self-pay userops when every paymaster fails (instead of failing the submission).
+    pub paymaster_self_funded_fallback: bool,
+    /// Warn when a paymaster's EntryPoint deposit is below this (zero disables the warning).
+    pub paymaster_deposit_min_wei: U256,
+    /// How often to read paymaster EntryPoint deposits (`None` = disabled).
+    pub paymaster_deposit_check_interval: Option<Duration>,
+    /// How long `USDT()`, `V3()`, `tronUsdt()` and `CONTROLLER_ADDRESS()` results are reused.
+    pub address_cache_ttl: Duration,
+    /// Before claiming, simulate the claim and a placeholder prove against hub state and skip
+    /// the intent (`simulation_failed`) if either reverts on the pool side. Needs an RPC that
+    /// serves `eth_simulateV1`.
+    pub simulate_before_claim: bool,
+    /// EOA mode: confirmations (the inclusion block counts as one) a claim/prove tx needs before
+    /// the job advances. Guards against reorgs on the hub chain; 1 = first receipt.
+    pub min_confirmations: u64,
 
-    /// Private key used to sign hub chain transactions.
-    /// - In EOA mode: the EOA's private key.
-    /// - In Safe4337 mode: the Safe owner key.
-    pub signer_private_key: [u8; 32],
+    /// Account used to sign hub chain transactions.
+    /// - In EOA mode: the EOA.
+    /// - In Safe4337 mode: the Safe owner.
+    pub signer_key: HubKey,
+    /// Signing service for `HubKey::Remote`.
+    pub remote_signer: Option<HubRemoteSignerConfig>,
+    /// Account that supplies claim deposits instead of the signer/Safe (`None` = the solver pays).
+    pub deposit_funder: Option<DepositFunderConfig>,
+    pub prove_mode: ProveMode,
+    /// Set iff `prove_mode` is `DelegatedRelayer`.
+    pub prove_relayer: Option<ProveRelayerConfig>,
+    /// Most proved jobs whose `proveIntentFill` calls share one Safe4337 userop (through
+    /// `multisend`). 1 disables batching.
+    pub prove_batch_max: usize,
+    /// Safe `MultiSendCallOnly` address; set whenever `prove_batch_max > 1`.
+    pub multisend: Option<Address>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProveRelayerConfig {
+    pub url: String,
+    pub timeout: Duration,
+    /// POST the proof again if the intent still isn't solved this long after the last handoff.
+    pub resubmit_after: Duration,
+}
+
+/// Separate USDT "spend" account for claim deposits.
+///
+/// `claimIntent` pulls the deposit from the tx sender, so the solver address still initiates
+/// every claim; before claiming it tops itself up from the funder via `transferFrom`.
+#[derive(Debug, Clone)]
+pub struct DepositFunderConfig {
+    pub address: Address,
+    /// Lets the solver approve itself on the funder's behalf, with an in-process key or through
+    /// `HubConfig.remote_signer`. Without it the funder must grant the solver address a USDT
+    /// allowance out of band.
+    pub key: Option<HubKey>,
+}
+
+/// The hub signing account: an in-process key, or an address whose key is held by
+/// `HubConfig.remote_signer`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HubKey {
+    Local([u8; 32]),
+    Remote(Address),
+}
+
+impl std::fmt::Debug for HubKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HubKey::Local(_) => f.write_str("Local(..)"),
+            HubKey::Remote(addr) => write!(f, "Remote({addr})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HubRemoteSignerConfig {
+    pub url: String,
+    pub timeout: Duration,
+    /// Sent as `Authorization: Bearer <token>` with every signing request.
+    pub auth_token: Option<String>,
+}
+
+impl HubConfig {
+    /// Signer for `signer_key`, local or through the remote signer.
+    pub fn signer(&self) -> anyhow::Result<std::sync::Arc<dyn aa::HubSigner>> {
+        self.signer_for(self.signer_key, "HUB_SIGNER_PRIVATE_KEY_HEX")
+    }
+
+    /// Signer for the deposit funder's key, if one is configured.
+    pub fn deposit_funder_signer(
+        &self,
+    ) -> anyhow::Result<Option<std::sync::Arc<dyn aa::HubSigner>>> {
+        self.deposit_funder
+            .as_ref()
+            .and_then(|f| f.key)
+            .map(|key| self.signer_for(key, "HUB_DEPOSIT_FUNDER_PRIVATE_KEY_HEX"))
+            .transpose()
+    }
+
+    fn signer_for(
+        &self,
+        key: HubKey,
+        key_env: &str,
+    ) -> anyhow::Result<std::sync::Arc<dyn aa::HubSigner>> {
+        match key {
+            HubKey::Local(pk) => Ok(std::sync::Arc::new(
+                aa::LocalHubSigner::new(pk).with_context(|| format!("invalid {key_env}"))?,
+            )),
+            HubKey::Remote(addr) => {
+                let remote = self
+                    .remote_signer
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("no remote signer for hub key {addr}"))?;
+                Ok(std::sync::Arc::new(aa::RemoteHubSigner::new(
+                    remote.url.clone(),
+                    remote.timeout,
+                    remote.auth_token.clone(),
+                    addr,
+                )?))
+            }
+        }
+    }
+}
+
+/// A configured Tron account: an in-process key, or an address whose key is held by
+/// `TronConfig.remote_signer`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TronKey {
+    Local([u8; 32]),
+    Remote(tron::TronAddress),
+}
+
+impl TronKey {
+    /// Placeholder for `TronConfig.private_key` in mock mode.
+    pub const UNSET: TronKey = TronKey::Local([0u8; 32]);
+
+    pub fn address(&self) -> anyhow::Result<tron::TronAddress> {
+        match self {
+            TronKey::Local(pk) => Ok(tron::LocalTronSigner::new(*pk)?.address()),
+            TronKey::Remote(addr) => Ok(*addr),
+        }
+    }
+}
+
+impl std::fmt::Debug for TronKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TronKey::Local(_) => f.write_str("Local(..)"),
+            TronKey::Remote(addr) => write!(f, "Remote({})", addr.to_base58check()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TronRemoteSignerConfig {
+    pub url: String,
+    pub timeout: Duration,
+    /// `(name, value)` header sent with every signing request.
+    pub auth_header: Option<(String, String)>,
 }
 
 #[derive(Debug, Clone)]
@@ -102,12 +436,24 @@ pub struct TronConfig {
     pub mode: TronMode,
     pub grpc_url: String,
     pub api_key: Option<String>,
+    /// Per-attempt deadline for every Tron gRPC call. `None` waits indefinitely.
+    pub grpc_timeout: Option<Duration>,
+    /// Retries after a timeout or `UNAVAILABLE` status, inside `TronGrpc`.
+    pub grpc_max_retries: u32,
+    /// After a failed Tron health ping, skip discovery and leasing of Tron-stage jobs for this long
+    /// before pinging again (`None` = no health gate). Jobs that only need the hub keep running.
+    pub health_gate_cooldown: Option<Duration>,
     /// Default Tron key (back-compat; also used when only one key is configured).
-    pub private_key: [u8; 32],
+    pub private_key: TronKey,
     /// All configured Tron keys (one or more) for inventory selection and consolidation.
-    pub private_keys: Vec<[u8; 32]>,
+    pub private_keys: Vec<TronKey>,
+    /// Signing service for `TronKey::Remote` accounts.
+    pub remote_signer: Option<TronRemoteSignerConfig>,
     pub controller_address: String,
     pub mock_reader_address: Option<Address>,
+    pub proof_format: TronProofFormat,
+    /// Refuse to start unless the gRPC node's genesis block matches (`None` = unchecked).
+    pub expected_network: Option<TronNetwork>,
 
     pub block_lag: u64,
     pub fee_limit_cap_sun: u64,
@@ -126,22 +472,118 @@ pub struct TronConfig {
     pub rental_provider_fail_window_secs: i64,
     /// Rental provider freeze duration in seconds.
     pub rental_provider_freeze_secs: i64,
+    /// Expiry of the per-job lock held while placing an energy rental order; should outlast a
+    /// pass over every provider.
+    pub rental_order_lock_secs: u64,
+    /// Prepared txs above this size fail with `tx_too_large` instead of being broadcast.
+    pub max_tx_size_bytes: Option<u64>,
     /// When converting `balanceSun` -> energy units for rental APIs, add headroom (ppm).
     pub resell_energy_headroom_ppm: u64,
+    /// How long (seconds) a pre-claim energy rental quote is reused for the same intent.
+    pub rental_quote_ttl_secs: u64,
+    /// Quotes costing more than this (TRX) are ignored. `None` = unlimited.
+    pub rental_quote_max_cost_trx: Option<f64>,
+    /// Energy rental orders (resold delegations, sponsored transfers and the solver's own top-ups)
+    /// whose quoted cost exceeds this (USD) aren't placed; per-provider `max_rental_cost_usd` caps
+    /// apply on top. `None` = unlimited.
+    pub rental_max_cost_usd: Option<f64>,
+    /// After a broadcast fails with OUT_OF_ENERGY, skip the sending key for new plans (and delay
+    /// the job's retry when no rental could be requested) for this many seconds. 0 disables.
+    pub out_of_energy_cooldown_secs: u64,
+    /// If true, USDT transfers whose estimated energy exceeds what the executor key has staked get
+    /// the shortfall rented from the configured providers before broadcast, instead of burning TRX.
+    pub sponsor_usdt_transfer_energy: bool,
+    /// Max TRX (SUN) / USDT (base units) any single key may sign away in fill plans over a
+    /// rolling 24h window, including consolidation pre-txs. `None` = unlimited. Proactive sweeps
+    /// between our own keys don't count.
+    pub key_daily_limit_sun: Option<u64>,
+    pub key_daily_limit_usdt: Option<u64>,
+    /// If true, TriggerSmartContract intents whose selector is in `trigger_spend_selectors` get a
+    /// USDT `approve(target, amount)` from the executor key when its allowance is short (reset to 0
+    /// first if it's partial), and the call is planned once it lands. Emulation can't see past a
+    /// pending approve, so with emulation on such intents are skipped before claiming.
+    pub auto_approve_trigger_spend: bool,
+    /// Token-spending selectors mapped to the ABI argument index holding the USDT amount pulled
+    /// from the caller. The pull counts towards the intent's cost, `max_usdt_transfer_amount` and
+    /// the executor key's `key_daily_limit_usdt`.
+    pub trigger_spend_selectors: HashMap<[u8; 4], usize>,
+    /// If true (and TRON_MODE=grpc), freeze more TRX on our own key whenever no key has the
+    /// target delegatable capacity for a resource, so non-resell delegations don't run dry.
+    pub auto_stake_enabled: bool,
+    /// Delegatable (staked, not yet delegated) SUN to keep available per resource. 0 disables.
+    pub auto_stake_target_energy_sun: u64,
+    pub auto_stake_target_bandwidth_sun: u64,
+    /// Max TRX (SUN) frozen by a single top-up. `None` = unlimited.
+    pub auto_stake_max_per_tx_sun: Option<u64>,
+    /// Liquid TRX (SUN) never staked, so the key can still pay for its own transactions.
+    pub auto_stake_reserve_sun: u64,
+    /// How often (at most) an instance checks capacity and tops it up.
+    pub auto_stake_interval: Duration,
 
     /// If true (and TRON_MODE=grpc), run pre-claim emulation checks for contract-call intents.
     pub emulation_enabled: bool,
+    /// If true, re-derive the sender from every signed Tron tx and refuse to persist it unless it
+    /// matches the key we signed with (guards against a node returning a foreign tx skeleton).
+    pub sender_check_enabled: bool,
+    /// If true, decode the included Tron tx before proving it and fail the job (`fill_mismatch`)
+    /// unless its recipient, amount and contract match the intent.
+    pub fill_verification_enabled: bool,
+}
+
+impl TronConfig {
+    /// Wallet that signs as `key`, locally or through the remote signer.
+    pub fn wallet(&self, key: TronKey) -> anyhow::Result<tron::TronWallet> {
+        match key {
+            TronKey::Local(pk) => tron::TronWallet::new(pk),
+            TronKey::Remote(addr) => {
+                let remote = self
+                    .remote_signer
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("no remote signer for Tron key {key:?}"))?;
+                let signer = tron::RemoteTronSigner::new(
+                    remote.url.clone(),
+                    remote.timeout,
+                    remote.auth_header.clone(),
+                    addr,
+                )?;
+                Ok(tron::TronWallet::with_signer(std::sync::Arc::new(signer)))
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct JobConfig {
     pub tick_interval: Duration,
+    /// How long a leased job stays owned by this instance without a heartbeat.
+    pub lease_secs: u64,
+    /// Retry backoff jitter (+/- percent). Kept below 33% so consecutive steps never overlap.
+    pub retry_jitter_pct: u64,
     pub tron_finality_blocks: u64,
+    /// Blocks the Tron tx block must be buried under (or be solidified) before building a proof.
+    pub tron_proof_min_confirmations: u64,
+    /// Max serialized proof size; a larger proof fails the job with `proof_too_large`.
+    pub tron_proof_max_bytes: Option<u64>,
+    /// Fail the job once proveIntentFill has rejected this many rebuilt proofs
+    /// (`solver.jobs.proof_rebuilds`). 0 keeps rebuilding.
+    pub tron_proof_max_rebuilds: u64,
     pub tip_proof_resend_blocks: u64,
+    /// How long tron_prepared waits for each broadcast tx to be included before giving up.
+    pub tron_inclusion_timeout_secs: u64,
+    /// Poll interval while waiting for inclusion.
+    pub tron_inclusion_poll_interval: Duration,
+    /// Once a tx has been sent, how many blocks past the broadcast height `build_proof` keeps
+    /// looking for it before failing with `tx_not_included`.
+    pub tron_max_inclusion_scan_blocks: Option<u64>,
+    /// Store new proofs zstd-compressed; rows load either way.
+    pub tron_proof_compress: bool,
 
     pub process_controller_max_events: u64,
     pub fill_max_claims: u64,
+    /// When a pool has more open intents than this (e.g. after downtime), fetch candidates
+    /// nearest-deadline first and skip ones already inside the deadline slack. 0 disables.
+    pub backlog_deadline_first_threshold: u64,
     pub max_in_flight_jobs: u64,
     /// Max number of claimed/unproved jobs allowed before new Safe4337 claims are backpressured.
     pub safe4337_max_claimed_unproved_jobs: u64,
@@ -153,11 +595,16 @@ pub struct JobConfig {
     pub concurrency_trigger_smart_contract: u64,
     /// Max concurrent Tron broadcasts (avoid ref-block collisions / node overload).
     pub concurrency_tron_broadcast: u64,
+    /// Max concurrent Tron proof builds (block/tx fetches against the Tron node).
+    pub concurrency_tron_proof: u64,
 
     /// Enable consolidation pre-transactions for TRX/USDT intents (moves funds into executor key).
     pub consolidation_enabled: bool,
     /// Maximum number of pre-transactions per job.
     pub consolidation_max_pre_txs: u64,
+    /// Pre-transactions broadcast per tick across all jobs (`None` = unlimited). Jobs past the
+    /// budget are retried on a later tick.
+    pub max_pre_txs_per_tick: Option<u64>,
     /// Maximum total TRX pulled into executor across all pre-transactions (SUN). 0 = unlimited.
     pub consolidation_max_total_trx_pull_sun: u64,
     /// Maximum TRX pulled in a single pre-transaction (SUN). 0 = unlimited.
@@ -166,8 +613,13 @@ pub struct JobConfig {
     pub consolidation_max_total_usdt_pull_amount: u64,
     /// Maximum USDT pulled in a single pre-transaction (token base units). 0 = unlimited.
     pub consolidation_max_per_tx_usdt_pull_amount: u64,
+    /// Sweep balances into one executor key on ticks with no leased jobs (same caps as above).
+    pub proactive_consolidation: bool,
+    /// How often (at most) an instance attempts a sweep.
+    pub proactive_consolidation_interval: Duration,
 
-    /// Rate limit: max claim submissions per minute (global). 0 = unlimited.
+    /// Rate limit: max claim submissions per calendar minute (global). 0 = unlimited. See
+    /// `PolicyConfig::max_claims_per_minute` for a smoothed (token bucket) limit.
     pub rate_limit_claims_per_minute_global: u64,
     /// Rate limit: max claim submissions per minute per intent type. 0 = unlimited.
     pub rate_limit_claims_per_minute_trx_transfer: u64,
@@ -175,6 +627,11 @@ pub struct JobConfig {
     pub rate_limit_claims_per_minute_delegate_resource: u64,
     pub rate_limit_claims_per_minute_trigger_smart_contract: u64,
 
+    /// Safe4337 only: fail the job once its userops have been rejected in account validation this
+    /// many submissions in a row (`solver.jobs.hub_structural_failures`). 0 keeps retrying (with
+    /// structural backoff).
+    pub hub_userop_validation_fatal_attempts: u64,
+
     /// Optional auto-pause when fatal errors spike. 0 disables auto-pause.
     pub global_pause_fatal_threshold: u64,
     pub global_pause_window_secs: u64,
@@ -185,6 +642,16 @@ pub struct JobConfig {
 
     /// Capacity reservation TTL for delegate jobs (seconds).
     pub delegate_reservation_ttl_secs: u64,
+    /// Release the hub claim (`unclaimIntent`) of a job still in `claimed` / `tron_prepared` this
+    /// many seconds after claiming. Never fires before the pool's fill window ends. 0 disables.
+    pub abandon_after_secs: u64,
+
+    /// Archive terminal jobs whose last update is older than this many days. 0 disables archival.
+    pub archive_after_days: u64,
+    /// How often (at most) an instance attempts archival.
+    pub archive_interval: Duration,
+    /// Max jobs moved per archival pass.
+    pub archive_batch_size: u64,
 
     pub controller_rebalance_threshold_usdt: String,
     pub controller_rebalance_keep_usdt: String,
@@ -0,0 +1,260 @@
+use super::{AppConfig, PolicyConfig, TronMode};
+use crate::types::IntentType;
+
+impl AppConfig {
+    /// Enabled intent types this config can't actually fill, with the reason for each.
+    ///
+    /// A solver with any of these would run, claim nothing (or fail every job) and look healthy,
+    /// so startup refuses them instead.
+    pub fn intent_type_misconfigurations(&self) -> Vec<String> {
+        let tron = &self.tron;
+        let mut out = Vec::new();
+        // The mock backend fills everything without keys or providers.
+        if tron.mode == TronMode::Mock {
+            return out;
+        }
+        for &ty in &self.policy.enabled_intent_types {
+            if tron.private_keys.is_empty() {
+                out.push(format!("{ty:?}: no Tron keys configured"));
+            }
+            match ty {
+                IntentType::DelegateResource
+                    if tron.delegate_resource_resell_enabled
+                        && tron.energy_rental_providers.is_empty() =>
+                {
+                    out.push(format!(
+                        "{ty:?}: TRON_DELEGATE_RESOURCE_RESELL_ENABLED is set but \
+                         TRON_ENERGY_RENTAL_APIS_JSON has no providers"
+                    ));
+                }
+                IntentType::UsdtTransfer
+                    if tron.sponsor_usdt_transfer_energy
+                        && tron.energy_rental_providers.is_empty() =>
+                {
+                    out.push(format!(
+                        "{ty:?}: TRON_SPONSOR_USDT_TRANSFER_ENERGY is set but \
+                         TRON_ENERGY_RENTAL_APIS_JSON has no providers"
+                    ));
+                }
+                _ => {}
+            }
+        }
+        out
+    }
+
+    /// True if energy rental orders are capped in USD, so the job loop needs a TRX/USD price.
+    pub fn has_rental_usd_caps(&self) -> bool {
+        let tron = &self.tron;
+        tron.rental_max_cost_usd.is_some()
+            || tron
+                .energy_rental_providers
+                .iter()
+                .any(|p| p.max_rental_cost_usd.is_some())
+    }
+
+    /// True if profitability checks for an enabled intent type price TRX-denominated costs, so
+    /// an unreachable TRX/USD source would skip every such intent.
+    pub fn needs_trx_usd_price(&self) -> bool {
+        let policy = &self.policy;
+        (policy.min_profit_usd > 0.0 || policy.require_priced_escrow)
+            && self.pricing.trx_usd_override.is_none()
+            && policy
+                .enabled_intent_types
+                .iter()
+                .any(|ty| *ty != IntentType::UsdtTransfer)
+    }
+}
+
+impl PolicyConfig {
+    /// Per-tx Tron fee used when `solver.tron_tx_costs` has no history for `intent_type` (or no
+    /// TRX price is available): `SOLVER_TRON_FEE_USD_BY_TYPE`, else `SOLVER_TRON_FEE_USD`.
+    pub fn static_tron_fee_usd(&self, intent_type: i16) -> f64 {
+        IntentType::from_i16(intent_type)
+            .ok()
+            .and_then(|ty| self.tron_fee_usd_by_type.get(&ty).copied())
+            .unwrap_or(self.tron_fee_usd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{HubKey, TronKey, env::Env, load::config_from_env};
+    use crate::types::IntentType;
+
+    fn env() -> Env {
+        Env {
+            solver_db_url: "postgres://db".to_string(),
+            indexer_api_base_url: "http://indexer".to_string(),
+            hub_rpc_url: "http://rpc".to_string(),
+            hub_pool_address: "0x0000000000000000000000000000000000000001".to_string(),
+            hub_signer_private_key_hex: "ab".repeat(32),
+            tron_private_key_hex: "cd".repeat(32),
+            tron_controller_address: "TController".to_string(),
+            ..Env::default()
+        }
+    }
+
+    #[test]
+    fn default_config_has_no_misconfigurations() {
+        let cfg = config_from_env(env()).unwrap();
+        assert!(cfg.intent_type_misconfigurations().is_empty());
+    }
+
+    #[test]
+    fn paymaster_self_funded_fallback_is_opt_in() {
+        let cfg = config_from_env(env()).unwrap();
+        assert!(!cfg.hub.paymaster_self_funded_fallback);
+        assert_ne!(
+            aa::Safe4337UserOpSenderOptions::default().paymaster_finalization,
+            aa::PaymasterFinalizationMode::SelfFundedFallback
+        );
+
+        let cfg = config_from_env(Env {
+            hub_paymaster_self_funded_fallback: true,
+            ..env()
+        })
+        .unwrap();
+        assert!(cfg.hub.paymaster_self_funded_fallback);
+    }
+
+    #[test]
+    fn resell_without_rental_providers_is_rejected() {
+        let cfg = config_from_env(Env {
+            tron_delegate_resource_resell_enabled: true,
+            ..env()
+        })
+        .unwrap();
+        let problems = cfg.intent_type_misconfigurations();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("DELEGATE_RESOURCE_RESELL"));
+
+        // Irrelevant when DelegateResource intents aren't filled.
+        let cfg = config_from_env(Env {
+            tron_delegate_resource_resell_enabled: true,
+            solver_enabled_intent_types: "trx_transfer".to_string(),
+            ..env()
+        })
+        .unwrap();
+        assert!(cfg.intent_type_misconfigurations().is_empty());
+    }
+
+    #[test]
+    fn sponsored_usdt_energy_without_providers_is_rejected() {
+        let cfg = config_from_env(Env {
+            tron_sponsor_usdt_transfer_energy: true,
+            solver_enabled_intent_types: "usdt_transfer".to_string(),
+            ..env()
+        })
+        .unwrap();
+        let problems = cfg.intent_type_misconfigurations();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("UsdtTransfer"));
+    }
+
+    #[test]
+    fn remote_signer_addresses_load_as_remote_keys() {
+        let addr = tron::TronAddress::from_evm(alloy::primitives::Address::repeat_byte(0x11));
+        let remote = addr.to_base58check();
+        let cfg = config_from_env(Env {
+            tron_private_key_hex: String::new(),
+            tron_remote_signer_url: "http://signer".to_string(),
+            tron_remote_signer_addresses_csv: remote.clone(),
+            ..env()
+        })
+        .unwrap();
+        assert_eq!(cfg.tron.private_keys, vec![TronKey::Remote(addr)]);
+        assert_eq!(cfg.tron.private_key, TronKey::Remote(addr));
+        assert!(cfg.tron.wallet(cfg.tron.private_key).is_ok());
+        assert!(cfg.intent_type_misconfigurations().is_empty());
+
+        // Remote addresses without a signer to ask are a config error.
+        assert!(
+            config_from_env(Env {
+                tron_remote_signer_addresses_csv: remote,
+                ..env()
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn hub_remote_signer_replaces_private_key() {
+        let addr = alloy::primitives::Address::repeat_byte(0x22);
+        let cfg = config_from_env(Env {
+            hub_signer_private_key_hex: String::new(),
+            hub_remote_signer_url: "http://signer".to_string(),
+            hub_remote_signer_address: addr.to_string(),
+            ..env()
+        })
+        .unwrap();
+        assert_eq!(cfg.hub.signer_key, HubKey::Remote(addr));
+        assert_eq!(cfg.hub.signer().unwrap().address(), addr);
+
+        // Both a local key and a remote address is ambiguous.
+        assert!(
+            config_from_env(Env {
+                hub_remote_signer_url: "http://signer".to_string(),
+                hub_remote_signer_address: addr.to_string(),
+                ..env()
+            })
+            .is_err()
+        );
+        // A remote address without a signer to ask is a config error.
+        assert!(
+            config_from_env(Env {
+                hub_signer_private_key_hex: String::new(),
+                hub_remote_signer_address: addr.to_string(),
+                ..env()
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn trx_price_needed_only_with_profitability_checks() {
+        let cfg = config_from_env(env()).unwrap();
+        assert!(!cfg.needs_trx_usd_price());
+
+        let cfg = config_from_env(Env {
+            solver_min_profit_usd: 0.5,
+            ..env()
+        })
+        .unwrap();
+        assert!(cfg.needs_trx_usd_price());
+
+        let cfg = config_from_env(Env {
+            solver_min_profit_usd: 0.5,
+            solver_enabled_intent_types: "usdt_transfer".to_string(),
+            ..env()
+        })
+        .unwrap();
+        assert!(!cfg.needs_trx_usd_price());
+    }
+
+    #[test]
+    fn static_tron_fee_prefers_per_type_fee() {
+        let policy = config_from_env(Env {
+            solver_tron_fee_usd: 1.0,
+            solver_tron_fee_usd_by_type: "usdt_transfer=3".to_string(),
+            ..env()
+        })
+        .unwrap()
+        .policy;
+        let fee = |ty: IntentType| policy.static_tron_fee_usd(ty as i16);
+        assert_eq!(fee(IntentType::UsdtTransfer), 3.0);
+        assert_eq!(fee(IntentType::TrxTransfer), 1.0);
+    }
+
+    #[test]
+    fn rental_usd_caps_apply_without_reselling() {
+        let cfg = config_from_env(env()).unwrap();
+        assert!(!cfg.has_rental_usd_caps());
+
+        let cfg = config_from_env(Env {
+            tron_rental_max_cost_usd: 5.0,
+            ..env()
+        })
+        .unwrap();
+        assert!(cfg.has_rental_usd_caps());
+    }
+}
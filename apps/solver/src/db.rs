@@ -2,15 +2,28 @@ use alloy::primitives::{Address, U256};
 use alloy::rpc::types::eth::erc4337::PackedUserOperation;
 use anyhow::{Context, Result};
 use sqlx::{Acquire, Executor, PgPool, Postgres, Row, postgres::PgPoolOptions};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+mod archive;
 mod breakers;
 mod hub_userops;
+mod instances;
 mod intents;
 mod jobs;
+mod key_spends;
 mod migrations;
 mod proofs;
+mod prove_relays;
+mod timeline;
+mod token_buckets;
 mod tron;
+mod unclaims;
+
+pub use breakers::ActiveBreaker;
+pub use proofs::TronProofCorruptedError;
+pub use timeline::JobTimelineEvent;
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -24,11 +37,18 @@ pub struct SolverJob {
     pub state: String,
     pub attempts: i32,
     pub tron_txid: Option<[u8; 32]>,
+    /// Tron head when `tron_txid` was sent; set on the first `tron_sent` tick.
+    pub tron_broadcast_block: Option<i64>,
+    /// `HubConfig::pool_id` of the pool the intent was discovered on.
+    pub pool_id: String,
 }
 
 #[derive(Clone)]
 pub struct SolverDb {
     pool: PgPool,
+    /// Local expiry of leases this instance holds, by job_id. Used to tell a lease that was taken
+    /// over while still valid locally (contention / clock skew) from one that simply expired.
+    leases: Arc<Mutex<HashMap<i64, Instant>>>,
 }
 
 #[derive(Debug, Clone)]
@@ -50,10 +70,22 @@ pub struct TronSignedTxRow {
     pub txid: [u8; 32],
     pub tx_bytes: Vec<u8>,
     pub fee_limit_sun: Option<i64>,
+    pub fee_limit_estimate_sun: Option<i64>,
     pub energy_required: Option<i64>,
     pub tx_size_bytes: Option<i64>,
 }
 
+/// One amount a Tron key signs away in a fill plan, counted against its daily limit.
+#[derive(Debug, Clone)]
+pub struct TronKeySpendRow {
+    pub txid: [u8; 32],
+    /// 21-byte `0x41`-prefixed owner address.
+    pub owner_address: Vec<u8>,
+    /// `"trx"` (SUN) or `"usdt"` (token base units).
+    pub asset: &'static str,
+    pub amount: u64,
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct TronRentalRow {
@@ -68,6 +100,25 @@ pub struct TronRentalRow {
     pub response_json: Option<serde_json::Value>,
 }
 
+/// See [`SolverDb::tron_tx_costs_fee_split`].
+#[derive(Debug, Clone, Copy)]
+pub struct TronFeeSplit {
+    /// Average fee minus the bandwidth burn: energy, mostly, which doesn't scale with tx size.
+    pub base_fee_sun: f64,
+    /// Average bandwidth burn per signed tx byte, counting txs covered by free or staked bandwidth
+    /// as zero.
+    pub net_fee_sun_per_byte: f64,
+}
+
+/// See [`SolverDb::fill_summary`].
+#[derive(Debug, Clone, Copy)]
+pub struct FillSummaryRow {
+    pub fills: i64,
+    /// Fills with a priced profit; the rest contribute nothing to `est_profit_usd`.
+    pub priced: i64,
+    pub est_profit_usd: f64,
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct IntentSkipSummaryRow {
@@ -96,6 +147,21 @@ pub struct DelegateReservationRow {
     pub expires_in_secs: i64,
 }
 
+#[derive(Debug, Clone)]
+pub struct RentalProviderStatsRow {
+    pub provider: String,
+    pub avg_order_ms: f64,
+    pub samples: i64,
+    pub last_order_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RequeuedJob {
+    pub job_id: i64,
+    pub from_state: crate::types::JobState,
+    pub to_state: crate::types::JobState,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HubUserOpKind {
     Claim,
@@ -123,6 +189,8 @@ pub struct HubUserOpRow {
     pub success: Option<bool>,
     pub receipt_json: Option<String>,
     pub attempts: i32,
+    /// Jobs whose rows share this row's userop (1 unless it is a batched prove).
+    pub batch_size: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -140,6 +208,9 @@ impl SolverDb {
             .connect(db_url)
             .await
             .context("connect SOLVER_DB_URL")?;
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            leases: Arc::default(),
+        })
     }
 }
@@ -0,0 +1,193 @@
+use super::*;
+
+impl SolverDb {
+    /// Moves terminal jobs (`done` / `failed_fatal` / `unclaimed` / `lost_race`) whose last update is
+    /// older than `older_than` into `solver.*_archive`, together with their hub userops, signed Tron
+    /// txs, energy rentals, Tron tx costs and Tron proofs. Also prunes Tron key spends older than
+    /// the 24h limit window.
+    ///
+    /// Only one instance archives at a time (transaction-scoped advisory lock). Returns `None` if
+    /// another instance currently holds the lock, otherwise the number of archived jobs.
+    pub async fn archive_terminal_jobs(
+        &self,
+        older_than: Duration,
+        limit: i64,
+    ) -> Result<Option<u64>> {
+        const ARCHIVE_LOCK_KEY: i64 = 0x554E_5452_4F4E_4152; // "UNTRONAR"
+
+        let secs: i64 = older_than.as_secs().try_into().unwrap_or(i64::MAX);
+        let mut tx = self.pool.begin().await.context("begin archive tx")?;
+
+        let locked: bool = sqlx::query_scalar("select pg_try_advisory_xact_lock($1)")
+            .bind(ARCHIVE_LOCK_KEY)
+            .fetch_one(&mut *tx)
+            .await
+            .context("acquire solver archive lock")?;
+        if !locked {
+            return Ok(None);
+        }
+
+        // Spends only matter inside the rolling 24h key limit window, so prune by age alone: this
+        // also drops rows whose job was archived before its spends aged out.
+        sqlx::query(
+            "delete from solver.tron_key_spends where created_at < now() - interval '24 hours'",
+        )
+        .execute(&mut *tx)
+        .await
+        .context("prune solver.tron_key_spends")?;
+
+        let job_ids: Vec<i64> = sqlx::query_scalar(
+            "select job_id from solver.jobs \
+             where state in ('done', 'failed_fatal', 'unclaimed', 'lost_race') \
+               and updated_at < now() - make_interval(secs => $1) \
+             order by job_id asc \
+             limit $2 \
+             for update skip locked",
+        )
+        .bind(secs)
+        .bind(limit.max(1))
+        .fetch_all(&mut *tx)
+        .await
+        .context("select archivable solver.jobs")?;
+
+        if job_ids.is_empty() {
+            tx.commit().await.context("commit archive tx")?;
+            return Ok(Some(0));
+        }
+
+        // Proofs are keyed by txid (not job_id), so move them via the job's final tron_txid.
+        sqlx::query(
+            "insert into solver.tron_proofs_archive( \
+                txid, blocks, encoded_tx, proof, index_dec, created_at, content_hash, encoding \
+             ) \
+             select p.txid, p.blocks, p.encoded_tx, p.proof, p.index_dec, p.created_at, \
+                    p.content_hash, p.encoding \
+             from solver.tron_proofs p \
+             join solver.jobs j on j.tron_txid = p.txid \
+             where j.job_id = any($1) \
+             on conflict (txid) do nothing",
+        )
+        .bind(&job_ids)
+        .execute(&mut *tx)
+        .await
+        .context("archive solver.tron_proofs")?;
+        sqlx::query(
+            "delete from solver.tron_proofs p \
+             using solver.jobs j \
+             where j.tron_txid = p.txid and j.job_id = any($1)",
+        )
+        .bind(&job_ids)
+        .execute(&mut *tx)
+        .await
+        .context("delete archived solver.tron_proofs")?;
+
+        // `tron_tx_costs.job_id` is `on delete set null`; move the rows explicitly so archived
+        // costs keep pointing at their (archived) job. Columns are listed because ones added after
+        // the archive table was created come after `archived_at` there.
+        sqlx::query(
+            "insert into solver.tron_tx_costs_archive( \
+                txid, job_id, fee_sun, energy_usage_total, net_usage, energy_fee_sun, net_fee_sun, \
+                block_number, block_timestamp, result_code, result_message, created_at, updated_at, \
+                intent_type, sponsored_energy, sponsored_energy_cost_sun, sponsored_energy_provider \
+             ) \
+             select \
+                c.txid, c.job_id, c.fee_sun, c.energy_usage_total, c.net_usage, c.energy_fee_sun, \
+                c.net_fee_sun, c.block_number, c.block_timestamp, c.result_code, c.result_message, \
+                c.created_at, c.updated_at, c.intent_type, c.sponsored_energy, \
+                c.sponsored_energy_cost_sun, c.sponsored_energy_provider \
+             from solver.tron_tx_costs c \
+             where c.job_id = any($1) \
+             on conflict (txid) do nothing",
+        )
+        .bind(&job_ids)
+        .execute(&mut *tx)
+        .await
+        .context("archive solver.tron_tx_costs")?;
+        sqlx::query("delete from solver.tron_tx_costs where job_id = any($1)")
+            .bind(&job_ids)
+            .execute(&mut *tx)
+            .await
+            .context("delete archived solver.tron_tx_costs")?;
+
+        sqlx::query(
+            "insert into solver.hub_userops_archive \
+             select u.* from solver.hub_userops u \
+             where u.job_id = any($1) \
+             on conflict (userop_id) do nothing",
+        )
+        .bind(&job_ids)
+        .execute(&mut *tx)
+        .await
+        .context("archive solver.hub_userops")?;
+
+        sqlx::query(
+            "insert into solver.tron_signed_txs_archive( \
+                txid, job_id, step, tx_bytes, fee_limit_sun, energy_required, tx_size_bytes, \
+                created_at, updated_at, fee_limit_estimate_sun \
+             ) \
+             select \
+                s.txid, s.job_id, s.step, s.tx_bytes, s.fee_limit_sun, s.energy_required, \
+                s.tx_size_bytes, s.created_at, s.updated_at, s.fee_limit_estimate_sun \
+             from solver.tron_signed_txs s \
+             where s.job_id = any($1) \
+             on conflict (txid) do nothing",
+        )
+        .bind(&job_ids)
+        .execute(&mut *tx)
+        .await
+        .context("archive solver.tron_signed_txs")?;
+
+        sqlx::query(
+            "insert into solver.tron_rentals_archive( \
+                job_id, provider, resource, receiver_evm, balance_sun, lock_period, order_id, \
+                txid, request_json, response_json, created_at, updated_at \
+             ) \
+             select \
+                r.job_id, r.provider, r.resource, r.receiver_evm, r.balance_sun, r.lock_period, \
+                r.order_id, r.txid, r.request_json, r.response_json, r.created_at, r.updated_at \
+             from solver.tron_rentals r \
+             where r.job_id = any($1) \
+             on conflict (job_id) do nothing",
+        )
+        .bind(&job_ids)
+        .execute(&mut *tx)
+        .await
+        .context("archive solver.tron_rentals")?;
+
+        // `pool_id` sits after `archived_at` in the archive table, so list columns explicitly.
+        sqlx::query(
+            "insert into solver.jobs_archive( \
+                job_id, intent_id, intent_type, intent_specs, deadline, state, attempts, \
+                next_retry_at, last_error, leased_by, lease_until, claim_tx_hash, prove_tx_hash, \
+                tron_txid, created_at, updated_at, claim_window_expires_at, pool_id, \
+                hub_structural_failures, proof_rebuilds, tron_broadcast_block, est_profit_usd, \
+                proved_at \
+             ) \
+             select \
+                j.job_id, j.intent_id, j.intent_type, j.intent_specs, j.deadline, j.state, \
+                j.attempts, j.next_retry_at, j.last_error, j.leased_by, j.lease_until, \
+                j.claim_tx_hash, j.prove_tx_hash, j.tron_txid, j.created_at, j.updated_at, \
+                j.claim_window_expires_at, j.pool_id, j.hub_structural_failures, \
+                j.proof_rebuilds, j.tron_broadcast_block, j.est_profit_usd, j.proved_at \
+             from solver.jobs j \
+             where j.job_id = any($1) \
+             on conflict (job_id) do nothing",
+        )
+        .bind(&job_ids)
+        .execute(&mut *tx)
+        .await
+        .context("archive solver.jobs")?;
+
+        // Cascades to the rows archived above and to per-job scratch state (delegate reservations,
+        // relay submissions, prove batches, rental order locks).
+        let n = sqlx::query("delete from solver.jobs where job_id = any($1)")
+            .bind(&job_ids)
+            .execute(&mut *tx)
+            .await
+            .context("delete archived solver.jobs")?
+            .rows_affected();
+
+        tx.commit().await.context("commit archive tx")?;
+        Ok(Some(n))
+    }
+}
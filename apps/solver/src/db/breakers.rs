@@ -1,6 +1,75 @@
 use super::*;
 
+/// One row of [`SolverDb::list_active_breakers`].
+#[derive(Debug, Clone)]
+pub struct ActiveBreaker {
+    pub contract: Address,
+    /// `None` for a contract-wide breaker.
+    pub selector: Option<[u8; 4]>,
+    /// RFC 3339 UTC timestamp of the failure that (re)armed the cooldown.
+    pub tripped_at: String,
+    /// RFC 3339 UTC timestamp.
+    pub cooldown_until: String,
+    pub last_error: Option<String>,
+    pub failure_count: i32,
+}
+
 impl SolverDb {
+    /// Breakers whose cooldown hasn't expired yet, longest-blocked first.
+    pub async fn list_active_breakers(&self) -> Result<Vec<ActiveBreaker>> {
+        let rows = sqlx::query(
+            "select contract, selector, fail_count, last_error, \
+                    to_char(updated_at at time zone 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"') as tripped_at, \
+                    to_char(cooldown_until at time zone 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"') as cooldown_until \
+             from solver.circuit_breakers \
+             where cooldown_until > now() \
+             order by cooldown_until desc, breaker_id asc",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("select active solver.circuit_breakers")?;
+
+        rows.into_iter()
+            .map(|r| {
+                let contract: Vec<u8> = r.try_get("contract")?;
+                let selector: Option<Vec<u8>> = r.try_get("selector")?;
+                Ok(ActiveBreaker {
+                    contract: Address::try_from(contract.as_slice())
+                        .context("circuit_breakers.contract must be 20 bytes")?,
+                    selector: selector
+                        .map(|s| {
+                            <[u8; 4]>::try_from(s.as_slice())
+                                .context("circuit_breakers.selector must be 4 bytes")
+                        })
+                        .transpose()?,
+                    tripped_at: r.try_get("tripped_at")?,
+                    cooldown_until: r.try_get("cooldown_until")?,
+                    last_error: r.try_get("last_error")?,
+                    failure_count: r.try_get("fail_count")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Deletes the breaker for `(contract, selector)` so the next failure starts from a clean
+    /// backoff. Returns false if there was no such breaker.
+    pub async fn breaker_reset(
+        &self,
+        contract: Address,
+        selector: Option<[u8; 4]>,
+    ) -> Result<bool> {
+        let res = sqlx::query(
+            "delete from solver.circuit_breakers \
+             where contract = $1 and selector is not distinct from $2",
+        )
+        .bind(contract.as_slice())
+        .bind(selector.map(|s| s.to_vec()))
+        .execute(&self.pool)
+        .await
+        .context("delete solver.circuit_breakers")?;
+        Ok(res.rows_affected() > 0)
+    }
+
     pub async fn breaker_is_active(
         &self,
         contract: Address,
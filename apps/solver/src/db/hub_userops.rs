@@ -16,8 +16,12 @@ impl SolverDb {
                 block_number, \
                 success, \
                 receipt::text as receipt_json, \
-                attempts \
-             from solver.hub_userops \
+                attempts, \
+                greatest( \
+                    (select count(*) from solver.hub_userops b where b.userop_hash = u.userop_hash), \
+                    1 \
+                ) as batch_size \
+             from solver.hub_userops u \
              where job_id=$1 and kind::text=$2",
         )
         .bind(job_id)
@@ -50,6 +54,7 @@ impl SolverDb {
             success: row.try_get("success")?,
             receipt_json: row.try_get("receipt_json")?,
             attempts: row.try_get("attempts")?,
+            batch_size: row.try_get("batch_size")?,
         }))
     }
 
@@ -82,6 +87,142 @@ impl SolverDb {
         Ok(())
     }
 
+    /// Inserts one prepared row per job for a userop shared by all of them (a batched prove).
+    /// All-or-nothing: fails without inserting anything if any job already has a row of this kind
+    /// or isn't leased by `leased_by`.
+    pub async fn insert_hub_userop_batch_prepared(
+        &self,
+        job_ids: &[i64],
+        leased_by: &str,
+        kind: HubUserOpKind,
+        userop_json: &str,
+    ) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("begin hub_userops batch insert")?;
+        let n = sqlx::query(
+            "insert into solver.hub_userops(job_id, kind, userop, state) \
+             select j.job_id, $1::solver.userop_kind, $2::jsonb, 'prepared' \
+             from solver.jobs j \
+             where j.job_id = any($3) and j.leased_by=$4 and j.lease_until >= now() \
+             on conflict (job_id, kind) do nothing",
+        )
+        .bind(kind.as_str())
+        .bind(userop_json)
+        .bind(job_ids)
+        .bind(leased_by)
+        .execute(&mut *tx)
+        .await
+        .context("insert solver.hub_userops batch prepared")?
+        .rows_affected();
+        if n != job_ids.len() as u64 {
+            tx.rollback().await.ok();
+            anyhow::bail!(
+                "inserted {n} of {} batched hub_userops rows (lease lost or row exists)",
+                job_ids.len()
+            );
+        }
+        tx.commit()
+            .await
+            .context("commit hub_userops batch insert")?;
+        Ok(())
+    }
+
+    /// Records the hash of a sent batched userop on every job it carries. Unlike the single-job
+    /// updates this doesn't check leases: the userop is already with the bundler, so each row must
+    /// learn its hash even if a lease lapsed meanwhile.
+    pub async fn record_hub_userop_batch_submitted(
+        &self,
+        job_ids: &[i64],
+        kind: HubUserOpKind,
+        userop_hash: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            "update solver.hub_userops set \
+                userop_hash = coalesce(userop_hash, $1), \
+                state = 'submitted', \
+                updated_at = now() \
+             where job_id = any($2) and kind=$3::solver.userop_kind",
+        )
+        .bind(userop_hash)
+        .bind(job_ids)
+        .bind(kind.as_str())
+        .execute(&self.pool)
+        .await
+        .context("update solver.hub_userops batch submitted")?;
+        Ok(())
+    }
+
+    /// Jobs among `job_ids` that may join a batched prove: no prove userop yet, and never part of
+    /// a batch that reverted.
+    pub async fn prove_batch_candidates(&self, job_ids: &[i64]) -> Result<Vec<i64>> {
+        let rows = sqlx::query(
+            "select j.job_id \
+             from solver.jobs j \
+             where j.job_id = any($1) \
+               and not exists ( \
+                 select 1 from solver.hub_userops u \
+                 where u.job_id = j.job_id and u.kind = 'prove'::solver.userop_kind \
+               ) \
+               and not exists ( \
+                 select 1 from solver.prove_batch_opt_outs o where o.job_id = j.job_id \
+               ) \
+             order by j.job_id",
+        )
+        .bind(job_ids)
+        .fetch_all(&self.pool)
+        .await
+        .context("select prove batch candidates")?;
+        rows.into_iter()
+            .map(|r| Ok(r.try_get::<i64, _>("job_id")?))
+            .collect()
+    }
+
+    /// After a batched prove userop reverted: keeps the job out of future batches and drops its
+    /// failed prove row so the next attempt builds a userop of its own.
+    pub async fn opt_out_of_prove_batches(
+        &self,
+        job_id: i64,
+        leased_by: &str,
+        reason: &str,
+    ) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("begin prove batch opt-out")?;
+        let n = sqlx::query(
+            "insert into solver.prove_batch_opt_outs(job_id, reason) \
+             select j.job_id, $1 from solver.jobs j \
+             where j.job_id=$2 and j.leased_by=$3 and j.lease_until >= now() \
+             on conflict (job_id) do update set reason = excluded.reason",
+        )
+        .bind(reason)
+        .bind(job_id)
+        .bind(leased_by)
+        .execute(&mut *tx)
+        .await
+        .context("insert solver.prove_batch_opt_outs")?
+        .rows_affected();
+        if n != 1 {
+            tx.rollback().await.ok();
+            return Err(self.lost_lease_error(job_id, leased_by).await);
+        }
+        sqlx::query(
+            "delete from solver.hub_userops \
+             where job_id = $1 and kind = 'prove'::solver.userop_kind \
+               and state = 'included' and success = false",
+        )
+        .bind(job_id)
+        .execute(&mut *tx)
+        .await
+        .context("delete failed batched prove solver.hub_userops")?;
+        tx.commit().await.context("commit prove batch opt-out")?;
+        Ok(())
+    }
+
     pub async fn record_hub_userop_submitted(
         &self,
         job_id: i64,
@@ -109,7 +250,7 @@ impl SolverDb {
         .rows_affected();
 
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
         Ok(())
     }
@@ -129,13 +270,25 @@ impl SolverDb {
     ) -> Result<()> {
         let actual_gas_cost_wei = actual_gas_cost_wei.map(|v| v.to_string());
         let actual_gas_used = actual_gas_used.map(|v| v.to_string());
+        // A batched prove userop's gas is split evenly across the jobs it carried, so per-job
+        // averages (`hub_userop_avg_actual_gas_cost_wei`) reflect what one prove costs.
         let n = sqlx::query(
-            "update solver.hub_userops u set \
+            "with batch as ( \
+                select greatest(count(*), 1)::numeric as n \
+                from solver.hub_userops b \
+                where b.userop_hash = ( \
+                    select userop_hash from solver.hub_userops \
+                    where job_id=$8 and kind=$7::solver.userop_kind \
+                ) \
+             ) \
+             update solver.hub_userops u set \
                 tx_hash=$1, \
                 block_number=coalesce($2, u.block_number), \
                 success=$3, \
-                actual_gas_cost_wei=coalesce($4::numeric, u.actual_gas_cost_wei), \
-                actual_gas_used=coalesce($5::numeric, u.actual_gas_used), \
+                actual_gas_cost_wei=coalesce( \
+                    div($4::numeric, (select n from batch)), u.actual_gas_cost_wei \
+                ), \
+                actual_gas_used=coalesce(div($5::numeric, (select n from batch)), u.actual_gas_used), \
                 receipt=$6::jsonb, \
                 state='included', \
                 updated_at=now() \
@@ -159,7 +312,7 @@ impl SolverDb {
         .rows_affected();
 
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
         Ok(())
     }
@@ -236,7 +389,7 @@ impl SolverDb {
         .rows_affected();
 
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
         Ok(())
     }
@@ -268,11 +421,40 @@ impl SolverDb {
         .rows_affected();
 
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
         Ok(())
     }
 
+    /// Tracks consecutive bundler validation rejections of a job's userops: increments
+    /// `solver.jobs.hub_structural_failures` when `structural`, otherwise resets it. Returns the
+    /// new count.
+    pub async fn record_hub_structural_outcome(
+        &self,
+        job_id: i64,
+        leased_by: &str,
+        structural: bool,
+    ) -> Result<i32> {
+        let n: Option<i32> = sqlx::query_scalar(
+            "update solver.jobs set \
+                hub_structural_failures = \
+                    case when $1 then hub_structural_failures + 1 else 0 end \
+             where job_id=$2 and leased_by=$3 and lease_until >= now() \
+             returning hub_structural_failures",
+        )
+        .bind(structural)
+        .bind(job_id)
+        .bind(leased_by)
+        .fetch_optional(&self.pool)
+        .await
+        .context("update solver.jobs hub_structural_failures")?;
+
+        match n {
+            Some(n) => Ok(n),
+            None => Err(self.lost_lease_error(job_id, leased_by).await),
+        }
+    }
+
     pub async fn delete_hub_userop_prepared(
         &self,
         job_id: i64,
@@ -0,0 +1,14 @@
+use super::*;
+
+impl SolverDb {
+    /// Whether an operator has promoted `instance_id` to active via `solver.instance_promotions`.
+    pub async fn is_instance_promoted(&self, instance_id: &str) -> Result<bool> {
+        let v: Option<i32> =
+            sqlx::query_scalar("select 1 from solver.instance_promotions where instance_id = $1")
+                .bind(instance_id)
+                .fetch_optional(&self.pool)
+                .await
+                .context("select solver.instance_promotions")?;
+        Ok(v.is_some())
+    }
+}
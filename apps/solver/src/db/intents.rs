@@ -1,8 +1,10 @@
 use super::*;
 
 impl SolverDb {
+    #[allow(clippy::too_many_arguments)]
     pub async fn upsert_intent_emulation(
         &self,
+        pool_id: &str,
         intent_id: [u8; 32],
         intent_type: i16,
         ok: bool,
@@ -11,9 +13,9 @@ impl SolverDb {
         selector: Option<&[u8]>,
     ) -> Result<()> {
         sqlx::query(
-            "insert into solver.intent_emulations(intent_id, intent_type, ok, reason, contract, selector, checked_at, updated_at) \
-             values ($1, $2, $3, $4, $5, $6, now(), now()) \
-             on conflict (intent_id) do update set \
+            "insert into solver.intent_emulations(pool_id, intent_id, intent_type, ok, reason, contract, selector, checked_at, updated_at) \
+             values ($1, $2, $3, $4, $5, $6, $7, now(), now()) \
+             on conflict (pool_id, intent_id) do update set \
                 intent_type = excluded.intent_type, \
                 ok = excluded.ok, \
                 reason = excluded.reason, \
@@ -22,6 +24,7 @@ impl SolverDb {
                 checked_at = now(), \
                 updated_at = now()",
         )
+        .bind(pool_id)
         .bind(intent_id.to_vec())
         .bind(intent_type)
         .bind(ok)
@@ -36,6 +39,7 @@ impl SolverDb {
 
     pub async fn get_intent_emulation(
         &self,
+        pool_id: &str,
         intent_id: [u8; 32],
     ) -> Result<Option<IntentEmulationRow>> {
         let row = sqlx::query(
@@ -46,8 +50,9 @@ impl SolverDb {
                 selector, \
                 extract(epoch from checked_at)::bigint as checked_at_unix \
              from solver.intent_emulations \
-             where intent_id = $1",
+             where pool_id = $1 and intent_id = $2",
         )
+        .bind(pool_id)
         .bind(intent_id.to_vec())
         .fetch_optional(&self.pool)
         .await
@@ -196,7 +201,71 @@ impl SolverDb {
         Ok(())
     }
 
-    #[allow(dead_code)]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upsert_dry_run_decision(
+        &self,
+        pool_id: &str,
+        intent_id: [u8; 32],
+        intent_type: i16,
+        plan_json: &str,
+        hub_cost_usd: f64,
+        tron_fee_usd: f64,
+        rental_cost_usd: f64,
+    ) -> Result<()> {
+        sqlx::query(
+            "insert into solver.dry_run_decisions( \
+               pool_id, intent_id, intent_type, plan, hub_cost_usd, tron_fee_usd, rental_cost_usd, \
+               decide_count \
+             ) values ($1, $2, $3, $4::jsonb, $5, $6, $7, 1) \
+             on conflict (pool_id, intent_id) do update set \
+               intent_type = excluded.intent_type, \
+               plan = excluded.plan, \
+               hub_cost_usd = excluded.hub_cost_usd, \
+               tron_fee_usd = excluded.tron_fee_usd, \
+               rental_cost_usd = excluded.rental_cost_usd, \
+               decide_count = solver.dry_run_decisions.decide_count + 1, \
+               last_seen_at = now()",
+        )
+        .bind(pool_id)
+        .bind(intent_id.to_vec())
+        .bind(intent_type)
+        .bind(plan_json)
+        .bind(hub_cost_usd)
+        .bind(tron_fee_usd)
+        .bind(rental_cost_usd)
+        .execute(&self.pool)
+        .await
+        .context("upsert solver.dry_run_decisions")?;
+        Ok(())
+    }
+
+    /// Intents whose latest skip falls within `since_secs`, per reason, most first. Each intent
+    /// counts once however often it was re-evaluated (`skip_count` is cumulative over its life).
+    pub async fn skipped_intents_by_reason(
+        &self,
+        since_secs: i64,
+        limit: i64,
+    ) -> Result<Vec<(String, i64)>> {
+        let since_secs = since_secs.clamp(1, 365 * 24 * 3600);
+        let limit = limit.clamp(1, 1_000);
+        let rows = sqlx::query(
+            "select reason, count(*)::bigint as intents \
+             from solver.intent_skips \
+             where last_seen_at > now() - make_interval(secs => $1) \
+             group by reason \
+             order by intents desc, reason \
+             limit $2",
+        )
+        .bind(since_secs)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("skipped_intents_by_reason")?;
+        rows.into_iter()
+            .map(|r| Ok((r.try_get("reason")?, r.try_get("intents")?)))
+            .collect()
+    }
+
     pub async fn intent_skip_summary(
         &self,
         since_secs: i64,
@@ -27,44 +27,158 @@ fn classify_transition_reject_reason(
     "unknown_conflict"
 }
 
+/// Time left on our local lease if the job is now held by someone else, i.e. it was re-leased
+/// before our lease expired.
+fn stolen_lease_remaining(
+    local_expiry: Option<Instant>,
+    now: Instant,
+    current_leased_by: Option<&str>,
+    expected_leased_by: &str,
+) -> Option<Duration> {
+    let owner = current_leased_by?;
+    if owner == expected_leased_by {
+        return None;
+    }
+    let remaining = local_expiry?.checked_duration_since(now)?;
+    (!remaining.is_zero()).then_some(remaining)
+}
+
 impl SolverDb {
+    fn note_local_lease(&self, job_id: i64, lease_for: Duration) {
+        let now = Instant::now();
+        let mut leases = self.leases.lock().unwrap_or_else(|e| e.into_inner());
+        leases.retain(|_, expiry| *expiry > now);
+        leases.insert(job_id, now + lease_for);
+    }
+
+    /// Error for a lease-guarded write that matched no rows. Tagged `[lease_stolen]` (and logged)
+    /// when another instance holds the lease while ours was still valid locally.
+    pub(crate) async fn lost_lease_error(&self, job_id: i64, leased_by: &str) -> anyhow::Error {
+        let local_expiry = self
+            .leases
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&job_id);
+        let current_leased_by: Option<String> =
+            sqlx::query_scalar("select leased_by from solver.jobs where job_id = $1")
+                .bind(job_id)
+                .fetch_optional(&self.pool)
+                .await
+                .ok()
+                .flatten()
+                .flatten();
+        self.lease_steal_tagged(
+            job_id,
+            leased_by,
+            local_expiry,
+            current_leased_by.as_deref(),
+            anyhow::anyhow!("lost job lease for job_id={job_id}"),
+        )
+    }
+
+    fn lease_steal_tagged(
+        &self,
+        job_id: i64,
+        leased_by: &str,
+        local_expiry: Option<Instant>,
+        current_leased_by: Option<&str>,
+        err: anyhow::Error,
+    ) -> anyhow::Error {
+        let Some(remaining) =
+            stolen_lease_remaining(local_expiry, Instant::now(), current_leased_by, leased_by)
+        else {
+            return err;
+        };
+        let other = current_leased_by.unwrap_or_default();
+        tracing::warn!(
+            job_id,
+            leased_by,
+            stolen_by = other,
+            local_lease_remaining_ms = remaining.as_millis() as u64,
+            "job lease taken by another instance before it expired locally; check SOLVER_LEASE_SECS and clock skew"
+        );
+        err.context(format!(
+            "[lease_stolen] job_id={job_id} re-leased by {other} {}ms before local lease expiry",
+            remaining.as_millis()
+        ))
+    }
+
+    /// Queues a `ready` job for the intent unless the pool already has (or archived) one.
+    /// Returns whether a job was inserted.
     pub async fn insert_job_if_new(
         &self,
+        pool_id: &str,
         intent_id: [u8; 32],
         intent_type: i16,
         intent_specs: &[u8],
         deadline: i64,
-    ) -> Result<()> {
-        sqlx::query(
-            "insert into solver.jobs(intent_id, intent_type, intent_specs, deadline, state) \
-             values ($1, $2, $3, $4, 'ready') \
-             on conflict (intent_id) do nothing",
+        est_profit_usd: Option<f64>,
+    ) -> Result<bool> {
+        let n = sqlx::query(
+            "insert into solver.jobs( \
+                intent_id, intent_type, intent_specs, deadline, state, pool_id, est_profit_usd \
+             ) \
+             select $1, $2, $3, $4, 'ready', $5, $6 \
+             where not exists ( \
+                select 1 from solver.jobs_archive where pool_id = $5 and intent_id = $1 \
+             ) \
+             on conflict (pool_id, intent_id) do nothing",
         )
         .bind(intent_id.to_vec())
         .bind(intent_type)
         .bind(intent_specs)
         .bind(deadline)
+        .bind(pool_id)
+        .bind(est_profit_usd)
         .execute(&self.pool)
         .await
-        .context("insert solver.jobs")?;
-        Ok(())
+        .context("insert solver.jobs")?
+        .rows_affected();
+        Ok(n == 1)
     }
 
-    pub async fn job_id_for_intent(&self, intent_id: [u8; 32]) -> Result<Option<i64>> {
-        let v: Option<i64> =
-            sqlx::query_scalar("select job_id from solver.jobs where intent_id = $1")
-                .bind(intent_id.to_vec())
-                .fetch_optional(&self.pool)
-                .await
-                .context("select solver.jobs.job_id by intent_id")?;
+    pub async fn job_id_for_intent(
+        &self,
+        pool_id: &str,
+        intent_id: [u8; 32],
+    ) -> Result<Option<i64>> {
+        let v: Option<i64> = sqlx::query_scalar(
+            "select job_id from solver.jobs where pool_id = $1 and intent_id = $2",
+        )
+        .bind(pool_id)
+        .bind(intent_id.to_vec())
+        .fetch_optional(&self.pool)
+        .await
+        .context("select solver.jobs.job_id by intent_id")?;
         Ok(v)
     }
 
+    /// Number of jobs per state in the hot table (archived jobs are not counted).
+    pub async fn count_jobs_by_state(&self) -> Result<Vec<(String, i64)>> {
+        let rows = sqlx::query(
+            "select state, count(*)::bigint as n from solver.jobs group by state order by state",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("count solver.jobs by state")?;
+
+        let mut out = Vec::with_capacity(rows.len());
+        for r in rows {
+            out.push((r.try_get("state")?, r.try_get("n")?));
+        }
+        Ok(out)
+    }
+
+    /// Leases up to `limit` due jobs belonging to `pool_ids` (jobs of other pools are left alone
+    /// until their pool is ready again). Without `tron_stages`, only jobs past their Tron fill
+    /// (proving and settlement, which only need the hub) are leased.
     pub async fn lease_jobs(
         &self,
         leased_by: &str,
         lease_for: Duration,
         limit: i64,
+        pool_ids: &[String],
+        tron_stages: bool,
     ) -> Result<Vec<SolverJob>> {
         let secs: i64 = lease_for.as_secs().try_into().unwrap_or(60);
         let rows = sqlx::query(
@@ -83,6 +197,8 @@ impl SolverDb {
                         'proved_waiting_settlement' \
                     ) \
                     and next_retry_at <= now() \
+                    and pool_id = any($4) \
+                    and ($5 or state not in ('ready', 'claimed', 'tron_prepared', 'tron_sent')) \
                     and ( \
                         (lease_until is null or lease_until < now()) \
                         or (leased_by = $2 and lease_until >= now()) \
@@ -102,11 +218,13 @@ impl SolverDb {
             where j.job_id = cte.job_id \
             returning j.job_id, j.intent_id, j.intent_type, j.intent_specs, j.deadline, \
                       extract(epoch from j.claim_window_expires_at)::bigint as claim_window_expires_at_unix, \
-                      j.state, j.attempts, j.tron_txid",
+                      j.state, j.attempts, j.tron_txid, j.tron_broadcast_block, j.pool_id",
         )
         .bind(limit)
         .bind(leased_by)
         .bind(secs)
+        .bind(pool_ids)
+        .bind(tron_stages)
         .fetch_all(&self.pool)
         .await
         .context("lease solver.jobs")?;
@@ -136,8 +254,13 @@ impl SolverDb {
                 state: row.try_get("state")?,
                 attempts: row.try_get("attempts")?,
                 tron_txid,
+                tron_broadcast_block: row.try_get("tron_broadcast_block")?,
+                pool_id: row.try_get("pool_id")?,
             });
         }
+        for job in &out {
+            self.note_local_lease(job.job_id, lease_for);
+        }
         Ok(out)
     }
 
@@ -153,7 +276,7 @@ impl SolverDb {
                 lease_until = now() + make_interval(secs => $1), \
                 updated_at = now() \
              where job_id = $2 and leased_by = $3 and lease_until >= now() \
-               and state not in ('done', 'failed_fatal')",
+               and state not in ('done', 'failed_fatal', 'unclaimed', 'lost_race')",
         )
         .bind(secs)
         .bind(job_id)
@@ -163,8 +286,9 @@ impl SolverDb {
         .context("renew solver.jobs lease")?
         .rows_affected();
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
+        self.note_local_lease(job_id, lease_for);
         Ok(())
     }
 
@@ -178,7 +302,8 @@ impl SolverDb {
         let expected_states: Vec<String> =
             expected_states.iter().map(|s| (*s).to_string()).collect();
         let n = sqlx::query(
-            "update solver.jobs set state = $1, updated_at = now() \
+            "update solver.jobs set state = $1, updated_at = now(), \
+                proved_at = case when $1 = 'proved' then now() else proved_at end \
              where job_id = $2 and leased_by = $3 and lease_until >= now() \
                and state = any($4::text[])",
         )
@@ -220,7 +345,7 @@ impl SolverDb {
                 job_exists,
             );
 
-            anyhow::bail!(
+            let err = anyhow::anyhow!(
                 "[transition_reject:{reason}] rejected state transition for job_id={job_id}: expected one of {:?} -> {} (current_state={:?}, leased_by={:?}, lease_valid={:?})",
                 expected_states,
                 state,
@@ -228,6 +353,21 @@ impl SolverDb {
                 current_leased_by,
                 lease_valid
             );
+            if reason != "lease_owner_mismatch" {
+                return Err(err);
+            }
+            let local_expiry = self
+                .leases
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&job_id);
+            return Err(self.lease_steal_tagged(
+                job_id,
+                leased_by,
+                local_expiry,
+                current_leased_by.as_deref(),
+                err,
+            ));
         }
         Ok(())
     }
@@ -235,7 +375,33 @@ impl SolverDb {
 
 #[cfg(test)]
 mod tests {
-    use super::classify_transition_reject_reason;
+    use super::{classify_transition_reject_reason, stolen_lease_remaining};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn stolen_lease_requires_other_owner_and_unexpired_local_lease() {
+        let now = Instant::now();
+        let later = Some(now + Duration::from_secs(30));
+
+        assert_eq!(
+            stolen_lease_remaining(later, now, Some("solver-b"), "solver-a"),
+            Some(Duration::from_secs(30))
+        );
+        // Our own lease, an expired local lease, an unknown local lease, or no owner: not a steal.
+        assert_eq!(
+            stolen_lease_remaining(later, now, Some("solver-a"), "solver-a"),
+            None
+        );
+        assert_eq!(
+            stolen_lease_remaining(Some(now), now, Some("solver-b"), "solver-a"),
+            None
+        );
+        assert_eq!(
+            stolen_lease_remaining(None, now, Some("solver-b"), "solver-a"),
+            None
+        );
+        assert_eq!(stolen_lease_remaining(later, now, None, "solver-a"), None);
+    }
 
     #[test]
     fn classify_transition_reject_reason_cases() {
@@ -23,7 +23,7 @@ impl SolverDb {
         .context("record claim")?
         .rows_affected();
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
         Ok(())
     }
@@ -49,7 +49,7 @@ impl SolverDb {
         .context("record tron txid")?
         .rows_affected();
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
         Ok(())
     }
@@ -72,7 +72,8 @@ impl SolverDb {
     ) -> Result<()> {
         let expected_states = super::transitions::expected_state_binds_for(JobState::Proved);
         let n = sqlx::query(
-            "update solver.jobs set state='proved', prove_tx_hash=$1, claim_window_expires_at=null, updated_at=now() \
+            "update solver.jobs set state='proved', prove_tx_hash=$1, claim_window_expires_at=null, \
+                proved_at=now(), updated_at=now() \
              where job_id=$2 and leased_by=$3 and lease_until >= now() \
                and state = any($4::text[])",
         )
@@ -85,7 +86,7 @@ impl SolverDb {
         .context("record prove")?
         .rows_affected();
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
         Ok(())
     }
@@ -112,7 +113,7 @@ impl SolverDb {
         .context("set claim_window_expires_at")?
         .rows_affected();
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
         Ok(())
     }
@@ -139,7 +140,8 @@ impl SolverDb {
         err: &str,
         next_retry_in: Duration,
     ) -> Result<()> {
-        let secs: i64 = next_retry_in.as_secs().try_into().unwrap_or(1);
+        // Fractional seconds: jittered delays are sub-second at low attempt counts.
+        let secs = next_retry_in.as_secs_f64();
         let n = sqlx::query(
             "update solver.jobs set \
                 attempts = attempts + 1, \
@@ -148,7 +150,7 @@ impl SolverDb {
                 lease_until = now(), \
                 updated_at = now() \
              where job_id=$3 and leased_by=$4 \
-               and state not in ('done', 'failed_fatal')",
+               and state not in ('done', 'failed_fatal', 'unclaimed', 'lost_race')",
         )
         .bind(err)
         .bind(secs)
@@ -159,7 +161,32 @@ impl SolverDb {
         .context("record retryable error")?
         .rows_affected();
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
+        }
+        Ok(())
+    }
+
+    /// Puts a job back until `retry_in` from now without counting an attempt or touching
+    /// `last_error`, for waits that aren't failures (tick budgets, waiting on a third party).
+    pub async fn defer_job(&self, job_id: i64, leased_by: &str, retry_in: Duration) -> Result<()> {
+        let secs = retry_in.as_secs_f64();
+        let n = sqlx::query(
+            "update solver.jobs set \
+                next_retry_at = now() + make_interval(secs => $1), \
+                lease_until = now(), \
+                updated_at = now() \
+             where job_id=$2 and leased_by=$3 \
+               and state not in ('done', 'failed_fatal', 'unclaimed', 'lost_race')",
+        )
+        .bind(secs)
+        .bind(job_id)
+        .bind(leased_by)
+        .execute(&self.pool)
+        .await
+        .context("defer job")?
+        .rows_affected();
+        if n != 1 {
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
         Ok(())
     }
@@ -172,7 +199,7 @@ impl SolverDb {
                 lease_until = now(), \
                 updated_at = now() \
              where job_id=$2 and leased_by=$3 \
-               and state <> 'done'",
+               and state not in ('done', 'unclaimed', 'lost_race')",
         )
         .bind(err)
         .bind(job_id)
@@ -182,11 +209,105 @@ impl SolverDb {
         .context("record fatal error")?
         .rows_affected();
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
         Ok(())
     }
 
+    /// Ends a `ready` job in the terminal `lost_race` state: another solver claimed the intent
+    /// before us. Kept apart from `failed_fatal` so claim-race losses can be counted on their own.
+    pub async fn record_lost_race(&self, job_id: i64, leased_by: &str, err: &str) -> Result<()> {
+        let expected = super::transitions::expected_state_binds_for(JobState::LostRace);
+        let n = sqlx::query(
+            "update solver.jobs set \
+                state = 'lost_race', \
+                last_error = $1, \
+                lease_until = now(), \
+                updated_at = now() \
+             where job_id=$2 and leased_by=$3 \
+               and state = any($4::text[])",
+        )
+        .bind(err)
+        .bind(job_id)
+        .bind(leased_by)
+        .bind(&expected)
+        .execute(&self.pool)
+        .await
+        .context("record lost race")?
+        .rows_affected();
+        if n != 1 {
+            return Err(self.lost_lease_error(job_id, leased_by).await);
+        }
+        Ok(())
+    }
+
+    /// Manually puts a stuck or `failed_fatal` job back into rotation: resets `attempts`, clears
+    /// `last_error` and makes it due immediately. `to_state` defaults to the current state for live
+    /// jobs and to the recorded progress for `failed_fatal` jobs; see
+    /// [`super::transitions::validate_requeue`] for which targets are accepted.
+    pub async fn requeue_job(
+        &self,
+        pool_id: &str,
+        intent_id: [u8; 32],
+        to_state: Option<JobState>,
+    ) -> Result<RequeuedJob> {
+        let mut tx = self.pool.begin().await.context("begin requeue tx")?;
+
+        let row = sqlx::query(
+            "select job_id, state, \
+                    claim_tx_hash is not null as has_claim_tx, \
+                    tron_txid is not null as has_tron_txid, \
+                    prove_tx_hash is not null as has_prove_tx \
+             from solver.jobs where pool_id = $1 and intent_id = $2 \
+             for update",
+        )
+        .bind(pool_id)
+        .bind(intent_id.to_vec())
+        .fetch_optional(&mut *tx)
+        .await
+        .context("select solver.jobs for requeue")?
+        .with_context(|| {
+            format!(
+                "no job for pool_id={pool_id} intent_id=0x{}",
+                hex::encode(intent_id)
+            )
+        })?;
+
+        let job_id: i64 = row.try_get("job_id")?;
+        let current = JobState::parse(row.try_get::<String, _>("state")?.as_str())?;
+        let furthest = super::transitions::furthest_recorded_state(
+            row.try_get("has_claim_tx")?,
+            row.try_get("has_tron_txid")?,
+            row.try_get("has_prove_tx")?,
+        );
+        let target = to_state
+            .unwrap_or_else(|| super::transitions::default_requeue_target(current, furthest));
+        super::transitions::validate_requeue(current, target, furthest)
+            .with_context(|| format!("requeue job_id={job_id}"))?;
+
+        sqlx::query(
+            "update solver.jobs set \
+                state = $1, \
+                attempts = 0, \
+                last_error = null, \
+                next_retry_at = now(), \
+                updated_at = now() \
+             where job_id = $2",
+        )
+        .bind(target.as_db_str())
+        .bind(job_id)
+        .execute(&mut *tx)
+        .await
+        .context("requeue solver.jobs")?;
+
+        tx.commit().await.context("commit requeue tx")?;
+        Ok(RequeuedJob {
+            job_id,
+            from_state: current,
+            to_state: target,
+        })
+    }
+
     pub async fn global_pause_active(&self) -> Result<Option<(i64, Option<String>)>> {
         let row = sqlx::query(
             "select \
@@ -241,6 +362,30 @@ impl SolverDb {
         Ok(row.try_get::<i64, _>("n")?)
     }
 
+    /// Jobs proved within `window_secs` (by `proved_at`), with the profit the policy priced them
+    /// at when they were accepted.
+    pub async fn fill_summary(&self, window_secs: i64) -> Result<FillSummaryRow> {
+        let window_secs = window_secs.max(1);
+        let row = sqlx::query(
+            "select \
+                count(*)::bigint as fills, \
+                count(est_profit_usd)::bigint as priced, \
+                coalesce(sum(est_profit_usd), 0)::double precision as est_profit_usd \
+             from solver.jobs \
+             where state in ('proved', 'proved_waiting_funding', 'proved_waiting_settlement', 'done') \
+               and proved_at > now() - make_interval(secs => $1)",
+        )
+        .bind(window_secs)
+        .fetch_one(&self.pool)
+        .await
+        .context("fill_summary")?;
+        Ok(FillSummaryRow {
+            fills: row.try_get("fills")?,
+            priced: row.try_get("priced")?,
+            est_profit_usd: row.try_get("est_profit_usd")?,
+        })
+    }
+
     pub async fn count_claimed_unproved_jobs(&self) -> Result<i64> {
         let row = sqlx::query(
             "select count(*)::bigint as n \
@@ -253,6 +398,37 @@ impl SolverDb {
         Ok(row.try_get::<i64, _>("n")?)
     }
 
+    /// Jobs queued for `pool_id` but not claimed yet: each still needs a claim deposit.
+    pub async fn count_unclaimed_ready_jobs(&self, pool_id: &str) -> Result<i64> {
+        let row = sqlx::query(
+            "select count(*)::bigint as n \
+             from solver.jobs \
+             where state = 'ready' and pool_id = $1",
+        )
+        .bind(pool_id)
+        .fetch_one(&self.pool)
+        .await
+        .context("count_unclaimed_ready_jobs")?;
+        Ok(row.try_get::<i64, _>("n")?)
+    }
+
+    /// Jobs whose claim deposit is still locked in the pool: claimed through proved, until the
+    /// intent settles (or is closed).
+    pub async fn count_inflight_deposits(&self) -> Result<i64> {
+        let row = sqlx::query(
+            "select count(*)::bigint as n \
+             from solver.jobs \
+             where state in ( \
+                'claimed', 'tron_prepared', 'tron_sent', 'proof_built', \
+                'proved', 'proved_waiting_funding', 'proved_waiting_settlement' \
+             )",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("count_inflight_deposits")?;
+        Ok(row.try_get::<i64, _>("n")?)
+    }
+
     pub async fn rate_limit_claim_per_minute(&self, key: &str, limit: u64) -> Result<Option<i64>> {
         if limit == 0 {
             return Ok(None);
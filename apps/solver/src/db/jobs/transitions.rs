@@ -20,6 +20,8 @@ pub(crate) fn expected_previous_states_for(next_state: JobState) -> &'static [Jo
             JobState::ProvedWaitingSettlement,
             JobState::Done,
         ],
+        JobState::Unclaimed => &[JobState::Claimed, JobState::TronPrepared],
+        JobState::LostRace => &[JobState::Ready],
         JobState::Ready | JobState::FailedFatal => &[],
     }
 }
@@ -43,6 +45,8 @@ pub(crate) fn expected_previous_state_names_for(next_state: JobState) -> &'stati
             "proved_waiting_settlement",
             "done",
         ],
+        JobState::Unclaimed => &["claimed", "tron_prepared"],
+        JobState::LostRace => &["ready"],
         JobState::Ready | JobState::FailedFatal => &[],
     }
 }
@@ -72,6 +76,92 @@ pub(crate) fn expected_state_binds(next_state: &str) -> Result<Vec<String>> {
         .collect())
 }
 
+/// Furthest job state implied by the artifacts a job has already recorded on-chain.
+///
+/// Used to decide which states a job may be requeued into: a requeue must never send a job back
+/// to a step whose side effects (claim, Tron broadcast, prove) already happened.
+pub(crate) fn furthest_recorded_state(
+    has_claim_tx: bool,
+    has_tron_txid: bool,
+    has_prove_tx: bool,
+) -> JobState {
+    if has_prove_tx {
+        JobState::Proved
+    } else if has_tron_txid {
+        JobState::TronSent
+    } else if has_claim_tx {
+        JobState::Claimed
+    } else {
+        JobState::Ready
+    }
+}
+
+fn requeue_targets_for(furthest: JobState) -> &'static [JobState] {
+    match furthest {
+        JobState::Ready => &[JobState::Ready],
+        JobState::Claimed => &[JobState::Claimed],
+        JobState::TronSent => &[JobState::TronSent],
+        JobState::Proved => &[
+            JobState::Proved,
+            JobState::ProvedWaitingFunding,
+            JobState::ProvedWaitingSettlement,
+        ],
+        _ => &[],
+    }
+}
+
+/// Validates a manual requeue of a job from `current` into `target`.
+///
+/// Live jobs may only be requeued in place (resetting their backoff). `failed_fatal` jobs may be
+/// revived into the state matching the progress they recorded before failing. `done`,
+/// `unclaimed` and `lost_race` jobs (the latter two don't hold the hub claim) and terminal targets
+/// are rejected.
+pub(crate) fn validate_requeue(
+    current: JobState,
+    target: JobState,
+    furthest: JobState,
+) -> Result<()> {
+    if matches!(
+        target,
+        JobState::Done | JobState::FailedFatal | JobState::Unclaimed | JobState::LostRace
+    ) {
+        anyhow::bail!("cannot requeue into terminal state {}", target.as_db_str());
+    }
+    match current {
+        JobState::Done => anyhow::bail!("cannot requeue a done job"),
+        JobState::Unclaimed => anyhow::bail!("cannot requeue an unclaimed job"),
+        JobState::LostRace => anyhow::bail!("cannot requeue a lost_race job"),
+        JobState::FailedFatal => {
+            if !requeue_targets_for(furthest).contains(&target) {
+                anyhow::bail!(
+                    "cannot requeue failed_fatal job into {}: recorded progress is {}",
+                    target.as_db_str(),
+                    furthest.as_db_str()
+                );
+            }
+        }
+        live => {
+            if live != target {
+                anyhow::bail!(
+                    "cannot move live job from {} to {}; only in-place requeue is allowed",
+                    live.as_db_str(),
+                    target.as_db_str()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Default requeue target: the current state for live jobs, or the recorded progress for
+/// `failed_fatal` jobs.
+pub(crate) fn default_requeue_target(current: JobState, furthest: JobState) -> JobState {
+    match current {
+        JobState::FailedFatal => furthest,
+        other => other,
+    }
+}
+
 #[cfg(test)]
 fn transition_allowed(from_state: JobState, to_state: JobState) -> bool {
     expected_previous_states_for(to_state).contains(&from_state)
@@ -82,7 +172,8 @@ mod job_state_transition_tests {
     use crate::types::JobState;
 
     use super::{
-        expected_previous_states_for_transition, expected_state_binds, transition_allowed,
+        default_requeue_target, expected_previous_states_for_transition, expected_state_binds,
+        furthest_recorded_state, transition_allowed, validate_requeue,
     };
 
     #[test]
@@ -110,6 +201,12 @@ mod job_state_transition_tests {
             JobState::Done
         ));
         assert!(transition_allowed(JobState::Done, JobState::Done));
+        assert!(transition_allowed(JobState::Claimed, JobState::Unclaimed));
+        assert!(transition_allowed(JobState::Ready, JobState::LostRace));
+        assert!(transition_allowed(
+            JobState::TronPrepared,
+            JobState::Unclaimed
+        ));
     }
 
     #[test]
@@ -119,6 +216,8 @@ mod job_state_transition_tests {
         assert!(!transition_allowed(JobState::TronSent, JobState::Claimed));
         assert!(!transition_allowed(JobState::Done, JobState::Proved));
         assert!(!transition_allowed(JobState::FailedFatal, JobState::Done));
+        assert!(!transition_allowed(JobState::TronSent, JobState::Unclaimed));
+        assert!(!transition_allowed(JobState::Claimed, JobState::LostRace));
     }
 
     #[test]
@@ -149,4 +248,53 @@ mod job_state_transition_tests {
         assert!(expected_previous_states_for_transition("not_a_real_state").is_err());
         assert!(expected_state_binds("not_a_real_state").is_err());
     }
+
+    #[test]
+    fn requeue_revives_failed_fatal_only_into_recorded_progress() {
+        let unclaimed = furthest_recorded_state(false, false, false);
+        let sent = furthest_recorded_state(true, true, false);
+        let proved = furthest_recorded_state(true, true, true);
+        assert_eq!(unclaimed, JobState::Ready);
+        assert_eq!(sent, JobState::TronSent);
+        assert_eq!(proved, JobState::Proved);
+
+        assert!(validate_requeue(JobState::FailedFatal, JobState::Ready, unclaimed).is_ok());
+        assert!(validate_requeue(JobState::FailedFatal, JobState::TronSent, sent).is_ok());
+        assert!(
+            validate_requeue(
+                JobState::FailedFatal,
+                JobState::ProvedWaitingSettlement,
+                proved
+            )
+            .is_ok()
+        );
+
+        // Never re-run side effects that already happened, nor skip ones that did not.
+        assert!(validate_requeue(JobState::FailedFatal, JobState::Claimed, sent).is_err());
+        assert!(validate_requeue(JobState::FailedFatal, JobState::Ready, proved).is_err());
+        assert!(validate_requeue(JobState::FailedFatal, JobState::Proved, sent).is_err());
+
+        assert_eq!(
+            default_requeue_target(JobState::FailedFatal, sent),
+            JobState::TronSent
+        );
+    }
+
+    #[test]
+    fn requeue_rejects_done_terminal_targets_and_live_state_changes() {
+        let claimed = furthest_recorded_state(true, false, false);
+        assert!(validate_requeue(JobState::Done, JobState::Proved, JobState::Proved).is_err());
+        assert!(validate_requeue(JobState::FailedFatal, JobState::Done, claimed).is_err());
+        assert!(validate_requeue(JobState::FailedFatal, JobState::FailedFatal, claimed).is_err());
+        assert!(validate_requeue(JobState::Unclaimed, JobState::Claimed, claimed).is_err());
+        assert!(validate_requeue(JobState::Claimed, JobState::Unclaimed, claimed).is_err());
+        assert!(validate_requeue(JobState::LostRace, JobState::Ready, JobState::Ready).is_err());
+
+        assert!(validate_requeue(JobState::TronPrepared, JobState::TronPrepared, claimed).is_ok());
+        assert!(validate_requeue(JobState::TronPrepared, JobState::Claimed, claimed).is_err());
+        assert_eq!(
+            default_requeue_target(JobState::TronPrepared, claimed),
+            JobState::TronPrepared
+        );
+    }
 }
@@ -0,0 +1,34 @@
+use super::*;
+
+impl SolverDb {
+    /// Sums what each of `owners` (21-byte prefixed addresses) signed away in `asset` over the
+    /// last 24 hours, in the same order as `owners`.
+    pub async fn tron_key_spent_24h(&self, asset: &str, owners: &[Vec<u8>]) -> Result<Vec<u64>> {
+        let rows = sqlx::query(
+            "select owner_address, sum(amount)::text as spent \
+             from solver.tron_key_spends \
+             where asset = $1 and owner_address = any($2) \
+               and created_at > now() - interval '24 hours' \
+             group by owner_address",
+        )
+        .bind(asset)
+        .bind(owners)
+        .fetch_all(&self.pool)
+        .await
+        .context("sum solver.tron_key_spends")?;
+
+        let mut by_owner = HashMap::with_capacity(rows.len());
+        for row in rows {
+            let owner: Vec<u8> = row.try_get("owner_address")?;
+            let spent: String = row.try_get("spent")?;
+            let spent = crate::types::parse_u256_dec(&spent)
+                .map(|v| u64::try_from(v).unwrap_or(u64::MAX))
+                .unwrap_or(u64::MAX);
+            by_owner.insert(owner, spent);
+        }
+        Ok(owners
+            .iter()
+            .map(|o| by_owner.get(o).copied().unwrap_or(0))
+            .collect())
+    }
+}
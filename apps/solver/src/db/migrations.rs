@@ -43,9 +43,128 @@ const MIGRATIONS: &[(i32, &str)] = &[
         15,
         include_str!("../../db/migrations/0015_claim_window_deadline.sql"),
     ),
+    (
+        16,
+        include_str!("../../db/migrations/0016_jobs_archive.sql"),
+    ),
+    (
+        17,
+        include_str!("../../db/migrations/0017_rental_provider_latency.sql"),
+    ),
+    (
+        18,
+        include_str!("../../db/migrations/0018_dry_run_decisions.sql"),
+    ),
+    (
+        19,
+        include_str!("../../db/migrations/0019_consolidation_sweeps.sql"),
+    ),
+    (
+        20,
+        include_str!("../../db/migrations/0020_tron_tx_sponsored_energy.sql"),
+    ),
+    (
+        21,
+        include_str!("../../db/migrations/0021_tron_proof_content_hash.sql"),
+    ),
+    (
+        22,
+        include_str!("../../db/migrations/0022_instance_promotions.sql"),
+    ),
+    (
+        23,
+        include_str!("../../db/migrations/0023_tron_signed_tx_fee_limit_estimate.sql"),
+    ),
+    (
+        24,
+        include_str!("../../db/migrations/0024_job_unclaims.sql"),
+    ),
+    (
+        25,
+        include_str!("../../db/migrations/0025_tron_key_spends.sql"),
+    ),
+    (26, include_str!("../../db/migrations/0026_job_pool_id.sql")),
+    (
+        27,
+        include_str!("../../db/migrations/0027_claim_token_buckets.sql"),
+    ),
+    (
+        28,
+        include_str!("../../db/migrations/0028_jobs_lost_race.sql"),
+    ),
+    (
+        29,
+        include_str!("../../db/migrations/0029_prove_relay_submissions.sql"),
+    ),
+    (
+        30,
+        include_str!("../../db/migrations/0030_prove_batches.sql"),
+    ),
+    (
+        31,
+        include_str!("../../db/migrations/0031_tron_broadcast_block.sql"),
+    ),
+    (
+        32,
+        include_str!("../../db/migrations/0032_job_est_profit.sql"),
+    ),
+    (
+        33,
+        include_str!("../../db/migrations/0033_tron_proof_encoding.sql"),
+    ),
+    (
+        34,
+        include_str!("../../db/migrations/0034_tron_rental_order_locks.sql"),
+    ),
+    (
+        35,
+        include_str!("../../db/migrations/0035_archive_tron_signed_txs_rentals.sql"),
+    ),
+    (
+        36,
+        include_str!("../../db/migrations/0036_pool_scoped_intent_rows.sql"),
+    ),
+    (
+        37,
+        include_str!("../../db/migrations/0037_hub_structural_failures.sql"),
+    ),
+    (
+        38,
+        include_str!("../../db/migrations/0038_proof_rebuilds.sql"),
+    ),
+    (
+        39,
+        include_str!("../../db/migrations/0039_archive_tron_broadcast_block.sql"),
+    ),
+    (
+        40,
+        include_str!("../../db/migrations/0040_job_proved_at.sql"),
+    ),
 ];
 
 impl SolverDb {
+    /// Migration versions `migrate` would apply, without applying anything.
+    pub async fn pending_migrations(&self) -> Result<Vec<i32>> {
+        let exists: bool =
+            sqlx::query_scalar("select to_regclass('solver.schema_migrations') is not null")
+                .fetch_one(&self.pool)
+                .await
+                .context("check solver.schema_migrations")?;
+        let applied: Vec<i32> = if exists {
+            sqlx::query_scalar("select version from solver.schema_migrations")
+                .fetch_all(&self.pool)
+                .await
+                .context("read solver.schema_migrations")?
+        } else {
+            Vec::new()
+        };
+        Ok(MIGRATIONS
+            .iter()
+            .map(|(v, _)| *v)
+            .filter(|v| !applied.contains(v))
+            .collect())
+    }
+
     pub async fn migrate(&self) -> Result<()> {
         // Prevent concurrent migrations when multiple solver processes start at once.
         //
@@ -1,40 +1,247 @@
 use super::*;
 
+/// The stored proof for `txid` doesn't match the content hash written with it.
+#[derive(Debug)]
+pub struct TronProofCorruptedError {
+    pub txid: [u8; 32],
+}
+
+impl std::fmt::Display for TronProofCorruptedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "tron proof for txid=0x{} failed its content hash check",
+            hex::encode(self.txid)
+        )
+    }
+}
+
+impl std::error::Error for TronProofCorruptedError {}
+
+/// `solver.tron_proofs.encoding` of rows whose `blocks` and `encoded_tx` are stored as-is.
+const ENCODING_RAW: &str = "raw";
+/// `blocks` and `encoded_tx` are each a zstd frame. Merkle siblings are 32-byte hashes and don't
+/// compress, so `proof` is always stored raw.
+const ENCODING_ZSTD: &str = "zstd";
+const ZSTD_LEVEL: i32 = 3;
+
+fn encode_blob(blob: &[u8], encoding: &str) -> Result<Vec<u8>> {
+    match encoding {
+        ENCODING_ZSTD => zstd::encode_all(blob, ZSTD_LEVEL).context("zstd compress tron proof"),
+        _ => Ok(blob.to_vec()),
+    }
+}
+
+fn decode_blob(blob: Vec<u8>, encoding: &str) -> Result<Vec<u8>> {
+    match encoding {
+        ENCODING_RAW => Ok(blob),
+        ENCODING_ZSTD => zstd::decode_all(blob.as_slice()).context("zstd decompress tron proof"),
+        other => anyhow::bail!("unknown tron proof encoding: {other}"),
+    }
+}
+
+impl TronProofRow {
+    /// keccak256 over a length-prefixed encoding of every field, so no two distinct rows can
+    /// serialize to the same bytes.
+    pub fn content_hash(&self) -> [u8; 32] {
+        fn put(buf: &mut Vec<u8>, bytes: &[u8]) {
+            buf.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+            buf.extend_from_slice(bytes);
+        }
+
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(self.blocks.len() as u64).to_be_bytes());
+        for b in &self.blocks {
+            put(&mut buf, b);
+        }
+        put(&mut buf, &self.encoded_tx);
+        buf.extend_from_slice(&(self.proof.len() as u64).to_be_bytes());
+        for p in &self.proof {
+            put(&mut buf, p);
+        }
+        put(&mut buf, self.index_dec.as_bytes());
+        alloy::primitives::keccak256(&buf).0
+    }
+}
+
 impl SolverDb {
-    pub async fn save_tron_proof(&self, txid: [u8; 32], proof: &TronProofRow) -> Result<()> {
+    /// Saves a proof with its content hash, zstd-compressing the block blobs and encoded tx when
+    /// `compress` is set. Re-saving an identical proof leaves the row untouched.
+    pub async fn save_tron_proof(
+        &self,
+        txid: [u8; 32],
+        proof: &TronProofRow,
+        compress: bool,
+    ) -> Result<()> {
+        let encoding = if compress {
+            ENCODING_ZSTD
+        } else {
+            ENCODING_RAW
+        };
+        let blocks = proof
+            .blocks
+            .iter()
+            .map(|b| encode_blob(b, encoding))
+            .collect::<Result<Vec<_>>>()?;
+        let encoded_tx = encode_blob(&proof.encoded_tx, encoding)?;
         sqlx::query(
-            "insert into solver.tron_proofs(txid, blocks, encoded_tx, proof, index_dec) \
-             values ($1, $2, $3, $4, $5) \
+            "insert into solver.tron_proofs( \
+                txid, blocks, encoded_tx, proof, index_dec, content_hash, encoding \
+             ) \
+             values ($1, $2, $3, $4, $5, $6, $7) \
              on conflict (txid) do update set \
                blocks = excluded.blocks, \
                encoded_tx = excluded.encoded_tx, \
                proof = excluded.proof, \
-               index_dec = excluded.index_dec",
+               index_dec = excluded.index_dec, \
+               content_hash = excluded.content_hash, \
+               encoding = excluded.encoding \
+             where solver.tron_proofs.content_hash is distinct from excluded.content_hash",
         )
         .bind(txid.to_vec())
-        .bind(&proof.blocks)
-        .bind(&proof.encoded_tx)
+        .bind(&blocks)
+        .bind(&encoded_tx)
         .bind(&proof.proof)
         .bind(&proof.index_dec)
+        .bind(proof.content_hash().to_vec())
+        .bind(encoding)
         .execute(&self.pool)
         .await
         .context("save solver.tron_proofs")?;
         Ok(())
     }
 
+    /// Loads a proof, failing with [`TronProofCorruptedError`] if it doesn't match its stored
+    /// content hash. Rows saved before hashes were recorded are returned unchecked.
     pub async fn load_tron_proof(&self, txid: [u8; 32]) -> Result<TronProofRow> {
         let row = sqlx::query(
-            "select blocks, encoded_tx, proof, index_dec from solver.tron_proofs where txid = $1",
+            "select blocks, encoded_tx, proof, index_dec, content_hash, encoding \
+             from solver.tron_proofs where txid = $1",
         )
         .bind(txid.to_vec())
         .fetch_one(&self.pool)
         .await
         .context("load solver.tron_proofs")?;
-        Ok(TronProofRow {
-            blocks: row.try_get("blocks")?,
-            encoded_tx: row.try_get("encoded_tx")?,
+        let encoding: String = row.try_get("encoding")?;
+        let blocks: Vec<Vec<u8>> = row.try_get("blocks")?;
+        let proof = TronProofRow {
+            blocks: blocks
+                .into_iter()
+                .map(|b| decode_blob(b, &encoding))
+                .collect::<Result<_>>()?,
+            encoded_tx: decode_blob(row.try_get("encoded_tx")?, &encoding)?,
             proof: row.try_get("proof")?,
             index_dec: row.try_get("index_dec")?,
-        })
+        };
+        let stored: Option<Vec<u8>> = row.try_get("content_hash")?;
+        if stored.is_some_and(|h| h.as_slice() != proof.content_hash()) {
+            return Err(TronProofCorruptedError { txid }.into());
+        }
+        Ok(proof)
+    }
+
+    /// Deletes a corrupted or stale proof and moves its job from `proof_built` back to
+    /// `tron_sent`, where inclusion is re-checked and the proof is rebuilt from chain data.
+    ///
+    /// A prove userop that carries the old proof is dropped too, unless it is still pending with
+    /// the bundler.
+    pub async fn discard_tron_proof_for_rebuild(
+        &self,
+        job_id: i64,
+        leased_by: &str,
+        txid: [u8; 32],
+        err: &str,
+    ) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("begin discard tron proof")?;
+        sqlx::query("delete from solver.tron_proofs where txid = $1")
+            .bind(txid.to_vec())
+            .execute(&mut *tx)
+            .await
+            .context("delete corrupted solver.tron_proofs")?;
+        sqlx::query(
+            "delete from solver.hub_userops \
+             where job_id = $1 and kind = 'prove'::solver.userop_kind \
+               and (userop_hash is null or (state = 'included' and success = false))",
+        )
+        .bind(job_id)
+        .execute(&mut *tx)
+        .await
+        .context("delete stale prove solver.hub_userops")?;
+        let n = sqlx::query(
+            "update solver.jobs set \
+                state = 'tron_sent', \
+                attempts = attempts + 1, \
+                proof_rebuilds = proof_rebuilds + 1, \
+                last_error = $1, \
+                next_retry_at = now(), \
+                lease_until = now(), \
+                updated_at = now() \
+             where job_id = $2 and leased_by = $3 and state = 'proof_built'",
+        )
+        .bind(err)
+        .bind(job_id)
+        .bind(leased_by)
+        .execute(&mut *tx)
+        .await
+        .context("requeue job for proof rebuild")?
+        .rows_affected();
+        if n != 1 {
+            tx.rollback().await.ok();
+            return Err(self.lost_lease_error(job_id, leased_by).await);
+        }
+        tx.commit().await.context("commit discard tron proof")?;
+        Ok(())
+    }
+
+    /// How many times [`Self::discard_tron_proof_for_rebuild`] has requeued this job.
+    pub async fn job_proof_rebuilds(&self, job_id: i64) -> Result<i32> {
+        sqlx::query_scalar("select proof_rebuilds from solver.jobs where job_id = $1")
+            .bind(job_id)
+            .fetch_one(&self.pool)
+            .await
+            .context("select solver.jobs proof_rebuilds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row() -> TronProofRow {
+        TronProofRow {
+            blocks: vec![vec![1, 2], vec![3]],
+            encoded_tx: vec![4, 5, 6],
+            proof: vec![vec![7; 32]],
+            index_dec: "5".to_string(),
+        }
+    }
+
+    #[test]
+    fn content_hash_is_stable_and_detects_changes() {
+        assert_eq!(row().content_hash(), row().content_hash());
+
+        let mut truncated = row();
+        truncated.proof.clear();
+        assert_ne!(row().content_hash(), truncated.content_hash());
+
+        // Moving a byte across a field boundary must change the hash.
+        let mut shifted = row();
+        shifted.blocks = vec![vec![1], vec![2, 3]];
+        assert_ne!(row().content_hash(), shifted.content_hash());
+    }
+
+    #[test]
+    fn blobs_round_trip_through_each_encoding() {
+        let blob = vec![0xab; 4096];
+        for encoding in [ENCODING_RAW, ENCODING_ZSTD] {
+            let stored = encode_blob(&blob, encoding).unwrap();
+            assert_eq!(decode_blob(stored, encoding).unwrap(), blob);
+        }
+        assert!(encode_blob(&blob, ENCODING_ZSTD).unwrap().len() < blob.len());
+        assert!(decode_blob(blob, "lz4").is_err());
     }
 }
@@ -0,0 +1,29 @@
+use super::*;
+
+impl SolverDb {
+    /// Seconds since the job's proof was last handed to the prove relayer (`None` if never).
+    pub async fn prove_relay_submitted_secs_ago(&self, job_id: i64) -> Result<Option<i64>> {
+        sqlx::query_scalar(
+            "select extract(epoch from now() - last_submitted_at)::bigint \
+             from solver.prove_relay_submissions where job_id = $1",
+        )
+        .bind(job_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("select solver.prove_relay_submissions")
+    }
+
+    pub async fn record_prove_relay_submission(&self, job_id: i64) -> Result<()> {
+        sqlx::query(
+            "insert into solver.prove_relay_submissions(job_id) values ($1) \
+             on conflict (job_id) do update set \
+               submit_count = solver.prove_relay_submissions.submit_count + 1, \
+               last_submitted_at = now()",
+        )
+        .bind(job_id)
+        .execute(&self.pool)
+        .await
+        .context("upsert solver.prove_relay_submissions")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use super::*;
+
+/// One entry of [`SolverDb::job_timeline`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct JobTimelineEvent {
+    /// RFC 3339 UTC timestamp.
+    pub at: String,
+    /// Table the event was read from (archive tables report their live name plus `archived`).
+    pub source: String,
+    /// State, step or kind, depending on the source.
+    pub kind: String,
+    pub fields: serde_json::Value,
+}
+
+impl SolverDb {
+    /// Chronological view of everything recorded for an intent of `pool_id`: the job row, hub
+    /// userops, Tron signed txs, Tron tx costs, rentals, the last candidate skip and any unclaim.
+    /// Archived jobs are included.
+    ///
+    /// Tables only keep their latest state, so rows contribute a "created" event and (where they
+    /// track one) an event for their last update; intermediate transitions are not recoverable.
+    pub async fn job_timeline(
+        &self,
+        pool_id: &str,
+        intent_id: [u8; 32],
+    ) -> Result<Vec<JobTimelineEvent>> {
+        let rows = sqlx::query(
+            "with j as ( \
+                select job_id, intent_type, deadline, state, attempts, last_error, leased_by, \
+                       claim_tx_hash, prove_tx_hash, tron_txid, created_at, updated_at, false as archived \
+                from solver.jobs where pool_id = $2 and intent_id = $1 \
+                union all \
+                select job_id, intent_type, deadline, state, attempts, last_error, leased_by, \
+                       claim_tx_hash, prove_tx_hash, tron_txid, created_at, updated_at, true \
+                from solver.jobs_archive where pool_id = $2 and intent_id = $1 \
+             ), \
+             u as ( \
+                select kind, state, userop_hash, tx_hash, block_number, success, actual_gas_cost_wei, \
+                       attempts, last_error, created_at, updated_at, false as archived \
+                from solver.hub_userops where job_id in (select job_id from j) \
+                union all \
+                select kind, state, userop_hash, tx_hash, block_number, success, actual_gas_cost_wei, \
+                       attempts, last_error, created_at, updated_at, true \
+                from solver.hub_userops_archive where job_id in (select job_id from j) \
+             ), \
+             c as ( \
+                select txid, fee_sun, energy_usage_total, net_usage, energy_fee_sun, net_fee_sun, \
+                       block_number, result_code, result_message, sponsored_energy, \
+                       sponsored_energy_cost_sun, sponsored_energy_provider, updated_at, false as archived \
+                from solver.tron_tx_costs where job_id in (select job_id from j) \
+                union all \
+                select txid, fee_sun, energy_usage_total, net_usage, energy_fee_sun, net_fee_sun, \
+                       block_number, result_code, result_message, sponsored_energy, \
+                       sponsored_energy_cost_sun, sponsored_energy_provider, updated_at, true \
+                from solver.tron_tx_costs_archive where job_id in (select job_id from j) \
+             ), \
+             e as ( \
+                select created_at as at, 'jobs' as source, 'created' as kind, \
+                       jsonb_build_object('job_id', job_id, 'intent_type', intent_type, \
+                                          'deadline', deadline) as fields \
+                from j \
+                union all \
+                select updated_at, 'jobs', state, \
+                       jsonb_build_object('attempts', attempts, 'last_error', last_error, \
+                                          'leased_by', leased_by, \
+                                          'claim_tx_hash', '0x' || encode(claim_tx_hash, 'hex'), \
+                                          'prove_tx_hash', '0x' || encode(prove_tx_hash, 'hex'), \
+                                          'tron_txid', '0x' || encode(tron_txid, 'hex'), \
+                                          'archived', archived) \
+                from j \
+                union all \
+                select created_at, 'hub_userops', kind::text || ':prepared', \
+                       jsonb_build_object('archived', archived) \
+                from u \
+                union all \
+                select updated_at, 'hub_userops', kind::text || ':' || state::text, \
+                       jsonb_build_object('userop_hash', userop_hash, \
+                                          'tx_hash', '0x' || encode(tx_hash, 'hex'), \
+                                          'block_number', block_number, 'success', success, \
+                                          'actual_gas_cost_wei', actual_gas_cost_wei::text, \
+                                          'attempts', attempts, 'last_error', last_error, \
+                                          'archived', archived) \
+                from u \
+                union all \
+                select s.created_at, 'tron_signed_txs', s.step, \
+                       jsonb_build_object('txid', '0x' || encode(s.txid, 'hex'), \
+                                          'fee_limit_sun', s.fee_limit_sun, \
+                                          'fee_limit_estimate_sun', s.fee_limit_estimate_sun, \
+                                          'energy_required', s.energy_required, \
+                                          'tx_size_bytes', s.tx_size_bytes) \
+                from solver.tron_signed_txs s where s.job_id in (select job_id from j) \
+                union all \
+                select updated_at, 'tron_tx_costs', \
+                       case when block_number is null then 'pending' \
+                            when result_code is null or result_code = 0 then 'confirmed' \
+                            else 'failed' end, \
+                       jsonb_build_object('txid', '0x' || encode(txid, 'hex'), 'fee_sun', fee_sun, \
+                                          'energy_usage_total', energy_usage_total, \
+                                          'net_usage', net_usage, 'energy_fee_sun', energy_fee_sun, \
+                                          'net_fee_sun', net_fee_sun, 'block_number', block_number, \
+                                          'result_code', result_code, \
+                                          'result_message', result_message, \
+                                          'sponsored_energy', sponsored_energy, \
+                                          'sponsored_energy_cost_sun', sponsored_energy_cost_sun, \
+                                          'sponsored_energy_provider', sponsored_energy_provider, \
+                                          'archived', archived) \
+                from c \
+                union all \
+                select r.created_at, 'tron_rentals', 'created', \
+                       jsonb_build_object('provider', r.provider, 'resource', r.resource, \
+                                          'balance_sun', r.balance_sun, \
+                                          'lock_period', r.lock_period) \
+                from solver.tron_rentals r where r.job_id in (select job_id from j) \
+                union all \
+                select r.updated_at, 'tron_rentals', 'updated', \
+                       jsonb_build_object('provider', r.provider, 'order_id', r.order_id, \
+                                          'txid', '0x' || encode(r.txid, 'hex')) \
+                from solver.tron_rentals r \
+                where r.job_id in (select job_id from j) and r.updated_at > r.created_at \
+                union all \
+                select last_seen_at, 'intent_skips', reason, \
+                       jsonb_build_object('details', details, 'skip_count', skip_count, \
+                                          'first_seen_at', first_seen_at) \
+                from solver.intent_skips where intent_id = $1 \
+                union all \
+                select created_at, 'job_unclaims', 'unclaimed', \
+                       jsonb_build_object('from_state', from_state, \
+                                          'tx_hash', '0x' || encode(unclaim_tx_hash, 'hex'), \
+                                          'funded', funded, \
+                                          'recovered_deposit', recovered_deposit::text, \
+                                          'reason', reason) \
+                from solver.job_unclaims where pool_id = $2 and intent_id = $1 \
+             ) \
+             select to_char(at at time zone 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS.US\"Z\"') as at, \
+                    source, kind, jsonb_strip_nulls(fields)::text as fields_json \
+             from e \
+             order by e.at asc, source asc",
+        )
+        .bind(intent_id.to_vec())
+        .bind(pool_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("select job timeline")?;
+
+        let mut out = Vec::with_capacity(rows.len());
+        for r in rows {
+            let fields_json: String = r.try_get("fields_json")?;
+            out.push(JobTimelineEvent {
+                at: r.try_get("at")?,
+                source: r.try_get("source")?,
+                kind: r.try_get("kind")?,
+                fields: serde_json::from_str(&fields_json).context("parse timeline fields")?,
+            });
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,85 @@
+use super::*;
+
+impl SolverDb {
+    /// Takes one token from the bucket `key`, which holds at most `per_minute` tokens and refills
+    /// at `per_minute` tokens per minute (starting full). Returns `Some(wait_secs)` until a token
+    /// is available; `per_minute == 0` disables the bucket.
+    pub async fn take_claim_token(&self, key: &str, per_minute: u64) -> Result<Option<i64>> {
+        if per_minute == 0 {
+            return Ok(None);
+        }
+        let capacity = per_minute as f64;
+        let mut tx = self.pool.begin().await.context("begin token bucket tx")?;
+
+        sqlx::query(
+            "insert into solver.claim_token_buckets(key, tokens, updated_at) \
+             values ($1, $2, now()) \
+             on conflict (key) do nothing",
+        )
+        .bind(key)
+        .bind(capacity)
+        .execute(&mut *tx)
+        .await
+        .context("init solver.claim_token_buckets")?;
+
+        let row = sqlx::query(
+            "select tokens, extract(epoch from (now() - updated_at))::float8 as elapsed_secs \
+             from solver.claim_token_buckets where key = $1 for update",
+        )
+        .bind(key)
+        .fetch_one(&mut *tx)
+        .await
+        .context("select solver.claim_token_buckets for update")?;
+        let tokens: f64 = row.try_get("tokens")?;
+        let elapsed_secs: f64 = row.try_get("elapsed_secs")?;
+
+        let (tokens, wait) = token_bucket_take(tokens, elapsed_secs, capacity);
+        sqlx::query(
+            "update solver.claim_token_buckets set tokens = $2, updated_at = now() where key = $1",
+        )
+        .bind(key)
+        .bind(tokens)
+        .execute(&mut *tx)
+        .await
+        .context("update solver.claim_token_buckets")?;
+        tx.commit().await.context("commit token bucket tx")?;
+        Ok(wait)
+    }
+}
+
+/// Refills a bucket of `capacity` tokens (refilling `capacity` per minute) for `elapsed_secs`,
+/// then takes one token if available. Returns the new level and, when empty, the seconds until
+/// the next token.
+fn token_bucket_take(tokens: f64, elapsed_secs: f64, capacity: f64) -> (f64, Option<i64>) {
+    let per_sec = capacity / 60.0;
+    let level = (tokens + elapsed_secs.max(0.0) * per_sec).min(capacity);
+    if level >= 1.0 {
+        return (level - 1.0, None);
+    }
+    let wait = ((1.0 - level) / per_sec).ceil() as i64;
+    (level, Some(wait.max(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::token_bucket_take;
+
+    #[test]
+    fn token_bucket_refills_continuously_and_caps_at_capacity() {
+        // 60/min = 1 token per second.
+        let (level, wait) = token_bucket_take(60.0, 0.0, 60.0);
+        assert_eq!((level, wait), (59.0, None));
+
+        // Idle for an hour: refill stops at capacity.
+        let (level, wait) = token_bucket_take(10.0, 3600.0, 60.0);
+        assert_eq!((level, wait), (59.0, None));
+
+        // Empty bucket: half a second later there is half a token, so wait one more second.
+        let (level, wait) = token_bucket_take(0.0, 0.5, 60.0);
+        assert_eq!((level, wait), (0.5, Some(1)));
+
+        // 30/min = one token every 2s.
+        let (_, wait) = token_bucket_take(0.25, 0.0, 30.0);
+        assert_eq!(wait, Some(2));
+    }
+}
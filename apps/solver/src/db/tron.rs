@@ -9,6 +9,7 @@ impl SolverDb {
         txid: [u8; 32],
         tx_bytes: &[u8],
         fee_limit_sun: Option<i64>,
+        fee_limit_estimate_sun: Option<i64>,
         energy_required: Option<i64>,
         tx_size_bytes: Option<i64>,
     ) -> Result<()> {
@@ -16,13 +17,14 @@ impl SolverDb {
         let mut tx = self.pool.begin().await.context("begin tron_prepared tx")?;
 
         sqlx::query(
-            "insert into solver.tron_signed_txs(txid, job_id, step, tx_bytes, fee_limit_sun, energy_required, tx_size_bytes, updated_at) \
-             values ($1, $2, 'final', $3, $4, $5, $6, now()) \
+            "insert into solver.tron_signed_txs(txid, job_id, step, tx_bytes, fee_limit_sun, fee_limit_estimate_sun, energy_required, tx_size_bytes, updated_at) \
+             values ($1, $2, 'final', $3, $4, $5, $6, $7, now()) \
              on conflict (txid) do update set \
                 job_id = excluded.job_id, \
                 step = excluded.step, \
                 tx_bytes = excluded.tx_bytes, \
                 fee_limit_sun = excluded.fee_limit_sun, \
+                fee_limit_estimate_sun = excluded.fee_limit_estimate_sun, \
                 energy_required = excluded.energy_required, \
                 tx_size_bytes = excluded.tx_size_bytes, \
                 updated_at = now()",
@@ -31,6 +33,7 @@ impl SolverDb {
         .bind(job_id)
         .bind(tx_bytes)
         .bind(fee_limit_sun)
+        .bind(fee_limit_estimate_sun)
         .bind(energy_required)
         .bind(tx_size_bytes)
         .execute(&mut *tx)
@@ -51,19 +54,22 @@ impl SolverDb {
         .context("record tron_prepared")?
         .rows_affected();
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
 
         tx.commit().await.context("commit tron_prepared tx")?;
         Ok(())
     }
 
+    /// Persists a signed plan and moves the job to `tron_prepared`. `spends` go into the per-key
+    /// daily spend ledger in the same transaction.
     pub async fn record_tron_plan(
         &self,
         job_id: i64,
         leased_by: &str,
         pre_txs: &[TronSignedTxRow],
         final_tx: &TronSignedTxRow,
+        spends: &[TronKeySpendRow],
     ) -> Result<()> {
         let expected_states = super::jobs::transitions::expected_state_binds("tron_prepared")?;
         let mut tx = self.pool.begin().await.context("begin tron_plan tx")?;
@@ -77,13 +83,14 @@ impl SolverDb {
 
         for row in pre_txs.iter().chain(std::iter::once(final_tx)) {
             sqlx::query(
-                "insert into solver.tron_signed_txs(txid, job_id, step, tx_bytes, fee_limit_sun, energy_required, tx_size_bytes, updated_at) \
-                 values ($1, $2, $3, $4, $5, $6, $7, now()) \
+                "insert into solver.tron_signed_txs(txid, job_id, step, tx_bytes, fee_limit_sun, fee_limit_estimate_sun, energy_required, tx_size_bytes, updated_at) \
+                 values ($1, $2, $3, $4, $5, $6, $7, $8, now()) \
                  on conflict (txid) do update set \
                     job_id = excluded.job_id, \
                     step = excluded.step, \
                     tx_bytes = excluded.tx_bytes, \
                     fee_limit_sun = excluded.fee_limit_sun, \
+                    fee_limit_estimate_sun = excluded.fee_limit_estimate_sun, \
                     energy_required = excluded.energy_required, \
                     tx_size_bytes = excluded.tx_size_bytes, \
                     updated_at = now()",
@@ -93,6 +100,7 @@ impl SolverDb {
             .bind(&row.step)
             .bind(&row.tx_bytes)
             .bind(row.fee_limit_sun)
+            .bind(row.fee_limit_estimate_sun)
             .bind(row.energy_required)
             .bind(row.tx_size_bytes)
             .execute(&mut *tx)
@@ -100,6 +108,22 @@ impl SolverDb {
             .context("upsert solver.tron_signed_txs (plan row)")?;
         }
 
+        for spend in spends {
+            sqlx::query(
+                "insert into solver.tron_key_spends(txid, job_id, owner_address, asset, amount) \
+                 values ($1, $2, $3, $4, $5::numeric) \
+                 on conflict (txid) do nothing",
+            )
+            .bind(spend.txid.to_vec())
+            .bind(job_id)
+            .bind(&spend.owner_address)
+            .bind(spend.asset)
+            .bind(spend.amount.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("insert solver.tron_key_spends")?;
+        }
+
         let n = sqlx::query(
             "update solver.jobs set state='tron_prepared', tron_txid=$1, updated_at=now() \
              where job_id=$2 and leased_by=$3 and lease_until >= now() \
@@ -114,16 +138,123 @@ impl SolverDb {
         .context("record tron_prepared (plan)")?
         .rows_affected();
         if n != 1 {
-            anyhow::bail!("lost job lease for job_id={job_id}");
+            return Err(self.lost_lease_error(job_id, leased_by).await);
         }
 
         tx.commit().await.context("commit tron_plan tx")?;
         Ok(())
     }
 
+    /// Drops a job's signed Tron txs and sends the job back to `claimed`, where the plan is rebuilt
+    /// and re-signed: after one expired unbroadcast, or once an approve-only plan landed.
+    pub async fn discard_tron_plan(
+        &self,
+        job_id: i64,
+        leased_by: &str,
+        reason: &str,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await.context("begin discard plan tx")?;
+
+        sqlx::query("delete from solver.tron_signed_txs where job_id = $1")
+            .bind(job_id)
+            .execute(&mut *tx)
+            .await
+            .context("delete solver.tron_signed_txs (discarded plan)")?;
+
+        let n = sqlx::query(
+            "update solver.jobs set \
+                state = 'claimed', \
+                tron_txid = null, \
+                last_error = $1, \
+                next_retry_at = now(), \
+                lease_until = now(), \
+                updated_at = now() \
+             where job_id=$2 and leased_by=$3 and state = 'tron_prepared'",
+        )
+        .bind(reason)
+        .bind(job_id)
+        .bind(leased_by)
+        .execute(&mut *tx)
+        .await
+        .context("record claimed (discarded tron plan)")?
+        .rows_affected();
+        if n != 1 {
+            return Err(self.lost_lease_error(job_id, leased_by).await);
+        }
+
+        tx.commit().await.context("commit discard plan tx")?;
+        Ok(())
+    }
+
+    /// Stamps the Tron head a `tron_sent` job's tx was sent at (kept if already set).
+    pub async fn record_tron_broadcast_block(
+        &self,
+        job_id: i64,
+        leased_by: &str,
+        block: i64,
+    ) -> Result<()> {
+        let n = sqlx::query(
+            "update solver.jobs set \
+                tron_broadcast_block = coalesce(tron_broadcast_block, $1), \
+                updated_at = now() \
+             where job_id=$2 and leased_by=$3 and state = 'tron_sent'",
+        )
+        .bind(block)
+        .bind(job_id)
+        .bind(leased_by)
+        .execute(&self.pool)
+        .await
+        .context("record tron_broadcast_block")?
+        .rows_affected();
+        if n != 1 {
+            return Err(self.lost_lease_error(job_id, leased_by).await);
+        }
+        Ok(())
+    }
+
+    /// Sends a `tron_sent` job whose tx never landed back to `tron_prepared`, which re-broadcasts
+    /// the stored signed tx (or rebuilds it if it has expired).
+    pub async fn record_tron_not_included(
+        &self,
+        job_id: i64,
+        leased_by: &str,
+        reason: &str,
+    ) -> Result<()> {
+        let n = sqlx::query(
+            "update solver.jobs set \
+                state = 'tron_prepared', \
+                tron_broadcast_block = null, \
+                last_error = $1, \
+                next_retry_at = now(), \
+                lease_until = now(), \
+                updated_at = now() \
+             where job_id=$2 and leased_by=$3 and state = 'tron_sent'",
+        )
+        .bind(reason)
+        .bind(job_id)
+        .bind(leased_by)
+        .execute(&self.pool)
+        .await
+        .context("record tron_prepared (tx not included)")?
+        .rows_affected();
+        if n != 1 {
+            return Err(self.lost_lease_error(job_id, leased_by).await);
+        }
+        Ok(())
+    }
+
+    pub async fn has_tron_signed_tx(&self, txid: [u8; 32]) -> Result<bool> {
+        sqlx::query_scalar("select exists(select 1 from solver.tron_signed_txs where txid = $1)")
+            .bind(txid.to_vec())
+            .fetch_one(&self.pool)
+            .await
+            .context("select solver.tron_signed_txs (exists)")
+    }
+
     pub async fn list_tron_signed_txs_for_job(&self, job_id: i64) -> Result<Vec<TronSignedTxRow>> {
         let rows = sqlx::query(
-            "select step, txid, tx_bytes, fee_limit_sun, energy_required, tx_size_bytes \
+            "select step, txid, tx_bytes, fee_limit_sun, fee_limit_estimate_sun, energy_required, \
+                    tx_size_bytes \
              from solver.tron_signed_txs \
              where job_id = $1 \
              order by (step = 'final')::int, step asc",
@@ -143,6 +274,7 @@ impl SolverDb {
                 txid: t,
                 tx_bytes: r.try_get("tx_bytes")?,
                 fee_limit_sun: r.try_get("fee_limit_sun")?,
+                fee_limit_estimate_sun: r.try_get("fee_limit_estimate_sun")?,
                 energy_required: r.try_get("energy_required")?,
                 tx_size_bytes: r.try_get("tx_size_bytes")?,
             });
@@ -205,6 +337,45 @@ impl SolverDb {
         Ok(())
     }
 
+    /// Takes the job's rental order lock for `ttl_secs`, returning the token to release it with.
+    /// `None` while any other holder's lock is unexpired, including another task of this instance.
+    pub async fn try_lock_tron_rental_order(
+        &self,
+        job_id: i64,
+        locked_by: &str,
+        ttl_secs: u64,
+    ) -> Result<Option<String>> {
+        let ttl = i64::try_from(ttl_secs).unwrap_or(i64::MAX);
+        sqlx::query_scalar(
+            "insert into solver.tron_rental_order_locks(job_id, token, locked_by, locked_until) \
+             values ($1, gen_random_uuid()::text, $2, now() + make_interval(secs => $3)) \
+             on conflict (job_id) do update set \
+                token = excluded.token, \
+                locked_by = excluded.locked_by, \
+                locked_until = excluded.locked_until \
+             where solver.tron_rental_order_locks.locked_until < now() \
+             returning token",
+        )
+        .bind(job_id)
+        .bind(locked_by)
+        .bind(ttl)
+        .fetch_optional(&self.pool)
+        .await
+        .context("lock solver.tron_rental_order_locks")
+    }
+
+    /// Releases a lock taken by [`Self::try_lock_tron_rental_order`]; a no-op once it has expired
+    /// and been re-taken.
+    pub async fn unlock_tron_rental_order(&self, job_id: i64, token: &str) -> Result<()> {
+        sqlx::query("delete from solver.tron_rental_order_locks where job_id = $1 and token = $2")
+            .bind(job_id)
+            .bind(token)
+            .execute(&self.pool)
+            .await
+            .context("unlock solver.tron_rental_order_locks")?;
+        Ok(())
+    }
+
     pub async fn get_tron_rental_for_job(&self, job_id: i64) -> Result<Option<TronRentalRow>> {
         let row = sqlx::query(
             "select provider, resource, receiver_evm, balance_sun, lock_period, order_id, txid, request_json, response_json \
@@ -360,6 +531,56 @@ impl SolverDb {
         Ok(())
     }
 
+    /// Folds one order round-trip into the provider's rolling average and returns the new average.
+    ///
+    /// Plain mean over the first `RENTAL_LATENCY_WINDOW` samples, then an exponential moving average
+    /// with the same window so a provider that slows down (or recovers) is noticed quickly.
+    pub async fn rental_provider_record_order_ms(&self, provider: &str, ms: u64) -> Result<f64> {
+        const RENTAL_LATENCY_WINDOW: i64 = 10;
+        let ms = i64::try_from(ms).unwrap_or(i64::MAX);
+        let avg: f64 = sqlx::query_scalar(
+            "insert into solver.rental_provider_stats(provider, avg_order_ms, samples, last_order_ms, updated_at) \
+             values ($1, $2::double precision, 1, $2, now()) \
+             on conflict (provider) do update set \
+                avg_order_ms = solver.rental_provider_stats.avg_order_ms \
+                    + ($2::double precision - solver.rental_provider_stats.avg_order_ms) \
+                    / least(solver.rental_provider_stats.samples + 1, $3)::double precision, \
+                samples = solver.rental_provider_stats.samples + 1, \
+                last_order_ms = $2, \
+                updated_at = now() \
+             returning avg_order_ms",
+        )
+        .bind(provider)
+        .bind(ms)
+        .bind(RENTAL_LATENCY_WINDOW)
+        .fetch_one(&self.pool)
+        .await
+        .context("upsert solver.rental_provider_stats")?;
+        Ok(avg)
+    }
+
+    pub async fn rental_provider_stats(&self) -> Result<Vec<RentalProviderStatsRow>> {
+        let rows = sqlx::query(
+            "select provider, avg_order_ms, samples, last_order_ms \
+             from solver.rental_provider_stats \
+             order by avg_order_ms asc, provider asc",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("select solver.rental_provider_stats")?;
+
+        rows.into_iter()
+            .map(|r| {
+                Ok(RentalProviderStatsRow {
+                    provider: r.try_get("provider")?,
+                    avg_order_ms: r.try_get("avg_order_ms")?,
+                    samples: r.try_get("samples")?,
+                    last_order_ms: r.try_get("last_order_ms")?,
+                })
+            })
+            .collect()
+    }
+
     pub async fn upsert_tron_tx_costs(
         &self,
         job_id: i64,
@@ -407,6 +628,79 @@ impl SolverDb {
         Ok(())
     }
 
+    /// Records energy rented for `txid`'s sender ahead of its broadcast on the tx's cost row,
+    /// creating the row if the tx hasn't been observed yet.
+    pub async fn record_tron_sponsored_energy(
+        &self,
+        job_id: i64,
+        txid: [u8; 32],
+        intent_type: i16,
+        energy: i64,
+        cost_sun: Option<i64>,
+        provider: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            "insert into solver.tron_tx_costs( \
+                txid, job_id, intent_type, sponsored_energy, sponsored_energy_cost_sun, \
+                sponsored_energy_provider, updated_at \
+             ) values ($1, $2, $3, $4, $5, $6, now()) \
+             on conflict (txid) do update set \
+                sponsored_energy = excluded.sponsored_energy, \
+                sponsored_energy_cost_sun = excluded.sponsored_energy_cost_sun, \
+                sponsored_energy_provider = excluded.sponsored_energy_provider, \
+                updated_at = now()",
+        )
+        .bind(txid.to_vec())
+        .bind(job_id)
+        .bind(intent_type)
+        .bind(energy)
+        .bind(cost_sun)
+        .bind(provider)
+        .execute(&self.pool)
+        .await
+        .context("record solver.tron_tx_costs sponsored energy")?;
+        Ok(())
+    }
+
+    /// Splits recent fees for `intent_type` into a size-independent part and a per-byte bandwidth
+    /// part, using the size recorded when each tx was signed. `None` without such history.
+    pub async fn tron_tx_costs_fee_split(
+        &self,
+        intent_type: i16,
+        lookback: i64,
+    ) -> Result<Option<TronFeeSplit>> {
+        let lookback = lookback.clamp(1, 10_000);
+        let row = sqlx::query(
+            "select \
+               avg(t.fee_sun - t.net_fee_sun)::float8 as base_fee_sun, \
+               avg(t.net_fee_sun::float8 / t.tx_size_bytes)::float8 as net_fee_sun_per_byte \
+             from ( \
+               select c.fee_sun, coalesce(c.net_fee_sun, 0) as net_fee_sun, s.tx_size_bytes \
+               from solver.tron_tx_costs c \
+               join solver.tron_signed_txs s on s.txid = c.txid \
+               where c.fee_sun is not null \
+                 and c.intent_type = $1 \
+                 and s.tx_size_bytes > 0 \
+               order by c.updated_at desc \
+               limit $2 \
+             ) t",
+        )
+        .bind(intent_type)
+        .bind(lookback)
+        .fetch_one(&self.pool)
+        .await
+        .context("split solver.tron_tx_costs fees")?;
+        let base: Option<f64> = row.try_get("base_fee_sun")?;
+        let per_byte: Option<f64> = row.try_get("net_fee_sun_per_byte")?;
+        Ok(match (base, per_byte) {
+            (Some(b), Some(p)) if b.is_finite() && p.is_finite() => Some(TronFeeSplit {
+                base_fee_sun: b.max(0.0),
+                net_fee_sun_per_byte: p.max(0.0),
+            }),
+            _ => None,
+        })
+    }
+
     pub async fn tron_tx_costs_avg_fee_sun(
         &self,
         intent_type: i16,
@@ -438,4 +732,33 @@ impl SolverDb {
             }
         }))
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_consolidation_sweep(
+        &self,
+        asset: &str,
+        from_evm: [u8; 20],
+        to_evm: [u8; 20],
+        amount: u64,
+        txid: [u8; 32],
+        instance_id: &str,
+        error: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            "insert into solver.consolidation_sweeps( \
+                asset, from_evm, to_evm, amount, txid, instance_id, error \
+             ) values ($1, $2, $3, $4::numeric, $5, $6, $7)",
+        )
+        .bind(asset)
+        .bind(from_evm.to_vec())
+        .bind(to_evm.to_vec())
+        .bind(amount.to_string())
+        .bind(txid.to_vec())
+        .bind(instance_id)
+        .bind(error)
+        .execute(&self.pool)
+        .await
+        .context("insert solver.consolidation_sweeps")?;
+        Ok(())
+    }
 }
@@ -0,0 +1,81 @@
+use super::*;
+use crate::types::JobState;
+
+impl SolverDb {
+    /// Moves a `claimed` / `tron_prepared` job to the terminal `unclaimed` state after we released
+    /// its hub claim, records the unclaim (tx, recovered deposit) in `solver.job_unclaims` and
+    /// drops the job's delegate reservation, all in one transaction.
+    ///
+    /// `unclaim_tx_hash` is `None` when the claim was already gone on-chain (someone else
+    /// unclaimed it), in which case nothing is known about the deposit.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_unclaimed(
+        &self,
+        job_id: i64,
+        leased_by: &str,
+        from_state: JobState,
+        unclaim_tx_hash: Option<[u8; 32]>,
+        funded: Option<bool>,
+        recovered_deposit: Option<U256>,
+        reason: &str,
+    ) -> Result<()> {
+        let expected_states =
+            super::jobs::transitions::expected_state_binds_for(JobState::Unclaimed);
+        let mut tx = self.pool.begin().await.context("begin unclaimed tx")?;
+
+        let row = sqlx::query(
+            "update solver.jobs set \
+                state = 'unclaimed', \
+                last_error = $1, \
+                claim_window_expires_at = null, \
+                lease_until = now(), \
+                updated_at = now() \
+             where job_id = $2 and leased_by = $3 and lease_until >= now() \
+               and state = any($4::text[]) \
+             returning pool_id, intent_id, intent_type",
+        )
+        .bind(reason)
+        .bind(job_id)
+        .bind(leased_by)
+        .bind(expected_states)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("record unclaimed")?;
+        let Some(row) = row else {
+            tx.rollback().await.ok();
+            return Err(self.lost_lease_error(job_id, leased_by).await);
+        };
+        let pool_id: String = row.try_get("pool_id")?;
+        let intent_id: Vec<u8> = row.try_get("intent_id")?;
+        let intent_type: i16 = row.try_get("intent_type")?;
+
+        sqlx::query(
+            "insert into solver.job_unclaims( \
+                pool_id, intent_id, job_id, intent_type, from_state, unclaim_tx_hash, funded, \
+                recovered_deposit, reason \
+             ) values ($1, $2, $3, $4, $5, $6, $7, $8::numeric, $9) \
+             on conflict (pool_id, intent_id) do nothing",
+        )
+        .bind(pool_id)
+        .bind(intent_id)
+        .bind(job_id)
+        .bind(intent_type)
+        .bind(from_state.as_db_str())
+        .bind(unclaim_tx_hash.map(|h| h.to_vec()))
+        .bind(funded)
+        .bind(recovered_deposit.map(|v| v.to_string()))
+        .bind(reason)
+        .execute(&mut *tx)
+        .await
+        .context("insert solver.job_unclaims")?;
+
+        sqlx::query("delete from solver.delegate_reservations where job_id = $1")
+            .bind(job_id)
+            .execute(&mut *tx)
+            .await
+            .context("delete solver.delegate_reservations (unclaimed)")?;
+
+        tx.commit().await.context("commit unclaimed tx")?;
+        Ok(())
+    }
+}
@@ -4,12 +4,12 @@ use alloy::primitives::{Address, B256, U256};
 use alloy::providers::{DynProvider, Provider, ProviderBuilder};
 use alloy::rpc::types::eth::erc4337::PackedUserOperation;
 use alloy::rpc::types::{BlockNumberOrTag, Filter, TransactionReceipt};
-use alloy::signers::Signer;
-use alloy::signers::local::PrivateKeySigner;
-use alloy::sol_types::SolCall;
+use alloy::sol_types::{SolCall, SolInterface};
 use anyhow::{Context, Result};
 use reqwest::Client;
-use std::time::Instant;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use url::Url;
 
 mod safe4337;
@@ -37,7 +37,10 @@ alloy::sol! {
         interface IUntronIntents {
             function USDT() external view returns (address);
             function V3() external view returns (address);
+            function INTENT_CLAIM_DEPOSIT() external view returns (uint256);
             function claimIntent(bytes32 id) external;
+            function unclaimIntent(bytes32 id) external;
+            function settleIntent(bytes32 id) external;
             function proveIntentFill(bytes32 id, bytes[20] calldata blocks, bytes calldata encodedTx, bytes32[] calldata proof, uint256 index) external;
             function intents(bytes32 id)
                 external
@@ -51,6 +54,16 @@ alloy::sol! {
                     bool funded,
                     bool settled
                 );
+
+            event IntentUnclaimed(
+                bytes32 indexed id,
+                address indexed caller,
+                address indexed prevSolver,
+                bool funded,
+                uint256 depositToCaller,
+                uint256 depositToRefundBeneficiary,
+                uint256 depositToPrevSolver
+            );
         }
 
     #[sol(rpc)]
@@ -63,6 +76,8 @@ alloy::sol! {
     interface IERC20 {
         function allowance(address owner, address spender) external view returns (uint256);
         function approve(address spender, uint256 amount) external returns (bool);
+        function balanceOf(address owner) external view returns (uint256);
+        function transferFrom(address from, address to, uint256 amount) external returns (bool);
     }
 
     struct TransferContract {
@@ -96,6 +111,35 @@ alloy::sol! {
         bool lock;
     }
 
+    /// `TronTxReaderErrors`: anything the Tron reader reverts with while verifying a proof.
+    interface ITronTxReaderErrors {
+        error SrSetNotSorted(uint256 index, bytes20 prev, bytes20 next);
+        error UnknownSr(bytes20 sr);
+        error DuplicateSr(bytes20 sr);
+        error InvalidBlockSequence();
+        error InvalidEncodedBlockLength(uint256 got);
+        error InvalidHeaderPrefix();
+        error InvalidWitnessAddressPrefix(uint8 got);
+        error InvalidWitnessSignature();
+        error TimestampOverflow();
+        error InvalidTxMerkleProof();
+        error NotTriggerSmartContract();
+        error NotTransferContract();
+        error NotDelegateResourceContract();
+        error TronTxNotSuccessful();
+        error TronInvalidOwnerLength();
+        error TronInvalidOwnerPrefix();
+        error TronInvalidContractLength();
+        error TronInvalidContractPrefix();
+        error TronInvalidCallValue();
+        error TronInvalidResource();
+        error TronInvalidReceiverLength();
+        error TronInvalidReceiverPrefix();
+        error TronInvalidBalance();
+        error TronInvalidLock();
+        error TronInvalidLockPeriod();
+    }
+
     #[sol(rpc)]
     interface IMockTronTxReader {
         function setTx(TriggerSmartContract calldata tx_) external;
@@ -106,6 +150,54 @@ alloy::sol! {
 
 pub struct HubClient {
     inner: HubClientInner,
+    addresses: AddressCache,
+    deposit_funder: Option<DepositFunder>,
+    /// Blocks (the inclusion block counts as one) an EOA claim/prove must be buried under before
+    /// its receipt is returned.
+    min_confirmations: u64,
+}
+
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Budget per required confirmation before `await_confirmations` gives up.
+const CONFIRMATION_WAIT_PER_BLOCK: Duration = Duration::from_secs(60);
+
+/// Account the solver pulls claim deposits from (see `ensure_deposit_funded`).
+struct DepositFunder {
+    address: Address,
+    /// Wallet provider for the funder, used only to approve the solver.
+    provider: Option<DynProvider>,
+}
+
+/// Contract addresses read from immutable-ish pool/V3 getters, reused for `ttl` so hot paths
+/// (every USDT intent, every claim) don't cost an RPC round trip each.
+struct AddressCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<&'static str, (Address, Instant)>>,
+}
+
+impl AddressCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get_at(&self, key: &'static str, now: Instant) -> Option<Address> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(key)
+            .filter(|(_, at)| now.saturating_duration_since(*at) < self.ttl)
+            .map(|(addr, _)| *addr)
+    }
+
+    fn put_at(&self, key: &'static str, addr: Address, now: Instant) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(key, (addr, now));
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -145,8 +237,12 @@ struct HubSafe4337Client {
     provider: DynProvider,
     solver: Address,
     entrypoint: Address,
-    bundler_urls: Vec<String>,
+    /// Shared with `sender`'s failover, so receipt polling skips bundlers it keeps failing on.
+    bundler_health: aa::BundlerHealthHandle,
     sender: tokio::sync::Mutex<Safe4337UserOpSender>,
+    /// Bundler that accepted each recently sent userop, keyed by hash; receipts are asked there
+    /// first. In-memory only: after a restart every bundler (then the EntryPoint logs) is asked.
+    accepted_by: Mutex<VecDeque<(String, String)>>,
     http: Client,
     telemetry: SolverTelemetry,
 }
@@ -164,32 +260,26 @@ impl HubClient {
         rpc_url: &str,
         chain_id: Option<u64>,
         pool: Address,
-        signer_private_key: [u8; 32],
+        signer: Arc<dyn aa::HubSigner>,
         telemetry: SolverTelemetry,
     ) -> Result<Self> {
         let url: Url = rpc_url.parse().context("parse HUB_RPC_URL")?;
         let base_provider = ProviderBuilder::new().connect_http(url.clone());
         let base_provider = DynProvider::new(base_provider);
 
-        // Discover chain id (required for EIP-155 signatures) and optionally validate it.
+        // Validate the configured chain id; the provider's chain id filler sets it on every tx
+        // (EIP-155) before the signer sees it.
         let started = Instant::now();
         let discovered = base_provider.get_chain_id().await.context("eth_chainId")?;
         telemetry.hub_rpc_ms("eth_chainId", true, started.elapsed().as_millis() as u64);
-        let chain_id = match chain_id {
-            Some(expected) => {
-                if discovered != expected {
-                    anyhow::bail!("HUB_CHAIN_ID mismatch: configured={expected} rpc={discovered}");
-                }
-                expected
-            }
-            None => discovered,
-        };
+        if let Some(expected) = chain_id
+            && discovered != expected
+        {
+            anyhow::bail!("HUB_CHAIN_ID mismatch: configured={expected} rpc={discovered}");
+        }
 
-        let signer = PrivateKeySigner::from_slice(&signer_private_key)
-            .context("invalid HUB_SIGNER_PRIVATE_KEY_HEX")?
-            .with_chain_id(Some(chain_id));
         let eoa = signer.address();
-        let wallet = alloy::network::EthereumWallet::from(signer);
+        let wallet = aa::HubSignerWallet(signer);
 
         let provider = ProviderBuilder::new().wallet(wallet).connect_http(url);
         let provider = DynProvider::new(provider);
@@ -201,6 +291,9 @@ impl HubClient {
                 eoa,
                 telemetry,
             }),
+            addresses: AddressCache::new(Duration::ZERO),
+            deposit_funder: None,
+            min_confirmations: 1,
         })
     }
 
@@ -215,7 +308,8 @@ impl HubClient {
         safe_deployment: Option<aa::SafeDeterministicDeploymentConfig>,
         bundler_urls: Vec<String>,
         paymasters: Vec<aa::paymaster::PaymasterService>,
-        signer_private_key: [u8; 32],
+        options: Safe4337UserOpSenderOptions,
+        signer: Arc<dyn aa::HubSigner>,
         telemetry: SolverTelemetry,
     ) -> Result<Self> {
         let url: Url = rpc_url.parse().context("parse HUB_RPC_URL")?;
@@ -242,10 +336,10 @@ impl HubClient {
             safe,
             safe_4337_module,
             safe_deployment,
-            bundler_urls: bundler_urls.clone(),
-            owner_private_key: signer_private_key,
+            bundler_urls,
+            owner: signer,
             paymasters,
-            options: Safe4337UserOpSenderOptions::default(),
+            options,
         })
         .await
         .context("init Safe4337UserOpSender")?;
@@ -256,14 +350,76 @@ impl HubClient {
                 provider,
                 solver: sender.safe_address(),
                 entrypoint,
-                bundler_urls,
+                bundler_health: sender.bundler_health(),
                 sender: tokio::sync::Mutex::new(sender),
+                accepted_by: Mutex::new(VecDeque::new()),
                 http: Client::new(),
                 telemetry,
             })),
+            addresses: AddressCache::new(Duration::ZERO),
+            deposit_funder: None,
+            min_confirmations: 1,
         })
     }
 
+    /// Makes EOA `claim_intent` / `prove_intent_fill` wait until their tx is `n` blocks deep
+    /// (1 = return the first receipt).
+    pub fn with_min_confirmations(mut self, n: u64) -> Self {
+        self.min_confirmations = n.max(1);
+        self
+    }
+
+    /// Caches `pool_usdt`, `pool_v3`, `v3_tron_usdt` and `v3_controller_address` for `ttl`
+    /// (zero disables caching).
+    pub fn with_address_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.addresses = AddressCache::new(ttl);
+        self
+    }
+
+    /// Sources claim deposits from `funder` instead of the solver's own balance. With
+    /// `funder_signer` the client approves the solver from the funder when needed.
+    pub fn with_deposit_funder(
+        mut self,
+        rpc_url: &str,
+        funder: Address,
+        funder_signer: Option<Arc<dyn aa::HubSigner>>,
+    ) -> Result<Self> {
+        let provider = match funder_signer {
+            Some(signer) => {
+                if signer.address() != funder {
+                    anyhow::bail!(
+                        "deposit funder signer is for {}, not {funder}",
+                        signer.address()
+                    );
+                }
+                let url: Url = rpc_url.parse().context("parse HUB_RPC_URL")?;
+                let wallet = aa::HubSignerWallet(signer);
+                Some(DynProvider::new(
+                    ProviderBuilder::new().wallet(wallet).connect_http(url),
+                ))
+            }
+            None => None,
+        };
+        self.deposit_funder = Some(DepositFunder {
+            address: funder,
+            provider,
+        });
+        Ok(self)
+    }
+
+    async fn cached_address(
+        &self,
+        key: &'static str,
+        fetch: impl std::future::Future<Output = Result<Address>>,
+    ) -> Result<Address> {
+        if let Some(addr) = self.addresses.get_at(key, Instant::now()) {
+            return Ok(addr);
+        }
+        let addr = fetch.await?;
+        self.addresses.put_at(key, addr, Instant::now());
+        Ok(addr)
+    }
+
     pub fn pool_address(&self) -> Address {
         match &self.inner {
             HubClientInner::Eoa(c) => c.pool,
@@ -278,6 +434,51 @@ impl HubClient {
         }
     }
 
+    pub fn has_deposit_funder(&self) -> bool {
+        self.deposit_funder.is_some()
+    }
+
+    /// Solver's own balance of the pool's deposit token (USDT), excluding anything a deposit
+    /// funder could top it up with.
+    pub async fn deposit_token_balance(&self) -> Result<U256> {
+        let token = self.pool_usdt().await?;
+        let (provider, telemetry) = match &self.inner {
+            HubClientInner::Eoa(c) => (c.provider.clone(), c.telemetry.clone()),
+            HubClientInner::Safe4337(c) => (c.provider.clone(), c.telemetry.clone()),
+        };
+        let started = Instant::now();
+        let res = IERC20::new(token, provider)
+            .balanceOf(self.solver_address())
+            .call()
+            .await;
+        telemetry.hub_rpc_ms(
+            "erc20_balance_of",
+            res.is_ok(),
+            started.elapsed().as_millis() as u64,
+        );
+        res.context("ERC20.balanceOf")
+    }
+
+    /// How much of the deposit token the pool may pull from the solver for claim deposits.
+    pub async fn deposit_token_allowance(&self) -> Result<U256> {
+        let token = self.pool_usdt().await?;
+        let (provider, telemetry) = match &self.inner {
+            HubClientInner::Eoa(c) => (c.provider.clone(), c.telemetry.clone()),
+            HubClientInner::Safe4337(c) => (c.provider.clone(), c.telemetry.clone()),
+        };
+        let started = Instant::now();
+        let res = IERC20::new(token, provider)
+            .allowance(self.solver_address(), self.pool_address())
+            .call()
+            .await;
+        telemetry.hub_rpc_ms(
+            "erc20_allowance",
+            res.is_ok(),
+            started.elapsed().as_millis() as u64,
+        );
+        res.context("ERC20.allowance")
+    }
+
     pub async fn safe4337_set_nonce_floor(&self, floor: U256) -> Result<()> {
         match &self.inner {
             HubClientInner::Safe4337(c) => {
@@ -299,7 +500,32 @@ impl HubClient {
         }
     }
 
+    /// EntryPoint deposits of every known paymaster (empty in EOA mode).
+    pub async fn safe4337_paymaster_deposits(&self) -> Result<Vec<(Address, U256)>> {
+        let HubClientInner::Safe4337(c) = &self.inner else {
+            return Ok(Vec::new());
+        };
+        let sender = c.sender.lock().await;
+        let mut out = Vec::new();
+        for paymaster in sender.paymaster_addresses() {
+            let started = Instant::now();
+            let res = sender.entrypoint_deposit(paymaster).await;
+            c.telemetry.hub_rpc_ms(
+                "entrypoint_balance_of",
+                res.is_ok(),
+                started.elapsed().as_millis() as u64,
+            );
+            out.push((paymaster, res?));
+        }
+        Ok(out)
+    }
+
     pub async fn pool_usdt(&self) -> Result<Address> {
+        self.cached_address("pool_usdt", self.fetch_pool_usdt())
+            .await
+    }
+
+    async fn fetch_pool_usdt(&self) -> Result<Address> {
         let (pool_addr, provider, telemetry) = match &self.inner {
             HubClientInner::Eoa(c) => (c.pool, c.provider.clone(), c.telemetry.clone()),
             HubClientInner::Safe4337(c) => (c.pool, c.provider.clone(), c.telemetry.clone()),
@@ -313,6 +539,10 @@ impl HubClient {
     }
 
     pub async fn pool_v3(&self) -> Result<Address> {
+        self.cached_address("pool_v3", self.fetch_pool_v3()).await
+    }
+
+    async fn fetch_pool_v3(&self) -> Result<Address> {
         let (pool_addr, provider, telemetry) = match &self.inner {
             HubClientInner::Eoa(c) => (c.pool, c.provider.clone(), c.telemetry.clone()),
             HubClientInner::Safe4337(c) => (c.pool, c.provider.clone(), c.telemetry.clone()),
@@ -325,6 +555,23 @@ impl HubClient {
         res.context("UntronIntents.V3")
     }
 
+    pub async fn pool_claim_deposit(&self) -> Result<U256> {
+        let (pool_addr, provider, telemetry) = match &self.inner {
+            HubClientInner::Eoa(c) => (c.pool, c.provider.clone(), c.telemetry.clone()),
+            HubClientInner::Safe4337(c) => (c.pool, c.provider.clone(), c.telemetry.clone()),
+        };
+        let pool = IUntronIntents::new(pool_addr, provider);
+        let started = Instant::now();
+        let res = pool.INTENT_CLAIM_DEPOSIT().call().await;
+        let ok = res.is_ok();
+        telemetry.hub_rpc_ms(
+            "pool_claim_deposit",
+            ok,
+            started.elapsed().as_millis() as u64,
+        );
+        res.context("UntronIntents.INTENT_CLAIM_DEPOSIT")
+    }
+
     pub async fn hub_block_number(&self) -> Result<u64> {
         let (provider, telemetry) = match &self.inner {
             HubClientInner::Eoa(c) => (c.provider.clone(), c.telemetry.clone()),
@@ -338,6 +585,11 @@ impl HubClient {
     }
 
     pub async fn v3_tron_usdt(&self) -> Result<Address> {
+        self.cached_address("v3_tron_usdt", self.fetch_v3_tron_usdt())
+            .await
+    }
+
+    async fn fetch_v3_tron_usdt(&self) -> Result<Address> {
         let v3 = self.pool_v3().await?;
         let provider = match &self.inner {
             HubClientInner::Eoa(c) => c.provider.clone(),
@@ -362,6 +614,11 @@ impl HubClient {
 
     #[allow(dead_code)]
     pub async fn v3_controller_address(&self) -> Result<Address> {
+        self.cached_address("v3_controller_address", self.fetch_v3_controller_address())
+            .await
+    }
+
+    async fn fetch_v3_controller_address(&self) -> Result<Address> {
         let v3 = self.pool_v3().await?;
         let provider = match &self.inner {
             HubClientInner::Eoa(c) => c.provider.clone(),
@@ -435,6 +692,244 @@ impl HubClient {
         }
     }
 
+    /// Makes sure the solver holds at least `amount` of `token` by pulling the shortfall from the
+    /// deposit funder. No-op when no funder is configured.
+    ///
+    /// The pool takes the claim deposit from the tx sender, so the funder can't pay it directly:
+    /// the solver `transferFrom`s it over first, using an allowance the funder granted.
+    pub async fn ensure_deposit_funded(&self, token: Address, amount: U256) -> Result<()> {
+        let Some(funder) = &self.deposit_funder else {
+            return Ok(());
+        };
+        let solver = self.solver_address();
+        let (provider, telemetry) = match &self.inner {
+            HubClientInner::Eoa(c) => (c.provider.clone(), c.telemetry.clone()),
+            HubClientInner::Safe4337(c) => (c.provider.clone(), c.telemetry.clone()),
+        };
+        let erc20 = IERC20::new(token, provider);
+
+        let started = Instant::now();
+        let balance = erc20
+            .balanceOf(solver)
+            .call()
+            .await
+            .context("ERC20.balanceOf")?;
+        telemetry.hub_rpc_ms(
+            "erc20_balance_of",
+            true,
+            started.elapsed().as_millis() as u64,
+        );
+        if balance >= amount {
+            return Ok(());
+        }
+        let shortfall = amount - balance;
+
+        let started = Instant::now();
+        let allowance = erc20
+            .allowance(funder.address, solver)
+            .call()
+            .await
+            .context("ERC20.allowance")?;
+        telemetry.hub_rpc_ms(
+            "erc20_allowance",
+            true,
+            started.elapsed().as_millis() as u64,
+        );
+        if allowance < shortfall {
+            let Some(funder_provider) = &funder.provider else {
+                anyhow::bail!(
+                    "deposit funder {} allows solver {solver} only {allowance} (need {shortfall}); \
+                     approve the solver or set HUB_DEPOSIT_FUNDER_PRIVATE_KEY_HEX",
+                    funder.address
+                );
+            };
+            let started = Instant::now();
+            let pending = IERC20::new(token, funder_provider.clone())
+                .approve(solver, U256::MAX)
+                .send()
+                .await;
+            let ok = pending.is_ok();
+            telemetry.hub_rpc_ms(
+                "erc20_funder_approve",
+                ok,
+                started.elapsed().as_millis() as u64,
+            );
+            let receipt = pending
+                .context("funder ERC20.approve send")?
+                .get_receipt()
+                .await?;
+            tracing::info!(
+                funder = %funder.address,
+                tx = %receipt.transaction_hash,
+                "approved solver to pull claim deposits from funder"
+            );
+        }
+
+        let tx_hash = match &self.inner {
+            HubClientInner::Eoa(c) => {
+                let started = Instant::now();
+                let pending = erc20
+                    .transferFrom(funder.address, solver, shortfall)
+                    .send()
+                    .await;
+                let ok = pending.is_ok();
+                c.telemetry.hub_rpc_ms(
+                    "erc20_transfer_from",
+                    ok,
+                    started.elapsed().as_millis() as u64,
+                );
+                pending
+                    .context("ERC20.transferFrom send")?
+                    .get_receipt()
+                    .await?
+                    .transaction_hash
+            }
+            HubClientInner::Safe4337(c) => {
+                let call = IERC20::transferFromCall {
+                    from: funder.address,
+                    to: solver,
+                    amount: shortfall,
+                };
+                c.send_call_and_wait(token, call.abi_encode(), "erc20_transfer_from")
+                    .await?
+                    .transaction_hash
+            }
+        };
+        tracing::info!(
+            funder = %funder.address,
+            amount = %shortfall,
+            tx = %tx_hash,
+            "pulled claim deposit from funder"
+        );
+        Ok(())
+    }
+
+    /// Dry-runs `claimIntent` and then `proveIntentFill` with a placeholder proof from the solver
+    /// address in one `eth_simulateV1` block, so hub-side problems (pool paused or
+    /// misconfigured, deposit unpayable, Tron reader missing) show up before funds are committed.
+    /// A missing USDT allowance or funder top-up is simulated first.
+    ///
+    /// The placeholder proof can't verify, so the prove step passes when it reverts inside the
+    /// Tron reader (`ITronTxReaderErrors`). Returns the failing step and its revert, or `None`.
+    pub async fn simulate_claim_and_prove(
+        &self,
+        id: B256,
+        deposit: U256,
+    ) -> Result<Option<String>> {
+        let solver = self.solver_address();
+        let pool = self.pool_address();
+        let usdt = self.pool_usdt().await?;
+        let (provider, telemetry) = match &self.inner {
+            HubClientInner::Eoa(c) => (c.provider.clone(), c.telemetry.clone()),
+            HubClientInner::Safe4337(c) => (c.provider.clone(), c.telemetry.clone()),
+        };
+        let erc20 = IERC20::new(usdt, provider.clone());
+
+        let mut steps: Vec<(&'static str, Address, Vec<u8>)> = Vec::new();
+        let allowance = erc20
+            .allowance(solver, pool)
+            .call()
+            .await
+            .context("ERC20.allowance")?;
+        if allowance < deposit {
+            let call = IERC20::approveCall {
+                spender: pool,
+                amount: U256::MAX,
+            };
+            steps.push(("approve", usdt, call.abi_encode()));
+        }
+        if let Some(funder) = &self.deposit_funder {
+            let balance = erc20
+                .balanceOf(solver)
+                .call()
+                .await
+                .context("ERC20.balanceOf")?;
+            if balance < deposit {
+                let call = IERC20::transferFromCall {
+                    from: funder.address,
+                    to: solver,
+                    amount: deposit - balance,
+                };
+                steps.push(("funder_pull", usdt, call.abi_encode()));
+            }
+        }
+        steps.push((
+            "claimIntent",
+            pool,
+            IUntronIntents::claimIntentCall { id }.abi_encode(),
+        ));
+        let prove = IUntronIntents::proveIntentFillCall {
+            id,
+            blocks: std::array::from_fn(|_| alloy::primitives::Bytes::new()),
+            encodedTx: alloy::primitives::Bytes::new(),
+            proof: Vec::new(),
+            index: U256::ZERO,
+        };
+        steps.push(("proveIntentFill", pool, prove.abi_encode()));
+
+        let calls: Vec<serde_json::Value> = steps
+            .iter()
+            .map(|(_, to, data)| {
+                serde_json::json!({
+                    "from": solver,
+                    "to": to,
+                    "input": format!("0x{}", hex::encode(data)),
+                })
+            })
+            .collect();
+        let payload = serde_json::json!({
+            "blockStateCalls": [{ "calls": calls }],
+            "validation": false,
+        });
+        let started = Instant::now();
+        let res = provider
+            .raw_request::<_, serde_json::Value>("eth_simulateV1".into(), (payload, "latest"))
+            .await;
+        telemetry.hub_rpc_ms(
+            "eth_simulate_v1",
+            res.is_ok(),
+            started.elapsed().as_millis() as u64,
+        );
+        let res = res.context("eth_simulateV1")?;
+        let results = res
+            .get(0)
+            .and_then(|block| block.get("calls"))
+            .and_then(|calls| calls.as_array())
+            .context("eth_simulateV1: missing call results")?;
+        if results.len() != steps.len() {
+            anyhow::bail!(
+                "eth_simulateV1: {} call results for {} calls",
+                results.len(),
+                steps.len()
+            );
+        }
+
+        for ((step, _, _), result) in steps.iter().zip(results) {
+            let ok = result.get("status").and_then(|s| s.as_str()) == Some("0x1");
+            let data = result
+                .get("returnData")
+                .and_then(|d| d.as_str())
+                .and_then(|d| hex::decode(d.trim_start_matches("0x")).ok())
+                .unwrap_or_default();
+            let reader_revert = data.len() >= 4
+                && ITronTxReaderErrors::ITronTxReaderErrorsErrors::valid_selector(
+                    data[..4].try_into().expect("4 bytes"),
+                );
+            if ok || (*step == "proveIntentFill" && reader_revert) {
+                continue;
+            }
+            let error = result
+                .get("error")
+                .map(|e| e.to_string())
+                .unwrap_or_default();
+            return Ok(Some(format!(
+                "{step} reverted: 0x{} {error}",
+                hex::encode(&data)
+            )));
+        }
+        Ok(None)
+    }
+
     pub async fn claim_intent(&self, id: B256) -> Result<TransactionReceipt> {
         match &self.inner {
             HubClientInner::Eoa(c) => {
@@ -444,7 +939,8 @@ impl HubClient {
                 let ok = pending.is_ok();
                 c.telemetry
                     .hub_rpc_ms("claim_intent", ok, started.elapsed().as_millis() as u64);
-                Ok(pending?.get_receipt().await?)
+                let receipt = pending?.get_receipt().await?;
+                self.await_confirmations(&c.provider, receipt).await
             }
             HubClientInner::Safe4337(c) => {
                 let call = IUntronIntents::claimIntentCall { id };
@@ -454,6 +950,76 @@ impl HubClient {
         }
     }
 
+    /// Clears our claim on `id` once the pool's fill window has passed. The pool refunds the full
+    /// deposit if the intent is unfunded and pays half of it to the caller (us) otherwise.
+    pub async fn unclaim_intent(&self, id: B256) -> Result<TransactionReceipt> {
+        match &self.inner {
+            HubClientInner::Eoa(c) => {
+                let pool = IUntronIntents::new(c.pool, c.provider.clone());
+                let started = Instant::now();
+                let pending = pool.unclaimIntent(id).send().await;
+                let ok = pending.is_ok();
+                c.telemetry
+                    .hub_rpc_ms("unclaim_intent", ok, started.elapsed().as_millis() as u64);
+                Ok(pending?.get_receipt().await?)
+            }
+            HubClientInner::Safe4337(c) => {
+                let call = IUntronIntents::unclaimIntentCall { id };
+                c.send_call_and_wait(c.pool, call.abi_encode(), "unclaim_intent")
+                    .await
+            }
+        }
+    }
+
+    /// Pays out a solved and funded intent to its solver. Reverts with `NothingToSettle` if the
+    /// pool has already settled it.
+    pub async fn settle_intent(&self, id: B256) -> Result<TransactionReceipt> {
+        match &self.inner {
+            HubClientInner::Eoa(c) => {
+                let pool = IUntronIntents::new(c.pool, c.provider.clone());
+                let started = Instant::now();
+                let pending = pool.settleIntent(id).send().await;
+                let ok = pending.is_ok();
+                c.telemetry
+                    .hub_rpc_ms("settle_intent", ok, started.elapsed().as_millis() as u64);
+                let receipt = pending?.get_receipt().await?;
+                self.await_confirmations(&c.provider, receipt).await
+            }
+            HubClientInner::Safe4337(c) => {
+                let call = IUntronIntents::settleIntentCall { id };
+                c.send_call_and_wait(c.pool, call.abi_encode(), "settle_intent")
+                    .await
+            }
+        }
+    }
+
+    /// Deposit paid back to this solver by the pool's `IntentUnclaimed` event in `receipt`, with
+    /// whether the intent was funded at the time. `None` if the receipt has no such event.
+    pub fn unclaim_recovered_deposit(&self, receipt: &TransactionReceipt) -> Option<(U256, bool)> {
+        let pool = self.pool_address();
+        let me = self.solver_address();
+        receipt
+            .inner
+            .logs()
+            .iter()
+            .filter(|log| log.address() == pool)
+            .find_map(|log| {
+                log.log_decode::<IUntronIntents::IntentUnclaimed>()
+                    .ok()
+                    .map(|ev| ev.inner.data)
+            })
+            .map(|ev| {
+                let mut recovered = U256::ZERO;
+                if ev.caller == me {
+                    recovered += ev.depositToCaller;
+                }
+                if ev.prevSolver == me {
+                    recovered += ev.depositToPrevSolver;
+                }
+                (recovered, ev.funded)
+            })
+    }
+
     pub async fn safe4337_build_call_userop(
         &self,
         to: Address,
@@ -467,6 +1033,21 @@ impl HubClient {
         }
     }
 
+    /// One userop running every `(to, data)` call through the Safe `MultiSendCallOnly` at
+    /// `multisend`; all calls revert together.
+    pub async fn safe4337_build_multisend_userop(
+        &self,
+        multisend: Address,
+        calls: &[(Address, Vec<u8>)],
+    ) -> Result<PackedUserOperation> {
+        match &self.inner {
+            HubClientInner::Safe4337(c) => c.build_multisend_userop(multisend, calls).await,
+            HubClientInner::Eoa(_) => {
+                anyhow::bail!("safe4337_build_multisend_userop called in eoa mode")
+            }
+        }
+    }
+
     pub async fn safe4337_send_userop(&self, userop: PackedUserOperation) -> Result<String> {
         match &self.inner {
             HubClientInner::Safe4337(c) => Ok(c.send_userop(userop).await?.userop_hash),
@@ -506,7 +1087,8 @@ impl HubClient {
                     ok,
                     started.elapsed().as_millis() as u64,
                 );
-                Ok(pending?.get_receipt().await?)
+                let receipt = pending?.get_receipt().await?;
+                self.await_confirmations(&c.provider, receipt).await
             }
             HubClientInner::Safe4337(c) => {
                 let call = IUntronIntents::proveIntentFillCall {
@@ -522,6 +1104,78 @@ impl HubClient {
         }
     }
 
+    /// `eth_call`s `proveIntentFill` from the solver address, so a proof can be checked before it
+    /// shares a batched userop whose calls all revert together.
+    pub async fn check_prove_intent_fill(&self, id: B256, tron: &TronProof) -> Result<()> {
+        let (provider, telemetry) = match &self.inner {
+            HubClientInner::Eoa(c) => (c.provider.clone(), c.telemetry.clone()),
+            HubClientInner::Safe4337(c) => (c.provider.clone(), c.telemetry.clone()),
+        };
+        let pool = IUntronIntents::new(self.pool_address(), provider);
+        let started = Instant::now();
+        let res = pool
+            .proveIntentFill(
+                id,
+                tron.blocks.clone().map(alloy::primitives::Bytes::from),
+                alloy::primitives::Bytes::from(tron.encoded_tx.clone()),
+                tron.proof.clone(),
+                tron.index,
+            )
+            .from(self.solver_address())
+            .call()
+            .await;
+        telemetry.hub_rpc_ms(
+            "check_prove_intent_fill",
+            res.is_ok(),
+            started.elapsed().as_millis() as u64,
+        );
+        res.context("eth_call proveIntentFill")?;
+        Ok(())
+    }
+
+    /// Polls the head until `receipt`'s block has `min_confirmations` confirmations, then
+    /// re-reads the receipt, so a tx reorged out meanwhile fails here instead of advancing the
+    /// job.
+    async fn await_confirmations(
+        &self,
+        provider: &DynProvider,
+        mut receipt: TransactionReceipt,
+    ) -> Result<TransactionReceipt> {
+        let needed = self.min_confirmations;
+        if needed <= 1 {
+            return Ok(receipt);
+        }
+        let tx_hash = receipt.transaction_hash;
+        let deadline = Instant::now()
+            + CONFIRMATION_WAIT_PER_BLOCK.saturating_mul(u32::try_from(needed).unwrap_or(u32::MAX));
+        loop {
+            if Instant::now() >= deadline {
+                anyhow::bail!("timeout waiting for {needed} confirmations of {tx_hash:#x}");
+            }
+            let block = receipt
+                .block_number
+                .context("hub receipt without block number")?;
+            let head = provider
+                .get_block_number()
+                .await
+                .context("eth_blockNumber")?;
+            if head.saturating_sub(block) + 1 < needed {
+                tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+                continue;
+            }
+            let current = provider
+                .get_transaction_receipt(tx_hash)
+                .await
+                .context("eth_getTransactionReceipt")?
+                .with_context(|| format!("hub tx {tx_hash:#x} was reorged out"))?;
+            if current.block_hash == receipt.block_hash {
+                return Ok(current);
+            }
+            // Re-included in another block: count confirmations from there.
+            receipt = current;
+        }
+    }
+
     pub async fn intent_status(&self, id: B256) -> Result<HubIntentStatus> {
         let (pool, provider, telemetry) = match &self.inner {
             HubClientInner::Eoa(c) => (c.pool, c.provider.clone(), c.telemetry.clone()),
@@ -658,6 +1312,32 @@ impl HubClient {
 mod tests {
     use super::*;
 
+    #[test]
+    fn address_cache_expires_after_ttl() {
+        let cache = AddressCache::new(Duration::from_secs(60));
+        let t0 = Instant::now();
+        assert_eq!(cache.get_at("pool_usdt", t0), None);
+
+        cache.put_at("pool_usdt", Address::repeat_byte(0x11), t0);
+        assert_eq!(
+            cache.get_at("pool_usdt", t0 + Duration::from_secs(59)),
+            Some(Address::repeat_byte(0x11))
+        );
+        assert_eq!(
+            cache.get_at("pool_usdt", t0 + Duration::from_secs(60)),
+            None
+        );
+        assert_eq!(cache.get_at("pool_v3", t0), None);
+    }
+
+    #[test]
+    fn address_cache_zero_ttl_never_caches() {
+        let cache = AddressCache::new(Duration::ZERO);
+        let t0 = Instant::now();
+        cache.put_at("pool_usdt", Address::repeat_byte(0x11), t0);
+        assert_eq!(cache.get_at("pool_usdt", t0), None);
+    }
+
     #[test]
     fn userop_json_roundtrip() {
         let op = PackedUserOperation {
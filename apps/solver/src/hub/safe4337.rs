@@ -1,4 +1,5 @@
 use super::*;
+use crate::config::redact_url;
 use serde::Deserialize;
 use serde_json::json;
 
@@ -15,7 +16,38 @@ impl HubSafe4337Client {
         data: Vec<u8>,
     ) -> Result<PackedUserOperation> {
         let mut sender = self.sender.lock().await;
-        sender.build_call_userop(to, data).await
+        let res = sender.build_call_userop(to, data).await;
+        self.record_sender_attempts(&mut sender);
+        res
+    }
+
+    pub(super) async fn build_multisend_userop(
+        &self,
+        multisend: Address,
+        calls: &[(Address, Vec<u8>)],
+    ) -> Result<PackedUserOperation> {
+        let mut sender = self.sender.lock().await;
+        let res = sender.build_multisend_userop(multisend, calls).await;
+        self.record_sender_attempts(&mut sender);
+        res
+    }
+
+    fn record_sender_attempts(&self, sender: &mut aa::Safe4337UserOpSender) {
+        for attempt in sender.take_paymaster_attempts() {
+            self.telemetry
+                .hub_paymaster_request(&attempt.paymaster, attempt.ok);
+            if attempt.froze {
+                self.telemetry.hub_paymaster_frozen(&attempt.paymaster);
+            }
+        }
+        for attempt in sender.take_bundler_attempts() {
+            self.telemetry.hub_bundler_request(
+                &redact_url(&attempt.bundler),
+                attempt.method,
+                attempt.ok,
+                attempt.ms,
+            );
+        }
     }
 
     pub(super) async fn send_userop(
@@ -23,24 +55,69 @@ impl HubSafe4337Client {
         userop: PackedUserOperation,
     ) -> Result<aa::Safe4337UserOpSubmission> {
         let mut sender = self.sender.lock().await;
-        sender.send_userop(&userop).await
+        let res = sender.send_userop(&userop).await;
+        self.record_sender_attempts(&mut sender);
+        if let Ok(submission) = &res {
+            self.remember_accepting_bundler(submission);
+        }
+        res
+    }
+
+    fn remember_accepting_bundler(&self, submission: &aa::Safe4337UserOpSubmission) {
+        let mut accepted = self.accepted_by.lock().unwrap_or_else(|e| e.into_inner());
+        if accepted.len() >= ACCEPTED_BY_CAPACITY {
+            accepted.pop_front();
+        }
+        accepted.push_back((
+            submission.userop_hash.to_ascii_lowercase(),
+            submission.bundler_url.clone(),
+        ));
+    }
+
+    /// Bundlers to ask for `userop_hash`'s receipt: the one that accepted it first, if known, then
+    /// the rest healthiest first.
+    fn receipt_bundler_order(&self, userop_hash: &str) -> Vec<String> {
+        let hash = userop_hash.to_ascii_lowercase();
+        let accepted = self
+            .accepted_by
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .rev()
+            .find(|(h, _)| *h == hash)
+            .map(|(_, url)| url.clone());
+        accepting_bundler_first(&self.bundler_health.urls_healthy_first(), accepted)
     }
 
     pub(super) async fn get_userop_receipt(
         &self,
         userop_hash: &str,
     ) -> Result<Option<HubUserOpReceipt>> {
-        let mut i = 0usize;
-        // Try all bundlers once each; treat this as a "poll" (no internal waiting).
-        for _ in 0..self.bundler_urls.len().max(1) {
-            let url = self
-                .bundler_urls
-                .get(i % self.bundler_urls.len())
-                .context("no HUB_BUNDLER_URLS configured")?
-                .to_string();
-            i = i.wrapping_add(1);
-
-            if let Some(raw) = self.query_userop_receipt_raw(&url, userop_hash).await? {
+        // Try all bundlers once each; treat this as a "poll" (no internal waiting). Only the
+        // accepting bundler is guaranteed to know the op, so it goes first.
+        for url in self.receipt_bundler_order(userop_hash) {
+            let started = Instant::now();
+            let res = self.query_userop_receipt_raw(&url, userop_hash).await;
+            self.telemetry.hub_bundler_request(
+                &redact_url(&url),
+                "eth_getUserOperationReceipt",
+                res.is_ok(),
+                started.elapsed().as_millis() as u64,
+            );
+            // Transport errors only: a JSON-RPC error still means the bundler is up.
+            self.bundler_health.record(&url, res.is_ok(), None);
+            let raw = match res {
+                Ok(v) => v,
+                Err(err) => {
+                    tracing::warn!(
+                        bundler = %redact_url(&url),
+                        err = %format!("{err:#}"),
+                        "bundler receipt query failed; trying next bundler"
+                    );
+                    continue;
+                }
+            };
+            if let Some(raw) = raw {
                 let (tx_hash, block_number, success, actual_gas_cost_wei, actual_gas_used, reason) =
                     extract_userop_receipt_fields(&raw)?;
                 let (actual_gas_cost_wei, actual_gas_used, raw) =
@@ -158,8 +235,11 @@ impl HubSafe4337Client {
         let started = Instant::now();
         let submission = {
             let mut sender = self.sender.lock().await;
-            sender.send_call(to, data).await?
+            let res = sender.send_call(to, data).await;
+            self.record_sender_attempts(&mut sender);
+            res?
         };
+        self.remember_accepting_bundler(&submission);
         self.telemetry
             .hub_rpc_ms(op, true, started.elapsed().as_millis() as u64);
 
@@ -221,13 +301,29 @@ impl HubSafe4337Client {
         let val: JsonRpcResponse<serde_json::Value> =
             resp.json().await.context("decode jsonrpc")?;
         if let Some(err) = val.error {
-            tracing::warn!(bundler_url, err = %err, "bundler error");
+            tracing::warn!(
+                bundler = %redact_url(bundler_url),
+                err = %err,
+                "bundler error"
+            );
             return Ok(None);
         }
         Ok(val.result)
     }
 }
 
+/// Userop hashes whose accepting bundler is remembered (oldest dropped first).
+const ACCEPTED_BY_CAPACITY: usize = 1_024;
+
+fn accepting_bundler_first(urls: &[String], accepted: Option<String>) -> Vec<String> {
+    let mut order = urls.to_vec();
+    if let Some(url) = accepted {
+        order.retain(|u| *u != url);
+        order.insert(0, url);
+    }
+    order
+}
+
 type UserOpReceiptFields = (
     Option<B256>,
     Option<u64>,
@@ -21,11 +21,13 @@ where
 #[derive(Debug, Clone, Deserialize)]
 pub struct PoolOpenIntentRow {
     pub id: String,
+    pub creator: String,
     pub intent_type: i16,
     pub intent_specs: String,
     pub escrow_token: String,
     #[serde(deserialize_with = "de_string_or_number")]
     pub escrow_amount: String,
+    pub refund_beneficiary: String,
     #[serde(default)]
     pub solver: Option<String>,
     pub deadline: i64,
@@ -34,6 +36,21 @@ pub struct PoolOpenIntentRow {
     #[serde(default)]
     pub settled: bool,
     pub closed: bool,
+    /// Block timestamp of the intent's creation (unix secs); missing on older indexers.
+    #[serde(default)]
+    pub created_at: Option<i64>,
+}
+
+/// `createIntentFromReceiver` parameters; the id of such intents is derived from these instead
+/// of the creator and deadline.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReceiverIntentParamsRow {
+    pub forwarder: String,
+    pub to_tron_evm: String,
+    pub forward_salt: String,
+    pub receiver_token: String,
+    #[serde(deserialize_with = "de_string_or_number")]
+    pub amount_param: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -41,6 +58,53 @@ struct EventBlockRow {
     pub block_number: u64,
 }
 
+/// Pool stream position as reported by the indexer (`api.stream_heads`).
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct PoolStreamHeadsRow {
+    /// Latest block seen by the indexer's RPC.
+    pub head_block: u64,
+    /// `head_block - confirmations`; the indexer only ingests up to here.
+    pub confirmed_head_block: u64,
+    pub confirmations: u64,
+    /// Highest block whose logs are fully ingested.
+    pub indexed_through_block: u64,
+}
+
+/// How far the indexer has ingested the pool stream.
+#[derive(Debug, Clone, Copy)]
+pub struct IndexedPoolBlock {
+    /// Highest block whose pool events are fully ingested.
+    pub block: u64,
+    /// The indexer's own heads; `None` for indexers without `api.stream_heads`.
+    pub heads: Option<PoolStreamHeadsRow>,
+}
+
+impl IndexedPoolBlock {
+    /// Blocks the indexer deliberately stays behind head before ingesting.
+    pub fn confirmations(&self) -> u64 {
+        self.heads.map_or(0, |h| h.confirmations)
+    }
+}
+
+/// Order in which `pool_open_intents` rows are returned. Both end with `id.asc`, so the order
+/// is total and repeated fetches see the same sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenIntentsOrder {
+    /// Creation order (`valid_from_seq.asc`).
+    Sequence,
+    /// Nearest deadline first (`deadline.asc`); used to drain a backlog after downtime.
+    DeadlineAsc,
+}
+
+impl OpenIntentsOrder {
+    fn as_query(self) -> &'static str {
+        match self {
+            Self::Sequence => "valid_from_seq.asc,id.asc",
+            Self::DeadlineAsc => "deadline.asc,valid_from_seq.asc,id.asc",
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct IndexerClient {
     base_url: String,
@@ -75,11 +139,23 @@ impl IndexerClient {
         Ok(())
     }
 
-    pub async fn fetch_open_intents(&self, limit: u64) -> Result<Vec<PoolOpenIntentRow>> {
-        let url = format!(
-            "{}/pool_open_intents?order=valid_from_seq.asc&limit={}",
-            self.base_url, limit
+    /// Open intents in `order`, optionally only those with `deadline >= min_deadline`. Each id
+    /// appears at most once (the view can briefly repeat rows while projections update).
+    pub async fn fetch_open_intents(
+        &self,
+        limit: u64,
+        order: OpenIntentsOrder,
+        min_deadline: Option<i64>,
+    ) -> Result<Vec<PoolOpenIntentRow>> {
+        let mut url = format!(
+            "{}/pool_open_intents?order={}&limit={}",
+            self.base_url,
+            order.as_query(),
+            limit
         );
+        if let Some(min) = min_deadline {
+            url.push_str(&format!("&deadline=gte.{min}"));
+        }
         let started = Instant::now();
         let resp = self.http.get(&url).send().await;
         let ok = resp
@@ -96,9 +172,49 @@ impl IndexerClient {
             anyhow::bail!("indexer /pool_open_intents failed: {}", resp.status());
         }
         let rows: Vec<PoolOpenIntentRow> = resp.json().await.context("decode open intents")?;
+        let fetched = rows.len();
+        let rows = dedup_by_id(rows);
+        if rows.len() < fetched {
+            tracing::debug!(
+                duplicates = fetched - rows.len(),
+                "dropped duplicate open intents"
+            );
+        }
         Ok(rows)
     }
 
+    /// Total number of open intents, from PostgREST's exact count (`Content-Range: */N`).
+    pub async fn count_open_intents(&self) -> Result<u64> {
+        let url = format!("{}/pool_open_intents?limit=0", self.base_url);
+        let started = Instant::now();
+        let resp = self
+            .http
+            .head(&url)
+            .header("Prefer", "count=exact")
+            .send()
+            .await;
+        let ok = resp
+            .as_ref()
+            .map(|r| r.status().is_success())
+            .unwrap_or(false);
+        self.telemetry.indexer_http_ms(
+            "pool_open_intents_count",
+            ok,
+            started.elapsed().as_millis() as u64,
+        );
+        let resp = resp.context("HEAD /pool_open_intents (count)")?;
+        if !resp.status().is_success() {
+            anyhow::bail!("indexer /pool_open_intents count failed: {}", resp.status());
+        }
+        let range = resp
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .context("indexer count response missing Content-Range")?;
+        parse_content_range_total(range)
+            .with_context(|| format!("unexpected Content-Range: {range}"))
+    }
+
     pub async fn fetch_intent(&self, id: &str) -> Result<Option<PoolOpenIntentRow>> {
         let url = format!("{}/pool_intents?id=eq.{}&limit=1", self.base_url, id);
         let started = Instant::now();
@@ -120,9 +236,76 @@ impl IndexerClient {
         Ok(rows.into_iter().next())
     }
 
-    pub async fn latest_indexed_pool_block_number(&self) -> Result<Option<u64>> {
-        // Derive lag from the highest indexed pool event block. This is robust across schema changes
-        // because it's sourced from the canonical `api.event_appended` view.
+    pub async fn fetch_receiver_intent_params(
+        &self,
+        id: &str,
+    ) -> Result<Option<ReceiverIntentParamsRow>> {
+        let url = format!(
+            "{}/pool_receiver_intents?id=eq.{}&limit=1&select=forwarder,to_tron_evm,forward_salt,receiver_token,amount_param",
+            self.base_url, id
+        );
+        let started = Instant::now();
+        let resp = self.http.get(&url).send().await;
+        let ok = resp
+            .as_ref()
+            .map(|r| r.status().is_success())
+            .unwrap_or(false);
+        self.telemetry.indexer_http_ms(
+            "pool_receiver_intents_by_id",
+            ok,
+            started.elapsed().as_millis() as u64,
+        );
+        let resp = resp.context("GET /pool_receiver_intents (by id)")?;
+        if !resp.status().is_success() {
+            anyhow::bail!("indexer /pool_receiver_intents failed: {}", resp.status());
+        }
+        let rows: Vec<ReceiverIntentParamsRow> =
+            resp.json().await.context("decode pool_receiver_intents")?;
+        Ok(rows.into_iter().next())
+    }
+
+    pub async fn pool_stream_heads(&self) -> Result<Option<PoolStreamHeadsRow>> {
+        let url = format!(
+            "{}/stream_heads?stream=eq.pool&limit=1&select=head_block,confirmed_head_block,confirmations,indexed_through_block",
+            self.base_url
+        );
+        let started = Instant::now();
+        let resp = self.http.get(&url).send().await;
+        let ok = resp
+            .as_ref()
+            .map(|r| r.status().is_success())
+            .unwrap_or(false);
+        self.telemetry.indexer_http_ms(
+            "stream_heads_pool",
+            ok,
+            started.elapsed().as_millis() as u64,
+        );
+        let resp = resp.context("GET /stream_heads (pool)")?;
+        if !resp.status().is_success() {
+            anyhow::bail!("indexer /stream_heads failed: {}", resp.status());
+        }
+        let rows: Vec<PoolStreamHeadsRow> = resp.json().await.context("decode stream_heads")?;
+        Ok(rows.into_iter().next())
+    }
+
+    pub async fn latest_indexed_pool_block(&self) -> Result<Option<IndexedPoolBlock>> {
+        // Prefer the indexer's scan progress: it accounts for confirmation depth and keeps moving
+        // while the pool is quiet.
+        match self.pool_stream_heads().await {
+            Ok(Some(heads)) => {
+                return Ok(Some(IndexedPoolBlock {
+                    block: heads.indexed_through_block,
+                    heads: Some(heads),
+                }));
+            }
+            Ok(None) => {}
+            Err(err) => {
+                tracing::debug!(err = %err, "stream_heads unavailable; falling back to event_appended");
+            }
+        }
+
+        // Older indexers: derive lag from the highest indexed pool event block, sourced from the
+        // canonical `api.event_appended` view.
         let url = format!(
             "{}/event_appended?stream=eq.pool&order=block_number.desc&limit=1&select=block_number",
             self.base_url
@@ -143,6 +326,69 @@ impl IndexerClient {
             anyhow::bail!("indexer /event_appended failed: {}", resp.status());
         }
         let rows: Vec<EventBlockRow> = resp.json().await.context("decode event_appended")?;
-        Ok(rows.first().map(|r| r.block_number))
+        Ok(rows.first().map(|r| IndexedPoolBlock {
+            block: r.block_number,
+            heads: None,
+        }))
+    }
+}
+
+/// Keeps the first row per intent id (hex compared case-insensitively), preserving order.
+fn dedup_by_id(rows: Vec<PoolOpenIntentRow>) -> Vec<PoolOpenIntentRow> {
+    let mut seen = std::collections::HashSet::with_capacity(rows.len());
+    rows.into_iter()
+        .filter(|row| seen.insert(row.id.to_ascii_lowercase()))
+        .collect()
+}
+
+/// Total from a PostgREST `Content-Range` header (`0-24/3573` or `*/3573`).
+fn parse_content_range_total(v: &str) -> Option<u64> {
+    v.rsplit_once('/')?.1.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PoolOpenIntentRow, dedup_by_id, parse_content_range_total};
+
+    fn row(id: &str, deadline: i64) -> PoolOpenIntentRow {
+        PoolOpenIntentRow {
+            id: id.to_string(),
+            creator: String::new(),
+            intent_type: 2,
+            intent_specs: "0x".to_string(),
+            escrow_token: String::new(),
+            escrow_amount: "0".to_string(),
+            refund_beneficiary: String::new(),
+            solver: None,
+            deadline,
+            solved: false,
+            funded: true,
+            settled: false,
+            closed: false,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn dedup_by_id_keeps_first_occurrence_in_order() {
+        let rows = vec![
+            row("0xAA", 1),
+            row("0xbb", 2),
+            row("0xaa", 3),
+            row("0xcc", 4),
+        ];
+        let out = dedup_by_id(rows);
+        let ids = out.iter().map(|r| r.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, ["0xAA", "0xbb", "0xcc"]);
+        assert_eq!(out[0].deadline, 1);
+    }
+
+    #[test]
+    fn parse_content_range_total_reads_exact_count() {
+        assert_eq!(parse_content_range_total("*/3573"), Some(3573));
+        assert_eq!(parse_content_range_total("0-24/3573"), Some(3573));
+        assert_eq!(parse_content_range_total("*/0"), Some(0));
+        // No exact count requested.
+        assert_eq!(parse_content_range_total("0-24/*"), None);
     }
 }
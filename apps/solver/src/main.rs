@@ -1,4 +1,5 @@
 mod abi;
+mod cli;
 mod config;
 mod db;
 mod hub;
@@ -6,9 +7,11 @@ mod indexer;
 mod metrics;
 mod policy;
 mod pricing;
+mod prove_relayer;
 mod runner;
 mod tron_backend;
 mod types;
+mod webhook;
 
 use anyhow::{Context, Result};
 use tokio_util::sync::CancellationToken;
@@ -17,6 +20,26 @@ use tokio_util::sync::CancellationToken;
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
 
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("requeue") {
+        return cli::requeue(&args[2..]).await;
+    }
+    if args.get(1).map(String::as_str) == Some("inspect") {
+        return cli::inspect(&args[2..]).await;
+    }
+    if args.get(1).map(String::as_str) == Some("breakers") {
+        return cli::breakers(&args[2..]).await;
+    }
+    if args.get(1).map(String::as_str) == Some("breaker") {
+        return cli::breaker(&args[2..]).await;
+    }
+    if args.get(1).map(String::as_str) == Some("emulate") {
+        return cli::emulate(&args[2..]).await;
+    }
+    if args.get(1).map(String::as_str) == Some("check") {
+        return cli::check(&args[2..]).await;
+    }
+
     let cfg = config::load_config()?;
     let otel = untron_observability::init(untron_observability::Config {
         service_name: "solver",
@@ -28,9 +51,13 @@ async fn main() -> Result<()> {
     tracing::info!(
         indexer = %cfg.indexer.base_url,
         hub_rpc = %cfg.hub.rpc_url,
+        extra_pools = cfg.extra_pools.len(),
         tron_mode = ?cfg.tron.mode,
         "config loaded"
     );
+    if cfg.log_effective_config {
+        tracing::info!(config = %cfg.redacted_debug(), "effective config");
+    }
 
     let shutdown = CancellationToken::new();
 
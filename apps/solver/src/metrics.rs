@@ -1,6 +1,6 @@
 use opentelemetry::{
     KeyValue, global,
-    metrics::{Counter, Histogram},
+    metrics::{Counter, Gauge, Histogram},
 };
 use std::sync::Arc;
 
@@ -17,6 +17,7 @@ struct Inner {
     hub_userop_errors_total: Counter<u64>,
     tron_txs_total: Counter<u64>,
     tron_tx_errors_total: Counter<u64>,
+    tron_out_of_energy_total: Counter<u64>,
     claim_rate_limited_total: Counter<u64>,
     global_paused_total: Counter<u64>,
     emulation_mismatch_total: Counter<u64>,
@@ -25,17 +26,42 @@ struct Inner {
     rental_quotes_total: Counter<u64>,
     rental_orders_total: Counter<u64>,
     rental_provider_freezes_total: Counter<u64>,
+    hub_paymaster_requests_total: Counter<u64>,
+    hub_paymaster_freezes_total: Counter<u64>,
+    hub_bundler_requests_total: Counter<u64>,
     candidate_skips_total: Counter<u64>,
+    jobs_archived_total: Counter<u64>,
+    lease_steal_detected_total: Counter<u64>,
+    consolidation_sweep_transfers_total: Counter<u64>,
+    auto_stake_top_ups_total: Counter<u64>,
+    tron_grpc_timeouts_total: Counter<u64>,
+    fatal_webhook_failures_total: Counter<u64>,
+    ticks_skipped_lag_total: Counter<u64>,
+    ticks_skipped_tron_unhealthy_total: Counter<u64>,
+    claim_race_lost_total: Counter<u64>,
+    indexer_health_fail_total: Counter<u64>,
+    proof_rebuilds_total: Counter<u64>,
+    prove_batch_jobs_total: Counter<u64>,
 
     job_ms: Histogram<u64>,
     hub_submit_ms: Histogram<u64>,
     tron_broadcast_ms: Histogram<u64>,
+    tron_inclusion_ms: Histogram<u64>,
     indexer_http_ms: Histogram<u64>,
     hub_rpc_ms: Histogram<u64>,
+    hub_bundler_rpc_ms: Histogram<u64>,
     tron_proof_ms: Histogram<u64>,
+    tron_proof_block_fetch_ms: Histogram<u64>,
     tron_grpc_ms: Histogram<u64>,
+    tron_grpc_call_ms: Histogram<u64>,
     rental_quote_ms: Histogram<u64>,
     rental_order_ms: Histogram<u64>,
+
+    rental_provider_avg_order_ms: Gauge<f64>,
+    jobs_by_state: Gauge<u64>,
+    paymaster_deposit_wei: Gauge<f64>,
+    deposit_token_balance: Gauge<f64>,
+    indexer_lag_blocks: Gauge<u64>,
 }
 
 impl SolverTelemetry {
@@ -70,6 +96,10 @@ impl SolverTelemetry {
             .u64_counter("solver.tron_tx_errors_total")
             .with_description("Total Tron transaction errors")
             .build();
+        let tron_out_of_energy_total = meter
+            .u64_counter("solver.tron_out_of_energy_total")
+            .with_description("Total Tron broadcasts rejected for insufficient energy")
+            .build();
         let claim_rate_limited_total = meter
             .u64_counter("solver.claim_rate_limited_total")
             .with_description("Total claim rate-limited events")
@@ -105,10 +135,79 @@ impl SolverTelemetry {
             .u64_counter("solver.rental_provider_freezes_total")
             .with_description("Total rental provider freeze events")
             .build();
+
+        let hub_paymaster_requests_total = meter
+            .u64_counter("solver.hub_paymaster_requests_total")
+            .with_description("Total hub paymaster sponsorship attempts")
+            .build();
+
+        let hub_paymaster_freezes_total = meter
+            .u64_counter("solver.hub_paymaster_freezes_total")
+            .with_description("Total hub paymaster freeze events")
+            .build();
+        let hub_bundler_requests_total = meter
+            .u64_counter("solver.hub_bundler_requests_total")
+            .with_description("Total Safe4337 bundler JSON-RPC calls")
+            .build();
         let candidate_skips_total = meter
             .u64_counter("solver.candidate_skips_total")
             .with_description("Total candidate intents skipped before job creation")
             .build();
+        let jobs_archived_total = meter
+            .u64_counter("solver.jobs_archived_total")
+            .with_description("Total terminal jobs moved to archive tables")
+            .build();
+        let lease_steal_detected_total = meter
+            .u64_counter("solver.lease_steal_detected_total")
+            .with_description("Total jobs re-leased by another instance before our lease expired")
+            .build();
+        let consolidation_sweep_transfers_total = meter
+            .u64_counter("solver.consolidation_sweep_transfers_total")
+            .with_description("Total proactive consolidation transfers broadcast")
+            .build();
+        let auto_stake_top_ups_total = meter
+            .u64_counter("solver.auto_stake_top_ups_total")
+            .with_description("Total auto-stake FreezeBalanceV2 top-ups attempted")
+            .build();
+        let tron_grpc_timeouts_total = meter
+            .u64_counter("solver.tron_grpc_timeouts_total")
+            .with_description("Total Tron gRPC attempts that hit TRON_GRPC_TIMEOUT_MS")
+            .build();
+        let fatal_webhook_failures_total = meter
+            .u64_counter("solver.fatal_webhook_failures_total")
+            .with_description("Total fatal-job webhook deliveries that failed or timed out")
+            .build();
+        let ticks_skipped_lag_total = meter
+            .u64_counter("solver.ticks_skipped_lag_total")
+            .with_description("Total per-pool ticks skipped because the indexer lagged too far")
+            .build();
+
+        let ticks_skipped_tron_unhealthy_total = meter
+            .u64_counter("solver.ticks_skipped_tron_unhealthy_total")
+            .with_description(
+                "Total ticks that held back Tron-stage jobs while the Tron node was down",
+            )
+            .build();
+
+        let claim_race_lost_total = meter
+            .u64_counter("solver.claim_race_lost_total")
+            .with_description("Total claims that lost the race to another solver (AlreadyClaimed)")
+            .build();
+
+        let indexer_health_fail_total = meter
+            .u64_counter("solver.indexer_health_fail_total")
+            .with_description("Total indexer health checks that failed at least once")
+            .build();
+
+        let proof_rebuilds_total = meter
+            .u64_counter("solver.proof_rebuilds_total")
+            .with_description("Total built Tron proofs discarded and rebuilt from tron_sent")
+            .build();
+
+        let prove_batch_jobs_total = meter
+            .u64_counter("solver.prove_batch_jobs_total")
+            .with_description("Total jobs carried by batched prove userops, by outcome")
+            .build();
 
         let job_ms = meter
             .u64_histogram("solver.job_ms")
@@ -128,6 +227,12 @@ impl SolverTelemetry {
             .with_unit("ms")
             .build();
 
+        let tron_inclusion_ms = meter
+            .u64_histogram("solver.tron_inclusion_ms")
+            .with_description("Time from Tron broadcast until the tx is seen in a block")
+            .with_unit("ms")
+            .build();
+
         let indexer_http_ms = meter
             .u64_histogram("solver.indexer_http_ms")
             .with_description("Indexer (PostgREST) HTTP request runtime")
@@ -139,18 +244,33 @@ impl SolverTelemetry {
             .with_description("Hub chain JSON-RPC call runtime")
             .with_unit("ms")
             .build();
+        let hub_bundler_rpc_ms = meter
+            .u64_histogram("solver.hub_bundler_rpc_ms")
+            .with_description("Safe4337 bundler JSON-RPC call runtime, per bundler")
+            .with_unit("ms")
+            .build();
 
         let tron_proof_ms = meter
             .u64_histogram("solver.tron_proof_ms")
             .with_description("Tron proof build runtime")
             .with_unit("ms")
             .build();
+        let tron_proof_block_fetch_ms = meter
+            .u64_histogram("solver.tron_proof_block_fetch_ms")
+            .with_description("Time a successful Tron proof build spent fetching blocks")
+            .with_unit("ms")
+            .build();
 
         let tron_grpc_ms = meter
             .u64_histogram("solver.tron_grpc_ms")
             .with_description("Tron gRPC call runtime")
             .with_unit("ms")
             .build();
+        let tron_grpc_call_ms = meter
+            .u64_histogram("solver.tron_grpc_call_ms")
+            .with_description("Tron gRPC runtime per RPC attempt, by method")
+            .with_unit("ms")
+            .build();
 
         let rental_quote_ms = meter
             .u64_histogram("solver.rental_quote_ms")
@@ -164,6 +284,29 @@ impl SolverTelemetry {
             .with_unit("ms")
             .build();
 
+        let rental_provider_avg_order_ms = meter
+            .f64_gauge("solver.rental_provider_avg_order_ms")
+            .with_description("Rolling average rental order HTTP runtime per provider")
+            .with_unit("ms")
+            .build();
+        let jobs_by_state = meter
+            .u64_gauge("solver.jobs_by_state")
+            .with_description("Jobs currently in each state (hot table only)")
+            .build();
+        let paymaster_deposit_wei = meter
+            .f64_gauge("solver.paymaster_deposit_wei")
+            .with_description("Paymaster EntryPoint deposit backing userop sponsorship")
+            .with_unit("wei")
+            .build();
+        let deposit_token_balance = meter
+            .f64_gauge("solver.deposit_token_balance")
+            .with_description("Solver balance of the pool's claim deposit token, in base units")
+            .build();
+        let indexer_lag_blocks = meter
+            .u64_gauge("solver.indexer_lag_blocks")
+            .with_description("Hub head minus the pool indexer's latest indexed block")
+            .build();
+
         Self {
             inner: Arc::new(Inner {
                 jobs_total,
@@ -173,6 +316,7 @@ impl SolverTelemetry {
                 hub_userop_errors_total,
                 tron_txs_total,
                 tron_tx_errors_total,
+                tron_out_of_energy_total,
                 claim_rate_limited_total,
                 global_paused_total,
                 emulation_mismatch_total,
@@ -181,16 +325,40 @@ impl SolverTelemetry {
                 rental_quotes_total,
                 rental_orders_total,
                 rental_provider_freezes_total,
+                hub_paymaster_requests_total,
+                hub_paymaster_freezes_total,
+                hub_bundler_requests_total,
                 candidate_skips_total,
+                jobs_archived_total,
+                lease_steal_detected_total,
+                consolidation_sweep_transfers_total,
+                auto_stake_top_ups_total,
+                tron_grpc_timeouts_total,
+                fatal_webhook_failures_total,
+                ticks_skipped_lag_total,
+                ticks_skipped_tron_unhealthy_total,
+                claim_race_lost_total,
+                indexer_health_fail_total,
+                proof_rebuilds_total,
+                prove_batch_jobs_total,
                 job_ms,
                 hub_submit_ms,
                 tron_broadcast_ms,
+                tron_inclusion_ms,
                 indexer_http_ms,
                 hub_rpc_ms,
+                hub_bundler_rpc_ms,
                 tron_proof_ms,
+                tron_proof_block_fetch_ms,
                 tron_grpc_ms,
+                tron_grpc_call_ms,
                 rental_quote_ms,
                 rental_order_ms,
+                rental_provider_avg_order_ms,
+                jobs_by_state,
+                paymaster_deposit_wei,
+                deposit_token_balance,
+                indexer_lag_blocks,
             }),
         }
     }
@@ -222,8 +390,10 @@ impl SolverTelemetry {
         self.inner.hub_userops_total.add(1, &[]);
     }
 
-    pub fn hub_userop_err(&self) {
-        self.inner.hub_userop_errors_total.add(1, &[]);
+    /// `class` is the bundler error class (`aa::BundlerErrorClass::as_str`).
+    pub fn hub_userop_err(&self, class: &'static str) {
+        let attrs = [KeyValue::new("class", class)];
+        self.inner.hub_userop_errors_total.add(1, &attrs);
     }
 
     pub fn tron_tx_ok(&self) {
@@ -234,6 +404,10 @@ impl SolverTelemetry {
         self.inner.tron_tx_errors_total.add(1, &[]);
     }
 
+    pub fn tron_out_of_energy(&self) {
+        self.inner.tron_out_of_energy_total.add(1, &[]);
+    }
+
     pub fn claim_rate_limited(&self, key: &'static str) {
         let attrs = [KeyValue::new("key", key)];
         self.inner.claim_rate_limited_total.add(1, &attrs);
@@ -278,11 +452,94 @@ impl SolverTelemetry {
         self.inner.rental_order_ms.record(ms, &attrs);
     }
 
+    pub fn rental_provider_avg_order_ms(&self, provider: &str, avg_ms: f64) {
+        let attrs = [KeyValue::new("provider", provider.to_string())];
+        self.inner
+            .rental_provider_avg_order_ms
+            .record(avg_ms, &attrs);
+    }
+
+    pub fn paymaster_deposit_wei(&self, paymaster: &str, wei: f64) {
+        let attrs = [KeyValue::new("paymaster", paymaster.to_string())];
+        self.inner.paymaster_deposit_wei.record(wei, &attrs);
+    }
+
+    pub fn deposit_token_balance(&self, pool_id: &str, balance: f64) {
+        let attrs = [KeyValue::new("pool_id", pool_id.to_string())];
+        self.inner.deposit_token_balance.record(balance, &attrs);
+    }
+
+    pub fn indexer_lag_blocks(&self, pool_id: &str, lag: u64) {
+        let attrs = [KeyValue::new("pool_id", pool_id.to_string())];
+        self.inner.indexer_lag_blocks.record(lag, &attrs);
+    }
+
+    pub fn tick_skipped_lag(&self, pool_id: &str) {
+        let attrs = [KeyValue::new("pool_id", pool_id.to_string())];
+        self.inner.ticks_skipped_lag_total.add(1, &attrs);
+    }
+
+    pub fn tick_skipped_tron_unhealthy(&self) {
+        self.inner.ticks_skipped_tron_unhealthy_total.add(1, &[]);
+    }
+
+    pub fn claim_race_lost(&self, intent_type: i16) {
+        let attrs = [KeyValue::new("intent_type", intent_type as i64)];
+        self.inner.claim_race_lost_total.add(1, &attrs);
+    }
+
+    /// `recovered`: a retry succeeded (transient blip) rather than every attempt failing.
+    pub fn indexer_health_fail(&self, pool_id: &str, recovered: bool) {
+        let attrs = [
+            KeyValue::new("pool_id", pool_id.to_string()),
+            KeyValue::new("outcome", if recovered { "recovered" } else { "failed" }),
+        ];
+        self.inner.indexer_health_fail_total.add(1, &attrs);
+    }
+
+    /// `reason`: `corrupted` (stored proof unreadable) or `stale` (prove rejected its blocks).
+    pub fn proof_rebuild(&self, intent_type: i16, reason: &'static str) {
+        let attrs = [
+            KeyValue::new("intent_type", intent_type as i64),
+            KeyValue::new("reason", reason),
+        ];
+        self.inner.proof_rebuilds_total.add(1, &attrs);
+    }
+
+    /// `outcome`: `submitted`, `send_failed`, or `reverted` (the job then proves on its own).
+    pub fn prove_batch_jobs(&self, outcome: &'static str, jobs: usize) {
+        let attrs = [KeyValue::new("outcome", outcome)];
+        self.inner.prove_batch_jobs_total.add(jobs as u64, &attrs);
+    }
+
     pub fn rental_provider_frozen(&self, provider: &str) {
         let attrs = [KeyValue::new("provider", provider.to_string())];
         self.inner.rental_provider_freezes_total.add(1, &attrs);
     }
 
+    pub fn hub_paymaster_request(&self, paymaster: &str, ok: bool) {
+        let attrs = [
+            KeyValue::new("paymaster", paymaster.to_string()),
+            KeyValue::new("status", if ok { "ok" } else { "err" }),
+        ];
+        self.inner.hub_paymaster_requests_total.add(1, &attrs);
+    }
+
+    pub fn hub_paymaster_frozen(&self, paymaster: &str) {
+        let attrs = [KeyValue::new("paymaster", paymaster.to_string())];
+        self.inner.hub_paymaster_freezes_total.add(1, &attrs);
+    }
+
+    pub fn hub_bundler_request(&self, bundler: &str, method: &'static str, ok: bool, ms: u64) {
+        let attrs = [
+            KeyValue::new("bundler", bundler.to_string()),
+            KeyValue::new("method", method),
+            KeyValue::new("status", if ok { "ok" } else { "err" }),
+        ];
+        self.inner.hub_bundler_requests_total.add(1, &attrs);
+        self.inner.hub_bundler_rpc_ms.record(ms, &attrs);
+    }
+
     pub fn candidate_skip(&self, intent_type: i16, reason: &'static str) {
         let attrs = [
             KeyValue::new("intent_type", intent_type as i64),
@@ -291,6 +548,34 @@ impl SolverTelemetry {
         self.inner.candidate_skips_total.add(1, &attrs);
     }
 
+    pub fn jobs_archived(&self, n: u64) {
+        self.inner.jobs_archived_total.add(n, &[]);
+    }
+
+    pub fn lease_steal_detected(&self, intent_type: i16) {
+        self.inner
+            .lease_steal_detected_total
+            .add(1, &[KeyValue::new("intent_type", intent_type as i64)]);
+    }
+
+    pub fn consolidation_sweep_transfer(&self, asset: &'static str, ok: bool) {
+        let attrs = [
+            KeyValue::new("asset", asset),
+            KeyValue::new("status", if ok { "ok" } else { "err" }),
+        ];
+        self.inner
+            .consolidation_sweep_transfers_total
+            .add(1, &attrs);
+    }
+
+    pub fn auto_stake_top_up(&self, resource: &'static str, ok: bool) {
+        let attrs = [
+            KeyValue::new("resource", resource),
+            KeyValue::new("status", if ok { "ok" } else { "err" }),
+        ];
+        self.inner.auto_stake_top_ups_total.add(1, &attrs);
+    }
+
     pub fn hub_submit_ms(&self, name: &'static str, ok: bool, ms: u64) {
         let attrs = [
             KeyValue::new("name", name),
@@ -304,6 +589,15 @@ impl SolverTelemetry {
         self.inner.tron_broadcast_ms.record(ms, &attrs);
     }
 
+    /// `final_tx` separates the fill itself from consolidation pre-txs.
+    pub fn tron_inclusion_ms(&self, intent_type: i16, final_tx: bool, ms: u64) {
+        let attrs = [
+            KeyValue::new("intent_type", intent_type as i64),
+            KeyValue::new("step", if final_tx { "final" } else { "pre" }),
+        ];
+        self.inner.tron_inclusion_ms.record(ms, &attrs);
+    }
+
     pub fn indexer_http_ms(&self, op: &'static str, ok: bool, ms: u64) {
         let attrs = [
             KeyValue::new("op", op),
@@ -325,6 +619,10 @@ impl SolverTelemetry {
         self.inner.tron_proof_ms.record(ms, &attrs);
     }
 
+    pub fn tron_proof_block_fetch_ms(&self, ms: u64) {
+        self.inner.tron_proof_block_fetch_ms.record(ms, &[]);
+    }
+
     pub fn tron_grpc_ms(&self, op: &'static str, ok: bool, ms: u64) {
         let attrs = [
             KeyValue::new("op", op),
@@ -332,4 +630,37 @@ impl SolverTelemetry {
         ];
         self.inner.tron_grpc_ms.record(ms, &attrs);
     }
+
+    pub fn tron_grpc_call(&self, method: &'static str, status: &'static str, ms: u64) {
+        let attrs = [
+            KeyValue::new("method", method),
+            KeyValue::new("status", status),
+        ];
+        self.inner.tron_grpc_call_ms.record(ms, &attrs);
+        if status == "timeout" {
+            self.inner
+                .tron_grpc_timeouts_total
+                .add(1, &[KeyValue::new("method", method)]);
+        }
+    }
+
+    /// `reason` is `timeout`, `http_status` (non-2xx response) or `error`.
+    pub fn fatal_webhook_failed(&self, reason: &'static str) {
+        let attrs = [KeyValue::new("reason", reason)];
+        self.inner.fatal_webhook_failures_total.add(1, &attrs);
+    }
+
+    /// Records one sample per known state; states missing from `counts` report 0.
+    pub fn jobs_by_state(&self, counts: &[(String, i64)]) {
+        for state in crate::types::JobState::ALL {
+            let name = state.as_db_str();
+            let n = counts
+                .iter()
+                .find(|(s, _)| s == name)
+                .map_or(0, |(_, n)| u64::try_from(*n).unwrap_or(0));
+            self.inner
+                .jobs_by_state
+                .record(n, &[KeyValue::new("state", name)]);
+        }
+    }
 }
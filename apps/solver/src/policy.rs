@@ -4,12 +4,14 @@ use crate::{
     pricing::Pricing,
     tron_backend::{
         DelegateResourceIntent, TRXTransferIntent, TriggerSmartContractIntent, USDTTransferIntent,
+        trigger_spend_amount,
     },
-    types::IntentType,
+    types::{DeadlineKind, IntentType},
 };
 use alloy::primitives::{Address, U256};
 use alloy::sol_types::SolValue;
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 
 const TRON_BLOCK_TIME_SECS: u64 = 3;
 
@@ -24,11 +26,34 @@ pub struct PolicyEvaluation {
     pub allowed: bool,
     pub reason: Option<String>,
     pub breaker: Option<BreakerQuery>,
+    /// Set when the profitability check priced the intent.
+    pub profit: Option<ProfitEstimate>,
+}
+
+/// Inputs and result of the profitability check, all in USD.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfitEstimate {
+    pub revenue_usd: f64,
+    /// Principal delivered on Tron (0 for resold delegations).
+    pub cost_usd: f64,
+    pub hub_cost_usd: f64,
+    pub tron_fee_usd: f64,
+    pub profit_usd: f64,
+}
+
+impl ProfitEstimate {
+    /// Whether the intent would clear `min_profit_usd` if `fee_usd` of its Tron fees (e.g. the
+    /// consolidation pre-txs) weren't paid.
+    pub fn clears_floor_without(&self, fee_usd: f64, min_profit_usd: f64) -> bool {
+        self.profit_usd + fee_usd >= min_profit_usd.max(0.0)
+    }
 }
 
 #[derive(Clone)]
 pub struct PolicyEngine {
     cfg: PolicyConfig,
+    /// `TronConfig::trigger_spend_selectors`: calls that also pull USDT from the solver.
+    trigger_spend_selectors: HashMap<[u8; 4], usize>,
 }
 
 struct StaticCheckOutcome {
@@ -38,13 +63,23 @@ struct StaticCheckOutcome {
 
 impl PolicyEngine {
     pub fn new(cfg: PolicyConfig) -> Self {
-        Self { cfg }
+        Self {
+            cfg,
+            trigger_spend_selectors: HashMap::new(),
+        }
+    }
+
+    /// Prices and caps the USDT that TriggerSmartContract calls with these selectors pull from the
+    /// solver, on top of their call value.
+    pub fn with_trigger_spend_selectors(mut self, selectors: HashMap<[u8; 4], usize>) -> Self {
+        self.trigger_spend_selectors = selectors;
+        self
     }
 
     pub async fn evaluate_open_intent(
         &self,
         row: &PoolOpenIntentRow,
-        now_unix_secs: i64,
+        deadline_now: i64,
         pricing: &mut Pricing,
         hub_cost_usd: f64,
         tron_fee_usd: f64,
@@ -54,6 +89,7 @@ impl PolicyEngine {
             allowed: false,
             reason: None,
             breaker: None,
+            profit: None,
         };
 
         if row.closed || row.solved {
@@ -69,8 +105,13 @@ impl PolicyEngine {
             return Ok(eval);
         }
 
-        let slack = i64::try_from(self.cfg.min_deadline_slack_secs).unwrap_or(i64::MAX);
-        if row.deadline.saturating_sub(now_unix_secs) < slack {
+        // `deadline_now` is on the deadline's own clock: unix seconds or the hub block number.
+        let slack = match self.cfg.deadline_kind {
+            DeadlineKind::Timestamp => self.cfg.min_deadline_slack_secs,
+            DeadlineKind::Block => self.cfg.min_deadline_slack_blocks,
+        };
+        let slack = i64::try_from(slack).unwrap_or(i64::MAX);
+        if row.deadline.saturating_sub(deadline_now) < slack {
             eval.reason = Some("deadline_slack".to_string());
             return Ok(eval);
         }
@@ -89,8 +130,13 @@ impl PolicyEngine {
         }
         eval.breaker = static_eval.breaker;
 
+        if let Some(reason) = self.value_cap_check(row, ty) {
+            eval.reason = Some(reason);
+            return Ok(eval);
+        }
+
         // Best-effort profitability gating.
-        if let Some(reason) = self
+        let (reason, profit) = self
             .profitability_check(
                 row,
                 ty,
@@ -100,8 +146,9 @@ impl PolicyEngine {
                 delegate_resource_resell,
             )
             .await
-            .context("profitability_check")?
-        {
+            .context("profitability_check")?;
+        eval.profit = profit;
+        if let Some(reason) = reason {
             eval.reason = Some(reason);
             return Ok(eval);
         }
@@ -150,6 +197,16 @@ impl PolicyEngine {
                 }
 
                 let data = intent.data.as_ref();
+                if let Some(max) = self.cfg.max_usdt_transfer_amount
+                    && let Some(pull) = trigger_spend_amount(&self.trigger_spend_selectors, data)
+                    && pull > U256::from(max)
+                {
+                    return Ok(StaticCheckOutcome {
+                        breaker: None,
+                        reject_reason: Some("trigger_spend_cap".to_string()),
+                    });
+                }
+
                 if let Some(max_len) = self.cfg.max_trigger_calldata_len
                     && data.len() > max_len as usize
                 {
@@ -183,6 +240,18 @@ impl PolicyEngine {
                     });
                 }
 
+                let budget = selector.and_then(|sel| self.cfg.trigger_selector_budgets.get(&sel));
+                let selector_allowed = match budget {
+                    Some(b) => b.enabled,
+                    None => !self.cfg.trigger_selector_allowlist_mode,
+                };
+                if !selector_allowed {
+                    return Ok(StaticCheckOutcome {
+                        breaker: None,
+                        reject_reason: Some("selector_not_allowed".to_string()),
+                    });
+                }
+
                 Ok(StaticCheckOutcome {
                     breaker: Some(BreakerQuery {
                         contract: intent.to,
@@ -318,6 +387,95 @@ impl PolicyEngine {
         true
     }
 
+    /// Checks intent targets against the Tron address lists. Denylists win over allowlists.
+    pub fn check_tron_targets(
+        &self,
+        recipient: Option<Address>,
+        contract: Option<Address>,
+    ) -> Option<&'static str> {
+        if let Some(to) = recipient {
+            if self.cfg.tron_address_denylist.contains(&to) {
+                return Some("address_denylisted");
+            }
+            if !self.cfg.tron_address_allowlist.is_empty()
+                && !self.cfg.tron_address_allowlist.contains(&to)
+            {
+                return Some("address_not_allowlisted");
+            }
+        }
+        if let Some(c) = contract {
+            if self.cfg.tron_contract_denylist.contains(&c) {
+                return Some("address_denylisted");
+            }
+            if !self.cfg.tron_contract_allowlist.is_empty()
+                && !self.cfg.tron_contract_allowlist.contains(&c)
+            {
+                return Some("address_not_allowlisted");
+            }
+        }
+        None
+    }
+
+    /// Age floor (`min_intent_age_secs`): returns the intent's age when it is still too fresh to
+    /// attempt at unix time `now`. Intents without a known creation time (older indexers) aren't
+    /// held back.
+    pub fn intent_too_fresh(&self, row: &PoolOpenIntentRow, now: i64) -> Option<i64> {
+        let min_age = i64::try_from(self.cfg.min_intent_age_secs?).unwrap_or(i64::MAX);
+        let age = now.saturating_sub(row.created_at?);
+        (age < min_age).then_some(age)
+    }
+
+    /// Checks the intent's hub escrow token against `escrow_token_allowlist`. With the list
+    /// configured, an unparseable token is rejected.
+    pub fn is_escrow_token_allowed(&self, escrow_token: &str) -> bool {
+        let allow = &self.cfg.escrow_token_allowlist;
+        if allow.is_empty() {
+            return true;
+        }
+        escrow_token
+            .parse::<Address>()
+            .is_ok_and(|token| allow.contains(&token))
+    }
+
+    /// Checks the intent's hub refund beneficiary against the configured lists. With any list
+    /// configured, an unparseable beneficiary is rejected.
+    pub fn is_refund_beneficiary_allowed(&self, beneficiary: &str) -> bool {
+        let allow = &self.cfg.refund_beneficiary_allowlist;
+        let deny = &self.cfg.refund_beneficiary_denylist;
+        if allow.is_empty() && deny.is_empty() {
+            return true;
+        }
+        let Ok(addr) = beneficiary.parse::<Address>() else {
+            return false;
+        };
+        !deny.contains(&addr) && (allow.is_empty() || allow.contains(&addr))
+    }
+
+    /// USD value of the intent's escrow, or `None` if the escrow token is not priced.
+    fn escrow_value_usd(&self, row: &PoolOpenIntentRow) -> Option<f64> {
+        let escrow_token: Address = row.escrow_token.parse().unwrap_or_default();
+        if !self.cfg.allowed_escrow_tokens.contains(&escrow_token) {
+            return None;
+        }
+        let escrow_amount = crate::types::parse_u256_dec(&row.escrow_amount).unwrap_or(U256::ZERO);
+        // For MVP we treat allowed escrow tokens as 6-decimal $1 stables.
+        Some((escrow_amount.to_string().parse::<f64>().unwrap_or(0.0)) / 1e6)
+    }
+
+    /// Rejects intents whose escrow value exceeds the per-type (or global) USD cap.
+    ///
+    /// Unpriced escrow can't be valued; use `require_priced_escrow` to refuse it outright.
+    fn value_cap_check(&self, row: &PoolOpenIntentRow, ty: IntentType) -> Option<String> {
+        let cap = self
+            .cfg
+            .max_intent_value_usd_by_type
+            .get(&ty)
+            .copied()
+            .or(self.cfg.max_intent_value_usd)?;
+        let value_usd = self.escrow_value_usd(row)?;
+        (value_usd > cap).then(|| "value_over_cap".to_string())
+    }
+
     async fn profitability_check(
         &self,
         row: &PoolOpenIntentRow,
@@ -326,39 +484,55 @@ impl PolicyEngine {
         hub_cost_usd: f64,
         tron_fee_usd: f64,
         delegate_resource_resell: bool,
-    ) -> Result<Option<String>> {
+    ) -> Result<(Option<String>, Option<ProfitEstimate>)> {
         if self.cfg.min_profit_usd <= 0.0 && !self.cfg.require_priced_escrow {
-            return Ok(None);
+            return Ok((None, None));
         }
 
-        let escrow_token: Address = row.escrow_token.parse().unwrap_or_default();
-        let priced = self.cfg.allowed_escrow_tokens.contains(&escrow_token);
-        if !priced && self.cfg.require_priced_escrow {
-            return Ok(Some("escrow_token_unpriced".to_string()));
+        let escrow_value_usd = self.escrow_value_usd(row);
+        if escrow_value_usd.is_none() && self.cfg.require_priced_escrow {
+            return Ok((Some("escrow_token_unpriced".to_string()), None));
         }
-        if !priced || self.cfg.min_profit_usd <= 0.0 {
-            return Ok(None);
+        let Some(revenue_usd) = escrow_value_usd else {
+            return Ok((None, None));
+        };
+        if self.cfg.min_profit_usd <= 0.0 {
+            return Ok((None, None));
         }
 
-        let escrow_amount = crate::types::parse_u256_dec(&row.escrow_amount).unwrap_or(U256::ZERO);
-        // For MVP we treat allowed escrow tokens as 6-decimal $1 stables.
-        let revenue_usd = (escrow_amount.to_string().parse::<f64>().unwrap_or(0.0)) / 1e6;
-
         let trx_usd = pricing.trx_usd().await.unwrap_or(0.0);
         let cost_usd = if ty == IntentType::DelegateResource && delegate_resource_resell {
             0.0
         } else {
-            match estimate_cost_usd(&self.cfg, ty, &row.intent_specs, trx_usd) {
+            match estimate_cost_usd(
+                &self.cfg,
+                &self.trigger_spend_selectors,
+                ty,
+                &row.intent_specs,
+                trx_usd,
+            ) {
                 Ok(v) => v,
-                Err(_) => return Ok(Some("cost_estimate_failed".to_string())),
+                Err(_) => return Ok((Some("cost_estimate_failed".to_string()), None)),
             }
         };
-        let profit = revenue_usd - cost_usd - hub_cost_usd - tron_fee_usd;
-        if profit < self.cfg.min_profit_usd {
-            return Ok(Some("unprofitable".to_string()));
-        }
+        let profit_usd = revenue_usd - cost_usd - hub_cost_usd - tron_fee_usd;
+        let estimate = ProfitEstimate {
+            revenue_usd,
+            cost_usd,
+            hub_cost_usd,
+            tron_fee_usd,
+            profit_usd,
+        };
+        // Distinguish losing money from covering costs but missing the absolute floor.
+        let reason = if profit_usd < 0.0 {
+            Some("unprofitable")
+        } else if profit_usd < self.cfg.min_profit_usd {
+            Some("below_min_profit")
+        } else {
+            None
+        };
 
-        Ok(None)
+        Ok((reason.map(str::to_string), Some(estimate)))
     }
 }
 
@@ -380,8 +554,11 @@ fn lock_period_secs_from_blocks(lock_period_blocks: u64) -> u64 {
     lock_period_blocks.saturating_mul(TRON_BLOCK_TIME_SECS)
 }
 
+/// Principal the solver gives up on Tron to fill the intent, in USD. `trigger_spend_selectors`
+/// adds the USDT a TriggerSmartContract call pulls to its call value.
 pub fn estimate_cost_usd(
     cfg: &PolicyConfig,
+    trigger_spend_selectors: &HashMap<[u8; 4], usize>,
     ty: IntentType,
     intent_specs_hex: &str,
     trx_usd: f64,
@@ -392,7 +569,10 @@ pub fn estimate_cost_usd(
             let intent = TriggerSmartContractIntent::abi_decode(&specs)
                 .context("decode TriggerSmartContractIntent")?;
             let sun: f64 = intent.callValueSun.to_string().parse().unwrap_or(0.0);
-            (sun / 1e6) * trx_usd
+            let pulled: f64 = trigger_spend_amount(trigger_spend_selectors, &intent.data)
+                .map(|amt| amt.to_string().parse().unwrap_or(0.0))
+                .unwrap_or(0.0);
+            (sun / 1e6) * trx_usd + pulled / 1e6
         }
         IntentType::TrxTransfer => {
             let intent =
@@ -428,15 +608,31 @@ mod tests {
     use crate::pricing::{Pricing, PricingConfig};
     use alloy::primitives::{Bytes, U256};
 
+    #[test]
+    fn clears_floor_without_adds_back_the_fee() {
+        let p = ProfitEstimate {
+            revenue_usd: 10.0,
+            cost_usd: 9.0,
+            hub_cost_usd: 0.5,
+            tron_fee_usd: 1.5,
+            profit_usd: -1.0,
+        };
+        assert!(p.clears_floor_without(1.2, 0.1));
+        assert!(!p.clears_floor_without(1.0, 0.1));
+    }
+
     fn cfg() -> PolicyConfig {
         PolicyConfig {
             enabled_intent_types: vec![IntentType::TriggerSmartContract],
             min_deadline_slack_secs: 0,
+            deadline_kind: DeadlineKind::Timestamp,
+            min_deadline_slack_blocks: 0,
             min_profit_usd: 0.0,
             hub_cost_usd: 0.0,
             hub_cost_history_lookback: 50,
             hub_cost_headroom_ppm: 0,
             tron_fee_usd: 0.0,
+            tron_fee_usd_by_type: Default::default(),
             tron_fee_history_lookback: 50,
             tron_fee_headroom_ppm: 0,
             capital_lock_ppm_per_day: 0,
@@ -446,28 +642,64 @@ mod tests {
             trigger_contract_denylist: vec![],
             trigger_selector_denylist: vec![],
             trigger_allow_fallback_calls: false,
+            trigger_selector_budgets: Default::default(),
+            trigger_selector_allowlist_mode: false,
+            tron_address_allowlist: vec![],
+            tron_address_denylist: vec![],
+            tron_contract_allowlist: vec![],
+            tron_contract_denylist: vec![],
+            refund_beneficiary_allowlist: vec![],
+            refund_beneficiary_denylist: vec![],
+            escrow_token_allowlist: vec![],
             max_trx_transfer_sun: None,
             max_usdt_transfer_amount: None,
             max_delegate_balance_sun: None,
             max_delegate_lock_period_secs: None,
             max_trigger_call_value_sun: None,
             max_trigger_calldata_len: None,
+            max_inflight_claim_deposit: None,
+            max_claims_per_minute: None,
+            min_intent_age_secs: None,
+            max_intent_value_usd: None,
+            max_intent_value_usd_by_type: Default::default(),
         }
     }
 
+    #[test]
+    fn intent_age_floor() {
+        let mut c = cfg();
+        let mut row = row_for(IntentType::TriggerSmartContract, vec![], 0);
+        row.created_at = Some(1_000);
+        assert_eq!(
+            PolicyEngine::new(c.clone()).intent_too_fresh(&row, 1_005),
+            None
+        );
+
+        c.min_intent_age_secs = Some(10);
+        let engine = PolicyEngine::new(c);
+        assert_eq!(engine.intent_too_fresh(&row, 1_005), Some(5));
+        assert_eq!(engine.intent_too_fresh(&row, 1_010), None);
+
+        row.created_at = None;
+        assert_eq!(engine.intent_too_fresh(&row, 1_005), None);
+    }
+
     fn row_for(intent_type: IntentType, intent_specs: Vec<u8>, deadline: i64) -> PoolOpenIntentRow {
         PoolOpenIntentRow {
             id: format!("0x{}", "11".repeat(32)),
+            creator: Address::ZERO.to_string(),
             intent_type: intent_type as i16,
             intent_specs: format!("0x{}", hex::encode(intent_specs)),
             escrow_token: Address::ZERO.to_string(),
             escrow_amount: "0".to_string(),
+            refund_beneficiary: Address::ZERO.to_string(),
             solver: None,
             deadline,
             solved: false,
             funded: true,
             settled: false,
             closed: false,
+            created_at: None,
         }
     }
 
@@ -492,6 +724,44 @@ mod tests {
         assert!(!p.is_trigger_contract_allowed(Address::ZERO));
     }
 
+    #[test]
+    fn escrow_token_allowlist_gates_intents() {
+        let listed = "0x00000000000000000000000000000000000000aa";
+        let other = "0x00000000000000000000000000000000000000bb";
+
+        let p = PolicyEngine::new(cfg());
+        assert!(p.is_escrow_token_allowed(other));
+
+        let mut c = cfg();
+        c.escrow_token_allowlist = vec![listed.parse().unwrap()];
+        let p = PolicyEngine::new(c);
+        assert!(p.is_escrow_token_allowed(listed));
+        assert!(!p.is_escrow_token_allowed(other));
+        assert!(!p.is_escrow_token_allowed("garbage"));
+    }
+
+    #[test]
+    fn refund_beneficiary_lists_gate_intents() {
+        let listed = "0x00000000000000000000000000000000000000aa";
+        let other = "0x00000000000000000000000000000000000000bb";
+
+        let p = PolicyEngine::new(cfg());
+        assert!(p.is_refund_beneficiary_allowed("garbage"));
+
+        let mut c = cfg();
+        c.refund_beneficiary_allowlist = vec![listed.parse().unwrap()];
+        let p = PolicyEngine::new(c);
+        assert!(p.is_refund_beneficiary_allowed(listed));
+        assert!(!p.is_refund_beneficiary_allowed(other));
+        assert!(!p.is_refund_beneficiary_allowed("garbage"));
+
+        let mut c = cfg();
+        c.refund_beneficiary_denylist = vec![listed.parse().unwrap()];
+        let p = PolicyEngine::new(c);
+        assert!(!p.is_refund_beneficiary_allowed(listed));
+        assert!(p.is_refund_beneficiary_allowed(other));
+    }
+
     #[test]
     fn trigger_denylist_blocks_even_when_no_allowlist() {
         let mut c = cfg();
@@ -505,6 +775,41 @@ mod tests {
         assert!(p.is_trigger_contract_allowed(Address::ZERO));
     }
 
+    #[test]
+    fn tron_address_lists_deny_and_allow() {
+        let a: Address = "0x00000000000000000000000000000000000000aa"
+            .parse()
+            .unwrap();
+        let b: Address = "0x00000000000000000000000000000000000000bb"
+            .parse()
+            .unwrap();
+
+        let mut c = cfg();
+        c.tron_address_denylist = vec![a];
+        c.tron_contract_denylist = vec![b];
+        let p = PolicyEngine::new(c);
+        assert_eq!(
+            p.check_tron_targets(Some(a), None),
+            Some("address_denylisted")
+        );
+        assert_eq!(
+            p.check_tron_targets(None, Some(b)),
+            Some("address_denylisted")
+        );
+        assert_eq!(p.check_tron_targets(Some(b), Some(a)), None);
+
+        let mut c = cfg();
+        c.tron_address_allowlist = vec![a];
+        let p = PolicyEngine::new(c);
+        assert_eq!(p.check_tron_targets(Some(a), None), None);
+        assert_eq!(
+            p.check_tron_targets(Some(b), None),
+            Some("address_not_allowlisted")
+        );
+        // Recipient allowlist doesn't constrain trigger contracts.
+        assert_eq!(p.check_tron_targets(None, Some(b)), None);
+    }
+
     #[test]
     fn delegate_lock_cost_scales_with_lock_period_and_principal() {
         let mut c = cfg();
@@ -517,11 +822,57 @@ mod tests {
             lockPeriod: U256::from(28_800u64),    // 1 day in Tron blocks
         };
         let specs_hex = format!("0x{}", hex::encode(intent.abi_encode()));
-        let cost = estimate_cost_usd(&c, IntentType::DelegateResource, &specs_hex, 0.5).unwrap();
+        let cost = estimate_cost_usd(
+            &c,
+            &HashMap::new(),
+            IntentType::DelegateResource,
+            &specs_hex,
+            0.5,
+        )
+        .unwrap();
         // principal = $0.50; 10%/day => $0.05
         assert!((cost - 0.05).abs() < 1e-9, "cost={cost}");
     }
 
+    #[test]
+    fn trigger_spend_is_priced_and_capped() {
+        let sel = [0x12, 0x34, 0x56, 0x78];
+        let mut data = sel.to_vec();
+        data.extend_from_slice(&U256::from(25_000_000u64).to_be_bytes::<32>()); // 25 USDT
+        let intent = TriggerSmartContractIntent {
+            to: Address::ZERO,
+            callValueSun: U256::from(2_000_000u64), // 2 TRX
+            data: Bytes::from(data),
+        };
+        let specs_hex = format!("0x{}", hex::encode(intent.abi_encode()));
+        let selectors = HashMap::from([(sel, 0usize)]);
+
+        let cost = |selectors: &HashMap<[u8; 4], usize>| {
+            estimate_cost_usd(
+                &cfg(),
+                selectors,
+                IntentType::TriggerSmartContract,
+                &specs_hex,
+                0.5,
+            )
+            .unwrap()
+        };
+        assert!((cost(&HashMap::new()) - 1.0).abs() < 1e-9);
+        assert!((cost(&selectors) - 26.0).abs() < 1e-9);
+
+        let mut c = cfg();
+        c.max_usdt_transfer_amount = Some(20_000_000);
+        let p = PolicyEngine::new(c.clone()).with_trigger_spend_selectors(selectors);
+        let out = p
+            .static_intent_checks(IntentType::TriggerSmartContract, &specs_hex)
+            .unwrap();
+        assert_eq!(out.reject_reason.as_deref(), Some("trigger_spend_cap"));
+        let out = PolicyEngine::new(c)
+            .static_intent_checks(IntentType::TriggerSmartContract, &specs_hex)
+            .unwrap();
+        assert_eq!(out.reject_reason, None);
+    }
+
     #[tokio::test]
     async fn delegate_lock_cap_treats_lock_period_as_blocks() {
         let mut c = cfg();
@@ -553,6 +904,109 @@ mod tests {
         assert_eq!(eval.reason.as_deref(), Some("delegate_lock_cap"));
     }
 
+    #[tokio::test]
+    async fn intent_value_cap_uses_per_type_override() {
+        let usdt: Address = "0x00000000000000000000000000000000000000bb"
+            .parse()
+            .unwrap();
+        let mut c = cfg();
+        c.enabled_intent_types = vec![IntentType::TrxTransfer, IntentType::DelegateResource];
+        c.allowed_escrow_tokens = vec![usdt];
+        c.max_intent_value_usd = Some(1_000.0);
+        c.max_intent_value_usd_by_type
+            .insert(IntentType::TrxTransfer, 100.0);
+        let engine = PolicyEngine::new(c);
+        let mut pricing = Pricing::new(PricingConfig {
+            trx_usd_override: Some(0.3),
+            trx_usd_ttl: std::time::Duration::from_secs(60),
+            trx_usd_url: "http://example.invalid".to_string(),
+            eth_usd_override: Some(2_000.0),
+            eth_usd_ttl: std::time::Duration::from_secs(60),
+            eth_usd_url: "http://example.invalid".to_string(),
+        });
+
+        let trx = TRXTransferIntent {
+            to: Address::ZERO,
+            amountSun: U256::from(1u64),
+        };
+        let delegate = DelegateResourceIntent {
+            receiver: Address::ZERO,
+            resource: 1,
+            balanceSun: U256::from(1_000_000u64),
+            lockPeriod: U256::ZERO,
+        };
+
+        // $500 of escrow: over the $100 TRX transfer override, under the $1000 global cap.
+        let mut row = row_for(IntentType::TrxTransfer, trx.abi_encode(), 2_000_000);
+        row.escrow_token = usdt.to_string();
+        row.escrow_amount = "500000000".to_string();
+        let eval = engine
+            .evaluate_open_intent(&row, 1_000_000, &mut pricing, 0.0, 0.0, false)
+            .await
+            .unwrap();
+        assert!(!eval.allowed);
+        assert_eq!(eval.reason.as_deref(), Some("value_over_cap"));
+
+        let mut row = row_for(
+            IntentType::DelegateResource,
+            delegate.abi_encode(),
+            2_000_000,
+        );
+        row.escrow_token = usdt.to_string();
+        row.escrow_amount = "500000000".to_string();
+        let eval = engine
+            .evaluate_open_intent(&row, 1_000_000, &mut pricing, 0.0, 0.0, false)
+            .await
+            .unwrap();
+        assert_ne!(eval.reason.as_deref(), Some("value_over_cap"));
+
+        row.escrow_amount = "1000000001".to_string();
+        let eval = engine
+            .evaluate_open_intent(&row, 1_000_000, &mut pricing, 0.0, 0.0, false)
+            .await
+            .unwrap();
+        assert_eq!(eval.reason.as_deref(), Some("value_over_cap"));
+    }
+
+    #[tokio::test]
+    async fn block_deadlines_use_block_slack() {
+        let mut c = cfg();
+        c.enabled_intent_types = vec![IntentType::TrxTransfer];
+        c.min_deadline_slack_secs = 600;
+        c.deadline_kind = DeadlineKind::Block;
+        c.min_deadline_slack_blocks = 10;
+
+        let intent = TRXTransferIntent {
+            to: Address::ZERO,
+            amountSun: U256::from(1u64),
+        };
+        let mut pricing = Pricing::new(PricingConfig {
+            trx_usd_override: Some(0.3),
+            trx_usd_ttl: std::time::Duration::from_secs(60),
+            trx_usd_url: "http://example.invalid".to_string(),
+            eth_usd_override: Some(2_000.0),
+            eth_usd_ttl: std::time::Duration::from_secs(60),
+            eth_usd_url: "http://example.invalid".to_string(),
+        });
+        let engine = PolicyEngine::new(c);
+
+        // Head at block 1_000: 20 blocks left clears a 10-block slack (a 600s slack would not).
+        let row = row_for(IntentType::TrxTransfer, intent.abi_encode(), 1_020);
+        let eval = engine
+            .evaluate_open_intent(&row, 1_000, &mut pricing, 0.0, 0.0, false)
+            .await
+            .unwrap();
+        assert_ne!(eval.reason.as_deref(), Some("deadline_slack"));
+
+        let row = row_for(IntentType::TrxTransfer, intent.abi_encode(), 1_005);
+        let eval = engine
+            .evaluate_open_intent(&row, 1_000, &mut pricing, 0.0, 0.0, false)
+            .await
+            .unwrap();
+        assert!(!eval.allowed);
+        assert_eq!(eval.reason.as_deref(), Some("deadline_slack"));
+    }
+
     #[tokio::test]
     async fn trigger_selector_denylist_rejects() {
         let to: Address = "0x00000000000000000000000000000000000000aa"
@@ -592,6 +1046,72 @@ mod tests {
         assert_eq!(eval.reason.as_deref(), Some("trigger_selector_denied"));
     }
 
+    #[tokio::test]
+    async fn trigger_selector_budgets_allowlist_mode() {
+        let to: Address = "0x00000000000000000000000000000000000000aa"
+            .parse()
+            .unwrap();
+        let call = |sel: [u8; 4]| TriggerSmartContractIntent {
+            to,
+            callValueSun: U256::ZERO,
+            data: Bytes::from(sel.to_vec()),
+        };
+        let listed = [0xa9, 0x05, 0x9c, 0xbb];
+        let disabled = [0x23, 0xb8, 0x72, 0xdd];
+        let unlisted = [0x12, 0x34, 0x56, 0x78];
+
+        let mut c = cfg();
+        c.trigger_contract_allowlist = vec![to];
+        c.trigger_selector_allowlist_mode = true;
+        c.trigger_selector_budgets = [
+            (
+                listed,
+                crate::config::TriggerSelectorBudget {
+                    max_energy: Some(65_000),
+                    enabled: true,
+                },
+            ),
+            (
+                disabled,
+                crate::config::TriggerSelectorBudget {
+                    max_energy: None,
+                    enabled: false,
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let p = PolicyEngine::new(c);
+
+        let now = 1_000_000i64;
+        let mut pricing = Pricing::new(PricingConfig {
+            trx_usd_override: Some(0.3),
+            trx_usd_ttl: std::time::Duration::from_secs(60),
+            trx_usd_url: "http://example.invalid".to_string(),
+            eth_usd_override: Some(2_000.0),
+            eth_usd_ttl: std::time::Duration::from_secs(60),
+            eth_usd_url: "http://example.invalid".to_string(),
+        });
+
+        for (sel, want) in [
+            (listed, None),
+            (disabled, Some("selector_not_allowed")),
+            (unlisted, Some("selector_not_allowed")),
+        ] {
+            let row = row_for(
+                IntentType::TriggerSmartContract,
+                call(sel).abi_encode(),
+                now + 10_000,
+            );
+            let eval = p
+                .evaluate_open_intent(&row, now, &mut pricing, 0.0, 0.0, false)
+                .await
+                .unwrap();
+            assert_eq!(eval.reason.as_deref(), want, "selector={sel:02x?}");
+            assert_eq!(eval.allowed, want.is_none());
+        }
+    }
+
     #[tokio::test]
     async fn trigger_fallback_calls_rejected_by_default() {
         let to: Address = "0x00000000000000000000000000000000000000aa"
@@ -664,6 +1184,22 @@ mod tests {
             .await
             .unwrap();
         assert!(!eval.allowed);
+        assert_eq!(eval.reason.as_deref(), Some("below_min_profit"));
+        let profit = eval.profit.unwrap();
+        assert!((profit.profit_usd - 0.05).abs() < 1e-9, "{profit:?}");
+
+        // $1.00 revenue - $1.20 hub = -$0.20: doesn't even cover costs.
+        let eval = p
+            .evaluate_open_intent(&row, now, &mut pricing, 1.2, 0.0, false)
+            .await
+            .unwrap();
         assert_eq!(eval.reason.as_deref(), Some("unprofitable"));
+
+        let eval = p
+            .evaluate_open_intent(&row, now, &mut pricing, 0.5, 0.0, false)
+            .await
+            .unwrap();
+        assert!(eval.allowed);
+        assert!((eval.profit.unwrap().profit_usd - 0.5).abs() < 1e-9);
     }
 }
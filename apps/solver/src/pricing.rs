@@ -49,6 +49,17 @@ impl Pricing {
         }
     }
 
+    /// Swaps in reloaded settings. Cached prices survive unless their source URL changed.
+    pub fn set_config(&mut self, cfg: PricingConfig) {
+        if cfg.trx_usd_url != self.cfg.trx_usd_url {
+            self.cached_trx = None;
+        }
+        if cfg.eth_usd_url != self.cfg.eth_usd_url {
+            self.cached_eth = None;
+        }
+        self.cfg = cfg;
+    }
+
     pub async fn trx_usd(&mut self) -> Result<f64> {
         if let Some(v) = self.cfg.trx_usd_override {
             return Ok(v);
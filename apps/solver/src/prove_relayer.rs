@@ -0,0 +1,81 @@
+use crate::{config::ProveRelayerConfig, hub::TronProof};
+use alloy::primitives::{Address, B256};
+use anyhow::{Context, Result};
+use reqwest::Client;
+
+/// Hands built Tron proofs to an external relayer that submits `proveIntentFill` on the pool
+/// (`ProveMode::DelegatedRelayer`).
+///
+/// The relayer is expected to be idempotent per `(pool, intent_id)`: a proof is POSTed again if
+/// the intent isn't solved `resubmit_after` later.
+#[derive(Clone)]
+pub struct ProveRelayerClient {
+    http: Client,
+    url: String,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ProvePayload {
+    pool: String,
+    chain_id: Option<u64>,
+    intent_id: String,
+    proof: ProofPayload,
+}
+
+/// `proveIntentFill`'s Tron proof arguments, hex-encoded (`index` as a decimal string).
+#[derive(Debug, serde::Serialize)]
+struct ProofPayload {
+    blocks: Vec<String>,
+    encoded_tx: String,
+    proof: Vec<String>,
+    index: String,
+}
+
+impl ProveRelayerClient {
+    pub fn new(cfg: &ProveRelayerConfig) -> Self {
+        Self {
+            http: Client::builder()
+                .timeout(cfg.timeout)
+                .build()
+                .expect("reqwest"),
+            url: cfg.url.clone(),
+        }
+    }
+
+    pub async fn submit(
+        &self,
+        pool: Address,
+        chain_id: Option<u64>,
+        id: B256,
+        tron: &TronProof,
+    ) -> Result<()> {
+        let payload = ProvePayload {
+            pool: pool.to_string(),
+            chain_id,
+            intent_id: id.to_string(),
+            proof: ProofPayload {
+                blocks: tron
+                    .blocks
+                    .iter()
+                    .map(|b| format!("0x{}", hex::encode(b)))
+                    .collect(),
+                encoded_tx: format!("0x{}", hex::encode(&tron.encoded_tx)),
+                proof: tron.proof.iter().map(|p| p.to_string()).collect(),
+                index: tron.index.to_string(),
+            },
+        };
+        let resp = self
+            .http
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .context("POST prove relayer")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("prove relayer rejected proof: {status}: {body}");
+        }
+        Ok(())
+    }
+}
@@ -1,17 +1,21 @@
 use crate::hub::HubClient;
 use crate::{
-    config::{AppConfig, HubTxMode},
+    config::{AppConfig, HubConfig, HubTxMode, SolverRole},
     db::SolverDb,
     db::{HubUserOpKind, SolverJob},
-    indexer::IndexerClient,
+    indexer::{IndexerClient, OpenIntentsOrder, PoolOpenIntentRow},
     metrics::SolverTelemetry,
     policy::{BreakerQuery, PolicyEngine},
     pricing::Pricing,
+    prove_relayer::ProveRelayerClient,
     tron_backend::TronBackend,
-    types::{IntentType, parse_b256, parse_hex_bytes},
+    types::{DeadlineKind, IntentType, parse_b256, parse_hex_bytes},
+    webhook::{FatalWebhook, SummaryWebhook},
 };
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 use tokio::sync::Semaphore;
 use tokio_util::sync::CancellationToken;
@@ -21,21 +25,61 @@ mod context;
 mod executor;
 mod hub_flow;
 mod job;
+mod lag;
 mod lease;
 mod retry;
+mod settlement;
+mod summary;
 mod tron_flow;
+mod tron_health;
 
 use alloy::primitives::U256;
-use context::{JobCtx, JobTypeSems};
+use context::{HubPool, JobCtx, JobTypeSems, PreTxBudget};
 use executor::execute_leased_jobs;
 use job::{
-    b256_to_bytes32, decode_trigger_contract_and_selector, duration_hours_for_lock_period_blocks,
-    ensure_delegate_reservation, finalize_after_prove, looks_like_tron_contract_failure,
-    looks_like_tron_server_busy,
+    b256_to_bytes32, decode_trigger_contract_and_selector, decode_tron_recipient,
+    duration_hours_for_lock_period_blocks, ensure_delegate_reservation,
+    estimate_trigger_tx_size_bytes, finalize_after_prove, looks_like_stale_tron_proof,
+    looks_like_tron_contract_failure, looks_like_tron_out_of_energy, looks_like_tron_server_busy,
 };
 
-const INTENT_CLAIM_DEPOSIT: u64 = 1_000_000;
-pub(super) const LEASE_FOR_SECS: u64 = 30;
+/// Flags `promote` on SIGUSR1 so a follower switches to active on its next tick.
+#[cfg(unix)]
+fn spawn_promotion_signal_listener(promote: Arc<AtomicBool>) -> Result<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut sigusr1 = signal(SignalKind::user_defined1()).context("install SIGUSR1 handler")?;
+    tokio::spawn(async move {
+        if sigusr1.recv().await.is_some() {
+            promote.store(true, Ordering::Relaxed);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn spawn_promotion_signal_listener(_promote: Arc<AtomicBool>) -> Result<()> {
+    Ok(())
+}
+
+/// Flags `reload` on every SIGHUP; the next tick reloads the policy and pricing config.
+#[cfg(unix)]
+fn spawn_reload_signal_listener(reload: Arc<AtomicBool>) -> Result<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut sighup = signal(SignalKind::hangup()).context("install SIGHUP handler")?;
+    tokio::spawn(async move {
+        while sighup.recv().await.is_some() {
+            reload.store(true, Ordering::Relaxed);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn spawn_reload_signal_listener(_reload: Arc<AtomicBool>) -> Result<()> {
+    Ok(())
+}
 
 pub fn estimate_hub_cost_usd_from_userops(
     eth_usd: f64,
@@ -59,112 +103,164 @@ pub fn estimate_hub_cost_usd_from_userops(
     Some(usd)
 }
 
+/// Deposit token the pool can still pull for new claims: what the solver holds and has allowed
+/// the pool to take, less one `claim_deposit` per `ready` job already queued but not claimed.
+///
+/// A zero allowance doesn't limit: the claim path approves the pool for `U256::MAX` first. A
+/// partial one does, since tokens like USDT refuse to change a non-zero allowance.
+fn claim_deposit_budget(
+    balance: U256,
+    allowance: U256,
+    ready_jobs: u64,
+    claim_deposit: u64,
+) -> U256 {
+    let spendable = if allowance.is_zero() {
+        balance
+    } else {
+        balance.min(allowance)
+    };
+    spendable.saturating_sub(U256::from(ready_jobs).saturating_mul(U256::from(claim_deposit)))
+}
+
 struct ShouldAttemptDecision {
     ok: bool,
-    rental_quote: Option<RentalQuoteDecision>,
+    rental_quote: Option<crate::tron_backend::EnergyRentalQuote>,
     skip_reason: Option<&'static str>,
+    /// Cost estimates the decision was made on (zero for skips decided before costing).
+    estimate: CostEstimate,
 }
 
-struct RentalQuoteDecision {
-    provider: String,
-    receiver_evm: [u8; 20],
-    balance_sun: i64,
-    lock_period: i64,
-    amount_units: u64,
-    duration_hours: u64,
-    cost_trx: f64,
-    rendered_request: tron::RenderedJsonApiRequest,
-    response_json: serde_json::Value,
+#[derive(Debug, Clone, Copy, Default)]
+struct CostEstimate {
+    required_pre_txs: usize,
+    hub_cost_usd: f64,
+    tron_fee_usd: f64,
+    rental_cost_usd: f64,
+    /// Priced profit, when the policy's profitability check ran.
+    profit_usd: Option<f64>,
 }
 
 pub struct Solver {
     cfg: AppConfig,
     telemetry: SolverTelemetry,
     db: SolverDb,
-    indexer: IndexerClient,
-    hub: Arc<HubClient>,
+    /// Primary pool first; see [`AppConfig::pools`].
+    pools: Vec<HubPool>,
     tron: TronBackend,
     pricing: Pricing,
     policy: PolicyEngine,
     instance_id: String,
     hub_userop_submit_sem: Arc<Semaphore>,
     tron_broadcast_sem: Arc<Semaphore>,
+    tron_proof_sem: Arc<Semaphore>,
     job_type_sems: Arc<JobTypeSems>,
+    fatal_webhook: Option<FatalWebhook>,
+    last_archive_at: Option<Instant>,
+    last_sweep_at: Option<Instant>,
+    last_auto_stake_at: Option<Instant>,
+    lag_warnings: lag::LagWarnings,
+    tron_health: tron_health::TronHealthGate,
+    last_paymaster_deposit_check_at: Option<Instant>,
+    summary_webhook: Option<SummaryWebhook>,
+    last_summary_at: Option<Instant>,
+    /// Claim deposit budget per pool id (see `claim_deposit_budget`), read before each discovery
+    /// pass and drawn down by the claims it queues. Absent when a read failed or a deposit funder
+    /// tops the solver up.
+    deposit_balances: HashMap<String, U256>,
+    role: SolverRole,
+    /// Set by the SIGUSR1 listener; consumed by the next follower tick.
+    promote_requested: Arc<AtomicBool>,
+    /// Set by the SIGHUP listener; consumed before the next tick.
+    reload_requested: Arc<AtomicBool>,
 }
 
 impl Solver {
     pub async fn new(cfg: AppConfig, telemetry: SolverTelemetry) -> Result<Self> {
+        let problems = cfg.intent_type_misconfigurations();
+        if !problems.is_empty() {
+            anyhow::bail!(
+                "enabled intent types can't be filled with this config: {}",
+                problems.join("; ")
+            );
+        }
+
         let db = SolverDb::connect(&cfg.db_url, 10).await?;
         db.migrate().await?;
 
-        let hub = match cfg.hub.tx_mode {
-            HubTxMode::Eoa => {
-                HubClient::new_eoa(
-                    &cfg.hub.rpc_url,
-                    cfg.hub.chain_id,
-                    cfg.hub.pool,
-                    cfg.hub.signer_private_key,
-                    telemetry.clone(),
-                )
-                .await?
+        let mut pools = Vec::new();
+        for pool_cfg in cfg.pools() {
+            let pool_id = pool_cfg.hub.pool_id.clone();
+            let hub = build_hub_client(&pool_cfg.hub, &telemetry)
+                .await
+                .with_context(|| format!("init hub client for pool {pool_id}"))?;
+            let hub = Arc::new(hub);
+
+            // The claim deposit is a pool constant; a mismatch means allowance and budget math are
+            // off.
+            match hub.pool_claim_deposit().await {
+                Ok(onchain) if onchain != U256::from(pool_cfg.hub.claim_deposit) => {
+                    tracing::warn!(
+                        pool_id = %pool_id,
+                        configured = pool_cfg.hub.claim_deposit,
+                        onchain = %onchain,
+                        "HUB_CLAIM_DEPOSIT does not match pool INTENT_CLAIM_DEPOSIT"
+                    );
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::warn!(
+                        pool_id = %pool_id,
+                        err = %err,
+                        "failed to read pool INTENT_CLAIM_DEPOSIT"
+                    );
+                }
             }
-            HubTxMode::Safe4337 => {
-                let entrypoint = cfg
-                    .hub
-                    .entrypoint
-                    .context("missing HUB_ENTRYPOINT_ADDRESS")?;
-                let module = cfg
-                    .hub
-                    .safe_4337_module
-                    .context("missing HUB_SAFE_4337_MODULE_ADDRESS")?;
-                let paymasters = cfg
-                    .hub
-                    .paymasters
-                    .iter()
-                    .map(|pm| aa::paymaster::PaymasterService {
-                        url: pm.url.clone(),
-                        context: pm.context.clone(),
-                    })
-                    .collect::<Vec<_>>();
-
-                HubClient::new_safe4337(
-                    &cfg.hub.rpc_url,
-                    cfg.hub.chain_id,
-                    cfg.hub.pool,
-                    entrypoint,
-                    cfg.hub.safe,
-                    module,
-                    cfg.hub.safe_deployment.clone(),
-                    cfg.hub.bundler_urls.clone(),
-                    paymasters,
-                    cfg.hub.signer_private_key,
-                    telemetry.clone(),
-                )
-                .await?
+
+            // For Safe4337 mode: on restart, the bundler may have pending userops that are not yet
+            // reflected in EntryPoint.getNonce(). Seed a local nonce floor from our persisted
+            // submitted userops to avoid AA25 invalid nonce loops.
+            if pool_cfg.hub.tx_mode == HubTxMode::Safe4337
+                && let Some(floor) = db
+                    .hub_userop_nonce_floor_for_sender(hub.solver_address())
+                    .await?
+            {
+                hub.safe4337_set_nonce_floor(floor).await?;
             }
-        };
-        let hub = Arc::new(hub);
-
-        // For Safe4337 mode: on restart, the bundler may have pending userops that are not yet
-        // reflected in EntryPoint.getNonce(). Seed a local nonce floor from our persisted
-        // submitted userops to avoid AA25 invalid nonce loops.
-        if cfg.hub.tx_mode == HubTxMode::Safe4337
-            && let Some(floor) = db
-                .hub_userop_nonce_floor_for_sender(hub.solver_address())
-                .await?
-        {
-            hub.safe4337_set_nonce_floor(floor).await?;
-        }
 
-        let indexer = IndexerClient::new(
-            cfg.indexer.base_url.clone(),
-            cfg.indexer.timeout,
-            telemetry.clone(),
-        );
+            let indexer = IndexerClient::new(
+                pool_cfg.indexer.base_url.clone(),
+                pool_cfg.indexer.timeout,
+                telemetry.clone(),
+            );
+            let prove_relayer = pool_cfg
+                .hub
+                .prove_relayer
+                .as_ref()
+                .map(ProveRelayerClient::new);
+            pools.push(HubPool {
+                cfg: pool_cfg.hub,
+                indexer_cfg: pool_cfg.indexer,
+                hub,
+                indexer,
+                prove_relayer,
+            });
+        }
 
         let tron = TronBackend::new(cfg.tron.clone(), cfg.jobs.clone(), telemetry.clone());
-        let pricing = Pricing::new(cfg.pricing.clone());
-        let policy = PolicyEngine::new(cfg.policy.clone());
+        tron.verify_network().await.context("verify Tron network")?;
+        let mut pricing = Pricing::new(cfg.pricing.clone());
+        // The price source can be briefly down, so this only warns; left unfixed, every intent
+        // with a TRX-denominated cost is skipped as unpriced.
+        if cfg.needs_trx_usd_price()
+            && let Err(err) = pricing.trx_usd().await
+        {
+            tracing::warn!(
+                err = %err,
+                "TRX/USD price source unreachable at startup; profitability checks will skip intents until it recovers"
+            );
+        }
+        let policy = PolicyEngine::new(cfg.policy.clone())
+            .with_trigger_spend_selectors(cfg.tron.trigger_spend_selectors.clone());
 
         let job_type_sems = Arc::new(JobTypeSems {
             trx_transfer: Arc::new(Semaphore::new(
@@ -183,24 +279,51 @@ impl Solver {
         let tron_broadcast_sem = Arc::new(Semaphore::new(
             usize::try_from(cfg.jobs.concurrency_tron_broadcast).unwrap_or(1),
         ));
+        let tron_proof_sem = Arc::new(Semaphore::new(
+            usize::try_from(cfg.jobs.concurrency_tron_proof).unwrap_or(1),
+        ));
+
+        let fatal_webhook = FatalWebhook::new(&cfg.fatal_webhook, telemetry.clone());
+        let summary_webhook = SummaryWebhook::new(&cfg.summary);
 
         Ok(Self {
             instance_id: cfg.instance_id.clone(),
+            role: cfg.role,
             cfg,
             telemetry,
             db,
-            indexer,
-            hub,
+            pools,
             tron,
             pricing,
             policy,
             hub_userop_submit_sem: Arc::new(Semaphore::new(1)),
             tron_broadcast_sem,
+            tron_proof_sem,
             job_type_sems,
+            fatal_webhook,
+            last_archive_at: None,
+            last_sweep_at: None,
+            last_auto_stake_at: None,
+            lag_warnings: lag::LagWarnings::default(),
+            tron_health: tron_health::TronHealthGate::default(),
+            last_paymaster_deposit_check_at: None,
+            summary_webhook,
+            last_summary_at: None,
+            deposit_balances: HashMap::new(),
+            promote_requested: Arc::new(AtomicBool::new(false)),
+            reload_requested: Arc::new(AtomicBool::new(false)),
         })
     }
 
     pub async fn run(mut self, shutdown: CancellationToken) -> Result<()> {
+        if self.role == SolverRole::Follower {
+            tracing::info!(
+                instance_id = %self.instance_id,
+                "running as follower; not leasing or claiming"
+            );
+            spawn_promotion_signal_listener(self.promote_requested.clone())?;
+        }
+        spawn_reload_signal_listener(self.reload_requested.clone())?;
         let mut interval = tokio::time::interval(self.cfg.jobs.tick_interval);
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
@@ -213,6 +336,9 @@ impl Solver {
                 _ = interval.tick() => {}
             }
 
+            if self.reload_requested.swap(false, Ordering::Relaxed) {
+                self.reload_policy_config();
+            }
             let started = Instant::now();
             let res = self.tick().await;
             match res {
@@ -229,38 +355,263 @@ impl Solver {
     }
 
     async fn tick(&mut self) -> Result<()> {
-        self.indexer.health().await?;
+        match self.db.count_jobs_by_state().await {
+            Ok(counts) => self.telemetry.jobs_by_state(&counts),
+            Err(err) => tracing::warn!(err = %err, "failed to count jobs by state"),
+        }
+        self.maybe_check_paymaster_deposits().await;
+        if self.role == SolverRole::Follower && !self.maybe_promote().await {
+            return Ok(());
+        }
         let _ = self.db.cleanup_expired_delegate_reservations().await;
+        self.maybe_archive_terminal_jobs().await;
+        self.maybe_report_summary().await;
+
+        // With the Tron node down, new claims and Tron-stage jobs would only fail and retry; hold
+        // them back but keep driving jobs that only need the hub (proving, settlement).
+        let tron_healthy = self.tron_healthy().await;
 
-        // Indexer lag guard: do not claim if we're too far behind head.
-        match self.indexer.latest_indexed_pool_block_number().await {
-            Ok(Some(indexed)) => {
-                let head = self.hub.hub_block_number().await?;
-                let lag = head.saturating_sub(indexed);
-                if lag > self.cfg.indexer.max_head_lag_blocks {
+        // Jobs are only driven for pools whose indexer is healthy and caught up; one failing pool
+        // doesn't hold up the others.
+        let mut ready_pools = Vec::with_capacity(self.pools.len());
+        for pool in self.pools.clone() {
+            match self.pool_ready(&pool).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(err) => {
                     tracing::warn!(
-                        head,
-                        indexed,
-                        lag,
-                        max_lag = self.cfg.indexer.max_head_lag_blocks,
-                        "indexer lag too high; skipping tick"
+                        pool_id = %pool.id(),
+                        err = %err,
+                        "pool readiness check failed; skipping pool this tick"
                     );
+                    continue;
+                }
+            }
+            if tron_healthy && let Err(err) = self.discover_intents(&pool).await {
+                tracing::warn!(
+                    pool_id = %pool.id(),
+                    err = %err,
+                    "intent discovery failed; skipping pool this tick"
+                );
+                continue;
+            }
+            ready_pools.push(pool);
+        }
+
+        // Dry run: never lease jobs, so nothing (including jobs left over from a live run) gets
+        // claimed, broadcast or proved.
+        if self.cfg.dry_run || ready_pools.is_empty() {
+            return Ok(());
+        }
+
+        let ready_pool_ids: Vec<String> = ready_pools.iter().map(|p| p.id().to_string()).collect();
+        let jobs = self
+            .db
+            .lease_jobs(
+                &self.instance_id,
+                std::time::Duration::from_secs(self.cfg.jobs.lease_secs),
+                i64::try_from(self.cfg.jobs.max_in_flight_jobs)
+                    .unwrap_or(50)
+                    .max(1),
+                &ready_pool_ids,
+                tron_healthy,
+            )
+            .await?;
+
+        let trx_usd = if self.cfg.has_rental_usd_caps() {
+            match self.pricing.trx_usd().await {
+                Ok(v) => Some(v),
+                Err(err) => {
+                    tracing::warn!(err = %err, "trx_usd unavailable; capped rentals will be refused");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let ctx = JobCtx {
+            cfg: self.cfg.clone(),
+            db: self.db.clone(),
+            indexer: self.pools[0].indexer.clone(),
+            hub: self.pools[0].hub.clone(),
+            prove_relayer: self.pools[0].prove_relayer.clone(),
+            tron: self.tron.clone(),
+            instance_id: self.instance_id.clone(),
+            hub_userop_submit_sem: self.hub_userop_submit_sem.clone(),
+            tron_broadcast_sem: self.tron_broadcast_sem.clone(),
+            tron_proof_sem: self.tron_proof_sem.clone(),
+            job_type_sems: self.job_type_sems.clone(),
+            fatal_webhook: self.fatal_webhook.clone(),
+            telemetry: self.telemetry.clone(),
+            pre_tx_budget: Arc::new(PreTxBudget::new(self.cfg.jobs.max_pre_txs_per_tick)),
+            trx_usd,
+        };
+
+        let idle = jobs.is_empty();
+        execute_leased_jobs(ctx, &ready_pools, jobs).await;
+        if idle && tron_healthy {
+            self.maybe_sweep_consolidation(trx_usd).await;
+            self.maybe_auto_stake().await;
+        }
+        Ok(())
+    }
+
+    /// Tron health gate: pings the node unless a recent failure is still cooling down.
+    async fn tron_healthy(&mut self) -> bool {
+        let Some(cooldown) = self.cfg.tron.health_gate_cooldown else {
+            return true;
+        };
+        let now = Instant::now();
+        if self.tron_health.is_open(now) {
+            self.telemetry.tick_skipped_tron_unhealthy();
+            return false;
+        }
+        match self.tron.ping().await {
+            Ok(()) => {
+                if self.tron_health.on_success() {
+                    tracing::info!("tron node healthy again; resuming tron-stage jobs");
+                }
+                true
+            }
+            Err(err) => {
+                let failures = self.tron_health.on_failure(cooldown, now);
+                self.telemetry.tick_skipped_tron_unhealthy();
+                tracing::warn!(
+                    err = %err,
+                    failures,
+                    cooldown_secs = cooldown.as_secs(),
+                    "tron node unhealthy; not leasing tron-stage jobs"
+                );
+                false
+            }
+        }
+    }
+
+    /// Checks the pool's indexer `/health`, retrying transient failures; errors only once every
+    /// attempt failed.
+    async fn check_indexer_health(&self, pool: &HubPool) -> Result<()> {
+        let mut attempt: u32 = 0;
+        loop {
+            match pool.indexer.health().await {
+                Ok(()) => {
+                    if attempt > 0 {
+                        self.telemetry.indexer_health_fail(pool.id(), true);
+                        tracing::info!(
+                            pool_id = %pool.id(),
+                            failed_attempts = attempt,
+                            "indexer health recovered after retry"
+                        );
+                    }
                     return Ok(());
                 }
+                Err(err) if attempt < pool.indexer_cfg.health_retries => {
+                    attempt += 1;
+                    tracing::debug!(
+                        pool_id = %pool.id(),
+                        attempt,
+                        err = %err,
+                        "indexer health check failed; retrying"
+                    );
+                    tokio::time::sleep(pool.indexer_cfg.health_retry_delay).await;
+                }
+                Err(err) => {
+                    self.telemetry.indexer_health_fail(pool.id(), false);
+                    return Err(
+                        err.context(format!("indexer unhealthy after {} attempts", attempt + 1))
+                    );
+                }
+            }
+        }
+    }
+
+    /// Whether `pool`'s jobs can be driven this tick: its indexer is healthy and within the lag
+    /// limit.
+    async fn pool_ready(&mut self, pool: &HubPool) -> Result<bool> {
+        self.check_indexer_health(pool).await?;
+
+        // Indexer lag guard: do not claim if we're too far behind head. Lag is measured against
+        // the indexer's confirmed head, so its configured confirmation depth doesn't count.
+        match pool.indexer.latest_indexed_pool_block().await {
+            Ok(Some(progress)) => {
+                let head = pool.hub.hub_block_number().await?;
+                let indexed = progress.block;
+                let confirmations = progress.confirmations();
+                let lag = head.saturating_sub(confirmations).saturating_sub(indexed);
+                self.telemetry.indexer_lag_blocks(pool.id(), lag);
+                let max_lag = pool.indexer_cfg.max_head_lag_blocks;
+                if lag > max_lag {
+                    self.telemetry.tick_skipped_lag(pool.id());
+                    let (level, skipped_ticks) = self.lag_warnings.on_skip(
+                        pool.id(),
+                        lag,
+                        pool.indexer_cfg.lag_warn_interval,
+                        Instant::now(),
+                    );
+                    match level {
+                        lag::LagLog::Silent => {}
+                        lag::LagLog::Warn => tracing::warn!(
+                            pool_id = %pool.id(),
+                            head,
+                            confirmations,
+                            indexed,
+                            lag,
+                            max_lag,
+                            skipped_ticks,
+                            "indexer lag too high; skipping pool"
+                        ),
+                        lag::LagLog::Error => tracing::error!(
+                            pool_id = %pool.id(),
+                            head,
+                            confirmations,
+                            indexed,
+                            lag,
+                            max_lag,
+                            skipped_ticks,
+                            "indexer lag too high and still growing; skipping pool"
+                        ),
+                    }
+                    return Ok(false);
+                }
+                if self.lag_warnings.on_caught_up(pool.id()) {
+                    tracing::info!(pool_id = %pool.id(), lag, "indexer caught up; resuming pool");
+                }
             }
             Ok(None) => {}
             Err(err) => {
-                tracing::warn!(err = %err, "failed to query indexer lag; continuing without lag guard");
+                tracing::warn!(
+                    pool_id = %pool.id(),
+                    err = %err,
+                    "failed to query indexer lag; continuing without lag guard"
+                );
             }
         }
+        Ok(true)
+    }
 
-        let rows = self
-            .indexer
-            .fetch_open_intents(self.cfg.jobs.fill_max_claims)
-            .await?;
+    async fn discover_intents(&mut self, pool: &HubPool) -> Result<()> {
+        self.refresh_deposit_balance(pool).await;
+
+        let rows = if self.backlog_exceeds_threshold(pool).await {
+            let min_deadline = self.min_fillable_deadline(pool).await?;
+            pool.indexer
+                .fetch_open_intents(
+                    self.cfg.jobs.fill_max_claims,
+                    OpenIntentsOrder::DeadlineAsc,
+                    Some(min_deadline),
+                )
+                .await?
+        } else {
+            pool.indexer
+                .fetch_open_intents(
+                    self.cfg.jobs.fill_max_claims,
+                    OpenIntentsOrder::Sequence,
+                    None,
+                )
+                .await?
+        };
 
         for row in rows {
-            let decision = self.should_attempt(&row).await?;
+            let decision = self.should_attempt(pool, &row).await?;
             if !decision.ok {
                 if let Some(reason) = decision.skip_reason {
                     self.telemetry.candidate_skip(row.intent_type, reason);
@@ -270,12 +621,29 @@ impl Solver {
             let id = parse_b256(&row.id)?;
             let specs = parse_hex_bytes(&row.intent_specs)?;
             let intent_id = b256_to_bytes32(id);
-            self.db
-                .insert_job_if_new(intent_id, row.intent_type, &specs, row.deadline)
+            if self.cfg.dry_run {
+                self.record_dry_run_decision(pool, &row, intent_id, &decision)
+                    .await;
+                continue;
+            }
+            let inserted = self
+                .db
+                .insert_job_if_new(
+                    pool.id(),
+                    intent_id,
+                    row.intent_type,
+                    &specs,
+                    row.deadline,
+                    decision.estimate.profit_usd,
+                )
                 .await?;
+            // An intent that already has a ready job is counted by `refresh_deposit_balance`.
+            if inserted && let Some(balance) = self.deposit_balances.get_mut(pool.id()) {
+                *balance = balance.saturating_sub(U256::from(pool.cfg.claim_deposit));
+            }
 
             if let Some(q) = decision.rental_quote
-                && let Some(job_id) = self.db.job_id_for_intent(intent_id).await?
+                && let Some(job_id) = self.db.job_id_for_intent(pool.id(), intent_id).await?
             {
                 let request_json = serde_json::json!({
                     "quote": q.rendered_request,
@@ -305,37 +673,430 @@ impl Solver {
                     .await;
             }
         }
+        Ok(())
+    }
 
-        let jobs = self
+    /// Works out how much `pool` can still take in claim deposits (see `claim_deposit_budget`) so
+    /// `should_attempt` can skip claims the solver couldn't post a deposit for. A failed read
+    /// leaves claims unblocked; the claim itself surfaces the shortfall.
+    async fn refresh_deposit_balance(&mut self, pool: &HubPool) {
+        self.deposit_balances.remove(pool.id());
+        let balance = match pool.hub.deposit_token_balance().await {
+            Ok(v) => v,
+            Err(err) => {
+                tracing::warn!(
+                    pool_id = %pool.id(),
+                    err = %format!("{err:#}"),
+                    "failed to read deposit token balance"
+                );
+                return;
+            }
+        };
+        let units = balance.to_string().parse::<f64>().unwrap_or(0.0);
+        self.telemetry.deposit_token_balance(pool.id(), units);
+        if pool.hub.has_deposit_funder() {
+            return;
+        }
+        let (allowance, ready) = match tokio::try_join!(
+            pool.hub.deposit_token_allowance(),
+            self.db.count_unclaimed_ready_jobs(pool.id()),
+        ) {
+            Ok(v) => v,
+            Err(err) => {
+                tracing::warn!(
+                    pool_id = %pool.id(),
+                    err = %format!("{err:#}"),
+                    "failed to read deposit allowance or queued claims"
+                );
+                return;
+            }
+        };
+        let budget = claim_deposit_budget(
+            balance,
+            allowance,
+            u64::try_from(ready).unwrap_or(0),
+            pool.cfg.claim_deposit,
+        );
+        self.deposit_balances.insert(pool.id().to_string(), budget);
+    }
+
+    /// Whether `pool` has a backlog large enough (typically after downtime) that candidates should
+    /// be taken nearest-deadline first, so as many as possible are filled before they expire.
+    async fn backlog_exceeds_threshold(&self, pool: &HubPool) -> bool {
+        let threshold = self.cfg.jobs.backlog_deadline_first_threshold;
+        if threshold == 0 {
+            return false;
+        }
+        match pool.indexer.count_open_intents().await {
+            Ok(n) if n > threshold => {
+                tracing::info!(
+                    pool_id = %pool.id(),
+                    open_intents = n,
+                    threshold,
+                    "open intent backlog; prioritizing nearest deadlines"
+                );
+                true
+            }
+            Ok(_) => false,
+            Err(err) => {
+                tracing::debug!(pool_id = %pool.id(), err = %err, "failed to count open intents");
+                false
+            }
+        }
+    }
+
+    /// Earliest deadline that still clears the policy's deadline slack (see `PolicyEngine`).
+    async fn min_fillable_deadline(&self, pool: &HubPool) -> Result<i64> {
+        Ok(match self.cfg.policy.deadline_kind {
+            DeadlineKind::Timestamp => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                i64::try_from(now.saturating_add(self.cfg.policy.min_deadline_slack_secs))
+                    .unwrap_or(i64::MAX)
+            }
+            DeadlineKind::Block => {
+                let head = pool.hub.hub_block_number().await?;
+                i64::try_from(head.saturating_add(self.cfg.policy.min_deadline_slack_blocks))
+                    .unwrap_or(i64::MAX)
+            }
+        })
+    }
+
+    async fn record_dry_run_decision(
+        &self,
+        pool: &HubPool,
+        row: &PoolOpenIntentRow,
+        intent_id: [u8; 32],
+        decision: &ShouldAttemptDecision,
+    ) {
+        let est = decision.estimate;
+        let mut actions = vec!["claim_intent"];
+        actions.extend(std::iter::repeat_n("tron_pre_tx", est.required_pre_txs));
+        if decision.rental_quote.is_some() {
+            actions.push("rent_energy");
+        }
+        actions.extend(["tron_fill", "prove_intent_fill"]);
+        // The actions are derived from the pre-claim estimate, not from built Tron txs; mark the
+        // row so it can't be mistaken for a job's real plan.
+        let plan = serde_json::json!({
+            "dry_run": true,
+            "plan_source": "estimate",
+            "actions": actions,
+            "required_pre_txs": est.required_pre_txs,
+            "escrow_token": row.escrow_token,
+            "escrow_amount": row.escrow_amount,
+            "deadline": row.deadline,
+            "pool_id": pool.id(),
+            "claim_deposit": pool.cfg.claim_deposit,
+            "rental_quote": decision.rental_quote.as_ref().map(|q| serde_json::json!({
+                "provider": q.provider,
+                "duration_hours": q.duration_hours,
+                "amount_units": q.amount_units,
+                "cost_trx": q.cost_trx,
+            })),
+        });
+        tracing::info!(
+            id = %row.id,
+            intent_type = row.intent_type,
+            hub_cost_usd = est.hub_cost_usd,
+            tron_fee_usd = est.tron_fee_usd,
+            rental_cost_usd = est.rental_cost_usd,
+            plan = %plan,
+            "dry run: would claim intent"
+        );
+        if let Err(err) = self
             .db
-            .lease_jobs(
-                &self.instance_id,
-                std::time::Duration::from_secs(LEASE_FOR_SECS),
-                i64::try_from(self.cfg.jobs.max_in_flight_jobs)
-                    .unwrap_or(50)
-                    .max(1),
+            .upsert_dry_run_decision(
+                pool.id(),
+                intent_id,
+                row.intent_type,
+                &plan.to_string(),
+                est.hub_cost_usd,
+                est.tron_fee_usd,
+                est.rental_cost_usd,
             )
-            .await?;
+            .await
+        {
+            tracing::warn!(err = %err, "failed to record dry run decision");
+        }
+    }
 
-        let ctx = JobCtx {
-            cfg: self.cfg.clone(),
-            db: self.db.clone(),
-            indexer: self.indexer.clone(),
-            hub: self.hub.clone(),
-            tron: self.tron.clone(),
-            instance_id: self.instance_id.clone(),
-            hub_userop_submit_sem: self.hub_userop_submit_sem.clone(),
-            tron_broadcast_sem: self.tron_broadcast_sem.clone(),
-            job_type_sems: self.job_type_sems.clone(),
-            telemetry: self.telemetry.clone(),
+    async fn maybe_sweep_consolidation(&mut self, trx_usd: Option<f64>) {
+        if !self.cfg.jobs.proactive_consolidation {
+            return;
+        }
+        if self
+            .last_sweep_at
+            .is_some_and(|t| t.elapsed() < self.cfg.jobs.proactive_consolidation_interval)
+        {
+            return;
+        }
+        self.last_sweep_at = Some(Instant::now());
+
+        let transfers = match self
+            .tron
+            .prepare_consolidation_sweep(self.pools[0].hub.as_ref())
+            .await
+        {
+            Ok(v) => v,
+            Err(err) => {
+                tracing::warn!(err = %err, "failed to plan consolidation sweep");
+                return;
+            }
         };
+        for t in transfers {
+            let energy = u64::try_from(t.tx.energy_required.unwrap_or(0)).unwrap_or(0);
+            if let Err(err) = self
+                .tron
+                .rent_energy_for_tx(&self.db, &t.tx.tx_bytes, t.tx.txid, energy, trx_usd, None)
+                .await
+            {
+                tracing::warn!(err = %err, "failed to rent energy for consolidation sweep transfer");
+            }
+            let res = match self.tron_broadcast_sem.acquire().await {
+                Ok(_permit) => self.tron.broadcast_signed_tx(&t.tx.tx_bytes).await,
+                Err(err) => Err(anyhow::Error::new(err).context("acquire tron_broadcast_sem")),
+            };
+            let error = res.err().map(|err| format!("{err:#}"));
+            self.telemetry
+                .consolidation_sweep_transfer(t.asset, error.is_none());
+            match &error {
+                None => tracing::info!(
+                    asset = t.asset,
+                    from = %t.from,
+                    to = %t.to,
+                    amount = t.amount,
+                    txid = %hex::encode(t.tx.txid),
+                    "consolidation sweep transfer broadcast"
+                ),
+                Some(err) => tracing::warn!(
+                    asset = t.asset,
+                    from = %t.from,
+                    to = %t.to,
+                    amount = t.amount,
+                    err = %err,
+                    "consolidation sweep transfer failed"
+                ),
+            }
+            if let Err(err) = self
+                .db
+                .record_consolidation_sweep(
+                    t.asset,
+                    t.from.evm().into_array(),
+                    t.to.evm().into_array(),
+                    t.amount,
+                    t.tx.txid,
+                    &self.instance_id,
+                    error.as_deref(),
+                )
+                .await
+            {
+                tracing::warn!(err = %err, "failed to record consolidation sweep");
+            }
+        }
+    }
 
-        execute_leased_jobs(ctx, jobs).await;
-        Ok(())
+    async fn maybe_auto_stake(&mut self) {
+        if !self.cfg.tron.auto_stake_enabled {
+            return;
+        }
+        if self
+            .last_auto_stake_at
+            .is_some_and(|t| t.elapsed() < self.cfg.tron.auto_stake_interval)
+        {
+            return;
+        }
+        self.last_auto_stake_at = Some(Instant::now());
+
+        let targets = [
+            (
+                "energy",
+                tron::protocol::ResourceCode::Energy,
+                self.cfg.tron.auto_stake_target_energy_sun,
+            ),
+            (
+                "bandwidth",
+                tron::protocol::ResourceCode::Bandwidth,
+                self.cfg.tron.auto_stake_target_bandwidth_sun,
+            ),
+        ];
+        for (label, resource, target_sun) in targets {
+            if target_sun == 0 {
+                continue;
+            }
+            let target_sun = i64::try_from(target_sun).unwrap_or(i64::MAX);
+            let res = match self.tron_broadcast_sem.acquire().await {
+                Ok(_permit) => self.tron.ensure_staked_for(resource, target_sun).await,
+                Err(err) => Err(anyhow::Error::new(err).context("acquire tron_broadcast_sem")),
+            };
+            match res {
+                Ok(None) => {}
+                Ok(Some(t)) => {
+                    self.telemetry.auto_stake_top_up(label, true);
+                    tracing::info!(
+                        resource = label,
+                        owner = %t.owner,
+                        available_sun = t.available_sun,
+                        target_sun,
+                        amount_sun = t.amount_sun,
+                        txid = %hex::encode(t.txid),
+                        "auto-stake top-up broadcast"
+                    );
+                }
+                Err(err) => {
+                    self.telemetry.auto_stake_top_up(label, false);
+                    tracing::warn!(resource = label, err = %err, "auto-stake top-up failed");
+                }
+            }
+        }
+    }
+
+    /// SIGHUP: re-reads the config and applies its `policy` and `pricing` sections (profit
+    /// thresholds, caps, allow/deny lists, price sources). Everything else (connections, keys,
+    /// pools, job tuning) keeps its startup value until a restart. An invalid config is logged
+    /// and ignored.
+    fn reload_policy_config(&mut self) {
+        let loaded = match crate::config::reload_config() {
+            Ok(cfg) => cfg,
+            Err(err) => {
+                tracing::warn!(
+                    err = %format!("{err:#}"),
+                    "config reload failed; keeping current policy"
+                );
+                return;
+            }
+        };
+        let mut next = self.cfg.clone();
+        next.policy = loaded.policy;
+        next.pricing = loaded.pricing;
+        let problems = next.intent_type_misconfigurations();
+        if !problems.is_empty() {
+            tracing::warn!(
+                problems = %problems.join("; "),
+                "reloaded config can't fill the enabled intent types; keeping current policy"
+            );
+            return;
+        }
+        self.policy = PolicyEngine::new(next.policy.clone())
+            .with_trigger_spend_selectors(next.tron.trigger_spend_selectors.clone());
+        self.pricing.set_config(next.pricing.clone());
+        self.cfg = next;
+        tracing::info!(
+            min_profit_usd = self.cfg.policy.min_profit_usd,
+            "reloaded policy and pricing config"
+        );
     }
 
-    async fn estimate_hub_cost_usd(&mut self) -> Result<f64> {
-        if self.cfg.hub.tx_mode != HubTxMode::Safe4337 {
+    /// Follower only: switches to active once promoted via SIGUSR1 or `solver.instance_promotions`.
+    async fn maybe_promote(&mut self) -> bool {
+        let via = if self.promote_requested.swap(false, Ordering::Relaxed) {
+            "SIGUSR1"
+        } else {
+            match self.db.is_instance_promoted(&self.instance_id).await {
+                Ok(true) => "db",
+                Ok(false) => return false,
+                Err(err) => {
+                    tracing::warn!(err = %err, "failed to check follower promotion");
+                    return false;
+                }
+            }
+        };
+        tracing::warn!(instance_id = %self.instance_id, via, "follower promoted to active");
+        self.role = SolverRole::Active;
+        true
+    }
+
+    async fn maybe_archive_terminal_jobs(&mut self) {
+        if self.cfg.jobs.archive_after_days == 0 {
+            return;
+        }
+        if self
+            .last_archive_at
+            .is_some_and(|t| t.elapsed() < self.cfg.jobs.archive_interval)
+        {
+            return;
+        }
+        self.last_archive_at = Some(Instant::now());
+
+        let older_than =
+            std::time::Duration::from_secs(self.cfg.jobs.archive_after_days.saturating_mul(86_400));
+        let limit = i64::try_from(self.cfg.jobs.archive_batch_size).unwrap_or(500);
+        match self.db.archive_terminal_jobs(older_than, limit).await {
+            Ok(Some(n)) => {
+                if n > 0 {
+                    self.telemetry.jobs_archived(n);
+                    tracing::info!(archived = n, "archived terminal jobs");
+                }
+            }
+            Ok(None) => {}
+            Err(err) => tracing::warn!(err = %err, "failed to archive terminal jobs"),
+        }
+    }
+
+    /// Reads each paymaster's EntryPoint deposit so operators can top up before sponsorship
+    /// (and with it every Safe4337 userop) starts failing.
+    async fn maybe_check_paymaster_deposits(&mut self) {
+        // Checked as often as the most eager pool asks for.
+        let Some(interval) = self
+            .pools
+            .iter()
+            .filter_map(|p| p.cfg.paymaster_deposit_check_interval)
+            .min()
+        else {
+            return;
+        };
+        if self
+            .last_paymaster_deposit_check_at
+            .is_some_and(|t| t.elapsed() < interval)
+        {
+            return;
+        }
+        self.last_paymaster_deposit_check_at = Some(Instant::now());
+
+        // Pools usually share paymasters; report each one once.
+        let mut seen = Vec::new();
+        // Only Safe4337 pools use paymasters; the tx mode is per pool.
+        for pool in self.pools.iter().filter(|p| {
+            p.cfg.tx_mode == HubTxMode::Safe4337 && p.cfg.paymaster_deposit_check_interval.is_some()
+        }) {
+            let deposits = match pool.hub.safe4337_paymaster_deposits().await {
+                Ok(v) => v,
+                Err(err) => {
+                    tracing::warn!(
+                        pool_id = %pool.id(),
+                        err = %err,
+                        "failed to read paymaster deposits"
+                    );
+                    continue;
+                }
+            };
+            for (paymaster, deposit) in deposits {
+                if seen.contains(&paymaster) {
+                    continue;
+                }
+                seen.push(paymaster);
+                let wei = deposit.to_string().parse::<f64>().unwrap_or(0.0);
+                self.telemetry
+                    .paymaster_deposit_wei(&format!("{paymaster:#x}"), wei);
+                let min = pool.cfg.paymaster_deposit_min_wei;
+                if deposit < min {
+                    tracing::warn!(
+                        pool_id = %pool.id(),
+                        paymaster = %paymaster,
+                        deposit_wei = %deposit,
+                        min_wei = %min,
+                        "paymaster EntryPoint deposit below threshold; top up before sponsorship fails"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Claim + prove cost on `pool`'s hub. Only Safe4337 pools have userop history to go by.
+    async fn estimate_hub_cost_usd(&mut self, pool: &HubPool) -> Result<f64> {
+        if pool.cfg.tx_mode != HubTxMode::Safe4337 {
             return Ok(self.cfg.policy.hub_cost_usd);
         }
 
@@ -377,20 +1138,38 @@ impl Solver {
         self.db.breaker_is_active(_b.contract, _b.selector).await
     }
 
-    async fn estimate_tron_fee_usd(&mut self, intent_type: i16) -> Result<f64> {
+    /// Per-tx Tron fee from recent history. With `tx_size_bytes`, bandwidth is priced for that size
+    /// rather than for the average past tx.
+    async fn estimate_tron_fee_usd(
+        &mut self,
+        intent_type: i16,
+        tx_size_bytes: Option<u64>,
+    ) -> Result<f64> {
+        let static_fee_usd = self.cfg.policy.static_tron_fee_usd(intent_type);
         let trx_usd = match self.pricing.trx_usd().await {
             Ok(v) => v,
-            Err(_) => return Ok(self.cfg.policy.tron_fee_usd),
+            Err(_) => return Ok(static_fee_usd),
         };
 
         let lookback = i64::try_from(self.cfg.policy.tron_fee_history_lookback).unwrap_or(50);
-        let fee_sun = self
-            .db
-            .tron_tx_costs_avg_fee_sun(intent_type, lookback)
-            .await?
-            .unwrap_or(0);
+        let sized_fee_sun = match tx_size_bytes {
+            Some(size) => self
+                .db
+                .tron_tx_costs_fee_split(intent_type, lookback)
+                .await?
+                .map(|s| (s.base_fee_sun + s.net_fee_sun_per_byte * size as f64).round() as i64),
+            None => None,
+        };
+        let fee_sun = match sized_fee_sun {
+            Some(v) => v,
+            None => self
+                .db
+                .tron_tx_costs_avg_fee_sun(intent_type, lookback)
+                .await?
+                .unwrap_or(0),
+        };
         if fee_sun <= 0 {
-            return Ok(self.cfg.policy.tron_fee_usd);
+            return Ok(static_fee_usd);
         }
         let mut usd = (fee_sun as f64 / 1e6) * trx_usd;
         usd *= 1.0 + (self.cfg.policy.tron_fee_headroom_ppm as f64 / 1e6);
@@ -398,6 +1177,72 @@ impl Solver {
     }
 }
 
+pub(crate) async fn build_hub_client(
+    cfg: &HubConfig,
+    telemetry: &SolverTelemetry,
+) -> Result<HubClient> {
+    let hub = match cfg.tx_mode {
+        HubTxMode::Eoa => {
+            HubClient::new_eoa(
+                &cfg.rpc_url,
+                cfg.chain_id,
+                cfg.pool,
+                cfg.signer()?,
+                telemetry.clone(),
+            )
+            .await?
+        }
+        HubTxMode::Safe4337 => {
+            let entrypoint = cfg.entrypoint.context("missing HUB_ENTRYPOINT_ADDRESS")?;
+            let module = cfg
+                .safe_4337_module
+                .context("missing HUB_SAFE_4337_MODULE_ADDRESS")?;
+            let paymasters = cfg
+                .paymasters
+                .iter()
+                .map(|pm| aa::paymaster::PaymasterService {
+                    url: pm.url.clone(),
+                    context: pm.context.clone(),
+                    address: pm.address,
+                })
+                .collect::<Vec<_>>();
+
+            HubClient::new_safe4337(
+                &cfg.rpc_url,
+                cfg.chain_id,
+                cfg.pool,
+                entrypoint,
+                cfg.safe,
+                module,
+                cfg.safe_deployment.clone(),
+                cfg.bundler_urls.clone(),
+                paymasters,
+                aa::Safe4337UserOpSenderOptions {
+                    paymaster_finalization: if cfg.paymaster_self_funded_fallback {
+                        aa::PaymasterFinalizationMode::SelfFundedFallback
+                    } else {
+                        aa::PaymasterFinalizationMode::AlwaysFetchFinal
+                    },
+                    paymaster_freeze: cfg.paymaster_freeze,
+                    ..Default::default()
+                },
+                cfg.signer()?,
+                telemetry.clone(),
+            )
+            .await?
+        }
+    };
+    let hub = hub
+        .with_address_cache_ttl(cfg.address_cache_ttl)
+        .with_min_confirmations(cfg.min_confirmations);
+    match &cfg.deposit_funder {
+        Some(funder) => {
+            hub.with_deposit_funder(&cfg.rpc_url, funder.address, cfg.deposit_funder_signer()?)
+        }
+        None => Ok(hub),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -411,4 +1256,13 @@ mod tests {
         let usd = estimate_hub_cost_usd_from_userops(eth_usd, claim, prove, 100_000).unwrap();
         assert!((usd - 2.2).abs() < 1e-9);
     }
+
+    #[test]
+    fn claim_deposit_budget_caps_by_allowance_and_queued_claims() {
+        let u = U256::from;
+        assert_eq!(claim_deposit_budget(u(100), U256::ZERO, 0, 10), u(100));
+        assert_eq!(claim_deposit_budget(u(100), U256::MAX, 3, 10), u(70));
+        assert_eq!(claim_deposit_budget(u(100), u(25), 1, 10), u(15));
+        assert_eq!(claim_deposit_budget(u(100), U256::MAX, 20, 10), U256::ZERO);
+    }
 }
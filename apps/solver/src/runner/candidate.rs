@@ -1,15 +1,17 @@
 use super::{
-    RentalQuoteDecision, ShouldAttemptDecision, Solver, b256_to_bytes32,
-    decode_trigger_contract_and_selector, duration_hours_for_lock_period_blocks,
+    CostEstimate, HubPool, ShouldAttemptDecision, Solver, b256_to_bytes32,
+    decode_trigger_contract_and_selector, decode_tron_recipient, estimate_trigger_tx_size_bytes,
 };
 use crate::{
     config::TronMode,
     indexer::PoolOpenIntentRow,
-    types::{IntentType, parse_b256, parse_hex_bytes},
+    types::{
+        DeadlineKind, IntentType, compute_intent_id, compute_receiver_intent_id, parse_address,
+        parse_b256, parse_hex_bytes, parse_u256_dec,
+    },
 };
 use alloy::sol_types::SolValue;
 use anyhow::Result;
-use std::time::Instant;
 
 impl Solver {
     async fn skip_intent(
@@ -29,158 +31,54 @@ impl Solver {
             ok: false,
             rental_quote: None,
             skip_reason: Some(metric_reason),
+            estimate: CostEstimate::default(),
         })
     }
 
-    async fn quote_best_energy_rental(
-        &self,
-        receiver: tron::TronAddress,
-        balance_sun: u64,
-        lock_period_blocks: u64,
-        amount_units: u64,
-        duration_hours: u64,
-    ) -> Result<RentalQuoteDecision> {
-        let mut recv = [0u8; 20];
-        recv.copy_from_slice(receiver.evm().as_slice());
-
-        let ctx_quote = tron::RentalContext {
-            resource: tron::RentalResourceKind::Energy,
-            amount: amount_units,
-            lock_period: Some(lock_period_blocks),
-            duration_hours: Some(duration_hours),
-            balance_sun: Some(balance_sun),
-            address_base58check: receiver.to_base58check(),
-            address_hex41: format!("0x{}", hex::encode(receiver.prefixed_bytes())),
-            address_evm_hex: format!("{:#x}", receiver.evm()),
-            txid: None,
-        };
-
-        let mut best: Option<RentalQuoteDecision> = None;
-        let mut last_err: Option<String> = None;
-
-        for provider_cfg in &self.cfg.tron.energy_rental_providers {
-            if provider_cfg.quote.is_none() {
-                continue;
-            }
-            if self
-                .db
-                .rental_provider_is_frozen(&provider_cfg.name)
-                .await?
-                .is_some()
-            {
-                continue;
-            }
-
-            let provider = tron::JsonApiRentalProvider::new(provider_cfg.clone());
-            let started = Instant::now();
-            let res = tokio::time::timeout(
-                std::time::Duration::from_secs(10),
-                provider.quote_with_rendered_request(&ctx_quote),
-            )
-            .await;
-            let ms = started.elapsed().as_millis() as u64;
-
-            match res {
-                Ok(Ok((req, attempt))) => {
-                    let ok = attempt.ok && attempt.cost_trx.is_some();
-                    self.telemetry.rental_quote_ms(provider.name(), ok, ms);
-                    if ok {
-                        let cost_trx = attempt.cost_trx.unwrap_or(f64::INFINITY);
-                        let resp = attempt.response_json.unwrap_or(serde_json::Value::Null);
-                        let candidate = RentalQuoteDecision {
-                            provider: provider.name().to_string(),
-                            receiver_evm: recv,
-                            balance_sun: i64::try_from(balance_sun).unwrap_or(i64::MAX),
-                            lock_period: i64::try_from(lock_period_blocks).unwrap_or(i64::MAX),
-                            amount_units,
-                            duration_hours,
-                            cost_trx,
-                            rendered_request: req,
-                            response_json: resp,
-                        };
-                        let choose = match &best {
-                            None => true,
-                            Some(b) => cost_trx < b.cost_trx,
-                        };
-                        if choose {
-                            best = Some(candidate);
-                        }
-                        let _ = self
-                            .db
-                            .rental_provider_record_success(provider.name())
-                            .await;
-                    } else {
-                        let msg = format!(
-                            "ok={} cost_trx={:?} err={:?}",
-                            attempt.ok, attempt.cost_trx, attempt.error
-                        );
-                        last_err = Some(format!("{}: {msg}", provider.name()));
-                        self.telemetry.rental_quote_ms(provider.name(), false, ms);
-                        let froze = self
-                            .db
-                            .rental_provider_record_failure(
-                                provider.name(),
-                                self.cfg.tron.rental_provider_fail_window_secs,
-                                self.cfg.tron.rental_provider_freeze_secs,
-                                self.cfg.tron.rental_provider_fail_threshold,
-                                &msg,
-                            )
-                            .await;
-                        if froze.unwrap_or(false) {
-                            self.telemetry.rental_provider_frozen(provider.name());
-                        }
-                    }
-                }
-                Ok(Err(err)) => {
-                    self.telemetry.rental_quote_ms(provider.name(), false, ms);
-                    let msg = format!("{err:#}");
-                    last_err = Some(format!("{}: {msg}", provider.name()));
-                    let froze = self
-                        .db
-                        .rental_provider_record_failure(
-                            provider.name(),
-                            self.cfg.tron.rental_provider_fail_window_secs,
-                            self.cfg.tron.rental_provider_freeze_secs,
-                            self.cfg.tron.rental_provider_fail_threshold,
-                            &msg,
-                        )
-                        .await;
-                    if froze.unwrap_or(false) {
-                        self.telemetry.rental_provider_frozen(provider.name());
-                    }
-                }
-                Err(_) => {
-                    self.telemetry.rental_quote_ms(provider.name(), false, ms);
-                    let msg = "timeout".to_string();
-                    last_err = Some(format!("{}: {msg}", provider.name()));
-                    let froze = self
-                        .db
-                        .rental_provider_record_failure(
-                            provider.name(),
-                            self.cfg.tron.rental_provider_fail_window_secs,
-                            self.cfg.tron.rental_provider_freeze_secs,
-                            self.cfg.tron.rental_provider_fail_threshold,
-                            &msg,
-                        )
-                        .await;
-                    if froze.unwrap_or(false) {
-                        self.telemetry.rental_provider_frozen(provider.name());
-                    }
-                }
+    /// Recomputes the intent id from the row's fields the way the pool derives it, so a buggy or
+    /// compromised indexer can't get us to claim an id that isn't the intent we'd fill.
+    async fn intent_id_matches(&self, pool: &HubPool, row: &PoolOpenIntentRow) -> Result<bool> {
+        let id = parse_b256(&row.id)?;
+        let ty = IntentType::from_i16(row.intent_type)?;
+        let specs = parse_hex_bytes(&row.intent_specs)?;
+        let token = parse_address(&row.escrow_token)?;
+        let amount = parse_u256_dec(&row.escrow_amount)?;
+        if let Ok(deadline) = u64::try_from(row.deadline) {
+            let maker_id = compute_intent_id(
+                parse_address(&row.creator)?,
+                deadline,
+                ty,
+                &specs,
+                parse_address(&row.refund_beneficiary)?,
+                token,
+                amount,
+            );
+            if maker_id == id {
+                return Ok(true);
             }
         }
 
-        best.ok_or_else(|| {
-            anyhow::anyhow!(
-                "{}",
-                last_err
-                    .unwrap_or_else(|| "no energy rental quote providers succeeded".to_string())
-            )
-        })
+        // Receiver-originated intents (always USDT transfers) hash the forwarder pull instead.
+        if ty != IntentType::UsdtTransfer {
+            return Ok(false);
+        }
+        let Some(p) = pool.indexer.fetch_receiver_intent_params(&row.id).await? else {
+            return Ok(false);
+        };
+        let receiver_token = parse_address(&p.receiver_token)?;
+        let receiver_id = compute_receiver_intent_id(
+            parse_address(&p.forwarder)?,
+            parse_address(&p.to_tron_evm)?,
+            parse_b256(&p.forward_salt)?,
+            receiver_token,
+            parse_u256_dec(&p.amount_param)?,
+        );
+        Ok(receiver_id == id && receiver_token == token)
     }
 
     pub(super) async fn should_attempt(
         &mut self,
+        pool: &HubPool,
         row: &PoolOpenIntentRow,
     ) -> Result<ShouldAttemptDecision> {
         let now = std::time::SystemTime::now()
@@ -188,20 +86,158 @@ impl Solver {
             .unwrap()
             .as_secs() as i64;
         let ty = IntentType::from_i16(row.intent_type)?;
-        let mut rental_quote: Option<RentalQuoteDecision> = None;
+        let mut rental_quote: Option<crate::tron_backend::EnergyRentalQuote> = None;
         let mut rental_cost_usd: f64 = 0.0;
         let mut delegate_resource_resell: bool = false;
 
-        // Pre-claim inventory check for TRX/USDT: if we can't fill (and can't consolidate
-        // within configured limits), skip before we spend the claim deposit.
+        // Checked first: a fresh intent is retried on a later tick, so don't spend RPCs on it yet.
+        if let Some(age) = self.policy.intent_too_fresh(row, now) {
+            let details = serde_json::json!({
+                "age_secs": age,
+                "min_age_secs": self.cfg.policy.min_intent_age_secs,
+            })
+            .to_string();
+            return self
+                .skip_intent(row, "too_fresh", Some(&details), "too_fresh")
+                .await;
+        }
+
+        match self.intent_id_matches(pool, row).await {
+            Ok(true) => {}
+            Ok(false) => {
+                tracing::warn!(
+                    pool_id = %pool.id(),
+                    id = %row.id,
+                    "indexer intent id doesn't match its fields; skipping"
+                );
+                return self
+                    .skip_intent(row, "intent_id_mismatch", None, "intent_id_mismatch")
+                    .await;
+            }
+            Err(err) => {
+                let details = format!("{err:#}");
+                return self
+                    .skip_intent(
+                        row,
+                        "intent_id_mismatch",
+                        Some(&details),
+                        "intent_id_mismatch",
+                    )
+                    .await;
+            }
+        }
+
+        // Bound capital at risk: each claim locks the claim deposit until settlement.
+        if let Some(cap) = self.cfg.policy.max_inflight_claim_deposit {
+            let deposit = pool.cfg.claim_deposit;
+            let inflight = self.db.count_inflight_deposits().await?;
+            let committed = u64::try_from(inflight).unwrap_or(0).saturating_mul(deposit);
+            if committed.saturating_add(deposit) > cap {
+                let details = serde_json::json!({
+                    "inflight_jobs": inflight,
+                    "committed": committed,
+                    "cap": cap,
+                })
+                .to_string();
+                return self
+                    .skip_intent(
+                        row,
+                        "deposit_budget_exhausted",
+                        Some(&details),
+                        "deposit_budget_exhausted",
+                    )
+                    .await;
+            }
+        }
+
+        // Every claim posts one deposit; don't queue claims the solver can't pay for.
+        if let Some(balance) = self.deposit_balances.get(pool.id()) {
+            let deposit = pool.cfg.claim_deposit;
+            if *balance < alloy::primitives::U256::from(deposit) {
+                let details = serde_json::json!({
+                    "budget": balance.to_string(),
+                    "claim_deposit": deposit,
+                })
+                .to_string();
+                return self
+                    .skip_intent(
+                        row,
+                        "insufficient_deposit_balance",
+                        Some(&details),
+                        "insufficient_deposit_balance",
+                    )
+                    .await;
+            }
+        }
+
+        // Tron address allow/deny lists (both EVM-hex and base58 config entries are normalized to
+        // the EVM form used in intent specs). Undecodable specs are rejected by policy below.
+        if let Ok(specs) = parse_hex_bytes(&row.intent_specs) {
+            let recipient = decode_tron_recipient(ty, &specs);
+            let contract = match ty {
+                IntentType::TriggerSmartContract => {
+                    decode_trigger_contract_and_selector(&specs).map(|(c, _)| c)
+                }
+                _ => None,
+            };
+            if let Some(reason) = self.policy.check_tron_targets(recipient, contract) {
+                let details = serde_json::json!({
+                    "recipient": recipient.map(|a| tron::TronAddress::from_evm(a).to_base58check()),
+                    "contract": contract.map(|a| tron::TronAddress::from_evm(a).to_base58check()),
+                })
+                .to_string();
+                return self.skip_intent(row, reason, Some(&details), reason).await;
+            }
+        }
+
+        if !self.policy.is_escrow_token_allowed(&row.escrow_token) {
+            let details = serde_json::json!({
+                "escrow_token": row.escrow_token,
+            })
+            .to_string();
+            return self
+                .skip_intent(
+                    row,
+                    "escrow_token_not_allowed",
+                    Some(&details),
+                    "escrow_token_not_allowed",
+                )
+                .await;
+        }
+
+        if !self
+            .policy
+            .is_refund_beneficiary_allowed(&row.refund_beneficiary)
+        {
+            let details = serde_json::json!({
+                "refund_beneficiary": row.refund_beneficiary,
+            })
+            .to_string();
+            return self
+                .skip_intent(
+                    row,
+                    "refund_beneficiary_blocked",
+                    Some(&details),
+                    "refund_beneficiary_blocked",
+                )
+                .await;
+        }
+
+        // Pre-claim inventory check for TRX/USDT (rental quote for resold DelegateResource ENERGY,
+        // delegatable stake otherwise): if we can't fill (and can't consolidate within configured
+        // limits), skip before we spend the claim deposit.
         let mut required_pre_txs: usize = 0;
         if self.cfg.tron.mode == TronMode::Grpc
-            && matches!(ty, IntentType::TrxTransfer | IntentType::UsdtTransfer)
+            && matches!(
+                ty,
+                IntentType::TrxTransfer | IntentType::UsdtTransfer | IntentType::DelegateResource
+            )
         {
             let specs = parse_hex_bytes(&row.intent_specs)?;
+            let intent_id = b256_to_bytes32(parse_b256(&row.id)?);
             match self
                 .tron
-                .can_fill_preclaim(self.hub.as_ref(), ty, &specs)
+                .can_fill_preclaim(pool.hub.as_ref(), &self.db, intent_id, ty, &specs)
                 .await
             {
                 Ok(inv) => {
@@ -212,12 +248,18 @@ impl Solver {
                             "required_pre_txs": inv.required_pre_txs,
                         })
                         .to_string();
+                        let metric_reason = match inv.reason {
+                            Some("no_rental_quote") => "no_rental_quote",
+                            Some("insufficient_delegatable") => "insufficient_delegatable",
+                            Some("key_daily_limit") => "key_daily_limit",
+                            _ => "inventory_insufficient",
+                        };
                         return self
                             .skip_intent(
                                 row,
                                 inv.reason.unwrap_or("inventory_insufficient"),
                                 Some(&details),
-                                "inventory_insufficient",
+                                metric_reason,
                             )
                             .await;
                     }
@@ -228,92 +270,97 @@ impl Solver {
             }
         }
 
-        let hub_cost_usd = self.estimate_hub_cost_usd().await?;
-        let tron_fee_usd_per_tx = self.estimate_tron_fee_usd(row.intent_type).await?;
-        let tron_fee_usd = tron_fee_usd_per_tx * (1.0 + required_pre_txs as f64);
+        let tx_size_bytes = match ty {
+            IntentType::TriggerSmartContract => parse_hex_bytes(&row.intent_specs)
+                .ok()
+                .and_then(|specs| estimate_trigger_tx_size_bytes(&specs)),
+            _ => None,
+        };
+        // The calldata fixes the tx size; a tx the node would refuse isn't worth a claim.
+        if let (Some(size), Some(max)) = (tx_size_bytes, self.cfg.tron.max_tx_size_bytes)
+            && size > max
+        {
+            let details = serde_json::json!({
+                "estimated_tx_size_bytes": size,
+                "max_tx_size_bytes": max,
+            })
+            .to_string();
+            return self
+                .skip_intent(row, "tx_too_large", Some(&details), "tx_too_large")
+                .await;
+        }
+
+        let hub_cost_usd = self.estimate_hub_cost_usd(pool).await?;
+        // Consolidation pre-txs are transfers of the same asset, so each is priced like the fill.
+        let tron_fee_usd_per_tx = self
+            .estimate_tron_fee_usd(row.intent_type, tx_size_bytes)
+            .await?;
+        let pre_tx_fee_usd = tron_fee_usd_per_tx * required_pre_txs as f64;
+        let tron_fee_usd = tron_fee_usd_per_tx + pre_tx_fee_usd;
 
-        // DelegateResource resell (ENERGY-only): quote rental providers before claim to ensure profitability.
+        // DelegateResource resell (ENERGY-only): price the rental quote (cached by the pre-claim
+        // check above) into profitability.
         if self.cfg.tron.mode == TronMode::Grpc
             && ty == IntentType::DelegateResource
             && self.cfg.tron.delegate_resource_resell_enabled
         {
             let specs = parse_hex_bytes(&row.intent_specs)?;
-            if let Ok(intent) = crate::tron_backend::DelegateResourceIntent::abi_decode(&specs) {
-                // Only resell ENERGY. Bandwidth/TRON_POWER use the solver's own capacity and are gated separately.
-                if intent.resource == 1 {
+            let intent_id = b256_to_bytes32(parse_b256(&row.id)?);
+            match self
+                .tron
+                .quote_energy_rental(&self.db, intent_id, &specs)
+                .await
+            {
+                Ok(Some(q)) => {
                     delegate_resource_resell = true;
-
-                    let receiver = tron::TronAddress::from_evm(intent.receiver);
-                    let balance_sun_u64 = u64::try_from(intent.balanceSun).unwrap_or(u64::MAX);
-                    let lock_period_blocks = u64::try_from(intent.lockPeriod).unwrap_or(u64::MAX);
-
-                    let duration_hours = duration_hours_for_lock_period_blocks(lock_period_blocks);
-                    let totals = self.tron.energy_stake_totals().await?;
-                    let amount_units = tron::resources::resource_units_for_min_trx_sun(
-                        balance_sun_u64,
-                        totals,
-                        self.cfg.tron.resell_energy_headroom_ppm,
-                    );
-
                     let need_profitability = self.cfg.policy.min_profit_usd > 0.0
                         || self.cfg.policy.require_priced_escrow;
-
-                    match self
-                        .quote_best_energy_rental(
-                            receiver,
-                            balance_sun_u64,
-                            lock_period_blocks,
-                            amount_units,
-                            duration_hours,
-                        )
-                        .await
-                    {
-                        Ok(q) => {
-                            if need_profitability {
-                                let trx_usd = match self.pricing.trx_usd().await {
-                                    Ok(v) => v,
-                                    Err(err) => {
-                                        tracing::warn!(err = %err, "trx_usd unavailable; skipping rental quote");
-                                        return self
-                                            .skip_intent(
-                                                row,
-                                                "rental_quote_no_price",
-                                                None,
-                                                "rental_quote_no_price",
-                                            )
-                                            .await;
-                                    }
-                                };
-                                rental_cost_usd = q.cost_trx * trx_usd;
-                            }
-                            rental_quote = Some(q);
-                        }
-                        Err(err) => {
-                            if need_profitability {
-                                tracing::warn!(
-                                    err = %err,
-                                    "energy rental quote failed; skipping intent"
-                                );
+                    if need_profitability {
+                        let trx_usd = match self.pricing.trx_usd().await {
+                            Ok(v) => v,
+                            Err(err) => {
+                                tracing::warn!(err = %err, "trx_usd unavailable; skipping rental quote");
                                 return self
                                     .skip_intent(
                                         row,
-                                        "rental_quote_failed",
-                                        Some(&format!("{err:#}")),
-                                        "rental_quote_failed",
+                                        "rental_quote_no_price",
+                                        None,
+                                        "rental_quote_no_price",
                                     )
                                     .await;
                             }
-                        }
+                        };
+                        rental_cost_usd = q.cost_trx * trx_usd;
                     }
+                    rental_quote = Some(q);
+                }
+                // Not an ENERGY delegation: filled from our own stake below.
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!(err = %err, "energy rental quote failed; skipping intent");
+                    return self
+                        .skip_intent(
+                            row,
+                            "no_rental_quote",
+                            Some(&format!("{err:#}")),
+                            "no_rental_quote",
+                        )
+                        .await;
                 }
             }
         }
 
+        let deadline_now = match self.cfg.policy.deadline_kind {
+            DeadlineKind::Timestamp => now,
+            DeadlineKind::Block => {
+                i64::try_from(pool.hub.hub_block_number().await?).unwrap_or(i64::MAX)
+            }
+        };
         let eval = self
             .policy
             .evaluate_open_intent(
                 row,
-                now,
+                deadline_now,
                 &mut self.pricing,
                 hub_cost_usd,
                 tron_fee_usd + rental_cost_usd,
@@ -321,12 +368,36 @@ impl Solver {
             )
             .await?;
         if !eval.allowed {
-            let db_reason = eval.reason.as_deref().unwrap_or("policy_reject");
+            let mut db_reason = eval.reason.as_deref().unwrap_or("policy_reject");
+            let mut metric_reason = "policy_reject";
+            // Tell fragmented-balance losses apart: the fill alone would have paid.
+            if matches!(db_reason, "unprofitable" | "below_min_profit")
+                && pre_tx_fee_usd > 0.0
+                && eval.profit.is_some_and(|p| {
+                    p.clears_floor_without(pre_tx_fee_usd, self.cfg.policy.min_profit_usd)
+                })
+            {
+                db_reason = "unprofitable_with_consolidation";
+                metric_reason = "unprofitable_with_consolidation";
+            }
             if let Some(reason) = eval.reason.as_deref() {
                 tracing::debug!(id = %row.id, intent_type = row.intent_type, reason, "skip intent");
             }
+            let details = eval.profit.map(|p| {
+                serde_json::json!({
+                    "revenue_usd": p.revenue_usd,
+                    "cost_usd": p.cost_usd,
+                    "hub_cost_usd": p.hub_cost_usd,
+                    "tron_fee_usd": p.tron_fee_usd,
+                    "required_pre_txs": required_pre_txs,
+                    "pre_tx_fee_usd": pre_tx_fee_usd,
+                    "profit_usd": p.profit_usd,
+                    "min_profit_usd": self.cfg.policy.min_profit_usd,
+                })
+                .to_string()
+            });
             return self
-                .skip_intent(row, db_reason, None, "policy_reject")
+                .skip_intent(row, db_reason, details.as_deref(), metric_reason)
                 .await;
         }
 
@@ -339,8 +410,27 @@ impl Solver {
                 .await;
         }
 
+        let estimate = CostEstimate {
+            required_pre_txs,
+            hub_cost_usd,
+            tron_fee_usd,
+            rental_cost_usd,
+            profit_usd: eval.profit.map(|p| p.profit_usd),
+        };
+
+        // Per-selector energy budget (TriggerSmartContract only); enforced via emulation below.
+        let selector_max_energy = match ty {
+            IntentType::TriggerSmartContract => parse_hex_bytes(&row.intent_specs)
+                .ok()
+                .and_then(|specs| decode_trigger_contract_and_selector(&specs))
+                .and_then(|(_, sel)| sel)
+                .and_then(|sel| self.cfg.policy.trigger_selector_budgets.get(&sel))
+                .and_then(|b| b.max_energy),
+            _ => None,
+        };
+
         // Optional Tron emulation gating: avoid claiming intents we know will revert.
-        if self.cfg.tron.emulation_enabled
+        if (self.cfg.tron.emulation_enabled || selector_max_energy.is_some())
             && self.cfg.tron.mode == TronMode::Grpc
             && matches!(
                 ty,
@@ -350,45 +440,123 @@ impl Solver {
             let specs = parse_hex_bytes(&row.intent_specs)?;
             let emu = self
                 .tron
-                .precheck_emulation(self.hub.as_ref(), ty, &specs)
+                .precheck_emulation(pool.hub.as_ref(), ty, &specs, selector_max_energy)
                 .await;
+            let (contract, selector) = match ty {
+                IntentType::TriggerSmartContract => decode_trigger_contract_and_selector(&specs)
+                    .map(|(c, s)| (Some(c), s))
+                    .unwrap_or((None, None)),
+                _ => (None, None),
+            };
+            let db_reason = if emu.over_budget {
+                Some("selector_over_budget")
+            } else if emu.needs_approve {
+                Some("trigger_spend_needs_approve")
+            } else if emu.reason.is_some() {
+                Some("tron_emulation_revert")
+            } else if !emu.ok {
+                Some("tron_emulation_failed")
+            } else {
+                None
+            };
             if let Ok(id) = parse_b256(&row.id) {
-                let (contract, selector) = match ty {
-                    IntentType::TriggerSmartContract => {
-                        decode_trigger_contract_and_selector(&specs)
-                            .map(|(c, s)| (Some(c), s))
-                            .unwrap_or((None, None))
-                    }
-                    _ => (None, None),
-                };
                 let contract_bytes = contract.map(|c| c.as_slice().to_vec());
                 let selector_bytes = selector.map(|s| s.to_vec());
                 let _ = self
                     .db
                     .upsert_intent_emulation(
+                        pool.id(),
                         b256_to_bytes32(id),
                         row.intent_type,
                         emu.ok,
-                        emu.reason.as_deref(),
+                        db_reason,
                         contract_bytes.as_deref(),
                         selector_bytes.as_deref(),
                     )
                     .await;
             }
+            if emu.over_budget {
+                let details = serde_json::json!({
+                    "energy_used": emu.energy_used,
+                    "max_energy": selector_max_energy,
+                    "selector": selector.map(|s| format!("0x{}", hex::encode(s))),
+                })
+                .to_string();
+                return self
+                    .skip_intent(
+                        row,
+                        "selector_over_budget",
+                        Some(&details),
+                        "selector_over_budget",
+                    )
+                    .await;
+            }
             if !emu.ok {
-                let db_reason = emu.reason.as_deref().unwrap_or("tron_emulation_failed");
+                let db_reason = db_reason.unwrap_or("tron_emulation_failed");
+                let details = emu
+                    .reason
+                    .as_ref()
+                    .and_then(|r| serde_json::to_string(r).ok());
+                // Deterministic reverts (bad calldata, custom errors, panics) will keep failing for
+                // every intent hitting this call; breaker it. State-dependent ones just skip.
+                if let (Some(revert), Some(contract)) = (emu.reason.as_ref(), contract)
+                    && revert.is_deterministic()
+                {
+                    let _ = self
+                        .db
+                        .breaker_record_failure(
+                            contract,
+                            selector,
+                            details.as_deref().unwrap_or(db_reason),
+                        )
+                        .await;
+                }
                 tracing::debug!(
                     id = %row.id,
                     intent_type = row.intent_type,
                     reason = db_reason,
+                    revert = ?emu.reason,
                     "skip intent (tron emulation)"
                 );
                 return self
-                    .skip_intent(row, db_reason, None, "tron_emulation_failed")
+                    .skip_intent(row, db_reason, details.as_deref(), "tron_emulation_failed")
                     .await;
             }
         }
 
+        // Optional hub dry run: catches pool-side reverts (paused pool, unpayable deposit, reader
+        // mismatch) that Tron emulation can't see. RPC failures don't block the claim.
+        if pool.cfg.simulate_before_claim {
+            let id = parse_b256(&row.id)?;
+            let deposit = alloy::primitives::U256::from(pool.cfg.claim_deposit);
+            match pool.hub.simulate_claim_and_prove(id, deposit).await {
+                Ok(None) => {}
+                Ok(Some(revert)) => {
+                    tracing::debug!(
+                        id = %row.id,
+                        intent_type = row.intent_type,
+                        revert = %revert,
+                        "skip intent (hub simulation)"
+                    );
+                    let details = serde_json::json!({ "revert": revert }).to_string();
+                    return self
+                        .skip_intent(
+                            row,
+                            "simulation_failed",
+                            Some(&details),
+                            "simulation_failed",
+                        )
+                        .await;
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        err = %format!("{err:#}"),
+                        "hub claim simulation failed; continuing"
+                    );
+                }
+            }
+        }
+
         // Best-effort capacity check for resource delegation: avoid claiming intents we cannot fill
         // because we don't have enough staked TRX for the requested resource.
         if self.cfg.tron.mode == TronMode::Grpc
@@ -413,6 +581,7 @@ impl Solver {
                             ok: true,
                             rental_quote,
                             skip_reason: None,
+                            estimate,
                         });
                     }
                 };
@@ -428,6 +597,7 @@ impl Solver {
                             ok: true,
                             rental_quote,
                             skip_reason: None,
+                            estimate,
                         });
                     }
                 };
@@ -474,6 +644,7 @@ impl Solver {
             ok: true,
             rental_quote,
             skip_reason: None,
+            estimate,
         })
     }
 }
@@ -1,9 +1,16 @@
 use super::IntentType;
 use crate::{
-    config::AppConfig, db::SolverDb, hub::HubClient, indexer::IndexerClient,
-    metrics::SolverTelemetry, tron_backend::TronBackend,
+    config::{AppConfig, HubConfig, IndexerConfig},
+    db::SolverDb,
+    hub::HubClient,
+    indexer::IndexerClient,
+    metrics::SolverTelemetry,
+    prove_relayer::ProveRelayerClient,
+    tron_backend::TronBackend,
+    webhook::FatalWebhook,
 };
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::Semaphore;
 
 #[derive(Clone)]
@@ -12,12 +19,74 @@ pub(super) struct JobCtx {
     pub(super) db: SolverDb,
     pub(super) indexer: IndexerClient,
     pub(super) hub: Arc<HubClient>,
+    pub(super) prove_relayer: Option<ProveRelayerClient>,
     pub(super) tron: TronBackend,
     pub(super) instance_id: String,
     pub(super) hub_userop_submit_sem: Arc<Semaphore>,
     pub(super) tron_broadcast_sem: Arc<Semaphore>,
+    pub(super) tron_proof_sem: Arc<Semaphore>,
     pub(super) job_type_sems: Arc<JobTypeSems>,
+    pub(super) fatal_webhook: Option<FatalWebhook>,
     pub(super) telemetry: SolverTelemetry,
+    pub(super) pre_tx_budget: Arc<PreTxBudget>,
+    /// TRX/USD at the start of the tick; only fetched when rental orders have USD caps.
+    pub(super) trx_usd: Option<f64>,
+}
+
+/// Clients for one `UntronIntents` deployment (see [`crate::config::HubPoolConfig`]).
+#[derive(Clone)]
+pub(super) struct HubPool {
+    pub(super) cfg: HubConfig,
+    pub(super) indexer_cfg: IndexerConfig,
+    pub(super) hub: Arc<HubClient>,
+    pub(super) indexer: IndexerClient,
+    /// Set iff the pool proves through a relayer (`ProveMode::DelegatedRelayer`).
+    pub(super) prove_relayer: Option<ProveRelayerClient>,
+}
+
+impl HubPool {
+    pub(super) fn id(&self) -> &str {
+        &self.cfg.pool_id
+    }
+}
+
+impl JobCtx {
+    /// Points the context at `pool`, so claims, proves and indexer lookups for a job go to the
+    /// pool it was discovered on.
+    pub(super) fn for_pool(mut self, pool: &HubPool) -> Self {
+        self.cfg.hub = pool.cfg.clone();
+        self.cfg.indexer = pool.indexer_cfg.clone();
+        self.hub = pool.hub.clone();
+        self.indexer = pool.indexer.clone();
+        self.prove_relayer = pool.prove_relayer.clone();
+        self
+    }
+}
+
+/// Pre-txs the current tick may still broadcast, shared by every job in it.
+pub(super) struct PreTxBudget {
+    remaining: Option<AtomicU64>,
+}
+
+impl PreTxBudget {
+    pub(super) fn new(limit: Option<u64>) -> Self {
+        Self {
+            remaining: limit.map(AtomicU64::new),
+        }
+    }
+
+    /// Takes `count` pre-txs from the budget at once; `false` (taking nothing) if fewer are left, so a
+    /// plan never gets stuck halfway through its pre-txs.
+    pub(super) fn try_take(&self, count: u64) -> bool {
+        let Some(remaining) = &self.remaining else {
+            return true;
+        };
+        remaining
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                n.checked_sub(count)
+            })
+            .is_ok()
+    }
 }
 
 pub(super) struct JobTypeSems {
@@ -37,3 +106,29 @@ impl JobTypeSems {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pre_tx_budget_caps_and_unlimited() {
+        let budget = PreTxBudget::new(Some(2));
+        assert!(budget.try_take(1));
+        assert!(budget.try_take(1));
+        assert!(!budget.try_take(1));
+
+        let unlimited = PreTxBudget::new(None);
+        assert!((0..100).all(|_| unlimited.try_take(1)));
+    }
+
+    #[test]
+    fn pre_tx_budget_reserves_whole_plans() {
+        let budget = PreTxBudget::new(Some(3));
+        assert!(budget.try_take(2));
+        // Not enough left for a 2-pre-tx plan: nothing is taken.
+        assert!(!budget.try_take(2));
+        assert!(budget.try_take(1));
+        assert!(!budget.try_take(1));
+    }
+}
@@ -1,12 +1,59 @@
-use super::{JobCtx, job::process_job};
-use crate::{db::SolverJob, types::IntentType};
+use super::{HubPool, JobCtx, hub_flow::submit_prove_batches, job::process_job};
+use crate::{
+    db::SolverJob,
+    types::{IntentType, JobState},
+};
+use alloy::primitives::B256;
 use tokio::task::JoinSet;
+use tracing::Instrument;
+
+/// Runs `jobs` concurrently against their own pools; jobs whose pool is not in `pools` are skipped.
+pub(super) async fn execute_leased_jobs(ctx: JobCtx, pools: &[HubPool], jobs: Vec<SolverJob>) {
+    // Proved jobs first share batched prove userops where enabled; each job then runs as usual
+    // and finds its prove already submitted.
+    for pool in pools.iter().filter(|p| p.cfg.prove_batch_max > 1) {
+        let proof_built: Vec<SolverJob> = jobs
+            .iter()
+            .filter(|j| j.pool_id == pool.id() && j.state == JobState::ProofBuilt.as_db_str())
+            .cloned()
+            .collect();
+        if proof_built.len() < 2 {
+            continue;
+        }
+        let ctx = ctx.clone().for_pool(pool);
+        if let Err(err) = submit_prove_batches(&ctx, &proof_built).await {
+            tracing::warn!(
+                pool_id = %pool.id(),
+                err = %format!("{err:#}"),
+                "prove batching failed"
+            );
+        }
+    }
 
-pub(super) async fn execute_leased_jobs(ctx: JobCtx, jobs: Vec<SolverJob>) {
     let mut set = JoinSet::new();
     for job in jobs {
-        let ctx = ctx.clone();
-        set.spawn(async move {
+        let Some(pool) = pools.iter().find(|p| p.id() == job.pool_id) else {
+            // Pool not configured, or its indexer is unhealthy/lagging this tick. The lease lapses
+            // and the job is picked up again later.
+            tracing::warn!(
+                job_id = job.job_id,
+                pool_id = %job.pool_id,
+                "job's pool is unavailable; skipping"
+            );
+            continue;
+        };
+        let ctx = ctx.clone().for_pool(pool);
+        // Everything logged while processing the job (including the failure below) carries these
+        // fields, so one intent can be followed across ticks and state transitions.
+        let span = tracing::info_span!(
+            "job",
+            job_id = job.job_id,
+            intent_id = %B256::from_slice(&job.intent_id),
+            state = %job.state,
+            intent_type = job.intent_type,
+            pool_id = %job.pool_id,
+        );
+        let task = async move {
             let intent_type = job.intent_type;
             let telemetry = ctx.telemetry.clone();
             let ty = match IntentType::from_i16(job.intent_type) {
@@ -26,9 +73,13 @@ pub(super) async fn execute_leased_jobs(ctx: JobCtx, jobs: Vec<SolverJob>) {
             if let Err(err) = process_job(ctx, job).await {
                 let reason = classify_job_error(&err);
                 telemetry.job_failure_reason(intent_type, reason);
+                if chain_contains(&err, "[lease_stolen]") {
+                    telemetry.lease_steal_detected(intent_type);
+                }
                 tracing::warn!(err = %err, "job failed");
             }
-        });
+        };
+        set.spawn(task.instrument(span));
     }
     while let Some(res) = set.join_next().await {
         if let Err(err) = res {
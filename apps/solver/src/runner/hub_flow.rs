@@ -1,14 +1,16 @@
+use super::retry::ErrorClass;
+use super::settlement::SettlementAction;
 use super::{
-    INTENT_CLAIM_DEPOSIT, JobCtx, SolverJob, b256_to_bytes32, ensure_delegate_reservation,
-    finalize_after_prove, retry,
+    JobCtx, SolverJob, b256_to_bytes32, ensure_delegate_reservation, finalize_after_prove, lease,
+    looks_like_stale_tron_proof, retry,
 };
 use crate::{
-    config::{HubTxMode, TronMode},
-    db::{HubUserOpKind, HubUserOpRow},
-    hub::TronProof,
-    types::{IntentType, JobState},
+    config::{HubTxMode, ProveMode, TronMode},
+    db::{HubUserOpKind, HubUserOpRow, TronProofCorruptedError, TronProofRow},
+    hub::{IUntronIntents, TronProof},
+    types::{IntentType, JobState, parse_address},
 };
-use alloy::primitives::{B256, U256};
+use alloy::primitives::{Address, B256, U256};
 use alloy::rpc::types::eth::erc4337::PackedUserOperation;
 use alloy::sol_types::SolCall;
 use anyhow::{Context, Result};
@@ -55,6 +57,143 @@ async fn refresh_claim_window_expires_at(ctx: &JobCtx, job: &SolverJob, id: B256
     Ok(())
 }
 
+/// Whether a claimed-but-unfilled job has reached `SOLVER_JOB_ABANDON_AFTER_SECS` since its claim.
+/// Never true before the pool's fill window ends, since `unclaimIntent` reverts until then.
+pub(super) fn abandon_due(ctx: &JobCtx, job: &SolverJob) -> bool {
+    let abandon_after = ctx.cfg.jobs.abandon_after_secs;
+    if abandon_after == 0 {
+        return false;
+    }
+    let Some(window_expires_at) = job.claim_window_expires_at_unix else {
+        return false;
+    };
+    let claimed_at = window_expires_at.saturating_sub(CLAIM_WINDOW_SECS);
+    let abandon_at = claimed_at
+        .saturating_add(i64::try_from(abandon_after).unwrap_or(i64::MAX))
+        .max(window_expires_at);
+    now_unix_secs() >= abandon_at
+}
+
+/// Releases the hub claim of a stalled job (see [`unclaim_job`]).
+pub(super) async fn unclaim_stalled_job(
+    ctx: &JobCtx,
+    job: &SolverJob,
+    id: B256,
+    from_state: JobState,
+) -> Result<()> {
+    let reason = format!(
+        "abandoned: no fill {}s after claim",
+        ctx.cfg.jobs.abandon_after_secs
+    );
+    unclaim_job(ctx, job, id, from_state, &reason).await
+}
+
+/// Releases the job's hub claim via `unclaimIntent` and moves it to `unclaimed` with `reason`,
+/// recording the deposit the pool paid back. `unclaimIntent` reverts while the claim window is
+/// open, so until then the job is just deferred. Callers must have ruled out that a fill for
+/// this job can still land on Tron.
+pub(super) async fn unclaim_job(
+    ctx: &JobCtx,
+    job: &SolverJob,
+    id: B256,
+    from_state: JobState,
+    reason: &str,
+) -> Result<()> {
+    let from = from_state.as_db_str();
+    let window_left = job
+        .claim_window_expires_at_unix
+        .map_or(0, |t| t.saturating_sub(now_unix_secs()));
+    if window_left > 0 {
+        tracing::warn!(
+            id = %id,
+            reason,
+            wait_secs = window_left,
+            "waiting for the claim window to end before unclaiming"
+        );
+        return ctx
+            .db
+            .defer_job(
+                job.job_id,
+                &ctx.instance_id,
+                std::time::Duration::from_secs(u64::try_from(window_left).unwrap_or(0)),
+            )
+            .await;
+    }
+
+    let (solver, _) = ctx.hub.intent_solver_claimed_at(id).await?;
+    if solver != ctx.hub.solver_address() {
+        // Someone else already cleared the claim (or the intent closed); nothing left to recover.
+        tracing::warn!(id = %id, solver = ?solver, "claim already released on-chain");
+        ctx.db
+            .record_unclaimed(
+                job.job_id,
+                &ctx.instance_id,
+                from_state,
+                None,
+                None,
+                None,
+                reason,
+            )
+            .await?;
+        ctx.telemetry
+            .job_state_transition(job.intent_type, from, "unclaimed");
+        return Ok(());
+    }
+
+    anyhow::ensure!(!ctx.cfg.dry_run, "dry run: refusing to unclaim intent");
+    let receipt =
+        match lease::with_lease_heartbeat(ctx, job.job_id, ctx.hub.unclaim_intent(id)).await {
+            Ok(receipt) => receipt,
+            Err(err) => {
+                let msg = format!("unclaim_intent failed: {err:#}");
+                ctx.db
+                    .record_retryable_error(
+                        job.job_id,
+                        &ctx.instance_id,
+                        &msg,
+                        retry::retry_delay_for(ctx, job.attempts, ErrorClass::of_hub(&msg)),
+                    )
+                    .await?;
+                return Ok(());
+            }
+        };
+    let Some((recovered, funded)) = ctx.hub.unclaim_recovered_deposit(&receipt) else {
+        ctx.db
+            .record_retryable_error(
+                job.job_id,
+                &ctx.instance_id,
+                "unclaim tx emitted no IntentUnclaimed event",
+                retry::retry_delay(ctx, job.attempts),
+            )
+            .await?;
+        return Ok(());
+    };
+
+    ctx.db
+        .record_unclaimed(
+            job.job_id,
+            &ctx.instance_id,
+            from_state,
+            Some(b256_to_bytes32(receipt.transaction_hash)),
+            Some(funded),
+            Some(recovered),
+            reason,
+        )
+        .await?;
+    ctx.telemetry
+        .job_state_transition(job.intent_type, from, "unclaimed");
+    tracing::warn!(
+        id = %id,
+        from_state = from,
+        funded,
+        recovered_deposit = %recovered,
+        tx = %receipt.transaction_hash,
+        reason,
+        "unclaimed intent"
+    );
+    Ok(())
+}
+
 async fn enforce_claim_submission_preconditions(
     ctx: &JobCtx,
     job: &SolverJob,
@@ -101,7 +240,7 @@ async fn enforce_claim_submission_preconditions(
                 job.job_id,
                 &ctx.instance_id,
                 &msg,
-                retry::retry_delay(job.attempts),
+                retry::retry_delay(ctx, job.attempts),
             )
             .await?;
         return Ok(true);
@@ -132,20 +271,7 @@ async fn delete_stale_prepared_userop_if_needed(
 }
 
 fn should_rebuild_prepared_userop_on_submit_error(msg: &str) -> bool {
-    let m = msg.to_ascii_lowercase();
-    m.contains("aa25 invalid account nonce")
-        || m.contains("invalid account nonce")
-        || m.contains("nonce too low")
-        || m.contains("nonce too high")
-        || m.contains("replacement transaction underpriced")
-        || m.contains("underpriced")
-        || m.contains("fee too low")
-        || m.contains("max fee per gas less than block base fee")
-        || m.contains("maxfeepergas")
-        || m.contains("maxpriorityfeepergas")
-        || (m.contains("aa3") && m.contains("paymaster"))
-        || (m.contains("paymaster")
-            && (m.contains("expired") || m.contains("deposit") || m.contains("stake")))
+    aa::classify_bundler_error(msg).rebuild_userop()
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -178,6 +304,7 @@ where
         userop
     };
 
+    anyhow::ensure!(!ctx.cfg.dry_run, "dry run: refusing to send hub userop");
     let started = Instant::now();
     match ctx.hub.safe4337_send_userop(userop).await {
         Ok(userop_hash) => {
@@ -187,36 +314,54 @@ where
             ctx.db
                 .record_hub_userop_submitted(job.job_id, &ctx.instance_id, kind, &userop_hash)
                 .await?;
+            ctx.db
+                .record_hub_structural_outcome(job.job_id, &ctx.instance_id, false)
+                .await?;
             Ok(false)
         }
         Err(err) => {
-            ctx.telemetry.hub_userop_err();
+            let msg = err.to_string();
+            let class = aa::classify_bundler_error(&msg);
+            ctx.telemetry.hub_userop_err(class.as_str());
             ctx.telemetry
                 .hub_submit_ms(metric_name, false, started.elapsed().as_millis() as u64);
-            let msg = err.to_string();
-            if should_rebuild_prepared_userop_on_submit_error(&msg) {
+            if kind == HubUserOpKind::Prove && rebuild_stale_proof(ctx, job, &msg).await? {
+                return Ok(true);
+            }
+            if class.rebuild_userop() {
                 ctx.db
                     .delete_hub_userop_prepared(job.job_id, &ctx.instance_id, kind)
                     .await
                     .ok();
             }
+            // Only an unbroken run of validation rejections is fatal; `job.attempts` also counts
+            // unrelated retries from earlier stages.
+            let streak = ctx
+                .db
+                .record_hub_structural_outcome(job.job_id, &ctx.instance_id, class.is_structural())
+                .await?;
+            let fatal_after = ctx.cfg.jobs.hub_userop_validation_fatal_attempts;
+            if class.is_structural()
+                && fatal_after > 0
+                && u64::try_from(streak).unwrap_or(0) >= fatal_after
+            {
+                let msg =
+                    format!("hub userop rejected in validation {streak} times in a row: {msg}");
+                retry::record_fatal(ctx, job, &msg).await?;
+                return Ok(true);
+            }
+            let delay_class = if class.is_structural() {
+                ErrorClass::Structural
+            } else {
+                ErrorClass::of_hub(&msg)
+            };
+            let delay = retry::retry_delay_for(ctx, job.attempts, delay_class);
             ctx.db
-                .record_hub_userop_retryable_error(
-                    job.job_id,
-                    &ctx.instance_id,
-                    kind,
-                    &msg,
-                    retry::retry_delay(job.attempts),
-                )
+                .record_hub_userop_retryable_error(job.job_id, &ctx.instance_id, kind, &msg, delay)
                 .await
                 .ok();
             ctx.db
-                .record_retryable_error(
-                    job.job_id,
-                    &ctx.instance_id,
-                    &msg,
-                    retry::retry_delay(job.attempts),
-                )
+                .record_retryable_error(job.job_id, &ctx.instance_id, &msg, delay)
                 .await?;
             Ok(true)
         }
@@ -268,23 +413,13 @@ async fn record_userop_poll_retryable(
     kind: HubUserOpKind,
     msg: &str,
 ) -> Result<()> {
+    let delay = retry::retry_delay_for(ctx, job.attempts, ErrorClass::of_hub(msg));
     ctx.db
-        .record_hub_userop_retryable_error(
-            job.job_id,
-            &ctx.instance_id,
-            kind,
-            msg,
-            retry::retry_delay(job.attempts),
-        )
+        .record_hub_userop_retryable_error(job.job_id, &ctx.instance_id, kind, msg, delay)
         .await
         .ok();
     ctx.db
-        .record_retryable_error(
-            job.job_id,
-            &ctx.instance_id,
-            msg,
-            retry::retry_delay(job.attempts),
-        )
+        .record_retryable_error(job.job_id, &ctx.instance_id, msg, delay)
         .await
 }
 
@@ -313,7 +448,7 @@ async fn reconcile_included_claim_userop(
                 job.job_id,
                 &ctx.instance_id,
                 "claim userop included without tx_hash",
-                retry::retry_delay(job.attempts),
+                retry::retry_delay(ctx, job.attempts),
             )
             .await?;
         return Ok(());
@@ -335,6 +470,12 @@ async fn reconcile_included_prove_userop(
 ) -> Result<()> {
     if !row.success.unwrap_or(false) {
         let msg = included_userop_failure_message("prove userop failed", row);
+        if row.batch_size > 1 {
+            return retry_prove_outside_batch(ctx, job, &msg).await;
+        }
+        if rebuild_stale_proof(ctx, job, &msg).await? {
+            return Ok(());
+        }
         retry::record_fatal(ctx, job, &msg).await?;
         return Ok(());
     }
@@ -345,7 +486,7 @@ async fn reconcile_included_prove_userop(
                 job.job_id,
                 &ctx.instance_id,
                 "prove userop included without tx_hash",
-                retry::retry_delay(job.attempts),
+                retry::retry_delay(ctx, job.attempts),
             )
             .await?;
         return Ok(());
@@ -395,18 +536,34 @@ pub(super) async fn process_ready_state(
                     job.job_id,
                     &ctx.instance_id,
                     &msg,
-                    retry::retry_delay(job.attempts),
+                    retry::retry_delay(ctx, job.attempts),
                 )
                 .await?;
             return Ok(());
         }
     };
+    if let Err(err) = ctx
+        .hub
+        .ensure_deposit_funded(usdt, U256::from(ctx.cfg.hub.claim_deposit))
+        .await
+    {
+        let msg = format!("ensure_deposit_funded failed: {err:#}");
+        ctx.db
+            .record_retryable_error(
+                job.job_id,
+                &ctx.instance_id,
+                &msg,
+                retry::retry_delay(ctx, job.attempts),
+            )
+            .await?;
+        return Ok(());
+    }
     if let Err(err) = ctx
         .hub
         .ensure_erc20_allowance(
             usdt,
             ctx.hub.pool_address(),
-            U256::from(INTENT_CLAIM_DEPOSIT),
+            U256::from(ctx.cfg.hub.claim_deposit),
         )
         .await
     {
@@ -416,7 +573,7 @@ pub(super) async fn process_ready_state(
                 job.job_id,
                 &ctx.instance_id,
                 &msg,
-                retry::retry_delay(job.attempts),
+                retry::retry_delay(ctx, job.attempts),
             )
             .await?;
         return Ok(());
@@ -445,13 +602,15 @@ pub(super) async fn process_ready_state(
                         job.job_id,
                         &ctx.instance_id,
                         &msg,
-                        retry::retry_delay(job.attempts),
+                        retry::retry_delay(ctx, job.attempts),
                     )
                     .await?;
                 return Ok(());
             }
 
-            match ctx.hub.claim_intent(id).await {
+            anyhow::ensure!(!ctx.cfg.dry_run, "dry run: refusing to claim intent");
+            // With HUB_MIN_CONFIRMATIONS > 1 this waits out several blocks; keep the lease alive.
+            match lease::with_lease_heartbeat(ctx, job.job_id, ctx.hub.claim_intent(id)).await {
                 Ok(receipt) => {
                     ctx.db
                         .record_claim(
@@ -486,12 +645,19 @@ pub(super) async fn process_ready_state(
                                 return Ok(());
                             }
                             Ok(_) => {
+                                ctx.telemetry.claim_race_lost(job.intent_type);
                                 ctx.telemetry.job_state_transition(
                                     job.intent_type,
                                     "ready",
-                                    "failed_fatal",
+                                    "lost_race",
                                 );
-                                retry::record_fatal(ctx, job, &msg).await?;
+                                ctx.db
+                                    .record_lost_race(job.job_id, &ctx.instance_id, &msg)
+                                    .await?;
+                                let _ = ctx
+                                    .db
+                                    .release_delegate_reservation_for_job(job.job_id)
+                                    .await;
                                 return Ok(());
                             }
                             Err(err) => {
@@ -502,7 +668,7 @@ pub(super) async fn process_ready_state(
                                         job.job_id,
                                         &ctx.instance_id,
                                         &reconcile_msg,
-                                        retry::retry_delay(job.attempts),
+                                        retry::retry_delay(ctx, job.attempts),
                                     )
                                     .await?;
                                 return Ok(());
@@ -514,7 +680,7 @@ pub(super) async fn process_ready_state(
                             job.job_id,
                             &ctx.instance_id,
                             &msg,
-                            retry::retry_delay(job.attempts),
+                            retry::retry_delay_for(ctx, job.attempts, ErrorClass::of_hub(&msg)),
                         )
                         .await?;
                     Ok(())
@@ -646,80 +812,108 @@ pub(super) async fn process_proof_built_state(
                 job.job_id,
                 &ctx.instance_id,
                 "missing tron_txid",
-                retry::retry_delay(job.attempts),
+                retry::retry_delay(ctx, job.attempts),
             )
             .await?;
         return Ok(());
     };
-    let proof = ctx.db.load_tron_proof(txid).await?;
-    let tron = TronProof {
-        blocks: std::array::from_fn(|i| proof.blocks[i].clone()),
-        encoded_tx: proof.encoded_tx,
-        proof: proof
-            .proof
-            .into_iter()
-            .map(|b| B256::from_slice(&b))
-            .collect(),
-        index: crate::types::parse_u256_dec(&proof.index_dec).unwrap_or(U256::ZERO),
+    let proof = match ctx.db.load_tron_proof(txid).await {
+        Ok(p) => p,
+        Err(err) if err.downcast_ref::<TronProofCorruptedError>().is_some() => {
+            tracing::warn!(id = %id, err = %err, "discarding corrupted tron proof; rebuilding");
+            ctx.db
+                .discard_tron_proof_for_rebuild(
+                    job.job_id,
+                    &ctx.instance_id,
+                    txid,
+                    &format!("{err:#}"),
+                )
+                .await?;
+            ctx.telemetry.proof_rebuild(job.intent_type, "corrupted");
+            ctx.telemetry
+                .job_state_transition(job.intent_type, "proof_built", "tron_sent");
+            return Ok(());
+        }
+        Err(err) => return Err(err),
     };
+    let tron = tron_proof_from_row(proof);
+    if ctx.cfg.hub.prove_mode == ProveMode::DelegatedRelayer {
+        return prove_via_relayer(ctx, job, id, &tron).await;
+    }
     tracing::info!(id = %id, "submitting proveIntentFill");
     match ctx.cfg.hub.tx_mode {
-        HubTxMode::Eoa => match ctx.hub.prove_intent_fill(id, tron).await {
-            Ok(receipt) => {
-                ctx.db
-                    .record_prove(
-                        job.job_id,
-                        &ctx.instance_id,
-                        b256_to_bytes32(receipt.transaction_hash),
-                    )
-                    .await?;
-                ctx.telemetry
-                    .job_state_transition(job.intent_type, "proof_built", "proved");
-                let _ = finalize_after_prove(ctx, job).await;
-                Ok(())
-            }
-            Err(err) => {
-                let msg = err.to_string();
-                match ctx.hub.intent_status(id).await {
-                    Ok(status) if status.solved => {
-                        ctx.db
-                            .record_job_state(job.job_id, &ctx.instance_id, JobState::Proved)
-                            .await?;
-                        ctx.telemetry.job_state_transition(
-                            job.intent_type,
-                            "proof_built",
-                            "proved",
-                        );
-                        let _ = finalize_after_prove(ctx, job).await;
-                        Ok(())
-                    }
-                    Ok(_) => {
-                        ctx.db
-                            .record_retryable_error(
-                                job.job_id,
-                                &ctx.instance_id,
-                                &msg,
-                                retry::retry_delay(job.attempts),
-                            )
-                            .await?;
-                        Ok(())
-                    }
-                    Err(status_err) => {
-                        let combined =
-                            format!("prove failed: {msg}; intent_status failed: {status_err:#}");
-                        ctx.db
-                            .record_retryable_error(
-                                job.job_id,
-                                &ctx.instance_id,
-                                &combined,
-                                retry::retry_delay(job.attempts),
-                            )
-                            .await?;
-                        Ok(())
+        HubTxMode::Eoa => {
+            match lease::with_lease_heartbeat(ctx, job.job_id, ctx.hub.prove_intent_fill(id, tron))
+                .await
+            {
+                Ok(receipt) => {
+                    ctx.db
+                        .record_prove(
+                            job.job_id,
+                            &ctx.instance_id,
+                            b256_to_bytes32(receipt.transaction_hash),
+                        )
+                        .await?;
+                    ctx.telemetry
+                        .job_state_transition(job.intent_type, "proof_built", "proved");
+                    let _ = finalize_after_prove(ctx, job).await;
+                    Ok(())
+                }
+                Err(err) => {
+                    let msg = err.to_string();
+                    match ctx.hub.intent_status(id).await {
+                        Ok(status) if status.solved => {
+                            ctx.db
+                                .record_job_state(job.job_id, &ctx.instance_id, JobState::Proved)
+                                .await?;
+                            ctx.telemetry.job_state_transition(
+                                job.intent_type,
+                                "proof_built",
+                                "proved",
+                            );
+                            let _ = finalize_after_prove(ctx, job).await;
+                            Ok(())
+                        }
+                        Ok(_) => {
+                            if rebuild_stale_proof(ctx, job, &msg).await? {
+                                return Ok(());
+                            }
+                            ctx.db
+                                .record_retryable_error(
+                                    job.job_id,
+                                    &ctx.instance_id,
+                                    &msg,
+                                    retry::retry_delay_for(
+                                        ctx,
+                                        job.attempts,
+                                        ErrorClass::of_hub(&msg),
+                                    ),
+                                )
+                                .await?;
+                            Ok(())
+                        }
+                        Err(status_err) => {
+                            let combined = format!(
+                                "prove failed: {msg}; intent_status failed: {status_err:#}"
+                            );
+                            ctx.db
+                                .record_retryable_error(
+                                    job.job_id,
+                                    &ctx.instance_id,
+                                    &combined,
+                                    retry::retry_delay_for(
+                                        ctx,
+                                        job.attempts,
+                                        ErrorClass::of_hub(&combined),
+                                    ),
+                                )
+                                .await?;
+                            Ok(())
+                        }
                     }
                 }
             }
-        },
+        }
         HubTxMode::Safe4337 => {
             let kind = HubUserOpKind::Prove;
             let mut row = ctx.db.get_hub_userop(job.job_id, kind).await?;
@@ -758,13 +952,7 @@ pub(super) async fn process_proof_built_state(
                     "serialize prove userop",
                     "build proveIntentFill userop",
                     || async move {
-                        let call = crate::hub::IUntronIntents::proveIntentFillCall {
-                            id,
-                            blocks: tron.blocks.map(alloy::primitives::Bytes::from),
-                            encodedTx: tron.encoded_tx.into(),
-                            proof: tron.proof,
-                            index: tron.index,
-                        };
+                        let call = prove_call(id, tron);
                         ctx.hub
                             .safe4337_build_call_userop(ctx.hub.pool_address(), call.abi_encode())
                             .await
@@ -821,6 +1009,12 @@ pub(super) async fn process_proof_built_state(
                             "prove userop failed: {:?}",
                             receipt.reason.unwrap_or(serde_json::Value::Null)
                         );
+                        if r.batch_size > 1 {
+                            return retry_prove_outside_batch(ctx, job, &msg).await;
+                        }
+                        if rebuild_stale_proof(ctx, job, &msg).await? {
+                            return Ok(());
+                        }
                         ctx.db
                             .record_hub_userop_fatal_error(job.job_id, &ctx.instance_id, kind, &msg)
                             .await
@@ -840,6 +1034,277 @@ pub(super) async fn process_proof_built_state(
     }
 }
 
+fn tron_proof_from_row(proof: TronProofRow) -> TronProof {
+    TronProof {
+        blocks: std::array::from_fn(|i| proof.blocks[i].clone()),
+        encoded_tx: proof.encoded_tx,
+        proof: proof
+            .proof
+            .into_iter()
+            .map(|b| B256::from_slice(&b))
+            .collect(),
+        index: crate::types::parse_u256_dec(&proof.index_dec).unwrap_or(U256::ZERO),
+    }
+}
+
+fn prove_call(id: B256, tron: TronProof) -> IUntronIntents::proveIntentFillCall {
+    IUntronIntents::proveIntentFillCall {
+        id,
+        blocks: tron.blocks.map(alloy::primitives::Bytes::from),
+        encodedTx: tron.encoded_tx.into(),
+        proof: tron.proof,
+        index: tron.index,
+    }
+}
+
+/// `HUB_PROVE_BATCH_MAX > 1`: packs the `proveIntentFill` calls of `jobs` (leased, in
+/// `proof_built`, all on `ctx`'s pool) into shared Safe4337 userops of up to `prove_batch_max`
+/// calls. Each batched job gets a prove row carrying the shared userop hash, so its own
+/// `process_proof_built_state` only polls for the receipt. Jobs left out prove on their own.
+pub(super) async fn submit_prove_batches(ctx: &JobCtx, jobs: &[SolverJob]) -> Result<()> {
+    let Some(multisend) = ctx.cfg.hub.multisend else {
+        return Ok(());
+    };
+    if ctx.cfg.dry_run || ctx.cfg.hub.prove_batch_max < 2 || jobs.len() < 2 {
+        return Ok(());
+    }
+    let job_ids: Vec<i64> = jobs.iter().map(|j| j.job_id).collect();
+    let candidates = ctx.db.prove_batch_candidates(&job_ids).await?;
+    if candidates.len() < 2 {
+        return Ok(());
+    }
+
+    let mut calls: Vec<(i64, Vec<u8>)> = Vec::new();
+    for job in jobs.iter().filter(|j| candidates.contains(&j.job_id)) {
+        let Some(txid) = job.tron_txid else {
+            continue;
+        };
+        // Unreadable proofs are discarded and rebuilt on the job's own path.
+        let Ok(proof) = ctx.db.load_tron_proof(txid).await else {
+            continue;
+        };
+        let id = B256::from_slice(&job.intent_id);
+        let tron = tron_proof_from_row(proof);
+        // One reverting call reverts the whole batch, so only proofs the pool accepts right now
+        // go in; the rest surface their revert on their own path.
+        if let Err(err) = ctx.hub.check_prove_intent_fill(id, &tron).await {
+            tracing::debug!(
+                job_id = job.job_id,
+                err = %format!("{err:#}"),
+                "proof fails prove check; not batching it"
+            );
+            continue;
+        }
+        calls.push((job.job_id, prove_call(id, tron).abi_encode()));
+    }
+
+    for chunk in calls.chunks(ctx.cfg.hub.prove_batch_max) {
+        if chunk.len() < 2 {
+            break;
+        }
+        if let Err(err) = submit_prove_batch(ctx, multisend, chunk).await {
+            tracing::warn!(
+                err = %format!("{err:#}"),
+                "batched prove failed; proving jobs alone"
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn submit_prove_batch(
+    ctx: &JobCtx,
+    multisend: Address,
+    chunk: &[(i64, Vec<u8>)],
+) -> Result<()> {
+    let kind = HubUserOpKind::Prove;
+    let pool = ctx.hub.pool_address();
+    let job_ids: Vec<i64> = chunk.iter().map(|(job_id, _)| *job_id).collect();
+    let calls: Vec<(Address, Vec<u8>)> =
+        chunk.iter().map(|(_, data)| (pool, data.clone())).collect();
+
+    let _permit = ctx
+        .hub_userop_submit_sem
+        .acquire()
+        .await
+        .context("acquire hub_userop_submit_sem (prove batch)")?;
+    let userop = ctx
+        .hub
+        .safe4337_build_multisend_userop(multisend, &calls)
+        .await
+        .context("build batched proveIntentFill userop")?;
+    let json = serde_json::to_string(&userop).context("serialize batched prove userop")?;
+    ctx.db
+        .insert_hub_userop_batch_prepared(&job_ids, &ctx.instance_id, kind, &json)
+        .await?;
+
+    let started = Instant::now();
+    match ctx.hub.safe4337_send_userop(userop).await {
+        Ok(userop_hash) => {
+            ctx.telemetry.hub_userop_ok();
+            ctx.telemetry.hub_submit_ms(
+                "prove_batch_userop",
+                true,
+                started.elapsed().as_millis() as u64,
+            );
+            ctx.db
+                .record_hub_userop_batch_submitted(&job_ids, kind, &userop_hash)
+                .await?;
+            ctx.telemetry.prove_batch_jobs("submitted", job_ids.len());
+            tracing::info!(
+                jobs = ?job_ids,
+                userop_hash = %userop_hash,
+                "submitted batched proveIntentFill userop"
+            );
+            Ok(())
+        }
+        Err(err) => {
+            let msg = err.to_string();
+            ctx.telemetry
+                .hub_userop_err(aa::classify_bundler_error(&msg).as_str());
+            ctx.telemetry.hub_submit_ms(
+                "prove_batch_userop",
+                false,
+                started.elapsed().as_millis() as u64,
+            );
+            ctx.telemetry.prove_batch_jobs("send_failed", job_ids.len());
+            // Never sent: drop the shared rows so each job builds a userop of its own.
+            for job_id in &job_ids {
+                ctx.db
+                    .delete_hub_userop_prepared(*job_id, &ctx.instance_id, kind)
+                    .await
+                    .ok();
+            }
+            Err(err).context("send batched proveIntentFill userop")
+        }
+    }
+}
+
+/// A batched prove userop reverted. MultiSend doesn't say which call failed, so the job leaves
+/// batching for good and retries with a userop of its own (where a bad proof fails on its own
+/// account).
+async fn retry_prove_outside_batch(ctx: &JobCtx, job: &SolverJob, msg: &str) -> Result<()> {
+    tracing::warn!(
+        job_id = job.job_id,
+        err = %msg,
+        "batched prove userop reverted; proving this job alone"
+    );
+    ctx.db
+        .opt_out_of_prove_batches(job.job_id, &ctx.instance_id, msg)
+        .await?;
+    ctx.telemetry.prove_batch_jobs("reverted", 1);
+    ctx.db
+        .record_retryable_error(
+            job.job_id,
+            &ctx.instance_id,
+            &format!("batched {msg}"),
+            retry::retry_delay(ctx, job.attempts),
+        )
+        .await
+}
+
+/// When `msg` is a prove revert on the proof's blocks (see `looks_like_stale_tron_proof`), sends
+/// the job back to `tron_sent` so inclusion is re-checked and a fresh proof built, instead of
+/// retrying the same proof. After `TRON_PROOF_MAX_REBUILDS` rebuilds the job fails instead.
+/// Returns whether the error was handled.
+async fn rebuild_stale_proof(ctx: &JobCtx, job: &SolverJob, msg: &str) -> Result<bool> {
+    let Some(txid) = job.tron_txid else {
+        return Ok(false);
+    };
+    if !looks_like_stale_tron_proof(msg) {
+        return Ok(false);
+    }
+    let max_rebuilds = ctx.cfg.jobs.tron_proof_max_rebuilds;
+    if max_rebuilds > 0 {
+        let rebuilds = ctx.db.job_proof_rebuilds(job.job_id).await?;
+        if u64::try_from(rebuilds).unwrap_or(0) >= max_rebuilds {
+            let msg = format!("proof rejected after {rebuilds} rebuilds: {msg}");
+            retry::record_fatal(ctx, job, &msg).await?;
+            return Ok(true);
+        }
+    }
+    tracing::warn!(
+        job_id = job.job_id,
+        err = %msg,
+        "proveIntentFill rejected the proof's blocks; rebuilding proof"
+    );
+    ctx.db
+        .discard_tron_proof_for_rebuild(
+            job.job_id,
+            &ctx.instance_id,
+            txid,
+            &format!("stale proof: {msg}"),
+        )
+        .await?;
+    ctx.telemetry.proof_rebuild(job.intent_type, "stale");
+    ctx.telemetry
+        .job_state_transition(job.intent_type, "proof_built", "tron_sent");
+    Ok(true)
+}
+
+/// `ProveMode::DelegatedRelayer`: hands the proof to the relayer and stays in `proof_built` until
+/// the indexer reports the intent solved. The proof is POSTed again if it still isn't solved
+/// `resubmit_after` past the last handoff.
+async fn prove_via_relayer(
+    ctx: &JobCtx,
+    job: &SolverJob,
+    id: B256,
+    tron: &TronProof,
+) -> Result<()> {
+    let (Some(relayer), Some(relayer_cfg)) = (
+        ctx.prove_relayer.as_ref(),
+        ctx.cfg.hub.prove_relayer.as_ref(),
+    ) else {
+        anyhow::bail!("HUB_PROVE_MODE=delegated_relayer without a prove relayer");
+    };
+
+    let intent_id_hex = format!("0x{}", hex::encode(job.intent_id));
+    match ctx.indexer.fetch_intent(&intent_id_hex).await {
+        Ok(Some(row)) if row.solved => {
+            ctx.db
+                .record_job_state(job.job_id, &ctx.instance_id, JobState::Proved)
+                .await?;
+            ctx.telemetry
+                .job_state_transition(job.intent_type, "proof_built", "proved");
+            let _ = finalize_after_prove(ctx, job).await;
+            return Ok(());
+        }
+        Ok(_) => {}
+        Err(err) => {
+            tracing::warn!(id = %id, err = %err, "failed to query pool_intents for relayed proof");
+        }
+    }
+
+    let due = match ctx.db.prove_relay_submitted_secs_ago(job.job_id).await? {
+        Some(ago) => ago >= i64::try_from(relayer_cfg.resubmit_after.as_secs()).unwrap_or(i64::MAX),
+        None => true,
+    };
+    if due {
+        tracing::info!(id = %id, "handing proof to prove relayer");
+        if let Err(err) = relayer
+            .submit(ctx.cfg.hub.pool, ctx.cfg.hub.chain_id, id, tron)
+            .await
+        {
+            let msg = format!("{err:#}");
+            ctx.db
+                .record_retryable_error(
+                    job.job_id,
+                    &ctx.instance_id,
+                    &msg,
+                    retry::retry_delay(ctx, job.attempts),
+                )
+                .await?;
+            return Ok(());
+        }
+        ctx.db.record_prove_relay_submission(job.job_id).await?;
+    }
+    // Waiting on the relayer isn't a failure: check back next tick without counting an attempt.
+    ctx.db
+        .defer_job(job.job_id, &ctx.instance_id, ctx.cfg.jobs.tick_interval)
+        .await?;
+    Ok(())
+}
+
 pub(super) async fn process_proved_state(
     ctx: &JobCtx,
     job: &SolverJob,
@@ -886,6 +1351,13 @@ pub(super) async fn process_proved_state(
                 return Ok(());
             }
             if row.solved && row.funded && !row.settled {
+                let ours = row
+                    .solver
+                    .as_deref()
+                    .and_then(|s| parse_address(s).ok())
+                    .is_some_and(|s| s == ctx.hub.solver_address());
+                let ty = IntentType::from_i16(job.intent_type)?;
+                let action = SettlementAction::for_job(ty, JobState::ProvedWaitingSettlement, ours);
                 if state != JobState::ProvedWaitingSettlement {
                     ctx.db
                         .record_job_state(
@@ -900,6 +1372,9 @@ pub(super) async fn process_proved_state(
                         "proved_waiting_settlement",
                     );
                 }
+                if action == SettlementAction::Settle {
+                    settle_proved_intent(ctx, job).await?;
+                }
                 return Ok(());
             }
             Ok(())
@@ -911,3 +1386,48 @@ pub(super) async fn process_proved_state(
         }
     }
 }
+
+/// Sends `settleIntent` for a job we solved whose payout hasn't run, then marks it done once the
+/// pool reports it settled.
+async fn settle_proved_intent(ctx: &JobCtx, job: &SolverJob) -> Result<()> {
+    anyhow::ensure!(!ctx.cfg.dry_run, "dry run: refusing to settle intent");
+    let id = B256::from_slice(&job.intent_id);
+    // The indexer can trail the pool; don't pay for a settle that would revert.
+    let status = ctx.hub.intent_status(id).await?;
+    if !status.closed && !status.settled {
+        if !(status.solved && status.funded) {
+            return Ok(());
+        }
+        if let Err(err) =
+            lease::with_lease_heartbeat(ctx, job.job_id, ctx.hub.settle_intent(id)).await
+        {
+            let msg = format!("settle_intent failed: {err:#}");
+            ctx.db
+                .record_retryable_error(
+                    job.job_id,
+                    &ctx.instance_id,
+                    &msg,
+                    retry::retry_delay_for(ctx, job.attempts, ErrorClass::of_hub(&msg)),
+                )
+                .await?;
+            return Ok(());
+        }
+        let status = ctx.hub.intent_status(id).await?;
+        if !status.closed && !status.settled {
+            ctx.db
+                .record_retryable_error(
+                    job.job_id,
+                    &ctx.instance_id,
+                    "settle tx did not settle intent",
+                    retry::retry_delay(ctx, job.attempts),
+                )
+                .await?;
+            return Ok(());
+        }
+        tracing::info!(job_id = job.job_id, id = %id, "settled proved intent");
+    }
+    ctx.db.record_done(job.job_id, &ctx.instance_id).await?;
+    ctx.telemetry
+        .job_state_transition(job.intent_type, "proved_waiting_settlement", "done");
+    Ok(())
+}
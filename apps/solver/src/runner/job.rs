@@ -1,10 +1,13 @@
 use super::{JobCtx, SolverJob, hub_flow, retry, tron_flow};
-use crate::types::{IntentType, JobState};
+use crate::{
+    config::TronKey,
+    types::{IntentType, JobState},
+};
 use alloy::primitives::B256;
 use alloy::sol_types::SolValue;
 use anyhow::{Context, Result};
 
-pub(super) async fn ensure_delegate_reservation(ctx: &JobCtx, job: &SolverJob) -> Result<[u8; 32]> {
+pub(super) async fn ensure_delegate_reservation(ctx: &JobCtx, job: &SolverJob) -> Result<TronKey> {
     let intent = crate::tron_backend::DelegateResourceIntent::abi_decode(&job.intent_specs)
         .context("decode DelegateResourceIntent")?;
     let needed = i64::try_from(intent.balanceSun).unwrap_or(i64::MAX);
@@ -152,7 +155,7 @@ pub(super) async fn process_job(ctx: JobCtx, job: SolverJob) -> Result<()> {
         JobState::Proved | JobState::ProvedWaitingFunding | JobState::ProvedWaitingSettlement => {
             hub_flow::process_proved_state(&ctx, &job, state).await
         }
-        JobState::Done | JobState::FailedFatal => Ok(()),
+        JobState::Done | JobState::FailedFatal | JobState::Unclaimed | JobState::LostRace => Ok(()),
     }
 }
 
@@ -162,11 +165,7 @@ pub(super) fn b256_to_bytes32(v: B256) -> [u8; 32] {
     out
 }
 
-pub(super) fn duration_hours_for_lock_period_blocks(lock_period_blocks: u64) -> u64 {
-    let secs = lock_period_blocks.saturating_mul(3);
-    let hours = secs.saturating_add(3599) / 3600;
-    hours.max(1)
-}
+pub(super) use crate::tron_backend::duration_hours_for_lock_period_blocks;
 
 pub(super) fn looks_like_tron_server_busy(msg: &str) -> bool {
     msg.contains("SERVER_BUSY")
@@ -182,6 +181,51 @@ pub(super) fn looks_like_tron_contract_failure(msg: &str) -> bool {
         || m.contains("validate")
 }
 
+/// Tron tx reader errors that reject the proof's blocks rather than the tx: the blocks were
+/// reorged away or weren't produced by the reader's SR set. Resubmitting the same proof fails
+/// identically.
+const STALE_TRON_PROOF_ERRORS: [&str; 4] = [
+    "InvalidBlockSequence()",
+    "InvalidWitnessSignature()",
+    "UnknownSr(bytes20)",
+    "InvalidTxMerkleProof()",
+];
+
+/// A prove revert caused by one of `STALE_TRON_PROOF_ERRORS`, by name or by selector in the
+/// revert data.
+pub(super) fn looks_like_stale_tron_proof(msg: &str) -> bool {
+    let m = msg.to_ascii_lowercase();
+    STALE_TRON_PROOF_ERRORS.iter().any(|sig| {
+        let name = sig.split('(').next().unwrap_or(sig).to_ascii_lowercase();
+        let selector = hex::encode(&alloy::primitives::keccak256(sig.as_bytes())[..4]);
+        m.contains(&name) || m.contains(&format!("0x{selector}"))
+    })
+}
+
+/// Broadcast rejected because the sender can't cover the tx's energy (as opposed to a revert).
+pub(super) fn looks_like_tron_out_of_energy(msg: &str) -> bool {
+    let m = msg.to_ascii_lowercase();
+    if m.contains("out_of_energy") || m.contains("out of energy") {
+        return true;
+    }
+    let validate_error =
+        m.contains("contract_validate_error") || m.contains("contract validate error");
+    validate_error && m.contains("energy")
+}
+
+/// Signed `TriggerSmartContract` tx bytes besides the calldata (raw data fields, contract `Any`
+/// wrapper, signature); approximate, as varint widths depend on the values.
+const TRIGGER_TX_OVERHEAD_BYTES: u64 = 200;
+
+/// Rough signed size of the tx that fills a `TriggerSmartContract` intent, for pricing bandwidth
+/// before anything is built.
+pub(super) fn estimate_trigger_tx_size_bytes(intent_specs: &[u8]) -> Option<u64> {
+    use alloy::sol_types::SolValue;
+
+    let intent = crate::tron_backend::TriggerSmartContractIntent::abi_decode(intent_specs).ok()?;
+    Some(TRIGGER_TX_OVERHEAD_BYTES + intent.data.len() as u64)
+}
+
 pub(super) fn decode_trigger_contract_and_selector(
     intent_specs: &[u8],
 ) -> Option<(alloy::primitives::Address, Option<[u8; 4]>)> {
@@ -198,3 +242,62 @@ pub(super) fn decode_trigger_contract_and_selector(
     };
     Some((intent.to, selector))
 }
+
+/// Tron recipient (`to` / `receiver`) of transfer and delegate intents.
+pub(super) fn decode_tron_recipient(
+    ty: crate::types::IntentType,
+    intent_specs: &[u8],
+) -> Option<alloy::primitives::Address> {
+    use crate::tron_backend::{DelegateResourceIntent, TRXTransferIntent, USDTTransferIntent};
+    use crate::types::IntentType;
+    use alloy::sol_types::SolValue;
+
+    match ty {
+        IntentType::TriggerSmartContract => None,
+        IntentType::UsdtTransfer => USDTTransferIntent::abi_decode(intent_specs)
+            .ok()
+            .map(|i| i.to),
+        IntentType::TrxTransfer => TRXTransferIntent::abi_decode(intent_specs)
+            .ok()
+            .map(|i| i.to),
+        IntentType::DelegateResource => DelegateResourceIntent::abi_decode(intent_specs)
+            .ok()
+            .map(|i| i.receiver),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{looks_like_stale_tron_proof, looks_like_tron_out_of_energy};
+
+    #[test]
+    fn detects_out_of_energy_broadcast_errors() {
+        assert!(looks_like_tron_out_of_energy(
+            "broadcast failed: msg_hex=0x, msg_utf8=OUT_OF_ENERGY"
+        ));
+        assert!(looks_like_tron_out_of_energy(
+            "broadcast failed: msg_utf8=Contract validate error : account does not have enough energy"
+        ));
+        assert!(!looks_like_tron_out_of_energy(
+            "broadcast failed: msg_utf8=Contract validate error : balance is not sufficient"
+        ));
+        assert!(!looks_like_tron_out_of_energy("SERVER_BUSY"));
+    }
+
+    #[test]
+    fn detects_stale_proof_reverts_by_name_or_selector() {
+        assert!(looks_like_stale_tron_proof(
+            "execution reverted: custom error InvalidBlockSequence()"
+        ));
+        assert!(looks_like_stale_tron_proof(
+            "server returned an error response: error code 3: execution reverted, data: \"0xe14a7931\""
+        ));
+        assert!(looks_like_stale_tron_proof(
+            r#"prove userop failed: "0x075f374c""#
+        ));
+        assert!(!looks_like_stale_tron_proof(
+            "execution reverted, data: \"0xa9059cbb\""
+        ));
+        assert!(!looks_like_stale_tron_proof("AlreadySolved()"));
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How a lag-guard skip should be logged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum LagLog {
+    /// Already reported within the warn interval.
+    Silent,
+    Warn,
+    /// Lag grew since the last report: the indexer is falling further behind, not recovering.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LagState {
+    last_logged_at: Instant,
+    last_logged_lag: u64,
+    /// Ticks skipped since the last report (including this one once reported).
+    skipped: u64,
+}
+
+/// Per-pool rate limiting for "indexer lag too high" logs, so a persistently lagging indexer
+/// yields one line per interval instead of one per tick.
+#[derive(Debug, Default)]
+pub(super) struct LagWarnings {
+    pools: HashMap<String, LagState>,
+}
+
+impl LagWarnings {
+    /// Records a skipped tick for `pool_id`. Returns the log level and the number of skipped
+    /// ticks the report covers.
+    pub(super) fn on_skip(
+        &mut self,
+        pool_id: &str,
+        lag: u64,
+        interval: Duration,
+        now: Instant,
+    ) -> (LagLog, u64) {
+        let Some(state) = self.pools.get_mut(pool_id) else {
+            self.pools.insert(
+                pool_id.to_string(),
+                LagState {
+                    last_logged_at: now,
+                    last_logged_lag: lag,
+                    skipped: 0,
+                },
+            );
+            return (LagLog::Warn, 1);
+        };
+
+        state.skipped += 1;
+        if now.saturating_duration_since(state.last_logged_at) < interval {
+            return (LagLog::Silent, state.skipped);
+        }
+        let level = if lag > state.last_logged_lag {
+            LagLog::Error
+        } else {
+            LagLog::Warn
+        };
+        let skipped = state.skipped;
+        *state = LagState {
+            last_logged_at: now,
+            last_logged_lag: lag,
+            skipped: 0,
+        };
+        (level, skipped)
+    }
+
+    /// Forgets `pool_id` once it's caught up. Returns true if it was lagging.
+    pub(super) fn on_caught_up(&mut self, pool_id: &str) -> bool {
+        self.pools.remove(pool_id).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_skip_warns_then_rate_limits() {
+        let mut w = LagWarnings::default();
+        let t0 = Instant::now();
+        let interval = Duration::from_secs(60);
+
+        assert_eq!(w.on_skip("p", 100, interval, t0), (LagLog::Warn, 1));
+        assert_eq!(
+            w.on_skip("p", 100, interval, t0 + Duration::from_secs(5)),
+            (LagLog::Silent, 1)
+        );
+        assert_eq!(
+            w.on_skip("p", 90, interval, t0 + Duration::from_secs(61)),
+            (LagLog::Warn, 2)
+        );
+    }
+
+    #[test]
+    fn growing_lag_escalates() {
+        let mut w = LagWarnings::default();
+        let t0 = Instant::now();
+        let interval = Duration::from_secs(60);
+
+        w.on_skip("p", 100, interval, t0);
+        let (level, _) = w.on_skip("p", 150, interval, t0 + Duration::from_secs(61));
+        assert_eq!(level, LagLog::Error);
+        // Compared against the last *reported* lag.
+        let (level, _) = w.on_skip("p", 150, interval, t0 + Duration::from_secs(122));
+        assert_eq!(level, LagLog::Warn);
+    }
+
+    #[test]
+    fn pools_are_tracked_independently_and_reset_on_catch_up() {
+        let mut w = LagWarnings::default();
+        let t0 = Instant::now();
+        let interval = Duration::from_secs(60);
+
+        w.on_skip("a", 100, interval, t0);
+        assert_eq!(w.on_skip("b", 100, interval, t0).0, LagLog::Warn);
+
+        assert!(w.on_caught_up("a"));
+        assert!(!w.on_caught_up("a"));
+        assert_eq!(w.on_skip("a", 100, interval, t0).0, LagLog::Warn);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{JobCtx, LEASE_FOR_SECS};
+use super::JobCtx;
 use anyhow::Result;
 use std::future::Future;
 use std::time::Duration;
@@ -11,7 +11,7 @@ pub(super) async fn renew_job_lease(ctx: &JobCtx, job_id: i64) -> Result<()> {
         .renew_job_lease(
             job_id,
             &ctx.instance_id,
-            Duration::from_secs(LEASE_FOR_SECS),
+            Duration::from_secs(ctx.cfg.jobs.lease_secs),
         )
         .await
 }
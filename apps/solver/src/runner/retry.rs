@@ -1,12 +1,108 @@
-use super::{JobCtx, SolverJob};
+use super::{
+    JobCtx, SolverJob, looks_like_tron_contract_failure, looks_like_tron_out_of_energy,
+    looks_like_tron_server_busy,
+};
 use crate::types::IntentType;
 use anyhow::Result;
 
-pub(super) fn retry_delay(attempts: i32) -> std::time::Duration {
-    // Exponential backoff with caps. This is intentionally simple and centralized.
+/// Selects the backoff schedule for a retryable error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ErrorClass {
+    /// Node/RPC busyness that usually clears within seconds: 250ms, 500ms, ... capped at 15s.
+    Transient,
+    /// Unclassified: 1s, 2s, 4s, ... capped at 5 minutes.
+    Default,
+    /// Likely to fail the same way until something external changes (reverts, rejected proofs):
+    /// 30s, 60s, ... capped at 1 hour.
+    Structural,
+}
+
+impl ErrorClass {
+    /// Classifies a Tron node / broadcast error.
+    pub(super) fn of_tron(msg: &str) -> Self {
+        if looks_like_tron_server_busy(msg) || looks_like_network_blip(msg) {
+            return Self::Transient;
+        }
+        // Out-of-energy clears once energy is rented or regenerates; it has its own cooldown.
+        if looks_like_tron_contract_failure(msg) && !looks_like_tron_out_of_energy(msg) {
+            return Self::Structural;
+        }
+        Self::Default
+    }
+
+    /// Classifies a hub RPC / bundler error. Reverts are not structural here: bundler AA errors
+    /// (nonce races, stale gas) read like reverts but usually clear on the next attempt.
+    pub(super) fn of_hub(msg: &str) -> Self {
+        if looks_like_network_blip(msg) {
+            return Self::Transient;
+        }
+        let m = msg.to_ascii_lowercase();
+        if m.contains("proof") && (m.contains("invalid") || m.contains("malformed")) {
+            return Self::Structural;
+        }
+        Self::Default
+    }
+
+    fn schedule(self) -> (u64, f64) {
+        match self {
+            Self::Transient => (250, 15_000.0),
+            Self::Default => (1_000, 300_000.0),
+            Self::Structural => (30_000, 3_600_000.0),
+        }
+    }
+}
+
+fn looks_like_network_blip(msg: &str) -> bool {
+    let m = msg.to_ascii_lowercase();
+    m.contains("timed out")
+        || m.contains("too many requests")
+        || m.contains("connection reset")
+        || m.contains("connection refused")
+}
+
+pub(super) fn retry_delay(ctx: &JobCtx, attempts: i32) -> std::time::Duration {
+    retry_delay_for(ctx, attempts, ErrorClass::Default)
+}
+
+pub(super) fn retry_delay_for(
+    ctx: &JobCtx,
+    attempts: i32,
+    class: ErrorClass,
+) -> std::time::Duration {
+    backoff_delay(
+        class,
+        attempts,
+        ctx.cfg.jobs.retry_jitter_pct,
+        jitter_unit(),
+    )
+}
+
+/// Exponential backoff from the class's base delay, doubling per attempt up to its cap. This is
+/// intentionally simple and centralized.
+///
+/// `unit` in [-1, 1] spreads the delay by up to `jitter_pct` percent, so solver instances retrying
+/// the same job don't fire in lockstep. The spread is applied before the cap and is < 33%, so a
+/// step's range never overlaps the next one and delays still grow with `attempts`.
+fn backoff_delay(
+    class: ErrorClass,
+    attempts: i32,
+    jitter_pct: u64,
+    unit: f64,
+) -> std::time::Duration {
+    let (initial_ms, cap_ms) = class.schedule();
     let shift = u32::try_from(attempts.clamp(0, 10)).unwrap_or(0);
-    let base = 1u64.checked_shl(shift).unwrap_or(u64::MAX);
-    std::time::Duration::from_secs(base.min(300))
+    let base_ms = (initial_ms << shift) as f64;
+    let spread = jitter_pct.min(30) as f64 / 100.0 * unit.clamp(-1.0, 1.0);
+    let ms = (base_ms * (1.0 + spread)).min(cap_ms);
+    std::time::Duration::from_millis(ms as u64)
+}
+
+/// Uniform-ish value in [-1, 1]. `RandomState` is randomly keyed per instance, which is all the
+/// randomness jitter needs.
+fn jitter_unit() -> f64 {
+    use std::hash::BuildHasher;
+    let r = std::collections::hash_map::RandomState::new().hash_one(std::time::SystemTime::now());
+    (r as f64 / u64::MAX as f64) * 2.0 - 1.0
 }
 
 pub(super) async fn record_fatal(ctx: &JobCtx, job: &SolverJob, msg: &str) -> Result<()> {
@@ -17,6 +113,9 @@ pub(super) async fn record_fatal(ctx: &JobCtx, job: &SolverJob, msg: &str) -> Re
         .db
         .release_delegate_reservation_for_job(job.job_id)
         .await;
+    if let Some(hook) = &ctx.fatal_webhook {
+        hook.notify(job, msg);
+    }
 
     if ctx.cfg.jobs.global_pause_fatal_threshold > 0 {
         let window = i64::try_from(ctx.cfg.jobs.global_pause_window_secs).unwrap_or(300);
@@ -68,12 +167,26 @@ pub(super) async fn enforce_claim_rate_limits(ctx: &JobCtx, ty: IntentType) -> R
         ctx.telemetry.claim_rate_limited(k);
         return Ok(Some(wait));
     }
+
+    // The fixed windows above reset on the minute, so they allow a 2x burst across a boundary; the
+    // bucket refills continuously and smooths that out. Checked last so a token is only spent when
+    // the claim isn't held back by the limits above.
+    if let Some(per_minute) = ctx.cfg.policy.max_claims_per_minute
+        && let Some(wait) = ctx.db.take_claim_token("claim:bucket", per_minute).await?
+    {
+        ctx.telemetry.claim_rate_limited("claim:bucket");
+        return Ok(Some(wait));
+    }
     Ok(None)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::retry_delay;
+    use super::{ErrorClass, backoff_delay};
+
+    fn retry_delay(attempts: i32) -> std::time::Duration {
+        backoff_delay(ErrorClass::Default, attempts, 0, 0.0)
+    }
 
     #[test]
     fn retry_delay_grows_monotonically_until_cap() {
@@ -101,4 +214,83 @@ mod tests {
         assert_eq!(retry_delay(-1), std::time::Duration::from_secs(1));
         assert_eq!(retry_delay(i32::MIN), std::time::Duration::from_secs(1));
     }
+
+    #[test]
+    fn jittered_backoff_stays_within_step_and_keeps_growing() {
+        for attempts in 0..=16 {
+            let lo = backoff_delay(ErrorClass::Default, attempts, 25, -1.0);
+            let hi = backoff_delay(ErrorClass::Default, attempts, 25, 1.0);
+            let mid = retry_delay(attempts);
+            assert!(lo <= mid && mid <= hi, "attempts={attempts}");
+            assert!(hi <= std::time::Duration::from_secs(300));
+            // The slowest draw of this step never exceeds the fastest draw of the next one.
+            assert!(
+                hi <= backoff_delay(ErrorClass::Default, attempts + 1, 25, -1.0),
+                "jitter ranges overlap at attempts={attempts}"
+            );
+        }
+        assert_eq!(
+            backoff_delay(ErrorClass::Default, 0, 25, -1.0),
+            std::time::Duration::from_millis(750)
+        );
+        // Jitter is capped at 30% regardless of config.
+        assert_eq!(
+            backoff_delay(ErrorClass::Default, 1, 90, 1.0),
+            std::time::Duration::from_millis(2600)
+        );
+    }
+
+    #[test]
+    fn error_class_picks_schedule() {
+        assert_eq!(
+            ErrorClass::of_tron("tron broadcast failed: SERVER_BUSY"),
+            ErrorClass::Transient
+        );
+        assert_eq!(
+            ErrorClass::of_tron("CONTRACT_VALIDATE_ERROR: revert"),
+            ErrorClass::Structural
+        );
+        assert_eq!(
+            ErrorClass::of_tron("CONTRACT_VALIDATE_ERROR: account has insufficient energy"),
+            ErrorClass::Default
+        );
+        assert_eq!(
+            ErrorClass::of_hub("hub rpc: request timed out"),
+            ErrorClass::Transient
+        );
+        assert_eq!(
+            ErrorClass::of_hub("execution reverted: invalid proof"),
+            ErrorClass::Structural
+        );
+        assert_eq!(
+            ErrorClass::of_hub("AA23 reverted: validateUserOp"),
+            ErrorClass::Default
+        );
+        assert_eq!(ErrorClass::of_hub("pool_usdt failed"), ErrorClass::Default);
+    }
+
+    #[test]
+    fn class_schedules_are_ordered_and_capped() {
+        let at = |class, attempts| backoff_delay(class, attempts, 0, 0.0);
+        assert_eq!(
+            at(ErrorClass::Transient, 0),
+            std::time::Duration::from_millis(250)
+        );
+        assert_eq!(
+            at(ErrorClass::Transient, 20),
+            std::time::Duration::from_secs(15)
+        );
+        assert_eq!(
+            at(ErrorClass::Structural, 0),
+            std::time::Duration::from_secs(30)
+        );
+        assert_eq!(
+            at(ErrorClass::Structural, 20),
+            std::time::Duration::from_secs(3600)
+        );
+        for attempts in 0..=16 {
+            assert!(at(ErrorClass::Transient, attempts) <= at(ErrorClass::Default, attempts));
+            assert!(at(ErrorClass::Default, attempts) <= at(ErrorClass::Structural, attempts));
+        }
+    }
 }
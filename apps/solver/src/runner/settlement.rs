@@ -0,0 +1,58 @@
+use crate::types::{IntentType, JobState};
+
+/// What the solver does for a proved job the pool hasn't paid out yet. The proved-state loop only
+/// watches the indexer unless this says there's a transaction for us to send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SettlementAction {
+    /// Nothing for us to send; keep polling until the intent is settled or closed.
+    Wait,
+    /// Call `settleIntent`: the intent is solved and funded, but the payout hasn't run.
+    Settle,
+}
+
+impl SettlementAction {
+    /// Follow-up for a job of type `ty` sitting in `state`. `ours` is whether the pool records this
+    /// solver as the intent's solver; only then is the payout ours to trigger.
+    pub(super) fn for_job(ty: IntentType, state: JobState, ours: bool) -> Self {
+        if !ours {
+            return Self::Wait;
+        }
+        match ty {
+            // Funding a virtual receiver intent pulls through its forwarder (and may need bridge
+            // fees), so `proved_waiting_funding` is left to whoever funds the receiver.
+            IntentType::TriggerSmartContract
+            | IntentType::UsdtTransfer
+            | IntentType::TrxTransfer
+            | IntentType::DelegateResource => match state {
+                JobState::ProvedWaitingSettlement => Self::Settle,
+                _ => Self::Wait,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settles_only_our_solved_and_funded_intents() {
+        let ty = IntentType::UsdtTransfer;
+        assert_eq!(
+            SettlementAction::for_job(ty, JobState::ProvedWaitingSettlement, true),
+            SettlementAction::Settle
+        );
+        assert_eq!(
+            SettlementAction::for_job(ty, JobState::ProvedWaitingSettlement, false),
+            SettlementAction::Wait
+        );
+        assert_eq!(
+            SettlementAction::for_job(ty, JobState::ProvedWaitingFunding, true),
+            SettlementAction::Wait
+        );
+        assert_eq!(
+            SettlementAction::for_job(ty, JobState::Proved, true),
+            SettlementAction::Wait
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use super::Solver;
+use crate::db::ActiveBreaker;
+use anyhow::Result;
+use std::time::Instant;
+
+/// Fills (by when they were proved), profit and skipped intents are counted over this trailing
+/// window.
+const SUMMARY_WINDOW_SECS: i64 = 3_600;
+/// Skip reasons listed, most skipped intents first.
+const SUMMARY_TOP_SKIP_REASONS: usize = 5;
+
+/// Periodic "is everything okay" snapshot for operators without a metrics stack.
+#[derive(Debug, Clone, serde::Serialize)]
+pub(super) struct OperatorSummary {
+    window_secs: i64,
+    fills: i64,
+    /// Sum of the profit the policy priced accepted fills at; covers `priced_fills` of `fills`.
+    est_profit_usd: f64,
+    priced_fills: i64,
+    skipped_intents: Vec<SkipCount>,
+    active_breakers: Vec<String>,
+    key_reserve_sun: u64,
+    /// `None` when the Tron node couldn't be asked.
+    low_keys: Option<Vec<LowKey>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct SkipCount {
+    reason: String,
+    /// Distinct intents last skipped for `reason` within the window.
+    intents: i64,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct LowKey {
+    address: String,
+    balance_sun: i64,
+}
+
+impl Solver {
+    /// Logs an [`OperatorSummary`] every `SummaryConfig::interval`, and posts it to the summary
+    /// webhook when one is configured.
+    pub(super) async fn maybe_report_summary(&mut self) {
+        let Some(interval) = self.cfg.summary.interval else {
+            return;
+        };
+        if self.last_summary_at.is_some_and(|t| t.elapsed() < interval) {
+            return;
+        }
+        self.last_summary_at = Some(Instant::now());
+
+        let summary = match self.build_summary().await {
+            Ok(v) => v,
+            Err(err) => {
+                tracing::warn!(err = %format!("{err:#}"), "failed to build solver summary");
+                return;
+            }
+        };
+        tracing::info!(
+            fills = summary.fills,
+            est_profit_usd = summary.est_profit_usd,
+            active_breakers = summary.active_breakers.len(),
+            summary = %serde_json::to_string(&summary).unwrap_or_default(),
+            "solver summary"
+        );
+        if let Some(webhook) = &self.summary_webhook {
+            webhook.post(&summary.render(), &summary);
+        }
+    }
+
+    async fn build_summary(&self) -> Result<OperatorSummary> {
+        let fills = self.db.fill_summary(SUMMARY_WINDOW_SECS).await?;
+        let skipped = self
+            .db
+            .skipped_intents_by_reason(
+                SUMMARY_WINDOW_SECS,
+                i64::try_from(SUMMARY_TOP_SKIP_REASONS).unwrap_or(i64::MAX),
+            )
+            .await?;
+        let breakers = self.db.list_active_breakers().await?;
+
+        // A Tron node outage shouldn't suppress the rest of the summary.
+        let reserve = self.cfg.summary.key_reserve_sun;
+        let low_keys = match self.tron.key_trx_balances_sun().await {
+            Ok(balances) => Some(
+                balances
+                    .into_iter()
+                    .filter(|(_, sun)| u64::try_from(*sun).unwrap_or(0) < reserve)
+                    .map(|(addr, sun)| LowKey {
+                        address: addr.to_base58check(),
+                        balance_sun: sun,
+                    })
+                    .collect(),
+            ),
+            Err(err) => {
+                tracing::warn!(
+                    err = %format!("{err:#}"),
+                    "summary: fetch tron key balances failed"
+                );
+                None
+            }
+        };
+
+        Ok(OperatorSummary {
+            window_secs: SUMMARY_WINDOW_SECS,
+            fills: fills.fills,
+            est_profit_usd: fills.est_profit_usd,
+            priced_fills: fills.priced,
+            skipped_intents: skipped
+                .into_iter()
+                .map(|(reason, intents)| SkipCount { reason, intents })
+                .collect(),
+            active_breakers: breakers.iter().map(describe_breaker).collect(),
+            key_reserve_sun: reserve,
+            low_keys,
+        })
+    }
+}
+
+impl OperatorSummary {
+    /// Multi-line chat message.
+    fn render(&self) -> String {
+        let mut out = format!(
+            "solver summary (last {}m): {} fills, est. profit ${:.2} ({} priced)",
+            self.window_secs / 60,
+            self.fills,
+            self.est_profit_usd,
+            self.priced_fills
+        );
+        let skipped = if self.skipped_intents.is_empty() {
+            "none".to_string()
+        } else {
+            self.skipped_intents
+                .iter()
+                .map(|s| format!("{} {}", s.reason, s.intents))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        out.push_str(&format!("\nskipped intents: {skipped}"));
+        let breakers = if self.active_breakers.is_empty() {
+            "none".to_string()
+        } else {
+            self.active_breakers.join(", ")
+        };
+        out.push_str(&format!("\nactive breakers: {breakers}"));
+        let keys = match &self.low_keys {
+            None => "unknown (tron node unreachable)".to_string(),
+            Some(keys) if keys.is_empty() => "none".to_string(),
+            Some(keys) => keys
+                .iter()
+                .map(|k| format!("{} {}", k.address, format_trx(k.balance_sun)))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        out.push_str(&format!(
+            "\ntron keys below {}: {keys}",
+            format_trx(i64::try_from(self.key_reserve_sun).unwrap_or(i64::MAX))
+        ));
+        out
+    }
+}
+
+fn describe_breaker(b: &ActiveBreaker) -> String {
+    let selector = b
+        .selector
+        .map(|s| format!(":0x{}", hex::encode(s)))
+        .unwrap_or_default();
+    format!("{}{selector} until {}", b.contract, b.cooldown_until)
+}
+
+fn format_trx(sun: i64) -> String {
+    format!("{:.2} TRX", sun as f64 / 1e6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_reports_each_section() {
+        let summary = OperatorSummary {
+            window_secs: 3_600,
+            fills: 12,
+            est_profit_usd: 3.456,
+            priced_fills: 10,
+            skipped_intents: vec![SkipCount {
+                reason: "no_price".to_string(),
+                intents: 40,
+            }],
+            active_breakers: Vec::new(),
+            key_reserve_sun: 10_000_000,
+            low_keys: Some(vec![LowKey {
+                address: "TKey".to_string(),
+                balance_sun: 1_234_567,
+            }]),
+        };
+        assert_eq!(
+            summary.render(),
+            "solver summary (last 60m): 12 fills, est. profit $3.46 (10 priced)\n\
+             skipped intents: no_price 40\n\
+             active breakers: none\n\
+             tron keys below 10.00 TRX: TKey 1.23 TRX"
+        );
+
+        let unknown = OperatorSummary {
+            low_keys: None,
+            ..summary
+        };
+        assert!(
+            unknown
+                .render()
+                .ends_with("unknown (tron node unreachable)")
+        );
+    }
+}
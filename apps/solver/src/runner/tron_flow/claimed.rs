@@ -1,18 +1,18 @@
+use super::super::retry::ErrorClass;
 use super::super::{
     JobCtx, SolverJob, b256_to_bytes32, decode_trigger_contract_and_selector,
-    duration_hours_for_lock_period_blocks, ensure_delegate_reservation,
-    lease, looks_like_tron_contract_failure, looks_like_tron_server_busy, retry,
+    duration_hours_for_lock_period_blocks, ensure_delegate_reservation, hub_flow, lease,
+    looks_like_tron_contract_failure, looks_like_tron_server_busy, retry,
 };
 use crate::{
     config::TronMode,
     db::{TronProofRow, TronSignedTxRow},
-    tron_backend::TronExecution,
-    types::IntentType,
+    tron_backend::{EnergyRentalOrder, PlacedEnergyRental, TronExecution, TronPreparedTx},
+    types::{IntentType, JobState},
 };
 use alloy::primitives::B256;
 use alloy::sol_types::SolValue;
 use anyhow::{Context, Result};
-use std::time::Instant;
 
 pub(crate) async fn process_claimed_state(
     ctx: &JobCtx,
@@ -20,20 +20,29 @@ pub(crate) async fn process_claimed_state(
     id: B256,
     ty: IntentType,
 ) -> Result<()> {
+    // Nothing signed exists yet in `claimed`, so no fill can land behind our back.
+    if hub_flow::abandon_due(ctx, job) {
+        return hub_flow::unclaim_stalled_job(ctx, job, id, JobState::Claimed).await;
+    }
     if ctx.cfg.tron.mode == TronMode::Mock {
         return process_claimed_state_mock(ctx, job, id, ty).await;
     }
 
     tracing::info!(id = %id, "preparing tron tx (persist signed bytes)");
-    // TRX/USDT can optionally require consolidation (multi-key pre-txs). We persist the
-    // whole plan as (pre txs + final tx), then broadcast them in order in tron_prepared.
-    if matches!(ty, IntentType::TrxTransfer | IntentType::UsdtTransfer) {
+    // TRX/USDT can optionally require consolidation (multi-key pre-txs), and token-spending
+    // TriggerSmartContract calls are charged to the key budget and may need an approve first. We
+    // persist the whole plan as (pre txs + final tx), then broadcast them in order in
+    // tron_prepared.
+    if matches!(ty, IntentType::TrxTransfer | IntentType::UsdtTransfer)
+        || (ty == IntentType::TriggerSmartContract
+            && !ctx.cfg.tron.trigger_spend_selectors.is_empty())
+    {
         let plan = match ty {
             IntentType::TrxTransfer => {
                 lease::with_lease_heartbeat(
                     ctx,
                     job.job_id,
-                    ctx.tron.prepare_trx_transfer_plan(&job.intent_specs),
+                    ctx.tron.prepare_trx_transfer_plan(&ctx.db, &job.intent_specs),
                 )
                 .await
                 .context("prepare trx transfer plan")?
@@ -43,11 +52,30 @@ pub(crate) async fn process_claimed_state(
                     ctx,
                     job.job_id,
                     ctx.tron
-                        .prepare_usdt_transfer_plan(ctx.hub.as_ref(), &job.intent_specs),
+                        .prepare_usdt_transfer_plan(ctx.hub.as_ref(), &ctx.db, &job.intent_specs),
                 )
                 .await
                 .context("prepare usdt transfer plan")?
             }
+            IntentType::TriggerSmartContract => {
+                match lease::with_lease_heartbeat(
+                    ctx,
+                    job.job_id,
+                    ctx.tron.prepare_trigger_smart_contract_plan(
+                        ctx.hub.as_ref(),
+                        &ctx.db,
+                        &job.intent_specs,
+                    ),
+                )
+                .await
+                {
+                    Ok(v) => v,
+                    Err(err) => {
+                        handle_prepare_error(ctx, job, id, ty, &format!("{err:#}")).await?;
+                        return Ok(());
+                    }
+                }
+            }
             _ => unreachable!(),
         };
 
@@ -60,6 +88,7 @@ pub(crate) async fn process_claimed_state(
                 txid: p.txid,
                 tx_bytes: p.tx_bytes.clone(),
                 fee_limit_sun: p.fee_limit_sun,
+                fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                 energy_required: p.energy_required,
                 tx_size_bytes: p.tx_size_bytes,
             })
@@ -67,15 +96,91 @@ pub(crate) async fn process_claimed_state(
         let final_row = TronSignedTxRow {
             step: "final".to_string(),
             txid: plan.final_tx.txid,
-            tx_bytes: plan.final_tx.tx_bytes,
+            tx_bytes: plan.final_tx.tx_bytes.clone(),
             fee_limit_sun: plan.final_tx.fee_limit_sun,
+            fee_limit_estimate_sun: plan.final_tx.fee_limit_estimate_sun,
             energy_required: plan.final_tx.energy_required,
             tx_size_bytes: plan.final_tx.tx_size_bytes,
         };
 
         ctx.db
-            .record_tron_plan(job.job_id, &ctx.instance_id, &pre_rows, &final_row)
+            .record_tron_plan(
+                job.job_id,
+                &ctx.instance_id,
+                &pre_rows,
+                &final_row,
+                &plan.spends,
+            )
             .await?;
+
+        // Rent only after the plan is persisted, so a crash here can't lead to renting twice for
+        // two different signed txs.
+        let sponsored = ty == IntentType::UsdtTransfer && ctx.cfg.tron.sponsor_usdt_transfer_energy;
+        let mut own = plan.pre_txs.iter().collect::<Vec<_>>();
+        if !sponsored {
+            own.push(&plan.final_tx);
+        }
+        rent_own_energy(ctx, job, &own).await;
+        if ty == IntentType::UsdtTransfer {
+            match lease::with_lease_heartbeat(
+                ctx,
+                job.job_id,
+                ctx.tron.sponsor_transfer_energy(
+                    &ctx.db,
+                    &plan.final_tx,
+                    ctx.trx_usd,
+                    Some((job.intent_id, job.intent_type)),
+                ),
+            )
+            .await
+            {
+                Ok(Some(s)) => {
+                    ctx.db
+                        .record_tron_sponsored_energy(
+                            job.job_id,
+                            plan.final_tx.txid,
+                            job.intent_type,
+                            i64::try_from(s.energy).unwrap_or(i64::MAX),
+                            s.cost_sun,
+                            &s.provider,
+                        )
+                        .await?;
+                    let request_json = serde_json::json!({
+                        "order": s.order.rendered_request,
+                        "order_meta": {
+                            "kind": "sponsored_transfer_energy",
+                            "amount_units": s.energy,
+                            "cost_trx": s.order.cost_trx,
+                        },
+                    });
+                    let response_json = serde_json::json!({
+                        "order": s.order.attempt.response_json,
+                    });
+                    ctx.db
+                        .upsert_tron_rental(
+                            job.job_id,
+                            &s.provider,
+                            "energy",
+                            s.receiver_evm,
+                            0,
+                            0,
+                            s.order.attempt.order_id.as_deref(),
+                            None,
+                            Some(&request_json),
+                            Some(&response_json),
+                        )
+                        .await?;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!(
+                        job_id = job.job_id,
+                        err = %err,
+                        "sponsor usdt transfer energy failed"
+                    );
+                }
+            }
+        }
         return Ok(());
     }
 
@@ -129,7 +234,7 @@ pub(crate) async fn process_claimed_state(
                 ),
             )
             .await
-                .context("prepare delegate resource (reserved key)")
+            .context("prepare delegate resource (reserved key)")
         }
         _ => unreachable!(),
     };
@@ -137,7 +242,7 @@ pub(crate) async fn process_claimed_state(
     let exec = match exec_res {
         Ok(v) => v,
         Err(err) => {
-            handle_prepare_error(ctx, job, ty, &format!("{err:#}")).await?;
+            handle_prepare_error(ctx, job, id, ty, &format!("{err:#}")).await?;
             return Ok(());
         }
     };
@@ -154,15 +259,41 @@ pub(crate) async fn process_claimed_state(
                     p.txid,
                     &p.tx_bytes,
                     p.fee_limit_sun,
+                    p.fee_limit_estimate_sun,
                     p.energy_required,
                     p.tx_size_bytes,
                 )
                 .await?;
+            rent_own_energy(ctx, job, &[&p]).await;
             Ok(())
         }
     }
 }
 
+/// Best-effort energy rental for the solver's own signed txs, through the same capped provider
+/// loop as resell and sponsorship; a tx without rented energy burns TRX up to its fee limit.
+async fn rent_own_energy(ctx: &JobCtx, job: &SolverJob, txs: &[&TronPreparedTx]) {
+    for tx in txs {
+        let energy = u64::try_from(tx.energy_required.unwrap_or(0)).unwrap_or(0);
+        if let Err(err) = lease::with_lease_heartbeat(
+            ctx,
+            job.job_id,
+            ctx.tron.rent_energy_for_tx(
+                &ctx.db,
+                &tx.tx_bytes,
+                tx.txid,
+                energy,
+                ctx.trx_usd,
+                Some((job.intent_id, job.intent_type)),
+            ),
+        )
+        .await
+        {
+            tracing::warn!(job_id = job.job_id, err = %err, "energy rental for own tx failed");
+        }
+    }
+}
+
 async fn process_claimed_state_mock(
     ctx: &JobCtx,
     job: &SolverJob,
@@ -207,7 +338,7 @@ async fn process_claimed_state_mock(
     let exec = match exec_res {
         Ok(v) => v,
         Err(err) => {
-            handle_prepare_error(ctx, job, ty, &format!("{err:#}")).await?;
+            handle_prepare_error(ctx, job, id, ty, &format!("{err:#}")).await?;
             return Ok(());
         }
     };
@@ -228,7 +359,9 @@ async fn process_claimed_state_mock(
             let txid = job
                 .tron_txid
                 .unwrap_or_else(|| b256_to_bytes32(alloy::primitives::keccak256(id.as_slice())));
-            ctx.db.save_tron_proof(txid, &proof_row).await?;
+            ctx.db
+                .save_tron_proof(txid, &proof_row, ctx.cfg.jobs.tron_proof_compress)
+                .await?;
             ctx.db
                 .record_tron_txid(job.job_id, &ctx.instance_id, txid)
                 .await?;
@@ -253,214 +386,196 @@ async fn process_delegate_resource_resell(ctx: &JobCtx, job: &SolverJob) -> Resu
     let txid = if let Some(r) = existing.as_ref().and_then(|r| r.txid) {
         r
     } else {
-        let receiver = tron::TronAddress::from_evm(intent.receiver);
-        let balance_sun_i64 =
-            i64::try_from(intent.balanceSun).context("balanceSun out of i64 range")?;
-        let lock_period_i64 =
-            i64::try_from(intent.lockPeriod).context("lockPeriod out of i64 range")?;
-
-        let mut recv = [0u8; 20];
-        recv.copy_from_slice(intent.receiver.as_slice());
-
-        let totals = ctx.tron.energy_stake_totals().await?;
-        let units = tron::resources::resource_units_for_min_trx_sun(
-            u64::try_from(balance_sun_i64.max(0)).unwrap_or(0),
-            totals,
-            ctx.cfg.tron.resell_energy_headroom_ppm,
-        );
-        let duration_hours = duration_hours_for_lock_period_blocks(
-            u64::try_from(lock_period_i64.max(0)).unwrap_or(0),
-        );
-
-        // Prefer the pre-quoted provider (if present).
-        let preferred = existing.as_ref().map(|r| r.provider.as_str());
-        let ctx_rent = tron::RentalContext {
-            resource: tron::RentalResourceKind::Energy,
-            amount: units,
-            lock_period: Some(u64::try_from(lock_period_i64.max(0)).unwrap_or(0)),
-            duration_hours: Some(duration_hours),
-            balance_sun: Some(u64::try_from(balance_sun_i64.max(0)).unwrap_or(0)),
-            address_base58check: receiver.to_base58check(),
-            address_hex41: format!("0x{}", hex::encode(receiver.prefixed_bytes())),
-            address_evm_hex: format!("{:#x}", receiver.evm()),
-            txid: None,
-        };
-
-        let mut last_err: Option<String> = None;
-        let mut chosen: Option<(tron::RenderedJsonApiRequest, tron::RentalAttempt)> = None;
-
-        let mut providers = ctx.cfg.tron.energy_rental_providers.clone();
-        if let Some(p) = preferred {
-            providers.sort_by_key(|c| if c.name == p { 0 } else { 1 });
-        }
-        for p in &providers {
-            let _ = lease::renew_job_lease(ctx, job.job_id).await;
-            let provider = tron::JsonApiRentalProvider::new(p.clone());
-            if ctx
-                .db
-                .rental_provider_is_frozen(provider.name())
-                .await?
-                .is_some()
-            {
-                continue;
-            }
-
-            let started = Instant::now();
-            let res = tokio::time::timeout(
-                std::time::Duration::from_secs(10),
-                provider.rent_with_rendered_request(&ctx_rent),
-            )
-            .await;
-            let ms = started.elapsed().as_millis() as u64;
-
-            match res {
-                Ok(Ok((req, attempt))) if attempt.ok && attempt.txid.is_some() => {
-                    ctx.telemetry.rental_order_ms(provider.name(), true, ms);
-                    chosen = Some((req, attempt));
-                    let _ = ctx.db.rental_provider_record_success(provider.name()).await;
-                    break;
-                }
-                Ok(Ok((_req, attempt))) => {
-                    ctx.telemetry.rental_order_ms(provider.name(), false, ms);
-                    let msg = format!(
-                        "ok={} txid={:?} err={:?}",
-                        attempt.ok, attempt.txid, attempt.error
-                    );
-                    last_err = Some(format!("{}: {msg}", provider.name()));
-                    let froze = ctx
-                        .db
-                        .rental_provider_record_failure(
-                            provider.name(),
-                            ctx.cfg.tron.rental_provider_fail_window_secs,
-                            ctx.cfg.tron.rental_provider_freeze_secs,
-                            ctx.cfg.tron.rental_provider_fail_threshold,
-                            &msg,
-                        )
-                        .await;
-                    if froze.unwrap_or(false) {
-                        ctx.telemetry.rental_provider_frozen(provider.name());
-                    }
-                }
-                Ok(Err(err)) => {
-                    ctx.telemetry.rental_order_ms(provider.name(), false, ms);
-                    let msg = format!("{err:#}");
-                    last_err = Some(format!("{}: {msg}", provider.name()));
-                    let froze = ctx
-                        .db
-                        .rental_provider_record_failure(
-                            provider.name(),
-                            ctx.cfg.tron.rental_provider_fail_window_secs,
-                            ctx.cfg.tron.rental_provider_freeze_secs,
-                            ctx.cfg.tron.rental_provider_fail_threshold,
-                            &msg,
-                        )
-                        .await;
-                    if froze.unwrap_or(false) {
-                        ctx.telemetry.rental_provider_frozen(provider.name());
-                    }
-                }
-                Err(_) => {
-                    ctx.telemetry.rental_order_ms(provider.name(), false, ms);
-                    let msg = "timeout".to_string();
-                    last_err = Some(format!("{}: {msg}", provider.name()));
-                    let froze = ctx
-                        .db
-                        .rental_provider_record_failure(
-                            provider.name(),
-                            ctx.cfg.tron.rental_provider_fail_window_secs,
-                            ctx.cfg.tron.rental_provider_freeze_secs,
-                            ctx.cfg.tron.rental_provider_fail_threshold,
-                            &msg,
-                        )
-                        .await;
-                    if froze.unwrap_or(false) {
-                        ctx.telemetry.rental_provider_frozen(provider.name());
-                    }
-                }
-            }
-        }
-
-        let Some((rendered_req, attempt)) = chosen else {
-            let msg =
-                last_err.unwrap_or_else(|| "no energy rental providers succeeded".to_string());
+        // Ticks can overlap on a job (e.g. after a lease expires mid-order); only one of them may
+        // place an order, or the provider charges twice.
+        let lock_ttl = ctx.cfg.tron.rental_order_lock_secs;
+        let Some(lock) = ctx
+            .db
+            .try_lock_tron_rental_order(job.job_id, &ctx.instance_id, lock_ttl)
+            .await?
+        else {
             ctx.db
                 .record_retryable_error(
                     job.job_id,
                     &ctx.instance_id,
-                    &msg,
-                    retry::retry_delay(job.attempts),
+                    "energy rental order already in progress",
+                    ctx.cfg.jobs.tick_interval,
                 )
                 .await?;
             return Ok(true);
         };
-
-        let txid_hex = attempt.txid.as_ref().unwrap();
-        let bytes =
-            hex::decode(txid_hex.trim_start_matches("0x")).context("decode rental txid hex")?;
-        if bytes.len() != 32 {
-            anyhow::bail!("rental txid is not 32 bytes: {txid_hex}");
+        let ordered = order_energy_rental(ctx, job, &intent).await;
+        if let Err(err) = ctx.db.unlock_tron_rental_order(job.job_id, &lock).await {
+            tracing::warn!(job_id = job.job_id, err = %err, "release rental order lock failed");
         }
-        let mut out = [0u8; 32];
-        out.copy_from_slice(&bytes);
-
-        let mut request_json = existing
-            .as_ref()
-            .and_then(|r| r.request_json.clone())
-            .unwrap_or_else(|| serde_json::json!({}));
-        if !request_json.is_object() {
-            request_json = serde_json::json!({});
+        match ordered? {
+            Some(txid) => txid,
+            None => return Ok(true),
         }
-        request_json["order"] =
-            serde_json::to_value(&rendered_req).unwrap_or(serde_json::Value::Null);
-        request_json["order_meta"] = serde_json::json!({
-            "duration_hours": duration_hours,
-            "amount_units": units,
-        });
+    };
 
-        let mut response_json = existing
-            .as_ref()
-            .and_then(|r| r.response_json.clone())
-            .unwrap_or_else(|| serde_json::json!({}));
-        if !response_json.is_object() {
-            response_json = serde_json::json!({});
-        }
-        response_json["order"] = attempt
-            .response_json
-            .clone()
-            .unwrap_or(serde_json::Value::Null);
+    ctx.db
+        .record_tron_txid(job.job_id, &ctx.instance_id, txid)
+        .await?;
+    Ok(true)
+}
 
-        // Update rental row with txid once known.
+/// Places an energy rental order for a resold `DelegateResource` job and records its txid. Returns
+/// `None` (with a retry scheduled) when no provider filled the order. Callers must hold the job's
+/// rental order lock.
+async fn order_energy_rental(
+    ctx: &JobCtx,
+    job: &SolverJob,
+    intent: &crate::tron_backend::DelegateResourceIntent,
+) -> Result<Option<[u8; 32]>> {
+    // Re-read under the lock: a tick that held it before us may have just recorded an order.
+    let existing = ctx.db.get_tron_rental_for_job(job.job_id).await?;
+    if let Some(txid) = existing.as_ref().and_then(|r| r.txid) {
+        return Ok(Some(txid));
+    }
+
+    let receiver = tron::TronAddress::from_evm(intent.receiver);
+    let balance_sun_i64 =
+        i64::try_from(intent.balanceSun).context("balanceSun out of i64 range")?;
+    let lock_period_i64 =
+        i64::try_from(intent.lockPeriod).context("lockPeriod out of i64 range")?;
+
+    let mut recv = [0u8; 20];
+    recv.copy_from_slice(intent.receiver.as_slice());
+
+    let totals = ctx.tron.energy_stake_totals().await?;
+    let units = tron::resources::resource_units_for_min_trx_sun(
+        u64::try_from(balance_sun_i64.max(0)).unwrap_or(0),
+        totals,
+        ctx.cfg.tron.resell_energy_headroom_ppm,
+    );
+    let duration_hours =
+        duration_hours_for_lock_period_blocks(u64::try_from(lock_period_i64.max(0)).unwrap_or(0));
+
+    // Prefer the pre-quoted provider (if present).
+    let preferred = existing.as_ref().map(|r| r.provider.as_str());
+    let ctx_rent = tron::RentalContext {
+        resource: tron::RentalResourceKind::Energy,
+        amount: units,
+        lock_period: Some(u64::try_from(lock_period_i64.max(0)).unwrap_or(0)),
+        duration_hours: Some(duration_hours),
+        balance_sun: Some(u64::try_from(balance_sun_i64.max(0)).unwrap_or(0)),
+        address_base58check: receiver.to_base58check(),
+        address_hex41: format!("0x{}", hex::encode(receiver.prefixed_bytes())),
+        address_evm_hex: format!("{:#x}", receiver.evm()),
+        txid: None,
+    };
+
+    let outcome = lease::with_lease_heartbeat(
+        ctx,
+        job.job_id,
+        ctx.tron.rent_energy(
+            &ctx.db,
+            EnergyRentalOrder {
+                ctx: &ctx_rent,
+                preferred,
+                require_txid: true,
+                trx_usd: ctx.trx_usd,
+                quote_cost: false,
+                skip_intent: Some((job.intent_id, job.intent_type)),
+            },
+        ),
+    )
+    .await?;
+
+    let Some(PlacedEnergyRental {
+        rendered_request: rendered_req,
+        attempt,
+        ..
+    }) = outcome.placed
+    else {
+        let msg = outcome
+            .last_err
+            .unwrap_or_else(|| "no energy rental providers succeeded".to_string());
         ctx.db
-            .upsert_tron_rental(
+            .record_retryable_error(
                 job.job_id,
-                &attempt.provider,
-                "energy",
-                recv,
-                balance_sun_i64,
-                lock_period_i64,
-                attempt.order_id.as_deref(),
-                Some(out),
-                Some(&request_json),
-                Some(&response_json),
+                &ctx.instance_id,
+                &msg,
+                retry::retry_delay(ctx, job.attempts),
             )
-            .await
-            .ok();
-
-        out
+            .await?;
+        return Ok(None);
     };
 
+    let txid_hex = attempt.txid.as_ref().unwrap();
+    let bytes = hex::decode(txid_hex.trim_start_matches("0x")).context("decode rental txid hex")?;
+    if bytes.len() != 32 {
+        anyhow::bail!("rental txid is not 32 bytes: {txid_hex}");
+    }
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&bytes);
+
+    let mut request_json = existing
+        .as_ref()
+        .and_then(|r| r.request_json.clone())
+        .unwrap_or_else(|| serde_json::json!({}));
+    if !request_json.is_object() {
+        request_json = serde_json::json!({});
+    }
+    request_json["order"] = serde_json::to_value(&rendered_req).unwrap_or(serde_json::Value::Null);
+    request_json["order_meta"] = serde_json::json!({
+        "duration_hours": duration_hours,
+        "amount_units": units,
+    });
+
+    let mut response_json = existing
+        .as_ref()
+        .and_then(|r| r.response_json.clone())
+        .unwrap_or_else(|| serde_json::json!({}));
+    if !response_json.is_object() {
+        response_json = serde_json::json!({});
+    }
+    response_json["order"] = attempt
+        .response_json
+        .clone()
+        .unwrap_or(serde_json::Value::Null);
+
+    // Update rental row with txid once known.
     ctx.db
-        .record_tron_txid(job.job_id, &ctx.instance_id, txid)
-        .await?;
-    Ok(true)
+        .upsert_tron_rental(
+            job.job_id,
+            &attempt.provider,
+            "energy",
+            recv,
+            balance_sun_i64,
+            lock_period_i64,
+            attempt.order_id.as_deref(),
+            Some(out),
+            Some(&request_json),
+            Some(&response_json),
+        )
+        .await
+        .ok();
+
+    Ok(Some(out))
 }
 
 async fn handle_prepare_error(
     ctx: &JobCtx,
     job: &SolverJob,
+    id: B256,
     ty: IntentType,
     msg: &str,
 ) -> Result<()> {
+    // The intent's calldata fixes the tx size, so an oversized tx won't shrink on retry. Nothing
+    // was signed yet, so hand the claim back and recover the deposit.
+    if msg.contains("tx_too_large:") {
+        tracing::warn!(job_id = job.job_id, err = %msg, "tron tx too large");
+        if ty == IntentType::TriggerSmartContract
+            && let Some((contract, selector)) =
+                decode_trigger_contract_and_selector(&job.intent_specs)
+        {
+            let _ = ctx.db.breaker_record_failure(contract, selector, msg).await;
+        }
+        return hub_flow::unclaim_job(ctx, job, id, JobState::Claimed, msg).await;
+    }
+
     // If this is a trigger smart contract, record a breaker on likely-deterministic failures.
     if ty == IntentType::TriggerSmartContract
         && !looks_like_tron_server_busy(msg)
@@ -475,7 +590,7 @@ async fn handle_prepare_error(
             job.job_id,
             &ctx.instance_id,
             msg,
-            retry::retry_delay(job.attempts),
+            retry::retry_delay_for(ctx, job.attempts, ErrorClass::of_tron(msg)),
         )
         .await?;
     Ok(())
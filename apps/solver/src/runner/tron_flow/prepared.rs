@@ -1,11 +1,16 @@
-use super::super::{JobCtx, SolverJob, lease, retry};
+use super::super::retry::ErrorClass;
+use super::super::{JobCtx, SolverJob, hub_flow, lease, looks_like_tron_out_of_energy, retry};
 use crate::{
     db::{TronSignedTxRow, TronTxCostsRow},
-    types::IntentType,
+    types::{IntentType, JobState},
 };
+use alloy::primitives::B256;
 use anyhow::{Context, Result};
 use std::time::Instant;
 
+/// Treat a signed tx as expired slightly early: it still has to reach a block producer.
+const TRON_TX_EXPIRY_MARGIN_MS: i64 = 3_000;
+
 pub(crate) async fn process_tron_prepared_state(
     ctx: &JobCtx,
     job: &SolverJob,
@@ -17,12 +22,17 @@ pub(crate) async fn process_tron_prepared_state(
                 job.job_id,
                 &ctx.instance_id,
                 "missing tron_txid",
-                retry::retry_delay(job.attempts),
+                retry::retry_delay(ctx, job.attempts),
             )
             .await?;
         return Ok(());
     };
 
+    if hub_flow::abandon_due(ctx, job) && final_tx_cannot_land(ctx, final_txid).await? {
+        let id = B256::from_slice(&job.intent_id);
+        return hub_flow::unclaim_stalled_job(ctx, job, id, JobState::TronPrepared).await;
+    }
+
     let plan = ctx.db.list_tron_signed_txs_for_job(job.job_id).await?;
     let txs = if plan.is_empty() {
         vec![TronSignedTxRow {
@@ -30,6 +40,7 @@ pub(crate) async fn process_tron_prepared_state(
             txid: final_txid,
             tx_bytes: ctx.db.load_tron_signed_tx_bytes(final_txid).await?,
             fee_limit_sun: None,
+            fee_limit_estimate_sun: None,
             energy_required: None,
             tx_size_bytes: None,
         }]
@@ -37,14 +48,13 @@ pub(crate) async fn process_tron_prepared_state(
         plan
     };
 
-    for row in &txs {
+    let mut pre_txs_reserved = false;
+    for (i, row) in txs.iter().enumerate() {
         lease::renew_job_lease(ctx, job.job_id).await?;
 
         // If already included, skip.
-        let included = match ctx.tron.fetch_transaction_info(row.txid).await {
-            Ok(Some(info)) => info.block_number > 0,
-            _ => false,
-        };
+        let info = ctx.tron.fetch_transaction_info(row.txid).await;
+        let included = matches!(&info, Ok(Some(info)) if info.block_number > 0);
         if included {
             continue;
         }
@@ -54,6 +64,38 @@ pub(crate) async fn process_tron_prepared_state(
             continue;
         }
 
+        // A tx that sat here past its expiration (e.g. backing off through a node outage) can
+        // never be accepted. Only rebuild once the lookup above definitively said "not included".
+        if info.is_ok() && signed_tx_expired(&row.tx_bytes) {
+            tracing::warn!(
+                job_id = job.job_id,
+                step = %row.step,
+                txid = %hex::encode(row.txid),
+                "signed tron tx expired before broadcast; rebuilding"
+            );
+            ctx.db
+                .discard_tron_plan(
+                    job.job_id,
+                    &ctx.instance_id,
+                    &format!("tron tx expired before broadcast (step={})", row.step),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        anyhow::ensure!(!ctx.cfg.dry_run, "dry run: refusing to broadcast tron tx");
+        // Reserve every pre-tx still ahead of us in one go, so the plan doesn't stall halfway
+        // through its consolidation. Running out of tick budget isn't a failure: no attempt counts.
+        if row.txid != final_txid && !pre_txs_reserved {
+            let pending = txs[i..].iter().filter(|r| r.txid != final_txid).count();
+            if !ctx.pre_tx_budget.try_take(pending as u64) {
+                ctx.db
+                    .defer_job(job.job_id, &ctx.instance_id, ctx.cfg.jobs.tick_interval)
+                    .await?;
+                return Ok(());
+            }
+            pre_txs_reserved = true;
+        }
         let _permit = ctx
             .tron_broadcast_sem
             .clone()
@@ -68,6 +110,7 @@ pub(crate) async fn process_tron_prepared_state(
         )
         .await;
         let ms = started.elapsed().as_millis() as u64;
+        let broadcast_at = Instant::now();
         match res {
             Ok(()) => {
                 ctx.telemetry.tron_tx_ok();
@@ -77,27 +120,54 @@ pub(crate) async fn process_tron_prepared_state(
                 ctx.telemetry.tron_tx_err();
                 ctx.telemetry.tron_broadcast_ms(false, ms);
                 let msg = err.to_string();
+                let mut delay =
+                    retry::retry_delay_for(ctx, job.attempts, ErrorClass::of_tron(&msg));
+                if looks_like_tron_out_of_energy(&format!("{err:#}")) {
+                    ctx.telemetry.tron_out_of_energy();
+                    // Retrying the same key right away can't succeed; top it up (or wait out the
+                    // cooldown) instead of burning attempts.
+                    match ctx
+                        .tron
+                        .handle_out_of_energy(
+                            &ctx.db,
+                            &row.tx_bytes,
+                            row.txid,
+                            row.energy_required,
+                            ctx.trx_usd,
+                            Some((job.intent_id, job.intent_type)),
+                        )
+                        .await
+                    {
+                        Ok(outcome) if !outcome.rental_requested => {
+                            delay = delay.max(outcome.cooldown);
+                        }
+                        Ok(_) => {}
+                        Err(err) => tracing::warn!(err = %err, "handle out_of_energy failed"),
+                    }
+                }
                 ctx.db
-                    .record_retryable_error(
-                        job.job_id,
-                        &ctx.instance_id,
-                        &msg,
-                        retry::retry_delay(job.attempts),
-                    )
+                    .record_retryable_error(job.job_id, &ctx.instance_id, &msg, delay)
                     .await?;
                 return Ok(());
             }
         }
 
         // Wait until included so subsequent steps are reliably funded.
+        let inclusion_timeout =
+            std::time::Duration::from_secs(ctx.cfg.jobs.tron_inclusion_timeout_secs);
         let wait_inclusion = async {
             let started = Instant::now();
             loop {
-                if started.elapsed() > std::time::Duration::from_secs(60) {
+                if started.elapsed() > inclusion_timeout {
                     anyhow::bail!("tron tx inclusion timeout");
                 }
                 match ctx.tron.fetch_transaction_info(row.txid).await {
                     Ok(Some(info)) if info.block_number > 0 => {
+                        ctx.telemetry.tron_inclusion_ms(
+                            job.intent_type,
+                            row.txid == final_txid,
+                            broadcast_at.elapsed().as_millis() as u64,
+                        );
                         let receipt = info.receipt.as_ref();
                         let costs = TronTxCostsRow {
                             fee_sun: Some(info.fee),
@@ -118,18 +188,26 @@ pub(crate) async fn process_tron_prepared_state(
                             .await;
                         return Ok(());
                     }
-                    _ => tokio::time::sleep(std::time::Duration::from_secs(1)).await,
+                    _ => tokio::time::sleep(ctx.cfg.jobs.tron_inclusion_poll_interval).await,
                 }
             }
         };
         if let Err(err) = lease::with_lease_heartbeat(ctx, job.job_id, wait_inclusion).await {
+            // Slow to include is not the same as dropped: if the node still knows the tx, move on
+            // like we do for pending txs above instead of scheduling a re-broadcast.
+            if ctx.tron.tx_is_known(row.txid).await {
+                tracing::warn!(
+                    job_id = job.job_id,
+                    step = %row.step,
+                    txid = %hex::encode(row.txid),
+                    "tron tx not yet included after timeout but known to node; continuing"
+                );
+                continue;
+            }
+            let msg = err.to_string();
+            let delay = retry::retry_delay_for(ctx, job.attempts, ErrorClass::of_tron(&msg));
             ctx.db
-                .record_retryable_error(
-                    job.job_id,
-                    &ctx.instance_id,
-                    &err.to_string(),
-                    retry::retry_delay(job.attempts),
-                )
+                .record_retryable_error(job.job_id, &ctx.instance_id, &msg, delay)
                 .await?;
             return Ok(());
         }
@@ -142,8 +220,51 @@ pub(crate) async fn process_tron_prepared_state(
             .await;
     }
 
+    // A TriggerSmartContract plan that only approved the USDT pull ends in the approve, not the
+    // call. Now that it landed, go back and plan the call itself (emulated, with a real estimate).
+    if ty == IntentType::TriggerSmartContract
+        && let Some(last) = txs.last()
+        && crate::tron_backend::fill_mismatch(&last.tx_bytes, ty, &job.intent_specs, None).is_some()
+    {
+        ctx.db
+            .discard_tron_plan(
+                job.job_id,
+                &ctx.instance_id,
+                "usdt approve included; planning the call",
+            )
+            .await?;
+        return Ok(());
+    }
+
     ctx.db
         .record_tron_txid(job.job_id, &ctx.instance_id, final_txid)
         .await?;
     Ok(())
 }
+
+/// Whether the signed fill tx is definitely dead: not included, not pending and past its
+/// expiration. Only then is it safe to give up the claim; otherwise the fill could still land and
+/// we would pay for it without being able to prove it.
+async fn final_tx_cannot_land(ctx: &JobCtx, final_txid: [u8; 32]) -> Result<bool> {
+    match ctx.tron.fetch_transaction_info(final_txid).await {
+        Ok(Some(info)) if info.block_number > 0 => return Ok(false),
+        Ok(_) => {}
+        Err(_) => return Ok(false),
+    }
+    if ctx.tron.tx_is_known(final_txid).await {
+        return Ok(false);
+    }
+    let tx_bytes = ctx.db.load_tron_signed_tx_bytes(final_txid).await?;
+    Ok(signed_tx_expired(&tx_bytes))
+}
+
+fn signed_tx_expired(tx_bytes: &[u8]) -> bool {
+    let Ok(expiration_ms) = tron::signed_tx_expiration_ms(tx_bytes) else {
+        return false;
+    };
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
+        .unwrap_or(0);
+    expiration_ms <= now_ms.saturating_add(TRON_TX_EXPIRY_MARGIN_MS)
+}
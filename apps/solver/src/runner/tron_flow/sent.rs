@@ -1,11 +1,17 @@
+use super::super::retry::ErrorClass;
 use super::super::{
     JobCtx, SolverJob, decode_trigger_contract_and_selector, lease, retry,
 };
 use crate::{config::TronMode, db::TronProofRow, db::TronTxCostsRow, types::IntentType};
 use alloy::primitives::B256;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::time::Instant;
 
+/// Builds the inclusion proof for the job's final Tron tx.
+///
+/// Multi-step plans (consolidation / approve pre-txs) only prove the final step: the pool's
+/// `proveIntentFill` matches a single tx against the intent and marks it solved on the first
+/// proof, so there is no onchain way to prove several txs for one intent.
 pub(crate) async fn process_tron_sent_state(
     ctx: &JobCtx,
     job: &SolverJob,
@@ -18,15 +24,49 @@ pub(crate) async fn process_tron_sent_state(
                 job.job_id,
                 &ctx.instance_id,
                 "missing tron_txid",
-                retry::retry_delay(job.attempts),
+                retry::retry_delay(ctx, job.attempts),
             )
             .await?;
         return Ok(());
     };
+    // The tx was broadcast right before the job entered `tron_sent`, so the head on the first
+    // tick here anchors the inclusion window.
+    let broadcast_block = match job.tron_broadcast_block {
+        Some(block) => u64::try_from(block).ok(),
+        None if ctx.cfg.jobs.tron_max_inclusion_scan_blocks.is_some() => {
+            match ctx.tron.head_block_number().await {
+                Ok(Some(head)) => {
+                    ctx.db
+                        .record_tron_broadcast_block(
+                            job.job_id,
+                            &ctx.instance_id,
+                            i64::try_from(head).unwrap_or(i64::MAX),
+                        )
+                        .await?;
+                    Some(head)
+                }
+                Ok(None) => None,
+                Err(err) => {
+                    tracing::warn!(id = %id, err = %format!("{err:#}"), "fetch tron head failed");
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+    let permit = ctx
+        .tron_proof_sem
+        .clone()
+        .acquire_owned()
+        .await
+        .context("acquire tron_proof_sem")?;
     tracing::info!(id = %id, "building tron proof");
     let started = Instant::now();
-    let tron = match lease::with_lease_heartbeat(ctx, job.job_id, ctx.tron.build_proof(txid)).await
-    {
+    let res =
+        lease::with_lease_heartbeat(ctx, job.job_id, ctx.tron.build_proof(txid, broadcast_block))
+            .await;
+    drop(permit);
+    let tron = match res {
         Ok(v) => v,
         Err(err) => {
             ctx.telemetry
@@ -59,7 +99,10 @@ pub(crate) async fn process_tron_sent_state(
                         let mut mismatch = false;
                         if ctx.cfg.tron.emulation_enabled
                             && ctx.cfg.tron.mode == TronMode::Grpc
-                            && let Ok(Some(emu)) = ctx.db.get_intent_emulation(job.intent_id).await
+                            && let Ok(Some(emu)) = ctx
+                                .db
+                                .get_intent_emulation(&job.pool_id, job.intent_id)
+                                .await
                         {
                             mismatch = emu.ok;
                         }
@@ -89,19 +132,75 @@ pub(crate) async fn process_tron_sent_state(
                 retry::record_fatal(ctx, job, &msg).await?;
                 return Ok(());
             }
+            // Dropped after broadcast: hand the stored signed tx back to `tron_prepared`. Txs we
+            // hold no signed bytes for (e.g. sent by a rental provider) keep retrying below.
+            if msg.contains("tx_not_included:") && ctx.db.has_tron_signed_tx(txid).await? {
+                tracing::warn!(
+                    id = %id,
+                    txid = %hex::encode(txid),
+                    err = %msg,
+                    "tron tx not included; re-broadcasting"
+                );
+                ctx.db
+                    .record_tron_not_included(job.job_id, &ctx.instance_id, &msg)
+                    .await?;
+                ctx.telemetry
+                    .job_state_transition(job.intent_type, "tron_sent", "tron_prepared");
+                return Ok(());
+            }
+            if msg.contains("proof_too_large:") {
+                tracing::warn!(id = %id, txid = %hex::encode(txid), err = %msg, "tron proof too large");
+                if ty == IntentType::TriggerSmartContract
+                    && let Some((contract, selector)) =
+                        decode_trigger_contract_and_selector(&job.intent_specs)
+                {
+                    let _ = ctx
+                        .db
+                        .breaker_record_failure_weighted(contract, selector, &msg, 1)
+                        .await;
+                }
+                retry::record_fatal(ctx, job, &msg).await?;
+                return Ok(());
+            }
+            let delay = retry::retry_delay_for(ctx, job.attempts, ErrorClass::of_tron(&msg));
             ctx.db
-                .record_retryable_error(
-                    job.job_id,
-                    &ctx.instance_id,
-                    &msg,
-                    retry::retry_delay(job.attempts),
-                )
+                .record_retryable_error(job.job_id, &ctx.instance_id, &msg, delay)
                 .await?;
             return Ok(());
         }
     };
     ctx.telemetry
         .tron_proof_ms(true, started.elapsed().as_millis() as u64);
+
+    // A valid proof only shows *a* tx was included; make sure it's the one the intent asked for.
+    if ctx.cfg.tron.fill_verification_enabled {
+        let tron_usdt = match ty {
+            IntentType::UsdtTransfer => match ctx.hub.v3_tron_usdt().await {
+                Ok(v) => Some(v),
+                Err(err) => {
+                    let msg = format!("load V3.tronUsdt: {err:#}");
+                    ctx.db
+                        .record_retryable_error(
+                            job.job_id,
+                            &ctx.instance_id,
+                            &msg,
+                            retry::retry_delay(ctx, job.attempts),
+                        )
+                        .await?;
+                    return Ok(());
+                }
+            },
+            _ => None,
+        };
+        if let Some(reason) =
+            crate::tron_backend::fill_mismatch(&tron.encoded_tx, ty, &job.intent_specs, tron_usdt)
+        {
+            tracing::warn!(id = %id, txid = %hex::encode(txid), reason = %reason, "tron fill mismatch");
+            retry::record_fatal(ctx, job, &format!("fill_mismatch: {reason}")).await?;
+            return Ok(());
+        }
+    }
+
     let proof_row = TronProofRow {
         blocks: tron.blocks.into_iter().collect(),
         encoded_tx: tron.encoded_tx,
@@ -112,7 +211,9 @@ pub(crate) async fn process_tron_sent_state(
             .collect(),
         index_dec: tron.index.to_string(),
     };
-    ctx.db.save_tron_proof(txid, &proof_row).await?;
+    ctx.db
+        .save_tron_proof(txid, &proof_row, ctx.cfg.jobs.tron_proof_compress)
+        .await?;
 
     if let Ok(Some(info)) = ctx.tron.fetch_transaction_info(txid).await {
         let receipt = info.receipt.as_ref();
@@ -0,0 +1,52 @@
+use std::time::{Duration, Instant};
+
+/// Short-circuit for Tron outages: once a health ping fails, ticks skip discovery and leasing
+/// without re-pinging until the cooldown ends.
+#[derive(Debug, Default)]
+pub(super) struct TronHealthGate {
+    open_until: Option<Instant>,
+    /// Failed pings since the last successful one.
+    failures: u32,
+}
+
+impl TronHealthGate {
+    /// Whether the gate is still cooling down from a failed ping.
+    pub(super) fn is_open(&self, now: Instant) -> bool {
+        self.open_until.is_some_and(|until| now < until)
+    }
+
+    /// Records a failed ping. Returns the number of consecutive failures.
+    pub(super) fn on_failure(&mut self, cooldown: Duration, now: Instant) -> u32 {
+        self.open_until = Some(now + cooldown);
+        self.failures += 1;
+        self.failures
+    }
+
+    /// Records a successful ping. Returns true if the node was unhealthy before.
+    pub(super) fn on_success(&mut self) -> bool {
+        self.open_until = None;
+        std::mem::take(&mut self.failures) > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failure_opens_gate_for_cooldown() {
+        let mut gate = TronHealthGate::default();
+        let t0 = Instant::now();
+        let cooldown = Duration::from_secs(30);
+        assert!(!gate.is_open(t0));
+
+        assert_eq!(gate.on_failure(cooldown, t0), 1);
+        assert!(gate.is_open(t0 + Duration::from_secs(29)));
+        assert!(!gate.is_open(t0 + cooldown));
+
+        assert_eq!(gate.on_failure(cooldown, t0 + cooldown), 2);
+        assert!(gate.on_success());
+        assert!(!gate.is_open(t0 + cooldown));
+        assert!(!gate.on_success());
+    }
+}
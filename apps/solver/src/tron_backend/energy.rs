@@ -0,0 +1,225 @@
+use super::{
+    EnergyRentalOrder, PlacedEnergyRental, TronBackend, TronPreparedTx, grpc,
+    utils::tron_tx_owner_address,
+};
+use crate::{config::TronMode, db::SolverDb};
+use anyhow::{Context, Result};
+use prost::Message;
+use std::time::{Duration, Instant};
+
+/// What `handle_out_of_energy` did about a key that ran out of energy.
+#[derive(Debug, Clone, Copy)]
+pub struct OutOfEnergyOutcome {
+    /// A rental provider accepted an ENERGY order for the key, so a normal retry should succeed.
+    pub rental_requested: bool,
+    /// How long the key is skipped for new plans.
+    pub cooldown: Duration,
+}
+
+/// ENERGY rented for a transfer's sender ahead of its broadcast.
+#[derive(Debug, Clone)]
+pub struct SponsoredEnergy {
+    pub provider: String,
+    pub energy: u64,
+    /// The transfer's sender, which received the energy.
+    pub receiver_evm: [u8; 20],
+    /// Provider-quoted price; `None` when the provider has no quote endpoint.
+    pub cost_sun: Option<i64>,
+    pub order: PlacedEnergyRental,
+}
+
+impl TronBackend {
+    /// Reacts to a broadcast rejected for insufficient energy: puts the sending key on cooldown
+    /// and asks the configured rental providers to top it up (see [`Self::rent_energy_for_tx`]).
+    pub async fn handle_out_of_energy(
+        &self,
+        db: &SolverDb,
+        tx_bytes: &[u8],
+        txid: [u8; 32],
+        energy_required: Option<i64>,
+        trx_usd: Option<f64>,
+        skip_intent: Option<([u8; 32], i16)>,
+    ) -> Result<OutOfEnergyOutcome> {
+        let tx = tron::protocol::Transaction::decode(tx_bytes).context("decode signed tx bytes")?;
+        let owner = tron_tx_owner_address(&tx).context("derive tron tx sender")?;
+
+        let cooldown = Duration::from_secs(self.cfg.out_of_energy_cooldown_secs);
+        if !cooldown.is_zero() {
+            let mut cooldowns = self.energy_cooldowns.write().await;
+            cooldowns.retain(|_, until| *until > Instant::now());
+            cooldowns.insert(owner, Instant::now() + cooldown);
+        }
+
+        let energy = u64::try_from(energy_required.unwrap_or(0)).unwrap_or(0);
+        let rental_requested = self
+            .rent_energy_for_tx(db, tx_bytes, txid, energy, trx_usd, skip_intent)
+            .await?;
+
+        Ok(OutOfEnergyOutcome {
+            rental_requested,
+            cooldown,
+        })
+    }
+
+    /// Best-effort rental of `energy` ENERGY for the sender of the signed `tx_bytes`, ahead of (or
+    /// after a failed) broadcast. Goes through [`Self::rent_energy`], so USD caps and provider
+    /// freezes apply. Returns whether a provider accepted the order; without one the tx burns TRX
+    /// for its energy up to its fee limit.
+    pub async fn rent_energy_for_tx(
+        &self,
+        db: &SolverDb,
+        tx_bytes: &[u8],
+        txid: [u8; 32],
+        energy: u64,
+        trx_usd: Option<f64>,
+        skip_intent: Option<([u8; 32], i16)>,
+    ) -> Result<bool> {
+        if self.cfg.mode != TronMode::Grpc
+            || self.cfg.energy_rental_providers.is_empty()
+            || energy == 0
+        {
+            return Ok(false);
+        }
+        let tx = tron::protocol::Transaction::decode(tx_bytes).context("decode signed tx bytes")?;
+        let owner = tron_tx_owner_address(&tx).context("derive tron tx sender")?;
+        let owner =
+            tron::TronAddress::from_evm(alloy::primitives::Address::from_slice(&owner[1..]));
+
+        let ctx_rent = tron::RentalContext {
+            resource: tron::RentalResourceKind::Energy,
+            amount: energy,
+            lock_period: None,
+            duration_hours: None,
+            balance_sun: None,
+            address_base58check: owner.to_base58check(),
+            address_hex41: format!("0x{}", hex::encode(owner.prefixed_bytes())),
+            address_evm_hex: format!("{:#x}", owner.evm()),
+            txid: Some(format!("0x{}", hex::encode(txid))),
+        };
+        let outcome = self
+            .rent_energy(
+                db,
+                EnergyRentalOrder {
+                    ctx: &ctx_rent,
+                    preferred: None,
+                    require_txid: false,
+                    trx_usd,
+                    quote_cost: false,
+                    skip_intent,
+                },
+            )
+            .await?;
+        let Some(placed) = outcome.placed else {
+            tracing::warn!(
+                owner = %owner.to_base58check(),
+                energy,
+                err = outcome.last_err.as_deref().unwrap_or("no providers available"),
+                "energy rental request failed"
+            );
+            return Ok(false);
+        };
+        tracing::info!(
+            provider = %placed.attempt.provider,
+            energy,
+            order_id = placed.attempt.order_id.as_deref().unwrap_or(""),
+            "energy rental requested"
+        );
+        Ok(true)
+    }
+
+    /// For each address, whether it's still cooling down after an OUT_OF_ENERGY broadcast.
+    pub(super) async fn energy_cooldown_flags(&self, addrs: &[tron::TronAddress]) -> Vec<bool> {
+        let cooldowns = self.energy_cooldowns.read().await;
+        let now = Instant::now();
+        addrs
+            .iter()
+            .map(|a| {
+                cooldowns
+                    .get(&a.prefixed_bytes())
+                    .is_some_and(|until| *until > now)
+            })
+            .collect()
+    }
+
+    /// Rents the ENERGY `tx` needs beyond what its sender has staked, so the transfer doesn't
+    /// fail (or burn TRX) when the destination is a contract that's expensive to call.
+    ///
+    /// Only active with `TRON_SPONSOR_USDT_TRANSFER_ENERGY`. Returns `Ok(None)` when the sender
+    /// already covers the estimate or no provider accepted the order; the tx then pays for the
+    /// shortfall by burning TRX up to its fee limit, as before.
+    pub async fn sponsor_transfer_energy(
+        &self,
+        db: &SolverDb,
+        tx: &TronPreparedTx,
+        trx_usd: Option<f64>,
+        skip_intent: Option<([u8; 32], i16)>,
+    ) -> Result<Option<SponsoredEnergy>> {
+        if !self.cfg.sponsor_usdt_transfer_energy
+            || self.cfg.mode != TronMode::Grpc
+            || self.cfg.energy_rental_providers.is_empty()
+        {
+            return Ok(None);
+        }
+        let required = u64::try_from(tx.energy_required.unwrap_or(0)).unwrap_or(0);
+        if required == 0 {
+            return Ok(None);
+        }
+
+        let signed = tron::protocol::Transaction::decode(tx.tx_bytes.as_slice())
+            .context("decode signed tx bytes")?;
+        let owner = tron_tx_owner_address(&signed).context("derive tron tx sender")?;
+        let owner =
+            tron::TronAddress::from_evm(alloy::primitives::Address::from_slice(&owner[1..]));
+        let available = grpc::fetch_account_resources(&self.cfg, &self.telemetry, owner)
+            .await
+            .context("fetch sender account resources")?
+            .energy_available();
+        let shortfall = required.saturating_sub(available);
+        if shortfall == 0 {
+            return Ok(None);
+        }
+
+        let ctx_rent = tron::RentalContext {
+            resource: tron::RentalResourceKind::Energy,
+            amount: shortfall,
+            lock_period: None,
+            duration_hours: None,
+            balance_sun: None,
+            address_base58check: owner.to_base58check(),
+            address_hex41: format!("0x{}", hex::encode(owner.prefixed_bytes())),
+            address_evm_hex: format!("{:#x}", owner.evm()),
+            txid: Some(format!("0x{}", hex::encode(tx.txid))),
+        };
+        let outcome = self
+            .rent_energy(
+                db,
+                EnergyRentalOrder {
+                    ctx: &ctx_rent,
+                    preferred: None,
+                    require_txid: false,
+                    trx_usd,
+                    quote_cost: true,
+                    skip_intent,
+                },
+            )
+            .await?;
+        let Some(placed) = outcome.placed else {
+            return Ok(None);
+        };
+        tracing::info!(
+            provider = %placed.attempt.provider,
+            energy = shortfall,
+            order_id = placed.attempt.order_id.as_deref().unwrap_or(""),
+            "sponsored usdt transfer energy"
+        );
+        let mut receiver_evm = [0u8; 20];
+        receiver_evm.copy_from_slice(owner.evm().as_slice());
+        Ok(Some(SponsoredEnergy {
+            provider: placed.attempt.provider.clone(),
+            energy: shortfall,
+            receiver_evm,
+            cost_sun: placed.cost_trx.map(|c| (c * 1e6).round() as i64),
+            order: placed,
+        }))
+    }
+}
@@ -1,9 +1,65 @@
 use super::connect_grpc;
-use crate::{config::TronConfig, metrics::SolverTelemetry};
-use alloy::sol_types::SolValue;
+use crate::{config::TronConfig, metrics::SolverTelemetry, tron_backend::EmulationRevert};
+use alloy::sol_types::{Panic, Revert, SolError, SolValue};
 use anyhow::{Context, Result};
 use tron::{TronAddress, TronGrpc, TronWallet};
 
+/// Emulation reverted. `Display` keeps the `emulation_revert:` marker that string-based callers
+/// match on; `precheck_emulation` downcasts to read the decoded revert.
+#[derive(Debug)]
+pub(crate) struct EmulationRevertError {
+    pub code: i32,
+    pub message: Vec<u8>,
+    pub revert: EmulationRevert,
+    /// Raw revert return data, when the node returned any.
+    pub data: Option<Vec<u8>>,
+}
+
+impl std::fmt::Display for EmulationRevertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "emulation_revert: code={} msg_hex=0x{} msg_utf8={}",
+            self.code,
+            hex::encode(&self.message),
+            String::from_utf8_lossy(&self.message)
+        )?;
+        if let Some(selector) = self.revert.selector {
+            write!(f, " revert_selector=0x{}", hex::encode(selector))?;
+        }
+        if let Some(decoded) = &self.revert.decoded_message {
+            write!(f, " revert_msg={decoded}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for EmulationRevertError {}
+
+/// Decodes EVM revert return data: `Error(string)`, `Panic(uint256)`, or a bare custom error
+/// selector.
+pub(crate) fn decode_revert_data(data: &[u8]) -> EmulationRevert {
+    let Some(selector) = data
+        .get(..4)
+        .map(|s| <[u8; 4]>::try_from(s).expect("4 bytes"))
+    else {
+        return EmulationRevert::default();
+    };
+    let decoded_message = if selector == Revert::SELECTOR {
+        Revert::abi_decode(data).ok().map(|r| r.reason)
+    } else if selector == Panic::SELECTOR {
+        Panic::abi_decode(data)
+            .ok()
+            .map(|p| format!("Panic(0x{:x})", p.code))
+    } else {
+        None
+    };
+    EmulationRevert {
+        selector: Some(selector),
+        decoded_message,
+    }
+}
+
 pub(crate) async fn emulate_trigger_smart_contract_intent(
     cfg: &TronConfig,
     telemetry: &SolverTelemetry,
@@ -15,8 +71,8 @@ pub(crate) async fn emulate_trigger_smart_contract_intent(
     let call_value_i64 =
         i64::try_from(intent.callValueSun).context("callValueSun out of i64 range")?;
 
-    let wallet = TronWallet::new(cfg.private_key).context("init TronWallet")?;
-    let mut grpc = connect_grpc(cfg).await?;
+    let wallet = cfg.wallet(cfg.private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
     emulate_trigger_smart_contract(
         &mut grpc,
         telemetry,
@@ -39,8 +95,8 @@ pub(crate) async fn emulate_usdt_transfer_intent(
     let tron_usdt = hub.v3_tron_usdt().await.context("load V3.tronUsdt")?;
     let data = crate::abi::encode_trc20_transfer(intent.to, intent.amount);
 
-    let wallet = TronWallet::new(cfg.private_key).context("init TronWallet")?;
-    let mut grpc = connect_grpc(cfg).await?;
+    let wallet = cfg.wallet(cfg.private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
     emulate_trigger_smart_contract(
         &mut grpc,
         telemetry,
@@ -70,7 +126,10 @@ pub(crate) async fn emulate_trigger_smart_contract(
     };
 
     let started = std::time::Instant::now();
-    let est = grpc.estimate_energy(msg).await.context("EstimateEnergy")?;
+    let est = grpc
+        .estimate_energy(msg.clone())
+        .await
+        .context("EstimateEnergy")?;
     let ok = est.result.as_ref().map(|r| r.result).unwrap_or(false);
     telemetry.tron_grpc_ms("estimate_energy", ok, started.elapsed().as_millis() as u64);
 
@@ -82,12 +141,15 @@ pub(crate) async fn emulate_trigger_smart_contract(
         match tron::protocol::r#return::ResponseCode::try_from(ret.code) {
             Ok(tron::protocol::r#return::ResponseCode::ContractValidateError)
             | Ok(tron::protocol::r#return::ResponseCode::ContractExeError) => {
-                anyhow::bail!(
-                    "emulation_revert: code={} msg_hex=0x{} msg_utf8={}",
-                    ret.code,
-                    hex::encode(&ret.message),
-                    msg_utf8
-                );
+                let data = fetch_revert_data(grpc, telemetry, msg).await;
+                let revert = data.as_deref().map(decode_revert_data).unwrap_or_default();
+                return Err(EmulationRevertError {
+                    code: ret.code,
+                    message: ret.message,
+                    revert,
+                    data,
+                }
+                .into());
             }
             _ => {
                 anyhow::bail!(
@@ -103,11 +165,114 @@ pub(crate) async fn emulate_trigger_smart_contract(
     Ok(est.energy_required)
 }
 
+/// EstimateEnergy only reports the node's error string; a constant call returns the revert data.
+/// Best-effort: `None` if the call fails or returns nothing.
+async fn fetch_revert_data(
+    grpc: &mut TronGrpc,
+    telemetry: &SolverTelemetry,
+    msg: tron::protocol::TriggerSmartContract,
+) -> Option<Vec<u8>> {
+    let started = std::time::Instant::now();
+    let res = grpc.trigger_constant_contract(msg).await;
+    telemetry.tron_grpc_ms(
+        "trigger_constant_contract_revert_data",
+        res.is_ok(),
+        started.elapsed().as_millis() as u64,
+    );
+    match res {
+        Ok(ext) => ext
+            .constant_result
+            .into_iter()
+            .next()
+            .filter(|d| !d.is_empty()),
+        Err(err) => {
+            tracing::debug!(err = %err, "failed to fetch emulation revert data");
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
     #[test]
     fn emulation_revert_marker_is_stable() {
         let msg = anyhow::anyhow!("emulation_revert: code=3 msg_hex=0x00 msg_utf8=oops");
         assert!(msg.to_string().contains("emulation_revert:"));
+
+        let err = anyhow::Error::from(EmulationRevertError {
+            code: 3,
+            message: b"REVERT opcode executed".to_vec(),
+            revert: EmulationRevert {
+                selector: Some(Revert::SELECTOR),
+                decoded_message: Some("paused".to_string()),
+            },
+            data: None,
+        });
+        let s = err.to_string();
+        assert!(s.starts_with("emulation_revert: code=3 "));
+        assert!(s.contains("revert_selector=0x08c379a0"));
+        assert!(s.contains("revert_msg=paused"));
+        assert!(err.downcast_ref::<EmulationRevertError>().is_some());
+    }
+
+    #[test]
+    fn decode_revert_data_handles_error_panic_and_custom() {
+        let data = Revert {
+            reason: "ERC20: insufficient allowance".to_string(),
+        }
+        .abi_encode();
+        let r = decode_revert_data(&data);
+        assert_eq!(r.selector, Some(Revert::SELECTOR));
+        assert_eq!(
+            r.decoded_message.as_deref(),
+            Some("ERC20: insufficient allowance")
+        );
+
+        let data = Panic {
+            code: U256::from(0x11),
+        }
+        .abi_encode();
+        let r = decode_revert_data(&data);
+        assert_eq!(r.selector, Some(Panic::SELECTOR));
+        assert_eq!(r.decoded_message.as_deref(), Some("Panic(0x11)"));
+
+        let r = decode_revert_data(&[0xde, 0xad, 0xbe, 0xef, 0x00]);
+        assert_eq!(r.selector, Some([0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(r.decoded_message, None);
+
+        assert_eq!(decode_revert_data(&[]), EmulationRevert::default());
+        assert_eq!(decode_revert_data(&[1, 2]), EmulationRevert::default());
+    }
+
+    #[test]
+    fn revert_determinism_classification() {
+        let error = |reason: &str| {
+            decode_revert_data(
+                &Revert {
+                    reason: reason.to_string(),
+                }
+                .abi_encode(),
+            )
+        };
+        assert!(error("invalid recipient").is_deterministic());
+        assert!(!error("Pausable: paused").is_deterministic());
+        assert!(!error("ERC20: insufficient allowance").is_deterministic());
+
+        let panic = decode_revert_data(
+            &Panic {
+                code: U256::from(0x11),
+            }
+            .abi_encode(),
+        );
+        assert!(panic.is_deterministic());
+
+        assert!(decode_revert_data(&[0xde, 0xad, 0xbe, 0xef]).is_deterministic());
+        let paused = decode_revert_data(&crate::tron_backend::EnforcedPause {}.abi_encode());
+        assert!(!paused.is_deterministic());
+
+        assert!(!EmulationRevert::default().is_deterministic());
     }
 }
@@ -3,12 +3,62 @@ use crate::{config::TronConfig, metrics::SolverTelemetry};
 use anyhow::{Context, Result};
 use tron::TronAddress;
 
+/// Cheap liveness probe: the node answers `GetNowBlock2`.
+pub(crate) async fn ping(cfg: &TronConfig, telemetry: &SolverTelemetry) -> Result<()> {
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
+    let started = std::time::Instant::now();
+    let res = grpc.get_now_block2().await;
+    telemetry.tron_grpc_ms(
+        "get_now_block2",
+        res.is_ok(),
+        started.elapsed().as_millis() as u64,
+    );
+    res.context("GetNowBlock2")?;
+    Ok(())
+}
+
+/// Current head block number, as reported by `GetNowBlock2`.
+pub(crate) async fn head_block_number(
+    cfg: &TronConfig,
+    telemetry: &SolverTelemetry,
+) -> Result<u64> {
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
+    super::proof::head_block_number_with(&mut grpc, telemetry).await
+}
+
+/// Fails unless the node's genesis block is `TronConfig.expected_network`'s, so a mainnet
+/// solver can't broadcast to a testnet endpoint (or the reverse).
+pub(crate) async fn verify_network(cfg: &TronConfig, telemetry: &SolverTelemetry) -> Result<()> {
+    let Some(expected) = cfg.expected_network else {
+        return Ok(());
+    };
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
+    let started = std::time::Instant::now();
+    let res = grpc.get_block_by_num2(0).await;
+    telemetry.tron_grpc_ms(
+        "get_block_by_num2",
+        res.is_ok(),
+        started.elapsed().as_millis() as u64,
+    );
+    let genesis = res.context("GetBlockByNum2(0)")?;
+    let want = expected.genesis_block_id();
+    if genesis.blockid.as_slice() != want.as_slice() {
+        anyhow::bail!(
+            "TRON_EXPECTED_NETWORK={expected:?} (genesis 0x{}) but {} serves genesis 0x{}",
+            hex::encode(want),
+            crate::config::redact_url(&cfg.grpc_url),
+            hex::encode(&genesis.blockid)
+        );
+    }
+    Ok(())
+}
+
 pub(crate) async fn fetch_account(
     cfg: &TronConfig,
     telemetry: &SolverTelemetry,
     address: TronAddress,
 ) -> Result<tron::protocol::Account> {
-    let mut grpc = connect_grpc(cfg).await?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
     let started = std::time::Instant::now();
     let account = grpc
         .get_account(address.prefixed_bytes().to_vec())
@@ -23,7 +73,7 @@ pub(crate) async fn fetch_energy_stake_totals(
     telemetry: &SolverTelemetry,
     address: TronAddress,
 ) -> Result<tron::resources::ResourceStakeTotals> {
-    let mut grpc = connect_grpc(cfg).await?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
     let started = std::time::Instant::now();
     let msg = grpc
         .get_account_resource(address.prefixed_bytes().to_vec())
@@ -37,13 +87,32 @@ pub(crate) async fn fetch_energy_stake_totals(
     tron::resources::parse_energy_stake_totals(&msg).context("parse_energy_stake_totals")
 }
 
+pub(crate) async fn fetch_account_resources(
+    cfg: &TronConfig,
+    telemetry: &SolverTelemetry,
+    address: TronAddress,
+) -> Result<tron::resources::AccountResources> {
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
+    let started = std::time::Instant::now();
+    let msg = grpc
+        .get_account_resource(address.prefixed_bytes().to_vec())
+        .await
+        .context("GetAccountResource")?;
+    telemetry.tron_grpc_ms(
+        "get_account_resource",
+        true,
+        started.elapsed().as_millis() as u64,
+    );
+    tron::resources::parse_account_resources(&msg).context("parse_account_resources")
+}
+
 #[allow(dead_code)]
 pub(crate) async fn fetch_net_stake_totals(
     cfg: &TronConfig,
     telemetry: &SolverTelemetry,
     address: TronAddress,
 ) -> Result<tron::resources::ResourceStakeTotals> {
-    let mut grpc = connect_grpc(cfg).await?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
     let started = std::time::Instant::now();
     let msg = grpc
         .get_account_resource(address.prefixed_bytes().to_vec())
@@ -57,7 +126,6 @@ pub(crate) async fn fetch_net_stake_totals(
     tron::resources::parse_net_stake_totals(&msg).context("parse_net_stake_totals")
 }
 
-#[allow(dead_code)]
 pub(crate) async fn fetch_trx_balance_sun(
     cfg: &TronConfig,
     telemetry: &SolverTelemetry,
@@ -71,7 +139,7 @@ pub(crate) async fn fetch_trx_balances_sun(
     telemetry: &SolverTelemetry,
     addresses: &[TronAddress],
 ) -> Result<Vec<i64>> {
-    let mut grpc = connect_grpc(cfg).await?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
     let mut out = Vec::with_capacity(addresses.len());
     for a in addresses {
         let started = std::time::Instant::now();
@@ -92,7 +160,7 @@ pub(crate) async fn fetch_trc20_balance_u64(
     token: TronAddress,
     owner: TronAddress,
 ) -> Result<u64> {
-    let mut grpc = connect_grpc(cfg).await?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
     let msg = tron::protocol::TriggerSmartContract {
         owner_address: owner.prefixed_bytes().to_vec(),
         contract_address: token.prefixed_bytes().to_vec(),
@@ -131,7 +199,7 @@ pub(crate) async fn fetch_trc20_balances_u64(
     token: TronAddress,
     owners: &[TronAddress],
 ) -> Result<Vec<u64>> {
-    let mut grpc = connect_grpc(cfg).await?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
     let mut out = Vec::with_capacity(owners.len());
     for o in owners {
         let msg = tron::protocol::TriggerSmartContract {
@@ -166,6 +234,44 @@ pub(crate) async fn fetch_trc20_balances_u64(
     Ok(out)
 }
 
+pub(crate) async fn fetch_trc20_allowance(
+    cfg: &TronConfig,
+    telemetry: &SolverTelemetry,
+    token: TronAddress,
+    owner: TronAddress,
+    spender: TronAddress,
+) -> Result<alloy::primitives::U256> {
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
+    let msg = tron::protocol::TriggerSmartContract {
+        owner_address: owner.prefixed_bytes().to_vec(),
+        contract_address: token.prefixed_bytes().to_vec(),
+        data: crate::abi::encode_trc20_allowance(owner.evm(), spender.evm()),
+        ..Default::default()
+    };
+
+    let started = std::time::Instant::now();
+    let res = grpc
+        .trigger_constant_contract(msg)
+        .await
+        .context("TriggerConstantContract(allowance)")?;
+    telemetry.tron_grpc_ms(
+        "trigger_constant_contract_allowance",
+        true,
+        started.elapsed().as_millis() as u64,
+    );
+
+    let Some(first) = res.constant_result.first() else {
+        return Ok(alloy::primitives::U256::ZERO);
+    };
+    let mut buf = [0u8; 32];
+    if first.len() >= 32 {
+        buf.copy_from_slice(&first[first.len() - 32..]);
+    } else {
+        buf[32 - first.len()..].copy_from_slice(first);
+    }
+    Ok(alloy::primitives::U256::from_be_bytes(buf))
+}
+
 pub(crate) fn delegated_resource_available_sun(
     account: &tron::protocol::Account,
     resource: tron::protocol::ResourceCode,
@@ -197,7 +303,7 @@ pub(crate) async fn fetch_transaction_info(
     telemetry: &SolverTelemetry,
     txid: [u8; 32],
 ) -> Result<tron::protocol::TransactionInfo> {
-    let mut grpc = connect_grpc(cfg).await?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
     let started = std::time::Instant::now();
     let info = grpc
         .get_transaction_info_by_id(txid)
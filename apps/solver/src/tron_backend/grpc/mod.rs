@@ -1,22 +1,26 @@
-use crate::config::TronConfig;
+use crate::{config::TronConfig, metrics::SolverTelemetry};
 use anyhow::{Context, Result};
-use tron::TronGrpc;
+use std::sync::Arc;
+use tron::{GrpcOptions, TronGrpc};
 
 mod emulate;
 mod fetch;
 mod prepare;
 mod proof;
 
+pub(crate) use emulate::EmulationRevertError;
 pub(super) use emulate::{emulate_trigger_smart_contract_intent, emulate_usdt_transfer_intent};
 pub(super) use fetch::{
-    delegated_resource_available_sun, fetch_account, fetch_energy_stake_totals,
-    fetch_net_stake_totals, fetch_transaction_info, fetch_trc20_balances_u64,
-    fetch_trx_balances_sun,
+    delegated_resource_available_sun, fetch_account, fetch_account_resources,
+    fetch_energy_stake_totals, fetch_net_stake_totals, fetch_transaction_info,
+    fetch_trc20_allowance, fetch_trc20_balances_u64, fetch_trx_balance_sun, fetch_trx_balances_sun,
+    head_block_number, ping, verify_network,
 };
 pub(super) use prepare::{
-    build_trc20_transfer, build_trx_transfer, prepare_delegate_resource,
-    prepare_delegate_resource_with_key, prepare_trigger_smart_contract, prepare_trx_transfer,
-    prepare_trx_transfer_with_key, prepare_usdt_transfer, prepare_usdt_transfer_with_key,
+    build_freeze_balance_v2, build_trc20_approve, build_trc20_transfer, build_trx_transfer,
+    prepare_delegate_resource, prepare_delegate_resource_with_key, prepare_trc20_transfer,
+    prepare_trc20_transfer_with_key, prepare_trigger_smart_contract, prepare_trx_transfer,
+    prepare_trx_transfer_with_key,
 };
 pub(super) use proof::{broadcast_signed_tx, build_proof, tx_is_known};
 
@@ -25,12 +29,24 @@ pub(super) struct PreparedTronTx {
     pub txid: [u8; 32],
     pub tx_bytes: Vec<u8>,
     pub fee_limit_sun: Option<i64>,
+    pub fee_limit_estimate_sun: Option<i64>,
     pub energy_required: Option<i64>,
     pub tx_size_bytes: Option<i64>,
 }
 
-pub(super) async fn connect_grpc(cfg: &TronConfig) -> Result<TronGrpc> {
-    TronGrpc::connect(&cfg.grpc_url, cfg.api_key.as_deref())
+pub(super) async fn connect_grpc(
+    cfg: &TronConfig,
+    telemetry: &SolverTelemetry,
+) -> Result<TronGrpc> {
+    let telemetry = telemetry.clone();
+    let opts = GrpcOptions {
+        timeout: cfg.grpc_timeout,
+        max_retries: cfg.grpc_max_retries,
+        observer: Some(Arc::new(move |method, outcome, elapsed| {
+            telemetry.tron_grpc_call(method, outcome.as_str(), elapsed.as_millis() as u64);
+        })),
+    };
+    TronGrpc::connect_with_options(&cfg.grpc_url, cfg.api_key.as_deref(), opts)
         .await
         .context("connect tron grpc")
 }
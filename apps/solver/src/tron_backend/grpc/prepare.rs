@@ -1,10 +1,13 @@
+use super::super::utils::{ensure_tron_sender_matches, tron_tx_owner_address};
 use super::{PreparedTronTx, connect_grpc, emulate::emulate_trigger_smart_contract};
-use crate::{config::TronConfig, metrics::SolverTelemetry};
+use crate::{
+    config::{TronConfig, TronKey},
+    metrics::SolverTelemetry,
+};
 use alloy::sol_types::SolValue;
 use anyhow::{Context, Result};
 use prost::Message;
-use std::time::Duration;
-use tron::{TronAddress, TronWallet};
+use tron::{SignedTronTx, TronAddress, TronWallet};
 
 pub(crate) async fn prepare_trx_transfer(
     cfg: &TronConfig,
@@ -17,8 +20,8 @@ pub(crate) async fn prepare_trx_transfer(
     let amount_sun_i64 = i64::try_from(intent.amountSun).context("amountSun out of i64 range")?;
     let to = TronAddress::from_evm(intent.to);
 
-    let wallet = TronWallet::new(cfg.private_key).context("init TronWallet")?;
-    let mut grpc = connect_grpc(cfg).await?;
+    let wallet = cfg.wallet(cfg.private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
 
     let started = std::time::Instant::now();
     let signed = wallet
@@ -31,10 +34,16 @@ pub(crate) async fn prepare_trx_transfer(
         started.elapsed().as_millis() as u64,
     );
 
+    ensure_signed_by(cfg, &wallet, &signed)?;
+    ensure_tx_size_within(cfg, &signed)?;
+
     Ok(PreparedTronTx {
         txid: signed.txid,
         tx_bytes: signed.tx.encode_to_vec(),
         fee_limit_sun: Some(i64::try_from(signed.fee_limit_sun).unwrap_or(i64::MAX)),
+        fee_limit_estimate_sun: Some(
+            i64::try_from(signed.fee_limit_estimate_sun).unwrap_or(i64::MAX),
+        ),
         energy_required: Some(i64::try_from(signed.energy_required).unwrap_or(i64::MAX)),
         tx_size_bytes: Some(i64::try_from(signed.tx_size_bytes).unwrap_or(i64::MAX)),
     })
@@ -43,7 +52,7 @@ pub(crate) async fn prepare_trx_transfer(
 pub(crate) async fn prepare_trx_transfer_with_key(
     cfg: &TronConfig,
     telemetry: &SolverTelemetry,
-    private_key: [u8; 32],
+    private_key: TronKey,
     intent_specs: &[u8],
 ) -> Result<PreparedTronTx> {
     let intent = super::super::TRXTransferIntent::abi_decode(intent_specs)
@@ -56,12 +65,12 @@ pub(crate) async fn prepare_trx_transfer_with_key(
 pub(crate) async fn build_trx_transfer(
     cfg: &TronConfig,
     telemetry: &SolverTelemetry,
-    private_key: [u8; 32],
+    private_key: TronKey,
     to: TronAddress,
     amount_sun: i64,
 ) -> Result<PreparedTronTx> {
-    let wallet = TronWallet::new(private_key).context("init TronWallet")?;
-    let mut grpc = connect_grpc(cfg).await?;
+    let wallet = cfg.wallet(private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
 
     let started = std::time::Instant::now();
     let signed = wallet
@@ -74,10 +83,52 @@ pub(crate) async fn build_trx_transfer(
         started.elapsed().as_millis() as u64,
     );
 
+    ensure_signed_by(cfg, &wallet, &signed)?;
+    ensure_tx_size_within(cfg, &signed)?;
+
     Ok(PreparedTronTx {
         txid: signed.txid,
         tx_bytes: signed.tx.encode_to_vec(),
         fee_limit_sun: Some(i64::try_from(signed.fee_limit_sun).unwrap_or(i64::MAX)),
+        fee_limit_estimate_sun: Some(
+            i64::try_from(signed.fee_limit_estimate_sun).unwrap_or(i64::MAX),
+        ),
+        energy_required: Some(i64::try_from(signed.energy_required).unwrap_or(i64::MAX)),
+        tx_size_bytes: Some(i64::try_from(signed.tx_size_bytes).unwrap_or(i64::MAX)),
+    })
+}
+
+pub(crate) async fn build_freeze_balance_v2(
+    cfg: &TronConfig,
+    telemetry: &SolverTelemetry,
+    private_key: TronKey,
+    amount_sun: i64,
+    resource: tron::protocol::ResourceCode,
+) -> Result<PreparedTronTx> {
+    let wallet = cfg.wallet(private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
+
+    let started = std::time::Instant::now();
+    let signed = wallet
+        .build_and_sign_freeze_balance_v2(&mut grpc, amount_sun, resource)
+        .await
+        .context("build_and_sign_freeze_balance_v2")?;
+    telemetry.tron_grpc_ms(
+        "build_and_sign_freeze_balance_v2",
+        true,
+        started.elapsed().as_millis() as u64,
+    );
+
+    ensure_signed_by(cfg, &wallet, &signed)?;
+    ensure_tx_size_within(cfg, &signed)?;
+
+    Ok(PreparedTronTx {
+        txid: signed.txid,
+        tx_bytes: signed.tx.encode_to_vec(),
+        fee_limit_sun: Some(i64::try_from(signed.fee_limit_sun).unwrap_or(i64::MAX)),
+        fee_limit_estimate_sun: Some(
+            i64::try_from(signed.fee_limit_estimate_sun).unwrap_or(i64::MAX),
+        ),
         energy_required: Some(i64::try_from(signed.energy_required).unwrap_or(i64::MAX)),
         tx_size_bytes: Some(i64::try_from(signed.tx_size_bytes).unwrap_or(i64::MAX)),
     })
@@ -95,8 +146,8 @@ pub(crate) async fn prepare_trigger_smart_contract(
     let call_value_i64 =
         i64::try_from(intent.callValueSun).context("callValueSun out of i64 range")?;
 
-    let wallet = TronWallet::new(cfg.private_key).context("init TronWallet")?;
-    let mut grpc = connect_grpc(cfg).await?;
+    let wallet = cfg.wallet(cfg.private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
 
     if cfg.emulation_enabled {
         // Defensive: ensure the call is at least simulatable before we spend time broadcasting.
@@ -133,12 +184,16 @@ pub(crate) async fn prepare_trigger_smart_contract(
         started.elapsed().as_millis() as u64,
     );
 
-    maybe_attempt_energy_rental(cfg, wallet.address(), signed.energy_required, signed.txid).await;
+    ensure_signed_by(cfg, &wallet, &signed)?;
+    ensure_tx_size_within(cfg, &signed)?;
 
     Ok(PreparedTronTx {
         txid: signed.txid,
         tx_bytes: signed.tx.encode_to_vec(),
         fee_limit_sun: Some(i64::try_from(signed.fee_limit_sun).unwrap_or(i64::MAX)),
+        fee_limit_estimate_sun: Some(
+            i64::try_from(signed.fee_limit_estimate_sun).unwrap_or(i64::MAX),
+        ),
         energy_required: Some(i64::try_from(signed.energy_required).unwrap_or(i64::MAX)),
         tx_size_bytes: Some(i64::try_from(signed.tx_size_bytes).unwrap_or(i64::MAX)),
     })
@@ -147,13 +202,13 @@ pub(crate) async fn prepare_trigger_smart_contract(
 pub(crate) async fn build_trc20_transfer(
     cfg: &TronConfig,
     telemetry: &SolverTelemetry,
-    private_key: [u8; 32],
+    private_key: TronKey,
     token: TronAddress,
     to: TronAddress,
     amount: u64,
 ) -> Result<PreparedTronTx> {
-    let wallet = TronWallet::new(private_key).context("init TronWallet")?;
-    let mut grpc = connect_grpc(cfg).await?;
+    let wallet = cfg.wallet(private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
 
     let data = crate::abi::encode_trc20_transfer(to.evm(), alloy::primitives::U256::from(amount));
     let fee_policy = tron::sender::FeePolicy {
@@ -172,73 +227,84 @@ pub(crate) async fn build_trc20_transfer(
         started.elapsed().as_millis() as u64,
     );
 
-    maybe_attempt_energy_rental(cfg, wallet.address(), signed.energy_required, signed.txid).await;
+    ensure_signed_by(cfg, &wallet, &signed)?;
+    ensure_tx_size_within(cfg, &signed)?;
 
     Ok(PreparedTronTx {
         txid: signed.txid,
         tx_bytes: signed.tx.encode_to_vec(),
         fee_limit_sun: Some(i64::try_from(signed.fee_limit_sun).unwrap_or(i64::MAX)),
+        fee_limit_estimate_sun: Some(
+            i64::try_from(signed.fee_limit_estimate_sun).unwrap_or(i64::MAX),
+        ),
         energy_required: Some(i64::try_from(signed.energy_required).unwrap_or(i64::MAX)),
         tx_size_bytes: Some(i64::try_from(signed.tx_size_bytes).unwrap_or(i64::MAX)),
     })
 }
 
-async fn maybe_attempt_energy_rental(
+pub(crate) async fn build_trc20_approve(
     cfg: &TronConfig,
-    owner: TronAddress,
-    energy_required: u64,
-    txid: [u8; 32],
-) {
-    if cfg.energy_rental_providers.is_empty() || energy_required == 0 {
-        return;
-    }
+    telemetry: &SolverTelemetry,
+    private_key: TronKey,
+    token: TronAddress,
+    spender: TronAddress,
+    amount: alloy::primitives::U256,
+) -> Result<PreparedTronTx> {
+    let wallet = cfg.wallet(private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
 
-    let ctx = tron::rental::RentalContext {
-        resource: tron::rental::RentalResourceKind::Energy,
-        amount: energy_required,
-        lock_period: None,
-        duration_hours: None,
-        balance_sun: None,
-        address_base58check: owner.to_base58check(),
-        address_hex41: format!("0x{}", hex::encode(owner.prefixed_bytes())),
-        address_evm_hex: format!("{:#x}", owner.evm()),
-        txid: Some(format!("0x{}", hex::encode(txid))),
+    let data = crate::abi::encode_trc20_approve(spender.evm(), amount);
+    let fee_policy = tron::sender::FeePolicy {
+        fee_limit_cap_sun: cfg.fee_limit_cap_sun,
+        fee_limit_headroom_ppm: cfg.fee_limit_headroom_ppm,
     };
 
-    for p in &cfg.energy_rental_providers {
-        let provider = tron::rental::JsonApiRentalProvider::new(p.clone());
-        let res = tokio::time::timeout(Duration::from_secs(2), provider.rent(&ctx)).await;
-        match res {
-            Ok(Ok(attempt)) => {
-                if attempt.ok {
-                    tracing::info!(
-                        provider = %attempt.provider,
-                        order_id = attempt.order_id.as_deref().unwrap_or(""),
-                        "energy rental requested"
-                    );
-                    break;
-                }
-                tracing::warn!(
-                    provider = %attempt.provider,
-                    error = attempt.error.as_deref().unwrap_or(""),
-                    "energy rental request failed"
-                );
-            }
-            Ok(Err(err)) => {
-                tracing::warn!(
-                    provider = %provider.name(),
-                    error = %err,
-                    "energy rental request error"
-                );
-            }
-            Err(_) => {
-                tracing::warn!(
-                    provider = %provider.name(),
-                    "energy rental request timed out"
-                );
-            }
-        }
+    let started = std::time::Instant::now();
+    let signed = wallet
+        .build_and_sign_trigger_smart_contract(&mut grpc, token, data, 0, fee_policy)
+        .await
+        .context("build_and_sign_trigger_smart_contract (approve)")?;
+    telemetry.tron_grpc_ms(
+        "build_and_sign_trigger_smart_contract",
+        true,
+        started.elapsed().as_millis() as u64,
+    );
+
+    ensure_signed_by(cfg, &wallet, &signed)?;
+    ensure_tx_size_within(cfg, &signed)?;
+
+    Ok(PreparedTronTx {
+        txid: signed.txid,
+        tx_bytes: signed.tx.encode_to_vec(),
+        fee_limit_sun: Some(i64::try_from(signed.fee_limit_sun).unwrap_or(i64::MAX)),
+        fee_limit_estimate_sun: Some(
+            i64::try_from(signed.fee_limit_estimate_sun).unwrap_or(i64::MAX),
+        ),
+        energy_required: Some(i64::try_from(signed.energy_required).unwrap_or(i64::MAX)),
+        tx_size_bytes: Some(i64::try_from(signed.tx_size_bytes).unwrap_or(i64::MAX)),
+    })
+}
+
+fn ensure_signed_by(cfg: &TronConfig, wallet: &TronWallet, signed: &SignedTronTx) -> Result<()> {
+    if !cfg.sender_check_enabled {
+        return Ok(());
     }
+    let owner = tron_tx_owner_address(&signed.tx).context("re-derive tron tx sender")?;
+    ensure_tron_sender_matches(wallet.address().prefixed_bytes(), owner)
+}
+
+/// Fails with `tx_too_large:` above `TronConfig::max_tx_size_bytes`: the node refuses oversized
+/// txs, and bandwidth is burned per byte.
+fn ensure_tx_size_within(cfg: &TronConfig, signed: &SignedTronTx) -> Result<()> {
+    if let Some(max) = cfg.max_tx_size_bytes
+        && signed.tx_size_bytes > max
+    {
+        anyhow::bail!(
+            "tx_too_large: {} bytes exceeds TRON_MAX_TX_SIZE_BYTES={max}",
+            signed.tx_size_bytes
+        );
+    }
+    Ok(())
 }
 
 pub(crate) async fn prepare_delegate_resource(
@@ -252,7 +318,7 @@ pub(crate) async fn prepare_delegate_resource(
 pub(crate) async fn prepare_delegate_resource_with_key(
     cfg: &TronConfig,
     telemetry: &SolverTelemetry,
-    private_key: [u8; 32],
+    private_key: TronKey,
     intent_specs: &[u8],
 ) -> Result<PreparedTronTx> {
     let intent = super::super::DelegateResourceIntent::abi_decode(intent_specs)
@@ -272,8 +338,8 @@ pub(crate) async fn prepare_delegate_resource_with_key(
 
     let receiver = TronAddress::from_evm(intent.receiver);
 
-    let wallet = TronWallet::new(private_key).context("init TronWallet")?;
-    let mut grpc = connect_grpc(cfg).await?;
+    let wallet = cfg.wallet(private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
 
     let started = std::time::Instant::now();
     let signed = wallet
@@ -293,27 +359,33 @@ pub(crate) async fn prepare_delegate_resource_with_key(
         started.elapsed().as_millis() as u64,
     );
 
+    ensure_signed_by(cfg, &wallet, &signed)?;
+    ensure_tx_size_within(cfg, &signed)?;
+
     Ok(PreparedTronTx {
         txid: signed.txid,
         tx_bytes: signed.tx.encode_to_vec(),
         fee_limit_sun: Some(i64::try_from(signed.fee_limit_sun).unwrap_or(i64::MAX)),
+        fee_limit_estimate_sun: Some(
+            i64::try_from(signed.fee_limit_estimate_sun).unwrap_or(i64::MAX),
+        ),
         energy_required: Some(i64::try_from(signed.energy_required).unwrap_or(i64::MAX)),
         tx_size_bytes: Some(i64::try_from(signed.tx_size_bytes).unwrap_or(i64::MAX)),
     })
 }
 
-pub(crate) async fn prepare_usdt_transfer(
-    hub: &crate::hub::HubClient,
+/// Signs the fill for a TRC20 transfer intent (`to`, `amount`) paid in `token`.
+pub(crate) async fn prepare_trc20_transfer(
     cfg: &TronConfig,
     telemetry: &SolverTelemetry,
+    token: TronAddress,
     intent_specs: &[u8],
 ) -> Result<PreparedTronTx> {
     let intent = super::super::USDTTransferIntent::abi_decode(intent_specs)
         .context("abi_decode USDTTransferIntent")?;
-    let tron_usdt = hub.v3_tron_usdt().await.context("load V3.tronUsdt")?;
 
-    let wallet = TronWallet::new(cfg.private_key).context("init TronWallet")?;
-    let mut grpc = connect_grpc(cfg).await?;
+    let wallet = cfg.wallet(cfg.private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
 
     let data = crate::abi::encode_trc20_transfer(intent.to, intent.amount);
     if cfg.emulation_enabled {
@@ -321,7 +393,7 @@ pub(crate) async fn prepare_usdt_transfer(
             &mut grpc,
             telemetry,
             &wallet,
-            TronAddress::from_evm(tron_usdt),
+            token,
             &alloy::primitives::Bytes::from(data.as_slice().to_vec()),
             0,
         )
@@ -334,13 +406,7 @@ pub(crate) async fn prepare_usdt_transfer(
 
     let started = std::time::Instant::now();
     let signed = wallet
-        .build_and_sign_trigger_smart_contract(
-            &mut grpc,
-            TronAddress::from_evm(tron_usdt),
-            data,
-            0,
-            fee_policy,
-        )
+        .build_and_sign_trigger_smart_contract(&mut grpc, token, data, 0, fee_policy)
         .await
         .context("build_and_sign_trigger_smart_contract")?;
     telemetry.tron_grpc_ms(
@@ -349,30 +415,33 @@ pub(crate) async fn prepare_usdt_transfer(
         started.elapsed().as_millis() as u64,
     );
 
-    maybe_attempt_energy_rental(cfg, wallet.address(), signed.energy_required, signed.txid).await;
+    ensure_signed_by(cfg, &wallet, &signed)?;
+    ensure_tx_size_within(cfg, &signed)?;
 
     Ok(PreparedTronTx {
         txid: signed.txid,
         tx_bytes: signed.tx.encode_to_vec(),
         fee_limit_sun: Some(i64::try_from(signed.fee_limit_sun).unwrap_or(i64::MAX)),
+        fee_limit_estimate_sun: Some(
+            i64::try_from(signed.fee_limit_estimate_sun).unwrap_or(i64::MAX),
+        ),
         energy_required: Some(i64::try_from(signed.energy_required).unwrap_or(i64::MAX)),
         tx_size_bytes: Some(i64::try_from(signed.tx_size_bytes).unwrap_or(i64::MAX)),
     })
 }
 
-pub(crate) async fn prepare_usdt_transfer_with_key(
-    hub: &crate::hub::HubClient,
+pub(crate) async fn prepare_trc20_transfer_with_key(
     cfg: &TronConfig,
     telemetry: &SolverTelemetry,
-    private_key: [u8; 32],
+    private_key: TronKey,
+    token: TronAddress,
     intent_specs: &[u8],
 ) -> Result<PreparedTronTx> {
     let intent = super::super::USDTTransferIntent::abi_decode(intent_specs)
         .context("abi_decode USDTTransferIntent")?;
-    let tron_usdt = hub.v3_tron_usdt().await.context("load V3.tronUsdt")?;
 
-    let wallet = TronWallet::new(private_key).context("init TronWallet")?;
-    let mut grpc = connect_grpc(cfg).await?;
+    let wallet = cfg.wallet(private_key).context("init TronWallet")?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
 
     let data = crate::abi::encode_trc20_transfer(intent.to, intent.amount);
     if cfg.emulation_enabled {
@@ -380,7 +449,7 @@ pub(crate) async fn prepare_usdt_transfer_with_key(
             &mut grpc,
             telemetry,
             &wallet,
-            TronAddress::from_evm(tron_usdt),
+            token,
             &alloy::primitives::Bytes::from(data.as_slice().to_vec()),
             0,
         )
@@ -393,13 +462,7 @@ pub(crate) async fn prepare_usdt_transfer_with_key(
 
     let started = std::time::Instant::now();
     let signed = wallet
-        .build_and_sign_trigger_smart_contract(
-            &mut grpc,
-            TronAddress::from_evm(tron_usdt),
-            data,
-            0,
-            fee_policy,
-        )
+        .build_and_sign_trigger_smart_contract(&mut grpc, token, data, 0, fee_policy)
         .await
         .context("build_and_sign_trigger_smart_contract")?;
     telemetry.tron_grpc_ms(
@@ -408,12 +471,16 @@ pub(crate) async fn prepare_usdt_transfer_with_key(
         started.elapsed().as_millis() as u64,
     );
 
-    maybe_attempt_energy_rental(cfg, wallet.address(), signed.energy_required, signed.txid).await;
+    ensure_signed_by(cfg, &wallet, &signed)?;
+    ensure_tx_size_within(cfg, &signed)?;
 
     Ok(PreparedTronTx {
         txid: signed.txid,
         tx_bytes: signed.tx.encode_to_vec(),
         fee_limit_sun: Some(i64::try_from(signed.fee_limit_sun).unwrap_or(i64::MAX)),
+        fee_limit_estimate_sun: Some(
+            i64::try_from(signed.fee_limit_estimate_sun).unwrap_or(i64::MAX),
+        ),
         energy_required: Some(i64::try_from(signed.energy_required).unwrap_or(i64::MAX)),
         tx_size_bytes: Some(i64::try_from(signed.tx_size_bytes).unwrap_or(i64::MAX)),
     })
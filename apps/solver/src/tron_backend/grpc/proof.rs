@@ -7,15 +7,68 @@ use crate::{
 use alloy::primitives::B256;
 use anyhow::{Context, Result};
 use prost::Message;
+use tracing::Instrument;
 use tron::{TronGrpc, TronTxProofBuilder};
 
 pub(crate) async fn build_proof(
     cfg: &TronConfig,
+    telemetry: &SolverTelemetry,
     jobs: &JobConfig,
     txid: [u8; 32],
+    broadcast_block: Option<u64>,
 ) -> Result<TronProof> {
-    let mut grpc = connect_grpc(cfg).await?;
-    build_proof_with(&mut grpc, jobs, txid).await
+    let span = tracing::info_span!("tron_build_proof", txid = %hex::encode(txid));
+    async {
+        let mut grpc = connect_grpc(cfg, telemetry).await?;
+        build_proof_with(&mut grpc, telemetry, jobs, txid, broadcast_block).await
+    }
+    .instrument(span)
+    .await
+}
+
+pub(super) async fn head_block_number_with(
+    grpc: &mut TronGrpc,
+    telemetry: &SolverTelemetry,
+) -> Result<u64> {
+    let started = std::time::Instant::now();
+    let res = grpc.get_now_block2().await;
+    telemetry.tron_grpc_ms(
+        "get_now_block2",
+        res.is_ok(),
+        started.elapsed().as_millis() as u64,
+    );
+    let block = res.context("GetNowBlock2")?;
+    let raw = block
+        .block_header
+        .as_ref()
+        .and_then(|h| h.raw_data.as_ref())
+        .context("missing now block header.raw_data")?;
+    u64::try_from(raw.number).context("now block number out of range")
+}
+
+/// Fails with `tx_not_included:` once head is more than `window` blocks past `broadcast_block`
+/// and `txid` still has no block: the tx was dropped and needs re-broadcasting, not more polling.
+async fn check_included_within(
+    grpc: &mut TronGrpc,
+    telemetry: &SolverTelemetry,
+    txid: [u8; 32],
+    broadcast_block: u64,
+    window: u64,
+) -> Result<()> {
+    let info = grpc
+        .get_transaction_info_by_id(txid)
+        .await
+        .context("get_transaction_info_by_id (inclusion check)")?;
+    if info.block_number > 0 {
+        return Ok(());
+    }
+    let head = head_block_number_with(grpc, telemetry).await?;
+    if head > broadcast_block.saturating_add(window) {
+        anyhow::bail!(
+            "tx_not_included: not in any block {broadcast_block}..={head} (window {window})"
+        );
+    }
+    Ok(())
 }
 
 pub(crate) async fn tx_is_known(
@@ -23,7 +76,7 @@ pub(crate) async fn tx_is_known(
     telemetry: &SolverTelemetry,
     txid: [u8; 32],
 ) -> bool {
-    let mut grpc = match connect_grpc(cfg).await {
+    let mut grpc = match connect_grpc(cfg, telemetry).await {
         Ok(v) => v,
         Err(_) => return false,
     };
@@ -55,7 +108,7 @@ pub(crate) async fn broadcast_signed_tx(
     tx_bytes: &[u8],
 ) -> Result<()> {
     let tx = tron::protocol::Transaction::decode(tx_bytes).context("decode signed tx bytes")?;
-    let mut grpc = connect_grpc(cfg).await?;
+    let mut grpc = connect_grpc(cfg, telemetry).await?;
     let started = std::time::Instant::now();
     let ret = grpc
         .broadcast_transaction(tx)
@@ -85,17 +138,25 @@ pub(crate) async fn broadcast_signed_tx(
 
 async fn build_proof_with(
     grpc: &mut TronGrpc,
+    telemetry: &SolverTelemetry,
     jobs: &JobConfig,
     txid: [u8; 32],
+    broadcast_block: Option<u64>,
 ) -> Result<TronProof> {
-    let builder = TronTxProofBuilder::new(jobs.tron_finality_blocks);
+    let builder = TronTxProofBuilder::new(jobs.tron_finality_blocks)
+        .with_min_confirmations(jobs.tron_proof_min_confirmations)
+        .with_max_proof_bytes(
+            jobs.tron_proof_max_bytes
+                .map(|v| usize::try_from(v).unwrap_or(usize::MAX)),
+        );
 
-    // `build` already checks finality. We retry here to avoid making callers implement
+    // `build` already checks finality and confirmation depth. We retry here to avoid making callers implement
     // their own polling loops.
     let start = std::time::Instant::now();
     loop {
         match builder.build(grpc, txid).await {
             Ok(bundle) => {
+                telemetry.tron_proof_block_fetch_ms(bundle.block_fetch_ms);
                 let info = grpc
                     .get_transaction_info_by_id(txid)
                     .await
@@ -121,6 +182,16 @@ async fn build_proof_with(
                 });
             }
             Err(err) => {
+                // Guard failures won't clear by waiting.
+                if err.to_string().starts_with("proof_too_large:") {
+                    return Err(err);
+                }
+                // Neither will waiting on a tx that never made it into a block.
+                if let (Some(from), Some(window)) =
+                    (broadcast_block, jobs.tron_max_inclusion_scan_blocks)
+                {
+                    check_included_within(grpc, telemetry, txid, from, window).await?;
+                }
                 if start.elapsed() > std::time::Duration::from_secs(180) {
                     return Err(err).context("build tron proof (timeout)");
                 }
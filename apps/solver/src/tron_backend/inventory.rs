@@ -1,5 +1,9 @@
-use super::{InventoryCheck, TRXTransferIntent, TronBackend, USDTTransferIntent, grpc};
-use crate::config::TronMode;
+use super::{
+    DelegateResourceIntent, InventoryCheck, KeyBudgets, TRXTransferIntent, TronBackend,
+    USDTTransferIntent, grpc,
+};
+use crate::config::{TronKey, TronMode};
+use crate::db::SolverDb;
 use crate::hub::HubClient;
 use alloy::sol_types::SolValue;
 use anyhow::{Context, Result};
@@ -8,6 +12,8 @@ impl TronBackend {
     pub async fn can_fill_preclaim(
         &self,
         hub: &HubClient,
+        db: &SolverDb,
+        intent_id: [u8; 32],
         ty: crate::types::IntentType,
         intent_specs: &[u8],
     ) -> Result<InventoryCheck> {
@@ -25,6 +31,45 @@ impl TronBackend {
                 required_pre_txs: 0,
             });
         }
+        // DelegateResource resell (ENERGY-only): we can only fill if some provider will rent the
+        // energy to us, so require a live quote before claiming. Without resell we delegate from
+        // our own stake, so some key must have enough staked-but-undelegated TRX on its own.
+        // Outstanding reservations are accounted for later, in `should_attempt`.
+        if ty == crate::types::IntentType::DelegateResource {
+            if !self.cfg.delegate_resource_resell_enabled {
+                let intent = DelegateResourceIntent::abi_decode(intent_specs)
+                    .context("abi_decode DelegateResourceIntent")?;
+                let rc = match intent.resource {
+                    0 => tron::protocol::ResourceCode::Bandwidth,
+                    1 => tron::protocol::ResourceCode::Energy,
+                    2 => tron::protocol::ResourceCode::TronPower,
+                    other => anyhow::bail!("unsupported DelegateResourceIntent.resource: {other}"),
+                };
+                let needed = i64::try_from(intent.balanceSun).unwrap_or(i64::MAX);
+                let by_key = self.delegate_available_sun_by_key(rc).await?;
+                let ok = by_key.iter().any(|(_, available)| *available >= needed);
+                return Ok(InventoryCheck {
+                    ok,
+                    reason: (!ok).then_some("insufficient_delegatable"),
+                    required_pre_txs: 0,
+                });
+            }
+            return match self.quote_energy_rental(db, intent_id, intent_specs).await {
+                Ok(_) => Ok(InventoryCheck {
+                    ok: true,
+                    reason: None,
+                    required_pre_txs: 0,
+                }),
+                Err(err) => {
+                    tracing::debug!(err = %err, "no energy rental quote");
+                    Ok(InventoryCheck {
+                        ok: false,
+                        reason: Some("no_rental_quote"),
+                        required_pre_txs: 0,
+                    })
+                }
+            };
+        }
         if !matches!(
             ty,
             crate::types::IntentType::TrxTransfer | crate::types::IntentType::UsdtTransfer
@@ -37,14 +82,12 @@ impl TronBackend {
         }
 
         // Quick inventory check (no signing): can any key fill, or can we consolidate within limits?
-        let wallets = self
+        let addrs = self
             .cfg
             .private_keys
             .iter()
-            .copied()
-            .map(|k| tron::TronWallet::new(k).context("init TronWallet"))
+            .map(|k| k.address().context("derive Tron key address"))
             .collect::<Result<Vec<_>>>()?;
-        let addrs = wallets.iter().map(|w| w.address()).collect::<Vec<_>>();
 
         const BALANCE_RESERVE_SUN: i64 = 2_000_000;
 
@@ -56,10 +99,24 @@ impl TronBackend {
                     i64::try_from(intent.amountSun).context("amountSun out of i64 range")?;
                 let balances =
                     grpc::fetch_trx_balances_sun(&self.cfg, &self.telemetry, &addrs).await?;
-                if balances
-                    .iter()
-                    .any(|b| *b >= amount_sun_i64.saturating_add(BALANCE_RESERVE_SUN))
-                {
+                let remaining = self
+                    .key_budgets_remaining(db, "trx", self.cfg.key_daily_limit_sun, &addrs)
+                    .await?;
+                let budgets = remaining.as_deref().map(|remaining| KeyBudgets {
+                    remaining,
+                    executor_spend: u64::try_from(amount_sun_i64).unwrap_or(0),
+                });
+                if budgets.is_some_and(|kb| kb.none_can_execute()) {
+                    return Ok(InventoryCheck {
+                        ok: false,
+                        reason: Some("key_daily_limit"),
+                        required_pre_txs: 0,
+                    });
+                }
+                if balances.iter().enumerate().any(|(i, b)| {
+                    *b >= amount_sun_i64.saturating_add(BALANCE_RESERVE_SUN)
+                        && budgets.is_none_or(|kb| kb.can_execute(i))
+                }) {
                     return Ok(InventoryCheck {
                         ok: true,
                         reason: None,
@@ -78,6 +135,7 @@ impl TronBackend {
                     &balances,
                     amount_sun_i64.saturating_add(BALANCE_RESERVE_SUN),
                     max_pre_txs,
+                    budgets,
                 )?
                 else {
                     return Ok(InventoryCheck {
@@ -111,21 +169,36 @@ impl TronBackend {
                 let intent = USDTTransferIntent::abi_decode(intent_specs)
                     .context("abi_decode USDTTransferIntent")?;
                 let amount_u64 = u64::try_from(intent.amount).unwrap_or(u64::MAX);
-                let tron_usdt = hub.v3_tron_usdt().await.context("load V3.tronUsdt")?;
+                let token = self.transfer_token(hub, ty).await?;
 
                 let token_balances = grpc::fetch_trc20_balances_u64(
                     &self.cfg,
                     &self.telemetry,
-                    tron::TronAddress::from_evm(tron_usdt),
+                    token.address,
                     &addrs,
                 )
                 .await?;
                 let trx_balances =
                     grpc::fetch_trx_balances_sun(&self.cfg, &self.telemetry, &addrs).await?;
+                let remaining = self
+                    .key_budgets_remaining(db, token.asset, token.key_daily_limit, &addrs)
+                    .await?;
+                let budgets = remaining.as_deref().map(|remaining| KeyBudgets {
+                    remaining,
+                    executor_spend: amount_u64,
+                });
+                if budgets.is_some_and(|kb| kb.none_can_execute()) {
+                    return Ok(InventoryCheck {
+                        ok: false,
+                        reason: Some("key_daily_limit"),
+                        required_pre_txs: 0,
+                    });
+                }
 
                 if token_balances.iter().enumerate().any(|(i, b)| {
                     *b >= amount_u64
                         && trx_balances.get(i).copied().unwrap_or(0) >= BALANCE_RESERVE_SUN
+                        && budgets.is_none_or(|kb| kb.can_execute(i))
                 }) {
                     return Ok(InventoryCheck {
                         ok: true,
@@ -141,8 +214,12 @@ impl TronBackend {
                     });
                 }
                 let max_pre_txs = usize::try_from(self.jobs.consolidation_max_pre_txs).unwrap_or(0);
-                let Some(plan) =
-                    super::plan_trc20_consolidation(&token_balances, amount_u64, max_pre_txs)?
+                let Some(plan) = super::plan_trc20_consolidation(
+                    &token_balances,
+                    amount_u64,
+                    max_pre_txs,
+                    budgets,
+                )?
                 else {
                     return Ok(InventoryCheck {
                         ok: false,
@@ -153,8 +230,8 @@ impl TronBackend {
 
                 if super::validate_trc20_consolidation_caps(
                     &plan,
-                    self.jobs.consolidation_max_total_usdt_pull_amount,
-                    self.jobs.consolidation_max_per_tx_usdt_pull_amount,
+                    token.max_total_pull,
+                    token.max_per_tx_pull,
                 )
                 .is_err()
                 {
@@ -191,9 +268,8 @@ impl TronBackend {
         match self.cfg.mode {
             TronMode::Mock => Ok(None),
             TronMode::Grpc => {
-                let wallet = tron::TronWallet::new(self.cfg.private_key)
-                    .context("init TronWallet (capacity check)")?;
-                let account = grpc::fetch_account(&self.cfg, &self.telemetry, wallet.address())
+                let owner = self.cfg.private_key.address()?;
+                let account = grpc::fetch_account(&self.cfg, &self.telemetry, owner)
                     .await
                     .context("fetch Tron account")?;
                 Ok(Some(grpc::delegated_resource_available_sun(
@@ -206,30 +282,54 @@ impl TronBackend {
     pub fn tron_key_addresses(&self) -> Result<Vec<tron::TronAddress>> {
         let keys = if !self.cfg.private_keys.is_empty() {
             self.cfg.private_keys.clone()
-        } else if self.cfg.private_key != [0u8; 32] {
+        } else if self.cfg.private_key != TronKey::UNSET {
             vec![self.cfg.private_key]
         } else {
             Vec::new()
         };
 
         let mut out = Vec::with_capacity(keys.len());
-        for pk in keys {
-            let w = tron::TronWallet::new(pk).context("init TronWallet")?;
-            out.push(w.address());
+        for key in keys {
+            out.push(key.address()?);
         }
         Ok(out)
     }
 
-    pub fn private_key_for_owner(&self, owner_address_prefixed: &[u8]) -> Option<[u8; 32]> {
-        for pk in &self.cfg.private_keys {
-            if let Ok(w) = tron::TronWallet::new(*pk)
-                && w.address().prefixed_bytes().as_slice() == owner_address_prefixed
+    /// Remaining rolling-24h spend budget per key for `asset` (`"trx"` / `"usdt"`), or `None`
+    /// when `limit` is unset.
+    pub(super) async fn key_budgets_remaining(
+        &self,
+        db: &SolverDb,
+        asset: &str,
+        limit: Option<u64>,
+        addrs: &[tron::TronAddress],
+    ) -> Result<Option<Vec<u64>>> {
+        let Some(limit) = limit else {
+            return Ok(None);
+        };
+        let owners = addrs
+            .iter()
+            .map(|a| a.prefixed_bytes().to_vec())
+            .collect::<Vec<_>>();
+        let spent = db
+            .tron_key_spent_24h(asset, &owners)
+            .await
+            .context("load tron key spends")?;
+        Ok(Some(
+            spent.into_iter().map(|s| limit.saturating_sub(s)).collect(),
+        ))
+    }
+
+    pub fn private_key_for_owner(&self, owner_address_prefixed: &[u8]) -> Option<TronKey> {
+        for key in &self.cfg.private_keys {
+            if let Ok(addr) = key.address()
+                && addr.prefixed_bytes().as_slice() == owner_address_prefixed
             {
-                return Some(*pk);
+                return Some(*key);
             }
         }
-        if let Ok(w) = tron::TronWallet::new(self.cfg.private_key)
-            && w.address().prefixed_bytes().as_slice() == owner_address_prefixed
+        if let Ok(addr) = self.cfg.private_key.address()
+            && addr.prefixed_bytes().as_slice() == owner_address_prefixed
         {
             return Some(self.cfg.private_key);
         }
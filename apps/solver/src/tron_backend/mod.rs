@@ -1,28 +1,43 @@
 use crate::{
-    config::{JobConfig, TronConfig, TronMode},
+    config::{JobConfig, TronConfig, TronKey, TronMode, TronProofFormat},
+    db::{SolverDb, TronKeySpendRow},
     hub::HubClient,
     metrics::SolverTelemetry,
 };
 use alloy::primitives::B256;
 use alloy::sol_types::SolValue;
 use anyhow::{Context, Result};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, atomic::AtomicU64};
 use std::time::Instant;
 use tokio::sync::RwLock;
 use tron::resources::ResourceStakeTotals;
 
+mod energy;
 mod grpc;
 mod inventory;
 mod mock;
 mod planner;
+mod proof;
+mod rental;
+mod stake;
+mod sweep;
 mod utils;
 
-use planner::{plan_trc20_consolidation, plan_trx_consolidation};
-pub use utils::select_delegate_executor_index;
+pub(crate) use grpc::EmulationRevertError;
+use planner::{KeyBudgets, plan_trc20_consolidation, plan_trigger_spend, plan_trx_consolidation};
+use proof::{GrpcProofV1, TronProofBuilder};
+use rental::CachedRentalQuote;
+pub use rental::{
+    EnergyRentalOrder, EnergyRentalQuote, PlacedEnergyRental, duration_hours_for_lock_period_blocks,
+};
+pub use stake::StakeTopUp;
+pub use sweep::SweepTransfer;
 use utils::{
     empty_proof, evm_to_tron_raw21, tron_sender_from_privkey_or_fallback,
     validate_trc20_consolidation_caps, validate_trx_consolidation_caps,
 };
+pub use utils::{fill_mismatch, select_delegate_executor_index, trigger_spend_amount};
 
 alloy::sol! {
     struct TriggerSmartContractIntent {
@@ -55,6 +70,11 @@ pub struct TronBackend {
     jobs: JobConfig,
     telemetry: SolverTelemetry,
     stake_totals_cache: Arc<RwLock<StakeTotalsCache>>,
+    rental_quote_cache: Arc<RwLock<HashMap<[u8; 32], CachedRentalQuote>>>,
+    rental_rr_cursor: Arc<AtomicU64>,
+    /// Tron keys (0x41-prefixed) whose last broadcast failed with OUT_OF_ENERGY, with the instant
+    /// their cooldown ends.
+    energy_cooldowns: Arc<RwLock<HashMap<[u8; 21], Instant>>>,
 }
 
 #[derive(Debug, Clone)]
@@ -81,14 +101,45 @@ pub struct TronPreparedTx {
     pub txid: [u8; 32],
     pub tx_bytes: Vec<u8>,
     pub fee_limit_sun: Option<i64>,
+    /// Fee estimate before `TRON_FEE_LIMIT_HEADROOM_PPM` and `TRON_FEE_LIMIT_CAP_SUN` were applied.
+    pub fee_limit_estimate_sun: Option<i64>,
     pub energy_required: Option<i64>,
     pub tx_size_bytes: Option<i64>,
 }
 
+/// USDT a TriggerSmartContract call pulls from the executor key.
+#[derive(Debug, Clone, Copy)]
+struct TriggerSpend {
+    token: tron::TronAddress,
+    spender: tron::TronAddress,
+    amount: u64,
+    allowance: alloy::primitives::U256,
+}
+
+impl TriggerSpend {
+    fn needs_approve(&self) -> bool {
+        self.allowance < alloy::primitives::U256::from(self.amount)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TronPreparedPlan {
     pub pre_txs: Vec<TronPreparedTx>,
     pub final_tx: TronPreparedTx,
+    /// Per-key amounts this plan signs away, for the daily spend ledger.
+    pub spends: Vec<TronKeySpendRow>,
+}
+
+/// TRC20 token a transfer intent pays out in, with the limits that apply to it.
+#[derive(Debug, Clone, Copy)]
+pub struct Trc20Token {
+    pub address: tron::TronAddress,
+    /// Label in the per-key spend ledger and daily limits.
+    pub asset: &'static str,
+    pub key_daily_limit: Option<u64>,
+    /// Consolidation caps in token base units (0 = unlimited).
+    pub max_total_pull: u64,
+    pub max_per_tx_pull: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -106,7 +157,75 @@ pub enum TronExecution {
 #[derive(Debug, Clone)]
 pub struct EmulationCheck {
     pub ok: bool,
-    pub reason: Option<String>,
+    /// Set when emulation reverted.
+    pub reason: Option<EmulationRevert>,
+    /// Energy reported by a successful emulation.
+    pub energy_used: Option<i64>,
+    /// Emulation succeeded but used more energy than the caller's `max_energy`.
+    pub over_budget: bool,
+    /// Not emulated: the call pulls more USDT than the executor key has approved, and Tron nodes
+    /// can't emulate it against the approve that would precede it.
+    pub needs_approve: bool,
+}
+
+/// Decoded revert from a Tron emulation. Both fields are `None` when the node returned no revert
+/// data (e.g. out-of-energy or a bare `revert()`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct EmulationRevert {
+    #[serde(serialize_with = "serialize_selector")]
+    pub selector: Option<[u8; 4]>,
+    /// `Error(string)` reason, or `Panic(0x..)` for panics.
+    pub decoded_message: Option<String>,
+}
+
+alloy::sol! {
+    error EnforcedPause();
+    error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+    error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed);
+}
+
+impl EmulationRevert {
+    /// Whether the revert would recur for any caller until the contract itself changes, i.e. it is
+    /// worth a circuit breaker on `(contract, selector)`. Reverts that depend on mutable state
+    /// (paused, balances, allowances) and reverts without data are treated as transient.
+    pub fn is_deterministic(&self) -> bool {
+        use alloy::sol_types::{Panic, Revert, SolError};
+
+        let Some(selector) = self.selector else {
+            return false;
+        };
+        if selector == Revert::SELECTOR {
+            let Some(m) = self.decoded_message.as_deref() else {
+                return false;
+            };
+            let m = m.to_ascii_lowercase();
+            const TRANSIENT: &[&str] = &[
+                "paused",
+                "insufficient",
+                "allowance",
+                "exceeds balance",
+                "locked",
+                "cooldown",
+            ];
+            return !TRANSIENT.iter().any(|t| m.contains(t));
+        }
+        if selector == Panic::SELECTOR {
+            return true;
+        }
+        selector != EnforcedPause::SELECTOR
+            && selector != ERC20InsufficientBalance::SELECTOR
+            && selector != ERC20InsufficientAllowance::SELECTOR
+    }
+}
+
+fn serialize_selector<S: serde::Serializer>(
+    selector: &Option<[u8; 4]>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    match selector {
+        Some(s) => serializer.serialize_some(&format!("0x{}", hex::encode(s))),
+        None => serializer.serialize_none(),
+    }
 }
 
 impl TronBackend {
@@ -119,6 +238,9 @@ impl TronBackend {
                 energy: None,
                 net: None,
             })),
+            rental_quote_cache: Arc::new(RwLock::new(HashMap::new())),
+            rental_rr_cursor: Arc::new(AtomicU64::new(0)),
+            energy_cooldowns: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -132,8 +254,8 @@ impl TronBackend {
         {
             return Ok(cached);
         }
-        let wallet = tron::TronWallet::new(self.cfg.private_key).context("init TronWallet")?;
-        let totals = grpc::fetch_energy_stake_totals(&self.cfg, &self.telemetry, wallet.address())
+        let owner = self.cfg.private_key.address()?;
+        let totals = grpc::fetch_energy_stake_totals(&self.cfg, &self.telemetry, owner)
             .await
             .context("fetch_energy_stake_totals")?;
         self.put_cached_stake_totals(ResourceStakeTotalsKind::Energy, totals)
@@ -152,8 +274,8 @@ impl TronBackend {
         {
             return Ok(cached);
         }
-        let wallet = tron::TronWallet::new(self.cfg.private_key).context("init TronWallet")?;
-        let totals = grpc::fetch_net_stake_totals(&self.cfg, &self.telemetry, wallet.address())
+        let owner = self.cfg.private_key.address()?;
+        let totals = grpc::fetch_net_stake_totals(&self.cfg, &self.telemetry, owner)
             .await
             .context("fetch_net_stake_totals")?;
         self.put_cached_stake_totals(ResourceStakeTotalsKind::Net, totals)
@@ -213,6 +335,7 @@ impl TronBackend {
                     txid: p.txid,
                     tx_bytes: p.tx_bytes,
                     fee_limit_sun: p.fee_limit_sun,
+                    fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                     energy_required: p.energy_required,
                     tx_size_bytes: p.tx_size_bytes,
                 }))
@@ -239,6 +362,7 @@ impl TronBackend {
                     txid: p.txid,
                     tx_bytes: p.tx_bytes,
                     fee_limit_sun: p.fee_limit_sun,
+                    fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                     energy_required: p.energy_required,
                     tx_size_bytes: p.tx_size_bytes,
                 }))
@@ -246,6 +370,140 @@ impl TronBackend {
         }
     }
 
+    /// Like `prepare_trigger_smart_contract`, for calls that may pull USDT from the executor key
+    /// (see `TronConfig::trigger_spend_selectors`). The pull is charged against the key's daily
+    /// USDT budget and recorded in the plan's spends.
+    ///
+    /// When the allowance for the target contract doesn't cover the pull (and auto-approve is on),
+    /// the plan is only the approve (after a reset to 0 if the allowance is partial): the call can't
+    /// be emulated or estimated until the approve lands. Once it has, the job goes back to
+    /// `claimed` and the call is planned against the new allowance.
+    pub async fn prepare_trigger_smart_contract_plan(
+        &self,
+        hub: &HubClient,
+        db: &SolverDb,
+        intent_specs: &[u8],
+    ) -> Result<TronPreparedPlan> {
+        if self.cfg.mode != TronMode::Grpc {
+            anyhow::bail!(
+                "prepare_trigger_smart_contract_plan is only available in TRON_MODE=grpc"
+            );
+        }
+
+        let owner = self.cfg.private_key.address()?;
+        let spend = self.trigger_spend(hub, intent_specs).await?;
+        if let Some(spend) = &spend {
+            let remaining = self
+                .key_budgets_remaining(db, "usdt", self.cfg.key_daily_limit_usdt, &[owner])
+                .await?;
+            let budgets = remaining.as_deref().map(|remaining| KeyBudgets {
+                remaining,
+                executor_spend: spend.amount,
+            });
+            let allowance = u64::try_from(spend.allowance).unwrap_or(u64::MAX);
+            let Some(steps) = plan_trigger_spend(spend.amount, allowance, 0, budgets) else {
+                anyhow::bail!(
+                    "key_daily_limit: executor key has no budget left to spend {} usdt",
+                    spend.amount
+                );
+            };
+            if steps.approve && self.cfg.auto_approve_trigger_spend {
+                let mut pre_txs = Vec::new();
+                if steps.reset_allowance {
+                    pre_txs.push(self.build_usdt_approve(spend, 0).await?);
+                }
+                return Ok(TronPreparedPlan {
+                    pre_txs,
+                    final_tx: self.build_usdt_approve(spend, spend.amount).await?,
+                    spends: Vec::new(),
+                });
+            }
+        }
+
+        let p = grpc::prepare_trigger_smart_contract(&self.cfg, &self.telemetry, intent_specs)
+            .await
+            .context("grpc prepare trigger_smart_contract")?;
+        let spends = spend
+            .map(|s| TronKeySpendRow {
+                txid: p.txid,
+                owner_address: owner.prefixed_bytes().to_vec(),
+                asset: "usdt",
+                amount: s.amount,
+            })
+            .into_iter()
+            .collect();
+        Ok(TronPreparedPlan {
+            pre_txs: Vec::new(),
+            spends,
+            final_tx: TronPreparedTx {
+                txid: p.txid,
+                tx_bytes: p.tx_bytes,
+                fee_limit_sun: p.fee_limit_sun,
+                fee_limit_estimate_sun: p.fee_limit_estimate_sun,
+                energy_required: p.energy_required,
+                tx_size_bytes: p.tx_size_bytes,
+            },
+        })
+    }
+
+    async fn build_usdt_approve(
+        &self,
+        spend: &TriggerSpend,
+        amount: u64,
+    ) -> Result<TronPreparedTx> {
+        let p = grpc::build_trc20_approve(
+            &self.cfg,
+            &self.telemetry,
+            self.cfg.private_key,
+            spend.token,
+            spend.spender,
+            alloy::primitives::U256::from(amount),
+        )
+        .await?;
+        Ok(TronPreparedTx {
+            txid: p.txid,
+            tx_bytes: p.tx_bytes,
+            fee_limit_sun: p.fee_limit_sun,
+            fee_limit_estimate_sun: p.fee_limit_estimate_sun,
+            energy_required: p.energy_required,
+            tx_size_bytes: p.tx_size_bytes,
+        })
+    }
+
+    /// USDT a TriggerSmartContract intent pulls from the executor key, with the key's current
+    /// allowance for the target contract. `None` if the selector isn't a configured spender.
+    async fn trigger_spend(
+        &self,
+        hub: &HubClient,
+        intent_specs: &[u8],
+    ) -> Result<Option<TriggerSpend>> {
+        if self.cfg.mode != TronMode::Grpc {
+            return Ok(None);
+        }
+        let intent = TriggerSmartContractIntent::abi_decode(intent_specs)
+            .context("abi_decode TriggerSmartContractIntent")?;
+        let Some(amount) = trigger_spend_amount(&self.cfg.trigger_spend_selectors, &intent.data)
+        else {
+            return Ok(None);
+        };
+        let amount = u64::try_from(amount).context("trigger spend amount out of u64 range")?;
+
+        let token = hub.v3_tron_usdt().await.context("load V3.tronUsdt")?;
+        let token = tron::TronAddress::from_evm(token);
+        let owner = self.cfg.private_key.address()?;
+        let spender = tron::TronAddress::from_evm(intent.to);
+        let allowance =
+            grpc::fetch_trc20_allowance(&self.cfg, &self.telemetry, token, owner, spender)
+                .await
+                .context("fetch usdt allowance")?;
+        Ok(Some(TriggerSpend {
+            token,
+            spender,
+            amount,
+            allowance,
+        }))
+    }
+
     pub async fn prepare_delegate_resource(
         &self,
         hub: &HubClient,
@@ -264,6 +522,7 @@ impl TronBackend {
                     txid: p.txid,
                     tx_bytes: p.tx_bytes,
                     fee_limit_sun: p.fee_limit_sun,
+                    fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                     energy_required: p.energy_required,
                     tx_size_bytes: p.tx_size_bytes,
                 }))
@@ -275,7 +534,7 @@ impl TronBackend {
         &self,
         hub: &HubClient,
         intent_id: B256,
-        private_key: [u8; 32],
+        private_key: TronKey,
         intent_specs: &[u8],
     ) -> Result<TronExecution> {
         match self.cfg.mode {
@@ -295,6 +554,7 @@ impl TronBackend {
                     txid: p.txid,
                     tx_bytes: p.tx_bytes,
                     fee_limit_sun: p.fee_limit_sun,
+                    fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                     energy_required: p.energy_required,
                     tx_size_bytes: p.tx_size_bytes,
                 }))
@@ -302,7 +562,11 @@ impl TronBackend {
         }
     }
 
-    pub async fn prepare_trx_transfer_plan(&self, intent_specs: &[u8]) -> Result<TronPreparedPlan> {
+    pub async fn prepare_trx_transfer_plan(
+        &self,
+        db: &SolverDb,
+        intent_specs: &[u8],
+    ) -> Result<TronPreparedPlan> {
         if self.cfg.mode != TronMode::Grpc {
             anyhow::bail!("prepare_trx_transfer_plan is only available in TRON_MODE=grpc");
         }
@@ -315,23 +579,34 @@ impl TronBackend {
         let amount_sun_i64 =
             i64::try_from(intent.amountSun).context("amountSun out of i64 range")?;
 
-        let wallets = self
+        let addrs = self
             .cfg
             .private_keys
             .iter()
-            .copied()
-            .map(|k| tron::TronWallet::new(k).context("init TronWallet"))
+            .map(|k| k.address().context("derive Tron key address"))
             .collect::<Result<Vec<_>>>()?;
-        let addrs = wallets.iter().map(|w| w.address()).collect::<Vec<_>>();
         let balances = grpc::fetch_trx_balances_sun(&self.cfg, &self.telemetry, &addrs)
             .await
             .context("fetch_trx_balances_sun")?;
+        let amount_sun = u64::try_from(amount_sun_i64).unwrap_or(0);
+        let remaining = self
+            .key_budgets_remaining(db, "trx", self.cfg.key_daily_limit_sun, &addrs)
+            .await?;
+        let budgets = remaining.as_deref().map(|remaining| KeyBudgets {
+            remaining,
+            executor_spend: amount_sun,
+        });
+        if budgets.is_some_and(|kb| kb.none_can_execute()) {
+            anyhow::bail!("key_daily_limit: no tron key has {amount_sun} SUN of daily budget left");
+        }
 
         // Reserve some TRX for fees.
         const BALANCE_RESERVE_SUN: i64 = 2_000_000;
         let mut best: Option<usize> = None;
         for (i, b) in balances.iter().enumerate() {
-            if *b >= amount_sun_i64.saturating_add(BALANCE_RESERVE_SUN) {
+            if *b >= amount_sun_i64.saturating_add(BALANCE_RESERVE_SUN)
+                && budgets.is_none_or(|kb| kb.can_execute(i))
+            {
                 best = Some(i);
                 break;
             }
@@ -346,10 +621,17 @@ impl TronBackend {
             .await?;
             return Ok(TronPreparedPlan {
                 pre_txs: Vec::new(),
+                spends: vec![TronKeySpendRow {
+                    txid: p.txid,
+                    owner_address: addrs[executor_index].prefixed_bytes().to_vec(),
+                    asset: "trx",
+                    amount: amount_sun,
+                }],
                 final_tx: TronPreparedTx {
                     txid: p.txid,
                     tx_bytes: p.tx_bytes,
                     fee_limit_sun: p.fee_limit_sun,
+                    fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                     energy_required: p.energy_required,
                     tx_size_bytes: p.tx_size_bytes,
                 },
@@ -361,8 +643,12 @@ impl TronBackend {
         }
 
         let max_pre_txs = usize::try_from(self.jobs.consolidation_max_pre_txs).unwrap_or(0);
-        let Some(plan) =
-            plan_trx_consolidation(&balances, amount_sun_i64 + BALANCE_RESERVE_SUN, max_pre_txs)?
+        let Some(plan) = plan_trx_consolidation(
+            &balances,
+            amount_sun_i64 + BALANCE_RESERVE_SUN,
+            max_pre_txs,
+            budgets,
+        )?
         else {
             anyhow::bail!("insufficient TRX balance (cannot consolidate within limits)");
         };
@@ -373,8 +659,9 @@ impl TronBackend {
             self.jobs.consolidation_max_per_tx_trx_pull_sun,
         )?;
 
-        let executor = wallets[plan.executor_index].address();
+        let executor = addrs[plan.executor_index];
         let mut pre_txs = Vec::with_capacity(plan.transfers.len());
+        let mut spends = Vec::with_capacity(plan.transfers.len() + 1);
         for (from_idx, amt) in plan.transfers {
             let p = grpc::build_trx_transfer(
                 &self.cfg,
@@ -384,10 +671,17 @@ impl TronBackend {
                 amt,
             )
             .await?;
+            spends.push(TronKeySpendRow {
+                txid: p.txid,
+                owner_address: addrs[from_idx].prefixed_bytes().to_vec(),
+                asset: "trx",
+                amount: u64::try_from(amt).unwrap_or(0),
+            });
             pre_txs.push(TronPreparedTx {
                 txid: p.txid,
                 tx_bytes: p.tx_bytes,
                 fee_limit_sun: p.fee_limit_sun,
+                fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                 energy_required: p.energy_required,
                 tx_size_bytes: p.tx_size_bytes,
             });
@@ -400,19 +694,48 @@ impl TronBackend {
             intent_specs,
         )
         .await?;
+        spends.push(TronKeySpendRow {
+            txid: p.txid,
+            owner_address: executor.prefixed_bytes().to_vec(),
+            asset: "trx",
+            amount: amount_sun,
+        });
 
         Ok(TronPreparedPlan {
             pre_txs,
+            spends,
             final_tx: TronPreparedTx {
                 txid: p.txid,
                 tx_bytes: p.tx_bytes,
                 fee_limit_sun: p.fee_limit_sun,
+                fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                 energy_required: p.energy_required,
                 tx_size_bytes: p.tx_size_bytes,
             },
         })
     }
 
+    /// Token paid out by a TRC20 transfer intent type. USDT (`V3.tronUsdt`) is the only one today.
+    pub async fn transfer_token(
+        &self,
+        hub: &HubClient,
+        ty: crate::types::IntentType,
+    ) -> Result<Trc20Token> {
+        match ty {
+            crate::types::IntentType::UsdtTransfer => {
+                let tron_usdt = hub.v3_tron_usdt().await.context("load V3.tronUsdt")?;
+                Ok(Trc20Token {
+                    address: tron::TronAddress::from_evm(tron_usdt),
+                    asset: "usdt",
+                    key_daily_limit: self.cfg.key_daily_limit_usdt,
+                    max_total_pull: self.jobs.consolidation_max_total_usdt_pull_amount,
+                    max_per_tx_pull: self.jobs.consolidation_max_per_tx_usdt_pull_amount,
+                })
+            }
+            other => anyhow::bail!("{other:?} is not a TRC20 transfer intent"),
+        }
+    }
+
     pub async fn prepare_usdt_transfer(
         &self,
         hub: &HubClient,
@@ -424,13 +747,22 @@ impl TronBackend {
                 mock::execute_usdt_transfer(hub, &self.cfg, intent_id, intent_specs).await
             }
             TronMode::Grpc => {
-                let p = grpc::prepare_usdt_transfer(hub, &self.cfg, &self.telemetry, intent_specs)
-                    .await
-                    .context("grpc prepare usdt transfer")?;
+                let token = self
+                    .transfer_token(hub, crate::types::IntentType::UsdtTransfer)
+                    .await?;
+                let p = grpc::prepare_trc20_transfer(
+                    &self.cfg,
+                    &self.telemetry,
+                    token.address,
+                    intent_specs,
+                )
+                .await
+                .context("grpc prepare usdt transfer")?;
                 Ok(TronExecution::PreparedTx(TronPreparedTx {
                     txid: p.txid,
                     tx_bytes: p.tx_bytes,
                     fee_limit_sun: p.fee_limit_sun,
+                    fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                     energy_required: p.energy_required,
                     tx_size_bytes: p.tx_size_bytes,
                 }))
@@ -441,10 +773,29 @@ impl TronBackend {
     pub async fn prepare_usdt_transfer_plan(
         &self,
         hub: &HubClient,
+        db: &SolverDb,
+        intent_specs: &[u8],
+    ) -> Result<TronPreparedPlan> {
+        let token = self
+            .transfer_token(hub, crate::types::IntentType::UsdtTransfer)
+            .await?;
+        self.prepare_trc20_transfer_plan(db, token, intent_specs)
+            .await
+    }
+
+    /// Picks (or consolidates into) an executor key holding enough `token` and signs the transfer.
+    ///
+    /// The fill always comes from a single key: `proveIntentFill` matches exactly one transfer of
+    /// the full intent amount to the recipient, so partial transfers from several keys could
+    /// never be proven. Fragmented balances have to be consolidated within the configured caps.
+    pub async fn prepare_trc20_transfer_plan(
+        &self,
+        db: &SolverDb,
+        token: Trc20Token,
         intent_specs: &[u8],
     ) -> Result<TronPreparedPlan> {
         if self.cfg.mode != TronMode::Grpc {
-            anyhow::bail!("prepare_usdt_transfer_plan is only available in TRON_MODE=grpc");
+            anyhow::bail!("prepare_trc20_transfer_plan is only available in TRON_MODE=grpc");
         }
         if self.cfg.private_keys.is_empty() {
             anyhow::bail!("no tron private keys configured");
@@ -453,52 +804,75 @@ impl TronBackend {
         let intent = USDTTransferIntent::abi_decode(intent_specs)
             .context("abi_decode USDTTransferIntent")?;
         let amount_u64 = u64::try_from(intent.amount).unwrap_or(u64::MAX);
-        let tron_usdt = hub.v3_tron_usdt().await.context("load V3.tronUsdt")?;
+        let asset = token.asset;
 
-        let wallets = self
+        let addrs = self
             .cfg
             .private_keys
             .iter()
-            .copied()
-            .map(|k| tron::TronWallet::new(k).context("init TronWallet"))
+            .map(|k| k.address().context("derive Tron key address"))
             .collect::<Result<Vec<_>>>()?;
-        let addrs = wallets.iter().map(|w| w.address()).collect::<Vec<_>>();
-        let token_balances = grpc::fetch_trc20_balances_u64(
-            &self.cfg,
-            &self.telemetry,
-            tron::TronAddress::from_evm(tron_usdt),
-            &addrs,
-        )
-        .await
-        .context("fetch usdt balances")?;
+        let token_balances =
+            grpc::fetch_trc20_balances_u64(&self.cfg, &self.telemetry, token.address, &addrs)
+                .await
+                .with_context(|| format!("fetch {asset} balances"))?;
         let trx_balances = grpc::fetch_trx_balances_sun(&self.cfg, &self.telemetry, &addrs)
             .await
             .context("fetch trx balances")?;
+        let remaining = self
+            .key_budgets_remaining(db, asset, token.key_daily_limit, &addrs)
+            .await?;
+        let budgets = remaining.as_deref().map(|remaining| KeyBudgets {
+            remaining,
+            executor_spend: amount_u64,
+        });
+        if budgets.is_some_and(|kb| kb.none_can_execute()) {
+            anyhow::bail!(
+                "key_daily_limit: no tron key has {amount_u64} {asset} units of daily budget left"
+            );
+        }
+
+        // Keys that just ran out of energy can't execute a TRC20 transfer until topped up; don't
+        // pick them as a direct executor. Consolidation below may still use them as the executor
+        // since that's where the funds are.
+        let cooling_down = self.energy_cooldown_flags(&addrs).await;
 
         const BALANCE_RESERVE_SUN: i64 = 2_000_000;
         let mut best: Option<usize> = None;
         for (i, b) in token_balances.iter().enumerate() {
-            if *b >= amount_u64 && trx_balances.get(i).copied().unwrap_or(0) >= BALANCE_RESERVE_SUN
+            if cooling_down[i] {
+                continue;
+            }
+            if *b >= amount_u64
+                && trx_balances.get(i).copied().unwrap_or(0) >= BALANCE_RESERVE_SUN
+                && budgets.is_none_or(|kb| kb.can_execute(i))
             {
                 best = Some(i);
                 break;
             }
         }
         if let Some(executor_index) = best {
-            let p = grpc::prepare_usdt_transfer_with_key(
-                hub,
+            let p = grpc::prepare_trc20_transfer_with_key(
                 &self.cfg,
                 &self.telemetry,
                 self.cfg.private_keys[executor_index],
+                token.address,
                 intent_specs,
             )
             .await?;
             return Ok(TronPreparedPlan {
                 pre_txs: Vec::new(),
+                spends: vec![TronKeySpendRow {
+                    txid: p.txid,
+                    owner_address: addrs[executor_index].prefixed_bytes().to_vec(),
+                    asset,
+                    amount: amount_u64,
+                }],
                 final_tx: TronPreparedTx {
                     txid: p.txid,
                     tx_bytes: p.tx_bytes,
                     fee_limit_sun: p.fee_limit_sun,
+                    fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                     energy_required: p.energy_required,
                     tx_size_bytes: p.tx_size_bytes,
                 },
@@ -506,55 +880,69 @@ impl TronBackend {
         }
 
         if !self.jobs.consolidation_enabled {
-            anyhow::bail!("insufficient USDT balance (and consolidation disabled)");
+            anyhow::bail!("insufficient {asset} balance (and consolidation disabled)");
         }
         let max_pre_txs = usize::try_from(self.jobs.consolidation_max_pre_txs).unwrap_or(0);
-        let Some(plan) = plan_trc20_consolidation(&token_balances, amount_u64, max_pre_txs)? else {
-            anyhow::bail!("insufficient USDT balance (cannot consolidate within limits)");
+        let Some(plan) =
+            plan_trc20_consolidation(&token_balances, amount_u64, max_pre_txs, budgets)?
+        else {
+            anyhow::bail!("insufficient {asset} balance (cannot consolidate within limits)");
         };
 
-        validate_trc20_consolidation_caps(
-            &plan,
-            self.jobs.consolidation_max_total_usdt_pull_amount,
-            self.jobs.consolidation_max_per_tx_usdt_pull_amount,
-        )?;
+        validate_trc20_consolidation_caps(&plan, token.max_total_pull, token.max_per_tx_pull)?;
 
-        let executor = wallets[plan.executor_index].address();
+        let executor = addrs[plan.executor_index];
         let mut pre_txs = Vec::with_capacity(plan.transfers.len());
+        let mut spends = Vec::with_capacity(plan.transfers.len() + 1);
         for (from_idx, amt) in plan.transfers {
             let p = grpc::build_trc20_transfer(
                 &self.cfg,
                 &self.telemetry,
                 self.cfg.private_keys[from_idx],
-                tron::TronAddress::from_evm(tron_usdt),
+                token.address,
                 executor,
                 amt,
             )
             .await?;
+            spends.push(TronKeySpendRow {
+                txid: p.txid,
+                owner_address: addrs[from_idx].prefixed_bytes().to_vec(),
+                asset,
+                amount: amt,
+            });
             pre_txs.push(TronPreparedTx {
                 txid: p.txid,
                 tx_bytes: p.tx_bytes,
                 fee_limit_sun: p.fee_limit_sun,
+                fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                 energy_required: p.energy_required,
                 tx_size_bytes: p.tx_size_bytes,
             });
         }
 
-        let p = grpc::prepare_usdt_transfer_with_key(
-            hub,
+        let p = grpc::prepare_trc20_transfer_with_key(
             &self.cfg,
             &self.telemetry,
             self.cfg.private_keys[plan.executor_index],
+            token.address,
             intent_specs,
         )
         .await?;
+        spends.push(TronKeySpendRow {
+            txid: p.txid,
+            owner_address: executor.prefixed_bytes().to_vec(),
+            asset,
+            amount: amount_u64,
+        });
 
         Ok(TronPreparedPlan {
             pre_txs,
+            spends,
             final_tx: TronPreparedTx {
                 txid: p.txid,
                 tx_bytes: p.tx_bytes,
                 fee_limit_sun: p.fee_limit_sun,
+                fee_limit_estimate_sun: p.fee_limit_estimate_sun,
                 energy_required: p.energy_required,
                 tx_size_bytes: p.tx_size_bytes,
             },
@@ -566,61 +954,164 @@ impl TronBackend {
         hub: &HubClient,
         ty: crate::types::IntentType,
         intent_specs: &[u8],
+        max_energy: Option<u64>,
     ) -> EmulationCheck {
-        if self.cfg.mode != TronMode::Grpc || !self.cfg.emulation_enabled {
-            return EmulationCheck {
-                ok: true,
-                reason: None,
-            };
+        let pass = EmulationCheck {
+            ok: true,
+            reason: None,
+            energy_used: None,
+            over_budget: false,
+            needs_approve: false,
+        };
+        // An energy budget needs an emulation even when general emulation gating is off.
+        if self.cfg.mode != TronMode::Grpc || (!self.cfg.emulation_enabled && max_energy.is_none())
+        {
+            return pass;
         }
 
-        let res = match ty {
-            crate::types::IntentType::TriggerSmartContract => {
-                grpc::emulate_trigger_smart_contract_intent(
-                    &self.cfg,
-                    &self.telemetry,
-                    intent_specs,
-                )
-                .await
-                .map(|_| ())
-            }
-            crate::types::IntentType::UsdtTransfer => {
-                grpc::emulate_usdt_transfer_intent(hub, &self.cfg, &self.telemetry, intent_specs)
-                    .await
-                    .map(|_| ())
+        if ty == crate::types::IntentType::TriggerSmartContract
+            && self.cfg.auto_approve_trigger_spend
+        {
+            // Tron has no state overrides, so a call that only succeeds after our approve can't be
+            // emulated; don't claim it unsimulated.
+            match self.trigger_spend(hub, intent_specs).await {
+                Ok(Some(spend)) if spend.needs_approve() => {
+                    return EmulationCheck {
+                        ok: false,
+                        needs_approve: true,
+                        ..pass
+                    };
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::warn!(err = %err, "trigger spend allowance check failed");
+                }
             }
-            _ => Ok(()),
-        };
+        }
+        let res = self.emulate_intent(hub, ty, intent_specs).await;
 
         match res {
-            Ok(()) => EmulationCheck {
-                ok: true,
-                reason: None,
-            },
+            Ok(energy_used) => {
+                let over_budget = match (energy_used, max_energy) {
+                    (Some(used), Some(max)) => u64::try_from(used).unwrap_or(0) > max,
+                    _ => false,
+                };
+                EmulationCheck {
+                    ok: !over_budget,
+                    energy_used,
+                    over_budget,
+                    ..pass
+                }
+            }
             Err(err) => {
-                let msg = err.to_string();
-                if msg.contains("emulation_revert:") {
+                if let Some(revert) = err.downcast_ref::<EmulationRevertError>() {
                     return EmulationCheck {
                         ok: false,
-                        reason: Some("tron_emulation_revert".to_string()),
+                        reason: Some(revert.revert.clone()),
+                        ..pass
                     };
                 }
                 tracing::warn!(
                     err = %err,
                     "tron emulation check failed; continuing without gating"
                 );
-                EmulationCheck {
-                    ok: true,
-                    reason: None,
-                }
+                pass
+            }
+        }
+    }
+
+    /// Emulates the fill for an intent without any gating. Returns the energy estimate, `None` for
+    /// intent types that aren't emulated, and an [`EmulationRevertError`] when the call reverts.
+    pub async fn emulate_intent(
+        &self,
+        hub: &HubClient,
+        ty: crate::types::IntentType,
+        intent_specs: &[u8],
+    ) -> Result<Option<i64>> {
+        if self.cfg.mode != TronMode::Grpc {
+            anyhow::bail!("emulation is not available in TRON_MODE=mock");
+        }
+        match ty {
+            crate::types::IntentType::TriggerSmartContract => {
+                grpc::emulate_trigger_smart_contract_intent(
+                    &self.cfg,
+                    &self.telemetry,
+                    intent_specs,
+                )
+                .await
+                .map(Some)
+            }
+            crate::types::IntentType::UsdtTransfer => {
+                grpc::emulate_usdt_transfer_intent(hub, &self.cfg, &self.telemetry, intent_specs)
+                    .await
+                    .map(Some)
             }
+            _ => Ok(None),
         }
     }
 
-    pub async fn build_proof(&self, txid: [u8; 32]) -> Result<crate::hub::TronProof> {
+    pub async fn build_proof(
+        &self,
+        txid: [u8; 32],
+        broadcast_block: Option<u64>,
+    ) -> Result<crate::hub::TronProof> {
         match self.cfg.mode {
             TronMode::Mock => anyhow::bail!("build_proof is not available in TRON_MODE=mock"),
-            TronMode::Grpc => grpc::build_proof(&self.cfg, &self.jobs, txid).await,
+            TronMode::Grpc => match self.cfg.proof_format {
+                TronProofFormat::V1 => {
+                    GrpcProofV1 {
+                        cfg: &self.cfg,
+                        telemetry: &self.telemetry,
+                        jobs: &self.jobs,
+                    }
+                    .build(txid, broadcast_block)
+                    .await
+                }
+            },
+        }
+    }
+
+    /// Whether the Tron node is reachable and serving blocks. Always `Ok` in mock mode.
+    pub async fn ping(&self) -> Result<()> {
+        match self.cfg.mode {
+            TronMode::Mock => Ok(()),
+            TronMode::Grpc => grpc::ping(&self.cfg, &self.telemetry).await,
+        }
+    }
+
+    /// Checks the gRPC node is on `TronConfig.expected_network` (no-op when unset or in mock
+    /// mode).
+    pub async fn verify_network(&self) -> Result<()> {
+        match self.cfg.mode {
+            TronMode::Mock => Ok(()),
+            TronMode::Grpc => grpc::verify_network(&self.cfg, &self.telemetry).await,
+        }
+    }
+
+    /// Liquid TRX (SUN) of each configured Tron key, in key order. Empty in mock mode.
+    pub async fn key_trx_balances_sun(&self) -> Result<Vec<(tron::TronAddress, i64)>> {
+        if self.cfg.mode != TronMode::Grpc {
+            return Ok(Vec::new());
+        }
+        let addrs = self
+            .cfg
+            .private_keys
+            .iter()
+            .map(|k| k.address().context("derive Tron key address"))
+            .collect::<Result<Vec<_>>>()?;
+        let balances = grpc::fetch_trx_balances_sun(&self.cfg, &self.telemetry, &addrs)
+            .await
+            .context("fetch_trx_balances_sun")?;
+        Ok(addrs.into_iter().zip(balances).collect())
+    }
+
+    /// Current Tron head block number; `None` in mock mode.
+    pub async fn head_block_number(&self) -> Result<Option<u64>> {
+        match self.cfg.mode {
+            TronMode::Mock => Ok(None),
+            TronMode::Grpc => grpc::head_block_number(&self.cfg, &self.telemetry)
+                .await
+                .map(Some),
         }
     }
 
@@ -1,6 +1,29 @@
 use anyhow::Result;
 use std::cmp::Ordering;
 
+/// Remaining per-key spend budgets (see `TronConfig::key_daily_limit_*`), indexed like the
+/// balances passed to the planners.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyBudgets<'a> {
+    pub remaining: &'a [u64],
+    /// What the executor itself sends in the final tx; keys with less budget can't execute.
+    pub executor_spend: u64,
+}
+
+impl KeyBudgets<'_> {
+    fn remaining(&self, i: usize) -> u64 {
+        self.remaining.get(i).copied().unwrap_or(0)
+    }
+
+    pub fn can_execute(&self, i: usize) -> bool {
+        self.remaining(i) >= self.executor_spend
+    }
+
+    pub fn none_can_execute(&self) -> bool {
+        self.remaining.iter().all(|r| *r < self.executor_spend)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TrxConsolidationPlan {
     pub executor_index: usize,
@@ -13,10 +36,13 @@ pub struct TrxConsolidationPlan {
 /// - Picks an executor (the account with the highest current balance).
 /// - Pulls from other accounts until the executor can cover `required_sun`.
 /// - Respects `max_pre_txs`.
+/// - With `budgets`, only keys that can afford the final transfer execute and donors give at
+///   most their remaining budget.
 pub fn plan_trx_consolidation(
     balances_sun: &[i64],
     required_sun: i64,
     max_pre_txs: usize,
+    budgets: Option<KeyBudgets<'_>>,
 ) -> Result<Option<TrxConsolidationPlan>> {
     if required_sun <= 0 {
         return Ok(Some(TrxConsolidationPlan {
@@ -28,11 +54,14 @@ pub fn plan_trx_consolidation(
         return Ok(None);
     }
 
-    let (executor_index, &executor_balance) = balances_sun
+    let Some((executor_index, &executor_balance)) = balances_sun
         .iter()
         .enumerate()
+        .filter(|(i, _)| budgets.is_none_or(|kb| kb.can_execute(*i)))
         .max_by(|(_, a), (_, b)| a.cmp(b))
-        .unwrap();
+    else {
+        return Ok(None);
+    };
 
     if executor_balance >= required_sun {
         return Ok(Some(TrxConsolidationPlan {
@@ -45,19 +74,22 @@ pub fn plan_trx_consolidation(
         return Ok(None);
     }
 
-    let total: i64 = balances_sun.iter().copied().sum();
+    let mut donors: Vec<(usize, i64)> = balances_sun
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != executor_index)
+        .map(|(i, &b)| match budgets {
+            Some(kb) => (i, b.min(i64::try_from(kb.remaining(i)).unwrap_or(i64::MAX))),
+            None => (i, b),
+        })
+        .collect();
+    let total: i64 = executor_balance + donors.iter().map(|(_, b)| *b).sum::<i64>();
     if total < required_sun {
         return Ok(None);
     }
 
     let mut deficit = required_sun.saturating_sub(executor_balance).max(0);
 
-    let mut donors: Vec<(usize, i64)> = balances_sun
-        .iter()
-        .enumerate()
-        .filter(|(i, _)| *i != executor_index)
-        .map(|(i, &b)| (i, b))
-        .collect();
     donors.sort_by(|a, b| b.1.cmp(&a.1));
 
     let mut transfers: Vec<(usize, i64)> = Vec::new();
@@ -93,10 +125,12 @@ pub struct Trc20ConsolidationPlan {
     pub transfers: Vec<(usize, u64)>,
 }
 
+/// TRC20 counterpart of [`plan_trx_consolidation`].
 pub fn plan_trc20_consolidation(
     balances: &[u64],
     required: u64,
     max_pre_txs: usize,
+    budgets: Option<KeyBudgets<'_>>,
 ) -> Result<Option<Trc20ConsolidationPlan>> {
     if required == 0 {
         return Ok(Some(Trc20ConsolidationPlan {
@@ -108,11 +142,14 @@ pub fn plan_trc20_consolidation(
         return Ok(None);
     }
 
-    let (executor_index, &executor_balance) = balances
+    let Some((executor_index, &executor_balance)) = balances
         .iter()
         .enumerate()
+        .filter(|(i, _)| budgets.is_none_or(|kb| kb.can_execute(*i)))
         .max_by(|(_, a), (_, b)| a.cmp(b))
-        .unwrap();
+    else {
+        return Ok(None);
+    };
 
     if executor_balance >= required {
         return Ok(Some(Trc20ConsolidationPlan {
@@ -125,19 +162,21 @@ pub fn plan_trc20_consolidation(
         return Ok(None);
     }
 
-    let total: u64 = balances.iter().copied().sum();
+    let mut donors: Vec<(usize, u64)> = balances
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != executor_index)
+        .map(|(i, &b)| (i, budgets.map_or(b, |kb| b.min(kb.remaining(i)))))
+        .collect();
+    let total = donors
+        .iter()
+        .fold(executor_balance, |acc, (_, b)| acc.saturating_add(*b));
     if total < required {
         return Ok(None);
     }
 
     let mut deficit = required.saturating_sub(executor_balance);
 
-    let mut donors: Vec<(usize, u64)> = balances
-        .iter()
-        .enumerate()
-        .filter(|(i, _)| *i != executor_index)
-        .map(|(i, &b)| (i, b))
-        .collect();
     donors.sort_by(|a, b| match b.1.cmp(&a.1) {
         Ordering::Equal => a.0.cmp(&b.0),
         other => other,
@@ -169,6 +208,83 @@ pub fn plan_trc20_consolidation(
     }))
 }
 
+/// Allowance steps for a TriggerSmartContract call that pulls a TRC20 amount from the executor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerSpendPlan {
+    /// Zero the allowance before re-approving: USDT refuses to change one non-zero allowance to
+    /// another.
+    pub reset_allowance: bool,
+    /// The allowance doesn't cover the pull, so an approve has to land before the call.
+    pub approve: bool,
+}
+
+/// Plans the allowance steps for a call pulling `amount` from key `executor_index`. The pull is
+/// charged against the key's budget (`budgets.executor_spend` is the pull); `None` when the key
+/// can't afford it.
+pub fn plan_trigger_spend(
+    amount: u64,
+    allowance: u64,
+    executor_index: usize,
+    budgets: Option<KeyBudgets>,
+) -> Option<TriggerSpendPlan> {
+    if budgets.is_some_and(|kb| !kb.can_execute(executor_index)) {
+        return None;
+    }
+    let approve = allowance < amount;
+    Some(TriggerSpendPlan {
+        reset_allowance: approve && allowance > 0,
+        approve,
+    })
+}
+
+/// Proactive sweep target for `plan_*_consolidation`.
+///
+/// Returns the balances to plan over (donors clamped to `max_per_tx`) and the `required` amount
+/// that makes the plan pull as much as the largest `max_pre_txs` donors hold, up to `max_total`.
+/// Caps of 0 are unlimited. `None` when there is nothing to sweep.
+pub fn plan_sweep_target(
+    balances: &[u64],
+    max_pre_txs: usize,
+    max_total: u64,
+    max_per_tx: u64,
+) -> Option<(Vec<u64>, u64)> {
+    if balances.len() < 2 || max_pre_txs == 0 {
+        return None;
+    }
+    let (executor_index, &executor_balance) = balances
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .unwrap();
+
+    let adjusted: Vec<u64> = balances
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| {
+            if i == executor_index || max_per_tx == 0 {
+                b
+            } else {
+                b.min(max_per_tx)
+            }
+        })
+        .collect();
+    let mut donors: Vec<u64> = adjusted
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != executor_index)
+        .map(|(_, &b)| b)
+        .collect();
+    donors.sort_unstable_by(|a, b| b.cmp(a));
+    let mut pull: u64 = donors.iter().take(max_pre_txs).sum();
+    if max_total > 0 {
+        pull = pull.min(max_total);
+    }
+    if pull == 0 {
+        return None;
+    }
+    Some((adjusted, executor_balance.saturating_add(pull)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,7 +292,9 @@ mod tests {
     #[test]
     fn trx_consolidation_picks_best_executor_and_plans_min_transfers() {
         let balances = vec![10, 5, 100];
-        let plan = plan_trx_consolidation(&balances, 115, 2).unwrap().unwrap();
+        let plan = plan_trx_consolidation(&balances, 115, 2, None)
+            .unwrap()
+            .unwrap();
         assert_eq!(plan.executor_index, 2);
         assert_eq!(plan.transfers, vec![(0, 10), (1, 5)]);
     }
@@ -184,17 +302,120 @@ mod tests {
     #[test]
     fn trx_consolidation_respects_max_pre_txs() {
         let balances = vec![10, 5, 100];
-        let plan = plan_trx_consolidation(&balances, 115, 1).unwrap();
+        let plan = plan_trx_consolidation(&balances, 115, 1, None).unwrap();
         assert!(plan.is_none());
     }
 
     #[test]
     fn trc20_consolidation_works_like_trx() {
         let balances = vec![10u64, 5, 100];
-        let plan = plan_trc20_consolidation(&balances, 115, 2)
+        let plan = plan_trc20_consolidation(&balances, 115, 2, None)
             .unwrap()
             .unwrap();
         assert_eq!(plan.executor_index, 2);
         assert_eq!(plan.transfers, vec![(0, 10), (1, 5)]);
     }
+
+    #[test]
+    fn sweep_target_pulls_top_donors_within_caps() {
+        let balances = vec![10u64, 5, 100, 7];
+
+        let (adjusted, required) = plan_sweep_target(&balances, 2, 0, 0).unwrap();
+        assert_eq!(required, 117);
+        let plan = plan_trc20_consolidation(&adjusted, required, 2, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(plan.executor_index, 2);
+        assert_eq!(plan.transfers, vec![(0, 10), (3, 7)]);
+
+        // Per-tx and total caps shrink the pull instead of failing the plan.
+        let (adjusted, required) = plan_sweep_target(&balances, 3, 12, 6).unwrap();
+        assert_eq!(required, 112);
+        let plan = plan_trc20_consolidation(&adjusted, required, 3, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(plan.transfers, vec![(0, 6), (3, 6)]);
+
+        assert!(plan_sweep_target(&[100], 2, 0, 0).is_none());
+        assert!(plan_sweep_target(&[100, 0], 2, 0, 0).is_none());
+        assert!(plan_sweep_target(&balances, 0, 0, 0).is_none());
+    }
+
+    #[test]
+    fn consolidation_respects_key_budgets() {
+        let balances = vec![10u64, 5, 100];
+
+        // The richest key can't afford the transfer itself, so the next one executes and the
+        // donors only give what their budget allows.
+        let budgets = KeyBudgets {
+            remaining: &[200, 200, 50],
+            executor_spend: 60,
+        };
+        let plan = plan_trc20_consolidation(&balances, 60, 2, Some(budgets))
+            .unwrap()
+            .unwrap();
+        assert_eq!(plan.executor_index, 0);
+        assert_eq!(plan.transfers, vec![(2, 50)]);
+
+        // No key can execute.
+        let budgets = KeyBudgets {
+            remaining: &[10, 10, 10],
+            executor_spend: 60,
+        };
+        assert!(
+            plan_trc20_consolidation(&balances, 60, 2, Some(budgets))
+                .unwrap()
+                .is_none()
+        );
+
+        let trx = vec![10i64, 5, 100];
+        let budgets = KeyBudgets {
+            remaining: &[200, 3, 0],
+            executor_spend: 12,
+        };
+        let plan = plan_trx_consolidation(&trx, 12, 2, Some(budgets))
+            .unwrap()
+            .unwrap();
+        assert_eq!(plan.executor_index, 0);
+        assert_eq!(plan.transfers, vec![(1, 2)]);
+    }
+
+    #[test]
+    fn trigger_spend_is_charged_against_the_key_budget() {
+        fn budgets(remaining: &[u64]) -> KeyBudgets<'_> {
+            KeyBudgets {
+                remaining,
+                executor_spend: 60,
+            }
+        }
+        assert_eq!(
+            plan_trigger_spend(60, 0, 0, Some(budgets(&[60]))),
+            Some(TriggerSpendPlan {
+                reset_allowance: false,
+                approve: true,
+            })
+        );
+        // Earlier spends left the key short of the pull, even though the allowance covers it.
+        assert_eq!(plan_trigger_spend(60, 100, 0, Some(budgets(&[59]))), None);
+        assert_eq!(
+            plan_trigger_spend(60, 100, 1, Some(budgets(&[60, 0]))),
+            None
+        );
+
+        // A partial allowance is reset before the approve; a covering one needs neither.
+        assert_eq!(
+            plan_trigger_spend(60, 10, 0, None),
+            Some(TriggerSpendPlan {
+                reset_allowance: true,
+                approve: true,
+            })
+        );
+        assert_eq!(
+            plan_trigger_spend(60, 60, 0, None),
+            Some(TriggerSpendPlan {
+                reset_allowance: false,
+                approve: false,
+            })
+        );
+    }
 }
@@ -0,0 +1,30 @@
+use super::grpc;
+use crate::{
+    config::{JobConfig, TronConfig},
+    hub::TronProof,
+    metrics::SolverTelemetry,
+};
+use anyhow::Result;
+
+/// Builds the inclusion proof for a finalized Tron tx in the shape a hub verifier expects.
+///
+/// One implementation per [`crate::config::TronProofFormat`], so a new verifier format can be
+/// added next to the current one during a hub upgrade and selected with `TRON_PROOF_FORMAT`.
+pub(crate) trait TronProofBuilder {
+    /// `broadcast_block` is the Tron head when the tx was sent; with
+    /// `JobConfig::tron_max_inclusion_scan_blocks` it bounds how long a missing tx is waited on.
+    async fn build(&self, txid: [u8; 32], broadcast_block: Option<u64>) -> Result<TronProof>;
+}
+
+/// `TronProofFormat::V1` over gRPC block and tx lookups.
+pub(super) struct GrpcProofV1<'a> {
+    pub cfg: &'a TronConfig,
+    pub telemetry: &'a SolverTelemetry,
+    pub jobs: &'a JobConfig,
+}
+
+impl TronProofBuilder for GrpcProofV1<'_> {
+    async fn build(&self, txid: [u8; 32], broadcast_block: Option<u64>) -> Result<TronProof> {
+        grpc::build_proof(self.cfg, self.telemetry, self.jobs, txid, broadcast_block).await
+    }
+}
@@ -0,0 +1,623 @@
+use super::{DelegateResourceIntent, TronBackend};
+use crate::{config::TronMode, db::SolverDb};
+use alloy::sol_types::SolValue;
+use anyhow::{Context, Result};
+use std::{
+    collections::HashMap,
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+/// Best pre-claim quote for renting the ENERGY a `DELEGATE_RESOURCE` intent asks for.
+#[derive(Debug, Clone)]
+pub struct EnergyRentalQuote {
+    pub provider: String,
+    pub receiver_evm: [u8; 20],
+    pub balance_sun: i64,
+    pub lock_period: i64,
+    pub amount_units: u64,
+    pub duration_hours: u64,
+    pub cost_trx: f64,
+    pub rendered_request: tron::RenderedJsonApiRequest,
+    pub response_json: serde_json::Value,
+}
+
+/// One energy rental order for [`TronBackend::rent_energy`].
+#[derive(Debug, Clone)]
+pub struct EnergyRentalOrder<'a> {
+    pub ctx: &'a tron::RentalContext,
+    /// Provider to try first (e.g. the one that quoted the intent before the claim).
+    pub preferred: Option<&'a str>,
+    /// Only count an order as placed once the provider returns the delegation txid.
+    pub require_txid: bool,
+    /// TRX/USD price for the `TRON_RENTAL_MAX_COST_USD` / per-provider `max_rental_cost_usd`
+    /// caps; capped orders are refused without one.
+    pub trx_usd: Option<f64>,
+    /// Quote providers that have a quote endpoint even when no cap needs the price.
+    pub quote_cost: bool,
+    /// Intent (id, type) to record a `rental_over_budget` skip against when no provider took the
+    /// order and at least one was passed over for its USD cap.
+    pub skip_intent: Option<([u8; 32], i16)>,
+}
+
+/// An order a provider accepted.
+#[derive(Debug, Clone)]
+pub struct PlacedEnergyRental {
+    pub rendered_request: tron::RenderedJsonApiRequest,
+    pub attempt: tron::RentalAttempt,
+    /// Provider-quoted price, when it was quoted.
+    pub cost_trx: Option<f64>,
+}
+
+/// What [`TronBackend::rent_energy`] did.
+#[derive(Debug, Clone, Default)]
+pub struct EnergyRentalOutcome {
+    pub placed: Option<PlacedEnergyRental>,
+    /// Last provider failure, for the caller's retry message.
+    pub last_err: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct CachedRentalQuote {
+    fetched_at: Instant,
+    quote: EnergyRentalQuote,
+}
+
+pub fn duration_hours_for_lock_period_blocks(lock_period_blocks: u64) -> u64 {
+    let secs = lock_period_blocks.saturating_mul(3);
+    let hours = secs.saturating_add(3599) / 3600;
+    hours.max(1)
+}
+
+/// Picks the cheapest quote, ignoring any that exceed `max_cost_trx`.
+fn pick_best_quote(
+    quotes: impl IntoIterator<Item = EnergyRentalQuote>,
+    max_cost_trx: Option<f64>,
+) -> Option<EnergyRentalQuote> {
+    quotes
+        .into_iter()
+        .filter(|q| q.cost_trx.is_finite() && max_cost_trx.is_none_or(|max| q.cost_trx <= max))
+        .min_by(|a, b| a.cost_trx.total_cmp(&b.cost_trx))
+}
+
+/// Caps a single provider's weight so the rotation schedule stays small.
+const MAX_PROVIDER_WEIGHT: u32 = 1000;
+
+/// Order in which to try providers on rotation step `cursor`.
+///
+/// Uses smooth weighted round-robin: over `sum(weights)` consecutive steps, provider `i` comes
+/// first exactly `weights[i]` times, interleaved rather than in bursts. The remaining providers
+/// follow in schedule order as fallbacks; zero-weight providers are always tried last.
+fn weighted_rotation_order(weights: &[u32], cursor: u64) -> Vec<usize> {
+    let weights: Vec<i64> = weights
+        .iter()
+        .map(|w| i64::from((*w).min(MAX_PROVIDER_WEIGHT)))
+        .collect();
+    let total: i64 = weights.iter().sum();
+    if total == 0 {
+        return (0..weights.len()).collect();
+    }
+
+    let mut current = vec![0i64; weights.len()];
+    let mut schedule = Vec::with_capacity(usize::try_from(total).unwrap_or(0));
+    for _ in 0..total {
+        for (c, w) in current.iter_mut().zip(&weights) {
+            *c += w;
+        }
+        let pick = (0..weights.len())
+            .max_by(|&a, &b| current[a].cmp(&current[b]).then(b.cmp(&a)))
+            .unwrap_or(0);
+        current[pick] -= total;
+        schedule.push(pick);
+    }
+
+    let start = usize::try_from(cursor % total as u64).unwrap_or(0);
+    let mut order = Vec::with_capacity(weights.len());
+    for &i in schedule[start..].iter().chain(&schedule[..start]) {
+        if !order.contains(&i) {
+            order.push(i);
+        }
+    }
+    order.extend((0..weights.len()).filter(|i| weights[*i] == 0));
+    order
+}
+
+/// Tighter of the global and per-provider USD caps on one rental order.
+fn rental_cost_cap_usd(global: Option<f64>, provider: Option<f64>) -> Option<f64> {
+    match (global, provider) {
+        (Some(g), Some(p)) => Some(g.min(p)),
+        (g, p) => g.or(p),
+    }
+}
+
+/// Resolution of the latency ordering: providers whose rolling averages fall in the same bucket
+/// keep their weighted round-robin order relative to each other.
+const RENTAL_LATENCY_BUCKET_MS: f64 = 250.0;
+
+/// Breaks ties between equally weighted providers by rolling average order latency (ascending),
+/// so a consistently slow provider is tried after its equal-weight peers. Each weight class keeps
+/// the slots the weighted rotation gave it, so configured weights still decide the order across
+/// classes. Providers without samples sort first so they get measured.
+pub fn sort_providers_by_order_latency(
+    providers: &mut [tron::JsonApiRentalProviderConfig],
+    avg_order_ms: &HashMap<String, f64>,
+) {
+    let bucket = |p: &tron::JsonApiRentalProviderConfig| {
+        avg_order_ms
+            .get(&p.name)
+            .filter(|ms| ms.is_finite() && **ms > 0.0)
+            .map_or(0, |ms| (ms / RENTAL_LATENCY_BUCKET_MS) as u64)
+    };
+    let weight = |p: &tron::JsonApiRentalProviderConfig| p.weight.min(MAX_PROVIDER_WEIGHT);
+
+    let mut classes: Vec<u32> = providers.iter().map(weight).collect();
+    classes.sort_unstable();
+    classes.dedup();
+    for class in classes {
+        let slots: Vec<usize> = (0..providers.len())
+            .filter(|&i| weight(&providers[i]) == class)
+            .collect();
+        let mut members: Vec<_> = slots.iter().map(|&i| providers[i].clone()).collect();
+        members.sort_by_key(bucket);
+        for (slot, p) in slots.into_iter().zip(members) {
+            providers[slot] = p;
+        }
+    }
+}
+
+impl TronBackend {
+    /// Configured rental providers in weighted round-robin order, advancing the shared cursor.
+    ///
+    /// Callers still skip frozen providers and may move a preferred one to the front.
+    pub fn energy_rental_providers_in_rotation(&self) -> Vec<tron::JsonApiRentalProviderConfig> {
+        let providers = &self.cfg.energy_rental_providers;
+        if providers.len() <= 1 {
+            return providers.clone();
+        }
+        let cursor = self.rental_rr_cursor.fetch_add(1, Ordering::Relaxed);
+        let weights: Vec<u32> = providers.iter().map(|p| p.weight).collect();
+        weighted_rotation_order(&weights, cursor)
+            .into_iter()
+            .map(|i| providers[i].clone())
+            .collect()
+    }
+
+    /// Places `order` with the first (non-frozen) provider that accepts it, trying providers in
+    /// weighted rotation with latency as the tie-break and `order.preferred` first.
+    ///
+    /// Every energy rental the solver places goes through here, so provider freezes, order
+    /// latency and cost caps are tracked the same way for all of them. Provider failures are
+    /// reported in the outcome rather than as errors; `Err` means the DB was unreachable.
+    pub async fn rent_energy(
+        &self,
+        db: &SolverDb,
+        order: EnergyRentalOrder<'_>,
+    ) -> Result<EnergyRentalOutcome> {
+        let mut out = EnergyRentalOutcome::default();
+        // Providers passed over for their USD cap; recorded as one skip if nothing gets placed.
+        let mut over_budget = Vec::new();
+
+        let mut providers = self.energy_rental_providers_in_rotation();
+        match db.rental_provider_stats().await {
+            Ok(stats) => {
+                let avg_order_ms = stats
+                    .into_iter()
+                    .map(|s| (s.provider, s.avg_order_ms))
+                    .collect();
+                sort_providers_by_order_latency(&mut providers, &avg_order_ms);
+            }
+            Err(err) => tracing::warn!(err = %err, "load rental provider stats failed"),
+        }
+        if let Some(p) = order.preferred {
+            providers.sort_by_key(|c| if c.name == p { 0 } else { 1 });
+        }
+
+        for p in &providers {
+            let provider = tron::JsonApiRentalProvider::new(p.clone());
+            if db
+                .rental_provider_is_frozen(provider.name())
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+
+            let cap_usd = rental_cost_cap_usd(self.cfg.rental_max_cost_usd, p.max_rental_cost_usd);
+            let cost_trx = if cap_usd.is_some()
+                || order.quote_cost
+                || self.cfg.rental_quote_max_cost_trx.is_some()
+            {
+                quote_rental_cost_trx(&provider, p, order.ctx).await
+            } else {
+                None
+            };
+            if let (Some(cost), Some(max)) = (cost_trx, self.cfg.rental_quote_max_cost_trx)
+                && cost > max
+            {
+                tracing::info!(
+                    provider = %provider.name(),
+                    cost_trx = cost,
+                    max_cost_trx = max,
+                    "rental quote over max cost; trying next provider"
+                );
+                out.last_err = Some(format!(
+                    "{}: quote {cost} TRX over max {max} TRX",
+                    provider.name()
+                ));
+                continue;
+            }
+            if let Some(cap_usd) = cap_usd {
+                let cost_usd = order.trx_usd.zip(cost_trx).map(|(px, c)| c * px);
+                // Unpriced orders (no quote or no TRX price) are refused too: the cap can't be
+                // checked.
+                if cost_usd.is_none_or(|c| c > cap_usd) {
+                    let details = serde_json::json!({
+                        "provider": provider.name(),
+                        "cost_usd": cost_usd,
+                        "cap_usd": cap_usd,
+                    });
+                    tracing::info!(
+                        provider = %provider.name(),
+                        cost_usd = ?cost_usd,
+                        cap_usd,
+                        "rental over budget; trying next provider"
+                    );
+                    out.last_err =
+                        Some(format!("{}: rental_over_budget {details}", provider.name()));
+                    over_budget.push(details);
+                    continue;
+                }
+            }
+
+            let started = Instant::now();
+            let res = tokio::time::timeout(
+                Duration::from_secs(10),
+                provider.rent_with_rendered_request(order.ctx),
+            )
+            .await;
+            let ms = started.elapsed().as_millis() as u64;
+            if let Ok(avg) = db
+                .rental_provider_record_order_ms(provider.name(), ms)
+                .await
+            {
+                self.telemetry
+                    .rental_provider_avg_order_ms(provider.name(), avg);
+            }
+
+            let failure = match res {
+                Ok(Ok((req, attempt)))
+                    if attempt.ok && (!order.require_txid || attempt.txid.is_some()) =>
+                {
+                    self.telemetry.rental_order_ms(provider.name(), true, ms);
+                    let _ = db.rental_provider_record_success(provider.name()).await;
+                    out.placed = Some(PlacedEnergyRental {
+                        rendered_request: req,
+                        attempt,
+                        cost_trx,
+                    });
+                    return Ok(out);
+                }
+                Ok(Ok((_req, attempt))) => format!(
+                    "ok={} txid={:?} err={:?}",
+                    attempt.ok, attempt.txid, attempt.error
+                ),
+                Ok(Err(err)) => format!("{err:#}"),
+                Err(_) => "timeout".to_string(),
+            };
+
+            self.telemetry.rental_order_ms(provider.name(), false, ms);
+            tracing::warn!(
+                provider = %provider.name(),
+                error = %failure,
+                "energy rental order failed"
+            );
+            out.last_err = Some(format!("{}: {failure}", provider.name()));
+            let froze = db
+                .rental_provider_record_failure(
+                    provider.name(),
+                    self.cfg.rental_provider_fail_window_secs,
+                    self.cfg.rental_provider_freeze_secs,
+                    self.cfg.rental_provider_fail_threshold,
+                    &failure,
+                )
+                .await;
+            if froze.unwrap_or(false) {
+                self.telemetry.rental_provider_frozen(provider.name());
+            }
+        }
+
+        if let Some((intent_id, intent_type)) = order.skip_intent
+            && !over_budget.is_empty()
+        {
+            let details = serde_json::json!({ "providers": over_budget }).to_string();
+            let _ = db
+                .upsert_intent_skip(intent_id, intent_type, "rental_over_budget", Some(&details))
+                .await;
+        }
+        Ok(out)
+    }
+
+    /// Asks every configured (non-frozen) provider's quote endpoint what renting the intent's
+    /// ENERGY would cost, without placing an order, and returns the cheapest quote within
+    /// `TRON_RENTAL_QUOTE_MAX_COST_TRX`.
+    ///
+    /// Results are cached per intent for `TRON_RENTAL_QUOTE_TTL_SECS` so repeated candidate
+    /// evaluation doesn't hammer provider APIs. Returns `Ok(None)` for non-ENERGY intents.
+    pub async fn quote_energy_rental(
+        &self,
+        db: &SolverDb,
+        intent_id: [u8; 32],
+        intent_specs: &[u8],
+    ) -> Result<Option<EnergyRentalQuote>> {
+        if self.cfg.mode != TronMode::Grpc {
+            return Ok(None);
+        }
+        let intent = DelegateResourceIntent::abi_decode(intent_specs)
+            .context("abi_decode DelegateResourceIntent")?;
+        if intent.resource != 1 {
+            return Ok(None);
+        }
+        if let Some(q) = self.get_cached_rental_quote(intent_id).await {
+            return Ok(Some(q));
+        }
+
+        let receiver = tron::TronAddress::from_evm(intent.receiver);
+        let balance_sun = u64::try_from(intent.balanceSun).unwrap_or(u64::MAX);
+        let lock_period_blocks = u64::try_from(intent.lockPeriod).unwrap_or(u64::MAX);
+        let duration_hours = duration_hours_for_lock_period_blocks(lock_period_blocks);
+        let totals = self.energy_stake_totals().await?;
+        let amount_units = tron::resources::resource_units_for_min_trx_sun(
+            balance_sun,
+            totals,
+            self.cfg.resell_energy_headroom_ppm,
+        );
+
+        let mut recv = [0u8; 20];
+        recv.copy_from_slice(receiver.evm().as_slice());
+
+        let ctx_quote = tron::RentalContext {
+            resource: tron::RentalResourceKind::Energy,
+            amount: amount_units,
+            lock_period: Some(lock_period_blocks),
+            duration_hours: Some(duration_hours),
+            balance_sun: Some(balance_sun),
+            address_base58check: receiver.to_base58check(),
+            address_hex41: format!("0x{}", hex::encode(receiver.prefixed_bytes())),
+            address_evm_hex: format!("{:#x}", receiver.evm()),
+            txid: None,
+        };
+
+        let mut quotes = Vec::new();
+        let mut last_err: Option<String> = None;
+
+        for provider_cfg in &self.cfg.energy_rental_providers {
+            if provider_cfg.quote.is_none() {
+                continue;
+            }
+            if db
+                .rental_provider_is_frozen(&provider_cfg.name)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+
+            let provider = tron::JsonApiRentalProvider::new(provider_cfg.clone());
+            let started = Instant::now();
+            let res = tokio::time::timeout(
+                Duration::from_secs(10),
+                provider.quote_with_rendered_request(&ctx_quote),
+            )
+            .await;
+            let ms = started.elapsed().as_millis() as u64;
+
+            let failure = match res {
+                Ok(Ok((req, attempt))) if attempt.ok && attempt.cost_trx.is_some() => {
+                    self.telemetry.rental_quote_ms(provider.name(), true, ms);
+                    let _ = db.rental_provider_record_success(provider.name()).await;
+                    quotes.push(EnergyRentalQuote {
+                        provider: provider.name().to_string(),
+                        receiver_evm: recv,
+                        balance_sun: i64::try_from(balance_sun).unwrap_or(i64::MAX),
+                        lock_period: i64::try_from(lock_period_blocks).unwrap_or(i64::MAX),
+                        amount_units,
+                        duration_hours,
+                        cost_trx: attempt.cost_trx.unwrap_or(f64::INFINITY),
+                        rendered_request: req,
+                        response_json: attempt.response_json.unwrap_or(serde_json::Value::Null),
+                    });
+                    continue;
+                }
+                Ok(Ok((_req, attempt))) => format!(
+                    "ok={} cost_trx={:?} err={:?}",
+                    attempt.ok, attempt.cost_trx, attempt.error
+                ),
+                Ok(Err(err)) => format!("{err:#}"),
+                Err(_) => "timeout".to_string(),
+            };
+
+            self.telemetry.rental_quote_ms(provider.name(), false, ms);
+            last_err = Some(format!("{}: {failure}", provider.name()));
+            let froze = db
+                .rental_provider_record_failure(
+                    provider.name(),
+                    self.cfg.rental_provider_fail_window_secs,
+                    self.cfg.rental_provider_freeze_secs,
+                    self.cfg.rental_provider_fail_threshold,
+                    &failure,
+                )
+                .await;
+            if froze.unwrap_or(false) {
+                self.telemetry.rental_provider_frozen(provider.name());
+            }
+        }
+
+        let any_quoted = !quotes.is_empty();
+        let Some(best) = pick_best_quote(quotes, self.cfg.rental_quote_max_cost_trx) else {
+            if any_quoted {
+                anyhow::bail!(
+                    "no energy rental quote within max cost {:?} TRX",
+                    self.cfg.rental_quote_max_cost_trx
+                );
+            }
+            anyhow::bail!(
+                "{}",
+                last_err
+                    .unwrap_or_else(|| "no energy rental quote providers succeeded".to_string())
+            );
+        };
+
+        self.put_cached_rental_quote(intent_id, best.clone()).await;
+        Ok(Some(best))
+    }
+
+    async fn get_cached_rental_quote(&self, intent_id: [u8; 32]) -> Option<EnergyRentalQuote> {
+        let ttl = Duration::from_secs(self.cfg.rental_quote_ttl_secs.max(1));
+        let cache = self.rental_quote_cache.read().await;
+        let entry = cache.get(&intent_id)?;
+        if entry.fetched_at.elapsed() <= ttl {
+            Some(entry.quote.clone())
+        } else {
+            None
+        }
+    }
+
+    async fn put_cached_rental_quote(&self, intent_id: [u8; 32], quote: EnergyRentalQuote) {
+        let ttl = Duration::from_secs(self.cfg.rental_quote_ttl_secs.max(1));
+        let mut cache = self.rental_quote_cache.write().await;
+        cache.retain(|_, e| e.fetched_at.elapsed() <= ttl);
+        cache.insert(
+            intent_id,
+            CachedRentalQuote {
+                fetched_at: Instant::now(),
+                quote,
+            },
+        );
+    }
+}
+
+/// Quoted cost (TRX) of the order `ctx_rent` describes, if the provider has a working quote API.
+async fn quote_rental_cost_trx(
+    provider: &tron::JsonApiRentalProvider,
+    cfg: &tron::JsonApiRentalProviderConfig,
+    ctx_rent: &tron::RentalContext,
+) -> Option<f64> {
+    cfg.quote.as_ref()?;
+    match tokio::time::timeout(
+        Duration::from_secs(10),
+        provider.quote_with_rendered_request(ctx_rent),
+    )
+    .await
+    {
+        Ok(Ok((_req, q))) if q.ok => q.cost_trx,
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(provider: &str, cost_trx: f64) -> EnergyRentalQuote {
+        EnergyRentalQuote {
+            provider: provider.to_string(),
+            receiver_evm: [0u8; 20],
+            balance_sun: 0,
+            lock_period: 0,
+            amount_units: 0,
+            duration_hours: 1,
+            cost_trx,
+            rendered_request: tron::RenderedJsonApiRequest {
+                method: "POST".to_string(),
+                url: String::new(),
+                headers: Default::default(),
+                body: serde_json::Value::Null,
+            },
+            response_json: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn pick_best_quote_prefers_cheapest_within_cap() {
+        let quotes = vec![quote("a", 12.0), quote("b", 7.5), quote("c", f64::NAN)];
+        assert_eq!(
+            pick_best_quote(quotes.clone(), None).map(|q| q.provider),
+            Some("b".to_string())
+        );
+        assert_eq!(
+            pick_best_quote(quotes.clone(), Some(10.0)).map(|q| q.provider),
+            Some("b".to_string())
+        );
+        assert!(pick_best_quote(quotes, Some(5.0)).is_none());
+        assert!(pick_best_quote(Vec::new(), None).is_none());
+    }
+
+    #[test]
+    fn weighted_rotation_order_follows_weights() {
+        let weights = [3, 1, 0];
+        let firsts: Vec<usize> = (0..8)
+            .map(|cursor| weighted_rotation_order(&weights, cursor)[0])
+            .collect();
+        assert_eq!(firsts.iter().filter(|i| **i == 0).count(), 6);
+        assert_eq!(firsts.iter().filter(|i| **i == 1).count(), 2);
+        // Smooth: the light provider isn't starved until the end of a cycle.
+        assert_eq!(&firsts[..4], &[0, 0, 1, 0]);
+
+        for cursor in 0..8 {
+            let order = weighted_rotation_order(&weights, cursor);
+            assert_eq!(order.len(), 3);
+            assert_eq!(order[2], 2, "zero-weight provider must be last");
+        }
+
+        assert_eq!(weighted_rotation_order(&[0, 0], 5), vec![0, 1]);
+    }
+
+    #[test]
+    fn sort_providers_by_order_latency_puts_slow_last() {
+        let provider = |name: &str| weighted_provider(name, 1);
+        let mut providers = vec![
+            provider("slow"),
+            provider("a"),
+            provider("new"),
+            provider("b"),
+        ];
+        let avg = HashMap::from([
+            ("slow".to_string(), 4_000.0),
+            ("a".to_string(), 300.0),
+            ("b".to_string(), 320.0),
+        ]);
+        sort_providers_by_order_latency(&mut providers, &avg);
+        let names: Vec<&str> = providers.iter().map(|p| p.name.as_str()).collect();
+        // "a" and "b" share a bucket, so they keep their incoming (rotation) order.
+        assert_eq!(names, vec!["new", "a", "b", "slow"]);
+    }
+
+    #[test]
+    fn sort_providers_by_order_latency_keeps_weight_order() {
+        let mut providers = vec![
+            weighted_provider("heavy_slow", 5),
+            weighted_provider("light_fast", 1),
+            weighted_provider("heavy_fast", 5),
+        ];
+        let avg = HashMap::from([
+            ("heavy_slow".to_string(), 4_000.0),
+            ("light_fast".to_string(), 100.0),
+            ("heavy_fast".to_string(), 300.0),
+        ]);
+        sort_providers_by_order_latency(&mut providers, &avg);
+        let names: Vec<&str> = providers.iter().map(|p| p.name.as_str()).collect();
+        // Latency only reorders the two weight-5 providers; the lighter one keeps its slot.
+        assert_eq!(names, vec!["heavy_fast", "light_fast", "heavy_slow"]);
+    }
+
+    fn weighted_provider(name: &str, weight: u32) -> tron::JsonApiRentalProviderConfig {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "url": "http://rent.example",
+            "body": {},
+            "response": {"success_pointer": "/ok"},
+            "weight": weight
+        }))
+        .unwrap()
+    }
+}
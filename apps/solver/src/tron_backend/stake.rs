@@ -0,0 +1,138 @@
+use super::{TronBackend, grpc};
+use crate::config::TronMode;
+use anyhow::{Context, Result};
+
+/// Smallest amount FreezeBalanceV2 accepts (1 TRX).
+const MIN_FREEZE_SUN: i64 = 1_000_000;
+
+/// One auto-stake freeze that was broadcast.
+#[derive(Debug, Clone)]
+pub struct StakeTopUp {
+    pub owner: tron::TronAddress,
+    /// Delegatable capacity on `owner` before the freeze.
+    pub available_sun: i64,
+    pub amount_sun: i64,
+    pub txid: [u8; 32],
+}
+
+impl TronBackend {
+    /// Freezes more TRX for `resource` when no key has `target_sun` of delegatable capacity.
+    ///
+    /// Delegations are served from a single key, so this tops up the key that already has the
+    /// most capacity, bounded by its liquid balance (minus `auto_stake_reserve_sun`) and
+    /// `auto_stake_max_per_tx_sun`. Returns `None` when nothing needed (or could) be staked.
+    pub async fn ensure_staked_for(
+        &self,
+        resource: tron::protocol::ResourceCode,
+        target_sun: i64,
+    ) -> Result<Option<StakeTopUp>> {
+        if !self.cfg.auto_stake_enabled || self.cfg.mode != TronMode::Grpc || target_sun <= 0 {
+            return Ok(None);
+        }
+
+        let by_key = self.delegate_available_sun_by_key(resource).await?;
+        let Some(&(owner, available_sun)) = by_key.iter().max_by_key(|(_, available)| *available)
+        else {
+            return Ok(None);
+        };
+        let balance_sun = grpc::fetch_trx_balance_sun(&self.cfg, &self.telemetry, owner)
+            .await
+            .context("fetch stake owner balance")?;
+        let Some(amount_sun) = plan_stake_top_up(
+            available_sun,
+            target_sun,
+            balance_sun,
+            i64::try_from(self.cfg.auto_stake_reserve_sun).unwrap_or(i64::MAX),
+            self.cfg.auto_stake_max_per_tx_sun,
+        ) else {
+            return Ok(None);
+        };
+
+        let private_key = self
+            .private_key_for_owner(owner.prefixed_bytes().as_slice())
+            .context("no private key for stake owner")?;
+        let p = grpc::build_freeze_balance_v2(
+            &self.cfg,
+            &self.telemetry,
+            private_key,
+            amount_sun,
+            resource,
+        )
+        .await?;
+        self.broadcast_signed_tx(&p.tx_bytes)
+            .await
+            .context("broadcast FreezeBalanceV2")?;
+
+        Ok(Some(StakeTopUp {
+            owner,
+            available_sun,
+            amount_sun,
+            txid: p.txid,
+        }))
+    }
+}
+
+/// SUN to freeze so `available_sun` reaches `target_sun`, or `None` if already there or the
+/// spendable balance can't cover the minimum freeze.
+fn plan_stake_top_up(
+    available_sun: i64,
+    target_sun: i64,
+    balance_sun: i64,
+    reserve_sun: i64,
+    max_per_tx_sun: Option<u64>,
+) -> Option<i64> {
+    let shortfall = target_sun.saturating_sub(available_sun);
+    if shortfall <= 0 {
+        return None;
+    }
+    let mut amount = shortfall.min(balance_sun.saturating_sub(reserve_sun));
+    if let Some(max) = max_per_tx_sun {
+        amount = amount.min(i64::try_from(max).unwrap_or(i64::MAX));
+    }
+    (amount >= MIN_FREEZE_SUN).then_some(amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_up_covers_shortfall() {
+        assert_eq!(
+            plan_stake_top_up(10_000_000, 30_000_000, 100_000_000, 5_000_000, None),
+            Some(20_000_000)
+        );
+    }
+
+    #[test]
+    fn top_up_skipped_at_target() {
+        assert_eq!(
+            plan_stake_top_up(30_000_000, 30_000_000, 100_000_000, 0, None),
+            None
+        );
+    }
+
+    #[test]
+    fn top_up_bounded_by_reserve_and_cap() {
+        assert_eq!(
+            plan_stake_top_up(0, 30_000_000, 20_000_000, 5_000_000, None),
+            Some(15_000_000)
+        );
+        assert_eq!(
+            plan_stake_top_up(0, 30_000_000, 100_000_000, 0, Some(7_000_000)),
+            Some(7_000_000)
+        );
+    }
+
+    #[test]
+    fn top_up_skipped_below_min_freeze() {
+        assert_eq!(
+            plan_stake_top_up(0, 30_000_000, 5_500_000, 5_000_000, None),
+            None
+        );
+        assert_eq!(
+            plan_stake_top_up(29_500_000, 30_000_000, 100_000_000, 0, None),
+            None
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use super::{
+    TronBackend, TronPreparedTx, grpc, plan_trc20_consolidation, plan_trx_consolidation,
+    planner::plan_sweep_target, validate_trc20_consolidation_caps, validate_trx_consolidation_caps,
+};
+use crate::config::TronMode;
+use crate::hub::HubClient;
+use anyhow::{Context, Result};
+
+/// TRX left on every donor key so it can still pay for its own transfers.
+const SWEEP_RESERVE_SUN: i64 = 2_000_000;
+
+/// One proactive consolidation transfer into the sweep executor, signed but not broadcast.
+#[derive(Debug, Clone)]
+pub struct SweepTransfer {
+    /// `"trx"` or `"usdt"`.
+    pub asset: &'static str,
+    pub from: tron::TronAddress,
+    pub to: tron::TronAddress,
+    /// SUN for TRX, token base units for USDT.
+    pub amount: u64,
+    pub tx: TronPreparedTx,
+}
+
+impl TronBackend {
+    /// Plans and signs a consolidation sweep: USDT and TRX from the other keys into the key that
+    /// already holds the most of each asset, so later fills rarely need pre-txs. Uses the same
+    /// planner and caps as fill-time consolidation.
+    pub async fn prepare_consolidation_sweep(&self, hub: &HubClient) -> Result<Vec<SweepTransfer>> {
+        if self.cfg.mode != TronMode::Grpc || self.cfg.private_keys.len() < 2 {
+            return Ok(Vec::new());
+        }
+        let max_pre_txs = usize::try_from(self.jobs.consolidation_max_pre_txs).unwrap_or(0);
+
+        let addrs = self
+            .cfg
+            .private_keys
+            .iter()
+            .map(|k| k.address().context("derive Tron key address"))
+            .collect::<Result<Vec<_>>>()?;
+        let mut out = Vec::new();
+
+        let tron_usdt = hub.v3_tron_usdt().await.context("load V3.tronUsdt")?;
+        let tron_usdt = tron::TronAddress::from_evm(tron_usdt);
+        let token_balances =
+            grpc::fetch_trc20_balances_u64(&self.cfg, &self.telemetry, tron_usdt, &addrs)
+                .await
+                .context("fetch usdt balances")?;
+        if let Some((balances, required)) = plan_sweep_target(
+            &token_balances,
+            max_pre_txs,
+            self.jobs.consolidation_max_total_usdt_pull_amount,
+            self.jobs.consolidation_max_per_tx_usdt_pull_amount,
+        ) && let Some(plan) = plan_trc20_consolidation(&balances, required, max_pre_txs, None)?
+        {
+            validate_trc20_consolidation_caps(
+                &plan,
+                self.jobs.consolidation_max_total_usdt_pull_amount,
+                self.jobs.consolidation_max_per_tx_usdt_pull_amount,
+            )?;
+            let executor = addrs[plan.executor_index];
+            for (from_idx, amount) in plan.transfers {
+                let p = grpc::build_trc20_transfer(
+                    &self.cfg,
+                    &self.telemetry,
+                    self.cfg.private_keys[from_idx],
+                    tron_usdt,
+                    executor,
+                    amount,
+                )
+                .await?;
+                out.push(SweepTransfer {
+                    asset: "usdt",
+                    from: addrs[from_idx],
+                    to: executor,
+                    amount,
+                    tx: TronPreparedTx {
+                        txid: p.txid,
+                        tx_bytes: p.tx_bytes,
+                        fee_limit_sun: p.fee_limit_sun,
+                        fee_limit_estimate_sun: p.fee_limit_estimate_sun,
+                        energy_required: p.energy_required,
+                        tx_size_bytes: p.tx_size_bytes,
+                    },
+                });
+            }
+        }
+
+        let trx_balances = grpc::fetch_trx_balances_sun(&self.cfg, &self.telemetry, &addrs)
+            .await
+            .context("fetch trx balances")?;
+        // Keys paying for a USDT sweep above keep their whole TRX balance this round.
+        let sweepable = trx_balances
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| {
+                if out.iter().any(|t| t.from == addrs[i]) {
+                    0
+                } else {
+                    u64::try_from(b.saturating_sub(SWEEP_RESERVE_SUN)).unwrap_or(0)
+                }
+            })
+            .collect::<Vec<_>>();
+        if let Some((balances, required)) = plan_sweep_target(
+            &sweepable,
+            max_pre_txs,
+            self.jobs.consolidation_max_total_trx_pull_sun,
+            self.jobs.consolidation_max_per_tx_trx_pull_sun,
+        ) {
+            let balances = balances
+                .into_iter()
+                .map(|b| i64::try_from(b).unwrap_or(i64::MAX))
+                .collect::<Vec<_>>();
+            let required = i64::try_from(required).unwrap_or(i64::MAX);
+            if let Some(plan) = plan_trx_consolidation(&balances, required, max_pre_txs, None)? {
+                validate_trx_consolidation_caps(
+                    &plan,
+                    self.jobs.consolidation_max_total_trx_pull_sun,
+                    self.jobs.consolidation_max_per_tx_trx_pull_sun,
+                )?;
+                let executor = addrs[plan.executor_index];
+                for (from_idx, amount) in plan.transfers {
+                    let p = grpc::build_trx_transfer(
+                        &self.cfg,
+                        &self.telemetry,
+                        self.cfg.private_keys[from_idx],
+                        executor,
+                        amount,
+                    )
+                    .await?;
+                    out.push(SweepTransfer {
+                        asset: "trx",
+                        from: addrs[from_idx],
+                        to: executor,
+                        amount: u64::try_from(amount).unwrap_or(0),
+                        tx: TronPreparedTx {
+                            txid: p.txid,
+                            tx_bytes: p.tx_bytes,
+                            fee_limit_sun: p.fee_limit_sun,
+                            fee_limit_estimate_sun: p.fee_limit_estimate_sun,
+                            energy_required: p.energy_required,
+                            tx_size_bytes: p.tx_size_bytes,
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
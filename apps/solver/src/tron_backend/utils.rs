@@ -1,6 +1,13 @@
-use super::{HubClient, planner};
-use alloy::primitives::{FixedBytes, U256};
-use anyhow::Result;
+use super::{
+    DelegateResourceIntent, HubClient, TRXTransferIntent, TriggerSmartContractIntent,
+    USDTTransferIntent, planner,
+};
+use crate::{config::TronKey, types::IntentType};
+use alloy::primitives::{Address, FixedBytes, U256};
+use alloy::sol_types::SolValue;
+use anyhow::{Context, Result};
+use prost::Message;
+use tron::protocol::transaction::contract::ContractType;
 
 pub(super) fn empty_proof() -> crate::hub::TronProof {
     crate::hub::TronProof {
@@ -19,18 +26,254 @@ pub(super) fn evm_to_tron_raw21(a: alloy::primitives::Address) -> FixedBytes<21>
 }
 
 pub(super) fn tron_sender_from_privkey_or_fallback(
-    tron_pk: [u8; 32],
+    tron_key: TronKey,
     hub: &HubClient,
 ) -> FixedBytes<21> {
-    if tron_pk != [0u8; 32]
-        && let Ok(w) = tron::TronWallet::new(tron_pk)
+    if tron_key != TronKey::UNSET
+        && let Ok(addr) = tron_key.address()
     {
-        let b = w.address().prefixed_bytes();
+        let b = addr.prefixed_bytes();
         return FixedBytes::from_slice(&b);
     }
     evm_to_tron_raw21(hub.solver_address())
 }
 
+/// Re-derives the sender (`owner_address`) of a signed Tron tx from its raw data.
+///
+/// The node builds the tx skeleton for us, so we don't trust that the owner is the key we signed
+/// with until we've decoded it ourselves.
+pub(super) fn tron_tx_owner_address(tx: &tron::protocol::Transaction) -> Result<[u8; 21]> {
+    let raw = tx.raw_data.as_ref().context("tron tx has no raw_data")?;
+    let [contract] = raw.contract.as_slice() else {
+        anyhow::bail!(
+            "expected exactly 1 contract in tron tx (got {})",
+            raw.contract.len()
+        );
+    };
+    let param = contract
+        .parameter
+        .as_ref()
+        .context("tron tx contract has no parameter")?;
+    let value = param.value.as_slice();
+    let owner = match ContractType::try_from(contract.r#type) {
+        Ok(ContractType::TransferContract) => {
+            tron::protocol::TransferContract::decode(value)
+                .context("decode TransferContract")?
+                .owner_address
+        }
+        Ok(ContractType::TriggerSmartContract) => {
+            tron::protocol::TriggerSmartContract::decode(value)
+                .context("decode TriggerSmartContract")?
+                .owner_address
+        }
+        Ok(ContractType::DelegateResourceContract) => {
+            tron::protocol::DelegateResourceContract::decode(value)
+                .context("decode DelegateResourceContract")?
+                .owner_address
+        }
+        _ => anyhow::bail!("unsupported tron contract type: {}", contract.r#type),
+    };
+    <[u8; 21]>::try_from(owner.as_slice())
+        .map_err(|_| anyhow::anyhow!("tron owner_address must be 21 bytes (got {})", owner.len()))
+}
+
+pub(super) fn ensure_tron_sender_matches(expected: [u8; 21], actual: [u8; 21]) -> Result<()> {
+    if expected != actual {
+        anyhow::bail!(
+            "tron_sender_mismatch: expected=0x{} actual=0x{}",
+            hex::encode(expected),
+            hex::encode(actual)
+        );
+    }
+    Ok(())
+}
+
+/// Compares what an included Tron tx actually did against the intent it was meant to fill.
+///
+/// `encoded_tx` is the protobuf `Transaction` being proven. Returns a description of the first
+/// divergence (recipient, amount, resource or contract), or `None` if the tx matches the intent.
+/// `tron_usdt` is required for `UsdtTransfer` intents.
+pub fn fill_mismatch(
+    encoded_tx: &[u8],
+    ty: IntentType,
+    intent_specs: &[u8],
+    tron_usdt: Option<Address>,
+) -> Option<String> {
+    match decode_fill_mismatch(encoded_tx, ty, intent_specs, tron_usdt) {
+        Ok(v) => v,
+        Err(err) => Some(format!("{err:#}")),
+    }
+}
+
+fn decode_fill_mismatch(
+    encoded_tx: &[u8],
+    ty: IntentType,
+    intent_specs: &[u8],
+    tron_usdt: Option<Address>,
+) -> Result<Option<String>> {
+    let tx = tron::protocol::Transaction::decode(encoded_tx).context("decode proven tx")?;
+    let raw = tx.raw_data.as_ref().context("tron tx has no raw_data")?;
+    let [contract] = raw.contract.as_slice() else {
+        anyhow::bail!(
+            "expected exactly 1 contract in tron tx (got {})",
+            raw.contract.len()
+        );
+    };
+    let value = contract
+        .parameter
+        .as_ref()
+        .context("tron tx contract has no parameter")?
+        .value
+        .as_slice();
+    let contract_type = ContractType::try_from(contract.r#type).ok();
+
+    let mismatch = |field: &str, expected: String, actual: String| {
+        Some(format!("{field}: expected={expected} actual={actual}"))
+    };
+
+    match ty {
+        IntentType::TrxTransfer => {
+            let intent = TRXTransferIntent::abi_decode(intent_specs)
+                .context("abi_decode TRXTransferIntent")?;
+            if contract_type != Some(ContractType::TransferContract) {
+                return Ok(mismatch(
+                    "contract_type",
+                    "TransferContract".into(),
+                    format!("{}", contract.r#type),
+                ));
+            }
+            let c = tron::protocol::TransferContract::decode(value)
+                .context("decode TransferContract")?;
+            let to = evm_to_tron_raw21(intent.to);
+            if c.to_address.as_slice() != to.as_slice() {
+                return Ok(mismatch("to", hex::encode(to), hex::encode(&c.to_address)));
+            }
+            if U256::try_from(c.amount).ok() != Some(intent.amountSun) {
+                return Ok(mismatch(
+                    "amount",
+                    intent.amountSun.to_string(),
+                    c.amount.to_string(),
+                ));
+            }
+        }
+        IntentType::DelegateResource => {
+            let intent = DelegateResourceIntent::abi_decode(intent_specs)
+                .context("abi_decode DelegateResourceIntent")?;
+            if contract_type != Some(ContractType::DelegateResourceContract) {
+                return Ok(mismatch(
+                    "contract_type",
+                    "DelegateResourceContract".into(),
+                    format!("{}", contract.r#type),
+                ));
+            }
+            let c = tron::protocol::DelegateResourceContract::decode(value)
+                .context("decode DelegateResourceContract")?;
+            let receiver = evm_to_tron_raw21(intent.receiver);
+            if c.receiver_address.as_slice() != receiver.as_slice() {
+                return Ok(mismatch(
+                    "receiver",
+                    hex::encode(receiver),
+                    hex::encode(&c.receiver_address),
+                ));
+            }
+            if c.resource != i32::from(intent.resource) {
+                return Ok(mismatch(
+                    "resource",
+                    intent.resource.to_string(),
+                    c.resource.to_string(),
+                ));
+            }
+            // Delegating more than asked (e.g. a rental provider rounding up) still fills the
+            // intent; delegating less, unlocked or for a shorter lock does not.
+            if U256::try_from(c.balance)
+                .ok()
+                .is_none_or(|b| b < intent.balanceSun)
+            {
+                return Ok(mismatch(
+                    "balance",
+                    format!(">={}", intent.balanceSun),
+                    c.balance.to_string(),
+                ));
+            }
+            if !c.lock {
+                return Ok(mismatch("lock", "true".into(), "false".into()));
+            }
+            if U256::try_from(c.lock_period)
+                .ok()
+                .is_none_or(|p| p < intent.lockPeriod)
+            {
+                return Ok(mismatch(
+                    "lock_period",
+                    format!(">={}", intent.lockPeriod),
+                    c.lock_period.to_string(),
+                ));
+            }
+        }
+        IntentType::UsdtTransfer => {
+            let intent = USDTTransferIntent::abi_decode(intent_specs)
+                .context("abi_decode USDTTransferIntent")?;
+            let tron_usdt = tron_usdt.context("tron_usdt is required for UsdtTransfer")?;
+            if contract_type != Some(ContractType::TriggerSmartContract) {
+                return Ok(mismatch(
+                    "contract_type",
+                    "TriggerSmartContract".into(),
+                    format!("{}", contract.r#type),
+                ));
+            }
+            let c = tron::protocol::TriggerSmartContract::decode(value)
+                .context("decode TriggerSmartContract")?;
+            let token = evm_to_tron_raw21(tron_usdt);
+            if c.contract_address.as_slice() != token.as_slice() {
+                return Ok(mismatch(
+                    "contract",
+                    hex::encode(token),
+                    hex::encode(&c.contract_address),
+                ));
+            }
+            let data = crate::abi::encode_trc20_transfer(intent.to, intent.amount);
+            if c.data.as_slice() != data.as_slice() {
+                return Ok(mismatch("data", hex::encode(&data), hex::encode(&c.data)));
+            }
+        }
+        IntentType::TriggerSmartContract => {
+            let intent = TriggerSmartContractIntent::abi_decode(intent_specs)
+                .context("abi_decode TriggerSmartContractIntent")?;
+            if contract_type != Some(ContractType::TriggerSmartContract) {
+                return Ok(mismatch(
+                    "contract_type",
+                    "TriggerSmartContract".into(),
+                    format!("{}", contract.r#type),
+                ));
+            }
+            let c = tron::protocol::TriggerSmartContract::decode(value)
+                .context("decode TriggerSmartContract")?;
+            let to = evm_to_tron_raw21(intent.to);
+            if c.contract_address.as_slice() != to.as_slice() {
+                return Ok(mismatch(
+                    "contract",
+                    hex::encode(to),
+                    hex::encode(&c.contract_address),
+                ));
+            }
+            if U256::try_from(c.call_value).ok() != Some(intent.callValueSun) {
+                return Ok(mismatch(
+                    "call_value",
+                    intent.callValueSun.to_string(),
+                    c.call_value.to_string(),
+                ));
+            }
+            if c.data.as_slice() != intent.data.as_ref() {
+                return Ok(mismatch(
+                    "data",
+                    hex::encode(&intent.data),
+                    hex::encode(&c.data),
+                ));
+            }
+        }
+    }
+    Ok(None)
+}
+
 pub(super) fn validate_trx_consolidation_caps(
     plan: &planner::TrxConsolidationPlan,
     max_total_pull_sun: u64,
@@ -92,6 +335,102 @@ pub fn select_delegate_executor_index(
     best.map(|(i, _)| i)
 }
 
+/// Token amount a TriggerSmartContract call pulls from the caller, read from the ABI argument
+/// configured for its selector. `None` if the selector isn't a configured spender or the calldata
+/// is too short.
+pub fn trigger_spend_amount(
+    spend_selectors: &std::collections::HashMap<[u8; 4], usize>,
+    data: &[u8],
+) -> Option<U256> {
+    let selector: [u8; 4] = data.get(..4)?.try_into().ok()?;
+    let arg = *spend_selectors.get(&selector)?;
+    let start = arg.checked_mul(32)?.checked_add(4)?;
+    let word = data.get(start..start.checked_add(32)?)?;
+    Some(U256::from_be_slice(word))
+}
+
+#[cfg(test)]
+mod spend_tests {
+    use super::trigger_spend_amount;
+    use alloy::primitives::U256;
+    use std::collections::HashMap;
+
+    #[test]
+    fn trigger_spend_amount_reads_configured_arg() {
+        let sel = [0x12, 0x34, 0x56, 0x78];
+        let mut data = sel.to_vec();
+        data.extend_from_slice(&[0x11u8; 32]);
+        data.extend_from_slice(&U256::from(5_000_000u64).to_be_bytes::<32>());
+
+        let selectors = HashMap::from([(sel, 1usize)]);
+        assert_eq!(
+            trigger_spend_amount(&selectors, &data),
+            Some(U256::from(5_000_000u64))
+        );
+
+        // Unknown selector, out-of-range argument, short calldata.
+        assert_eq!(trigger_spend_amount(&HashMap::new(), &data), None);
+        assert_eq!(
+            trigger_spend_amount(&HashMap::from([(sel, 2usize)]), &data),
+            None
+        );
+        assert_eq!(trigger_spend_amount(&selectors, &sel[..3]), None);
+    }
+}
+
+#[cfg(test)]
+mod sender_check_tests {
+    use super::{ensure_tron_sender_matches, tron_tx_owner_address};
+    use prost::Message;
+    use tron::protocol::transaction::contract::ContractType;
+
+    fn transfer_tx(owner: [u8; 21]) -> tron::protocol::Transaction {
+        let transfer = tron::protocol::TransferContract {
+            owner_address: owner.to_vec(),
+            to_address: [0x41u8; 21].to_vec(),
+            amount: 1,
+        };
+        let mut contract = tron::protocol::transaction::Contract {
+            r#type: ContractType::TransferContract as i32,
+            parameter: Some(Default::default()),
+            ..Default::default()
+        };
+        if let Some(param) = contract.parameter.as_mut() {
+            param.value = transfer.encode_to_vec();
+        }
+        tron::protocol::Transaction {
+            raw_data: Some(tron::protocol::transaction::Raw {
+                contract: vec![contract],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tron_tx_owner_address_rederives_sender() {
+        let mut owner = [0x11u8; 21];
+        owner[0] = 0x41;
+        assert_eq!(tron_tx_owner_address(&transfer_tx(owner)).unwrap(), owner);
+    }
+
+    #[test]
+    fn mismatched_sender_is_rejected() {
+        let mut expected = [0x11u8; 21];
+        expected[0] = 0x41;
+        let mut other = [0x22u8; 21];
+        other[0] = 0x41;
+
+        let actual = tron_tx_owner_address(&transfer_tx(other)).unwrap();
+        let err = ensure_tron_sender_matches(expected, actual)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("tron_sender_mismatch"));
+
+        ensure_tron_sender_matches(expected, expected).unwrap();
+    }
+}
+
 #[cfg(test)]
 mod delegate_selection_tests {
     use super::select_delegate_executor_index;
@@ -120,3 +459,173 @@ mod delegate_selection_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod fill_tests {
+    use super::{TRXTransferIntent, evm_to_tron_raw21, fill_mismatch};
+    use crate::types::IntentType;
+    use alloy::primitives::{Address, U256};
+    use alloy::sol_types::SolValue;
+    use prost::Message;
+    use tron::protocol::transaction::contract::ContractType;
+
+    fn transfer_tx(to: Address, amount: i64) -> Vec<u8> {
+        let transfer = tron::protocol::TransferContract {
+            owner_address: [0x41u8; 21].to_vec(),
+            to_address: evm_to_tron_raw21(to).to_vec(),
+            amount,
+        };
+        let mut contract = tron::protocol::transaction::Contract {
+            r#type: ContractType::TransferContract as i32,
+            parameter: Some(Default::default()),
+            ..Default::default()
+        };
+        if let Some(param) = contract.parameter.as_mut() {
+            param.value = transfer.encode_to_vec();
+        }
+        tron::protocol::Transaction {
+            raw_data: Some(tron::protocol::transaction::Raw {
+                contract: vec![contract],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .encode_to_vec()
+    }
+
+    fn delegate_tx(receiver: Address, balance: i64, lock: bool, lock_period: i64) -> Vec<u8> {
+        let delegate = tron::protocol::DelegateResourceContract {
+            owner_address: [0x41u8; 21].to_vec(),
+            resource: 1,
+            balance,
+            receiver_address: evm_to_tron_raw21(receiver).to_vec(),
+            lock,
+            lock_period,
+        };
+        let mut contract = tron::protocol::transaction::Contract {
+            r#type: ContractType::DelegateResourceContract as i32,
+            parameter: Some(Default::default()),
+            ..Default::default()
+        };
+        if let Some(param) = contract.parameter.as_mut() {
+            param.value = delegate.encode_to_vec();
+        }
+        tron::protocol::Transaction {
+            raw_data: Some(tron::protocol::transaction::Raw {
+                contract: vec![contract],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .encode_to_vec()
+    }
+
+    fn delegate_intent(receiver: Address, balance_sun: u64, lock_period: u64) -> Vec<u8> {
+        super::DelegateResourceIntent {
+            receiver,
+            resource: 1,
+            balanceSun: U256::from(balance_sun),
+            lockPeriod: U256::from(lock_period),
+        }
+        .abi_encode()
+    }
+
+    fn trx_intent(to: Address, amount_sun: u64) -> Vec<u8> {
+        TRXTransferIntent {
+            to,
+            amountSun: U256::from(amount_sun),
+        }
+        .abi_encode()
+    }
+
+    #[test]
+    fn matching_trx_transfer_passes() {
+        let to = Address::repeat_byte(0x11);
+        let tx = transfer_tx(to, 1_234);
+        assert_eq!(
+            fill_mismatch(&tx, IntentType::TrxTransfer, &trx_intent(to, 1_234), None),
+            None
+        );
+    }
+
+    #[test]
+    fn wrong_recipient_or_amount_is_reported() {
+        let to = Address::repeat_byte(0x11);
+        let specs = trx_intent(to, 1_234);
+
+        let other = transfer_tx(Address::repeat_byte(0x22), 1_234);
+        let msg = fill_mismatch(&other, IntentType::TrxTransfer, &specs, None).unwrap();
+        assert!(msg.starts_with("to:"), "{msg}");
+
+        let short = transfer_tx(to, 1_000);
+        let msg = fill_mismatch(&short, IntentType::TrxTransfer, &specs, None).unwrap();
+        assert!(msg.starts_with("amount:"), "{msg}");
+    }
+
+    #[test]
+    fn delegate_with_at_least_the_balance_and_lock_passes() {
+        let to = Address::repeat_byte(0x11);
+        let specs = delegate_intent(to, 1_000, 100);
+        for tx in [
+            delegate_tx(to, 1_000, true, 100),
+            delegate_tx(to, 1_500, true, 200),
+        ] {
+            assert_eq!(
+                fill_mismatch(&tx, IntentType::DelegateResource, &specs, None),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn delegate_with_smaller_balance_is_reported() {
+        let to = Address::repeat_byte(0x11);
+        let specs = delegate_intent(to, 1_000, 100);
+        let tx = delegate_tx(to, 999, true, 100);
+        let msg = fill_mismatch(&tx, IntentType::DelegateResource, &specs, None).unwrap();
+        assert!(msg.starts_with("balance:"), "{msg}");
+    }
+
+    #[test]
+    fn unlocked_delegate_is_reported() {
+        let to = Address::repeat_byte(0x11);
+        let specs = delegate_intent(to, 1_000, 100);
+        let tx = delegate_tx(to, 1_000, false, 100);
+        let msg = fill_mismatch(&tx, IntentType::DelegateResource, &specs, None).unwrap();
+        assert!(msg.starts_with("lock:"), "{msg}");
+    }
+
+    #[test]
+    fn delegate_with_shorter_lock_period_is_reported() {
+        let to = Address::repeat_byte(0x11);
+        let specs = delegate_intent(to, 1_000, 100);
+        let tx = delegate_tx(to, 1_000, true, 99);
+        let msg = fill_mismatch(&tx, IntentType::DelegateResource, &specs, None).unwrap();
+        assert!(msg.starts_with("lock_period:"), "{msg}");
+    }
+
+    #[test]
+    fn wrong_contract_type_or_garbage_is_reported() {
+        let to = Address::repeat_byte(0x11);
+        let tx = transfer_tx(to, 1_234);
+        let specs = super::DelegateResourceIntent {
+            receiver: to,
+            resource: 1,
+            balanceSun: U256::from(1_234u64),
+            lockPeriod: U256::ZERO,
+        }
+        .abi_encode();
+        let msg = fill_mismatch(&tx, IntentType::DelegateResource, &specs, None).unwrap();
+        assert!(msg.starts_with("contract_type:"), "{msg}");
+
+        assert!(
+            fill_mismatch(
+                &[0xff, 0xff],
+                IntentType::TrxTransfer,
+                &trx_intent(to, 1),
+                None
+            )
+            .is_some()
+        );
+    }
+}
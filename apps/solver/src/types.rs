@@ -1,7 +1,8 @@
-use alloy::primitives::{Address, B256, U256};
+use alloy::primitives::{Address, B256, Bytes, U256, keccak256};
+use alloy::sol_types::SolValue;
 use anyhow::{Context, Result};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum IntentType {
     TriggerSmartContract = 0,
     UsdtTransfer = 1,
@@ -21,6 +22,15 @@ impl IntentType {
     }
 }
 
+/// How pool intent `deadline`s are expressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadlineKind {
+    /// Unix timestamp in seconds.
+    Timestamp,
+    /// Hub chain block number.
+    Block,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JobState {
     Ready,
@@ -33,9 +43,28 @@ pub enum JobState {
     ProvedWaitingSettlement,
     Done,
     FailedFatal,
+    /// Claim released on the hub via `unclaimIntent` after the job stalled (terminal).
+    Unclaimed,
+    /// Another solver claimed the intent first (`AlreadyClaimed`) (terminal).
+    LostRace,
 }
 
 impl JobState {
+    pub const ALL: [JobState; 12] = [
+        Self::Ready,
+        Self::Claimed,
+        Self::TronPrepared,
+        Self::TronSent,
+        Self::ProofBuilt,
+        Self::Proved,
+        Self::ProvedWaitingFunding,
+        Self::ProvedWaitingSettlement,
+        Self::Done,
+        Self::FailedFatal,
+        Self::Unclaimed,
+        Self::LostRace,
+    ];
+
     pub fn as_db_str(self) -> &'static str {
         match self {
             Self::Ready => "ready",
@@ -48,6 +77,8 @@ impl JobState {
             Self::ProvedWaitingSettlement => "proved_waiting_settlement",
             Self::Done => "done",
             Self::FailedFatal => "failed_fatal",
+            Self::Unclaimed => "unclaimed",
+            Self::LostRace => "lost_race",
         }
     }
 
@@ -63,6 +94,8 @@ impl JobState {
             "proved_waiting_settlement" => Ok(Self::ProvedWaitingSettlement),
             "done" => Ok(Self::Done),
             "failed_fatal" => Ok(Self::FailedFatal),
+            "unclaimed" => Ok(Self::Unclaimed),
+            "lost_race" => Ok(Self::LostRace),
             other => anyhow::bail!("unknown job state: {other}"),
         }
     }
@@ -90,7 +123,6 @@ pub fn parse_b256(s: &str) -> Result<B256> {
     Ok(B256::from(out))
 }
 
-#[allow(dead_code)]
 pub fn parse_address(s: &str) -> Result<Address> {
     s.trim().parse().context("parse address")
 }
@@ -100,9 +132,43 @@ pub fn parse_u256_dec(s: &str) -> Result<U256> {
     U256::from_str_radix(s, 10).context("parse u256 decimal")
 }
 
+/// Maker intent id, derived like `UntronIntents.createIntent`:
+/// `keccak256(abi.encodePacked(creator, keccak256(abi.encode(intent)), deadline))`.
+pub fn compute_intent_id(
+    creator: Address,
+    deadline: u64,
+    intent_type: IntentType,
+    specs: &[u8],
+    refund_beneficiary: Address,
+    token: Address,
+    amount: U256,
+) -> B256 {
+    let intent = (
+        intent_type as u8,
+        Bytes::copy_from_slice(specs),
+        refund_beneficiary,
+        token,
+        amount,
+    );
+    let intent_hash = keccak256(intent.abi_encode());
+    keccak256((creator, intent_hash, U256::from(deadline)).abi_encode_packed())
+}
+
+/// Receiver-originated intent id (`UntronIntents.receiverIntentId`).
+pub fn compute_receiver_intent_id(
+    forwarder: Address,
+    to_tron: Address,
+    forward_salt: B256,
+    token: Address,
+    amount: U256,
+) -> B256 {
+    let intent_hash = keccak256((forwarder, to_tron).abi_encode());
+    keccak256((intent_hash, forward_salt, token, amount).abi_encode_packed())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::JobState;
+    use super::*;
 
     #[test]
     fn job_state_roundtrip_db_strings() {
@@ -117,6 +183,8 @@ mod tests {
             JobState::ProvedWaitingSettlement,
             JobState::Done,
             JobState::FailedFatal,
+            JobState::Unclaimed,
+            JobState::LostRace,
         ];
 
         for state in states {
@@ -126,8 +194,95 @@ mod tests {
         }
     }
 
+    #[test]
+    fn job_state_all_roundtrips_distinct_db_strings() {
+        let mut seen = std::collections::HashSet::new();
+        for state in JobState::ALL {
+            let db = state.as_db_str();
+            assert_eq!(JobState::parse(db).expect("parse known state"), state);
+            assert!(seen.insert(db), "duplicate state in ALL: {db}");
+        }
+    }
+
     #[test]
     fn job_state_parse_rejects_unknown() {
         assert!(JobState::parse("not_a_real_state").is_err());
     }
+
+    fn word(v: impl Into<U256>) -> [u8; 32] {
+        v.into().to_be_bytes()
+    }
+
+    fn address_word(a: Address) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out[12..].copy_from_slice(a.as_slice());
+        out
+    }
+
+    #[test]
+    fn compute_intent_id_matches_solidity_encoding() {
+        let creator = Address::repeat_byte(0xc1);
+        let refund = Address::repeat_byte(0xbe);
+        let token = Address::repeat_byte(0x70);
+        let specs = vec![0xab; 33];
+        let amount = U256::from(1_234_567u64);
+        let deadline = 1_700_000_000u64;
+
+        // abi.encode(Intent): the struct is dynamic, so it's preceded by its offset, and `specs`
+        // is a length word followed by right-padded data.
+        let mut enc = Vec::new();
+        enc.extend_from_slice(&word(0x20u64));
+        enc.extend_from_slice(&word(IntentType::UsdtTransfer as u64));
+        enc.extend_from_slice(&word(0xa0u64));
+        enc.extend_from_slice(&address_word(refund));
+        enc.extend_from_slice(&address_word(token));
+        enc.extend_from_slice(&word(amount));
+        enc.extend_from_slice(&word(specs.len() as u64));
+        enc.extend_from_slice(&specs);
+        enc.resize(enc.len() + 31, 0);
+        let intent_hash = keccak256(&enc);
+
+        let mut packed = Vec::new();
+        packed.extend_from_slice(creator.as_slice());
+        packed.extend_from_slice(intent_hash.as_slice());
+        packed.extend_from_slice(&word(deadline));
+
+        assert_eq!(
+            compute_intent_id(
+                creator,
+                deadline,
+                IntentType::UsdtTransfer,
+                &specs,
+                refund,
+                token,
+                amount
+            ),
+            keccak256(&packed)
+        );
+    }
+
+    #[test]
+    fn compute_receiver_intent_id_matches_solidity_encoding() {
+        let forwarder = Address::repeat_byte(0xf0);
+        let to_tron = Address::repeat_byte(0x41);
+        let salt = B256::repeat_byte(0x5a);
+        let token = Address::repeat_byte(0x70);
+        let amount = U256::from(99u64);
+
+        let mut enc = Vec::new();
+        enc.extend_from_slice(&address_word(forwarder));
+        enc.extend_from_slice(&address_word(to_tron));
+        let intent_hash = keccak256(&enc);
+
+        let mut packed = Vec::new();
+        packed.extend_from_slice(intent_hash.as_slice());
+        packed.extend_from_slice(salt.as_slice());
+        packed.extend_from_slice(token.as_slice());
+        packed.extend_from_slice(&word(amount));
+
+        assert_eq!(
+            compute_receiver_intent_id(forwarder, to_tron, salt, token, amount),
+            keccak256(&packed)
+        );
+    }
 }
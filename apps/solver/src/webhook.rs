@@ -0,0 +1,158 @@
+use crate::{
+    config::{SummaryConfig, WebhookConfig},
+    db::SolverJob,
+    metrics::SolverTelemetry,
+};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use sha2::Sha256;
+
+pub const SIGNATURE_HEADER: &str = "X-Untron-Signature";
+
+/// Posts fatal job failures to an operator-configured endpoint.
+///
+/// Delivery is fire-and-forget: the request runs on its own task with the configured timeout, so
+/// a slow or unreachable endpoint never holds up the job state machine. Failures are only logged
+/// and counted.
+#[derive(Clone)]
+pub struct FatalWebhook {
+    http: Client,
+    url: String,
+    hmac_secret: Option<Vec<u8>>,
+    telemetry: SolverTelemetry,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct FatalPayload<'a> {
+    intent_id: String,
+    intent_type: i16,
+    state: &'a str,
+    error: &'a str,
+}
+
+impl FatalWebhook {
+    /// Returns `None` when no URL is configured.
+    pub fn new(cfg: &WebhookConfig, telemetry: SolverTelemetry) -> Option<Self> {
+        let url = cfg.url.clone()?;
+        Some(Self {
+            http: Client::builder()
+                .timeout(cfg.timeout)
+                .build()
+                .expect("reqwest"),
+            url,
+            hmac_secret: cfg.hmac_secret.as_ref().map(|s| s.as_bytes().to_vec()),
+            telemetry,
+        })
+    }
+
+    pub fn notify(&self, job: &SolverJob, error: &str) {
+        let payload = FatalPayload {
+            intent_id: format!("0x{}", hex::encode(job.intent_id)),
+            intent_type: job.intent_type,
+            state: &job.state,
+            error,
+        };
+        let body = match serde_json::to_vec(&payload) {
+            Ok(b) => b,
+            Err(err) => {
+                tracing::warn!(job_id = job.job_id, err = %err, "fatal webhook: encode failed");
+                self.telemetry.fatal_webhook_failed("error");
+                return;
+            }
+        };
+
+        let mut req = self
+            .http
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.hmac_secret {
+            req = req.header(SIGNATURE_HEADER, format!("sha256={}", sign(secret, &body)));
+        }
+        let req = req.body(body);
+
+        let telemetry = self.telemetry.clone();
+        let job_id = job.job_id;
+        tokio::spawn(async move {
+            let reason = match req.send().await {
+                Ok(resp) if resp.status().is_success() => return,
+                Ok(resp) => {
+                    tracing::warn!(job_id, status = %resp.status(), "fatal webhook rejected");
+                    "http_status"
+                }
+                Err(err) => {
+                    tracing::warn!(job_id, err = %err, "fatal webhook delivery failed");
+                    if err.is_timeout() { "timeout" } else { "error" }
+                }
+            };
+            telemetry.fatal_webhook_failed(reason);
+        });
+    }
+}
+
+/// Posts the periodic operator summary to a chat incoming webhook.
+///
+/// The rendered text goes out as both `text` (Slack) and `content` (Discord) next to the
+/// structured `summary`, so either service accepts the body unchanged. Fire-and-forget like
+/// [`FatalWebhook`]; failures are only logged.
+#[derive(Clone)]
+pub struct SummaryWebhook {
+    http: Client,
+    url: String,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct SummaryPayload<'a, T> {
+    text: &'a str,
+    content: &'a str,
+    summary: &'a T,
+}
+
+impl SummaryWebhook {
+    /// Returns `None` when no URL is configured.
+    pub fn new(cfg: &SummaryConfig) -> Option<Self> {
+        let url = cfg.webhook_url.clone()?;
+        Some(Self {
+            http: Client::builder()
+                .timeout(cfg.webhook_timeout)
+                .build()
+                .expect("reqwest"),
+            url,
+        })
+    }
+
+    pub fn post<T: serde::Serialize>(&self, text: &str, summary: &T) {
+        let payload = SummaryPayload {
+            text,
+            content: text,
+            summary,
+        };
+        let req = self.http.post(&self.url).json(&payload);
+        tokio::spawn(async move {
+            match req.send().await {
+                Ok(resp) if resp.status().is_success() => {}
+                Ok(resp) => tracing::warn!(status = %resp.status(), "summary webhook rejected"),
+                Err(err) => tracing::warn!(err = %err, "summary webhook delivery failed"),
+            }
+        });
+    }
+}
+
+/// Hex HMAC-SHA256 of `body`.
+fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("hmac accepts any key length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_matches_rfc4231_vector() {
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -6,20 +6,112 @@ use alloy::{
 use alloy_provider::ext::Erc4337Api;
 use anyhow::{Context, Result};
 use serde::Deserialize;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use alloy::rpc::types::eth::erc4337::PackedUserOperation;
 use alloy::rpc::types::eth::erc4337::SendUserOperationResponse;
 
+use crate::errors::{BundlerErrorClass, classify_bundler_error};
 use crate::packing::redact_url;
 
 const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+/// Consecutive bundler-side failures (timeouts, transport errors, unclassified rejections) after
+/// which a bundler is tried after the healthy ones.
+const DEPRIORITIZE_AFTER_FAILURES: u32 = 3;
+const DEPRIORITIZE_FOR: Duration = Duration::from_secs(60);
 
 #[derive(Clone)]
 pub(crate) struct BundlerPool {
     urls: Vec<String>,
     providers: Vec<DynProvider>,
     next_idx: usize,
+    health: BundlerHealthHandle,
+    attempts: Vec<BundlerAttempt>,
+}
+
+/// Per-bundler health shared between the pool and callers that talk to the same bundlers
+/// directly (e.g. receipt polling), so both see (and feed) the same deprioritization.
+#[derive(Clone)]
+pub struct BundlerHealthHandle {
+    urls: Arc<[String]>,
+    health: Arc<Mutex<Vec<BundlerHealth>>>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BundlerHealth {
+    consecutive_failures: u32,
+    deprioritized_until: Option<Instant>,
+}
+
+impl BundlerHealth {
+    fn deprioritized(&self, now: Instant) -> bool {
+        self.deprioritized_until.is_some_and(|t| now < t)
+    }
+}
+
+impl BundlerHealthHandle {
+    fn new(urls: &[String]) -> Self {
+        Self {
+            urls: urls.into(),
+            health: Arc::new(Mutex::new(vec![BundlerHealth::default(); urls.len()])),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<BundlerHealth>> {
+        self.health.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Configured bundler URLs, deprioritized ones last.
+    pub fn urls_healthy_first(&self) -> Vec<String> {
+        let order = healthy_first((0..self.urls.len()).collect(), &self.lock(), Instant::now());
+        order
+            .into_iter()
+            .map(|idx| self.urls[idx].clone())
+            .collect()
+    }
+
+    /// Records a call to `url` made outside the pool, counting it like the pool's own calls.
+    /// `err` is the bundler's error message, `None` for timeouts. Unknown URLs are ignored.
+    pub fn record(&self, url: &str, ok: bool, err: Option<&str>) {
+        if let Some(idx) = self.urls.iter().position(|u| u == url) {
+            self.mark(idx, ok, err);
+        }
+    }
+
+    /// Rejections with an AA code (or a known node rejection) are about the op, not the bundler,
+    /// so they don't count towards deprioritizing it.
+    fn mark(&self, idx: usize, ok: bool, err: Option<&str>) {
+        let mut health = self.lock();
+        if ok {
+            health[idx] = BundlerHealth::default();
+            return;
+        }
+        if err.is_some_and(|e| classify_bundler_error(e) != BundlerErrorClass::Other) {
+            return;
+        }
+        let health = &mut health[idx];
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if health.consecutive_failures >= DEPRIORITIZE_AFTER_FAILURES {
+            health.deprioritized_until = Some(Instant::now() + DEPRIORITIZE_FOR);
+            tracing::warn!(
+                bundler = %redact_url(&self.urls[idx]),
+                failures = health.consecutive_failures,
+                "bundler failing; deprioritizing"
+            );
+        }
+    }
+}
+
+/// Outcome of one bundler JSON-RPC call, for callers that export metrics.
+#[derive(Debug, Clone)]
+pub struct BundlerAttempt {
+    /// Bundler URL as configured; it may carry an API key, so redact it before logging or
+    /// using it as a metric label.
+    pub bundler: String,
+    pub method: &'static str,
+    pub ok: bool,
+    pub ms: u64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -130,17 +222,60 @@ impl BundlerPool {
             providers.push(DynProvider::new(provider));
         }
 
+        let health = BundlerHealthHandle::new(&urls);
         Ok(Self {
             urls,
             providers,
             next_idx: 0,
+            health,
+            attempts: Vec::new(),
         })
     }
 
-    fn mark_success(&mut self, idx: usize) {
+    pub(crate) fn url(&self, idx: usize) -> &str {
+        &self.urls[idx]
+    }
+
+    /// Bundler attempts made since the last call, in order. Drains the buffer.
+    pub(crate) fn take_attempts(&mut self) -> Vec<BundlerAttempt> {
+        std::mem::take(&mut self.attempts)
+    }
+
+    pub(crate) fn health(&self) -> BundlerHealthHandle {
+        self.health.clone()
+    }
+
+    /// Round-robin from `next_idx`, with deprioritized bundlers moved to the back.
+    fn order(&self) -> Vec<usize> {
+        healthy_first(
+            rotate_order(self.next_idx, self.providers.len()),
+            &self.health.lock(),
+            Instant::now(),
+        )
+    }
+
+    fn mark_success(&mut self, idx: usize, method: &'static str, ms: u64) {
         if !self.providers.is_empty() {
             self.next_idx = (idx + 1) % self.providers.len();
         }
+        self.health.mark(idx, true, None);
+        self.attempts.push(BundlerAttempt {
+            bundler: self.urls[idx].clone(),
+            method,
+            ok: true,
+            ms,
+        });
+    }
+
+    /// `err` is `None` for timeouts.
+    fn mark_failure(&mut self, idx: usize, method: &'static str, ms: u64, err: Option<&str>) {
+        self.attempts.push(BundlerAttempt {
+            bundler: self.urls[idx].clone(),
+            method,
+            ok: false,
+            ms,
+        });
+        self.health.mark(idx, false, err);
     }
 
     pub(crate) async fn estimate_user_operation_gas(
@@ -148,13 +283,13 @@ impl BundlerPool {
         user_op: &PackedUserOperation,
         entry_point: Address,
     ) -> Result<UserOperationGasEstimationV07> {
+        const METHOD: &str = "eth_estimateUserOperationGas";
         let mut last_err: Option<anyhow::Error> = None;
 
-        let order = rotate_order(self.next_idx, self.providers.len());
-        for idx in order {
-            let url = &self.urls[idx];
-            let url = redact_url(url);
+        for idx in self.order() {
+            let url = redact_url(&self.urls[idx]);
             let provider = &self.providers[idx];
+            let started = Instant::now();
 
             // Use a tolerant response type: many bundlers return v0.7 field names
             // (`verificationGasLimit`, `paymasterVerificationGasLimit`, ...), but alloy's
@@ -167,16 +302,14 @@ impl BundlerPool {
             match tokio::time::timeout(RPC_TIMEOUT, fut).await {
                 Ok(Ok(v)) => {
                     let v: EstimateAny = v;
-                    self.mark_success(idx);
+                    self.mark_success(idx, METHOD, elapsed_ms(started));
                     return Ok(v.into());
                 }
                 Ok(Err(err)) => {
-                    let err = anyhow::Error::new(err).context("eth_estimateUserOperationGas");
-                    tracing::warn!(
-                        bundler = %url,
-                        err = %format!("{err:#}"),
-                        "bundler rpc failed"
-                    );
+                    let err = anyhow::Error::new(err).context(METHOD);
+                    let msg = format!("{err:#}");
+                    tracing::warn!(bundler = %url, err = %msg, "bundler rpc failed");
+                    self.mark_failure(idx, METHOD, elapsed_ms(started), Some(&msg));
                     last_err = Some(err);
                 }
                 Err(_) => {
@@ -186,6 +319,7 @@ impl BundlerPool {
                         err = %err,
                         "bundler rpc timed out (eth_estimateUserOperationGas)"
                     );
+                    self.mark_failure(idx, METHOD, elapsed_ms(started), None);
                     last_err = Some(err);
                 }
             }
@@ -200,14 +334,14 @@ impl BundlerPool {
         &mut self,
         user_op: &PackedUserOperation,
         entry_point: Address,
-    ) -> Result<SendUserOperationResponse> {
+    ) -> Result<(SendUserOperationResponse, usize)> {
+        const METHOD: &str = "eth_sendUserOperation";
         let mut last_err: Option<anyhow::Error> = None;
 
-        let order = rotate_order(self.next_idx, self.providers.len());
-        for idx in order {
-            let url = &self.urls[idx];
-            let url = redact_url(url);
+        for idx in self.order() {
+            let url = redact_url(&self.urls[idx]);
             let provider = &self.providers[idx];
+            let started = Instant::now();
 
             // Use a tolerant response type: some bundlers return `{ userOpHash }`, others return
             // the hash string directly.
@@ -220,16 +354,14 @@ impl BundlerPool {
                 Ok(Ok(v)) => {
                     let v: SendUserOperationResponseAny = v;
                     let v: SendUserOperationResponse = v.into();
-                    self.mark_success(idx);
-                    return Ok(v);
+                    self.mark_success(idx, METHOD, elapsed_ms(started));
+                    return Ok((v, idx));
                 }
                 Ok(Err(err)) => {
-                    let err = anyhow::Error::new(err).context("eth_sendUserOperation");
-                    tracing::warn!(
-                        bundler = %url,
-                        err = %format!("{err:#}"),
-                        "bundler rpc failed"
-                    );
+                    let err = anyhow::Error::new(err).context(METHOD);
+                    let msg = format!("{err:#}");
+                    tracing::warn!(bundler = %url, err = %msg, "bundler rpc failed");
+                    self.mark_failure(idx, METHOD, elapsed_ms(started), Some(&msg));
                     last_err = Some(err);
                 }
                 Err(_) => {
@@ -239,6 +371,7 @@ impl BundlerPool {
                         err = %err,
                         "bundler rpc timed out (eth_sendUserOperation)"
                     );
+                    self.mark_failure(idx, METHOD, elapsed_ms(started), None);
                     last_err = Some(err);
                 }
             }
@@ -249,27 +382,25 @@ impl BundlerPool {
     }
 
     pub(crate) async fn supported_entry_points(&mut self) -> Result<Vec<Address>> {
+        const METHOD: &str = "eth_supportedEntryPoints";
         let mut last_err: Option<anyhow::Error> = None;
 
-        let order = rotate_order(self.next_idx, self.providers.len());
-        for idx in order {
-            let url = &self.urls[idx];
-            let url = redact_url(url);
+        for idx in self.order() {
+            let url = redact_url(&self.urls[idx]);
             let provider = &self.providers[idx];
+            let started = Instant::now();
 
             let fut = provider.supported_entry_points();
             match tokio::time::timeout(RPC_TIMEOUT, fut).await {
                 Ok(Ok(v)) => {
-                    self.mark_success(idx);
+                    self.mark_success(idx, METHOD, elapsed_ms(started));
                     return Ok(v);
                 }
                 Ok(Err(err)) => {
-                    let err = anyhow::Error::new(err).context("eth_supportedEntryPoints");
-                    tracing::warn!(
-                        bundler = %url,
-                        err = %format!("{err:#}"),
-                        "bundler rpc failed"
-                    );
+                    let err = anyhow::Error::new(err).context(METHOD);
+                    let msg = format!("{err:#}");
+                    tracing::warn!(bundler = %url, err = %msg, "bundler rpc failed");
+                    self.mark_failure(idx, METHOD, elapsed_ms(started), Some(&msg));
                     last_err = Some(err);
                 }
                 Err(_) => {
@@ -279,6 +410,7 @@ impl BundlerPool {
                         err = %err,
                         "bundler rpc timed out (eth_supportedEntryPoints)"
                     );
+                    self.mark_failure(idx, METHOD, elapsed_ms(started), None);
                     last_err = Some(err);
                 }
             }
@@ -289,6 +421,16 @@ impl BundlerPool {
     }
 }
 
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Stable: healthy bundlers keep their round-robin order, as do the deprioritized ones after them.
+fn healthy_first(mut order: Vec<usize>, health: &[BundlerHealth], now: Instant) -> Vec<usize> {
+    order.sort_by_key(|&idx| health[idx].deprioritized(now));
+    order
+}
+
 fn rotate_order(start_idx: usize, len: usize) -> Vec<usize> {
     if len == 0 {
         return Vec::new();
@@ -308,6 +450,42 @@ mod tests {
         assert_eq!(rotate_order(5, 3), vec![2, 0, 1]);
     }
 
+    #[test]
+    fn deprioritized_bundlers_are_tried_last() {
+        let now = Instant::now();
+        let mut health = vec![BundlerHealth::default(); 3];
+        health[1].deprioritized_until = Some(now + Duration::from_secs(10));
+        assert_eq!(
+            healthy_first(rotate_order(1, 3), &health, now),
+            vec![2, 0, 1]
+        );
+
+        // Once the window passes, the bundler is back in rotation.
+        health[1].deprioritized_until = Some(now);
+        assert_eq!(
+            healthy_first(rotate_order(1, 3), &health, now),
+            vec![1, 2, 0]
+        );
+    }
+
+    #[test]
+    fn shared_health_deprioritizes_on_bundler_failures_only() {
+        let urls = ["http://a".to_string(), "http://b".to_string()];
+        let health = BundlerHealthHandle::new(&urls);
+        for _ in 0..DEPRIORITIZE_AFTER_FAILURES {
+            health.record("http://a", false, Some("AA21 didn't pay prefund"));
+        }
+        assert_eq!(health.urls_healthy_first(), urls);
+
+        for _ in 0..DEPRIORITIZE_AFTER_FAILURES {
+            health.record("http://a", false, None);
+        }
+        assert_eq!(health.urls_healthy_first(), ["http://b", "http://a"]);
+
+        health.record("http://a", true, None);
+        assert_eq!(health.urls_healthy_first(), urls);
+    }
+
     #[test]
     fn send_user_operation_response_accepts_object_or_hash_string() {
         let obj = r#"{"userOpHash":"0x1234"}"#;
@@ -14,6 +14,11 @@ alloy::sol! {
         function executeUserOpWithErrorString(address to, uint256 value, bytes data, uint8 operation) external;
     }
 
+    /// Safe `MultiSendCallOnly`; delegatecalled by the Safe to run several calls atomically.
+    interface IMultiSend {
+        function multiSend(bytes memory transactions) external payable;
+    }
+
     interface ISafe {
         function setup(
             address[] calldata owners,
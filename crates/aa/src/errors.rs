@@ -0,0 +1,145 @@
+/// What a bundler rejection of a user operation means for the caller.
+///
+/// Classification is by ERC-4337 `AAxx` code where the bundler reports one, falling back to the
+/// node error strings bundlers forward verbatim (nonce and fee rejections).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundlerErrorClass {
+    /// AA25 or a node nonce rejection: another op took the nonce. Rebuild with a fresh nonce.
+    Nonce,
+    /// AA21: the account could not pay its prefund (no ETH / EntryPoint deposit and no
+    /// sponsorship). Usually a funding or paymaster configuration issue that clears once fixed.
+    Prefund,
+    /// AA3x: the paymaster is undeployed, under-funded, expired, or reverted during validation.
+    /// Often a transient sponsor outage; a rebuild fetches fresh paymaster data.
+    Paymaster,
+    /// AA1x, AA20, AA22-AA24: account creation or validation failed. Resubmitting the same op
+    /// fails the same way.
+    Validation,
+    /// AA26, AA4x, AA5x or an underpriced fee: gas limits or fees are stale. Rebuild.
+    Gas,
+    /// No recognizable AA code or known node rejection.
+    Other,
+}
+
+impl BundlerErrorClass {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Nonce => "nonce",
+            Self::Prefund => "prefund",
+            Self::Paymaster => "paymaster",
+            Self::Validation => "validation",
+            Self::Gas => "gas",
+            Self::Other => "other",
+        }
+    }
+
+    /// Whether the stored op should be discarded so the next attempt builds (and signs) a new one.
+    pub fn rebuild_userop(self) -> bool {
+        !matches!(self, Self::Other)
+    }
+
+    /// Whether retrying is unlikely to help until the op or the account itself changes.
+    pub fn is_structural(self) -> bool {
+        matches!(self, Self::Validation)
+    }
+}
+
+/// Classifies a bundler / node error message for a submitted user operation.
+pub fn classify_bundler_error(msg: &str) -> BundlerErrorClass {
+    if let Some(code) = aa_code(msg) {
+        return match code {
+            25 => BundlerErrorClass::Nonce,
+            21 => BundlerErrorClass::Prefund,
+            30..=39 => BundlerErrorClass::Paymaster,
+            10..=19 | 20 | 22..=24 => BundlerErrorClass::Validation,
+            26 | 40..=59 => BundlerErrorClass::Gas,
+            _ => BundlerErrorClass::Other,
+        };
+    }
+
+    let m = msg.to_ascii_lowercase();
+    if m.contains("invalid account nonce")
+        || m.contains("nonce too low")
+        || m.contains("nonce too high")
+    {
+        return BundlerErrorClass::Nonce;
+    }
+    if m.contains("underpriced")
+        || m.contains("fee too low")
+        || m.contains("max fee per gas less than block base fee")
+        || m.contains("maxfeepergas")
+        || m.contains("maxpriorityfeepergas")
+    {
+        return BundlerErrorClass::Gas;
+    }
+    if m.contains("paymaster")
+        && (m.contains("expired") || m.contains("deposit") || m.contains("stake"))
+    {
+        return BundlerErrorClass::Paymaster;
+    }
+    BundlerErrorClass::Other
+}
+
+/// First standalone `AAxx` token in `msg` (so hex such as `0xaa25...` doesn't match).
+fn aa_code(msg: &str) -> Option<u8> {
+    let b = msg.as_bytes();
+    (0..b.len().saturating_sub(3)).find_map(|i| {
+        let standalone_start = i == 0 || !b[i - 1].is_ascii_alphanumeric();
+        let standalone_end = b.get(i + 4).is_none_or(|c| !c.is_ascii_alphanumeric());
+        let is_code = b[i].eq_ignore_ascii_case(&b'a')
+            && b[i + 1].eq_ignore_ascii_case(&b'a')
+            && b[i + 2].is_ascii_digit()
+            && b[i + 3].is_ascii_digit();
+        (standalone_start && standalone_end && is_code)
+            .then(|| (b[i + 2] - b'0') * 10 + (b[i + 3] - b'0'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_by_aa_code() {
+        let c = classify_bundler_error;
+        assert_eq!(c("AA25 invalid account nonce"), BundlerErrorClass::Nonce);
+        assert_eq!(
+            c("UserOperation reverted: AA21 didn't pay prefund"),
+            BundlerErrorClass::Prefund
+        );
+        assert_eq!(c("AA23 reverted: GS026"), BundlerErrorClass::Validation);
+        assert_eq!(
+            c("FailedOp(0, \"AA33 reverted\")"),
+            BundlerErrorClass::Paymaster
+        );
+        assert_eq!(
+            c("AA31 paymaster deposit too low"),
+            BundlerErrorClass::Paymaster
+        );
+        assert_eq!(c("AA40 over verificationGasLimit"), BundlerErrorClass::Gas);
+    }
+
+    #[test]
+    fn falls_back_to_node_messages() {
+        let c = classify_bundler_error;
+        assert_eq!(c("nonce too low"), BundlerErrorClass::Nonce);
+        assert_eq!(
+            c("replacement transaction underpriced"),
+            BundlerErrorClass::Gas
+        );
+        assert_eq!(
+            c("insufficient funds for transfer"),
+            BundlerErrorClass::Other
+        );
+        // Hex payloads are not AA codes.
+        assert_eq!(c("revert data 0xaa2300ff"), BundlerErrorClass::Other);
+    }
+
+    #[test]
+    fn only_unknown_errors_keep_the_prepared_op() {
+        assert!(BundlerErrorClass::Validation.rebuild_userop());
+        assert!(BundlerErrorClass::Validation.is_structural());
+        assert!(!BundlerErrorClass::Prefund.is_structural());
+        assert!(!BundlerErrorClass::Other.rebuild_userop());
+    }
+}
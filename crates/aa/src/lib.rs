@@ -1,9 +1,13 @@
 mod bundler_pool;
 mod contracts;
+mod errors;
+#[cfg(feature = "mock-bundler")]
+mod mock_bundler;
 mod packing;
 pub mod paymaster;
 mod safe;
 mod sender;
+mod signer;
 mod signing;
 
 pub use sender::{
@@ -11,7 +15,15 @@ pub use sender::{
     Safe4337UserOpSenderOptions, Safe4337UserOpSubmission,
 };
 
+pub use bundler_pool::{BundlerAttempt, BundlerHealthHandle};
+pub use errors::{BundlerErrorClass, classify_bundler_error};
+
+pub use signer::{HubSigner, HubSignerWallet, LocalHubSigner, RemoteHubSigner, SignFuture};
+
 pub use safe::{Safe4337Config, SafeDeterministicDeploymentConfig};
 
 // Exposed for e2e harnesses that want to provision a Safe before starting a solver process.
 pub use safe::ensure_safe_deployed;
+
+#[cfg(feature = "mock-bundler")]
+pub use mock_bundler::{MockBundler, MockBundlerConfig};
@@ -0,0 +1,375 @@
+//! Minimal in-process ERC-4337 bundler for offline Safe4337 testing.
+//!
+//! `MockBundler` serves the subset of the bundler JSON-RPC API used by
+//! [`Safe4337UserOpSender`](crate::Safe4337UserOpSender) and the solver's receipt polling.
+//! Userops are not simulated or batched: each `eth_sendUserOperation` is submitted right away
+//! as a single-op `EntryPoint.handleOps` transaction from a local executor key, and the resulting
+//! `UserOperationEvent` is served back via `eth_getUserOperationReceipt`.
+//!
+//! This is only meant for local chains (e.g. anvil) with an EntryPoint v0.7 deployment.
+
+use alloy::network::EthereumWallet;
+use alloy::primitives::{Address, B256, Bytes, FixedBytes, U256};
+use alloy::providers::{DynProvider, Provider, ProviderBuilder};
+use alloy::rpc::client::{BuiltInConnectionString, RpcClient};
+use alloy::rpc::types::eth::erc4337::PackedUserOperation;
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{Context, Result};
+use axum::{Json, Router, extract::State, routing::post};
+use serde_json::{Value, json};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{sync::Mutex, task::JoinHandle};
+
+use crate::packing::{pack_init_code, pack_paymaster_and_data};
+
+alloy::sol! {
+    /// Onchain (packed) v0.7 user operation layout.
+    struct PackedUserOperationV07 {
+        address sender;
+        uint256 nonce;
+        bytes initCode;
+        bytes callData;
+        bytes32 accountGasLimits;
+        uint256 preVerificationGas;
+        bytes32 gasFees;
+        bytes paymasterAndData;
+        bytes signature;
+    }
+
+    #[sol(rpc)]
+    interface IEntryPointV07 {
+        function handleOps(PackedUserOperationV07[] calldata ops, address payable beneficiary) external;
+        function getUserOpHash(PackedUserOperationV07 calldata userOp) external view returns (bytes32);
+
+        event UserOperationEvent(
+            bytes32 indexed userOpHash,
+            address indexed sender,
+            address indexed paymaster,
+            uint256 nonce,
+            bool success,
+            uint256 actualGasCost,
+            uint256 actualGasUsed
+        );
+    }
+}
+
+const JSONRPC_METHOD_NOT_FOUND: i64 = -32601;
+const JSONRPC_INVALID_PARAMS: i64 = -32602;
+// Matches the code used by common bundlers for userops rejected during validation.
+const JSONRPC_USEROP_REJECTED: i64 = -32500;
+
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct MockBundlerConfig {
+    pub rpc_url: String,
+    pub entrypoint: Address,
+    /// Key used to submit `handleOps`. Also receives the EntryPoint's gas refund.
+    pub executor_private_key: [u8; 32],
+}
+
+struct MockBundlerState {
+    entrypoint: Address,
+    chain_id: u64,
+    beneficiary: Address,
+    provider: DynProvider,
+    /// Keyed by userop hash; holds the `eth_getUserOperationReceipt` result.
+    receipts: Mutex<HashMap<B256, Value>>,
+    /// Serializes `handleOps` submissions so executor nonces never race.
+    submit: Mutex<()>,
+}
+
+pub struct MockBundler {
+    pub url: String,
+    handle: JoinHandle<()>,
+}
+
+impl MockBundler {
+    pub async fn start(cfg: MockBundlerConfig) -> Result<Self> {
+        let signer = PrivateKeySigner::from_bytes(&cfg.executor_private_key.into())
+            .context("invalid executor private key")?;
+        let beneficiary = signer.address();
+
+        let transport = BuiltInConnectionString::connect(&cfg.rpc_url)
+            .await
+            .with_context(|| format!("connect rpc: {}", cfg.rpc_url))?;
+        let client = RpcClient::builder().transport(transport, false);
+        let provider = ProviderBuilder::new()
+            .wallet(EthereumWallet::from(signer))
+            .connect_client(client);
+        let provider = DynProvider::new(provider);
+        let chain_id = provider.get_chain_id().await.context("eth_chainId")?;
+
+        let state = Arc::new(MockBundlerState {
+            entrypoint: cfg.entrypoint,
+            chain_id,
+            beneficiary,
+            provider,
+            receipts: Mutex::new(HashMap::new()),
+            submit: Mutex::new(()),
+        });
+
+        let app = Router::new().route("/", post(handle)).with_state(state);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("bind mock bundler")?;
+        let addr: SocketAddr = listener.local_addr().context("mock bundler local_addr")?;
+
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+
+        Ok(Self {
+            url: format!("http://{addr}"),
+            handle,
+        })
+    }
+}
+
+impl Drop for MockBundler {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+async fn handle(State(state): State<Arc<MockBundlerState>>, Json(req): Json<Value>) -> Json<Value> {
+    let id = req.get("id").cloned().unwrap_or(Value::Null);
+    let method = req.get("method").and_then(|v| v.as_str()).unwrap_or("");
+    let params = req.get("params").cloned().unwrap_or(Value::Null);
+
+    let out = match method {
+        "eth_chainId" => Ok(json!(format!("{:#x}", state.chain_id))),
+        "eth_supportedEntryPoints" => Ok(json!([state.entrypoint])),
+        "eth_estimateUserOperationGas" => estimate_user_operation_gas(&state, &params),
+        "eth_sendUserOperation" => send_user_operation(&state, &params).await,
+        "eth_getUserOperationReceipt" => get_user_operation_receipt(&state, &params).await,
+        _ => Err((
+            JSONRPC_METHOD_NOT_FOUND,
+            format!("method not found: {method}"),
+        )),
+    };
+
+    Json(match out {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }),
+    })
+}
+
+type RpcResult = std::result::Result<Value, (i64, String)>;
+
+fn userop_param(state: &MockBundlerState, params: &Value) -> Result<PackedUserOperation> {
+    let userop = params.get(0).context("missing userop param")?;
+    let userop: PackedUserOperation =
+        serde_json::from_value(userop.clone()).context("decode userop")?;
+    let entrypoint: Address = params
+        .get(1)
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .context("decode entrypoint")?
+        .context("missing entrypoint param")?;
+    if entrypoint != state.entrypoint {
+        anyhow::bail!("unsupported entrypoint: {entrypoint:#x}");
+    }
+    Ok(userop)
+}
+
+fn estimate_user_operation_gas(state: &MockBundlerState, params: &Value) -> RpcResult {
+    let userop =
+        userop_param(state, params).map_err(|e| (JSONRPC_INVALID_PARAMS, format!("{e:#}")))?;
+
+    // No simulation: hand back generous fixed limits. Overpaying is fine on a local chain, and
+    // validation failures surface on `eth_sendUserOperation` instead.
+    let (pm_verification, pm_post_op) = match userop.paymaster {
+        Some(_) => (U256::from(300_000u64), U256::from(100_000u64)),
+        None => (U256::ZERO, U256::ZERO),
+    };
+    Ok(json!({
+        "preVerificationGas": U256::from(100_000u64),
+        "verificationGasLimit": U256::from(1_500_000u64),
+        "callGasLimit": U256::from(3_000_000u64),
+        "paymasterVerificationGasLimit": pm_verification,
+        "paymasterPostOpGasLimit": pm_post_op,
+    }))
+}
+
+async fn send_user_operation(state: &MockBundlerState, params: &Value) -> RpcResult {
+    let userop =
+        userop_param(state, params).map_err(|e| (JSONRPC_INVALID_PARAMS, format!("{e:#}")))?;
+    let packed = pack_userop(&userop).map_err(|e| (JSONRPC_INVALID_PARAMS, format!("{e:#}")))?;
+
+    let _guard = state.submit.lock().await;
+    let (userop_hash, receipt) = submit_handle_ops(state, packed)
+        .await
+        .map_err(|e| (JSONRPC_USEROP_REJECTED, format!("{e:#}")))?;
+
+    state.receipts.lock().await.insert(userop_hash, receipt);
+    Ok(json!(userop_hash))
+}
+
+async fn submit_handle_ops(
+    state: &MockBundlerState,
+    packed: PackedUserOperationV07,
+) -> Result<(B256, Value)> {
+    let entrypoint = IEntryPointV07::new(state.entrypoint, &state.provider);
+
+    let userop_hash = entrypoint
+        .getUserOpHash(packed.clone())
+        .call()
+        .await
+        .context("EntryPoint.getUserOpHash")?;
+
+    let pending = entrypoint
+        .handleOps(vec![packed], state.beneficiary)
+        .send()
+        .await
+        .context("send EntryPoint.handleOps")?;
+    let receipt = pending
+        .with_timeout(Some(RECEIPT_TIMEOUT))
+        .get_receipt()
+        .await
+        .context("wait EntryPoint.handleOps receipt")?;
+    if !receipt.status() {
+        anyhow::bail!("handleOps tx reverted: {}", receipt.transaction_hash);
+    }
+
+    let event = receipt
+        .inner
+        .logs()
+        .iter()
+        .filter(|log| log.address() == state.entrypoint)
+        .filter_map(|log| log.log_decode::<IEntryPointV07::UserOperationEvent>().ok())
+        .map(|log| log.inner.data)
+        .find(|ev| ev.userOpHash == userop_hash)
+        .context("handleOps receipt has no UserOperationEvent for userop")?;
+
+    let out = json!({
+        "userOpHash": userop_hash,
+        "entryPoint": state.entrypoint,
+        "sender": event.sender,
+        "nonce": event.nonce,
+        "paymaster": event.paymaster,
+        "success": event.success,
+        "actualGasCost": event.actualGasCost,
+        "actualGasUsed": event.actualGasUsed,
+        "receipt": {
+            "transactionHash": receipt.transaction_hash,
+            "blockHash": receipt.block_hash,
+            "blockNumber": receipt.block_number.map(|n| format!("{n:#x}")),
+        },
+    });
+    Ok((userop_hash, out))
+}
+
+async fn get_user_operation_receipt(state: &MockBundlerState, params: &Value) -> RpcResult {
+    let userop_hash: B256 = params
+        .get(0)
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .ok()
+        .flatten()
+        .ok_or_else(|| {
+            (
+                JSONRPC_INVALID_PARAMS,
+                "invalid userop hash param".to_string(),
+            )
+        })?;
+    let receipts = state.receipts.lock().await;
+    Ok(receipts.get(&userop_hash).cloned().unwrap_or(Value::Null))
+}
+
+fn pack_userop(userop: &PackedUserOperation) -> Result<PackedUserOperationV07> {
+    let init_code = pack_init_code(userop.factory, userop.factory_data.as_ref())?;
+    let paymaster_and_data = pack_paymaster_and_data(
+        userop.paymaster,
+        userop.paymaster_verification_gas_limit,
+        userop.paymaster_post_op_gas_limit,
+        userop.paymaster_data.as_ref(),
+    )?;
+
+    Ok(PackedUserOperationV07 {
+        sender: userop.sender,
+        nonce: userop.nonce,
+        initCode: Bytes::from(init_code),
+        callData: userop.call_data.clone(),
+        accountGasLimits: pack_u128_pair(
+            userop.verification_gas_limit,
+            userop.call_gas_limit,
+            "verificationGasLimit",
+            "callGasLimit",
+        )?,
+        preVerificationGas: userop.pre_verification_gas,
+        gasFees: pack_u128_pair(
+            userop.max_priority_fee_per_gas,
+            userop.max_fee_per_gas,
+            "maxPriorityFeePerGas",
+            "maxFeePerGas",
+        )?,
+        paymasterAndData: Bytes::from(paymaster_and_data),
+        signature: userop.signature.clone(),
+    })
+}
+
+fn pack_u128_pair(
+    hi: U256,
+    lo: U256,
+    hi_label: &'static str,
+    lo_label: &'static str,
+) -> Result<FixedBytes<32>> {
+    let hi = u128::try_from(hi).with_context(|| format!("{hi_label} overflows uint128"))?;
+    let lo = u128::try_from(lo).with_context(|| format!("{lo_label} overflows uint128"))?;
+    let mut out = [0u8; 32];
+    out[..16].copy_from_slice(&hi.to_be_bytes());
+    out[16..].copy_from_slice(&lo.to_be_bytes());
+    Ok(FixedBytes(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_u128_pair_layout() {
+        let out = pack_u128_pair(U256::from(1u64), U256::from(2u64), "hi", "lo").unwrap();
+        assert_eq!(out[15], 1);
+        assert_eq!(out[31], 2);
+        assert!(out[..15].iter().all(|b| *b == 0));
+        assert!(out[16..31].iter().all(|b| *b == 0));
+
+        assert!(pack_u128_pair(U256::MAX, U256::ZERO, "hi", "lo").is_err());
+    }
+
+    #[test]
+    fn pack_userop_self_paid() {
+        let userop = PackedUserOperation {
+            sender: Address::repeat_byte(0x11),
+            nonce: U256::from(7u64),
+            factory: None,
+            factory_data: None,
+            call_data: Bytes::from(vec![0xAA]),
+            call_gas_limit: U256::from(3u64),
+            verification_gas_limit: U256::from(4u64),
+            pre_verification_gas: U256::from(5u64),
+            max_fee_per_gas: U256::from(6u64),
+            max_priority_fee_per_gas: U256::from(1u64),
+            paymaster: None,
+            paymaster_verification_gas_limit: None,
+            paymaster_post_op_gas_limit: None,
+            paymaster_data: None,
+            signature: Bytes::from(vec![0xBB]),
+        };
+        let packed = pack_userop(&userop).unwrap();
+        assert!(packed.initCode.is_empty());
+        assert!(packed.paymasterAndData.is_empty());
+        assert_eq!(packed.accountGasLimits[15], 4);
+        assert_eq!(packed.accountGasLimits[31], 3);
+        assert_eq!(packed.gasFees[15], 1);
+        assert_eq!(packed.gasFees[31], 6);
+    }
+}
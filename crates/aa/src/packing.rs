@@ -58,6 +58,21 @@ pub(crate) fn pack_paymaster_and_data(
     }
 }
 
+/// Packs calls for `MultiSendCallOnly.multiSend`: per call
+/// `operation (1) || to (20) || value (32) || data length (32) || data`, all plain calls with zero
+/// value.
+pub(crate) fn encode_multisend_calls(calls: &[(Address, Vec<u8>)]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(calls.iter().map(|(_, d)| 85 + d.len()).sum());
+    for (to, data) in calls {
+        out.push(0u8);
+        out.extend_from_slice(to.as_slice());
+        out.extend_from_slice(&U256::ZERO.to_be_bytes::<32>());
+        out.extend_from_slice(&U256::from(data.len()).to_be_bytes::<32>());
+        out.extend_from_slice(data);
+    }
+    out
+}
+
 pub(crate) fn ensure_u48(v: u64, label: &'static str) -> Result<()> {
     if v > 0xFFFF_FFFF_FFFF {
         anyhow::bail!("{label} must fit in uint48");
@@ -119,6 +134,21 @@ mod tests {
         assert_eq!(out.len(), 20 + 16 + 16 + 3);
     }
 
+    #[test]
+    fn encode_multisend_calls_layout() {
+        let a = Address::repeat_byte(0x11);
+        let b = Address::repeat_byte(0x22);
+        let out = encode_multisend_calls(&[(a, vec![0xAA, 0xBB]), (b, Vec::new())]);
+        assert_eq!(out.len(), (85 + 2) + 85);
+        assert_eq!(out[0], 0);
+        assert_eq!(&out[1..21], a.as_slice());
+        assert!(out[21..53].iter().all(|&x| x == 0));
+        assert_eq!(U256::from_be_slice(&out[53..85]), U256::from(2u64));
+        assert_eq!(&out[85..87], &[0xAA, 0xBB]);
+        assert_eq!(&out[88..108], b.as_slice());
+        assert_eq!(U256::from_be_slice(&out[140..172]), U256::ZERO);
+    }
+
     #[test]
     fn redact_url_strips_query() {
         assert_eq!(redact_url("http://x?a=b"), "http://x");
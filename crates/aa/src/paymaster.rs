@@ -3,12 +3,14 @@ use anyhow::{Context, Result};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone)]
 pub struct PaymasterService {
     pub url: String,
     pub context: Value,
+    /// Onchain paymaster contract, if known up front (otherwise learned from stub data).
+    pub address: Option<Address>,
 }
 
 /// ERC-7677 paymaster service `userOp` param shape for EntryPoint v0.7.
@@ -102,32 +104,84 @@ pub struct PaymasterDataResult {
     pub paymaster_data: Option<Bytes>,
 }
 
+/// Temporarily skip a paymaster after repeated failures, so one broken service doesn't add
+/// latency (or a hard failure) to every userop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaymasterFreezePolicy {
+    /// Consecutive failures (within `fail_window`) that freeze a paymaster.
+    pub fail_threshold: u32,
+    pub fail_window: Duration,
+    pub freeze: Duration,
+}
+
+impl Default for PaymasterFreezePolicy {
+    fn default() -> Self {
+        Self {
+            fail_threshold: 3,
+            fail_window: Duration::from_secs(60),
+            freeze: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Outcome of one paymaster sponsorship attempt, for callers that export metrics.
+#[derive(Debug, Clone)]
+pub struct PaymasterAttempt {
+    /// Paymaster URL with the query string (API keys) stripped.
+    pub paymaster: String,
+    pub ok: bool,
+    pub error: Option<String>,
+    /// True if this failure froze the paymaster.
+    pub froze: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct PaymasterPool {
     http: Client,
     selector: PaymasterSelector,
+    /// Paymaster contract last returned by each service's stub data.
+    observed: Vec<Option<Address>>,
     next_id: u64,
 }
 
+#[derive(Debug, Clone, Default)]
+struct PaymasterHealth {
+    fail_count: u32,
+    window_start: Option<Instant>,
+    frozen_until: Option<Instant>,
+}
+
 #[derive(Debug, Clone)]
 struct PaymasterSelector {
     services: Vec<PaymasterService>,
-    next_idx: usize,
+    health: Vec<PaymasterHealth>,
+    policy: PaymasterFreezePolicy,
 }
 
 impl PaymasterSelector {
-    fn new(services: Vec<PaymasterService>) -> Result<Self> {
+    fn new(services: Vec<PaymasterService>, policy: PaymasterFreezePolicy) -> Result<Self> {
         if services.is_empty() {
             anyhow::bail!("paymaster pool must be non-empty");
         }
         Ok(Self {
+            health: vec![PaymasterHealth::default(); services.len()],
             services,
-            next_idx: 0,
+            policy,
         })
     }
 
-    fn order(&self) -> impl Iterator<Item = usize> + '_ {
-        (0..self.services.len()).map(move |o| (self.next_idx + o) % self.services.len())
+    /// Configured order (primary first), skipping frozen paymasters.
+    fn order_at(&self, now: Instant) -> Vec<usize> {
+        (0..self.services.len())
+            .filter(|&idx| !self.is_frozen_at(idx, now))
+            .collect()
+    }
+
+    fn is_frozen_at(&self, idx: usize, now: Instant) -> bool {
+        self.health
+            .get(idx)
+            .and_then(|h| h.frozen_until)
+            .is_some_and(|until| now < until)
     }
 
     fn service(&self, idx: usize) -> Option<&PaymasterService> {
@@ -135,27 +189,54 @@ impl PaymasterSelector {
     }
 
     fn mark_success(&mut self, idx: usize) {
-        if !self.services.is_empty() {
-            self.next_idx = (idx + 1) % self.services.len();
+        if let Some(h) = self.health.get_mut(idx) {
+            *h = PaymasterHealth::default();
         }
     }
+
+    /// Records a failure; returns true if it froze the paymaster.
+    fn mark_failure_at(&mut self, idx: usize, now: Instant) -> bool {
+        let policy = self.policy;
+        let Some(h) = self.health.get_mut(idx) else {
+            return false;
+        };
+        let in_window = h
+            .window_start
+            .is_some_and(|start| now.saturating_duration_since(start) <= policy.fail_window);
+        if in_window {
+            h.fail_count = h.fail_count.saturating_add(1);
+        } else {
+            h.fail_count = 1;
+            h.window_start = Some(now);
+        }
+        if policy.fail_threshold == 0 || h.fail_count < policy.fail_threshold {
+            return false;
+        }
+        h.frozen_until = Some(now + policy.freeze);
+        h.fail_count = 0;
+        h.window_start = None;
+        true
+    }
 }
 
 impl PaymasterPool {
-    pub fn new(services: Vec<PaymasterService>) -> Result<Self> {
+    pub fn new(services: Vec<PaymasterService>, freeze: PaymasterFreezePolicy) -> Result<Self> {
         let http = Client::builder()
             .timeout(Duration::from_secs(10))
             .build()
             .context("build paymaster http client")?;
+        let observed = vec![None; services.len()];
         Ok(Self {
             http,
-            selector: PaymasterSelector::new(services)?,
+            selector: PaymasterSelector::new(services, freeze)?,
+            observed,
             next_id: 1,
         })
     }
 
-    pub fn order(&self) -> impl Iterator<Item = usize> + '_ {
-        self.selector.order()
+    /// Paymasters to try, in configured order, excluding frozen ones.
+    pub fn order(&self) -> Vec<usize> {
+        self.selector.order_at(Instant::now())
     }
 
     pub fn service(&self, idx: usize) -> Option<&PaymasterService> {
@@ -166,6 +247,24 @@ impl PaymasterPool {
         self.selector.mark_success(idx);
     }
 
+    /// Records a failed sponsorship; returns true if the paymaster is now frozen.
+    pub fn mark_failure(&mut self, idx: usize) -> bool {
+        self.selector.mark_failure_at(idx, Instant::now())
+    }
+
+    /// Paymaster contracts whose EntryPoint deposits back this pool's sponsorships:
+    /// configured addresses plus any learned from stub data, deduplicated.
+    pub fn paymaster_addresses(&self) -> Vec<Address> {
+        let configured = self.selector.services.iter().map(|svc| svc.address);
+        let mut out: Vec<Address> = Vec::new();
+        for addr in configured.chain(self.observed.iter().copied()).flatten() {
+            if addr != Address::ZERO && !out.contains(&addr) {
+                out.push(addr);
+            }
+        }
+        out
+    }
+
     pub async fn get_stub_data(
         &mut self,
         idx: usize,
@@ -178,13 +277,20 @@ impl PaymasterPool {
         };
         let url = svc.url.clone();
         let context = svc.context.clone();
-        self.jsonrpc(
-            &url,
-            "pm_getPaymasterStubData",
-            serde_json::json!([user_op, entry_point, hex_chain_id(chain_id), context]),
-        )
-        .await
-        .context("pm_getPaymasterStubData")
+        let stub: PaymasterStubDataResult = self
+            .jsonrpc(
+                &url,
+                "pm_getPaymasterStubData",
+                serde_json::json!([user_op, entry_point, hex_chain_id(chain_id), context]),
+            )
+            .await
+            .context("pm_getPaymasterStubData")?;
+        if let Some(addr) = stub.paymaster.filter(|a| *a != Address::ZERO)
+            && let Some(slot) = self.observed.get_mut(idx)
+        {
+            *slot = Some(addr);
+        }
+        Ok(stub)
     }
 
     pub async fn get_data(
@@ -287,29 +393,87 @@ fn hex_chain_id(chain_id: u64) -> String {
 mod tests {
     use super::*;
 
-    #[test]
-    fn paymaster_pool_order_rotates_on_success() {
-        let services = vec![
-            PaymasterService {
-                url: "http://a".to_string(),
-                context: serde_json::json!({}),
-            },
-            PaymasterService {
-                url: "http://b".to_string(),
+    fn services(n: usize) -> Vec<PaymasterService> {
+        (0..n)
+            .map(|i| PaymasterService {
+                url: format!("http://pm{i}"),
                 context: serde_json::json!({}),
-            },
-            PaymasterService {
-                url: "http://c".to_string(),
-                context: serde_json::json!({}),
-            },
-        ];
-        let mut sel = PaymasterSelector::new(services).unwrap();
+                address: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn paymaster_addresses_merge_configured_and_observed() {
+        let mut svcs = services(3);
+        svcs[0].address = Some(Address::repeat_byte(0x11));
+        svcs[2].address = Some(Address::repeat_byte(0x22));
+        let mut pool = PaymasterPool::new(svcs, PaymasterFreezePolicy::default()).unwrap();
+        assert_eq!(
+            pool.paymaster_addresses(),
+            vec![Address::repeat_byte(0x11), Address::repeat_byte(0x22)]
+        );
+
+        // Learned addresses are appended; duplicates of configured ones are dropped.
+        pool.observed[0] = Some(Address::repeat_byte(0x11));
+        pool.observed[1] = Some(Address::repeat_byte(0x33));
+        assert_eq!(
+            pool.paymaster_addresses(),
+            vec![
+                Address::repeat_byte(0x11),
+                Address::repeat_byte(0x22),
+                Address::repeat_byte(0x33)
+            ]
+        );
+    }
+
+    #[test]
+    fn paymaster_pool_order_is_config_order() {
+        let mut sel =
+            PaymasterSelector::new(services(3), PaymasterFreezePolicy::default()).unwrap();
+        let now = Instant::now();
 
-        let order = sel.order().collect::<Vec<_>>();
-        assert_eq!(order, vec![0, 1, 2]);
+        assert_eq!(sel.order_at(now), vec![0, 1, 2]);
 
+        // Success on a fallback doesn't demote the primary.
         sel.mark_success(1);
-        let order = sel.order().collect::<Vec<_>>();
-        assert_eq!(order, vec![2, 0, 1]);
+        assert_eq!(sel.order_at(now), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn paymaster_freezes_after_repeated_failures() {
+        let policy = PaymasterFreezePolicy {
+            fail_threshold: 2,
+            fail_window: Duration::from_secs(60),
+            freeze: Duration::from_secs(300),
+        };
+        let mut sel = PaymasterSelector::new(services(2), policy).unwrap();
+        let t0 = Instant::now();
+
+        assert!(!sel.mark_failure_at(0, t0));
+        assert_eq!(sel.order_at(t0), vec![0, 1]);
+        assert!(sel.mark_failure_at(0, t0 + Duration::from_secs(1)));
+        assert_eq!(sel.order_at(t0 + Duration::from_secs(2)), vec![1]);
+
+        // Thaws after the freeze elapses.
+        assert_eq!(sel.order_at(t0 + Duration::from_secs(302)), vec![0, 1]);
+    }
+
+    #[test]
+    fn paymaster_failures_outside_window_and_success_reset() {
+        let policy = PaymasterFreezePolicy {
+            fail_threshold: 2,
+            fail_window: Duration::from_secs(60),
+            freeze: Duration::from_secs(300),
+        };
+        let mut sel = PaymasterSelector::new(services(1), policy).unwrap();
+        let t0 = Instant::now();
+
+        assert!(!sel.mark_failure_at(0, t0));
+        assert!(!sel.mark_failure_at(0, t0 + Duration::from_secs(61)));
+
+        sel.mark_success(0);
+        assert!(!sel.mark_failure_at(0, t0 + Duration::from_secs(62)));
+        assert_eq!(sel.order_at(t0 + Duration::from_secs(62)), vec![0]);
     }
 }
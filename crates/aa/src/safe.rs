@@ -1,12 +1,12 @@
 use crate::contracts::{ISafe, ISafeModuleSetup, ISafeProxyFactory};
-use alloy::network::EthereumWallet;
+use crate::signer::{HubSigner, HubSignerWallet};
 use alloy::primitives::{Address, B256, Bytes, U256, keccak256};
 use alloy::providers::{DynProvider, Provider, ProviderBuilder};
 use alloy::rpc::client::{BuiltInConnectionString, RpcClient};
 use alloy::rpc::types::eth::transaction::{TransactionInput, TransactionRequest};
-use alloy::signers::local::PrivateKeySigner;
 use alloy::sol_types::SolCall;
 use anyhow::{Context, Result};
+use std::sync::Arc;
 use std::time::Duration;
 
 #[derive(Debug, Clone)]
@@ -42,10 +42,8 @@ async fn dyn_provider_for_rpc(rpc_url: &str) -> Result<DynProvider> {
     Ok(DynProvider::new(provider))
 }
 
-async fn wallet_provider_for_rpc(rpc_url: &str, private_key: [u8; 32]) -> Result<DynProvider> {
-    let signer =
-        PrivateKeySigner::from_bytes(&private_key.into()).context("invalid private key")?;
-    let wallet = EthereumWallet::from(signer);
+async fn wallet_provider_for_rpc(rpc_url: &str, signer: Arc<dyn HubSigner>) -> Result<DynProvider> {
+    let wallet = HubSignerWallet(signer);
 
     let transport = BuiltInConnectionString::connect(rpc_url)
         .await
@@ -158,16 +156,13 @@ pub fn predict_safe_proxy_address_create2(
 pub async fn ensure_safe_deployed(
     rpc_url: &str,
     chain_id: u64,
-    owner_private_key: [u8; 32],
+    owner_signer: Arc<dyn HubSigner>,
     safe_4337: &Safe4337Config,
     deploy: &SafeDeterministicDeploymentConfig,
 ) -> Result<Address> {
+    let owner = owner_signer.address();
     let read = dyn_provider_for_rpc(rpc_url).await?;
-    let wallet = wallet_provider_for_rpc(rpc_url, owner_private_key).await?;
-
-    let signer =
-        PrivateKeySigner::from_bytes(&owner_private_key.into()).context("invalid private key")?;
-    let owner = signer.address();
+    let wallet = wallet_provider_for_rpc(rpc_url, owner_signer).await?;
 
     let initializer = build_safe_4337_initializer(owner, safe_4337, deploy);
     let init_code_hash =
@@ -1,9 +1,12 @@
-use crate::bundler_pool::BundlerPool;
-use crate::contracts::{IEntryPointDeposits, IEntryPointNonces, Safe4337Module};
-use crate::packing::{add_gas_buffer, hex_bytes0x};
-use crate::paymaster::{PaymasterPool, PaymasterService};
+use crate::bundler_pool::{BundlerAttempt, BundlerHealthHandle, BundlerPool};
+use crate::contracts::{IEntryPointDeposits, IEntryPointNonces, IMultiSend, Safe4337Module};
+use crate::packing::{add_gas_buffer, encode_multisend_calls, hex_bytes0x, redact_url};
+use crate::paymaster::{
+    PaymasterAttempt, PaymasterFreezePolicy, PaymasterPool, PaymasterService, PaymasterUserOp,
+};
 use crate::safe::{Safe4337Config, SafeDeterministicDeploymentConfig, ensure_safe_deployed};
-use crate::signing::sign_userop_with_key;
+use crate::signer::HubSigner;
+use crate::signing::sign_userop_with_signer;
 use alloy::sol_types::SolCall;
 use alloy::{
     primitives::{Address, Bytes, U256},
@@ -11,7 +14,7 @@ use alloy::{
     rpc::client::{BuiltInConnectionString, RpcClient},
 };
 use anyhow::{Context, Result};
-use k256::ecdsa::SigningKey;
+use std::sync::Arc;
 
 use alloy::rpc::types::eth::erc4337::PackedUserOperation;
 
@@ -24,12 +27,16 @@ const MIN_PRIORITY_FEE_WEI: u128 = 1_000_000_000; // 1 gwei
 pub enum PaymasterFinalizationMode {
     SkipIfStubFinal,
     AlwaysFetchFinal,
+    /// Like `AlwaysFetchFinal`, but if no paymaster sponsors the userop, submit it without
+    /// `paymasterAndData` so the Safe pays from its EntryPoint deposit / ETH balance.
+    SelfFundedFallback,
 }
 
 #[derive(Debug, Clone)]
 pub struct Safe4337UserOpSenderOptions {
     pub check_bundler_entrypoints: bool,
     pub paymaster_finalization: PaymasterFinalizationMode,
+    pub paymaster_freeze: PaymasterFreezePolicy,
 }
 
 impl Default for Safe4337UserOpSenderOptions {
@@ -37,6 +44,7 @@ impl Default for Safe4337UserOpSenderOptions {
         Self {
             check_bundler_entrypoints: false,
             paymaster_finalization: PaymasterFinalizationMode::AlwaysFetchFinal,
+            paymaster_freeze: PaymasterFreezePolicy::default(),
         }
     }
 }
@@ -50,7 +58,8 @@ pub struct Safe4337UserOpSenderConfig {
     pub safe_4337_module: Address,
     pub safe_deployment: Option<SafeDeterministicDeploymentConfig>,
     pub bundler_urls: Vec<String>,
-    pub owner_private_key: [u8; 32],
+    /// Safe owner; signs every userop (and the deployment tx when the Safe is created).
+    pub owner: Arc<dyn HubSigner>,
     pub paymasters: Vec<PaymasterService>,
     pub options: Safe4337UserOpSenderOptions,
 }
@@ -59,10 +68,10 @@ pub struct Safe4337UserOpSender {
     cfg: Safe4337UserOpSenderConfig,
     provider: DynProvider,
     chain_id: u64,
-    owner_key: SigningKey,
     safe: Address,
     bundlers: BundlerPool,
     paymasters: Option<PaymasterPool>,
+    paymaster_attempts: Vec<PaymasterAttempt>,
     cached_nonce: Option<U256>,
 }
 
@@ -70,6 +79,8 @@ pub struct Safe4337UserOpSender {
 pub struct Safe4337UserOpSubmission {
     pub userop_hash: String,
     pub nonce: U256,
+    /// Bundler that accepted the op; the first place to look for its receipt.
+    pub bundler_url: String,
 }
 
 impl Safe4337UserOpSender {
@@ -86,9 +97,6 @@ impl Safe4337UserOpSender {
             None => provider.get_chain_id().await.context("eth_chainId")?,
         };
 
-        let owner_key =
-            SigningKey::from_slice(&cfg.owner_private_key).context("invalid owner private key")?;
-
         let safe = match cfg.safe {
             Some(addr) if addr != Address::ZERO => addr,
             _ => {
@@ -103,7 +111,7 @@ impl Safe4337UserOpSender {
                 ensure_safe_deployed(
                     &cfg.rpc_url,
                     chain_id,
-                    cfg.owner_private_key,
+                    cfg.owner.clone(),
                     &safe_4337,
                     &deploy,
                 )
@@ -133,7 +141,10 @@ impl Safe4337UserOpSender {
         let paymasters = if cfg.paymasters.is_empty() {
             None
         } else {
-            Some(PaymasterPool::new(cfg.paymasters.clone())?)
+            Some(PaymasterPool::new(
+                cfg.paymasters.clone(),
+                cfg.options.paymaster_freeze,
+            )?)
         };
 
         let cfg = Safe4337UserOpSenderConfig {
@@ -145,10 +156,10 @@ impl Safe4337UserOpSender {
             cfg,
             provider,
             chain_id,
-            owner_key,
             safe,
             bundlers,
             paymasters,
+            paymaster_attempts: Vec::new(),
             cached_nonce: None,
         })
     }
@@ -187,18 +198,100 @@ impl Safe4337UserOpSender {
         self.safe
     }
 
+    /// Known paymaster contracts (configured or learned from sponsorships).
+    pub fn paymaster_addresses(&self) -> Vec<Address> {
+        self.paymasters
+            .as_ref()
+            .map(PaymasterPool::paymaster_addresses)
+            .unwrap_or_default()
+    }
+
+    /// EntryPoint deposit held for `account` (e.g. a paymaster's sponsorship balance).
+    pub async fn entrypoint_deposit(&self, account: Address) -> Result<U256> {
+        self.entrypoint_deposits()
+            .balanceOf(account)
+            .call()
+            .await
+            .context("EntryPoint.balanceOf")
+    }
+
     pub async fn build_call_userop(
         &mut self,
         to: Address,
         data: Vec<u8>,
+    ) -> Result<PackedUserOperation> {
+        self.build_userop(to, data, 0).await
+    }
+
+    /// Like [`Self::build_call_userop`], but runs every `(to, data)` call in one userop by
+    /// delegatecalling `multisend` (a Safe `MultiSendCallOnly` deployment). Either all calls
+    /// succeed or the whole userop reverts.
+    pub async fn build_multisend_userop(
+        &mut self,
+        multisend: Address,
+        calls: &[(Address, Vec<u8>)],
+    ) -> Result<PackedUserOperation> {
+        anyhow::ensure!(
+            !calls.is_empty(),
+            "multisend userop needs at least one call"
+        );
+        let data = IMultiSend::multiSendCall {
+            transactions: encode_multisend_calls(calls).into(),
+        }
+        .abi_encode();
+        self.build_userop(multisend, data, 1).await
+    }
+
+    async fn build_userop(
+        &mut self,
+        to: Address,
+        data: Vec<u8>,
+        operation: u8,
     ) -> Result<PackedUserOperation> {
         let base_userop = self
-            .build_call_userop_unestimated(to, data)
+            .build_userop_unestimated(to, data, operation)
             .await
             .context("build_call_userop_unestimated")?;
+
+        if self.paymasters.is_none() {
+            return self.prepare_self_paid(base_userop).await;
+        }
+
+        let sponsor_err = match self.prepare_sponsored(base_userop.clone()).await {
+            Ok(userop) => return Ok(userop),
+            Err(err) => err,
+        };
+        if self.cfg.options.paymaster_finalization != PaymasterFinalizationMode::SelfFundedFallback
+        {
+            return Err(sponsor_err);
+        }
+        // Every paymaster failed (or is frozen): self-pay if the Safe can, otherwise surface both.
+        if let Err(err) = self.preflight_self_paid().await {
+            return Err(sponsor_err.context(format!("self-paid fallback unavailable: {err:#}")));
+        }
+        tracing::warn!(
+            safe = %self.safe,
+            err = %format!("{sponsor_err:#}"),
+            "no paymaster sponsored the userop; falling back to self-paid"
+        );
         self.prepare_self_paid(base_userop).await
     }
 
+    /// Paymaster attempts made since the last call, in order. Drains the buffer.
+    pub fn take_paymaster_attempts(&mut self) -> Vec<PaymasterAttempt> {
+        std::mem::take(&mut self.paymaster_attempts)
+    }
+
+    /// Bundler JSON-RPC calls made since the last call, in order. Drains the buffer.
+    pub fn take_bundler_attempts(&mut self) -> Vec<BundlerAttempt> {
+        self.bundlers.take_attempts()
+    }
+
+    /// Health of the configured bundlers, shared with this sender's failover.
+    pub fn bundler_health(&self) -> BundlerHealthHandle {
+        self.bundlers.health()
+    }
+
     /// Build a self-paid Safe4337 PackedUserOperation for `to(data)` without calling the bundler.
     ///
     /// This is primarily useful for debugging (e.g. reproducing simulation failures) where we want
@@ -207,6 +300,16 @@ impl Safe4337UserOpSender {
         &mut self,
         to: Address,
         data: Vec<u8>,
+    ) -> Result<PackedUserOperation> {
+        self.build_userop_unestimated(to, data, 0).await
+    }
+
+    /// `operation` is the Safe operation: 0 = call, 1 = delegatecall.
+    async fn build_userop_unestimated(
+        &mut self,
+        to: Address,
+        data: Vec<u8>,
+        operation: u8,
     ) -> Result<PackedUserOperation> {
         let nonce = self.current_nonce().await?;
 
@@ -246,7 +349,7 @@ impl Safe4337UserOpSender {
             to,
             value: U256::ZERO,
             data: data.into(),
-            operation: 0u8,
+            operation,
         }
         .abi_encode();
 
@@ -271,18 +374,11 @@ impl Safe4337UserOpSender {
             signature: Bytes::new(),
         };
 
-        if self.paymasters.is_some() {
-            tracing::warn!(
-                safe = %self.safe,
-                "paymasters configured but currently ignored (self-paid userops only)"
-            );
-        }
-
         if self.paymasters.is_none() {
             self.preflight_self_paid().await?;
         }
 
-        userop.signature = self.sign_userop(&userop)?.into();
+        userop.signature = self.sign_userop(&userop).await?.into();
         Ok(userop)
     }
 
@@ -290,7 +386,7 @@ impl Safe4337UserOpSender {
         &mut self,
         userop: &PackedUserOperation,
     ) -> Result<Safe4337UserOpSubmission> {
-        let resp = match self
+        let (resp, bundler_idx) = match self
             .bundlers
             .send_user_operation(userop, self.cfg.entrypoint)
             .await
@@ -342,6 +438,7 @@ impl Safe4337UserOpSender {
         Ok(Safe4337UserOpSubmission {
             userop_hash: hex_bytes0x(&resp.user_op_hash),
             nonce: userop.nonce,
+            bundler_url: self.bundlers.url(bundler_idx).to_string(),
         })
     }
 
@@ -397,6 +494,146 @@ impl Safe4337UserOpSender {
         Ok(())
     }
 
+    /// Tries each non-frozen paymaster in configured order; the first one that sponsors wins.
+    async fn prepare_sponsored(
+        &mut self,
+        userop: PackedUserOperation,
+    ) -> Result<PackedUserOperation> {
+        let order = match &self.paymasters {
+            Some(pool) => pool.order(),
+            None => anyhow::bail!("no paymasters configured"),
+        };
+        if order.is_empty() {
+            anyhow::bail!("all paymasters are frozen");
+        }
+
+        let mut errors = Vec::with_capacity(order.len());
+        for idx in order {
+            let url = self
+                .paymasters
+                .as_ref()
+                .and_then(|pool| pool.service(idx))
+                .map(|svc| redact_url(&svc.url))
+                .unwrap_or_default();
+
+            match self.sponsor_with(idx, userop.clone()).await {
+                Ok(sponsored) => {
+                    if let Some(pool) = self.paymasters.as_mut() {
+                        pool.mark_success(idx);
+                    }
+                    self.paymaster_attempts.push(PaymasterAttempt {
+                        paymaster: url,
+                        ok: true,
+                        error: None,
+                        froze: false,
+                    });
+                    return Ok(sponsored);
+                }
+                Err(err) => {
+                    let err = format!("{err:#}");
+                    let froze = self
+                        .paymasters
+                        .as_mut()
+                        .is_some_and(|pool| pool.mark_failure(idx));
+                    tracing::warn!(
+                        safe = %self.safe,
+                        paymaster = %url,
+                        froze,
+                        err = %err,
+                        "paymaster sponsorship failed; trying next"
+                    );
+                    errors.push(format!("{url}: {err}"));
+                    self.paymaster_attempts.push(PaymasterAttempt {
+                        paymaster: url,
+                        ok: false,
+                        error: Some(err),
+                        froze,
+                    });
+                }
+            }
+        }
+        anyhow::bail!("all paymasters failed: {}", errors.join("; "))
+    }
+
+    /// ERC-7677 flow against one paymaster: stub data -> bundler gas estimate -> final data.
+    async fn sponsor_with(
+        &mut self,
+        idx: usize,
+        mut userop: PackedUserOperation,
+    ) -> Result<PackedUserOperation> {
+        let entrypoint = self.cfg.entrypoint;
+        let chain_id = self.chain_id;
+        let pool = self
+            .paymasters
+            .as_mut()
+            .context("no paymasters configured")?;
+
+        let stub = pool
+            .get_stub_data(idx, &paymaster_user_op(&userop), entrypoint, chain_id)
+            .await?;
+        let paymaster = stub
+            .paymaster
+            .filter(|a| *a != Address::ZERO)
+            .context("stub data missing paymaster")?;
+        userop.paymaster = Some(paymaster);
+        userop.paymaster_data = Some(stub.paymaster_data.unwrap_or_default());
+        userop.paymaster_verification_gas_limit = stub.paymaster_verification_gas_limit;
+        userop.paymaster_post_op_gas_limit = stub.paymaster_post_op_gas_limit;
+        userop.signature = self.sign_userop(&userop).await?.into();
+
+        let estimate = self
+            .bundlers
+            .estimate_user_operation_gas(&userop, entrypoint)
+            .await
+            .context("eth_estimateUserOperationGas (sponsored)")?;
+        userop.call_gas_limit = add_gas_buffer(estimate.call_gas_limit, GAS_BUFFER_PCT)?;
+        userop.verification_gas_limit =
+            add_gas_buffer(estimate.verification_gas_limit, GAS_BUFFER_PCT)?;
+        userop.pre_verification_gas =
+            add_gas_buffer(estimate.pre_verification_gas, GAS_BUFFER_PCT)?;
+        if !estimate.paymaster_verification_gas_limit.is_zero() {
+            userop.paymaster_verification_gas_limit = Some(add_gas_buffer(
+                estimate.paymaster_verification_gas_limit,
+                GAS_BUFFER_PCT,
+            )?);
+        }
+        if !estimate.paymaster_post_op_gas_limit.is_zero() {
+            userop.paymaster_post_op_gas_limit = Some(add_gas_buffer(
+                estimate.paymaster_post_op_gas_limit,
+                GAS_BUFFER_PCT,
+            )?);
+        }
+
+        let stub_is_final = stub.is_final == Some(true);
+        if !(stub_is_final
+            && self.cfg.options.paymaster_finalization
+                == PaymasterFinalizationMode::SkipIfStubFinal)
+        {
+            let pool = self
+                .paymasters
+                .as_mut()
+                .context("no paymasters configured")?;
+            let data = pool
+                .get_data(idx, &paymaster_user_op(&userop), entrypoint, chain_id)
+                .await?;
+            let paymaster_data = data
+                .paymaster_data
+                .filter(|d| !d.is_empty())
+                .context("paymaster data missing paymasterData")?;
+            if let Some(addr) = data.paymaster
+                && addr != paymaster
+            {
+                anyhow::bail!(
+                    "paymaster data returned a different paymaster ({addr:#x}) than stub ({paymaster:#x})"
+                );
+            }
+            userop.paymaster_data = Some(paymaster_data);
+        }
+
+        userop.signature = self.sign_userop(&userop).await?.into();
+        Ok(userop)
+    }
+
     async fn prepare_self_paid(
         &mut self,
         mut userop: PackedUserOperation,
@@ -407,7 +644,7 @@ impl Safe4337UserOpSender {
             self.preflight_self_paid().await?;
         }
 
-        userop.signature = self.sign_userop(&userop)?.into();
+        userop.signature = self.sign_userop(&userop).await?.into();
 
         match self
             .bundlers
@@ -421,7 +658,7 @@ impl Safe4337UserOpSender {
                 userop.pre_verification_gas =
                     add_gas_buffer(estimate.pre_verification_gas, GAS_BUFFER_PCT)?;
 
-                userop.signature = self.sign_userop(&userop)?.into();
+                userop.signature = self.sign_userop(&userop).await?.into();
                 Ok(userop)
             }
             Err(err) => {
@@ -439,13 +676,33 @@ impl Safe4337UserOpSender {
         }
     }
 
-    fn sign_userop(&self, userop: &PackedUserOperation) -> Result<Vec<u8>> {
-        sign_userop_with_key(
-            &self.owner_key,
+    async fn sign_userop(&self, userop: &PackedUserOperation) -> Result<Vec<u8>> {
+        sign_userop_with_signer(
+            self.cfg.owner.as_ref(),
             self.chain_id,
             self.cfg.safe_4337_module,
             self.cfg.entrypoint,
             userop,
         )
+        .await
+    }
+}
+
+fn paymaster_user_op(userop: &PackedUserOperation) -> PaymasterUserOp {
+    PaymasterUserOp {
+        sender: userop.sender,
+        nonce: userop.nonce,
+        call_data: userop.call_data.clone(),
+        call_gas_limit: userop.call_gas_limit,
+        verification_gas_limit: userop.verification_gas_limit,
+        pre_verification_gas: userop.pre_verification_gas,
+        max_fee_per_gas: userop.max_fee_per_gas,
+        max_priority_fee_per_gas: userop.max_priority_fee_per_gas,
+        factory: userop.factory,
+        factory_data: userop.factory_data.clone(),
+        paymaster: userop.paymaster,
+        paymaster_verification_gas_limit: userop.paymaster_verification_gas_limit,
+        paymaster_post_op_gas_limit: userop.paymaster_post_op_gas_limit,
+        paymaster_data: userop.paymaster_data.clone(),
     }
 }
@@ -0,0 +1,202 @@
+use alloy::consensus::{SignableTransaction, TxEnvelope, TypedTransaction};
+use alloy::network::{Ethereum, NetworkWallet};
+use alloy::primitives::{Address, B256, Signature};
+use alloy::signers::{SignerSync, local::PrivateKeySigner};
+use anyhow::{Context, Result};
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+pub type SignFuture<'a> = Pin<Box<dyn Future<Output = Result<Signature>> + Send + 'a>>;
+
+/// Signs 32-byte prehashes (tx signing hashes, SafeOp EIP-712 digests) for one hub account.
+///
+/// Used for both the EOA that sends `claimIntent` / `proveIntentFill` and the Safe owner that
+/// signs Safe4337 userops.
+pub trait HubSigner: std::fmt::Debug + Send + Sync {
+    fn address(&self) -> Address;
+
+    fn sign_hash<'a>(&'a self, hash: &'a B256) -> SignFuture<'a>;
+}
+
+/// In-process secp256k1 key.
+pub struct LocalHubSigner {
+    signer: PrivateKeySigner,
+}
+
+impl LocalHubSigner {
+    pub fn new(private_key: [u8; 32]) -> Result<Self> {
+        let signer =
+            PrivateKeySigner::from_bytes(&private_key.into()).context("invalid private key")?;
+        Ok(Self { signer })
+    }
+
+    pub(crate) fn sign(&self, hash: &B256) -> Result<Signature> {
+        self.signer.sign_hash_sync(hash).context("sign hash")
+    }
+}
+
+impl std::fmt::Debug for LocalHubSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LocalHubSigner({})", self.signer.address())
+    }
+}
+
+impl HubSigner for LocalHubSigner {
+    fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    fn sign_hash<'a>(&'a self, hash: &'a B256) -> SignFuture<'a> {
+        Box::pin(async move { self.sign(hash) })
+    }
+}
+
+/// Delegates signing to an external service (KMS / HSM front), so the key never enters this
+/// process.
+///
+/// Protocol: `POST url` with `{"address": "0x<20 bytes>", "hash": "0x<32 bytes>"}`, answered
+/// with `{"signature": "0x<65 bytes r||s||v>"}`. `v` may be `0..=1` or `27..=28`. Signatures are
+/// checked to recover to `address` before use. When an auth token is configured every request
+/// carries it as `Authorization: Bearer <token>`.
+pub struct RemoteHubSigner {
+    http: reqwest::Client,
+    url: String,
+    auth_token: Option<String>,
+    address: Address,
+}
+
+#[derive(serde::Serialize)]
+struct RemoteSignRequest {
+    address: String,
+    hash: String,
+}
+
+#[derive(serde::Deserialize)]
+struct RemoteSignResponse {
+    signature: String,
+}
+
+impl RemoteHubSigner {
+    pub fn new(
+        url: String,
+        timeout: Duration,
+        auth_token: Option<String>,
+        address: Address,
+    ) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("build remote signer http client")?;
+        Ok(Self {
+            http,
+            url,
+            auth_token,
+            address,
+        })
+    }
+
+    async fn sign(&self, hash: &B256) -> Result<Signature> {
+        let mut req = self.http.post(&self.url).json(&RemoteSignRequest {
+            address: self.address.to_string(),
+            hash: hash.to_string(),
+        });
+        if let Some(token) = &self.auth_token {
+            req = req.bearer_auth(token);
+        }
+        let resp = req.send().await.context("POST remote signer")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("remote signer rejected request: {status}: {body}");
+        }
+        let body: RemoteSignResponse =
+            resp.json().await.context("decode remote signer response")?;
+        let raw = hex::decode(body.signature.trim_start_matches("0x"))
+            .context("decode remote signature hex")?;
+        if raw.len() != 65 {
+            anyhow::bail!("remote signature must be 65 bytes, got {}", raw.len());
+        }
+        let sig = Signature::from_raw(&raw).context("parse remote signature")?;
+        let recovered = sig
+            .recover_address_from_prehash(hash)
+            .context("recover remote signature")?;
+        if recovered != self.address {
+            anyhow::bail!(
+                "remote signature recovers to {recovered}, expected {}",
+                self.address
+            );
+        }
+        Ok(sig)
+    }
+}
+
+impl std::fmt::Debug for RemoteHubSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RemoteHubSigner({})", self.address)
+    }
+}
+
+impl HubSigner for RemoteHubSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn sign_hash<'a>(&'a self, hash: &'a B256) -> SignFuture<'a> {
+        Box::pin(self.sign(hash))
+    }
+}
+
+/// Adapts a [`HubSigner`] to alloy's provider wallet filler, so EOA transactions are signed
+/// through it.
+#[derive(Debug, Clone)]
+pub struct HubSignerWallet(pub Arc<dyn HubSigner>);
+
+impl NetworkWallet<Ethereum> for HubSignerWallet {
+    fn default_signer_address(&self) -> Address {
+        self.0.address()
+    }
+
+    fn has_signer_for(&self, address: &Address) -> bool {
+        *address == self.0.address()
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = Address> {
+        std::iter::once(self.0.address())
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        sender: Address,
+        tx: TypedTransaction,
+    ) -> alloy::signers::Result<TxEnvelope> {
+        if sender != self.0.address() {
+            return Err(alloy::signers::Error::other(format!(
+                "no signer for {sender} (have {})",
+                self.0.address()
+            )));
+        }
+        let hash = tx.signature_hash();
+        let sig = self
+            .0
+            .sign_hash(&hash)
+            .await
+            .map_err(|e| alloy::signers::Error::other(format!("{e:#}")))?;
+        Ok(tx.into_envelope(sig))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_signature_recovers_to_signer_address() {
+        let signer = LocalHubSigner::new([0x11u8; 32]).unwrap();
+        let hash = B256::repeat_byte(0x42);
+        let sig = signer.sign(&hash).unwrap();
+        assert_eq!(
+            sig.recover_address_from_prehash(&hash).unwrap(),
+            signer.address()
+        );
+        assert!(matches!(sig.as_bytes()[64], 27 | 28));
+    }
+}
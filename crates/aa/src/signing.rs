@@ -1,10 +1,10 @@
-use alloy::primitives::{Address, Bytes, FixedBytes, U256};
+use alloy::primitives::{Address, Bytes, FixedBytes, Signature, U256};
 use alloy::sol_types::{Eip712Domain, SolStruct};
 use anyhow::{Context, Result};
-use k256::ecdsa::SigningKey;
 
 use crate::contracts::SafeOp;
 use crate::packing::{ensure_u48, pack_init_code, pack_paymaster_and_data, u48_be_bytes};
+use crate::signer::HubSigner;
 use alloy::rpc::types::eth::erc4337::PackedUserOperation;
 
 pub(crate) fn safeop_digest(
@@ -61,26 +61,29 @@ pub(crate) fn safeop_digest(
     Ok(safeop.eip712_signing_hash(&domain))
 }
 
-pub(crate) fn sign_userop_with_key(
-    owner_key: &SigningKey,
+pub(crate) async fn sign_userop_with_signer(
+    owner: &dyn HubSigner,
     chain_id: u64,
     safe_4337_module: Address,
     entry_point: Address,
     op: &PackedUserOperation,
 ) -> Result<Vec<u8>> {
+    let digest = safeop_digest(chain_id, safe_4337_module, entry_point, op)?;
+    let sig = owner
+        .sign_hash(&digest)
+        .await
+        .context("sign SafeOp digest")?;
+    encode_userop_signature(&sig)
+}
+
+/// `validAfter || validUntil || r || s || v`, as the Safe4337 module expects.
+fn encode_userop_signature(sig: &Signature) -> Result<Vec<u8>> {
     let valid_after: u64 = 0;
     let valid_until: u64 = 0;
     ensure_u48(valid_after, "validAfter")?;
     ensure_u48(valid_until, "validUntil")?;
 
-    let digest = safeop_digest(chain_id, safe_4337_module, entry_point, op)?;
-
-    let (sig, recid) = owner_key
-        .sign_prehash_recoverable(digest.as_slice())
-        .context("sign SafeOp digest")?;
-
-    let mut sig65 = sig.to_bytes().to_vec();
-    sig65.push(recid.to_byte() + 27);
+    let sig65 = sig.as_bytes();
 
     let mut out = Vec::with_capacity(12 + sig65.len());
     out.extend_from_slice(&u48_be_bytes(valid_after));
@@ -92,13 +95,11 @@ pub(crate) fn sign_userop_with_key(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use k256::ecdsa::VerifyingKey;
-    use k256::ecdsa::signature::hazmat::PrehashVerifier;
+    use crate::signer::LocalHubSigner;
 
     #[test]
     fn sign_userop_prefix_and_verifies() {
-        let owner_key = SigningKey::from_slice(&[7u8; 32]).unwrap();
-        let verify_key = VerifyingKey::from(&owner_key);
+        let owner = LocalHubSigner::new([7u8; 32]).unwrap();
 
         let chain_id = 10u64;
         let module = Address::repeat_byte(0x11);
@@ -122,14 +123,15 @@ mod tests {
             signature: Bytes::new(),
         };
 
-        let sig = sign_userop_with_key(&owner_key, chain_id, module, entry, &op).unwrap();
+        let digest = safeop_digest(chain_id, module, entry, &op).unwrap();
+        let sig = encode_userop_signature(&owner.sign(&digest).unwrap()).unwrap();
         assert_eq!(sig.len(), 12 + 65);
         assert_eq!(&sig[0..12], &[0u8; 12]);
 
-        let digest = safeop_digest(chain_id, module, entry, &op).unwrap();
-        let sig64 = k256::ecdsa::Signature::from_slice(&sig[12..12 + 64]).unwrap();
-        verify_key
-            .verify_prehash(digest.as_slice(), &sig64)
-            .unwrap();
+        let parsed = Signature::from_raw(&sig[12..]).unwrap();
+        assert_eq!(
+            parsed.recover_address_from_prehash(&digest).unwrap(),
+            owner.address()
+        );
     }
 }
@@ -1,36 +1,79 @@
 use super::protocol::{
     Account, AccountResourceMessage, BlockExtention, BytesMessage, ChainParameters,
     CreateSmartContract, DelegateResourceContract, EmptyMessage, EstimateEnergyMessage,
-    FreezeBalanceV2Contract, NumberMessage, Return, Transaction, TransactionExtention,
+    FreezeBalanceV2Contract, NodeInfo, NumberMessage, Return, Transaction, TransactionExtention,
     TransactionInfo, TransferContract, TriggerSmartContract, UnDelegateResourceContract,
     wallet_client::WalletClient,
 };
 use anyhow::{Context, Result};
 use bytes::{Buf, BufMut, Bytes};
 use prost::Message;
+use std::future::Future;
 use std::marker::PhantomData;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tonic::{
-    Request, Status,
+    Code, Request, Response, Status,
     codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
     metadata::MetadataValue,
-    transport::Channel,
+    transport::{Channel, Endpoint},
 };
 
+/// Result of a single RPC attempt, as reported to [`GrpcOptions::observer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrpcCallOutcome {
+    Ok,
+    Error,
+    TimedOut,
+}
+
+impl GrpcCallOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Error => "err",
+            Self::TimedOut => "timeout",
+        }
+    }
+}
+
+/// Called after every RPC attempt with the gRPC method name, outcome and attempt duration.
+pub type GrpcCallObserver = Arc<dyn Fn(&'static str, GrpcCallOutcome, Duration) + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub struct GrpcOptions {
+    /// Per-attempt deadline, also used as the connect timeout. `None` waits indefinitely.
+    pub timeout: Option<Duration>,
+    /// Extra attempts after a timeout or an `UNAVAILABLE` / `DEADLINE_EXCEEDED` status.
+    pub max_retries: u32,
+    pub observer: Option<GrpcCallObserver>,
+}
+
 #[derive(Clone)]
 pub struct TronGrpc {
     api_key: Option<MetadataValue<tonic::metadata::Ascii>>,
     channel: Channel,
     wallet: WalletClient<Channel>,
+    opts: GrpcOptions,
 }
 
 impl TronGrpc {
     pub async fn connect(grpc_url: &str, api_key: Option<&str>) -> Result<Self> {
-        let channel = Channel::from_shared(grpc_url.to_string())
-            .context("invalid TRON_GRPC_URL")?
-            .connect()
-            .await
-            .context("connect TRON gRPC")?;
+        Self::connect_with_options(grpc_url, api_key, GrpcOptions::default()).await
+    }
+
+    pub async fn connect_with_options(
+        grpc_url: &str,
+        api_key: Option<&str>,
+        opts: GrpcOptions,
+    ) -> Result<Self> {
+        let mut endpoint =
+            Endpoint::from_shared(grpc_url.to_string()).context("invalid TRON_GRPC_URL")?;
+        if let Some(t) = opts.timeout {
+            endpoint = endpoint.connect_timeout(t);
+        }
+        let channel = endpoint.connect().await.context("connect TRON gRPC")?;
 
         let api_key = match api_key {
             Some(k) if !k.trim().is_empty() => {
@@ -43,6 +86,7 @@ impl TronGrpc {
             api_key,
             channel: channel.clone(),
             wallet: WalletClient::new(channel),
+            opts,
         })
     }
 
@@ -54,41 +98,91 @@ impl TronGrpc {
         req
     }
 
+    /// Runs one unary RPC under the configured timeout and retry policy.
+    async fn unary<M, T, F, Fut>(&self, method: &'static str, msg: M, call: F) -> Result<T>
+    where
+        M: Clone,
+        F: Fn(WalletClient<Channel>, Request<M>) -> Fut,
+        Fut: Future<Output = Result<Response<T>, Status>>,
+    {
+        let mut attempt = 0u32;
+        loop {
+            let started = Instant::now();
+            let fut = call(self.wallet.clone(), self.req(msg.clone()));
+            let res = match self.opts.timeout {
+                Some(t) => tokio::time::timeout(t, fut).await.ok(),
+                None => Some(fut.await),
+            };
+            let outcome = match &res {
+                Some(Ok(_)) => GrpcCallOutcome::Ok,
+                Some(Err(_)) => GrpcCallOutcome::Error,
+                None => GrpcCallOutcome::TimedOut,
+            };
+            if let Some(observer) = &self.opts.observer {
+                observer(method, outcome, started.elapsed());
+            }
+
+            match res {
+                Some(Ok(resp)) => return Ok(resp.into_inner()),
+                Some(Err(status)) => {
+                    let retryable =
+                        matches!(status.code(), Code::Unavailable | Code::DeadlineExceeded);
+                    if !retryable || attempt >= self.opts.max_retries {
+                        return Err(anyhow::Error::new(status).context(method));
+                    }
+                }
+                None if attempt >= self.opts.max_retries => {
+                    anyhow::bail!(
+                        "{method} timed out after {:?}",
+                        self.opts.timeout.unwrap_or_default()
+                    );
+                }
+                None => {}
+            }
+            attempt += 1;
+            tokio::time::sleep(Duration::from_millis(100u64 << attempt.min(4))).await;
+        }
+    }
+
     pub async fn get_now_block2(&mut self) -> Result<BlockExtention> {
-        let resp = self
-            .wallet
-            .get_now_block2(self.req(EmptyMessage {}))
-            .await
-            .context("GetNowBlock2")?;
-        Ok(resp.into_inner())
+        self.unary("GetNowBlock2", EmptyMessage {}, |mut w, r| async move {
+            w.get_now_block2(r).await
+        })
+        .await
     }
 
     pub async fn get_block_by_num2(&mut self, num: i64) -> Result<BlockExtention> {
-        let resp = self
-            .wallet
-            .get_block_by_num2(self.req(NumberMessage { num }))
-            .await
-            .context("GetBlockByNum2")?;
-        Ok(resp.into_inner())
+        self.unary(
+            "GetBlockByNum2",
+            NumberMessage { num },
+            |mut w, r| async move { w.get_block_by_num2(r).await },
+        )
+        .await
     }
 
     /// Fetches a block as raw protobuf bytes and extracts the canonical, nested `Transaction`
     /// bytes for each `TransactionExtention` without re-encoding (preserves unknown fields and
     /// wire layout relevant for `txTrieRoot`).
     pub async fn get_block_by_num2_raw_bytes(&mut self, num: i64) -> Result<Bytes> {
-        let mut grpc = tonic::client::Grpc::new(self.channel.clone());
-        grpc.ready().await.context("TRON gRPC not ready")?;
-
-        let path =
-            tonic::codegen::http::uri::PathAndQuery::from_static("/protocol.Wallet/GetBlockByNum2");
-        let codec = ProstRequestBytesResponseCodec::<NumberMessage>::default();
-
-        let resp = grpc
-            .unary(self.req(NumberMessage { num }), path, codec)
-            .await
-            .context("GetBlockByNum2 (raw)")?;
-
-        Ok(resp.into_inner())
+        let channel = self.channel.clone();
+        self.unary(
+            "GetBlockByNum2 (raw)",
+            NumberMessage { num },
+            move |_, req| {
+                let mut grpc = tonic::client::Grpc::new(channel.clone());
+                async move {
+                    grpc.ready()
+                        .await
+                        .map_err(|e| Status::unavailable(format!("TRON gRPC not ready: {e}")))?;
+                    let path = tonic::codegen::http::uri::PathAndQuery::from_static(
+                        "/protocol.Wallet/GetBlockByNum2",
+                    );
+                    let codec = ProstRequestBytesResponseCodec::<NumberMessage>::default();
+                    grpc.unary(req, path, codec).await
+                }
+            },
+        )
+        .await
     }
 
     /// Fetches a block as raw protobuf bytes and extracts the canonical, nested `Transaction`
@@ -115,50 +209,44 @@ impl TronGrpc {
     }
 
     pub async fn get_transaction_info_by_id(&mut self, txid: [u8; 32]) -> Result<TransactionInfo> {
-        let resp = self
-            .wallet
-            .get_transaction_info_by_id(self.req(BytesMessage {
+        self.unary(
+            "GetTransactionInfoById",
+            BytesMessage {
                 value: txid.to_vec(),
-            }))
-            .await
-            .context("GetTransactionInfoById")?;
-        Ok(resp.into_inner())
+            },
+            |mut w, r| async move { w.get_transaction_info_by_id(r).await },
+        )
+        .await
     }
 
     pub async fn trigger_contract(
         &mut self,
         msg: TriggerSmartContract,
     ) -> Result<TransactionExtention> {
-        let resp = self
-            .wallet
-            .trigger_contract(self.req(msg))
-            .await
-            .context("TriggerContract")?;
-        Ok(resp.into_inner())
+        self.unary("TriggerContract", msg, |mut w, r| async move {
+            w.trigger_contract(r).await
+        })
+        .await
     }
 
     pub async fn trigger_constant_contract(
         &mut self,
         msg: TriggerSmartContract,
     ) -> Result<TransactionExtention> {
-        let resp = self
-            .wallet
-            .trigger_constant_contract(self.req(msg))
-            .await
-            .context("TriggerConstantContract")?;
-        Ok(resp.into_inner())
+        self.unary("TriggerConstantContract", msg, |mut w, r| async move {
+            w.trigger_constant_contract(r).await
+        })
+        .await
     }
 
     pub async fn deploy_contract(
         &mut self,
         msg: CreateSmartContract,
     ) -> Result<TransactionExtention> {
-        let resp = self
-            .wallet
-            .deploy_contract(self.req(msg))
-            .await
-            .context("DeployContract")?;
-        Ok(resp.into_inner())
+        self.unary("DeployContract", msg, |mut w, r| async move {
+            w.deploy_contract(r).await
+        })
+        .await
     }
 
     /// Creates a native TRX transfer transaction skeleton.
@@ -170,12 +258,10 @@ impl TronGrpc {
         &mut self,
         msg: TransferContract,
     ) -> Result<Transaction> {
-        let resp = self
-            .wallet
-            .create_transaction(self.req(msg))
-            .await
-            .context("CreateTransaction")?;
-        Ok(resp.into_inner())
+        self.unary("CreateTransaction", msg, |mut w, r| async move {
+            w.create_transaction(r).await
+        })
+        .await
     }
 
     /// Creates a DelegateResource transaction skeleton.
@@ -185,12 +271,10 @@ impl TronGrpc {
         &mut self,
         msg: DelegateResourceContract,
     ) -> Result<TransactionExtention> {
-        let resp = self
-            .wallet
-            .delegate_resource(self.req(msg))
-            .await
-            .context("DelegateResource")?;
-        Ok(resp.into_inner())
+        self.unary("DelegateResource", msg, |mut w, r| async move {
+            w.delegate_resource(r).await
+        })
+        .await
     }
 
     /// Creates an UnDelegateResource transaction skeleton.
@@ -200,12 +284,10 @@ impl TronGrpc {
         &mut self,
         msg: UnDelegateResourceContract,
     ) -> Result<TransactionExtention> {
-        let resp = self
-            .wallet
-            .un_delegate_resource(self.req(msg))
-            .await
-            .context("UnDelegateResource")?;
-        Ok(resp.into_inner())
+        self.unary("UnDelegateResource", msg, |mut w, r| async move {
+            w.un_delegate_resource(r).await
+        })
+        .await
     }
 
     /// Creates a FreezeBalanceV2 transaction skeleton.
@@ -213,69 +295,77 @@ impl TronGrpc {
         &mut self,
         msg: FreezeBalanceV2Contract,
     ) -> Result<TransactionExtention> {
-        let resp = self
-            .wallet
-            .freeze_balance_v2(self.req(msg))
-            .await
-            .context("FreezeBalanceV2")?;
-        Ok(resp.into_inner())
+        self.unary("FreezeBalanceV2", msg, |mut w, r| async move {
+            w.freeze_balance_v2(r).await
+        })
+        .await
     }
 
     pub async fn broadcast_transaction(&mut self, tx: Transaction) -> Result<Return> {
-        let resp = self
-            .wallet
-            .broadcast_transaction(self.req(tx))
-            .await
-            .context("BroadcastTransaction")?;
-        Ok(resp.into_inner())
+        self.unary("BroadcastTransaction", tx, |mut w, r| async move {
+            w.broadcast_transaction(r).await
+        })
+        .await
     }
 
     pub async fn get_account(&mut self, address_prefixed: Vec<u8>) -> Result<Account> {
-        let resp = self
-            .wallet
-            .get_account(self.req(Account {
+        self.unary(
+            "GetAccount",
+            Account {
                 address: address_prefixed,
                 ..Default::default()
-            }))
-            .await
-            .context("GetAccount")?;
-        Ok(resp.into_inner())
+            },
+            |mut w, r| async move { w.get_account(r).await },
+        )
+        .await
     }
 
     pub async fn get_account_resource(
         &mut self,
         address_prefixed: Vec<u8>,
     ) -> Result<AccountResourceMessage> {
-        let resp = self
-            .wallet
-            .get_account_resource(self.req(Account {
+        self.unary(
+            "GetAccountResource",
+            Account {
                 address: address_prefixed,
                 ..Default::default()
-            }))
-            .await
-            .context("GetAccountResource")?;
-        Ok(resp.into_inner())
+            },
+            |mut w, r| async move { w.get_account_resource(r).await },
+        )
+        .await
+    }
+
+    pub async fn get_node_info(&mut self) -> Result<NodeInfo> {
+        self.unary("GetNodeInfo", EmptyMessage {}, |mut w, r| async move {
+            w.get_node_info(r).await
+        })
+        .await
+    }
+
+    /// Latest solidified (irreversible) block number as reported by the node.
+    pub async fn get_solidified_block_number(&mut self) -> Result<u64> {
+        let info = self.get_node_info().await?;
+        parse_node_info_block_num(&info.solidity_block)
+            .with_context(|| format!("parse NodeInfo.solidityBlock: {}", info.solidity_block))
     }
 
     pub async fn get_chain_parameters(&mut self) -> Result<ChainParameters> {
-        let resp = self
-            .wallet
-            .get_chain_parameters(self.req(EmptyMessage {}))
-            .await
-            .context("GetChainParameters")?;
-        Ok(resp.into_inner())
+        self.unary(
+            "GetChainParameters",
+            EmptyMessage {},
+            |mut w, r| async move { w.get_chain_parameters(r).await },
+        )
+        .await
     }
 
     pub async fn estimate_energy(
         &mut self,
         msg: TriggerSmartContract,
     ) -> Result<EstimateEnergyMessage> {
-        let resp = self
-            .wallet
-            .estimate_energy(self.req(msg))
-            .await
-            .context("EstimateEnergy")?;
-        Ok(resp.into_inner())
+        self.unary("EstimateEnergy", msg, |mut w, r| async move {
+            w.estimate_energy(r).await
+        })
+        .await
     }
 }
 
@@ -433,9 +523,20 @@ fn decode_varint(buf: &[u8]) -> Result<(u64, &[u8])> {
     anyhow::bail!("unexpected EOF decoding varint");
 }
 
+/// Parses the `Num:<n>,ID:<hash>` block descriptor used in `NodeInfo`.
+fn parse_node_info_block_num(s: &str) -> Result<u64> {
+    let num = s
+        .split(',')
+        .find_map(|part| part.trim().strip_prefix("Num:"))
+        .context("missing Num")?;
+    num.trim().parse::<u64>().context("invalid Num")
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{decode_varint, extract_block_extention_transaction_bytes};
+    use super::{
+        decode_varint, extract_block_extention_transaction_bytes, parse_node_info_block_num,
+    };
 
     fn encode_varint(mut value: u64) -> Vec<u8> {
         let mut out = Vec::new();
@@ -486,4 +587,12 @@ mod tests {
         assert_eq!(extracted.len(), 1);
         assert_eq!(extracted[0], tx_bytes);
     }
+
+    #[test]
+    fn parse_node_info_block_num_reads_num_field() {
+        let s = "Num:70412345,ID:000000000432f1b9c0ffee";
+        assert_eq!(parse_node_info_block_num(s).unwrap(), 70_412_345);
+        assert!(parse_node_info_block_num("").is_err());
+        assert!(parse_node_info_block_num("Num:abc,ID:00").is_err());
+    }
 }
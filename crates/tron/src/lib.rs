@@ -4,17 +4,19 @@ pub mod proof;
 pub mod rental;
 pub mod resources;
 pub mod sender;
+pub mod signer;
 pub mod wallet;
 
 pub use address::TronAddress;
-pub use grpc::TronGrpc;
+pub use grpc::{GrpcCallObserver, GrpcCallOutcome, GrpcOptions, TronGrpc};
 pub use proof::{TronTxProofBuilder, TronTxProofBundle};
 pub use rental::{
     JsonApiRentalProvider, JsonApiRentalProviderConfig, RenderedJsonApiRequest, RentalAttempt,
     RentalContext, RentalResourceKind,
 };
 pub use resources::{AccountResources, ChainFees, TxCostQuote};
-pub use sender::{FeePolicy, SignedTronTx};
+pub use sender::{FeePolicy, SignedTronTx, signed_tx_expiration_ms};
+pub use signer::{LocalTronSigner, RemoteTronSigner, SignFuture, TronSigner, recover_signer};
 pub use wallet::{BroadcastedTronTx, TronWallet};
 
 pub mod protocol {
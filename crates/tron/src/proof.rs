@@ -5,17 +5,37 @@ use anyhow::{Context, Result};
 use prost::Message;
 use serde::Serialize;
 use sha2::{Digest, Sha256};
+use std::time::Instant;
+use tracing::Instrument;
 
 pub struct TronTxProofBundle {
     pub blocks: [Vec<u8>; 20],
     pub encoded_tx: Vec<u8>,
     pub proof: Vec<FixedBytes<32>>,
     pub index: U256,
+    /// Time spent fetching blocks from the node (the tx block plus the 19 blocks after it).
+    pub block_fetch_ms: u64,
 }
 
 pub struct TronTxProofBuilder {
     /// Must be 19 for the hub's `bytes[20]` proof format.
     pub finality_blocks: u64,
+    /// Blocks the tx block must be buried under before proving, unless it is already solidified.
+    /// Anything at or below `finality_blocks` adds no extra wait.
+    pub min_confirmations: u64,
+    /// Refuse proofs whose serialized size (see [`TronTxProofBundle::serialized_len`]) exceeds
+    /// this many bytes.
+    pub max_proof_bytes: Option<usize>,
+}
+
+impl TronTxProofBundle {
+    /// Bytes the proof adds to `proveIntentFill` calldata (before ABI padding).
+    pub fn serialized_len(&self) -> usize {
+        self.blocks.iter().map(Vec::len).sum::<usize>()
+            + self.encoded_tx.len()
+            + 32 * self.proof.len()
+            + 32
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -49,7 +69,32 @@ struct TxTrieRootMismatchDump {
 
 impl TronTxProofBuilder {
     pub fn new(finality_blocks: u64) -> Self {
-        Self { finality_blocks }
+        Self {
+            finality_blocks,
+            min_confirmations: 0,
+            max_proof_bytes: None,
+        }
+    }
+
+    pub fn with_min_confirmations(mut self, min_confirmations: u64) -> Self {
+        self.min_confirmations = min_confirmations;
+        self
+    }
+
+    pub fn with_max_proof_bytes(mut self, max_proof_bytes: Option<usize>) -> Self {
+        self.max_proof_bytes = max_proof_bytes;
+        self
+    }
+
+    /// Guard errors are prefixed with `proof_too_large:` so callers can tell them from
+    /// not-yet-final errors worth retrying.
+    fn check_proof_bytes(&self, len: usize) -> Result<()> {
+        if let Some(max) = self.max_proof_bytes
+            && len > max
+        {
+            anyhow::bail!("proof_too_large: {len} bytes exceeds max {max}");
+        }
+        Ok(())
     }
 
     pub async fn build(&self, grpc: &mut TronGrpc, txid: [u8; 32]) -> Result<TronTxProofBundle> {
@@ -60,14 +105,19 @@ impl TronTxProofBuilder {
             );
         }
 
-        let tx_info = grpc
-            .get_transaction_info_by_id(txid)
-            .await
-            .context("get tx info")?;
+        let (tx_info, head) = async {
+            let tx_info = grpc
+                .get_transaction_info_by_id(txid)
+                .await
+                .context("get tx info")?;
+            let head = tron_head_block(grpc).await?;
+            anyhow::Ok((tx_info, head))
+        }
+        .instrument(tracing::info_span!("tron_proof.check_finality"))
+        .await?;
         let tron_block_number =
             u64::try_from(tx_info.block_number).context("Tron tx blockNumber out of range")?;
 
-        let head = tron_head_block(grpc).await?;
         if head < tron_block_number + self.finality_blocks {
             anyhow::bail!(
                 "tx not finalized: head={}, tx_block={}, need >= {}",
@@ -76,12 +126,35 @@ impl TronTxProofBuilder {
                 tron_block_number + self.finality_blocks
             );
         }
+        if head < tron_block_number + self.min_confirmations {
+            // Not deep enough yet; a solidified block can't be reorged out, so accept that too.
+            let solidified = grpc
+                .get_solidified_block_number()
+                .instrument(tracing::info_span!("tron_proof.check_solidified"))
+                .await
+                .context("get solidified block")?;
+            if solidified < tron_block_number {
+                anyhow::bail!(
+                    "tx not confirmed: head={}, solidified={}, tx_block={}, need >= {} or solidified",
+                    head,
+                    solidified,
+                    tron_block_number,
+                    tron_block_number + self.min_confirmations
+                );
+            }
+        }
 
         // Fetch the tx block (with tx list) first, preserving canonical tx bytes for txTrieRoot.
+        let started = Instant::now();
         let (tx_block, tx_bytes) = grpc
             .get_block_by_num2_raw_txs(i64::try_from(tron_block_number)?)
+            .instrument(tracing::info_span!(
+                "tron_proof.fetch_tx",
+                block = tron_block_number
+            ))
             .await
             .context("get tx block (raw tx bytes)")?;
+        let mut block_fetch = started.elapsed();
 
         let header_raw = tx_block
             .block_header
@@ -90,7 +163,10 @@ impl TronTxProofBuilder {
             .context("missing block_header.raw_data")?;
         let header_tx_trie_root = header_raw.tx_trie_root.clone();
 
-        let details = compute_proof_from_block_ext(&tx_block, &tx_bytes, tron_block_number, txid)
+        let details = tracing::info_span!("tron_proof.merkle", txs = tx_bytes.len())
+            .in_scope(|| {
+                compute_proof_from_block_ext(&tx_block, &tx_bytes, tron_block_number, txid)
+            })
             .context("tx proof")?;
 
         if details.computed_root.as_slice() != header_tx_trie_root.as_slice() {
@@ -100,25 +176,41 @@ impl TronTxProofBuilder {
                     .unwrap_or_else(|e| format!("{{\"error\":\"failed to serialize dump: {e}\"}}"))
             );
         }
+        // Headers are small; reject oversized txs before fetching the rest of the range.
+        self.check_proof_bytes(details.encoded_tx.len() + 32 * details.proof.len())?;
 
         // Fetch 19 blocks after, for the hub's stateful Tron reader.
         let mut blocks: [Vec<u8>; 20] = std::array::from_fn(|_| Vec::new());
         blocks[0] = encode_block_header(&tx_block.block_header)?;
-        for (i, block) in blocks.iter_mut().enumerate().skip(1) {
-            let num = tron_block_number + (i as u64);
-            let b = grpc
-                .get_block_by_num2(i64::try_from(num)?)
-                .await
-                .with_context(|| format!("get block {num}"))?;
-            *block = encode_block_header(&b.block_header)?;
+        let started = Instant::now();
+        async {
+            for (i, block) in blocks.iter_mut().enumerate().skip(1) {
+                let num = tron_block_number + (i as u64);
+                let b = grpc
+                    .get_block_by_num2(i64::try_from(num)?)
+                    .await
+                    .with_context(|| format!("get block {num}"))?;
+                *block = encode_block_header(&b.block_header)?;
+            }
+            anyhow::Ok(())
         }
-
-        Ok(TronTxProofBundle {
+        .instrument(tracing::info_span!(
+            "tron_proof.fetch_block_range",
+            from = tron_block_number + 1,
+            to = tron_block_number + self.finality_blocks
+        ))
+        .await?;
+        block_fetch += started.elapsed();
+
+        let bundle = TronTxProofBundle {
             blocks,
             encoded_tx: details.encoded_tx,
             proof: details.proof,
             index: details.index_bits,
-        })
+            block_fetch_ms: u64::try_from(block_fetch.as_millis()).unwrap_or(u64::MAX),
+        };
+        self.check_proof_bytes(bundle.serialized_len())?;
+        Ok(bundle)
     }
 }
 
@@ -577,6 +669,17 @@ mod tests {
         assert_eq!(decode_varint(&enc), 300);
     }
 
+    #[test]
+    fn proof_bytes_guard_rejects_only_over_max() {
+        let builder = TronTxProofBuilder::new(19);
+        assert!(builder.check_proof_bytes(usize::MAX).is_ok());
+
+        let builder = builder.with_max_proof_bytes(Some(100));
+        assert!(builder.check_proof_bytes(100).is_ok());
+        let err = builder.check_proof_bytes(101).unwrap_err().to_string();
+        assert!(err.starts_with("proof_too_large:"));
+    }
+
     #[test]
     fn encode_varint_fixed_errors_if_value_needs_more_bytes() {
         let err = encode_varint_fixed(300, 1).unwrap_err().to_string();
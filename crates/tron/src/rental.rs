@@ -51,12 +51,26 @@ pub struct JsonApiRentalProviderConfig {
     /// Optional quote endpoint for profitability gating / provider selection.
     #[serde(default)]
     pub quote: Option<JsonApiQuoteConfig>,
+
+    /// Relative share of rental orders routed to this provider (weighted round-robin).
+    /// `0` keeps the provider as a last-resort fallback only.
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+
+    /// Orders whose quoted cost exceeds this (USD) are not placed with this provider; applies on
+    /// top of the solver's global cap. Needs a `quote` endpoint to price the order.
+    #[serde(default)]
+    pub max_rental_cost_usd: Option<f64>,
 }
 
 fn default_method() -> String {
     "POST".to_string()
 }
 
+fn default_weight() -> u32 {
+    1
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct JsonApiResponseMapping {
     /// JSON pointer to a truthy success flag (bool/number/string).
@@ -699,6 +713,8 @@ mod tests {
                 error_pointer: Some("/error".to_string()),
             },
             quote: None,
+            weight: 1,
+            max_rental_cost_usd: None,
         };
 
         let res = interpret_json_response(
@@ -727,6 +743,8 @@ mod tests {
                 error_pointer: Some("/message".to_string()),
             },
             quote: None,
+            weight: 1,
+            max_rental_cost_usd: None,
         };
 
         let res = interpret_json_response(&cfg, 200, r#"{"code":200,"message":"ok"}"#);
@@ -753,6 +771,8 @@ mod tests {
                 error_pointer: Some("/error/message".to_string()),
             },
             quote: None,
+            weight: 1,
+            max_rental_cost_usd: None,
         };
 
         let res =
@@ -777,6 +797,8 @@ mod tests {
                 error_pointer: None,
             },
             quote: None,
+            weight: 1,
+            max_rental_cost_usd: None,
         };
 
         let res = interpret_json_response(&cfg, 200, "not json");
@@ -800,6 +822,8 @@ mod tests {
                 error_pointer: None,
             },
             quote: None,
+            weight: 1,
+            max_rental_cost_usd: None,
         };
 
         let res = interpret_json_response(&cfg, 503, r#"{"success":true}"#);
@@ -29,11 +29,22 @@ pub struct SignedTronTx {
     pub tx: Transaction,
     /// `sha256(raw_data_bytes)`.
     pub txid: [u8; 32],
+    /// fee_limit actually signed into the tx (estimate plus headroom, capped).
     pub fee_limit_sun: u64,
+    /// Unpadded fee estimate the applied `fee_limit_sun` was derived from.
+    pub fee_limit_estimate_sun: u64,
     pub energy_required: u64,
     pub tx_size_bytes: u64,
 }
 
+/// `raw_data.expiration` (unix ms) of a serialized signed tx. Nodes reject a tx once its
+/// expiration has passed.
+pub fn signed_tx_expiration_ms(tx_bytes: &[u8]) -> Result<i64> {
+    let tx = Transaction::decode(tx_bytes).context("decode signed tx bytes")?;
+    let raw = tx.raw_data.context("signed tx missing raw_data")?;
+    Ok(raw.expiration)
+}
+
 impl TronWallet {
     /// Builds and signs a contract deployment (`CreateSmartContract`) tx.
     ///
@@ -80,13 +91,15 @@ impl TronWallet {
 
         let mut tx = tx_ext.transaction.context("node returned no transaction")?;
         let raw = tx.raw_data.take().context("node returned no raw_data")?;
-        let (signed, txid, tx_size) =
-            self.sign_raw_with_fee_limit(raw, tx.ret.clone(), fee_limit_sun)?;
+        let (signed, txid, tx_size) = self
+            .sign_raw_with_fee_limit(raw, tx.ret.clone(), fee_limit_sun)
+            .await?;
 
         Ok(SignedTronTx {
             tx: signed,
             txid,
             fee_limit_sun: u64::try_from(fee_limit_sun.max(0)).unwrap_or(u64::MAX),
+            fee_limit_estimate_sun: u64::try_from(fee_limit_sun.max(0)).unwrap_or(u64::MAX),
             energy_required: 0,
             tx_size_bytes: tx_size,
         })
@@ -123,12 +136,13 @@ impl TronWallet {
         let mut tx = tx_ext.transaction.context("node returned no transaction")?;
         let raw = tx.raw_data.take().context("node returned no raw_data")?;
 
-        let (signed, txid, tx_size) = self.sign_raw_with_fee_limit(raw, tx.ret.clone(), 0)?;
+        let (signed, txid, tx_size) = self.sign_raw_with_fee_limit(raw, tx.ret.clone(), 0).await?;
 
         Ok(SignedTronTx {
             tx: signed,
             txid,
             fee_limit_sun: 0,
+            fee_limit_estimate_sun: 0,
             energy_required: 0,
             tx_size_bytes: tx_size,
         })
@@ -154,12 +168,13 @@ impl TronWallet {
             .context("create_transfer_transaction")?;
         let raw = tx.raw_data.take().context("node returned no raw_data")?;
 
-        let (signed, txid, tx_size) = self.sign_raw_with_fee_limit(raw, tx.ret.clone(), 0)?;
+        let (signed, txid, tx_size) = self.sign_raw_with_fee_limit(raw, tx.ret.clone(), 0).await?;
 
         Ok(SignedTronTx {
             tx: signed,
             txid,
             fee_limit_sun: 0,
+            fee_limit_estimate_sun: 0,
             energy_required: 0,
             tx_size_bytes: tx_size,
         })
@@ -203,12 +218,13 @@ impl TronWallet {
         let mut tx = tx_ext.transaction.context("node returned no transaction")?;
         let raw = tx.raw_data.take().context("node returned no raw_data")?;
 
-        let (signed, txid, tx_size) = self.sign_raw_with_fee_limit(raw, tx.ret.clone(), 0)?;
+        let (signed, txid, tx_size) = self.sign_raw_with_fee_limit(raw, tx.ret.clone(), 0).await?;
 
         Ok(SignedTronTx {
             tx: signed,
             txid,
             fee_limit_sun: 0,
+            fee_limit_estimate_sun: 0,
             energy_required: 0,
             tx_size_bytes: tx_size,
         })
@@ -228,16 +244,10 @@ impl TronWallet {
         call_value_sun: i64,
         fee_policy: FeePolicy,
     ) -> Result<SignedTronTx> {
-        let chain_params = grpc.get_chain_parameters().await?;
-        let fees = parse_chain_fees(&chain_params)?;
-
-        let owner = self.address.prefixed_bytes().to_vec();
-        let contract_addr = contract.prefixed_bytes().to_vec();
-
         let est = grpc
             .estimate_energy(TriggerSmartContract {
-                owner_address: owner.clone(),
-                contract_address: contract_addr.clone(),
+                owner_address: self.address.prefixed_bytes().to_vec(),
+                contract_address: contract.prefixed_bytes().to_vec(),
                 call_value: call_value_sun,
                 data: data.clone(),
                 call_token_value: 0,
@@ -277,6 +287,35 @@ impl TronWallet {
             energy_required = 50_000;
         }
 
+        self.build_and_sign_trigger_smart_contract_with_energy(
+            grpc,
+            contract,
+            data,
+            call_value_sun,
+            energy_required,
+            fee_policy,
+        )
+        .await
+    }
+
+    /// Like [`Self::build_and_sign_trigger_smart_contract`], but derives fee_limit from a caller
+    /// supplied `energy_required` instead of asking the node to estimate it. Useful when the call
+    /// only succeeds after an earlier, not yet included tx (e.g. a token approve).
+    pub async fn build_and_sign_trigger_smart_contract_with_energy(
+        &self,
+        grpc: &mut TronGrpc,
+        contract: TronAddress,
+        data: Vec<u8>,
+        call_value_sun: i64,
+        energy_required: u64,
+        fee_policy: FeePolicy,
+    ) -> Result<SignedTronTx> {
+        let chain_params = grpc.get_chain_parameters().await?;
+        let fees = parse_chain_fees(&chain_params)?;
+
+        let owner = self.address.prefixed_bytes().to_vec();
+        let contract_addr = contract.prefixed_bytes().to_vec();
+
         // Ask node to build the tx skeleton (ref block bytes/hash/etc).
         let tx_ext = grpc
             .trigger_contract(TriggerSmartContract {
@@ -294,42 +333,40 @@ impl TronWallet {
         let raw = tx.raw_data.take().context("node returned no raw_data")?;
 
         // Two-pass sizing to account for fee_limit varint size in raw_data (affects tx size/bandwidth fee).
-        let (_signed0, _txid0, tx_size0) =
-            self.sign_raw_with_fee_limit(raw.clone(), tx.ret.clone(), 0)?;
+        // Signatures are always 65 bytes, so sizing doesn't need a (possibly remote) signature.
+        let tx_size0 = signed_tx_size(raw.clone(), tx.ret.clone(), 0);
 
         let base0 = quote_fee_limit_sun(energy_required, tx_size0, fees);
         let fee_limit0 = fee_policy.apply(base0);
 
-        let (signed1, txid1, tx_size1) = self.sign_raw_with_fee_limit(
+        let tx_size1 = signed_tx_size(
             raw.clone(),
             tx.ret.clone(),
             i64::try_from(fee_limit0).context("fee_limit_sun out of range")?,
-        )?;
+        );
 
         let base1 = quote_fee_limit_sun(energy_required, tx_size1, fees);
         let fee_limit1 = fee_policy.apply(base1);
 
-        let (tx_final, txid_final, tx_size_final, fee_limit_final) = if fee_limit1 == fee_limit0 {
-            (signed1, txid1, tx_size1, fee_limit1)
-        } else {
-            let (signed2, txid2, tx_size2) = self.sign_raw_with_fee_limit(
+        let (tx_final, txid_final, tx_size_final) = self
+            .sign_raw_with_fee_limit(
                 raw,
                 tx.ret,
                 i64::try_from(fee_limit1).context("fee_limit_sun out of range")?,
-            )?;
-            (signed2, txid2, tx_size2, fee_limit1)
-        };
+            )
+            .await?;
 
         Ok(SignedTronTx {
             tx: tx_final,
             txid: txid_final,
-            fee_limit_sun: fee_limit_final,
+            fee_limit_sun: fee_limit1,
+            fee_limit_estimate_sun: base1,
             energy_required,
             tx_size_bytes: tx_size_final,
         })
     }
 
-    fn sign_raw_with_fee_limit(
+    async fn sign_raw_with_fee_limit(
         &self,
         mut raw: super::protocol::transaction::Raw,
         ret: Vec<super::protocol::transaction::Result>,
@@ -338,27 +375,58 @@ impl TronWallet {
         raw.fee_limit = fee_limit_sun.max(0);
 
         let raw_bytes = raw.encode_to_vec();
-        let txid = Sha256::digest(&raw_bytes);
-
-        let (rec_sig, recid) = self
-            .key
-            .clone()
-            .sign_digest_recoverable(Sha256::new_with_prefix(&raw_bytes))
-            .context("sign Tron tx")?;
+        let txid: [u8; 32] = Sha256::digest(&raw_bytes).into();
 
-        let mut sig65 = rec_sig.to_bytes().to_vec();
-        sig65.push(recid.to_byte() + 27);
+        let sig65 = self.signer.sign_txid(&txid).await?;
 
         let signed = Transaction {
             raw_data: Some(raw),
-            signature: vec![sig65],
+            signature: vec![sig65.to_vec()],
             ret,
         };
 
         let size = u64::try_from(signed.encode_to_vec().len()).unwrap_or(u64::MAX);
 
-        let mut out = [0u8; 32];
-        out.copy_from_slice(&txid);
-        Ok((signed, out, size))
+        Ok((signed, txid, size))
+    }
+}
+
+/// Encoded size of `raw` once signed with `fee_limit_sun`.
+fn signed_tx_size(
+    mut raw: super::protocol::transaction::Raw,
+    ret: Vec<super::protocol::transaction::Result>,
+    fee_limit_sun: i64,
+) -> u64 {
+    raw.fee_limit = fee_limit_sun.max(0);
+    let tx = Transaction {
+        raw_data: Some(raw),
+        signature: vec![vec![0u8; 65]],
+        ret,
+    };
+    u64::try_from(tx.encoded_len()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_tx_expiration_ms_reads_raw_data() {
+        let tx = Transaction {
+            raw_data: Some(crate::protocol::transaction::Raw {
+                timestamp: 1_700_000_000_000,
+                expiration: 1_700_000_060_000,
+                ..Default::default()
+            }),
+            signature: vec![vec![0u8; 65]],
+            ..Default::default()
+        };
+        assert_eq!(
+            signed_tx_expiration_ms(&tx.encode_to_vec()).unwrap(),
+            1_700_000_060_000
+        );
+
+        assert!(signed_tx_expiration_ms(&Transaction::default().encode_to_vec()).is_err());
+        assert!(signed_tx_expiration_ms(&[0xff, 0xff]).is_err());
     }
 }
@@ -0,0 +1,180 @@
+use super::TronAddress;
+use alloy::primitives::{Address, keccak256};
+use anyhow::{Context, Result};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use std::{future::Future, pin::Pin, time::Duration};
+
+pub type SignFuture<'a> = Pin<Box<dyn Future<Output = Result<[u8; 65]>> + Send + 'a>>;
+
+/// Signs Tron tx ids (`sha256(raw_data)`) for a single account.
+///
+/// Signatures are 65 bytes `r || s || v` with `v` in `27..=28`, as Tron nodes expect.
+pub trait TronSigner: Send + Sync {
+    fn address(&self) -> TronAddress;
+
+    fn sign_txid<'a>(&'a self, txid: &'a [u8; 32]) -> SignFuture<'a>;
+}
+
+/// In-process secp256k1 key.
+pub struct LocalTronSigner {
+    key: SigningKey,
+    address: TronAddress,
+}
+
+impl LocalTronSigner {
+    pub fn new(private_key: [u8; 32]) -> Result<Self> {
+        let key = SigningKey::from_slice(&private_key).context("invalid TRON private key")?;
+        let address = address_from_verifying_key(key.verifying_key());
+        Ok(Self { key, address })
+    }
+
+    fn sign(&self, txid: &[u8; 32]) -> Result<[u8; 65]> {
+        let (sig, recid) = self
+            .key
+            .sign_prehash_recoverable(txid)
+            .context("sign Tron tx")?;
+        let mut out = [0u8; 65];
+        out[..64].copy_from_slice(&sig.to_bytes());
+        out[64] = recid.to_byte() + 27;
+        Ok(out)
+    }
+}
+
+impl TronSigner for LocalTronSigner {
+    fn address(&self) -> TronAddress {
+        self.address
+    }
+
+    fn sign_txid<'a>(&'a self, txid: &'a [u8; 32]) -> SignFuture<'a> {
+        Box::pin(async move { self.sign(txid) })
+    }
+}
+
+/// Delegates signing to an external service (KMS / HSM front), so the key never enters this
+/// process.
+///
+/// Protocol: `POST url` with `{"address": "<base58>", "txid": "0x<32 bytes>"}`, answered with
+/// `{"signature": "0x<65 bytes r||s||v>"}`. `v` may be `0..=1` or `27..=28`. Signatures are
+/// checked to recover to `address` before use. An optional auth header (e.g.
+/// `Authorization: Bearer <token>`) is sent with every request.
+pub struct RemoteTronSigner {
+    http: reqwest::Client,
+    url: String,
+    address: TronAddress,
+}
+
+#[derive(serde::Serialize)]
+struct RemoteSignRequest {
+    address: String,
+    txid: String,
+}
+
+#[derive(serde::Deserialize)]
+struct RemoteSignResponse {
+    signature: String,
+}
+
+impl RemoteTronSigner {
+    pub fn new(
+        url: String,
+        timeout: Duration,
+        auth_header: Option<(String, String)>,
+        address: TronAddress,
+    ) -> Result<Self> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some((name, value)) = auth_header {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .context("invalid remote signer auth header name")?;
+            let mut value = reqwest::header::HeaderValue::from_str(&value)
+                .context("invalid remote signer auth header value")?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .default_headers(headers)
+            .build()
+            .context("build remote signer http client")?;
+        Ok(Self { http, url, address })
+    }
+
+    async fn sign(&self, txid: &[u8; 32]) -> Result<[u8; 65]> {
+        let resp = self
+            .http
+            .post(&self.url)
+            .json(&RemoteSignRequest {
+                address: self.address.to_base58check(),
+                txid: format!("0x{}", hex::encode(txid)),
+            })
+            .send()
+            .await
+            .context("POST remote signer")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("remote signer rejected request: {status}: {body}");
+        }
+        let body: RemoteSignResponse =
+            resp.json().await.context("decode remote signer response")?;
+        let raw = hex::decode(body.signature.trim_start_matches("0x"))
+            .context("decode remote signature hex")?;
+        let mut sig: [u8; 65] = raw
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("remote signature must be 65 bytes, got {}", raw.len()))?;
+        if sig[64] < 27 {
+            sig[64] += 27;
+        }
+        let recovered = recover_signer(txid, &sig)?;
+        if recovered != self.address {
+            anyhow::bail!(
+                "remote signature recovers to {}, expected {}",
+                recovered.to_base58check(),
+                self.address.to_base58check()
+            );
+        }
+        Ok(sig)
+    }
+}
+
+impl TronSigner for RemoteTronSigner {
+    fn address(&self) -> TronAddress {
+        self.address
+    }
+
+    fn sign_txid<'a>(&'a self, txid: &'a [u8; 32]) -> SignFuture<'a> {
+        Box::pin(self.sign(txid))
+    }
+}
+
+/// Address whose key produced `sig` (`r || s || v`, `v` in `27..=28`) over `txid`.
+pub fn recover_signer(txid: &[u8; 32], sig: &[u8; 65]) -> Result<TronAddress> {
+    let signature = Signature::from_slice(&sig[..64]).context("parse signature")?;
+    let recid = RecoveryId::from_byte(sig[64].wrapping_sub(27)).context("invalid recovery id")?;
+    let key =
+        VerifyingKey::recover_from_prehash(txid, &signature, recid).context("recover signer")?;
+    Ok(address_from_verifying_key(&key))
+}
+
+fn address_from_verifying_key(key: &VerifyingKey) -> TronAddress {
+    let public_key = key.to_encoded_point(false);
+    let hash = keccak256(&public_key.as_bytes()[1..]);
+    TronAddress::from_evm(Address::from_slice(&hash[12..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_signature_recovers_to_signer_address() {
+        let signer = LocalTronSigner::new([0x11u8; 32]).unwrap();
+        let txid = [0x42u8; 32];
+        let sig = signer.sign(&txid).unwrap();
+        assert!(matches!(sig[64], 27 | 28));
+        assert_eq!(recover_signer(&txid, &sig).unwrap(), signer.address());
+
+        let other = LocalTronSigner::new([0x22u8; 32]).unwrap();
+        assert_ne!(recover_signer(&txid, &sig).unwrap(), other.address());
+    }
+}
@@ -1,8 +1,9 @@
 use super::protocol::TriggerSmartContract;
+use super::signer::{LocalTronSigner, TronSigner};
 use super::{address::TronAddress, grpc::TronGrpc};
 use alloy::primitives::{Address, FixedBytes, U256, keccak256};
 use anyhow::{Context, Result};
-use k256::ecdsa::SigningKey;
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct BroadcastedTronTx {
@@ -24,16 +25,22 @@ impl BroadcastedTronTx {
     }
 }
 
+#[derive(Clone)]
 pub struct TronWallet {
-    pub(crate) key: SigningKey,
+    pub(crate) signer: Arc<dyn TronSigner>,
     pub(crate) address: TronAddress,
 }
 
 impl TronWallet {
     pub fn new(private_key: [u8; 32]) -> Result<Self> {
-        let key = SigningKey::from_slice(&private_key).context("invalid TRON private key")?;
-        let address = tron_address_from_signing_key(&key);
-        Ok(Self { key, address })
+        let signer = LocalTronSigner::new(private_key)?;
+        Ok(Self::with_signer(Arc::new(signer)))
+    }
+
+    /// Wallet whose txs are signed by `signer` (e.g. a [`crate::RemoteTronSigner`]).
+    pub fn with_signer(signer: Arc<dyn TronSigner>) -> Self {
+        let address = signer.address();
+        Self { signer, address }
     }
 
     pub fn address(&self) -> TronAddress {
@@ -258,14 +265,6 @@ impl TronWallet {
     }
 }
 
-fn tron_address_from_signing_key(key: &SigningKey) -> TronAddress {
-    let public_key = key.verifying_key().to_encoded_point(false);
-    let public_key_bytes = public_key.as_bytes();
-    let hash = keccak256(&public_key_bytes[1..]);
-    let addr20 = Address::from_slice(&hash[12..]);
-    TronAddress::from_evm(addr20)
-}
-
 // ===== ABI helpers (EVM ABI, used by Tron TriggerSmartContract) =====
 
 pub async fn trc20_balance_of(
@@ -351,7 +350,7 @@ fn encode_u256(v: U256) -> [u8; 32] {
 mod tests {
     use super::*;
     use k256::ecdsa::signature::DigestVerifier;
-    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+    use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
     use prost::Message;
     use sha2::{Digest, Sha256};
 